@@ -97,21 +97,60 @@ impl std::fmt::Display for LogcatPriority {
 }
 
 /// Logcat filter options
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct LogcatOptions {
     /// Filter by minimum log level
     pub priority: Option<LogcatPriority>,
     /// Filter by tag (can use wildcards)
     pub tag_filter: Option<String>,
     /// Filter by process ID
-    #[allow(dead_code)]
     pub pid: Option<u32>,
+    /// Filter by package, resolved to its PID whenever capture (re)connects
+    pub package: Option<String>,
     /// Clear log before reading
     pub clear_before_read: bool,
     /// Use threadtime format (includes PID and TID)
     pub threadtime_format: bool,
 }
 
+impl LogcatOptions {
+    /// Scope capture to one app, given as a PID or a package name
+    pub fn with_app_filter(mut self, filter: &str) -> Self {
+        let filter = filter.trim();
+        self.pid = filter.parse().ok();
+        self.package = (self.pid.is_none() && !filter.is_empty()).then(|| filter.to_string());
+        self
+    }
+}
+
+/// Whether `package` looks like an Android package or process name, so it
+/// is safe to pass to the device shell
+fn is_valid_package_name(package: &str) -> bool {
+    !package.is_empty()
+        && package
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | ':'))
+}
+
+/// Look up the PID of a running package with `pidof`
+fn pidof(device: &mut ADBServerDevice, package: &str) -> Result<u32> {
+    if !is_valid_package_name(package) {
+        return Err(anyhow::anyhow!("Invalid package name: {}", package));
+    }
+
+    let mut output = Vec::new();
+    device
+        .shell_command(&format!("pidof {}", package), &mut output)
+        .context(format!("Failed to look up PID of {}", package))?;
+
+    // pidof lists every matching process; the first is the main one
+    String::from_utf8_lossy(&output)
+        .split_whitespace()
+        .next()
+        .and_then(|pid| pid.parse().ok())
+        .context(format!("{} is not running", package))
+}
+
 /// ADB Manager for handling Android devices
 #[derive(Debug)]
 pub struct AdbManager {
@@ -233,7 +272,6 @@ impl AdbManager {
     }
 
     /// Get a device by serial number
-    #[allow(dead_code)]
     pub fn get_device(&self, serial: &str) -> Result<ADBServerDevice> {
         let mut server = ADBServer::new(self.server_addr);
         server
@@ -241,6 +279,23 @@ impl AdbManager {
             .context(format!("Device not found: {}", serial))
     }
 
+    /// Clear the device's logcat buffer (`adb -s <serial> logcat -c`)
+    pub fn clear_logcat(&self, serial: &str) -> Result<()> {
+        let mut device = self.get_device(serial)?;
+        let mut output = Vec::new();
+        device
+            .shell_command(&"logcat -c", &mut output)
+            .context(format!("Failed to clear logcat on {}", serial))?;
+
+        // logcat reports failures on its output but still exits normally
+        let output = String::from_utf8_lossy(&output);
+        if output.to_lowercase().contains("failed") {
+            return Err(anyhow::anyhow!("{}", output.trim()));
+        }
+
+        Ok(())
+    }
+
     /// Check if ADB server is running
     #[allow(dead_code)]
     pub fn is_server_running(&self) -> bool {
@@ -282,6 +337,8 @@ pub struct LogcatReader {
     is_running: Arc<AtomicBool>,
     /// Stop signal
     stop_signal: Arc<AtomicBool>,
+    /// Whether the filtered package wasn't running at the last connection
+    app_missing: Arc<AtomicBool>,
 }
 
 impl LogcatReader {
@@ -298,6 +355,7 @@ impl LogcatReader {
             log_receiver,
             is_running: Arc::new(AtomicBool::new(false)),
             stop_signal: Arc::new(AtomicBool::new(false)),
+            app_missing: Arc::new(AtomicBool::new(false)),
         }
     }
 
@@ -321,6 +379,7 @@ impl LogcatReader {
             log_receiver,
             is_running: Arc::new(AtomicBool::new(false)),
             stop_signal: Arc::new(AtomicBool::new(false)),
+            app_missing: Arc::new(AtomicBool::new(false)),
         })
     }
 
@@ -329,12 +388,23 @@ impl LogcatReader {
         self.log_receiver.clone()
     }
 
+    /// Options the reader was started with
+    pub fn options(&self) -> &LogcatOptions {
+        &self.options
+    }
+
     /// Check if reader is running
     #[allow(dead_code)]
     pub fn is_running(&self) -> bool {
         self.is_running.load(Ordering::Relaxed)
     }
 
+    /// Whether the filtered package couldn't be found on the device, so
+    /// capture is waiting for it to start
+    pub fn app_missing(&self) -> bool {
+        self.app_missing.load(Ordering::Relaxed)
+    }
+
     /// Start streaming logcat in background
     pub fn start_streaming(&self) -> Result<()> {
         if self.is_running.load(Ordering::Relaxed) {
@@ -350,6 +420,7 @@ impl LogcatReader {
         let log_sender = self.log_sender.clone();
         let is_running = Arc::clone(&self.is_running);
         let stop_signal = Arc::clone(&self.stop_signal);
+        let app_missing = Arc::clone(&self.app_missing);
 
         thread::spawn(move || {
             Self::streaming_loop(
@@ -359,6 +430,7 @@ impl LogcatReader {
                 &log_sender,
                 &is_running,
                 &stop_signal,
+                &app_missing,
             );
         });
 
@@ -381,6 +453,7 @@ impl LogcatReader {
         log_sender: &Sender<String>,
         is_running: &Arc<AtomicBool>,
         stop_signal: &Arc<AtomicBool>,
+        app_missing: &Arc<AtomicBool>,
     ) {
         // Build logcat command arguments
         let format_arg = if options.threadtime_format {
@@ -411,6 +484,24 @@ impl LogcatReader {
 
             first_run = false;
 
+            // Resolve the package on every connection, the app may have restarted
+            let pid = match (options.pid, &options.package) {
+                (Some(pid), _) => Some(pid),
+                (None, Some(package)) => {
+                    let pid = pidof(&mut device, package);
+                    app_missing.store(pid.is_err(), Ordering::Relaxed);
+                    match pid {
+                        Ok(pid) => Some(pid),
+                        Err(e) => {
+                            tracing::warn!("Logcat app filter: {:#}", e);
+                            thread::sleep(Duration::from_secs(2));
+                            continue;
+                        }
+                    }
+                }
+                (None, None) => None,
+            };
+
             // Build command string - avoid quotes by using simpler options
             // Using -T with a number (count) instead of timestamp to avoid shell quoting issues
             let mut cmd = format!("logcat -v {}", format_arg);
@@ -422,6 +513,10 @@ impl LogcatReader {
                 cmd.push_str(" -T 1");
             }
 
+            if let Some(pid) = pid {
+                cmd.push_str(&format!(" --pid={}", pid));
+            }
+
             if let Some(ref pf) = priority_filter {
                 cmd.push_str(&format!(" {}", pf));
             }
@@ -681,6 +776,24 @@ pub fn start_adb_server() -> Result<()> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_app_filter_is_pid_or_package() {
+        let options = LogcatOptions::default().with_app_filter(" 1234 ");
+        assert_eq!(options.pid, Some(1234));
+        assert_eq!(options.package, None);
+
+        let options = LogcatOptions::default().with_app_filter("com.example.app");
+        assert_eq!(options.pid, None);
+        assert_eq!(options.package.as_deref(), Some("com.example.app"));
+
+        let options = LogcatOptions::default().with_app_filter("  ");
+        assert_eq!(options, LogcatOptions::default());
+
+        assert!(is_valid_package_name("com.android.systemui:ui"));
+        assert!(!is_valid_package_name("com.example; reboot"));
+        assert!(!is_valid_package_name(""));
+    }
+
     #[test]
     #[ignore] // Requires ADB server and connected device
     fn test_list_devices() {
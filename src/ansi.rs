@@ -0,0 +1,274 @@
+//! ANSI escape sequence handling
+//!
+//! Translates SGR color sequences (`ESC [ ... m`) into styled spans for
+//! rendering and strips every escape sequence for copying. Sequences that
+//! aren't recognized are dropped rather than shown.
+
+use egui::Color32;
+use std::borrow::Cow;
+use std::ops::Range;
+
+/// Escape character that starts every sequence
+const ESC: char = '\x1b';
+
+/// Standard terminal palette (black, red, green, yellow, blue, magenta, cyan, white)
+const PALETTE: [Color32; 8] = [
+    Color32::from_rgb(0, 0, 0),
+    Color32::from_rgb(205, 49, 49),
+    Color32::from_rgb(13, 188, 121),
+    Color32::from_rgb(229, 229, 16),
+    Color32::from_rgb(36, 114, 200),
+    Color32::from_rgb(188, 63, 188),
+    Color32::from_rgb(17, 168, 205),
+    Color32::from_rgb(229, 229, 229),
+];
+
+/// Bright variants of the standard palette
+const BRIGHT_PALETTE: [Color32; 8] = [
+    Color32::from_rgb(102, 102, 102),
+    Color32::from_rgb(241, 76, 76),
+    Color32::from_rgb(35, 209, 139),
+    Color32::from_rgb(245, 245, 67),
+    Color32::from_rgb(59, 142, 234),
+    Color32::from_rgb(214, 112, 214),
+    Color32::from_rgb(41, 184, 219),
+    Color32::from_rgb(255, 255, 255),
+];
+
+/// Text style selected by SGR sequences
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AnsiStyle {
+    /// Foreground color (None for the default)
+    pub fg: Option<Color32>,
+    /// Background color (None for the default)
+    pub bg: Option<Color32>,
+    /// Bold / increased intensity
+    pub bold: bool,
+    /// Standard palette index of the foreground, so bold can brighten it
+    fg_index: Option<usize>,
+}
+
+impl AnsiStyle {
+    /// Foreground to draw, with bold standard colors shown bright
+    /// (the monospace font has no bold face)
+    pub fn display_fg(&self) -> Option<Color32> {
+        match (self.bold, self.fg_index) {
+            (true, Some(index)) => Some(BRIGHT_PALETTE[index]),
+            _ => self.fg,
+        }
+    }
+
+    /// Apply the parameters of one SGR sequence
+    fn apply(&mut self, params: &str) {
+        let mut codes = params.split(';').map(|code| {
+            if code.is_empty() {
+                Some(0)
+            } else {
+                code.parse::<u16>().ok()
+            }
+        });
+        while let Some(code) = codes.next() {
+            match code {
+                Some(0) => *self = Self::default(),
+                Some(1) => self.bold = true,
+                Some(22) => self.bold = false,
+                Some(code @ 30..=37) => self.set_fg(Some(code as usize - 30)),
+                Some(code @ 90..=97) => {
+                    self.fg = Some(BRIGHT_PALETTE[code as usize - 90]);
+                    self.fg_index = None;
+                }
+                Some(39) => self.set_fg(None),
+                Some(code @ 40..=47) => self.bg = Some(PALETTE[code as usize - 40]),
+                Some(code @ 100..=107) => self.bg = Some(BRIGHT_PALETTE[code as usize - 100]),
+                Some(49) => self.bg = None,
+                Some(38) => {
+                    self.fg = extended_color(&mut codes);
+                    self.fg_index = None;
+                }
+                Some(48) => self.bg = extended_color(&mut codes),
+                // Italic, underline, blink and others aren't rendered
+                _ => {}
+            }
+        }
+    }
+
+    /// Set a standard palette foreground (or the default)
+    fn set_fg(&mut self, index: Option<usize>) {
+        self.fg = index.map(|i| PALETTE[i]);
+        self.fg_index = index;
+    }
+}
+
+/// Parse the rest of a 256-color (`5;n`) or truecolor (`2;r;g;b`) parameter list
+fn extended_color(codes: &mut impl Iterator<Item = Option<u16>>) -> Option<Color32> {
+    let mut next = || codes.next().flatten();
+    match next()? {
+        5 => Some(color_256(next()?)),
+        2 => {
+            let (r, g, b) = (next()?, next()?, next()?);
+            Some(Color32::from_rgb(
+                r.min(255) as u8,
+                g.min(255) as u8,
+                b.min(255) as u8,
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// Color of an xterm 256-color palette index
+fn color_256(index: u16) -> Color32 {
+    match index {
+        0..=7 => PALETTE[index as usize],
+        8..=15 => BRIGHT_PALETTE[index as usize - 8],
+        16..=231 => {
+            let i = index - 16;
+            let level = |v: u16| if v == 0 { 0 } else { (55 + v * 40) as u8 };
+            Color32::from_rgb(level(i / 36), level(i / 6 % 6), level(i % 6))
+        }
+        _ => {
+            let gray = (8 + (index.min(255) - 232) * 10) as u8;
+            Color32::from_gray(gray)
+        }
+    }
+}
+
+/// Whether a line contains any escape sequence
+pub fn contains_ansi(text: &str) -> bool {
+    text.contains(ESC)
+}
+
+/// Text without escape sequences, with a style for each run of it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AnsiText {
+    /// Plain text
+    pub text: String,
+    /// Byte ranges of `text` and their styles, in order
+    pub spans: Vec<(Range<usize>, AnsiStyle)>,
+}
+
+/// Split a line into plain text and styled spans
+pub fn parse_ansi(line: &str) -> AnsiText {
+    let mut parsed = AnsiText {
+        text: String::with_capacity(line.len()),
+        spans: Vec::new(),
+    };
+    let mut style = AnsiStyle::default();
+    let mut span_start = 0;
+    let mut chars = line.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        if c != ESC {
+            parsed.text.push(c);
+            continue;
+        }
+
+        // Consume the sequence; only SGR changes the style
+        let sgr = match chars.peek().map(|&(_, c)| c) {
+            Some('[') => {
+                chars.next();
+                let params_start = i + 2;
+                let mut sgr = None;
+                for (j, c) in chars.by_ref() {
+                    if ('\x40'..='\x7e').contains(&c) {
+                        if c == 'm' {
+                            sgr = Some(&line[params_start..j]);
+                        }
+                        break;
+                    }
+                }
+                sgr
+            }
+            Some(']') => {
+                // OSC: runs until BEL or ESC \
+                chars.next();
+                let mut terminator = None;
+                for (_, c) in chars.by_ref() {
+                    if c == '\x07' || c == ESC {
+                        terminator = Some(c);
+                        break;
+                    }
+                }
+                if terminator == Some(ESC) {
+                    chars.next_if(|&(_, c)| c == '\\');
+                }
+                None
+            }
+            Some(_) => {
+                // Intermediate bytes, then a final byte (e.g. `ESC ( B`)
+                while chars.next_if(|&(_, c)| (' '..='/').contains(&c)).is_some() {}
+                chars.next();
+                None
+            }
+            None => None,
+        };
+
+        if let Some(params) = sgr {
+            let mut next_style = style;
+            next_style.apply(params);
+            if next_style != style {
+                if parsed.text.len() > span_start {
+                    parsed.spans.push((span_start..parsed.text.len(), style));
+                }
+                span_start = parsed.text.len();
+                style = next_style;
+            }
+        }
+    }
+
+    if parsed.text.len() > span_start {
+        parsed.spans.push((span_start..parsed.text.len(), style));
+    }
+    parsed
+}
+
+/// Line with all escape sequences removed
+pub fn strip_ansi(line: &str) -> Cow<'_, str> {
+    if contains_ansi(line) {
+        Cow::Owned(parse_ansi(line).text)
+    } else {
+        Cow::Borrowed(line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sgr_sequences_become_styled_spans() {
+        let parsed = parse_ansi("\x1b[1;31mERROR\x1b[0m disk \x1b[42;30mfull\x1b[m!");
+        assert_eq!(parsed.text, "ERROR disk full!");
+
+        let styles: Vec<(&str, AnsiStyle)> = parsed
+            .spans
+            .iter()
+            .map(|(range, style)| (&parsed.text[range.clone()], *style))
+            .collect();
+        assert_eq!(styles.len(), 4);
+        assert_eq!(styles[0].0, "ERROR");
+        assert!(styles[0].1.bold);
+        assert_eq!(styles[0].1.fg, Some(PALETTE[1]));
+        assert_eq!(styles[0].1.display_fg(), Some(BRIGHT_PALETTE[1]));
+        assert_eq!(styles[1], (" disk ", AnsiStyle::default()));
+        assert_eq!(styles[2].0, "full");
+        assert_eq!(styles[2].1.bg, Some(PALETTE[2]));
+        assert_eq!(styles[2].1.fg, Some(PALETTE[0]));
+        assert_eq!(styles[3], ("!", AnsiStyle::default()));
+    }
+
+    #[test]
+    fn test_unrecognized_sequences_are_stripped() {
+        let line = "\x1b[2K\x1b]0;title\x07start \x1b[38;5;208mhot\x1b[39m \x1b(Bend";
+        assert_eq!(strip_ansi(line), "start hot end");
+
+        let parsed = parse_ansi(line);
+        let hot = parsed
+            .spans
+            .iter()
+            .find(|(r, _)| &parsed.text[r.clone()] == "hot");
+        assert_eq!(hot.unwrap().1.fg, Some(Color32::from_rgb(255, 135, 0)));
+
+        assert!(matches!(strip_ansi("plain"), Cow::Borrowed("plain")));
+    }
+}
@@ -1,31 +1,51 @@
 //! Main application logic
 
+use crate::ansi::strip_ansi;
+use crate::bookmark_report::{self, FileReport, ReportFormat};
+use crate::autosave::{AutosaveTimer, AutosaveWriter, Snapshot, AUTOSAVE_DEBOUNCE};
 use crate::bookmarks::BookmarksStore;
-use crate::config::{AppConfig, DisplayConfig, Shortcuts, Theme};
-use crate::grok_parser::GrokParser;
+use crate::config::{
+    AppConfig, ClickAction, DisplayConfig, DisplayToggle, FolderDropBehavior, Shortcuts, Theme,
+};
+use crate::correlation::{cycle_correlated_line, CorrelationExtractor, CorrelationFilter};
+use crate::grok_parser::{json_columns, GrokParser, JSON_MODE_NAME};
 use crate::i18n::{set_language, Translations as t};
+use crate::line_export::{ExportSource, LineExport, LineExportFormat};
 use crate::log_buffer::LogBufferConfig;
+use crate::log_entry::{parse_time_query, LogEntry, LogLevel};
+use crate::match_export::{self, MatchExportFormat};
+use crate::redactor::Redactor;
 use crate::remote_server::{RemoteServer, ServerConfig, ServerEvent};
+use crate::search::FieldSort;
+use crate::stdin_reader::{stdin_cache_path, StdinReader, STDIN_TAB_NAME};
 use crate::tray::{TrayEvent, TrayManager};
 use crate::ui::activity_bar::{ActivityBar, ActivityBarAction, ActivityView};
-use crate::ui::advanced_filters_panel::AdvancedFiltersPanel;
+use crate::ui::advanced_filters_panel::{AdvancedFiltersPanel, PresetAction};
 use crate::ui::app_titlebar::AppTitleBar;
 use crate::ui::bookmarks_panel::{BookmarkAction, BookmarksPanel};
 use crate::ui::close_dialog::{CloseDialog, CloseDialogResult};
+use crate::ui::diff_view::DiffView;
 use crate::ui::explorer_panel::{ExplorerAction, ExplorerPanel};
 use crate::ui::filter_panel::FilterPanel;
-use crate::ui::global_search_panel::{GlobalSearchAction, GlobalSearchPanel};
+use crate::ui::folder_drop_dialog::{self, FolderDropDialog};
+use crate::ui::global_search_panel::{GlobalSearchAction, GlobalSearchPanel, SearchSource};
 use crate::ui::grok_panel::{GrokPanel, GrokPanelAction};
-use crate::ui::main_view::ContextMenuAction;
+use crate::ui::line_detail_panel::{LineDetail, LineDetailAction, LineDetailPanel, DETAIL_CONTEXT_LINES};
+use crate::ui::main_view::{ColumnAction, ContextMenuAction};
+use crate::ui::notes_panel::{NotesAction, NotesPanel};
+use crate::ui::open_recent_dialog::OpenRecentDialog;
+use crate::ui::presentation::{self, display_path};
 use crate::ui::search_bar::{SearchBar, SearchBarAction};
 use crate::ui::settings_panel::{SettingsAction, SettingsPanel};
 use crate::ui::source_picker_dialog::{SourcePickerAction, SourcePickerDialog, SourceTab};
-use crate::ui::status_bar::{StatusBar, StatusLevel};
+use crate::ui::status_bar::{format_size, StatusBar, StatusLevel};
 use crate::ui::tab_bar::TabBarAction;
-use crate::ui::tab_manager::TabManager;
+use crate::ui::tab_manager::{parse_grok_fields, TabManager};
+use crate::ui::theme;
+use crate::ui::timeline::TimelineAction;
 use crate::ui::toolbar::{Toolbar, ToolbarAction, ToolbarState};
 
-use crate::mcp::{McpConfig, McpServer};
+use crate::mcp::{local_source_id, remote_source_id, McpConfig, McpServer};
 
 use anyhow::Result;
 use eframe::egui;
@@ -62,6 +82,16 @@ pub struct LoglineApp {
 
     /// Go-to-line dialog state
     goto_dialog: GotoLineDialog,
+    /// Go-to-time dialog state
+    goto_time_dialog: GotoLineDialog,
+    /// Open Recent overlay
+    open_recent_dialog: OpenRecentDialog,
+    /// Confirmation for opening many files from a dropped folder
+    folder_drop_dialog: FolderDropDialog,
+    /// Snapshot diff window
+    diff_view: DiffView,
+    /// Per-file notes panel
+    notes_panel: NotesPanel,
     /// Source picker dialog (replaces file picker)
     source_picker_dialog: SourcePickerDialog,
 
@@ -85,8 +115,12 @@ pub struct LoglineApp {
     global_search_panel: GlobalSearchPanel,
     /// Grok parser panel
     grok_panel: GrokPanel,
+    /// Detail pane for the focused line
+    line_detail_panel: LineDetailPanel,
     /// Grok parser instance
     grok_parser: GrokParser,
+    /// Secret redaction for copied/exported text
+    redactor: Redactor,
     /// Sidebar visibility
     sidebar_visible: bool,
 
@@ -100,6 +134,12 @@ pub struct LoglineApp {
     /// Active logcat readers (device_serial -> (reader, cache_path, tab_id))
     active_logcat_readers: std::collections::HashMap<String, (crate::android_logcat::LogcatReader, std::path::PathBuf, crate::ui::tab_bar::TabId)>,
 
+    /// Reader streaming stdin into its tab (`mycmd | logline -`)
+    stdin_reader: Option<(StdinReader, crate::ui::tab_bar::TabId)>,
+
+    /// Grok config to apply to tabs still loading (applied with the first lines)
+    pending_tab_setup: std::collections::HashMap<crate::ui::tab_bar::TabId, Option<crate::config::FileGrokConfig>>,
+
     /// Whether this is the first frame (for initial theme application)
     first_frame: bool,
 
@@ -120,6 +160,12 @@ pub struct LoglineApp {
     // === Bookmarks Persistence ===
     /// Bookmarks storage
     bookmarks_store: BookmarksStore,
+    /// When the next periodic autosave is due
+    autosave_timer: AutosaveTimer,
+    /// Background writer for autosaves
+    autosave_writer: AutosaveWriter,
+    /// Export of a tab's shown lines in progress
+    line_export: Option<LineExport>,
 
     // === Custom Titlebar ===
     /// Custom title bar for the window
@@ -139,15 +185,18 @@ impl LoglineApp {
         Self::setup_fonts(&cc.egui_ctx);
 
         // Apply theme
-        let visuals = match config.theme {
-            Theme::Dark => egui::Visuals::dark(),
-            Theme::Light => egui::Visuals::light(),
-        };
-        cc.egui_ctx.set_visuals(visuals);
+        theme::apply(&cc.egui_ctx, config.theme, &config.accessibility);
+        theme::apply_zoom(&cc.egui_ctx, &config.accessibility);
 
         // Create remote server with config
         let server_config = ServerConfig {
             port: config.remote_server.port,
+            tls_cert_path: config.remote_server.tls_cert_path.clone(),
+            tls_key_path: config.remote_server.tls_key_path.clone(),
+            auth_token: config.remote_server.auth_token.clone(),
+            max_cache_bytes: config.remote_server.max_cache_bytes(),
+            reuse_existing: config.remote_server.reuse_existing_streams,
+            stale_timeout: config.remote_server.stale_timeout(),
             ..Default::default()
         };
         let mut remote_server = RemoteServer::new(server_config);
@@ -167,8 +216,24 @@ impl LoglineApp {
         settings_panel.mcp_enabled = config.mcp.enabled;
         settings_panel.mcp_port = config.mcp.port.to_string();
         settings_panel.server_port = config.remote_server.port.to_string();
+        settings_panel.max_cache_mb = config.remote_server.max_cache_mb;
+        settings_panel.reuse_agent_streams = config.remote_server.reuse_existing_streams;
         settings_panel.enable_remote_service = config.remote_server.enabled;
+        settings_panel.auth_token = config.remote_server.auth_token.clone().unwrap_or_default();
         settings_panel.close_button_behavior = config.window.close_button_behavior;
+        settings_panel.space_key_action = config.keyboard.space_key_action;
+        settings_panel.folder_drop = config.folder_drop.clone();
+        settings_panel.grok_eager_max_lines = config.grok.eager_parse_max_lines;
+        settings_panel.trim_strategy = config.buffer.trim_strategy;
+        settings_panel.trim_head_lines = config.buffer.trim_head_lines;
+        settings_panel.repaint_min_level = config.buffer.repaint_min_level;
+        settings_panel.reduced_repaint_interval_ms = config.buffer.reduced_repaint_interval_ms;
+        settings_panel.accessibility = config.accessibility.clone();
+        settings_panel.autosave = config.autosave.clone();
+        settings_panel.correlation = config.correlation.clone();
+        settings_panel.stack_traces = config.stack_traces.clone();
+        settings_panel.set_redaction_config(&config.redaction);
+        let autosave_timer = AutosaveTimer::new(config.autosave.interval(), Instant::now());
 
         // Initialize MCP server if enabled
         let (mcp_server, tokio_runtime) = {
@@ -217,10 +282,23 @@ impl LoglineApp {
                 let buffer_config = LogBufferConfig {
                     max_lines: config.buffer.max_lines,
                     auto_trim: config.buffer.auto_trim,
-                    chunk_size: 5_000,     // Load 5k lines per chunk when scrolling up
+                    trim_strategy: config.buffer.trim_strategy,
+                    head_lines: config.buffer.trim_head_lines,
+                    chunk_size: config.buffer.chunk_size.max(1),
+                    load_threshold: config.buffer.load_threshold,
+                    prefetch_frames: config.buffer.prefetch_frames,
+                    continuation: config.stack_traces.continuation_regex().ok().flatten(),
+                    timestamp_fallback: config.display.timestamp_fallback,
                 };
                 let mut manager = TabManager::new(buffer_config);
+                manager.set_partial_line_timeout(Duration::from_millis(
+                    config.buffer.partial_line_timeout_ms,
+                ));
+                manager.set_mmap_threshold(config.buffer.mmap_threshold());
+                manager.set_repaint_context(cc.egui_ctx.clone());
+                manager.set_repaint_min_level(config.buffer.repaint_min_level);
                 manager.set_dark_theme(config.theme == Theme::Dark);
+                manager.restore_split_layout(&config.split_layout);
                 manager
             },
             search_bar: SearchBar::new(),
@@ -233,9 +311,20 @@ impl LoglineApp {
                 dark_theme: config.theme == Theme::Dark,
                 reverse_order: false,
                 split_view_active: false,
+                capture_lines: config.buffer.capture_lines,
+                ..Default::default()
             },
             goto_dialog: GotoLineDialog::default(),
-            source_picker_dialog: SourcePickerDialog::new(),
+            goto_time_dialog: GotoLineDialog::default(),
+            open_recent_dialog: OpenRecentDialog::default(),
+            folder_drop_dialog: FolderDropDialog::default(),
+            diff_view: DiffView::default(),
+            notes_panel: NotesPanel::default(),
+            source_picker_dialog: {
+                let mut dialog = SourcePickerDialog::new();
+                dialog.set_max_suggestions(config.max_picker_suggestions);
+                dialog
+            },
             last_update: Instant::now(),
             // New components
             remote_server,
@@ -244,6 +333,12 @@ impl LoglineApp {
                 let mut panel = ExplorerPanel::new();
                 // Load recent files from config
                 panel.local_files = config.recent_files.clone();
+                for dir in &config.workspace_folders {
+                    let files =
+                        folder_drop_dialog::collect_log_files(dir, config.folder_drop.max_depth);
+                    panel.set_workspace(dir.clone(), files);
+                }
+                panel.aliases = config.source_aliases.clone();
                 panel
             },
             advanced_filters_panel: AdvancedFiltersPanel::new(),
@@ -277,6 +372,7 @@ impl LoglineApp {
                 }
                 parser
             },
+            redactor: Redactor::new(&config.redaction),
             // Custom titlebar - must be before config is moved
             title_bar: TitleBar::new(
                 TitleBarOptions::new()
@@ -298,6 +394,8 @@ impl LoglineApp {
             tokio_runtime,
             // Android logcat readers
             active_logcat_readers: std::collections::HashMap::new(),
+            stdin_reader: None,
+            pending_tab_setup: std::collections::HashMap::new(),
             // First frame flag for initial theme application
             first_frame: true,
             // System tray - will be initialized after event loop starts
@@ -307,8 +405,12 @@ impl LoglineApp {
             window_hidden: false,
             // Close dialog
             close_dialog: CloseDialog::new(),
+            line_detail_panel: LineDetailPanel::default(),
             // Load bookmarks from disk
             bookmarks_store: BookmarksStore::load().unwrap_or_default(),
+            autosave_timer,
+            autosave_writer: AutosaveWriter::spawn(AUTOSAVE_DEBOUNCE),
+            line_export: None,
         }
     }
 
@@ -378,7 +480,7 @@ impl LoglineApp {
 
         if !is_cache_file {
             self.config.add_recent_file(path.clone());
-            let _ = self.config.save();
+            self.save_config();
             // Also update explorer panel's local files list
             self.explorer_panel.local_files = self.config.recent_files.clone();
         }
@@ -388,14 +490,25 @@ impl LoglineApp {
             mcp_server.add_local_file(path.clone());
         }
 
-        // Restore saved grok config for this file
-        self.restore_file_grok_config(tab_id, &path);
+        // Restore saved grok config for this file once its lines are in
+        let grok_config = self.config.grok_config_for(&path);
+        self.setup_when_loaded(tab_id, grok_config);
 
-        // Scroll to bottom for new file
-        if let Some(state) = self.tab_manager.get_state_mut(tab_id) {
-            state.main_view.scroll_to_bottom();
+        // Restore saved display prefix strip for this file
+        self.restore_file_strip_prefix(tab_id, &path);
+
+        // Apply user-assigned alias to the new tab
+        self.sync_source_aliases();
+
+        // Restore the last split layout once both of its files are open
+        if self.tab_manager.apply_pending_split() {
+            self.toolbar_state.split_view_active = true;
         }
 
+        // Restore the file's reverse-order preference
+        let reverse = self.config.is_file_reverse_order(&path);
+        self.tab_manager.restore_reverse_order(tab_id, reverse);
+
         // Update toolbar state from the newly opened tab
         if let Some(state) = self.tab_manager.states.get(&tab_id) {
             self.toolbar_state.auto_scroll = state.main_view.virtual_scroll.state.auto_scroll;
@@ -408,8 +521,80 @@ impl LoglineApp {
         Ok(())
     }
 
+    /// Scroll a newly opened tab to the bottom and apply its grok config,
+    /// deferred until the tab's initial read is done
+    fn setup_when_loaded(
+        &mut self,
+        tab_id: crate::ui::tab_bar::TabId,
+        grok_config: Option<crate::config::FileGrokConfig>,
+    ) {
+        if self.tab_manager.states.get(&tab_id).is_some_and(|s| s.is_loading()) {
+            self.pending_tab_setup.insert(tab_id, grok_config);
+        } else {
+            self.finish_tab_setup(tab_id, grok_config);
+        }
+    }
+
+    /// Apply the setup deferred by [`Self::setup_when_loaded`]
+    fn finish_tab_setup(
+        &mut self,
+        tab_id: crate::ui::tab_bar::TabId,
+        grok_config: Option<crate::config::FileGrokConfig>,
+    ) {
+        if let Some(grok_config) = grok_config {
+            self.apply_file_grok_config(tab_id, grok_config);
+        }
+        if let Some(state) = self.tab_manager.get_state_mut(tab_id) {
+            state.main_view.scroll_to_bottom();
+        }
+    }
+
+    /// Finish setting up tabs whose initial read is done
+    fn handle_loaded_tabs(&mut self) {
+        for (tab_id, result) in self.tab_manager.poll_loading() {
+            let grok_config = self.pending_tab_setup.remove(&tab_id).flatten();
+            let merged = self
+                .tab_manager
+                .states
+                .get(&tab_id)
+                .map(|state| (state.merged, state.buffer.len()));
+            if let Err(e) = result {
+                let failed = match merged {
+                    Some((true, _)) => t::merge_failed(),
+                    _ => t::file_open_failed(),
+                };
+                self.status_bar
+                    .set_message(format!("{}: {:#}", failed, e), StatusLevel::Error);
+                continue;
+            }
+            self.finish_tab_setup(tab_id, grok_config);
+            if let Some((true, lines)) = merged {
+                self.status_bar.set_message(
+                    format!("{} ({} {})", t::sources_merged(), lines, t::lines()),
+                    StatusLevel::Success,
+                );
+            }
+
+            let reattached = self
+                .tab_manager
+                .states
+                .get(&tab_id)
+                .and_then(|state| state.reader.as_ref())
+                .is_some_and(|reader| reader.reattached());
+            if reattached {
+                self.status_bar
+                    .set_message(t::file_rotated_during_open(), StatusLevel::Warning);
+            }
+        }
+    }
+
     /// Open a remote stream in a new tab
-    pub fn open_remote_stream(&mut self, project_name: String, cache_path: PathBuf) -> Result<()> {
+    pub fn open_remote_stream(
+        &mut self,
+        project_name: String,
+        cache_path: PathBuf,
+        log_format: Option<&str>,
+    ) -> Result<()> {
         // Open in tab manager
         let tab_id = self.tab_manager.open_remote_stream(
             project_name.clone(),
@@ -417,10 +602,14 @@ impl LoglineApp {
             &self.bookmarks_store,
         )?;
 
-        // Scroll to bottom for new stream
-        if let Some(state) = self.tab_manager.get_state_mut(tab_id) {
-            state.main_view.scroll_to_bottom();
-        }
+        // A saved config wins over the format the agent declared
+        let grok_config = self
+            .config
+            .grok_config_for(&cache_path)
+            .or_else(|| log_format.map(|f| self.config.grok_config_for_pattern(f)));
+        self.setup_when_loaded(tab_id, grok_config);
+
+        self.sync_source_aliases();
 
         self.status_bar
             .set_message("Remote stream opened", StatusLevel::Success);
@@ -428,30 +617,105 @@ impl LoglineApp {
         Ok(())
     }
 
-    /// Open Android logcat in a new tab
-    pub fn open_android_logcat(&mut self, device: crate::android_logcat::AndroidDevice) -> Result<()> {
-        use crate::android_logcat::{LogcatReader, LogcatOptions};
-        
+    /// Stream the process's stdin into a new tab
+    pub fn open_stdin(&mut self) -> Result<()> {
+        let reader = StdinReader::spawn(stdin_cache_path())?;
+        let tab_id = self.tab_manager.open_remote_stream(
+            STDIN_TAB_NAME.to_string(),
+            reader.cache_path().to_path_buf(),
+            &self.bookmarks_store,
+        )?;
+        self.stdin_reader = Some((reader, tab_id));
+
+        // Follow new lines like a logcat stream
+        if let Some(state) = self.tab_manager.get_state_mut(tab_id) {
+            state.main_view.virtual_scroll.state.auto_scroll = true;
+        }
+        self.setup_when_loaded(tab_id, None);
+
+        self.status_bar
+            .set_message(t::streaming_stdin(), StatusLevel::Success);
+        Ok(())
+    }
+
+    /// Status bar label of the stdin tab (None for other sources)
+    fn stdin_status_label(&self, path: Option<&std::path::Path>) -> Option<&'static str> {
+        let (reader, _) = self.stdin_reader.as_ref()?;
+        (path? == reader.cache_path()).then(|| {
+            if reader.is_running() {
+                t::streaming_stdin()
+            } else {
+                t::stdin_ended()
+            }
+        })
+    }
+
+    /// Status bar label of a logcat tab whose app isn't running (None otherwise)
+    fn logcat_status_label(&self, path: Option<&std::path::Path>) -> Option<&'static str> {
+        let path = path?;
+        self.active_logcat_readers
+            .values()
+            .any(|(reader, cache_path, _)| cache_path == path && reader.app_missing())
+            .then(t::logcat_app_not_running)
+    }
+
+    /// Pick several log files and open them merged by timestamp in a
+    /// read-only tab
+    fn merge_sources(&mut self) {
+        let Some(paths) = rfd::FileDialog::new().pick_files() else {
+            return;
+        };
+        if paths.len() < 2 {
+            self.status_bar
+                .set_message(t::merge_needs_two_files(), StatusLevel::Warning);
+            return;
+        }
+
+        // Read and merged in the background, see `handle_loaded_tabs`
+        self.tab_manager.open_merged(&paths);
+    }
+
+    /// Open Android logcat in a new tab, optionally clearing the device buffer
+    /// first and scoped to one app (package name or PID)
+    pub fn open_android_logcat(
+        &mut self,
+        device: crate::android_logcat::AndroidDevice,
+        clear_logcat: bool,
+        app_filter: &str,
+    ) -> Result<()> {
+        use crate::android_logcat::{AdbManager, LogcatReader, LogcatOptions};
+
+        // The package is resolved by the capture thread, see `logcat_status_label`
+        let options = LogcatOptions::default().with_app_filter(app_filter);
+
         // Check if we already have an active logcat for this device
-        if let Some((_, _cache_path, tab_id)) = self.active_logcat_readers.get(&device.serial) {
+        if let Some((reader, _cache_path, tab_id)) = self.active_logcat_readers.get(&device.serial) {
+            let tab_id = *tab_id;
+            let same_filter = reader.options() == &options;
             // Check if the tab is still open
-            if self.tab_manager.states.contains_key(tab_id) {
+            if !self.tab_manager.states.contains_key(&tab_id) {
+                // Tab was closed, remove the stale entry
+                // Reader will be dropped and stopped automatically
+                self.active_logcat_readers.remove(&device.serial);
+            } else if same_filter {
                 // Just switch to the existing tab
-                self.tab_manager.tab_bar.active_tab = Some(*tab_id);
+                self.tab_manager.tab_bar.active_tab = Some(tab_id);
                 self.status_bar.set_message(
                     format!("Switched to existing logcat: {}", device.model),
                     StatusLevel::Info,
                 );
                 return Ok(());
             } else {
-                // Tab was closed, remove the stale entry
-                // Reader will be dropped and stopped automatically
-                self.active_logcat_readers.remove(&device.serial);
+                // The app filter changed: stop the old capture and start over
+                self.close_tab(tab_id);
             }
         }
 
         // Create a display name for the tab
-        let logcat_name = format!("📱 {}", device.model);
+        let logcat_name = match app_filter.trim() {
+            "" => format!("📱 {}", device.model),
+            filter => format!("📱 {} · {}", device.model, filter),
+        };
 
         // Create a cache file - use device serial only (no timestamp)
         let cache_dir = dirs::data_dir()
@@ -475,14 +739,11 @@ impl LoglineApp {
         // Create the cache file
         std::fs::File::create(&cache_file)?;
 
+        // Clear the device buffer before streaming so the tab starts fresh
+        let clear_result = clear_logcat.then(|| AdbManager::new().clear_logcat(&device.serial));
+
         // Create and start the logcat reader
-        let reader = LogcatReader::new(
-            device.serial.clone(),
-            LogcatOptions {
-                clear_before_read: true, // Start with fresh logs
-                ..Default::default()
-            },
-        );
+        let reader = LogcatReader::new(device.serial.clone(), options);
         reader.start_streaming()?;
 
         // Get the receiver for log entries
@@ -533,14 +794,24 @@ impl LoglineApp {
 
         // Enable auto-scroll for logcat
         if let Some(state) = self.tab_manager.get_state_mut(tab_id) {
-            state.main_view.scroll_to_bottom();
             state.main_view.virtual_scroll.state.auto_scroll = true;
         }
+        self.setup_when_loaded(tab_id, None);
 
-        self.status_bar.set_message(
-            format!("Android logcat started: {}", device.model),
-            StatusLevel::Success,
-        );
+        match clear_result {
+            Some(Err(e)) => self.status_bar.set_message(
+                format!("{}: {}", t::logcat_clear_failed(), e),
+                StatusLevel::Warning,
+            ),
+            Some(Ok(())) => self.status_bar.set_message(
+                format!("{}: {}", t::logcat_cleared(), device.model),
+                StatusLevel::Success,
+            ),
+            None => self.status_bar.set_message(
+                format!("Android logcat started: {}", device.model),
+                StatusLevel::Success,
+            ),
+        }
 
         Ok(())
     }
@@ -617,6 +888,7 @@ impl LoglineApp {
         
         // Then open it in split view
         self.tab_manager.enable_split(tab_id);
+        self.sync_source_aliases();
         
         self.status_bar
             .set_message("File opened in split view", StatusLevel::Success);
@@ -636,8 +908,102 @@ impl LoglineApp {
             // Remove and drop the reader (this will stop the streaming)
             self.active_logcat_readers.remove(&serial);
         }
-        
+
+        self.pending_tab_setup.remove(&tab_id);
         self.tab_manager.close_tab(tab_id, &mut self.bookmarks_store);
+
+        if self.stdin_reader.as_ref().is_some_and(|(_, id)| *id == tab_id) {
+            // Dropping the reader stops its thread and removes its cache
+            // (after the tab has let go of the file)
+            self.stdin_reader = None;
+        }
+    }
+
+    /// Show or hide the search bar
+    fn toggle_search_bar(&mut self) {
+        if self.search_bar.visible {
+            self.close_search_bar();
+        } else {
+            self.search_bar.open();
+        }
+        self.toolbar_state.search_visible = self.search_bar.visible;
+    }
+
+    /// Hide the search bar, keeping its highlights if configured to
+    fn close_search_bar(&mut self) {
+        let keep_highlights = self.display_config.keep_search_highlights;
+        match self.tab_manager.get_active_state_mut() {
+            Some(state) => {
+                self.search_bar.close_search(&mut state.filter, keep_highlights);
+                state.update_filter();
+            }
+            None => self.search_bar.close(),
+        }
+        self.toolbar_state.search_visible = false;
+    }
+
+    /// Clear the active tab's search along with its highlights
+    fn clear_search_highlights(&mut self) {
+        self.search_bar.input.clear();
+        if let Some(state) = self.tab_manager.get_active_state_mut() {
+            state.filter.clear_search();
+            state.update_filter();
+        }
+    }
+
+    /// Diff a tab against its snapshot and open the diff window
+    fn diff_against_snapshot(&mut self, tab_id: crate::ui::tab_bar::TabId) {
+        let Some((snapshot, live)) = self.tab_manager.snapshot_pair(tab_id) else {
+            self.status_bar.set_message(t::no_snapshot(), StatusLevel::Warning);
+            return;
+        };
+        let name_of = |id| {
+            self.tab_manager
+                .tab_bar
+                .tabs
+                .iter()
+                .find(|tab| tab.id == id)
+                .map(|tab| tab.display_name().to_string())
+                .unwrap_or_default()
+        };
+        let (old_name, new_name) = (name_of(snapshot), name_of(live));
+
+        if let Some(pending) = self.tab_manager.diff_against_snapshot(tab_id) {
+            self.diff_view.show_diff(old_name, new_name, pending);
+            self.toolbar_state.split_view_active = self.tab_manager.is_split();
+        }
+    }
+
+    /// MCP source ID of a tab's source (None for snapshots and streams
+    /// that are no longer known to the remote server)
+    fn tab_source_id(&self, tab_id: crate::ui::tab_bar::TabId) -> Option<String> {
+        let tab = self.tab_manager.tab_bar.tabs.iter().find(|tab| tab.id == tab_id)?;
+        if tab.snapshot_of.is_some() || tab.merged {
+            return None;
+        }
+        if !tab.is_remote {
+            return Some(local_source_id(&tab.path));
+        }
+        self.remote_server
+            .streams()
+            .iter()
+            .find(|stream| stream.cache_path == tab.path)
+            .map(remote_source_id)
+    }
+
+    /// Copy an MCP source ID to the clipboard
+    fn copy_source_id(&mut self, ctx: &egui::Context, source_id: Option<String>) {
+        match source_id {
+            Some(id) => {
+                ctx.copy_text(id.clone());
+                self.status_bar
+                    .set_message(format!("{}: {}", t::source_id_copied(), id), StatusLevel::Info);
+            }
+            None => {
+                self.status_bar
+                    .set_message(t::source_id_unavailable(), StatusLevel::Warning);
+            }
+        }
     }
 
     /// Reload the current file
@@ -657,8 +1023,10 @@ impl LoglineApp {
             let path = state.path.clone();
             
             // Save encoding preference
-            self.config.set_file_encoding(path.clone(), encoding);
-            let _ = self.config.save();
+            if state.persists_settings() {
+                self.config.set_file_encoding(path.clone(), encoding);
+                self.save_config();
+            }
 
             // Close the current tab and reopen with new encoding
             if let Some(tab_id) = self.tab_manager.tab_bar.active_tab {
@@ -695,21 +1063,22 @@ impl LoglineApp {
         };
 
         let file_path = state.path.clone();
+        let persist = state.persists_settings();
 
         match selection {
             GrokPatternSelection::None => {
                 // Disable grok parsing for this tab
                 state.grok_parser = None;
                 state.grok_config = None;
-                state.grok_parse_progress = 0;
+                state.reset_grok_parse();
                 
                 // Clear grok fields for this tab only
-                for entry in state.buffer.iter_mut() {
-                    entry.clear_grok_fields();
-                }
+                state.buffer.update_all(LogEntry::clear_grok_fields);
                 
                 // Remove file-specific config
-                self.config.set_file_grok_config(file_path, None);
+                if persist {
+                    self.config.set_file_grok_config(file_path, None);
+                }
                 
                 self.status_bar.set_message(t::grok_pattern_cleared(), StatusLevel::Info);
             }
@@ -766,17 +1135,16 @@ impl LoglineApp {
                     inline_pattern: None,
                     pre_processor: custom.pre_processor.clone(),
                 });
-                state.grok_parse_progress = 0;
+                state.reset_grok_parse();
                 
                 // Clear grok fields to reparse
-                for entry in state.buffer.iter_mut() {
-                    entry.clear_grok_fields();
-                }
+                state.buffer.update_all(LogEntry::clear_grok_fields);
                 
                 // Save file-specific config
-                let config = state.grok_config.clone();
-                let path = state.path.clone();
-                self.config.set_file_grok_config(path, config);
+                if persist {
+                    self.config
+                        .set_file_grok_config(file_path, state.grok_config.clone());
+                }
                 
                 self.status_bar.set_message(
                     format!("{}: {}", t::grok_active_pattern(), name),
@@ -786,143 +1154,703 @@ impl LoglineApp {
         }
         
         // Save config
-        if let Err(e) = self.config.save() {
-            tracing::error!("Failed to save config: {}", e);
+        self.save_config();
+    }
+
+    /// Save the config. A failure is reported once with the config path and
+    /// stays flagged in the status bar; every later save retries.
+    fn save_config(&mut self) -> bool {
+        // Written by the autosave writer so a pending autosave cannot
+        // replace it with an older copy
+        let result = AppConfig::config_path().and_then(|path| {
+            let contents = self.config.to_toml()?;
+            self.autosave_writer.write_now(vec![(path, contents)])
+        });
+        if let Err(e) = &result {
+            tracing::error!("Failed to save config: {:#}", e);
         }
+        let path = AppConfig::config_path().ok();
+        self.status_bar.report_config_save(&result, path.as_deref());
+        result.is_ok()
     }
 
-    /// Restore saved grok config for a file when opening it
-    fn restore_file_grok_config(&mut self, tab_id: crate::ui::tab_bar::TabId, path: &PathBuf) {
-        use crate::grok_parser::{GrokParser, BuiltinPattern};
-        
-        let path_str = path.to_string_lossy().to_string();
-        let saved_config = self.config.file_grok_configs.get(&path_str).cloned();
-        
-        let Some(file_config) = saved_config else {
-            return; // No saved config for this file
-        };
-        
-        if !file_config.enabled {
-            return; // Grok was disabled for this file
+    /// Save bookmarks and session state in the background once the
+    /// autosave interval has elapsed
+    fn tick_autosave(&mut self, ctx: &egui::Context) {
+        for result in self.autosave_writer.poll_results() {
+            if let Err(e) = result {
+                tracing::error!("Autosave failed: {:#}", e);
+                self.status_bar
+                    .set_message(format!("{}: {}", t::autosave_failed(), e), StatusLevel::Warning);
+            }
         }
-        
-        // Create a parser for this tab
-        let mut parser = GrokParser::new();
-        parser.import_custom_patterns(self.config.grok.custom_patterns.clone());
-        for (name, pat) in &self.config.grok.custom_definitions {
-            parser.add_pattern_definition(name, pat);
+        if !self.config.autosave.enabled {
+            return;
         }
-        
-        // Set the pattern based on saved config
-        let pattern_set = match file_config.pattern_type.as_str() {
-            "builtin" => {
-                if let Some(ref pattern_name) = file_config.builtin_pattern {
-                    // Find builtin pattern by name
-                    BuiltinPattern::all()
-                        .iter()
-                        .find(|p| p.display_name() == pattern_name)
-                        .and_then(|pattern| parser.set_builtin_pattern(*pattern).ok())
-                        .is_some()
-                } else {
-                    false
-                }
-            }
-            "custom" => {
-                if let Some(ref custom_name) = file_config.custom_pattern_name {
-                    if let Some(custom) = self.config.grok.custom_patterns.iter().find(|p| &p.name == custom_name) {
-                        let template = if custom.display_template.is_empty() {
-                            None
-                        } else {
-                            Some(custom.display_template.as_str())
-                        };
-                        let pattern_ok = parser.set_custom_pattern_with_template(custom_name, &custom.pattern, template).is_ok();
-                        if pattern_ok {
-                            if custom.pre_processor != crate::grok_parser::PreProcessor::None {
-                                parser.set_pre_processor(custom.pre_processor.clone());
-                            } else if file_config.pre_processor != crate::grok_parser::PreProcessor::None {
-                                parser.set_pre_processor(file_config.pre_processor.clone());
-                            }
-                        }
-                        pattern_ok
-                    } else {
-                        false
-                    }
-                } else {
-                    false
-                }
-            }
-            "inline" => {
-                if let Some(ref inline) = file_config.inline_pattern {
-                    let template = if inline.display_template.is_empty() {
-                        None
-                    } else {
-                        Some(inline.display_template.as_str())
-                    };
-                    let pattern_ok = parser.set_custom_pattern_with_template(&inline.name, &inline.pattern, template).is_ok();
-                    if pattern_ok {
-                        // Inline patterns may have their own pre_processor
-                        // Priority: inline pattern > file config
-                        if inline.pre_processor != crate::grok_parser::PreProcessor::None {
-                            parser.set_pre_processor(inline.pre_processor.clone());
-                        } else if file_config.pre_processor != crate::grok_parser::PreProcessor::None {
-                            parser.set_pre_processor(file_config.pre_processor.clone());
-                        }
-                    }
-                    pattern_ok
-                } else {
-                    false
-                }
-            }
-            _ => false,
-        };
-        
-        if pattern_set {
-            // Also restore pre_processor
-            parser.set_pre_processor(file_config.pre_processor.clone());
-            
-            if let Some(state) = self.tab_manager.get_state_mut(tab_id) {
-                state.grok_parser = Some(parser);
-                state.grok_config = Some(file_config);
-                state.grok_parse_progress = 0;
-                tracing::info!("Restored grok config for file: {}", path.display());
-            }
+
+        let now = Instant::now();
+        if self.autosave_timer.tick(now) {
+            self.autosave();
         }
+        ctx.request_repaint_after(self.autosave_timer.remaining(now));
     }
 
-    /// Start the MCP server
-    fn start_mcp_server(&mut self) {
-        if self.mcp_server.is_some() {
-            return; // Already running
+    /// Hand the current bookmarks and session state to the autosave writer
+    fn autosave(&mut self) {
+        self.config.split_layout = self.tab_manager.split_layout();
+        self.tab_manager.sync_all_bookmarks(&mut self.bookmarks_store);
+        match self.autosave_snapshot() {
+            Ok(snapshot) => {
+                self.autosave_writer.submit(snapshot);
+            }
+            Err(e) => tracing::error!("Failed to prepare autosave: {:#}", e),
         }
+    }
 
-        let cache_dir = dirs::data_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join("logline")
-            .join("cache");
-
-        let mcp_config = McpConfig {
-            port: self.config.mcp.port,
-            bind_address: self.config.mcp.bind_address.clone(),
-        };
+    /// Serialized config and bookmarks, as written by a regular save
+    fn autosave_snapshot(&self) -> Result<Snapshot> {
+        Ok(vec![
+            (AppConfig::config_path()?, self.config.to_toml()?),
+            (BookmarksStore::storage_path()?, self.bookmarks_store.to_toml()?),
+        ])
+    }
 
-        let mut server = McpServer::new(mcp_config, cache_dir);
+    /// Autosave before the window is hidden to the tray
+    fn autosave_on_hide(&mut self) {
+        if self.config.autosave.enabled {
+            self.autosave();
+        }
+    }
 
-        // Ensure we have a runtime
-        if self.tokio_runtime.is_none() {
-            self.tokio_runtime = tokio::runtime::Builder::new_multi_thread()
-                .worker_threads(2)
-                .enable_all()
-                .build()
-                .ok();
+    /// Set or clear the friendly display name of a source and refresh views
+    fn set_source_alias(&mut self, path: PathBuf, alias: Option<String>) {
+        if !self.tab_manager.persists_settings(&path) {
+            return;
         }
+        self.config.set_source_alias(&path, alias);
+        self.save_config();
+        self.sync_source_aliases();
+    }
 
-        if let Some(ref rt) = self.tokio_runtime {
-            let result = rt.block_on(async { server.start().await });
+    /// Push configured aliases to the tab bar and explorer
+    fn sync_source_aliases(&mut self) {
+        self.tab_manager
+            .tab_bar
+            .apply_aliases(&self.config.source_aliases);
+        self.explorer_panel.aliases = self.config.source_aliases.clone();
+    }
 
-            match result {
-                Ok(()) => {
-                    let endpoint = server.endpoint_url();
-                    let msg = format!("{}: {}", t::mcp_server_started(), endpoint);
+    /// Export an annotated report of the bookmarks in all open tabs
+    fn export_bookmark_report(&mut self, format: ReportFormat) {
+        let reports: Vec<FileReport> = self
+            .tab_manager
+            .tab_bar
+            .tabs
+            .iter()
+            .filter_map(|tab| {
+                let state = self.tab_manager.states.get(&tab.id)?;
+                Some(FileReport::collect(
+                    tab.display_name(),
+                    &state.path,
+                    &state.buffer,
+                    &self.bookmarks_store,
+                    DETAIL_CONTEXT_LINES,
+                ))
+            })
+            .filter(|report| !report.is_empty())
+            .collect();
+
+        if reports.is_empty() {
+            self.status_bar
+                .set_message(t::no_bookmarks_to_export(), StatusLevel::Warning);
+            return;
+        }
+
+        let Some(path) = rfd::FileDialog::new()
+            .set_file_name(format!("bookmarks-report.{}", format.extension()))
+            .add_filter(format.display_name(), &[format.extension()])
+            .save_file()
+        else {
+            return;
+        };
+
+        let report = bookmark_report::render(&reports, format);
+        match std::fs::write(&path, self.redactor.redact(&report).as_bytes()) {
+            Ok(()) => self.status_bar.set_message(
+                format!("{}: {}", t::report_exported(), display_path(&path)),
+                StatusLevel::Success,
+            ),
+            Err(e) => self.status_bar.set_message(
+                format!("{}: {}", t::report_export_failed(), e),
+                StatusLevel::Error,
+            ),
+        }
+    }
+
+    /// Open the log files of a dropped folder, asking first if there are many
+    fn open_dropped_folder(&mut self, dir: PathBuf) {
+        if self.config.folder_drop.behavior == FolderDropBehavior::OpenAsWorkspace {
+            self.add_workspace(dir);
+            return;
+        }
+        let files = folder_drop_dialog::files_to_open(&dir, &self.config.folder_drop);
+        if files.is_empty() {
+            self.status_bar.set_message(
+                format!("{}: {}", t::folder_drop_no_logs(), display_path(&dir)),
+                StatusLevel::Warning,
+            );
+        } else if files.len() > self.config.folder_drop.confirm_above {
+            self.folder_drop_dialog.ask(dir, files);
+        } else {
+            self.open_files(files);
+        }
+    }
+
+    /// Pin a folder in the explorer as a workspace listing its log files
+    fn add_workspace(&mut self, dir: PathBuf) {
+        let files = folder_drop_dialog::collect_log_files(&dir, self.config.folder_drop.max_depth);
+        self.explorer_panel.set_workspace(dir.clone(), files);
+        self.config.add_workspace_folder(dir.clone());
+        if self.save_config() {
+            self.status_bar.set_message(
+                format!(
+                    "{}: {}",
+                    t::folder_drop_workspace_added(),
+                    display_path(&dir)
+                ),
+                StatusLevel::Info,
+            );
+        }
+    }
+
+    /// Open several files in tabs, reporting how many were opened
+    fn open_files(&mut self, files: Vec<PathBuf>) {
+        let mut opened = 0;
+        for path in files {
+            match self.open_file(path.clone(), None) {
+                Ok(()) => opened += 1,
+                Err(e) => tracing::warn!("Failed to open {:?}: {}", path, e),
+            }
+        }
+        self.status_bar.set_message(
+            format!("{}: {}", t::folder_drop_opened(), opened),
+            StatusLevel::Success,
+        );
+    }
+
+    /// Toggle bookmarks on the active tab's search matches
+    fn bookmark_search_matches(&mut self) {
+        let Some(tab_id) = self.tab_manager.tab_bar.active_tab else {
+            return;
+        };
+        let Some(state) = self.tab_manager.get_active_state_mut() else {
+            return;
+        };
+        let (count, bookmarked) = state.toggle_match_bookmarks();
+        if count == 0 {
+            return;
+        }
+        self.tab_manager.save_bookmarks(tab_id, &mut self.bookmarks_store);
+        let message = if bookmarked {
+            t::matches_bookmarked()
+        } else {
+            t::matches_unbookmarked()
+        };
+        self.status_bar.set_message(
+            message.replacen("{}", &count.to_string(), 1),
+            StatusLevel::Info,
+        );
+    }
+
+    /// Export the active tab's search matches with context lines
+    fn export_search_matches(&mut self, format: MatchExportFormat) {
+        let Some(state) = self.tab_manager.get_active_state() else {
+            return;
+        };
+        let mut matches: Vec<usize> = state
+            .filter
+            .search
+            .results()
+            .iter()
+            .map(|m| m.buffer_index)
+            .collect();
+        // Fuzzy results are ranked by score; export in file order
+        matches.sort_unstable();
+        if matches.is_empty() {
+            return;
+        }
+
+        let Some(path) = rfd::FileDialog::new()
+            .set_file_name(format!("search-matches.{}", format.extension()))
+            .add_filter(format.display_name(), &[format.extension()])
+            .save_file()
+        else {
+            return;
+        };
+
+        let result = std::fs::File::create(&path).and_then(|file| {
+            match_export::write_matches(
+                std::io::BufWriter::new(file),
+                &state.buffer,
+                &matches,
+                self.search_bar.export_context,
+                format,
+                &self.redactor,
+            )
+        });
+        match result {
+            Ok(_) => self.status_bar.set_message(
+                format!("{}: {}", t::matches_exported(), display_path(&path)),
+                StatusLevel::Success,
+            ),
+            Err(e) => self.status_bar.set_message(
+                format!("{}: {}", t::matches_export_failed(), e),
+                StatusLevel::Error,
+            ),
+        }
+    }
+
+    /// Export the lines the active tab shows (all lines when unfiltered)
+    /// to a text, JSON lines or CSV file, written in the background
+    fn export_filtered_lines(&mut self) {
+        self.start_line_export("filtered.log", LineExportFormat::all());
+    }
+
+    /// Export the grok fields of the lines the active tab shows as CSV
+    fn export_parsed_csv(&mut self) {
+        self.start_line_export("parsed.csv", &[LineExportFormat::Csv]);
+    }
+
+    /// Ask where to save the lines the active tab shows and write them in
+    /// the background, in one of `formats`
+    fn start_line_export(&mut self, file_name: &str, formats: &[LineExportFormat]) {
+        if self.line_export.is_some() {
+            self.status_bar
+                .set_message(t::export_in_progress(), StatusLevel::Warning);
+            return;
+        }
+        let Some(state) = self.tab_manager.get_active_state() else {
+            return;
+        };
+        let indices: Vec<usize> = if state.filter_active {
+            state.filtered_indices.clone()
+        } else {
+            (0..state.buffer.len()).collect()
+        };
+        if indices.is_empty() {
+            self.status_bar
+                .set_message(t::no_lines_to_export(), StatusLevel::Warning);
+            return;
+        }
+
+        let mut dialog = rfd::FileDialog::new().set_file_name(file_name);
+        for format in formats {
+            dialog = dialog.add_filter(format.display_name(), format.extensions());
+        }
+        let Some(path) = dialog.save_file() else {
+            return;
+        };
+
+        let format = match formats {
+            [format] => *format,
+            _ => LineExportFormat::for_path(&path),
+        };
+        // Every CSV row needs its fields; the export thread parses the rest
+        let grok_parser = state
+            .grok_parser
+            .as_ref()
+            .filter(|p| format == LineExportFormat::Csv && p.has_active_pattern())
+            .cloned();
+        let source = ExportSource {
+            entries: indices
+                .iter()
+                .filter_map(|&index| state.buffer.get(index).cloned())
+                .collect(),
+            grok_parser,
+            redactor: self.redactor.clone(),
+        };
+        let columns = state
+            .grok_parser
+            .as_ref()
+            .and_then(|p| p.active_pattern())
+            .map(|p| p.columns.clone())
+            .unwrap_or_default();
+        self.line_export = Some(LineExport::start(path, source, format, columns));
+        self.status_bar.set_progress(0.0);
+    }
+
+    /// Show the progress of a running line export and report its result
+    fn poll_line_export(&mut self, ctx: &egui::Context) {
+        let Some(export) = self.line_export.as_mut() else {
+            return;
+        };
+        let Some(result) = export.poll() else {
+            self.status_bar.set_progress(export.progress());
+            ctx.request_repaint_after(Duration::from_millis(100));
+            return;
+        };
+
+        let path = export.path().to_path_buf();
+        self.line_export = None;
+        self.status_bar.clear_progress();
+        match result {
+            Ok(count) => self.status_bar.set_message(
+                format!("{} ({}): {}", t::lines_exported(), count, display_path(&path)),
+                StatusLevel::Success,
+            ),
+            Err(e) => self.status_bar.set_message(
+                format!("{}: {}", t::lines_export_failed(), e),
+                StatusLevel::Error,
+            ),
+        }
+    }
+
+    /// Jump to the next/previous error or warning among the displayed rows
+    fn cycle_problem_line(&mut self, forward: bool) {
+        let Some(state) = self.tab_manager.get_active_state_mut() else {
+            return;
+        };
+
+        let filtered = if state.filter_active {
+            Some(state.filtered_indices.as_slice())
+        } else {
+            None
+        };
+        let current = state.main_view.selected_line;
+
+        match state.buffer.cycle_problem_line(filtered, current, forward) {
+            Some((buffer_idx, position, total)) => {
+                state.main_view.scroll_to_line(buffer_idx);
+                let label = match state.buffer.get(buffer_idx).and_then(|e| e.level) {
+                    Some(LogLevel::Warn) => t::warning_position(),
+                    _ => t::error_position(),
+                };
+                self.status_bar.set_message(
+                    format!("{} {} {} {}", label, position, t::match_of(), total),
+                    StatusLevel::Info,
+                );
+            }
+            None => {
+                self.status_bar
+                    .set_message(t::no_problems_in_view(), StatusLevel::Warning);
+            }
+        }
+    }
+
+    /// Jump to the next (or previous) bookmark in view, from the selected
+    /// line or else the top of the view
+    fn cycle_bookmark(&mut self, forward: bool) {
+        let Some(state) = self.tab_manager.get_active_state_mut() else {
+            return;
+        };
+
+        let filtered = if state.filter_active {
+            Some(state.filtered_indices.as_slice())
+        } else {
+            None
+        };
+        let top = state.main_view.virtual_scroll.state.first_visible_row;
+        let top = match filtered {
+            Some(indices) => indices.get(top).copied(),
+            None => Some(top),
+        };
+        // From the top of the view a bookmark on the top row is next
+        let from_top = top.and_then(|top| if forward { top.checked_sub(1) } else { Some(top) });
+        let current = state.main_view.selected_line.or(from_top);
+
+        match state.buffer.cycle_bookmark(filtered, current, forward) {
+            Some((buffer_idx, position, total)) => {
+                state.main_view.scroll_to_line(buffer_idx);
+                self.status_bar.set_message(
+                    format!(
+                        "{} {} {} {}",
+                        t::bookmark_position(),
+                        position,
+                        t::match_of(),
+                        total
+                    ),
+                    StatusLevel::Info,
+                );
+            }
+            None => {
+                self.status_bar
+                    .set_message(t::no_bookmarks_in_view(), StatusLevel::Warning);
+            }
+        }
+    }
+
+    /// Change the display prefix strip for the current file
+    pub fn change_strip_prefix(&mut self, config: Option<crate::config::StripPrefixConfig>) {
+        use crate::highlighter::PrefixStripper;
+
+        let stripper = match config.clone().map(PrefixStripper::new).transpose() {
+            Ok(stripper) => stripper,
+            Err(e) => {
+                self.status_bar
+                    .set_message(format!("Invalid strip prefix: {}", e), StatusLevel::Error);
+                return;
+            }
+        };
+
+        if let Some(state) = self.tab_manager.get_active_state_mut() {
+            let path = state.path.clone();
+            state.main_view.strip_prefix = stripper;
+
+            if state.persists_settings() {
+                self.config.set_file_strip_prefix(path, config);
+                self.save_config();
+            }
+        }
+    }
+
+    /// Toggle reverse order for the active tab and remember it for the file
+    fn toggle_reverse_order(&mut self) {
+        if let Some((path, reverse)) = self.tab_manager.toggle_active_reverse_order() {
+            self.toolbar_state.reverse_order = reverse;
+            if self.tab_manager.persists_settings(&path) {
+                self.config.set_file_reverse_order(&path, reverse);
+                self.save_config();
+            }
+        }
+    }
+
+    /// Restore saved display prefix strip for a file when opening it
+    fn restore_file_strip_prefix(&mut self, tab_id: crate::ui::tab_bar::TabId, path: &PathBuf) {
+        use crate::highlighter::PrefixStripper;
+
+        let Some(config) = self.config.get_file_strip_prefix(path).cloned() else {
+            return;
+        };
+
+        if let Some(state) = self.tab_manager.get_state_mut(tab_id) {
+            state.main_view.strip_prefix = PrefixStripper::new(config).ok();
+        }
+    }
+
+    /// Parse the active tab's lines as JSON objects, or turn that off
+    fn set_json_mode(&mut self, enabled: bool) {
+        if !enabled {
+            self.change_grok_pattern(crate::ui::status_bar::GrokPatternSelection::None);
+            return;
+        }
+        let Some(tab_id) = self.tab_manager.tab_bar.active_tab else {
+            return;
+        };
+        let Some(state) = self.tab_manager.get_state_mut(tab_id) else {
+            return;
+        };
+
+        let config = crate::config::FileGrokConfig::json();
+        state.reset_grok_parse();
+        state.buffer.update_all(LogEntry::clear_grok_fields);
+        if state.persists_settings() {
+            self.config
+                .set_file_grok_config(state.path.clone(), Some(config.clone()));
+        }
+        self.apply_file_grok_config(tab_id, config);
+
+        self.status_bar.set_message(
+            format!("{}: {}", t::grok_active_pattern(), JSON_MODE_NAME),
+            StatusLevel::Success,
+        );
+        self.save_config();
+        self.remember_analysis_context();
+    }
+
+    /// Rank the builtin patterns against the active tab's first lines and
+    /// apply the best one if it matches well enough
+    fn auto_detect_grok_pattern(&mut self) {
+        use crate::grok_parser::{GrokParser, DETECT_MIN_MATCH_RATE, DETECT_SAMPLE_LINES};
+
+        let Some(state) = self.tab_manager.get_active_state() else {
+            return;
+        };
+        let mut parser = GrokParser::new();
+        for (name, pat) in &self.config.grok.custom_definitions {
+            parser.add_pattern_definition(name, pat);
+        }
+        let lines: Vec<&str> = state
+            .buffer
+            .iter()
+            .take(DETECT_SAMPLE_LINES)
+            .map(|e| e.content.as_str())
+            .collect();
+        let candidates = parser.rank_builtin_patterns(&lines);
+        let path = state.path.clone();
+
+        let best = candidates
+            .first()
+            .filter(|c| c.match_rate >= DETECT_MIN_MATCH_RATE)
+            .map(|c| c.pattern);
+        self.grok_panel.set_detected_patterns(path, candidates);
+        match best {
+            Some(pattern) => self.apply_builtin_grok_pattern(pattern),
+            None => self.status_bar.set_message(t::grok_auto_detect_none(), StatusLevel::Warning),
+        }
+    }
+
+    /// Apply a builtin pattern to the active tab and remember it for the file
+    fn apply_builtin_grok_pattern(&mut self, pattern: crate::grok_parser::BuiltinPattern) {
+        let Some(tab_id) = self.tab_manager.tab_bar.active_tab else {
+            return;
+        };
+        let Some(state) = self.tab_manager.get_state_mut(tab_id) else {
+            return;
+        };
+
+        let config = crate::config::FileGrokConfig::builtin(pattern);
+        state.reset_grok_parse();
+        state.buffer.update_all(LogEntry::clear_grok_fields);
+        if state.persists_settings() {
+            self.config
+                .set_file_grok_config(state.path.clone(), Some(config.clone()));
+        }
+        self.apply_file_grok_config(tab_id, config);
+
+        self.status_bar.set_message(
+            format!("{}: {}", t::grok_active_pattern(), pattern.display_name()),
+            StatusLevel::Success,
+        );
+        self.save_config();
+        self.remember_analysis_context();
+    }
+
+    /// Set up a tab's grok parser from a per-file config
+    fn apply_file_grok_config(
+        &mut self,
+        tab_id: crate::ui::tab_bar::TabId,
+        file_config: crate::config::FileGrokConfig,
+    ) {
+        use crate::grok_parser::{GrokParser, BuiltinPattern};
+        
+        if !file_config.enabled {
+            return; // Grok was disabled for this file
+        }
+        
+        // Create a parser for this tab
+        let mut parser = GrokParser::new();
+        parser.import_custom_patterns(self.config.grok.custom_patterns.clone());
+        for (name, pat) in &self.config.grok.custom_definitions {
+            parser.add_pattern_definition(name, pat);
+        }
+        
+        // Set the pattern based on saved config
+        let pattern_set = match file_config.pattern_type.as_str() {
+            "builtin" => {
+                if let Some(ref pattern_name) = file_config.builtin_pattern {
+                    // Find builtin pattern by name
+                    BuiltinPattern::all()
+                        .iter()
+                        .find(|p| p.display_name() == pattern_name)
+                        .and_then(|pattern| parser.set_builtin_pattern(*pattern).ok())
+                        .is_some()
+                } else {
+                    false
+                }
+            }
+            "custom" => {
+                if let Some(ref custom_name) = file_config.custom_pattern_name {
+                    if let Some(custom) = self.config.grok.custom_patterns.iter().find(|p| &p.name == custom_name) {
+                        let template = if custom.display_template.is_empty() {
+                            None
+                        } else {
+                            Some(custom.display_template.as_str())
+                        };
+                        let pattern_ok = parser.set_custom_pattern_with_template(custom_name, &custom.pattern, template).is_ok();
+                        if pattern_ok {
+                            if custom.pre_processor != crate::grok_parser::PreProcessor::None {
+                                parser.set_pre_processor(custom.pre_processor.clone());
+                            } else if file_config.pre_processor != crate::grok_parser::PreProcessor::None {
+                                parser.set_pre_processor(file_config.pre_processor.clone());
+                            }
+                        }
+                        pattern_ok
+                    } else {
+                        false
+                    }
+                } else {
+                    false
+                }
+            }
+            "inline" => {
+                if let Some(ref inline) = file_config.inline_pattern {
+                    let template = if inline.display_template.is_empty() {
+                        None
+                    } else {
+                        Some(inline.display_template.as_str())
+                    };
+                    let pattern_ok = parser.set_custom_pattern_with_template(&inline.name, &inline.pattern, template).is_ok();
+                    if pattern_ok {
+                        // Inline patterns may have their own pre_processor
+                        // Priority: inline pattern > file config
+                        if inline.pre_processor != crate::grok_parser::PreProcessor::None {
+                            parser.set_pre_processor(inline.pre_processor.clone());
+                        } else if file_config.pre_processor != crate::grok_parser::PreProcessor::None {
+                            parser.set_pre_processor(file_config.pre_processor.clone());
+                        }
+                    }
+                    pattern_ok
+                } else {
+                    false
+                }
+            }
+            "json" => {
+                let columns = self.tab_manager.states.get(&tab_id).map_or_else(Vec::new, |state| {
+                    json_columns(state.buffer.iter().map(|e| e.content.as_str()))
+                });
+                parser.set_json_mode(columns);
+                true
+            }
+            _ => false,
+        };
+        
+        if pattern_set {
+            // Also restore pre_processor
+            parser.set_pre_processor(file_config.pre_processor.clone());
+            
+            if let Some(state) = self.tab_manager.get_state_mut(tab_id) {
+                state.grok_parser = Some(parser);
+                state.grok_config = Some(file_config);
+                state.reset_grok_parse();
+                tracing::info!("Restored grok config for file: {}", state.path.display());
+            }
+        }
+    }
+
+    /// Start the MCP server
+    fn start_mcp_server(&mut self) {
+        if self.mcp_server.is_some() {
+            return; // Already running
+        }
+
+        let cache_dir = dirs::data_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("logline")
+            .join("cache");
+
+        let mcp_config = McpConfig {
+            port: self.config.mcp.port,
+            bind_address: self.config.mcp.bind_address.clone(),
+        };
+
+        let mut server = McpServer::new(mcp_config, cache_dir);
+
+        // Ensure we have a runtime
+        if self.tokio_runtime.is_none() {
+            self.tokio_runtime = tokio::runtime::Builder::new_multi_thread()
+                .worker_threads(2)
+                .enable_all()
+                .build()
+                .ok();
+        }
+
+        if let Some(ref rt) = self.tokio_runtime {
+            let result = rt.block_on(async { server.start().await });
+
+            match result {
+                Ok(()) => {
+                    let endpoint = server.endpoint_url();
+                    let msg = format!("{}: {}", t::mcp_server_started(), endpoint);
                     self.status_bar
                         .set_message(msg, StatusLevel::Success);
                     tracing::info!("MCP server started at {}", endpoint);
@@ -988,6 +1916,15 @@ impl LoglineApp {
                     // User can manually click to open it
                     has_stream_changes = true;
                 }
+                ServerEvent::AgentReconnected {
+                    project_name,
+                    stream_id,
+                } => {
+                    tracing::info!("Agent '{}' ({}) resumed its stream", project_name, stream_id);
+                    let msg = format!("{} '{}'", t::agent_reconnected(), stream_id);
+                    self.status_bar.set_message(msg, StatusLevel::Success);
+                    has_stream_changes = true;
+                }
                 ServerEvent::AgentDisconnected {
                     project_name,
                     stream_id,
@@ -1007,6 +1944,24 @@ impl LoglineApp {
                     // File watcher will handle the UI refresh when cache file changes
                     // No need to update stream list here
                 }
+                ServerEvent::CacheTrimmed {
+                    project_name: _,
+                    stream_id,
+                    bytes_removed,
+                } => {
+                    tracing::info!(
+                        "Trimmed {} bytes from the cache of {}",
+                        bytes_removed,
+                        stream_id
+                    );
+                    let msg = format!(
+                        "{} '{}' ({})",
+                        t::remote_cache_trimmed(),
+                        stream_id,
+                        format_size(bytes_removed)
+                    );
+                    self.status_bar.set_message(msg, StatusLevel::Info);
+                }
                 ServerEvent::Error(e) => {
                     tracing::error!("Server error: {}", e);
                     let msg = format!("{}: {}", t::server_error(), e);
@@ -1033,6 +1988,12 @@ impl LoglineApp {
     /// Handle keyboard shortcuts
     fn handle_shortcuts(&mut self, ctx: &egui::Context) -> Option<AppAction> {
         // Check for shortcuts using ctx.input_mut
+        // Open Recent (Cmd+Shift+O) - check before open_file (Cmd+O)
+        if ctx.input_mut(|i| i.consume_shortcut(&self.shortcuts.open_recent)) {
+            self.open_recent_dialog.show_dialog();
+            return None;
+        }
+
         if ctx.input_mut(|i| i.consume_shortcut(&self.shortcuts.open_file)) {
             return Some(AppAction::OpenSourcePicker);
         }
@@ -1044,8 +2005,7 @@ impl LoglineApp {
         }
 
         if ctx.input_mut(|i| i.consume_shortcut(&self.shortcuts.find)) {
-            self.search_bar.toggle();
-            self.toolbar_state.search_visible = self.search_bar.visible;
+            self.toggle_search_bar();
             return None;
         }
 
@@ -1067,40 +2027,80 @@ impl LoglineApp {
             return None;
         }
 
+        // Check Cmd+Shift+L before Cmd+L so clearing the buffer doesn't swallow it
+        if ctx.input_mut(|i| i.consume_shortcut(&self.shortcuts.clear_highlights)) {
+            self.clear_search_highlights();
+            return None;
+        }
+
+        // Check Shift+F8 before F8 so the plain shortcut doesn't swallow it
+        let problem_direction =
+            if ctx.input_mut(|i| i.consume_shortcut(&self.shortcuts.prev_problem)) {
+                Some(false)
+            } else if ctx.input_mut(|i| i.consume_shortcut(&self.shortcuts.next_problem)) {
+                Some(true)
+            } else {
+                None
+            };
+        if let Some(forward) = problem_direction {
+            self.cycle_problem_line(forward);
+            return None;
+        }
+
+        // Likewise Shift+F2 before F2
+        let bookmark_direction =
+            if ctx.input_mut(|i| i.consume_shortcut(&self.shortcuts.prev_bookmark)) {
+                Some(false)
+            } else if ctx.input_mut(|i| i.consume_shortcut(&self.shortcuts.next_bookmark)) {
+                Some(true)
+            } else {
+                None
+            };
+        if let Some(forward) = bookmark_direction {
+            self.cycle_bookmark(forward);
+            return None;
+        }
+
         if ctx.input_mut(|i| i.consume_shortcut(&self.shortcuts.clear)) {
             self.clear_buffer();
             return None;
         }
 
+        // Checked before go-to-line, which would also match with Shift held
+        if ctx.input_mut(|i| i.consume_shortcut(&self.shortcuts.goto_time)) {
+            self.goto_time_dialog.open = true;
+            self.goto_time_dialog.focus_input = true;
+            return None;
+        }
+
         if ctx.input_mut(|i| i.consume_shortcut(&self.shortcuts.goto_line)) {
             self.goto_dialog.open = true;
             return None;
         }
 
         if ctx.input_mut(|i| i.consume_shortcut(&self.shortcuts.toggle_auto_scroll)) {
+            let space_action = self.config.keyboard.space_key_action;
             if let Some(state) = self.tab_manager.get_active_state_mut() {
-                // Toggle monitoring state
-                if self.toolbar_state.auto_scroll {
-                    // Currently monitoring, stop it
-                    state.stop_monitoring();
-                    self.toolbar_state.auto_scroll = false;
-                } else {
-                    // Currently stopped, resume monitoring
-                    state.resume_monitoring();
-                    self.toolbar_state.auto_scroll = true;
-                }
+                // Toggle monitoring or page down, depending on settings
+                self.toolbar_state.auto_scroll =
+                    state.handle_space_key(space_action, self.toolbar_state.auto_scroll);
             }
             return None;
         }
 
         if ctx.input_mut(|i| i.consume_shortcut(&self.shortcuts.toggle_reverse_order)) {
-            if let Some(state) = self.tab_manager.get_active_state_mut() {
-                state.main_view.toggle_reverse_order();
-                self.toolbar_state.reverse_order = state.main_view.is_reverse_order();
-            }
+            self.toggle_reverse_order();
             return None;
         }
 
+        for &toggle in DisplayToggle::all() {
+            let shortcut = *self.shortcuts.display_toggle(toggle);
+            if ctx.input_mut(|i| i.consume_shortcut(&shortcut)) {
+                self.toggle_display(toggle);
+                return None;
+            }
+        }
+
         if ctx.input_mut(|i| i.consume_shortcut(&self.shortcuts.goto_top)) {
             if let Some(state) = self.tab_manager.get_active_state_mut() {
                 state.main_view.scroll_to_top();
@@ -1116,26 +2116,7 @@ impl LoglineApp {
         }
 
         if ctx.input_mut(|i| i.consume_shortcut(&self.shortcuts.copy)) {
-            if let Some(state) = self.tab_manager.get_active_state() {
-                let filtered = if state.filter_active {
-                    Some(state.filtered_indices.as_slice())
-                } else {
-                    None
-                };
-                if let Some(text) = state.main_view.get_selected_text(&state.buffer, filtered) {
-                    let lines_count = state.main_view.selected_lines_count();
-                    ctx.copy_text(text);
-                    if lines_count > 1 {
-                        self.status_bar.set_message(
-                            format!("Copied {} lines to clipboard", lines_count),
-                            StatusLevel::Info,
-                        );
-                    } else {
-                        self.status_bar
-                            .set_message("Copied to clipboard", StatusLevel::Info);
-                    }
-                }
-            }
+            self.copy_selection(ctx, self.display_config.copy_with_line_numbers);
             return None;
         }
 
@@ -1185,31 +2166,156 @@ impl LoglineApp {
         None
     }
 
+    /// Add the active tab's search, filter and grok pattern to the recent contexts
+    fn remember_analysis_context(&mut self) {
+        let Some(state) = self.tab_manager.get_active_state() else {
+            return;
+        };
+        let grok_pattern = state
+            .grok_config
+            .as_ref()
+            .filter(|c| c.enabled)
+            .and_then(|c| {
+                c.custom_pattern_name
+                    .clone()
+                    .or_else(|| c.builtin_pattern.clone())
+                    .or_else(|| (c.pattern_type == "json").then(|| JSON_MODE_NAME.to_string()))
+            });
+        let context = state.filter.context(grok_pattern);
+        if context.is_empty() || self.config.recent_contexts.first() == Some(&context) {
+            return;
+        }
+        self.config.add_recent_context(context);
+        self.save_config();
+    }
+
+    /// Apply a recent context's search, filter and grok pattern to the active tab
+    fn apply_analysis_context(&mut self, context: crate::search::AnalysisContext) {
+        let Some(tab_id) = self.tab_manager.tab_bar.active_tab else {
+            return;
+        };
+        if let Some(state) = self.tab_manager.get_active_state_mut() {
+            state.filter.apply_context(&context);
+            state.filter.search.search(&state.buffer);
+            state.update_filter();
+        }
+        self.search_bar.input = context.query.clone();
+
+        if let Some(pattern) = &context.grok_pattern {
+            if let Some(state) = self.tab_manager.get_state_mut(tab_id) {
+                // Reparse with the context's pattern
+                state.buffer.update_all(LogEntry::clear_grok_fields);
+            }
+            let grok_config = self.config.grok_config_for_pattern(pattern);
+            self.apply_file_grok_config(tab_id, grok_config);
+        }
+
+        self.status_bar.set_message(
+            format!("{}: {}", t::recent_context_applied(), context.label()),
+            StatusLevel::Info,
+        );
+        self.config.add_recent_context(context);
+        self.save_config();
+    }
+
+    /// Persist a filter preset saved or deleted in the filters panel
+    fn handle_preset_action(&mut self, action: PresetAction) {
+        let message = match action {
+            PresetAction::Save(preset) => {
+                let message = format!("{}: {}", t::filter_preset_saved(), preset.name);
+                self.config.save_filter_preset(preset);
+                message
+            }
+            PresetAction::Delete(name) => {
+                self.config.delete_filter_preset(&name);
+                format!("{}: {}", t::filter_preset_deleted(), name)
+            }
+        };
+        self.save_config();
+        self.status_bar.set_message(message, StatusLevel::Info);
+    }
+
+    /// Flip a display flag, keeping the settings panel in sync, and persist it
+    fn toggle_display(&mut self, toggle: DisplayToggle) {
+        let enabled = toggle.apply(&mut self.display_config);
+        self.settings_panel.display_config = self.display_config.clone();
+        self.config.display = self.display_config.clone();
+        self.save_config();
+
+        let label = match toggle {
+            DisplayToggle::LineNumbers => t::show_line_numbers(),
+            DisplayToggle::RowSeparator => t::show_row_separator(),
+            DisplayToggle::GrokFields => t::show_grok_fields(),
+            DisplayToggle::WrapLines => t::wrap_long_lines(),
+        };
+        let state = if enabled { t::toggle_on() } else { t::toggle_off() };
+        self.status_bar
+            .set_message(format!("{}: {}", label, state), StatusLevel::Info);
+    }
+
+    /// Copy the selected lines of the active tab, optionally prefixed with
+    /// their line numbers
+    fn copy_selection(&mut self, ctx: &egui::Context, with_line_numbers: bool) {
+        let Some(state) = self.tab_manager.get_active_state() else {
+            return;
+        };
+        let filtered = if state.filter_active {
+            Some(state.filtered_indices.as_slice())
+        } else {
+            None
+        };
+        let text = if with_line_numbers {
+            state.main_view.get_selected_numbered_text(&state.buffer, filtered)
+        } else {
+            state.main_view.get_selected_text(&state.buffer, filtered)
+        };
+        let Some(text) = text else {
+            return;
+        };
+        let lines_count = state.main_view.selected_lines_count();
+        ctx.copy_text(self.redactor.redact(&text).into_owned());
+        if lines_count > 1 {
+            self.status_bar.set_message(
+                format!("Copied {} lines to clipboard", lines_count),
+                StatusLevel::Info,
+            );
+        } else {
+            self.status_bar
+                .set_message("Copied to clipboard", StatusLevel::Info);
+        }
+    }
+
+    /// Copy the selection as a Markdown table of its grok fields (or a code block)
+    fn copy_selection_markdown(&mut self, ctx: &egui::Context) {
+        let Some(state) = self.tab_manager.get_active_state() else {
+            return;
+        };
+        let filtered = if state.filter_active {
+            Some(state.filtered_indices.as_slice())
+        } else {
+            None
+        };
+        let parser = state.grok_parser.as_ref().filter(|p| p.has_active_pattern());
+        let Some(text) = state
+            .main_view
+            .get_selected_markdown(&state.buffer, filtered, parser)
+        else {
+            return;
+        };
+        ctx.copy_text(self.redactor.redact(&text).into_owned());
+        self.status_bar
+            .set_message(t::copied_as_markdown(), StatusLevel::Info);
+    }
+
     /// Handle context menu actions
     fn handle_context_menu_action(&mut self, action: ContextMenuAction, ctx: egui::Context) {
         match action {
             ContextMenuAction::Copy => {
-                if let Some(state) = self.tab_manager.get_active_state() {
-                    let filtered = if state.filter_active {
-                        Some(state.filtered_indices.as_slice())
-                    } else {
-                        None
-                    };
-                    if let Some(text) = state.main_view.get_selected_text(&state.buffer, filtered) {
-                        let lines_count = state.main_view.selected_lines_count();
-                        ctx.copy_text(text);
-                        if lines_count > 1 {
-                            self.status_bar.set_message(
-                                format!("Copied {} lines to clipboard", lines_count),
-                                StatusLevel::Info,
-                            );
-                        } else {
-                            self.status_bar
-                                .set_message("Copied to clipboard", StatusLevel::Info);
-                        }
-                    }
-                }
+                self.copy_selection(&ctx, self.display_config.copy_with_line_numbers);
             }
+            ContextMenuAction::CopyWithLineNumbers => self.copy_selection(&ctx, true),
+            ContextMenuAction::CopyPlain => self.copy_selection(&ctx, false),
+            ContextMenuAction::CopyAsMarkdown => self.copy_selection_markdown(&ctx),
             ContextMenuAction::CopyAll => {
                 if let Some(state) = self.tab_manager.get_active_state() {
                     let filtered = if state.filter_active {
@@ -1220,7 +2326,7 @@ impl LoglineApp {
                     let text = Self::get_all_visible_text_from_state(state, filtered);
                     if !text.is_empty() {
                         let lines_count = text.lines().count();
-                        ctx.copy_text(text);
+                        ctx.copy_text(self.redactor.redact(&text).into_owned());
                         self.status_bar.set_message(
                             format!("Copied {} lines to clipboard", lines_count),
                             StatusLevel::Info,
@@ -1273,6 +2379,113 @@ impl LoglineApp {
                     state.main_view.scroll_to_bottom();
                 }
             }
+            ContextMenuAction::FilterByCorrelationId => self.filter_by_correlation_id(),
+            ContextMenuAction::ClearCorrelationFilter => {
+                if let Some(state) = self.tab_manager.get_active_state_mut() {
+                    state.filter.filter.correlation = None;
+                    state.filter.mark_dirty();
+                    state.update_filter();
+                    self.status_bar
+                        .set_message(t::correlation_filter_cleared(), StatusLevel::Info);
+                }
+            }
+            ContextMenuAction::NextCorrelatedLine => self.cycle_correlated_line(true),
+            ContextMenuAction::PrevCorrelatedLine => self.cycle_correlated_line(false),
+        }
+    }
+
+    /// Correlation id extractor for the configured source, reporting an
+    /// invalid regex in the status bar
+    fn correlation_extractor(&mut self) -> Option<CorrelationExtractor> {
+        match CorrelationExtractor::new(&self.config.correlation) {
+            Ok(extractor) => Some(extractor),
+            Err(e) => {
+                self.status_bar.set_message(
+                    format!("{}: {}", t::invalid_correlation_pattern(), e),
+                    StatusLevel::Error,
+                );
+                None
+            }
+        }
+    }
+
+    /// Filter the active tab to the lines sharing the selected line's correlation id
+    fn filter_by_correlation_id(&mut self) {
+        let Some(extractor) = self.correlation_extractor() else {
+            return;
+        };
+        let Some(state) = self.tab_manager.get_active_state_mut() else {
+            return;
+        };
+        let filtered = if state.filter_active {
+            Some(state.filtered_indices.as_slice())
+        } else {
+            None
+        };
+        let Some(selected) = state.main_view.get_selected_indices(filtered).first().copied() else {
+            return;
+        };
+        if extractor.needs_grok_fields() {
+            // The other lines get their fields in the background and are
+            // shown once parsed (see `TabState::parse_all_grok_fields`)
+            state.parse_grok_line(selected);
+        }
+        let id = state
+            .buffer
+            .get(selected)
+            .and_then(|entry| extractor.extract(entry))
+            .map(str::to_string);
+        let Some(id) = id else {
+            self.status_bar.set_message(t::no_correlation_id(), StatusLevel::Warning);
+            return;
+        };
+
+        self.status_bar.set_message(
+            format!("{}: {}", t::correlation_filter_applied(), id),
+            StatusLevel::Info,
+        );
+        state.filter.filter.correlation = Some(CorrelationFilter { extractor, id });
+        state.filter.mark_dirty();
+        state.update_filter();
+        state.main_view.scroll_to_line(selected);
+    }
+
+    /// Select the next/previous displayed line sharing the selected line's correlation id
+    fn cycle_correlated_line(&mut self, forward: bool) {
+        let Some(extractor) = self.correlation_extractor() else {
+            return;
+        };
+        let Some(state) = self.tab_manager.get_active_state_mut() else {
+            return;
+        };
+        let filtered = if state.filter_active {
+            Some(state.filtered_indices.as_slice())
+        } else {
+            None
+        };
+        let Some(current) = state.main_view.get_selected_indices(filtered).first().copied() else {
+            return;
+        };
+        if extractor.needs_grok_fields() {
+            // Lines not parsed yet are skipped until the background parse is done
+            state.parse_grok_line(current);
+            state.parse_all_grok_fields();
+        }
+        let filtered = state
+            .filter_active
+            .then_some(state.filtered_indices.as_slice());
+
+        match cycle_correlated_line(&state.buffer, &extractor, filtered, current, forward) {
+            Some((buffer_idx, position, total)) => {
+                state.main_view.scroll_to_line(buffer_idx);
+                self.status_bar.set_message(
+                    format!("{} {} / {}", t::correlated_line(), position, total),
+                    StatusLevel::Info,
+                );
+            }
+            None => {
+                self.status_bar.set_message(t::no_correlation_id(), StatusLevel::Warning);
+            }
         }
     }
 
@@ -1282,13 +2495,13 @@ impl LoglineApp {
         if let Some(indices) = filtered_indices {
             for &idx in indices {
                 if let Some(entry) = state.buffer.get(idx) {
-                    lines.push(entry.content.clone());
+                    lines.push(strip_ansi(&entry.content).into_owned());
                 }
             }
         } else {
             for i in 0..state.buffer.len() {
                 if let Some(entry) = state.buffer.get(i) {
-                    lines.push(entry.content.clone());
+                    lines.push(strip_ansi(&entry.content).into_owned());
                 }
             }
         }
@@ -1305,16 +2518,13 @@ impl LoglineApp {
             }
             ToolbarAction::ToggleAutoScroll => {
                 if let Some(state) = self.tab_manager.get_active_state_mut() {
-                    // Toggle monitoring state
-                    if self.toolbar_state.auto_scroll {
-                        // Currently monitoring, stop it
-                        state.stop_monitoring();
-                        self.toolbar_state.auto_scroll = false;
+                    // Explicit toggle: overrides following driven by scrolling
+                    if state.main_view.is_auto_scroll() {
+                        state.pause_following();
                     } else {
-                        // Currently stopped, resume monitoring
-                        state.resume_monitoring();
-                        self.toolbar_state.auto_scroll = true;
+                        state.resume_following();
                     }
+                    self.toolbar_state.auto_scroll = state.main_view.is_auto_scroll();
                 }
                 None
             }
@@ -1323,14 +2533,18 @@ impl LoglineApp {
                 None
             }
             ToolbarAction::ToggleSearch => {
-                self.search_bar.toggle();
-                self.toolbar_state.search_visible = self.search_bar.visible;
+                self.toggle_search_bar();
                 None
             }
             ToolbarAction::GoToLine => {
                 self.goto_dialog.open = true;
                 None
             }
+            ToolbarAction::GoToTime => {
+                self.goto_time_dialog.open = true;
+                self.goto_time_dialog.focus_input = true;
+                None
+            }
             ToolbarAction::GoToTop => {
                 if let Some(state) = self.tab_manager.get_active_state_mut() {
                     state.main_view.scroll_to_top();
@@ -1356,27 +2570,79 @@ impl LoglineApp {
                 } else {
                     ThemeMode::Light
                 });
-                let _ = self.config.save();
+                self.save_config();
                 Some(AppAction::UpdateTheme)
             }
-            ToolbarAction::OpenSettings => {
-                // Switch to settings view in sidebar
-                self.sidebar_visible = true;
-                self.activity_bar.active_view = ActivityView::Settings;
+            ToolbarAction::OpenSettings => {
+                // Switch to settings view in sidebar
+                self.sidebar_visible = true;
+                self.activity_bar.active_view = ActivityView::Settings;
+                None
+            }
+            ToolbarAction::ToggleReverseOrder => {
+                self.toggle_reverse_order();
+                None
+            }
+            ToolbarAction::ToggleSplitView => {
+                self.tab_manager.toggle_split();
+                self.toolbar_state.split_view_active = self.tab_manager.is_split();
+                self.config.split_layout = self.tab_manager.split_layout();
+                self.save_config();
+                None
+            }
+            ToolbarAction::ToggleErrorsWithContext => {
+                let context_lines = self.display_config.error_context_lines;
+                if let Some(state) = self.tab_manager.get_active_state_mut() {
+                    state.filter.toggle_errors_with_context(context_lines);
+                    state.update_filter();
+                    self.toolbar_state.errors_with_context = state.filter.is_errors_with_context();
+                }
+                None
+            }
+            ToolbarAction::TogglePinnedFilter => {
+                let pinned = self.tab_manager.toggle_pinned_filter();
+                self.toolbar_state.filter_pinned = pinned;
+                self.status_bar.set_message(
+                    if pinned { t::filter_pinned() } else { t::filter_unpinned() },
+                    StatusLevel::Info,
+                );
+                None
+            }
+            ToolbarAction::ExportFiltered => {
+                self.export_filtered_lines();
+                None
+            }
+            ToolbarAction::ToggleWrapLines => {
+                self.toggle_display(DisplayToggle::WrapLines);
+                None
+            }
+            ToolbarAction::ToggleNotes => {
+                self.notes_panel.visible = !self.notes_panel.visible;
+                self.toolbar_state.notes_visible = self.notes_panel.visible;
                 None
             }
-            ToolbarAction::ToggleReverseOrder => {
+            ToolbarAction::ToggleCapture => {
                 if let Some(state) = self.tab_manager.get_active_state_mut() {
-                    state.main_view.toggle_reverse_order();
-                    self.toolbar_state.reverse_order = state.main_view.is_reverse_order();
+                    if self.toolbar_state.capture_active {
+                        // Cancel the capture but keep following
+                        state.capture = None;
+                    } else {
+                        if !self.toolbar_state.auto_scroll {
+                            state.resume_following();
+                            self.toolbar_state.auto_scroll = true;
+                        }
+                        let lines = self.toolbar_state.capture_lines;
+                        state.start_capture(lines);
+                        self.config.buffer.capture_lines = lines;
+                        self.save_config();
+                        self.status_bar.set_message(
+                            format!("{}: {}", t::capture_started(), lines),
+                            StatusLevel::Info,
+                        );
+                    }
                 }
                 None
             }
-            ToolbarAction::ToggleSplitView => {
-                self.tab_manager.toggle_split();
-                self.toolbar_state.split_view_active = self.tab_manager.is_split();
-                None
-            }
             ToolbarAction::None => None,
         }
     }
@@ -1388,37 +2654,27 @@ impl eframe::App for LoglineApp {
         #[cfg(target_os = "windows")]
         apply_rounded_corners(_frame);
         
-        // Handle file drag-and-drop
-        ctx.input(|i| {
-            if !i.raw.dropped_files.is_empty() {
-                for file in &i.raw.dropped_files {
-                    if let Some(path) = &file.path {
-                        tracing::info!("File dropped: {:?}", path);
-
-                        // Open the dropped file in a new tab
-                        match self.open_file(path.clone(), None) {
-                            Ok(_) => {
-                                let msg = format!("{}: {}", t::file_opened_success(), path.display());
-                                self.status_bar.set_message(
-                                    msg,
-                                    StatusLevel::Success,
-                                );
-                            }
-                            Err(e) => {
-                                let msg = format!("{}: {}", t::file_open_failed(), e);
-                                self.status_bar.set_message(
-                                    msg,
-                                    StatusLevel::Error,
-                                );
-                            }
-                        }
+        // Handle file drag-and-drop (only the first dropped file or folder)
+        let dropped = ctx.input(|i| i.raw.dropped_files.iter().find_map(|f| f.path.clone()));
+        if let Some(path) = dropped {
+            tracing::info!("File dropped: {:?}", path);
 
-                        // Only handle the first dropped file
-                        break;
+            if path.is_dir() {
+                self.open_dropped_folder(path);
+            } else {
+                // Open the dropped file in a new tab
+                match self.open_file(path.clone(), None) {
+                    Ok(_) => {
+                        let msg = format!("{}: {}", t::file_opened_success(), display_path(&path));
+                        self.status_bar.set_message(msg, StatusLevel::Success);
+                    }
+                    Err(e) => {
+                        let msg = format!("{}: {}", t::file_open_failed(), e);
+                        self.status_bar.set_message(msg, StatusLevel::Error);
                     }
                 }
             }
-        });
+        }
 
         // Initialize system tray after event loop has started (macOS requirement)
         if !self.tray_initialized {
@@ -1448,6 +2704,7 @@ impl eframe::App for LoglineApp {
                     }
                     TrayEvent::HideWindow => {
                         // Minimize window (Windows compatible)
+                        self.autosave_on_hide();
                         ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(true));
                     }
                     TrayEvent::OpenFile => {
@@ -1510,6 +2767,7 @@ impl eframe::App for LoglineApp {
                     // Minimize window (Windows compatible)
                     if self.tray_manager.is_some() {
                         tracing::info!("Close button: Minimize window");
+                        self.autosave_on_hide();
                         ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(true));
                     } else {
                         // No tray available, just exit
@@ -1540,7 +2798,7 @@ impl eframe::App for LoglineApp {
                     tracing::info!("User chose to exit");
                     if self.close_dialog.should_remember() {
                         self.config.window.close_button_behavior = CloseButtonBehavior::Exit;
-                        let _ = self.config.save();
+                        self.save_config();
                     }
                     self.should_quit = true;
                     ctx.send_viewport_cmd(egui::ViewportCommand::Close);
@@ -1549,8 +2807,9 @@ impl eframe::App for LoglineApp {
                     tracing::info!("User chose to minimize window");
                     if self.close_dialog.should_remember() {
                         self.config.window.close_button_behavior = CloseButtonBehavior::MinimizeToTray;
-                        let _ = self.config.save();
+                        self.save_config();
                     }
+                    self.autosave_on_hide();
                     ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(true));
                 }
                 CloseDialogResult::Cancel => {
@@ -1563,18 +2822,60 @@ impl eframe::App for LoglineApp {
         // Apply theme on first frame to ensure it takes effect after eframe initialization
         if self.first_frame {
             self.first_frame = false;
-            let visuals = match self.config.theme {
-                Theme::Dark => egui::Visuals::dark(),
-                Theme::Light => egui::Visuals::light(),
-            };
-            ctx.set_visuals(visuals);
+            theme::apply(ctx, self.config.theme, &self.config.accessibility);
             
             // Refresh Android devices on startup (in background to avoid blocking)
             self.refresh_android_devices();
         }
 
+        // Finish opening tabs whose initial read is done
+        self.handle_loaded_tabs();
+        self.tick_autosave(ctx);
+        self.poll_line_export(ctx);
+
         // Process background messages for all tabs
         self.tab_manager.process_all_reader_messages();
+
+        // Stop following tails where an error just arrived
+        let paused = self
+            .tab_manager
+            .pause_on_arrived_errors(self.display_config.pause_tail_on_error);
+        let active = self.tab_manager.tab_bar.active_tab;
+        if active.is_some_and(|id| paused.contains(&id)) {
+            self.toolbar_state.auto_scroll = false;
+            self.status_bar
+                .set_message(t::tail_paused_on_error(), StatusLevel::Error);
+            self.status_bar.flash();
+        }
+
+        // Report bounded captures that reached their line budget
+        for (tab_id, capture) in self.tab_manager.take_completed_captures() {
+            if self.tab_manager.tab_bar.active_tab == Some(tab_id) {
+                self.toolbar_state.auto_scroll = false;
+            }
+            self.status_bar.set_message(
+                format!("{}: {}", t::capture_complete(), capture.captured),
+                StatusLevel::Success,
+            );
+        }
+
+        // Keep scanning searches over large buffers, a step per frame
+        for state in self.tab_manager.states.values_mut() {
+            if state.filter.search.continue_search(&state.buffer) {
+                ctx.request_repaint();
+            }
+        }
+
+        // Note rotated or truncated files; the reload keeps each tab's auto-scroll
+        for tab_id in self.tab_manager.take_reloaded_tabs() {
+            if let Some(state) = self.tab_manager.states.get(&tab_id) {
+                let name = state.path.file_name().unwrap_or_default().to_string_lossy();
+                self.status_bar.set_message(
+                    format!("{}: {}", t::file_rotated(), name),
+                    StatusLevel::Info,
+                );
+            }
+        }
         
         // Check if any tab needs to load more data (lazy loading)
         // This is triggered when user scrolls near the top of the loaded data (in normal mode)
@@ -1591,7 +2892,15 @@ impl eframe::App for LoglineApp {
                 visible_range.start
             };
             
-            if state.buffer.should_load_more(check_position) {
+            // Rows moved since the last frame, so fast scrolling prefetches earlier
+            let rows_per_frame = state
+                .buffer
+                .lazy_load
+                .last_check_row
+                .map_or(0, |last| last.abs_diff(check_position));
+            state.buffer.lazy_load.last_check_row = Some(check_position);
+
+            if state.buffer.should_load_more(check_position, rows_per_frame) {
                 state.request_load_more();
             }
         }
@@ -1600,6 +2909,7 @@ impl eframe::App for LoglineApp {
         // This avoids parsing the entire file, making format switching instant
         {
             const MAX_PARSE_PER_FRAME: usize = 100; // Increased since we're only parsing visible area
+            const MAX_EAGER_PARSE_PER_FRAME: usize = 500;
             
             // Parse entries in all tabs - prioritize visible entries
             for state in self.tab_manager.states.values_mut() {
@@ -1645,6 +2955,7 @@ impl eframe::App for LoglineApp {
                 // Get visible range for on-demand parsing (based on displayed rows)
                 let visible_range = state.main_view.get_visible_range(display_row_count);
                 let mut parsed_count = 0;
+                let mut parsed_indices = Vec::new();
                 let reverse_order = state.main_view.virtual_scroll.state.reverse_order;
                 
                 // Priority 1: Parse visible entries first
@@ -1671,30 +2982,26 @@ impl eframe::App for LoglineApp {
                         continue;
                     };
                     
-                    if let Some(entry) = state.buffer.get_mut(buffer_idx) {
+                    state.buffer.update(buffer_idx, |entry| {
                         if entry.grok_fields.is_none() {
-                            // Use parser.parse_with_format() to support mixed patterns and formatting
-                            if let Some((fields, formatted)) =
-                                parser.parse_with_format(&entry.content)
-                            {
-                                if !fields.is_empty() {
-                                    entry.set_grok_fields(fields.fields);
-                                    if let Some((plain_text, segments)) = formatted {
-                                        entry.formatted_content = Some(plain_text);
-                                        entry.formatted_segments = Some(segments);
-                                    }
-                                }
-                            }
+                            parse_grok_fields(parser, entry);
                             parsed_count += 1;
+                            parsed_indices.push(buffer_idx);
                         }
-                    }
+                    });
                 }
+                // JSON keys first seen in these lines get their own columns
+                state.add_json_columns(parsed_indices);
                 
-                // Note: We no longer track grok_parse_progress for sequential parsing
-                // since we now parse on-demand based on visible area
+                // Priority 2: small files are parsed in full, a chunk per frame
+                // (grok_parse_progress tracks how far that has got)
+                parsed_count += state.parse_grok_chunk(
+                    self.config.grok.eager_parse_max_lines,
+                    MAX_EAGER_PARSE_PER_FRAME,
+                );
                 
                 if parsed_count > 0 {
-                    tracing::trace!("Parsed {} visible entries with grok pattern this frame", parsed_count);
+                    tracing::trace!("Parsed {} entries with grok pattern this frame", parsed_count);
                 }
             }
         }
@@ -1702,6 +3009,18 @@ impl eframe::App for LoglineApp {
         // Process remote server events
         self.process_server_events();
 
+        // Pause monitoring of background tabs that have been idle too long
+        // (still runs when disabled so paused tabs resume on activation)
+        let idle_timeout = match self.config.buffer.idle_pause_secs {
+            0 => Duration::MAX,
+            secs => Duration::from_secs(secs),
+        };
+        self.tab_manager
+            .update_idle_monitoring(idle_timeout, Instant::now());
+
+        // Keep the pinned filter applied to whichever tab is active
+        self.tab_manager.apply_pinned_filter();
+
         // Rate-limit filter updates
         if self.last_update.elapsed() > Duration::from_millis(16) {
             self.tab_manager.update_pending_filters();
@@ -1770,6 +3089,23 @@ impl eframe::App for LoglineApp {
                     .stroke(egui::Stroke::new(1.0, border_color)),
             )
             .show(ctx, |ui| {
+            self.toolbar_state.errors_with_context = self
+                .tab_manager
+                .get_active_state()
+                .map(|state| state.filter.is_errors_with_context())
+                .unwrap_or(false);
+            self.toolbar_state.error_context_lines = self.display_config.error_context_lines;
+            self.toolbar_state.filter_pinned = self.tab_manager.is_filter_pinned();
+            self.toolbar_state.capture_active = self
+                .tab_manager
+                .get_active_state()
+                .is_some_and(|state| state.capture.is_some());
+            // Always reflect the active tab's own reverse-order and follow state
+            self.toolbar_state.reverse_order = self.tab_manager.active_reverse_order();
+            if let Some(state) = self.tab_manager.get_active_state() {
+                self.toolbar_state.auto_scroll = state.main_view.is_auto_scroll();
+            }
+            self.toolbar_state.wrap_lines = self.display_config.wrap_lines;
             let filter_config = self.tab_manager.get_active_state_mut()
                 .map(|state| &mut state.filter.filter);
             let (toolbar_action, filter_changed) = Toolbar::show(ui, &mut self.toolbar_state, filter_config);
@@ -1787,11 +3123,21 @@ impl eframe::App for LoglineApp {
         });
 
         // Search bar panel
+        let mut export_matches = None;
+        let mut bookmark_matches = false;
+        let mut remember_context = false;
+        let mut recent_context = None;
+        let mut close_search = false;
+        let mut clear_highlights = false;
         if self.search_bar.visible {
             egui::TopBottomPanel::top("search").show(ctx, |ui| {
                 // Get search engine from active tab if available
                 if let Some(state) = self.tab_manager.get_active_state_mut() {
-                    let search_action = self.search_bar.show(ui, &mut state.filter.search);
+                    let search_action = self.search_bar.show(
+                        ui,
+                        &mut state.filter.search,
+                        &self.config.recent_contexts,
+                    );
                     // Handle search action after getting the state again
                     match search_action {
                         SearchBarAction::SearchChanged => {
@@ -1802,21 +3148,51 @@ impl eframe::App for LoglineApp {
                             if let Some(m) = state.filter.search.next() {
                                 state.main_view.scroll_to_line(m.buffer_index);
                             }
+                            remember_context = true;
                         }
                         SearchBarAction::FindPrev => {
                             if let Some(m) = state.filter.search.previous() {
                                 state.main_view.scroll_to_line(m.buffer_index);
                             }
                         }
+                        SearchBarAction::ExportMatches(format) => {
+                            export_matches = Some(format);
+                        }
+                        SearchBarAction::BookmarkAllMatches => {
+                            bookmark_matches = true;
+                        }
+                        SearchBarAction::ApplyRecentContext(index) => {
+                            recent_context = self.config.recent_contexts.get(index).cloned();
+                        }
+                        SearchBarAction::ClearHighlights => {
+                            clear_highlights = true;
+                        }
                         SearchBarAction::Close => {
-                            self.search_bar.close();
-                            self.toolbar_state.search_visible = false;
+                            close_search = true;
                         }
                         SearchBarAction::None => {}
                     }
                 }
             });
         }
+        if let Some(format) = export_matches {
+            self.export_search_matches(format);
+        }
+        if bookmark_matches {
+            self.bookmark_search_matches();
+        }
+        if remember_context {
+            self.remember_analysis_context();
+        }
+        if let Some(context) = recent_context {
+            self.apply_analysis_context(context);
+        }
+        if clear_highlights {
+            self.clear_search_highlights();
+        }
+        if close_search {
+            self.close_search_bar();
+        }
 
         // Filter panel (only shown when expanded for advanced filters)
         if self.filter_panel.is_expanded() {
@@ -1876,16 +3252,41 @@ impl eframe::App for LoglineApp {
                 info
             };
             
+            let strip_prefix = self
+                .tab_manager
+                .get_active_state()
+                .and_then(|state| state.main_view.strip_prefix.as_ref())
+                .map(|stripper| stripper.config.clone());
+            let file_stats = self
+                .tab_manager
+                .get_active_state()
+                .and_then(|state| state.file_stats.as_ref())
+                .map(|scanner| scanner.snapshot());
+            let decode_progress = self
+                .tab_manager
+                .get_active_state()
+                .and_then(|state| state.decode_progress());
+            let source_alias = self
+                .stdin_status_label(current_file)
+                .or_else(|| self.logcat_status_label(current_file))
+                .or_else(|| current_file.and_then(|path| self.config.get_source_alias(path)));
+
             if let Some(buffer) = buffer_ref {
+                self.status_bar.level_colors = self.display_config.level_palette();
+                self.status_bar.timestamp_mode = self.display_config.timestamp_mode;
                 if let Some(action) = self.status_bar.show(
                     ui,
                     current_file,
+                    source_alias,
                     buffer,
                     reader_ref,
                     auto_scroll,
                     filtered_count,
                     selected_count,
                     Some(&grok_info),
+                    strip_prefix.as_ref(),
+                    file_stats.as_ref(),
+                    decode_progress,
                 ) {
                     match action {
                         crate::ui::status_bar::StatusBarAction::ChangeEncoding(encoding) => {
@@ -1893,6 +3294,18 @@ impl eframe::App for LoglineApp {
                         }
                         crate::ui::status_bar::StatusBarAction::ChangeGrokPattern(selection) => {
                             self.change_grok_pattern(selection);
+                            self.remember_analysis_context();
+                        }
+                        crate::ui::status_bar::StatusBarAction::ChangeStripPrefix(config) => {
+                            self.change_strip_prefix(config);
+                        }
+                        crate::ui::status_bar::StatusBarAction::ToggleTimestampMode => {
+                            self.display_config.timestamp_mode =
+                                self.display_config.timestamp_mode.toggled();
+                            self.settings_panel.display_config.timestamp_mode =
+                                self.display_config.timestamp_mode;
+                            self.config.display = self.display_config.clone();
+                            self.save_config();
                         }
                     }
                 }
@@ -1904,6 +3317,75 @@ impl eframe::App for LoglineApp {
             }
         });
 
+        // === Notes Panel ===
+        if self.notes_panel.visible {
+            // Follows the active tab
+            let active_path = self.tab_manager.get_active_state().map(|state| state.path.clone());
+            if self.notes_panel.source() != active_path.as_deref() {
+                let notes = active_path
+                    .as_deref()
+                    .map_or("", |path| self.config.get_file_notes(path));
+                self.notes_panel.set_source(active_path.clone(), notes);
+            }
+
+            let mut notes_action = NotesAction::None;
+            egui::SidePanel::right("notes_panel")
+                .resizable(true)
+                .default_width(260.0)
+                .min_width(160.0)
+                .show(ctx, |ui| {
+                    notes_action = self.notes_panel.show(ui);
+                });
+
+            match notes_action {
+                NotesAction::Changed(path, notes) => {
+                    if self.tab_manager.persists_settings(&path) {
+                        self.config.set_file_notes(&path, &notes);
+                    }
+                }
+                NotesAction::Commit => {
+                    self.save_config();
+                }
+                NotesAction::Close => {
+                    self.notes_panel.visible = false;
+                    self.toolbar_state.notes_visible = false;
+                    self.save_config();
+                }
+                NotesAction::None => {}
+            }
+        }
+
+        // === Line Detail Panel ===
+        if self.line_detail_panel.visible && !self.tab_manager.is_empty() {
+            // Follows the active tab's selection
+            let detail = self.tab_manager.get_active_state().and_then(|state| {
+                state
+                    .main_view
+                    .selected_line
+                    .and_then(|idx| LineDetail::from_buffer(&state.buffer, idx, DETAIL_CONTEXT_LINES))
+            });
+
+            let mut detail_action = LineDetailAction::None;
+            egui::TopBottomPanel::bottom("line_detail")
+                .resizable(true)
+                .default_height(180.0)
+                .min_height(80.0)
+                .show(ctx, |ui| {
+                    detail_action = self.line_detail_panel.show(ui, detail.as_ref());
+                });
+
+            match detail_action {
+                LineDetailAction::Close => {
+                    self.line_detail_panel.visible = false;
+                }
+                LineDetailAction::CopyLine(text) => {
+                    ctx.copy_text(self.redactor.redact(&text).into_owned());
+                    self.status_bar.set_message("Copied to clipboard", StatusLevel::Info);
+                }
+                LineDetailAction::None => {}
+            }
+        }
+
         // === Tab Bar Panel ===
         if !self.tab_manager.is_empty() {
             egui::TopBottomPanel::top("tab_bar").show(ctx, |ui| {
@@ -1950,6 +3432,30 @@ impl eframe::App for LoglineApp {
                             self.tab_manager.enable_split(id);
                             self.toolbar_state.split_view_active = true;
                         }
+                        TabBarAction::SetAlias(id, alias) => {
+                            let path = self
+                                .tab_manager
+                                .tab_bar
+                                .tabs
+                                .iter()
+                                .find(|tab| tab.id == id)
+                                .map(|tab| tab.path.clone());
+                            if let Some(path) = path {
+                                self.set_source_alias(path, alias);
+                            }
+                        }
+                        TabBarAction::TakeSnapshot(id) => {
+                            if self.tab_manager.take_snapshot(id).is_some() {
+                                self.status_bar.set_message(t::snapshot_taken(), StatusLevel::Success);
+                            }
+                        }
+                        TabBarAction::DiffAgainstSnapshot(id) => {
+                            self.diff_against_snapshot(id);
+                        }
+                        TabBarAction::CopySourceId(id) => {
+                            let source_id = self.tab_source_id(id);
+                            self.copy_source_id(ctx, source_id);
+                        }
                         TabBarAction::None => {}
                     }
                 });
@@ -1975,6 +3481,7 @@ impl eframe::App for LoglineApp {
                 // Update server status in activity bar
                 self.activity_bar.server_running = self.remote_server.is_running();
                 self.activity_bar.server_port = self.settings_panel.port();
+                self.activity_bar.server_tls = self.remote_server.tls_enabled();
                 self.activity_bar.sidebar_visible = self.sidebar_visible;
                 self.activity_bar.connected_agents = self
                     .remote_server
@@ -2002,7 +3509,7 @@ impl eframe::App for LoglineApp {
                             self.remote_server.stop();
                             self.config.remote_server.enabled = false;
                             self.settings_panel.enable_remote_service = false;
-                            let _ = self.config.save();
+                            self.save_config();
                             self.status_bar
                                 .set_message(t::remote_server_stopped(), StatusLevel::Info);
                         } else {
@@ -2014,7 +3521,7 @@ impl eframe::App for LoglineApp {
                                 Ok(()) => {
                                     self.config.remote_server.enabled = true;
                                     self.settings_panel.enable_remote_service = true;
-                                    let _ = self.config.save();
+                                    self.save_config();
                                     let msg = format!("{}: {}", t::server_started(), port);
                                     self.status_bar.set_message(
                                         msg,
@@ -2037,14 +3544,14 @@ impl eframe::App for LoglineApp {
                             self.stop_mcp_server();
                             self.config.mcp.enabled = false;
                             self.settings_panel.mcp_enabled = false;
-                            let _ = self.config.save();
+                            self.save_config();
                         } else {
                             // Start MCP server
                             self.start_mcp_server();
                             if self.mcp_server.is_some() {
                                 self.config.mcp.enabled = true;
                                 self.settings_panel.mcp_enabled = true;
-                                let _ = self.config.save();
+                                self.save_config();
                             }
                         }
                     }
@@ -2064,6 +3571,8 @@ impl eframe::App for LoglineApp {
                             // Update remote streams
                             self.explorer_panel
                                 .update_remote_streams(self.remote_server.streams());
+                            self.explorer_panel.show_sizes =
+                                self.display_config.show_source_sizes;
 
                             match self.explorer_panel.show(ui) {
                                 ExplorerAction::OpenLocalFile(path) => {
@@ -2075,7 +3584,11 @@ impl eframe::App for LoglineApp {
                                     }
                                 }
                                 ExplorerAction::OpenRemoteStream(stream) => {
-                                    if let Err(e) = self.open_remote_stream(stream.project_name.clone(), stream.cache_path.clone()) {
+                                    if let Err(e) = self.open_remote_stream(
+                                        stream.project_name.clone(),
+                                        stream.cache_path.clone(),
+                                        stream.metadata.log_format.as_deref(),
+                                    ) {
                                         self.status_bar.set_message(
                                             format!("{}: {}", t::remote_stream_failed(), e),
                                             StatusLevel::Error,
@@ -2088,6 +3601,9 @@ impl eframe::App for LoglineApp {
                                 ExplorerAction::OpenSourcePickerAndroid => {
                                     action = Some(AppAction::OpenSourcePickerAndroid);
                                 }
+                                ExplorerAction::MergeSources => {
+                                    action = Some(AppAction::MergeSources);
+                                }
                                 ExplorerAction::OpenInSplit(path) => {
                                     if let Err(e) = self.open_file_in_split(path.clone()) {
                                         self.status_bar.set_message(
@@ -2115,6 +3631,9 @@ impl eframe::App for LoglineApp {
                                         StatusLevel::Info,
                                     );
                                 }
+                                ExplorerAction::CopySourceId(id) => {
+                                    self.copy_source_id(ui.ctx(), Some(id));
+                                }
                                 ExplorerAction::RevealInFinder(path) => {
                                     #[cfg(target_os = "macos")]
                                     {
@@ -2173,15 +3692,18 @@ impl eframe::App for LoglineApp {
                                         }
                                     }
                                 }
+                                ExplorerAction::OpenFiles(files) => {
+                                    self.open_files(files);
+                                }
+                                ExplorerAction::RemoveWorkspace(dir) => {
+                                    self.explorer_panel.workspaces.retain(|(d, _)| d != &dir);
+                                    self.config.workspace_folders.retain(|d| d != &dir);
+                                    self.save_config();
+                                }
                                 ExplorerAction::RemoveFromRecent(path) => {
                                     self.explorer_panel.local_files.retain(|p| p != &path);
                                     self.config.recent_files.retain(|p| p != &path);
-                                    if let Err(e) = self.config.save() {
-                                        self.status_bar.set_message(
-                                            format!("{}: {}", t::config_save_failed(), e),
-                                            StatusLevel::Error,
-                                        );
-                                    } else {
+                                    if self.save_config() {
                                         self.status_bar.set_message(
                                             t::removed_from_recent_files().to_string(),
                                             StatusLevel::Info,
@@ -2191,12 +3713,7 @@ impl eframe::App for LoglineApp {
                                 ExplorerAction::ClearRecentFiles => {
                                     self.explorer_panel.local_files.clear();
                                     self.config.recent_files.clear();
-                                    if let Err(e) = self.config.save() {
-                                        self.status_bar.set_message(
-                                            format!("{}: {}", t::config_save_failed(), e),
-                                            StatusLevel::Error,
-                                        );
-                                    } else {
+                                    if self.save_config() {
                                         self.status_bar.set_message(
                                             t::recent_files_cleared().to_string(),
                                             StatusLevel::Info,
@@ -2204,7 +3721,9 @@ impl eframe::App for LoglineApp {
                                     }
                                 }
                                 ExplorerAction::OpenAndroidLogcat(device) => {
-                                    if let Err(e) = self.open_android_logcat(device.clone()) {
+                                    let clear_logcat = self.source_picker_dialog.clear_logcat;
+                                    let app_filter = self.source_picker_dialog.app_filter(&device.serial);
+                                    if let Err(e) = self.open_android_logcat(device.clone(), clear_logcat, &app_filter) {
                                         self.status_bar.set_message(
                                             format!("Failed to open Android logcat: {}", e),
                                             StatusLevel::Error,
@@ -2214,39 +3733,67 @@ impl eframe::App for LoglineApp {
                                 ExplorerAction::DisconnectAndroidDevice(serial) => {
                                     self.disconnect_android_device(serial);
                                 }
+                                ExplorerAction::SetAlias(path, alias) => {
+                                    self.set_source_alias(path, alias);
+                                }
                                 ExplorerAction::None => {}
                             }
                         }
                         ActivityView::Search => {
-                            // Global search view
-                            if let Some(state) = self.tab_manager.get_active_state() {
-                                match self.global_search_panel.show(ui, &state.buffer) {
-                                    GlobalSearchAction::JumpToLine(buffer_index) => {
-                                        // Jump to the line in main view (need mut access)
-                                        if let Some(state) = self.tab_manager.get_active_state_mut() {
-                                            state.main_view.scroll_to_line(buffer_index);
-                                            state.main_view.set_selection(buffer_index, buffer_index);
-                                        }
+                            // Global search view (active tab, or all tabs in order)
+                            let sources: Vec<SearchSource> = self
+                                .tab_manager
+                                .tab_bar
+                                .tabs
+                                .iter()
+                                .filter_map(|tab| {
+                                    let state = self.tab_manager.states.get(&tab.id)?;
+                                    Some(SearchSource {
+                                        tab_id: tab.id,
+                                        name: tab.display_name(),
+                                        buffer: &state.buffer,
+                                    })
+                                })
+                                .collect();
+                            let active = self.tab_manager.tab_bar.active_tab;
+                            match self.global_search_panel.show(ui, &sources, active) {
+                                GlobalSearchAction::JumpToLine(tab_id, buffer_index) => {
+                                    // Switch to the owning tab, then jump to the line
+                                    if let Some(state) = self.tab_manager.states.get_mut(&tab_id) {
+                                        state.main_view.scroll_to_line(buffer_index);
+                                        state.main_view.set_selection(buffer_index, buffer_index);
+                                        self.tab_manager.tab_bar.active_tab = Some(tab_id);
                                     }
-                                    GlobalSearchAction::None => {}
                                 }
+                                GlobalSearchAction::None => {}
                             }
                         }
                         ActivityView::Filters => {
                             // Advanced filters view
                             if let Some(state) = self.tab_manager.get_active_state_mut() {
-                                if self.advanced_filters_panel.show(ui, &mut state.filter.filter) {
+                                let time_bounds = state.time_bounds();
+                                if self.advanced_filters_panel.show(
+                                    ui,
+                                    &mut state.filter.filter,
+                                    &self.config.filter_presets,
+                                    time_bounds,
+                                ) {
                                     // Filter changed, update the view
                                     state.filter.mark_dirty();
                                     state.update_filter();
                                 }
                             }
+                            if let Some(action) = self.advanced_filters_panel.take_preset_action() {
+                                self.handle_preset_action(action);
+                            }
                         }
                         ActivityView::Grok => {
                             // Update grok panel with current file info
                             if let Some(state) = self.tab_manager.get_active_state() {
                                 self.grok_panel.current_file_path = Some(state.path.clone());
                                 self.grok_panel.use_file_specific = state.grok_config.is_some();
+                                self.grok_panel.json_mode =
+                                    state.grok_parser.as_ref().is_some_and(|p| p.is_json_mode());
                             } else {
                                 self.grok_panel.current_file_path = None;
                             }
@@ -2256,33 +3803,35 @@ impl eframe::App for LoglineApp {
                                 GrokPanelAction::PatternChanged => {
                                     // Pattern changed, reset grok parse progress for all tabs
                                     for state in self.tab_manager.states.values_mut() {
-                                        state.grok_parse_progress = 0;
+                                        state.reset_grok_parse();
                                         // Clear existing grok fields
-                                        for entry in state.buffer.iter_mut() {
-                                            entry.clear_grok_fields();
-                                        }
+                                        state.buffer.update_all(LogEntry::clear_grok_fields);
                                     }
                                     // Save config
                                     self.grok_panel.save_to_config(&mut self.config.grok);
                                     self.config.grok.custom_patterns = self.grok_parser.export_custom_patterns();
-                                    if let Err(e) = self.config.save() {
-                                        tracing::error!("Failed to save config: {}", e);
-                                    }
+                                    self.save_config();
                                 }
                                 GrokPanelAction::ConfigChanged => {
                                     // Config changed, save
                                     self.grok_panel.save_to_config(&mut self.config.grok);
                                     self.config.grok.custom_patterns = self.grok_parser.export_custom_patterns();
-                                    if let Err(e) = self.config.save() {
-                                        tracing::error!("Failed to save config: {}", e);
+                                    // Tabs keep their own copy of the custom patterns (e.g. color rules)
+                                    for state in self.tab_manager.states.values_mut() {
+                                        if let Some(parser) = state.grok_parser.as_mut() {
+                                            parser.import_custom_patterns(self.config.grok.custom_patterns.clone());
+                                        }
                                     }
+                                    self.save_config();
                                 }
                                 GrokPanelAction::FilePatternChanged { path, config } => {
                                     tracing::info!("FilePatternChanged received for path: {:?}", path);
                                     tracing::info!("FilePatternChanged config: {:?}", config);
                                     
                                     // Save per-file grok config
-                                    self.config.set_file_grok_config(path.clone(), config.clone());
+                                    if self.tab_manager.persists_settings(&path) {
+                                        self.config.set_file_grok_config(path.clone(), config.clone());
+                                    }
                                     
                                     // Update the tab state with the parser that was configured in grok_panel
                                     let state_found = self.tab_manager.get_state_by_path_mut(&path).is_some();
@@ -2344,16 +3893,25 @@ impl eframe::App for LoglineApp {
                                         }
                                         
                                         state.grok_config = config;
-                                        state.grok_parse_progress = 0;
+                                        state.reset_grok_parse();
                                         // Clear existing grok fields to reparse
-                                        for entry in state.buffer.iter_mut() {
-                                            entry.clear_grok_fields();
-                                        }
+                                        state.buffer.update_all(LogEntry::clear_grok_fields);
                                     }
                                     
-                                    if let Err(e) = self.config.save() {
-                                        tracing::error!("Failed to save config: {}", e);
-                                    }
+                                    self.save_config();
+                                    self.remember_analysis_context();
+                                }
+                                GrokPanelAction::JsonModeChanged(enabled) => {
+                                    self.set_json_mode(enabled);
+                                }
+                                GrokPanelAction::AutoDetectPattern => {
+                                    self.auto_detect_grok_pattern();
+                                }
+                                GrokPanelAction::ApplyBuiltinPattern(pattern) => {
+                                    self.apply_builtin_grok_pattern(pattern);
+                                }
+                                GrokPanelAction::ExportCsv => {
+                                    self.export_parsed_csv();
                                 }
                                 GrokPanelAction::RequestSampleLines => {
                                     // Get sample lines from current tab
@@ -2361,7 +3919,7 @@ impl eframe::App for LoglineApp {
                                         let sample_lines: Vec<String> = state.buffer
                                             .iter()
                                             .take(20)
-                                            .map(|e| e.content.clone())
+                                            .map(|e| e.content.to_string())
                                             .collect();
                                         self.grok_panel.set_sample_lines(sample_lines);
                                     }
@@ -2371,8 +3929,13 @@ impl eframe::App for LoglineApp {
                         }
                         ActivityView::Bookmarks => {
                             // Bookmarks view
+                            let mut export_format = None;
                             if let Some(state) = self.tab_manager.get_active_state_mut() {
-                                match self.bookmarks_panel.show(ui, &state.buffer) {
+                                let notes = self
+                                    .bookmarks_store
+                                    .get_bookmarks(&state.path)
+                                    .map(|bookmarks| &bookmarks.notes);
+                                match self.bookmarks_panel.show(ui, &state.buffer, notes) {
                                     BookmarkAction::JumpToLine(line_number) => {
                                         // Find the buffer index for this line number
                                         let index = state
@@ -2440,9 +4003,34 @@ impl eframe::App for LoglineApp {
                                             self.tab_manager.save_bookmarks(tab_id, &mut self.bookmarks_store);
                                         }
                                     }
+                                    BookmarkAction::EditBookmark {
+                                        line,
+                                        indices,
+                                        note,
+                                        color,
+                                    } => {
+                                        for index in indices {
+                                            state.buffer.update(index, |e| e.bookmark_color = color);
+                                        }
+                                        let path = state.path.clone();
+                                        // Make sure the store knows the line is bookmarked
+                                        if let Some(tab_id) = self.tab_manager.tab_bar.active_tab {
+                                            self.tab_manager.save_bookmarks(tab_id, &mut self.bookmarks_store);
+                                        }
+                                        self.bookmarks_store.set_note(&path, line, note);
+                                        if let Err(e) = self.bookmarks_store.save() {
+                                            tracing::error!("Failed to save bookmarks: {}", e);
+                                        }
+                                    }
+                                    BookmarkAction::ExportReport(format) => {
+                                        export_format = Some(format);
+                                    }
                                     BookmarkAction::None => {}
                                 }
                             }
+                            if let Some(format) = export_format {
+                                self.export_bookmark_report(format);
+                            }
                         }
                         ActivityView::Settings => {
                             match self.settings_panel.show(ui) {
@@ -2459,22 +4047,70 @@ impl eframe::App for LoglineApp {
                                         ThemeMode::Light
                                     });
                                     action = Some(AppAction::UpdateTheme);
-                                    let _ = self.config.save();
+                                    self.save_config();
                                 }
                                 SettingsAction::PortChanged => {
                                     // Save the port change to config
                                     self.config.remote_server.port = self.settings_panel.port();
-                                    let _ = self.config.save();
+                                    self.save_config();
                                     // Port change will take effect on next server restart
                                     self.status_bar.set_message(
                                         t::port_change_requires_restart(),
                                         StatusLevel::Info,
                                     );
                                 }
+                                SettingsAction::ReuseAgentStreamsChanged => {
+                                    self.config.remote_server.reuse_existing_streams =
+                                        self.settings_panel.reuse_agent_streams;
+                                    self.save_config();
+                                    let reuse = self.settings_panel.reuse_agent_streams;
+                                    self.remote_server.set_reuse_existing(reuse);
+                                    self.status_bar.set_message(
+                                        t::server_change_requires_restart(),
+                                        StatusLevel::Info,
+                                    );
+                                }
+                                SettingsAction::CacheLimitChanged => {
+                                    self.config.remote_server.max_cache_mb =
+                                        self.settings_panel.max_cache_mb;
+                                    self.save_config();
+                                    let max_cache_bytes = self.config.remote_server.max_cache_bytes();
+                                    self.remote_server.set_max_cache_bytes(max_cache_bytes);
+                                    self.status_bar.set_message(
+                                        t::cache_limit_requires_restart(),
+                                        StatusLevel::Info,
+                                    );
+                                }
+                                SettingsAction::AuthTokenChanged => {
+                                    let token = self.settings_panel.auth_token();
+                                    if token != self.config.remote_server.auth_token {
+                                        self.config.remote_server.auth_token = token.clone();
+                                        self.save_config();
+                                        self.remote_server.set_auth_token(token);
+
+                                        // Restart so the new token applies right away
+                                        let restarted = if self.remote_server.is_running() {
+                                            self.remote_server.stop();
+                                            self.remote_server.start()
+                                        } else {
+                                            Ok(())
+                                        };
+                                        match restarted {
+                                            Ok(()) => self.status_bar.set_message(
+                                                t::auth_token_updated(),
+                                                StatusLevel::Success,
+                                            ),
+                                            Err(e) => {
+                                                let msg = format!("{}: {}", t::server_start_failed(), e);
+                                                self.status_bar.set_message(msg, StatusLevel::Error);
+                                            }
+                                        }
+                                    }
+                                }
                                 SettingsAction::RemoteServiceEnabledChanged => {
                                     self.config.remote_server.enabled =
                                         self.settings_panel.enable_remote_service;
-                                    let _ = self.config.save();
+                                    self.save_config();
 
                                     // Start or stop remote server based on enabled state
                                     if self.settings_panel.enable_remote_service {
@@ -2497,7 +4133,7 @@ impl eframe::App for LoglineApp {
                                                     );
                                                     self.config.remote_server.enabled = false;
                                                     self.settings_panel.enable_remote_service = false;
-                                                    let _ = self.config.save();
+                                                    self.save_config();
                                                 }
                                             }
                                         }
@@ -2512,17 +4148,20 @@ impl eframe::App for LoglineApp {
                                 SettingsAction::LanguageChanged(lang) => {
                                     set_language(lang);
                                     self.config.language = lang;
-                                    let _ = self.config.save();
+                                    self.save_config();
                                 }
                                 SettingsAction::DisplayConfigChanged => {
                                     self.display_config =
                                         self.settings_panel.display_config.clone();
                                     self.config.display = self.display_config.clone();
-                                    let _ = self.config.save();
+                                    self.tab_manager.set_timestamp_fallback(
+                                        self.display_config.timestamp_fallback,
+                                    );
+                                    self.save_config();
                                 }
                                 SettingsAction::McpEnabledChanged(enabled) => {
                                     self.config.mcp.enabled = enabled;
-                                    let _ = self.config.save();
+                                    self.save_config();
 
                                     if enabled {
                                         // Start MCP server
@@ -2534,20 +4173,103 @@ impl eframe::App for LoglineApp {
                                 }
                                 SettingsAction::McpPortChanged => {
                                     self.config.mcp.port = self.settings_panel.mcp_port_number();
-                                    let _ = self.config.save();
+                                    self.save_config();
                                     self.status_bar.set_message(
                                         t::mcp_port_change_requires_restart(),
                                         StatusLevel::Info,
                                     );
                                 }
+                                SettingsAction::RedactionConfigChanged => {
+                                    self.config.redaction = self.settings_panel.redaction_config();
+                                    self.redactor = Redactor::new(&self.config.redaction);
+                                    self.save_config();
+                                }
                                 SettingsAction::CloseButtonBehaviorChanged(behavior) => {
                                     self.config.window.close_button_behavior = behavior;
-                                    let _ = self.config.save();
+                                    self.save_config();
+                                    self.status_bar.set_message(
+                                        t::settings_saved(),
+                                        StatusLevel::Info,
+                                    );
+                                }
+                                SettingsAction::PresentationModeChanged(enabled) => {
+                                    // Session only, nothing is written to the config
+                                    presentation::set_presentation_mode(enabled);
+                                }
+                                SettingsAction::AccessibilityChanged => {
+                                    self.config.accessibility =
+                                        self.settings_panel.accessibility.clone();
+                                    theme::apply_zoom(ui.ctx(), &self.config.accessibility);
+                                    action = Some(AppAction::UpdateTheme);
+                                    self.save_config();
+                                }
+                                SettingsAction::SpaceKeyActionChanged(space_action) => {
+                                    self.config.keyboard.space_key_action = space_action;
+                                    self.save_config();
                                     self.status_bar.set_message(
                                         t::settings_saved(),
                                         StatusLevel::Info,
                                     );
                                 }
+                                SettingsAction::GrokEagerParseChanged => {
+                                    self.config.grok.eager_parse_max_lines =
+                                        self.settings_panel.grok_eager_max_lines;
+                                    self.save_config();
+                                }
+                                SettingsAction::TrimStrategyChanged => {
+                                    self.config.buffer.trim_strategy =
+                                        self.settings_panel.trim_strategy;
+                                    self.config.buffer.trim_head_lines =
+                                        self.settings_panel.trim_head_lines;
+                                    self.tab_manager.set_trim_strategy(
+                                        self.config.buffer.trim_strategy,
+                                        self.config.buffer.trim_head_lines,
+                                    );
+                                    self.save_config();
+                                }
+                                SettingsAction::RepaintLevelChanged => {
+                                    self.config.buffer.repaint_min_level =
+                                        self.settings_panel.repaint_min_level;
+                                    self.config.buffer.reduced_repaint_interval_ms =
+                                        self.settings_panel.reduced_repaint_interval_ms;
+                                    self.tab_manager
+                                        .set_repaint_min_level(self.config.buffer.repaint_min_level);
+                                    self.save_config();
+                                }
+                                SettingsAction::FolderDropChanged => {
+                                    self.config.folder_drop = self.settings_panel.folder_drop.clone();
+                                    self.save_config();
+                                }
+                                SettingsAction::CorrelationChanged => {
+                                    self.config.correlation = self.settings_panel.correlation.clone();
+                                    self.save_config();
+                                }
+                                SettingsAction::StackTracesChanged => {
+                                    let stack_traces = self.settings_panel.stack_traces.clone();
+                                    if stack_traces != self.config.stack_traces {
+                                        // Invalid patterns are never saved
+                                        match stack_traces.continuation_regex() {
+                                            Ok(pattern) => {
+                                                self.tab_manager.set_continuation_pattern(pattern);
+                                                self.config.stack_traces = stack_traces;
+                                                self.save_config();
+                                            }
+                                            Err(e) => self.status_bar.set_message(
+                                                format!(
+                                                    "{}: {}",
+                                                    t::invalid_continuation_pattern(),
+                                                    e
+                                                ),
+                                                StatusLevel::Error,
+                                            ),
+                                        }
+                                    }
+                                }
+                                SettingsAction::AutosaveChanged => {
+                                    self.config.autosave = self.settings_panel.autosave.clone();
+                                    self.autosave_timer.set_interval(self.config.autosave.interval());
+                                    self.save_config();
+                                }
                                 _ => {}
                             }
                         }
@@ -2611,8 +4333,36 @@ impl eframe::App for LoglineApp {
                 let mut left_context_action: Option<ContextMenuAction> = None;
                 let mut right_context_action: Option<ContextMenuAction> = None;
 
+                // Diff mode aligns both panes instead of rendering two log views
+                let mut pane_tabs = (
+                    self.tab_manager.split_view.get_pane_tab(crate::ui::split_view::SplitPane::Left),
+                    self.tab_manager.split_view.get_pane_tab(crate::ui::split_view::SplitPane::Right),
+                );
+                if self.tab_manager.split_view.is_diff() {
+                    let states = &self.tab_manager.states;
+                    let side = |tab: Option<crate::ui::tab_bar::TabId>| {
+                        let tab = tab?;
+                        states.get(&tab).map(|state| crate::ui::split_view::DiffSide {
+                            tab,
+                            buffer: &state.buffer,
+                            filtered: state.filter_active.then_some(state.filtered_indices.as_slice()),
+                        })
+                    };
+                    if let (Some(left), Some(right), Some(right_rect)) = (side(pane_tabs.0), side(pane_tabs.1), right_rect_opt) {
+                        self.tab_manager.split_view.show_diff(
+                            ui,
+                            left_rect,
+                            right_rect,
+                            &left,
+                            &right,
+                            &self.display_config,
+                        );
+                        pane_tabs = (None, None);
+                    }
+                }
+
                 // Render left pane with unique ID scope
-                if let Some(left_id) = self.tab_manager.split_view.get_pane_tab(crate::ui::split_view::SplitPane::Left) {
+                if let Some(left_id) = pane_tabs.0 {
                     if let Some(state) = self.tab_manager.states.get_mut(&left_id) {
                         let filtered = if state.filter_active {
                             Some(state.filtered_indices.as_slice())
@@ -2626,6 +4376,15 @@ impl eframe::App for LoglineApp {
                         } else {
                             None
                         };
+                        let color_rules = state
+                            .grok_parser
+                            .as_ref()
+                            .map_or(&[][..], |p| p.active_color_rules());
+                        let column_widths = state
+                            .grok_parser
+                            .as_ref()
+                            .and_then(|p| p.active_pattern_name())
+                            .and_then(|name| self.config.grok.column_widths.get(name));
 
                         ui.scope_builder(egui::UiBuilder::new().max_rect(left_rect).id_salt("left_pane"), |ui| {
                             let (_, context_action) = state.main_view.show(
@@ -2635,6 +4394,8 @@ impl eframe::App for LoglineApp {
                                 &state.filter.search,
                                 &self.display_config,
                                 grok_pattern.as_ref(),
+                                color_rules,
+                                column_widths,
                             );
                             left_context_action = context_action;
                         });
@@ -2643,7 +4404,7 @@ impl eframe::App for LoglineApp {
 
                 // Render right pane with unique ID scope
                 if let Some(right_rect) = right_rect_opt {
-                    if let Some(right_id) = self.tab_manager.split_view.get_pane_tab(crate::ui::split_view::SplitPane::Right) {
+                    if let Some(right_id) = pane_tabs.1 {
                         if let Some(state) = self.tab_manager.states.get_mut(&right_id) {
                             let filtered = if state.filter_active {
                                 Some(state.filtered_indices.as_slice())
@@ -2657,6 +4418,15 @@ impl eframe::App for LoglineApp {
                             } else {
                                 None
                             };
+                            let color_rules = state
+                                .grok_parser
+                                .as_ref()
+                                .map_or(&[][..], |p| p.active_color_rules());
+                            let column_widths = state
+                                .grok_parser
+                                .as_ref()
+                                .and_then(|p| p.active_pattern_name())
+                                .and_then(|name| self.config.grok.column_widths.get(name));
 
                             ui.scope_builder(egui::UiBuilder::new().max_rect(right_rect).id_salt("right_pane"), |ui| {
                                 let (_, context_action) = state.main_view.show(
@@ -2666,6 +4436,8 @@ impl eframe::App for LoglineApp {
                                     &state.filter.search,
                                     &self.display_config,
                                     grok_pattern.as_ref(),
+                                    color_rules,
+                                    column_widths,
                                 );
                                 right_context_action = context_action;
                             });
@@ -2683,6 +4455,22 @@ impl eframe::App for LoglineApp {
             } else {
                 // Single pane mode
                 if let Some(state) = self.tab_manager.get_active_state_mut() {
+                    // Dragging across the timeline filters to a time range
+                    if self.display_config.show_timeline {
+                        let selected = state.filter.filter.time_range;
+                        let action = state.timeline.show(ui, &state.buffer, selected.as_ref());
+                        let time_range = match action {
+                            TimelineAction::SelectRange(range) => Some(Some(range)),
+                            TimelineAction::ClearRange => Some(None),
+                            TimelineAction::None => None,
+                        };
+                        if let Some(time_range) = time_range {
+                            state.filter.filter.time_range = time_range;
+                            state.filter.mark_dirty();
+                            state.update_filter();
+                        }
+                    }
+
                     let filtered = if state.filter_active {
                         Some(state.filtered_indices.as_slice())
                     } else {
@@ -2695,6 +4483,15 @@ impl eframe::App for LoglineApp {
                     } else {
                         None
                     };
+                    let color_rules = state
+                        .grok_parser
+                        .as_ref()
+                        .map_or(&[][..], |p| p.active_color_rules());
+                    let column_widths = state
+                        .grok_parser
+                        .as_ref()
+                        .and_then(|p| p.active_pattern_name())
+                        .and_then(|name| self.config.grok.column_widths.get(name));
 
                     let (_, context_action) = state.main_view.show(
                         ui,
@@ -2703,6 +4500,8 @@ impl eframe::App for LoglineApp {
                         &state.filter.search,
                         &self.display_config,
                         grok_pattern.as_ref(),
+                        color_rules,
+                        column_widths,
                     );
 
                     // Handle context menu actions
@@ -2757,12 +4556,16 @@ impl eframe::App for LoglineApp {
                                                 // Shortcuts list with consistent spacing
                                                 let shortcuts = [
                                                     t::shortcut_open_file(),
+                                                    t::shortcut_open_recent(),
                                                     t::shortcut_find(),
                                                     t::shortcut_goto_line(),
+                                                    t::shortcut_goto_time(),
                                                     t::shortcut_reload(),
                                                     t::shortcut_clear(),
                                                     t::shortcut_bookmark(),
                                                     t::shortcut_auto_scroll(),
+                                                    t::shortcut_next_problem(),
+                                                    t::shortcut_next_bookmark(),
                                                 ];
                                                 
                                                 for shortcut in shortcuts {
@@ -2830,6 +4633,8 @@ impl eframe::App for LoglineApp {
                                                 ui.add_space(12.0);
                                                 ui.label(RichText::new(t::agent_server_address()).weak().size(13.0));
                                                 ui.add_space(6.0);
+                                                ui.label(RichText::new(t::agent_tls_flag()).weak().size(13.0));
+                                                ui.add_space(6.0);
                                                 ui.label(RichText::new(t::agent_more_info()).weak().size(13.0));
                                                 ui.add_space(4.0);
                                             });
@@ -2844,11 +4649,89 @@ impl eframe::App for LoglineApp {
             }
         });
 
+        // Open the detail pane when a line was clicked (consume the flag on every pane)
+        let mut line_clicked = false;
+        for state in self.tab_manager.states.values_mut() {
+            line_clicked |= state.main_view.take_line_clicked();
+        }
+        if line_clicked
+            && self.display_config.click_action == ClickAction::OpenDetail
+            && !self.line_detail_panel.visible
+        {
+            self.line_detail_panel.visible = true;
+            ctx.request_repaint();
+        }
+
+        // Fold triangles: collapse or expand a stack trace block
+        for state in self.tab_manager.states.values_mut() {
+            if let Some(line_number) = state.main_view.take_fold_toggle() {
+                state.filter.toggle_block(line_number);
+                state.update_filter();
+            }
+        }
+
+        // Column view header: sort the view, or resize and remember a column
+        let mut save_column_widths = false;
+        for state in self.tab_manager.states.values_mut() {
+            match state.main_view.take_column_action() {
+                Some(ColumnAction::Sort(field)) => {
+                    let sort = FieldSort::next(state.filter.filter.sort_by.as_ref(), &field);
+                    state.filter.filter.sort_by = sort;
+                    state.filter.mark_dirty();
+                    state.update_filter();
+                }
+                Some(ColumnAction::Resize { field, width, done }) => {
+                    let pattern = state.grok_parser.as_ref().and_then(|p| p.active_pattern_name());
+                    let Some(pattern) = pattern else {
+                        continue;
+                    };
+                    self.config
+                        .grok
+                        .column_widths
+                        .entry(pattern.to_string())
+                        .or_default()
+                        .insert(field, width);
+                    save_column_widths |= done;
+                }
+                None => {}
+            }
+        }
+        if save_column_widths {
+            self.save_config();
+        }
+
         // Go to line dialog
         if self.goto_dialog.open {
             self.show_goto_dialog(ctx);
         }
 
+        // Go to time dialog
+        if self.goto_time_dialog.open {
+            self.show_goto_time_dialog(ctx);
+        }
+
+        // Open Recent overlay
+        if let Some(path) = self.open_recent_dialog.show(
+            ctx,
+            &self.config.recent_files,
+            &self.config.source_aliases,
+        ) {
+            if let Err(e) = self.open_file(path, None) {
+                self.status_bar.set_message(
+                    format!("{}: {}", t::file_open_failed(), e),
+                    StatusLevel::Error,
+                );
+            }
+        }
+
+        // Snapshot diff window
+        self.diff_view.show(ctx);
+
+        // Confirmation for a dropped folder with many log files
+        if let Some(files) = self.folder_drop_dialog.show(ctx) {
+            self.open_files(files);
+        }
+
         // Source picker dialog
         self.source_picker_dialog.update_android_devices(self.explorer_panel.android_devices.clone());
         match self.source_picker_dialog.show(ctx) {
@@ -2859,7 +4742,9 @@ impl eframe::App for LoglineApp {
                 }
             }
             SourcePickerAction::OpenAndroidDevice(device) => {
-                if let Err(e) = self.open_android_logcat(device.clone()) {
+                let clear_logcat = self.source_picker_dialog.clear_logcat;
+                let app_filter = self.source_picker_dialog.app_filter(&device.serial);
+                if let Err(e) = self.open_android_logcat(device.clone(), clear_logcat, &app_filter) {
                     self.status_bar.set_message(
                         format!("Failed to open Android logcat: {}", e),
                         StatusLevel::Error,
@@ -2889,6 +4774,7 @@ impl eframe::App for LoglineApp {
                     // Show the source picker dialog
                     self.source_picker_dialog.show_dialog();
                 }
+                AppAction::MergeSources => self.merge_sources(),
                 AppAction::OpenSourcePickerAndroid => {
                     // Open source picker with Android tab active
                     self.source_picker_dialog.set_recent_files(self.config.recent_files.clone());
@@ -2896,24 +4782,25 @@ impl eframe::App for LoglineApp {
                     self.source_picker_dialog.show_dialog_tab(SourceTab::AndroidDevices);
                 }
                 AppAction::UpdateTheme => {
-                    let visuals = match self.config.theme {
-                        Theme::Dark => egui::Visuals::dark(),
-                        Theme::Light => egui::Visuals::light(),
-                    };
-                    ctx.set_visuals(visuals);
+                    theme::apply(ctx, self.config.theme, &self.config.accessibility);
                 }
             }
         }
 
         // Request repaint for real-time updates only when actively viewing a file
         // Reduced repaint frequency when remote server is running but no file is open
+        let arrived_level = self.tab_manager.take_arrived_level();
         if self.window_hidden {
             // Window is hidden, use low-frequency updates to save resources
             // but keep responsive enough to handle tray events
             ctx.request_repaint_after(Duration::from_millis(100));
-        } else if self.tab_manager.any_watching() {
-            // Actively watching a file, need frequent updates
+        } else if self.tab_manager.any_loading() || self.tab_manager.any_grok_parsing() {
+            // Waiting for a file's initial read or a background grok parse
             ctx.request_repaint_after(Duration::from_millis(50));
+        } else if self.tab_manager.any_watching() {
+            // Actively watching a file, need frequent updates (in reduced-update
+            // mode only lines at the configured level repaint right away)
+            ctx.request_repaint_after(self.config.buffer.tailing_repaint_delay(arrived_level));
         } else if self.remote_server.is_running() {
             // Server is running but no active file, less frequent updates
             ctx.request_repaint_after(Duration::from_millis(200));
@@ -2928,7 +4815,8 @@ impl eframe::App for LoglineApp {
         tracing::info!("Application exit started");
         
         // Save configuration
-        let _ = self.config.save();
+        self.config.split_layout = self.tab_manager.split_layout();
+        self.save_config();
 
         // Stop MCP server first (before shutting down tokio runtime)
         if let Some(mut server) = self.mcp_server.take() {
@@ -2943,6 +4831,8 @@ impl eframe::App for LoglineApp {
         // Close all tabs (this will stop file watchers)
         tracing::info!("Closing all tabs");
         self.tab_manager.handle_action(TabBarAction::CloseAllTabs, &mut self.bookmarks_store);
+        // Removes the stdin cache file
+        self.stdin_reader = None;
 
         // Explicitly shutdown tokio runtime with timeout
         if let Some(runtime) = self.tokio_runtime.take() {
@@ -3012,6 +4902,75 @@ impl LoglineApp {
                 }
             });
     }
+
+    /// Show go-to-time dialog
+    fn show_goto_time_dialog(&mut self, ctx: &egui::Context) {
+        egui::Window::new(t::go_to_time())
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(t::time_query_label());
+                    let response = ui.add(
+                        egui::TextEdit::singleline(&mut self.goto_time_dialog.input)
+                            .hint_text(t::time_query_hint()),
+                    );
+
+                    if self.goto_time_dialog.focus_input {
+                        response.request_focus();
+                        self.goto_time_dialog.focus_input = false;
+                    }
+
+                    if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                        self.goto_time_dialog.submit = true;
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    if ui.button(t::go()).clicked() {
+                        self.goto_time_dialog.submit = true;
+                    }
+                    if ui.button(t::cancel()).clicked() {
+                        self.goto_time_dialog.open = false;
+                    }
+                });
+            });
+
+        if self.goto_time_dialog.submit {
+            self.goto_time_dialog.submit = false;
+            let input = self.goto_time_dialog.input.clone();
+            self.goto_time(&input);
+        }
+    }
+
+    /// Scroll the active tab to the timestamped line nearest `input`
+    ///
+    /// The dialog stays open on an unrecognized time so it can be corrected.
+    fn goto_time(&mut self, input: &str) {
+        let Some(state) = self.tab_manager.get_active_state_mut() else {
+            return;
+        };
+        let Some(first) = state.buffer.first_timestamp() else {
+            self.status_bar
+                .set_message(t::no_timestamps_found(), StatusLevel::Warning);
+            return;
+        };
+        // A bare time of day is taken on the day the log starts
+        let Some(target) = parse_time_query(input, first.date()) else {
+            self.status_bar.set_message(
+                format!("{}: {}", t::invalid_time_query(), input.trim()),
+                StatusLevel::Warning,
+            );
+            return;
+        };
+
+        if let Some(idx) = state.buffer.nearest_timestamp(target) {
+            state.main_view.scroll_to_line(idx);
+        }
+        self.goto_time_dialog.open = false;
+        self.goto_time_dialog.input.clear();
+    }
 }
 
 /// Application actions
@@ -3019,6 +4978,7 @@ impl LoglineApp {
 enum AppAction {
     OpenSourcePicker,
     OpenSourcePickerAndroid,
+    MergeSources,
     UpdateTheme,
 }
 
@@ -0,0 +1,270 @@
+//! Periodic autosave of bookmarks and session state
+//!
+//! The app serializes a snapshot on a timer (and when the window is hidden)
+//! and hands it to a background writer. The writer waits for snapshots to
+//! settle before touching the disk, so rapid changes are coalesced into a
+//! single write of the latest one and the UI never blocks on file I/O.
+//!
+//! Explicit saves of the same files go through the same writer, so a
+//! pending autosave can never land on top of a newer save.
+
+use anyhow::{anyhow, Context, Result};
+use crossbeam_channel::{bounded, unbounded, Receiver, RecvTimeoutError, Sender};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// How long the writer waits for a newer snapshot before writing
+pub const AUTOSAVE_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Files of one snapshot: path and serialized contents
+pub type Snapshot = Vec<(PathBuf, String)>;
+
+/// Interval timer deciding when the next autosave is due
+#[derive(Debug, Clone)]
+pub struct AutosaveTimer {
+    interval: Duration,
+    last_save: Instant,
+}
+
+impl AutosaveTimer {
+    /// Create a timer whose first save is due one interval from `now`
+    pub fn new(interval: Duration, now: Instant) -> Self {
+        Self {
+            interval,
+            last_save: now,
+        }
+    }
+
+    /// Change the interval (keeps the time of the last save)
+    pub fn set_interval(&mut self, interval: Duration) {
+        self.interval = interval;
+    }
+
+    /// Time left until the next save is due
+    pub fn remaining(&self, now: Instant) -> Duration {
+        self.interval
+            .saturating_sub(now.saturating_duration_since(self.last_save))
+    }
+
+    /// Whether a save is due, restarting the interval if so
+    pub fn tick(&mut self, now: Instant) -> bool {
+        if self.remaining(now).is_zero() {
+            self.last_save = now;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Work for the writer thread
+enum WriteRequest {
+    /// Autosave snapshot, written once no newer one arrives for a while
+    Debounced(Snapshot),
+    /// Files written right away, with the result sent back. A pending
+    /// autosave's copies of the same files are older and are dropped.
+    Now(Snapshot, Sender<Result<()>>),
+}
+
+/// Background writer for autosave snapshots and explicit saves
+pub struct AutosaveWriter {
+    /// Requests for the writer thread
+    tx: Sender<WriteRequest>,
+    /// Result of each completed write
+    results: Receiver<Result<()>>,
+    /// Last snapshot handed to the writer
+    last_submitted: Snapshot,
+}
+
+impl AutosaveWriter {
+    /// Start the writer thread
+    pub fn spawn(debounce: Duration) -> Self {
+        let (tx, rx) = unbounded::<WriteRequest>();
+        let (result_tx, results) = unbounded();
+
+        std::thread::spawn(move || {
+            // Autosave waiting for snapshots to settle
+            let mut pending: Option<Snapshot> = None;
+            loop {
+                let request = match &pending {
+                    // Coalesce snapshots submitted in quick succession
+                    Some(_) => rx.recv_timeout(debounce),
+                    None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+                };
+                match request {
+                    Ok(WriteRequest::Debounced(snapshot)) => pending = Some(snapshot),
+                    Ok(WriteRequest::Now(files, reply)) => {
+                        if let Some(snapshot) = pending.as_mut() {
+                            snapshot.retain(|(path, _)| files.iter().all(|(p, _)| p != path));
+                        }
+                        let _ = reply.send(write_snapshot(&files));
+                    }
+                    Err(timeout) => {
+                        if let Some(snapshot) = pending.take().filter(|s| !s.is_empty()) {
+                            if result_tx.send(write_snapshot(&snapshot)).is_err() {
+                                break;
+                            }
+                        }
+                        if timeout == RecvTimeoutError::Disconnected {
+                            break;
+                        }
+                    }
+                }
+            }
+        });
+
+        Self {
+            tx,
+            results,
+            last_submitted: Vec::new(),
+        }
+    }
+
+    /// Queue a snapshot for writing. Returns false (and writes nothing) if it
+    /// is identical to the previous one.
+    pub fn submit(&mut self, snapshot: Snapshot) -> bool {
+        if snapshot == self.last_submitted {
+            return false;
+        }
+        self.last_submitted = snapshot.clone();
+        self.tx.send(WriteRequest::Debounced(snapshot)).is_ok()
+    }
+
+    /// Write files now, after any write in progress and ahead of a pending
+    /// autosave. Blocks until they are written.
+    pub fn write_now(&mut self, files: Snapshot) -> Result<()> {
+        // Later autosaves compare against what is now on disk
+        for (path, contents) in &files {
+            if let Some(entry) = self.last_submitted.iter_mut().find(|(p, _)| p == path) {
+                entry.1 = contents.clone();
+            }
+        }
+        let (reply_tx, reply) = bounded(1);
+        self.tx
+            .send(WriteRequest::Now(files, reply_tx))
+            .map_err(|_| anyhow!("Autosave writer stopped"))?;
+        reply
+            .recv()
+            .map_err(|_| anyhow!("Autosave writer stopped"))?
+    }
+
+    /// Results of writes completed since the last call
+    pub fn poll_results(&self) -> Vec<Result<()>> {
+        self.results.try_iter().collect()
+    }
+}
+
+/// Write every file of a snapshot. Each file is written to a temporary
+/// sibling and renamed into place, so a crash mid-write never leaves a
+/// half-written file behind.
+fn write_snapshot(snapshot: &Snapshot) -> Result<()> {
+    for (path, contents) in snapshot {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let mut temp_name = path.file_name().unwrap_or_default().to_os_string();
+        temp_name.push(".autosave");
+        let temp_path = path.with_file_name(temp_name);
+        std::fs::write(&temp_path, contents)
+            .with_context(|| format!("Failed to write {}", temp_path.display()))?;
+        std::fs::rename(&temp_path, path)
+            .with_context(|| format!("Failed to replace {}", path.display()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Wait for the writer to report `count` results
+    fn wait_results(writer: &AutosaveWriter, count: usize) -> Vec<Result<()>> {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut results = Vec::new();
+        while results.len() < count && Instant::now() < deadline {
+            results.extend(writer.poll_results());
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        results
+    }
+
+    #[test]
+    fn test_timer_triggers_save_each_interval() {
+        let start = Instant::now();
+        let mut timer = AutosaveTimer::new(Duration::from_secs(30), start);
+
+        assert!(!timer.tick(start + Duration::from_secs(10)));
+        assert_eq!(
+            timer.remaining(start + Duration::from_secs(10)),
+            Duration::from_secs(20)
+        );
+        assert!(timer.tick(start + Duration::from_secs(30)));
+        // The interval restarts from the save
+        assert!(!timer.tick(start + Duration::from_secs(45)));
+        assert!(timer.tick(start + Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn test_timer_save_writes_snapshot_in_background() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session").join("bookmarks.toml");
+        let mut writer = AutosaveWriter::spawn(Duration::from_millis(20));
+        let start = Instant::now();
+        let mut timer = AutosaveTimer::new(Duration::from_secs(30), start);
+
+        if timer.tick(start + Duration::from_secs(30)) {
+            assert!(writer.submit(vec![(path.clone(), "lines = [3]".to_string())]));
+        }
+        let results = wait_results(&writer, 1);
+        assert_eq!(results.len(), 1);
+        assert!(results[0].is_ok());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "lines = [3]");
+
+        // An unchanged snapshot isn't written again
+        assert!(!writer.submit(vec![(path, "lines = [3]".to_string())]));
+    }
+
+    #[test]
+    fn test_rapid_changes_are_coalesced() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let mut writer = AutosaveWriter::spawn(Duration::from_millis(200));
+
+        for i in 1..=5 {
+            assert!(writer.submit(vec![(path.clone(), format!("revision = {}", i))]));
+        }
+        let results = wait_results(&writer, 1);
+        assert_eq!(results.len(), 1);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "revision = 5");
+
+        // Nothing else is pending
+        std::thread::sleep(Duration::from_millis(400));
+        assert!(writer.poll_results().is_empty());
+    }
+
+    #[test]
+    fn test_pending_autosave_never_overwrites_a_newer_save() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("config.toml");
+        let bookmarks = dir.path().join("bookmarks.toml");
+        let mut writer = AutosaveWriter::spawn(Duration::from_millis(200));
+
+        assert!(writer.submit(vec![
+            (config.clone(), "theme = \"dark\"".to_string()),
+            (bookmarks.clone(), "lines = [3]".to_string()),
+        ]));
+        // Saved while the autosave is still settling
+        let saved = "theme = \"light\"";
+        writer
+            .write_now(vec![(config.clone(), saved.to_string())])
+            .unwrap();
+        assert_eq!(std::fs::read_to_string(&config).unwrap(), saved);
+
+        // The autosave still writes its other files, but not its older config
+        let results = wait_results(&writer, 1);
+        assert_eq!(results.len(), 1);
+        assert_eq!(std::fs::read_to_string(&bookmarks).unwrap(), "lines = [3]");
+        assert_eq!(std::fs::read_to_string(&config).unwrap(), saved);
+    }
+}
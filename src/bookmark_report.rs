@@ -0,0 +1,299 @@
+//! Annotated bookmark reports
+//!
+//! Builds a human-readable report of bookmarked lines, grouped by file. Each
+//! bookmark lists its note, level, timestamp and a few lines of context.
+//! Reports render as Markdown or as a standalone HTML page.
+
+use crate::bookmarks::BookmarksStore;
+use crate::log_buffer::LogBuffer;
+use crate::ui::line_detail_panel::LineDetail;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+/// Output format of a bookmark report
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Markdown,
+    Html,
+}
+
+impl ReportFormat {
+    /// File extension for the format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ReportFormat::Markdown => "md",
+            ReportFormat::Html => "html",
+        }
+    }
+
+    /// Filter name for save dialogs
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ReportFormat::Markdown => "Markdown",
+            ReportFormat::Html => "HTML",
+        }
+    }
+}
+
+/// A bookmarked line with its note
+#[derive(Debug, Clone)]
+pub struct ReportBookmark {
+    /// Line details, including surrounding context
+    pub detail: LineDetail,
+    /// Note attached to the bookmark
+    pub note: Option<String>,
+}
+
+/// Bookmarks of a single file
+#[derive(Debug, Clone)]
+pub struct FileReport {
+    /// Display title (alias or file name)
+    pub title: String,
+    /// Source file path
+    pub path: PathBuf,
+    /// Bookmarked lines in line order
+    pub bookmarks: Vec<ReportBookmark>,
+}
+
+impl FileReport {
+    /// Collect the bookmarked lines of a buffer, with notes from the store
+    pub fn collect(
+        title: impl Into<String>,
+        path: &Path,
+        buffer: &LogBuffer,
+        store: &BookmarksStore,
+        context_lines: usize,
+    ) -> Self {
+        let bookmarks = buffer
+            .bookmarked_entries()
+            .into_iter()
+            .filter_map(|(index, entry)| {
+                let detail = LineDetail::from_buffer(buffer, index, context_lines)?;
+                Some(ReportBookmark {
+                    note: store.note(path, entry.line_number).map(str::to_string),
+                    detail,
+                })
+            })
+            .collect();
+
+        Self {
+            title: title.into(),
+            path: path.to_path_buf(),
+            bookmarks,
+        }
+    }
+
+    /// Check if the file has no bookmarks
+    pub fn is_empty(&self) -> bool {
+        self.bookmarks.is_empty()
+    }
+}
+
+/// Render a report for the given files
+pub fn render(reports: &[FileReport], format: ReportFormat) -> String {
+    let generated = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    match format {
+        ReportFormat::Markdown => render_markdown(reports, &generated),
+        ReportFormat::Html => render_html(reports, &generated),
+    }
+}
+
+/// Heading for a bookmark: line number, level and timestamp
+fn bookmark_heading(detail: &LineDetail) -> String {
+    let mut heading = format!("Line {}", detail.line_number);
+    if let Some(level) = detail.level {
+        heading.push_str(" · ");
+        heading.push_str(level.as_str());
+    }
+    if let Some(ref timestamp) = detail.timestamp {
+        heading.push_str(" · ");
+        heading.push_str(timestamp);
+    }
+    heading
+}
+
+/// Context lines with the bookmarked line marked
+fn context_lines(detail: &LineDetail) -> Vec<(bool, usize, &str)> {
+    detail
+        .context_before
+        .iter()
+        .map(|(n, c)| (false, *n, c.as_str()))
+        .chain(std::iter::once((true, detail.line_number, detail.content.as_str())))
+        .chain(detail.context_after.iter().map(|(n, c)| (false, *n, c.as_str())))
+        .collect()
+}
+
+fn render_markdown(reports: &[FileReport], generated: &str) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "# Bookmark Report\n");
+    let _ = writeln!(out, "_Generated {}_\n", generated);
+
+    for report in reports.iter().filter(|r| !r.is_empty()) {
+        let _ = writeln!(out, "## {}\n", report.title);
+        let _ = writeln!(out, "`{}`\n", report.path.display());
+
+        for bookmark in &report.bookmarks {
+            let _ = writeln!(out, "### {}\n", bookmark_heading(&bookmark.detail));
+            if let Some(ref note) = bookmark.note {
+                for line in note.lines() {
+                    let _ = writeln!(out, "> {}", line);
+                }
+                out.push('\n');
+            }
+
+            // Use a longer fence if the log content contains one
+            let fence = if context_lines(&bookmark.detail)
+                .iter()
+                .any(|(_, _, c)| c.contains("```"))
+            {
+                "````"
+            } else {
+                "```"
+            };
+            let _ = writeln!(out, "{}text", fence);
+            for (focused, line_number, content) in context_lines(&bookmark.detail) {
+                let marker = if focused { '>' } else { ' ' };
+                let _ = writeln!(out, "{} {:>6}  {}", marker, line_number, content);
+            }
+            let _ = writeln!(out, "{}\n", fence);
+        }
+    }
+
+    out
+}
+
+fn render_html(reports: &[FileReport], generated: &str) -> String {
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str("<title>Bookmark Report</title>\n<style>\n");
+    out.push_str(
+        "body { font-family: sans-serif; margin: 2em; }\n\
+         pre { background: #f5f5f5; padding: 0.5em; overflow-x: auto; }\n\
+         mark { background: #fff3b0; }\n\
+         blockquote { border-left: 3px solid #2196f3; margin: 0.5em 0; padding-left: 0.8em; }\n\
+         .path { color: #777; font-family: monospace; }\n",
+    );
+    out.push_str("</style>\n</head>\n<body>\n");
+    out.push_str("<h1>Bookmark Report</h1>\n");
+    let _ = writeln!(out, "<p><em>Generated {}</em></p>", escape_html(generated));
+
+    for report in reports.iter().filter(|r| !r.is_empty()) {
+        let _ = writeln!(out, "<h2>{}</h2>", escape_html(&report.title));
+        let _ = writeln!(
+            out,
+            "<p class=\"path\">{}</p>",
+            escape_html(&report.path.display().to_string())
+        );
+
+        for bookmark in &report.bookmarks {
+            out.push_str("<section>\n");
+            let _ = writeln!(out, "<h3>{}</h3>", escape_html(&bookmark_heading(&bookmark.detail)));
+            if let Some(ref note) = bookmark.note {
+                let _ = writeln!(
+                    out,
+                    "<blockquote>{}</blockquote>",
+                    escape_html(note).replace('\n', "<br>")
+                );
+            }
+            out.push_str("<pre>");
+            for (focused, line_number, content) in context_lines(&bookmark.detail) {
+                let line = format!("{:>6}  {}", line_number, escape_html(content));
+                if focused {
+                    let _ = writeln!(out, "<mark>{}</mark>", line);
+                } else {
+                    let _ = writeln!(out, "{}", line);
+                }
+            }
+            out.push_str("</pre>\n</section>\n");
+        }
+    }
+
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+/// Escape text for inclusion in HTML
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log_entry::LogEntry;
+    use std::collections::HashSet;
+
+    fn sample() -> (LogBuffer, BookmarksStore, PathBuf) {
+        let path = PathBuf::from("/logs/report-test.log");
+        let mut buffer = LogBuffer::new();
+        let lines = [
+            "INFO starting",
+            "INFO connecting to db",
+            "ERROR connection refused <db:5432>",
+            "WARN retrying",
+            "INFO connected",
+        ];
+        for (i, line) in lines.iter().enumerate() {
+            buffer.push(LogEntry::new(i + 1, line.to_string(), 0));
+        }
+        buffer.toggle_bookmark(2);
+
+        let mut store = BookmarksStore::default();
+        store.set_bookmarks(&path, HashSet::from([3]));
+        store.set_note(&path, 3, Some("Root cause: db was down".to_string()));
+        (buffer, store, path)
+    }
+
+    #[test]
+    fn test_markdown_report_has_notes_and_context() {
+        let (buffer, store, path) = sample();
+        let report = FileReport::collect("api", &path, &buffer, &store, 1);
+        assert_eq!(report.bookmarks.len(), 1);
+
+        let markdown = render(&[report], ReportFormat::Markdown);
+        assert!(markdown.contains("## api"));
+        assert!(markdown.contains("### Line 3 · ERROR"));
+        assert!(markdown.contains("> Root cause: db was down"));
+        assert!(markdown.contains(">      3  ERROR connection refused <db:5432>"));
+        // One line of context on each side
+        assert!(markdown.contains("       2  INFO connecting to db"));
+        assert!(markdown.contains("       4  WARN retrying"));
+        assert!(!markdown.contains("INFO starting"));
+    }
+
+    #[test]
+    fn test_html_report_escapes_content() {
+        let (buffer, store, path) = sample();
+        let report = FileReport::collect("api", &path, &buffer, &store, 1);
+
+        let html = render(&[report], ReportFormat::Html);
+        assert!(html.contains("<blockquote>Root cause: db was down</blockquote>"));
+        assert!(html.contains("<mark>     3  ERROR connection refused &lt;db:5432&gt;</mark>"));
+        assert!(html.contains("WARN retrying"));
+    }
+
+    #[test]
+    fn test_files_without_bookmarks_are_skipped() {
+        let mut buffer = LogBuffer::new();
+        buffer.push(LogEntry::new(1, "INFO quiet".to_string(), 0));
+        let report = FileReport::collect(
+            "quiet",
+            Path::new("/logs/quiet.log"),
+            &buffer,
+            &BookmarksStore::default(),
+            2,
+        );
+        assert!(report.is_empty());
+        assert!(!render(&[report], ReportFormat::Markdown).contains("## quiet"));
+    }
+}
@@ -7,11 +7,32 @@ use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
+/// Bookmark colors by index (from 1; 0 is the theme's bookmark color)
+pub const BOOKMARK_COLORS: [(&str, (u8, u8, u8)); 5] = [
+    ("red", (244, 67, 54)),
+    ("orange", (255, 112, 67)),
+    ("green", (76, 175, 80)),
+    ("blue", (33, 150, 243)),
+    ("purple", (171, 71, 188)),
+];
+
+/// Name of a bookmark color, None for the default color
+pub fn bookmark_color_name(color: u8) -> Option<&'static str> {
+    let index = (color as usize).checked_sub(1)?;
+    BOOKMARK_COLORS.get(index).map(|(name, _)| *name)
+}
+
 /// Bookmarks for a specific file
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct FileBookmarks {
     /// Bookmarked line numbers (1-indexed)
     pub lines: HashSet<usize>,
+    /// Notes attached to bookmarked lines (line number -> note)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub notes: HashMap<usize, String>,
+    /// Colors of bookmarked lines (line number -> color, see [`BOOKMARK_COLORS`])
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub colors: HashMap<usize, u8>,
     /// Last modified timestamp
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_modified: Option<u64>,
@@ -50,15 +71,18 @@ impl BookmarksStore {
             std::fs::create_dir_all(parent).context("Failed to create bookmarks directory")?;
         }
 
-        let content = toml::to_string_pretty(self).context("Failed to serialize bookmarks")?;
-
-        std::fs::write(&path, content).context("Failed to write bookmarks file")?;
+        std::fs::write(&path, self.to_toml()?).context("Failed to write bookmarks file")?;
 
         Ok(())
     }
 
+    /// Serialize the bookmarks as they are saved on disk
+    pub fn to_toml(&self) -> Result<String> {
+        toml::to_string_pretty(self).context("Failed to serialize bookmarks")
+    }
+
     /// Get storage file path
-    fn storage_path() -> Result<PathBuf> {
+    pub fn storage_path() -> Result<PathBuf> {
         let dir = dirs::data_dir()
             .context("Failed to get data directory")?
             .join("logline");
@@ -79,8 +103,19 @@ impl BookmarksStore {
                 // Remove entry if no bookmarks
                 self.files.remove(&key);
             } else {
+                // Keep notes and colors for lines that are still bookmarked
+                let (mut notes, mut colors) = self
+                    .files
+                    .remove(&key)
+                    .map(|existing| (existing.notes, existing.colors))
+                    .unwrap_or_default();
+                notes.retain(|line, _| lines.contains(line));
+                colors.retain(|line, _| lines.contains(line));
+
                 let file_bookmarks = FileBookmarks {
                     lines,
+                    notes,
+                    colors,
                     last_modified: Some(
                         std::time::SystemTime::now()
                             .duration_since(std::time::UNIX_EPOCH)
@@ -114,6 +149,8 @@ impl BookmarksStore {
         if let Some(key) = Self::path_to_key(file_path) {
             if let Some(file_bookmarks) = self.files.get_mut(&key) {
                 file_bookmarks.lines.remove(&line);
+                file_bookmarks.notes.remove(&line);
+                file_bookmarks.colors.remove(&line);
                 file_bookmarks.last_modified = Some(
                     std::time::SystemTime::now()
                         .duration_since(std::time::UNIX_EPOCH)
@@ -129,6 +166,44 @@ impl BookmarksStore {
         }
     }
 
+    /// Get the note attached to a bookmarked line
+    pub fn note(&self, file_path: &Path, line: usize) -> Option<&str> {
+        self.get_bookmarks(file_path)
+            .and_then(|bookmarks| bookmarks.notes.get(&line))
+            .map(|note| note.as_str())
+    }
+
+    /// Attach a note to a bookmarked line (empty or None removes it)
+    pub fn set_note(&mut self, file_path: &Path, line: usize, note: Option<String>) {
+        let Some(key) = Self::path_to_key(file_path) else {
+            return;
+        };
+        let Some(file_bookmarks) = self.files.get_mut(&key) else {
+            return;
+        };
+        match note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty()) {
+            Some(note) if file_bookmarks.lines.contains(&line) => {
+                file_bookmarks.notes.insert(line, note);
+            }
+            _ => {
+                file_bookmarks.notes.remove(&line);
+            }
+        }
+    }
+
+    /// Set the colors of a file's bookmarked lines (line number -> color).
+    /// Lines that aren't bookmarked and default colors (0) are left out.
+    pub fn set_colors(&mut self, file_path: &Path, mut colors: HashMap<usize, u8>) {
+        let Some(key) = Self::path_to_key(file_path) else {
+            return;
+        };
+        let Some(file_bookmarks) = self.files.get_mut(&key) else {
+            return;
+        };
+        colors.retain(|line, color| *color != 0 && file_bookmarks.lines.contains(line));
+        file_bookmarks.colors = colors;
+    }
+
     /// Clear all bookmarks for a file
     #[allow(dead_code)]
     pub fn clear_bookmarks(&mut self, file_path: &Path) {
@@ -186,4 +261,42 @@ mod tests {
         store.clear_bookmarks(&path);
         assert!(store.get_bookmarks(&path).is_none());
     }
+
+    #[test]
+    fn test_bookmark_notes() {
+        let mut store = BookmarksStore::default();
+        let path = PathBuf::from("/tmp/notes.log");
+
+        store.set_bookmarks(&path, HashSet::from([5, 9]));
+        store.set_note(&path, 5, Some("  first failure ".to_string()));
+        store.set_note(&path, 7, Some("not bookmarked".to_string()));
+        assert_eq!(store.note(&path, 5), Some("first failure"));
+        assert_eq!(store.note(&path, 7), None);
+
+        // Notes survive re-saving the bookmarks, but not unbookmarking
+        store.set_bookmarks(&path, HashSet::from([5, 9]));
+        assert_eq!(store.note(&path, 5), Some("first failure"));
+        store.set_bookmarks(&path, HashSet::from([9]));
+        assert_eq!(store.note(&path, 5), None);
+    }
+
+    #[test]
+    fn test_bookmark_colors() {
+        let mut store = BookmarksStore::default();
+        let path = PathBuf::from("/tmp/colors.log");
+
+        store.set_bookmarks(&path, HashSet::from([5, 9, 12]));
+        store.set_colors(&path, HashMap::from([(5, 1), (9, 0), (7, 2), (12, 4)]));
+        let colors = &store.get_bookmarks(&path).unwrap().colors;
+        assert_eq!(colors, &HashMap::from([(5, 1), (12, 4)]));
+        assert_eq!(bookmark_color_name(colors[&12]), Some("blue"));
+        assert_eq!(bookmark_color_name(0), None);
+
+        // Colors are saved and dropped with their bookmarks
+        let saved: BookmarksStore = toml::from_str(&store.to_toml().unwrap()).unwrap();
+        assert_eq!(saved.get_bookmarks(&path).unwrap().colors.len(), 2);
+        store.set_bookmarks(&path, HashSet::from([5, 9]));
+        store.remove_bookmark(&path, 5);
+        assert!(store.get_bookmarks(&path).unwrap().colors.is_empty());
+    }
 }
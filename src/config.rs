@@ -2,10 +2,13 @@
 
 use crate::grok_parser::GrokConfig;
 use crate::i18n::Language;
+use crate::log_entry::LogLevel;
+use crate::search::{AnalysisContext, NamedFilter};
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 /// Application configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,14 +26,48 @@ pub struct AppConfig {
     pub remote_server: RemoteServerConfig,
     /// Grok parser configuration
     pub grok: GrokConfig,
+    /// Secret redaction for copied/exported text
+    pub redaction: RedactionConfig,
+    /// Last split view layout
+    pub split_layout: SplitLayoutConfig,
+    /// Keyboard behavior
+    pub keyboard: KeyboardConfig,
+    /// Accessibility options
+    pub accessibility: AccessibilityConfig,
+    /// What dropping a folder on the window opens
+    pub folder_drop: FolderDropConfig,
+    /// Periodic autosave of bookmarks and session state
+    pub autosave: AutosaveConfig,
+    /// Where request / correlation ids are read from
+    pub correlation: CorrelationConfig,
+    /// Grouping of multiline stack traces
+    pub stack_traces: StackTraceConfig,
     /// Recent files list
     pub recent_files: Vec<PathBuf>,
     /// Maximum recent files to keep
     pub max_recent_files: usize,
+    /// Folders pinned in the explorer as workspaces
+    pub workspace_folders: Vec<PathBuf>,
+    /// Recently applied search/filter/pattern contexts (most recent first)
+    pub recent_contexts: Vec<AnalysisContext>,
+    /// Maximum recent contexts to keep
+    pub max_recent_contexts: usize,
+    /// Saved filter presets
+    pub filter_presets: Vec<NamedFilter>,
+    /// Maximum suggestions listed in the file picker
+    pub max_picker_suggestions: usize,
     /// File encoding preferences (file path -> encoding name)
     pub file_encodings: HashMap<String, String>,
     /// Per-file Grok configuration (file path -> grok pattern config)
     pub file_grok_configs: HashMap<String, FileGrokConfig>,
+    /// Files shown in reverse order (newest first)
+    pub file_reverse_order: HashSet<String>,
+    /// Per-file display prefix stripping (file path -> strip config)
+    pub file_strip_prefixes: HashMap<String, StripPrefixConfig>,
+    /// Friendly display names (file path / stream cache path -> alias)
+    pub source_aliases: HashMap<String, String>,
+    /// Free-form investigation notes (file path -> notes)
+    pub file_notes: HashMap<String, String>,
     /// Current theme
     pub theme: Theme,
     /// Application language
@@ -46,10 +83,27 @@ impl Default for AppConfig {
             mcp: McpServerConfig::default(),
             remote_server: RemoteServerConfig::default(),
             grok: GrokConfig::default(),
+            redaction: RedactionConfig::default(),
+            split_layout: SplitLayoutConfig::default(),
+            keyboard: KeyboardConfig::default(),
+            accessibility: AccessibilityConfig::default(),
+            folder_drop: FolderDropConfig::default(),
+            autosave: AutosaveConfig::default(),
+            correlation: CorrelationConfig::default(),
+            stack_traces: StackTraceConfig::default(),
             recent_files: Vec::new(),
             max_recent_files: 10,
+            workspace_folders: Vec::new(),
+            recent_contexts: Vec::new(),
+            max_recent_contexts: 10,
+            filter_presets: Vec::new(),
+            max_picker_suggestions: 50,
             file_encodings: HashMap::new(),
+            file_reverse_order: HashSet::new(),
             file_grok_configs: HashMap::new(),
+            file_strip_prefixes: HashMap::new(),
+            source_aliases: HashMap::new(),
+            file_notes: HashMap::new(),
             theme: Theme::Dark,
             language: Language::default(),
         }
@@ -72,24 +126,13 @@ impl AppConfig {
         Ok(config)
     }
 
-    /// Save configuration to file
-    pub fn save(&self) -> Result<()> {
-        let path = Self::config_path()?;
-
-        // Create parent directory if needed
-        if let Some(parent) = path.parent() {
-            std::fs::create_dir_all(parent).context("Failed to create config directory")?;
-        }
-
-        let content = toml::to_string_pretty(self).context("Failed to serialize config")?;
-
-        std::fs::write(&path, content).context("Failed to write config file")?;
-
-        Ok(())
+    /// Serialize the configuration as it is saved on disk
+    pub fn to_toml(&self) -> Result<String> {
+        toml::to_string_pretty(self).context("Failed to serialize config")
     }
 
     /// Get config file path
-    fn config_path() -> Result<PathBuf> {
+    pub fn config_path() -> Result<PathBuf> {
         let dir = dirs::config_dir()
             .context("Failed to get config directory")?
             .join("logline");
@@ -109,6 +152,33 @@ impl AppConfig {
         self.recent_files.truncate(self.max_recent_files);
     }
 
+    /// Pin a folder as a workspace (no-op if it already is one)
+    pub fn add_workspace_folder(&mut self, dir: PathBuf) {
+        if !self.workspace_folders.contains(&dir) {
+            self.workspace_folders.push(dir);
+        }
+    }
+
+    /// Add a context to the recent contexts, moving it to the front if present
+    pub fn add_recent_context(&mut self, context: AnalysisContext) {
+        self.recent_contexts.retain(|c| c != &context);
+        self.recent_contexts.insert(0, context);
+        self.recent_contexts.truncate(self.max_recent_contexts);
+    }
+
+    /// Save a filter preset, replacing any preset with the same name
+    pub fn save_filter_preset(&mut self, preset: NamedFilter) {
+        match self.filter_presets.iter_mut().find(|p| p.name == preset.name) {
+            Some(existing) => *existing = preset,
+            None => self.filter_presets.push(preset),
+        }
+    }
+
+    /// Delete the filter preset with a name
+    pub fn delete_filter_preset(&mut self, name: &str) {
+        self.filter_presets.retain(|p| p.name != name);
+    }
+
     /// Remove a recent file
     #[allow(dead_code)]
     pub fn remove_recent_file(&mut self, path: &PathBuf) {
@@ -143,6 +213,22 @@ impl AppConfig {
         }
     }
 
+    /// Check if a file is shown in reverse order
+    pub fn is_file_reverse_order(&self, path: &Path) -> bool {
+        self.file_reverse_order
+            .contains(path.to_string_lossy().as_ref())
+    }
+
+    /// Remember whether a file is shown in reverse order
+    pub fn set_file_reverse_order(&mut self, path: &Path, reverse: bool) {
+        let path_str = path.to_string_lossy().to_string();
+        if reverse {
+            self.file_reverse_order.insert(path_str);
+        } else {
+            self.file_reverse_order.remove(&path_str);
+        }
+    }
+
     /// Set grok config for a file
     pub fn set_file_grok_config(&mut self, path: PathBuf, config: Option<FileGrokConfig>) {
         let path_str = path.to_string_lossy().to_string();
@@ -152,6 +238,100 @@ impl AppConfig {
             self.file_grok_configs.remove(&path_str);
         }
     }
+
+    /// Grok config to restore for a file: its saved config if there is one,
+    /// otherwise the first glob rule matching the file
+    pub fn grok_config_for(&self, path: &Path) -> Option<FileGrokConfig> {
+        let path_str = path.to_string_lossy().to_string();
+        if let Some(cfg) = self.file_grok_configs.get(&path_str) {
+            return Some(cfg.clone());
+        }
+
+        let rule = self.grok.glob_rules.iter().find(|r| r.matches(path))?;
+        Some(self.grok_config_for_pattern(&rule.pattern))
+    }
+
+    /// Grok config selecting a pattern by name: a custom pattern if one has
+    /// that name, JSON mode, or a builtin pattern by display name
+    pub fn grok_config_for_pattern(&self, pattern: &str) -> FileGrokConfig {
+        let custom = self
+            .grok
+            .custom_patterns
+            .iter()
+            .find(|p| p.name == pattern);
+        if custom.is_none() && pattern == crate::grok_parser::JSON_MODE_NAME {
+            return FileGrokConfig::json();
+        }
+        FileGrokConfig {
+            enabled: true,
+            pattern_type: if custom.is_some() { "custom" } else { "builtin" }.to_string(),
+            builtin_pattern: custom.is_none().then(|| pattern.to_string()),
+            custom_pattern_name: custom.map(|p| p.name.clone()),
+            inline_pattern: None,
+            pre_processor: custom.map(|p| p.pre_processor.clone()).unwrap_or_default(),
+        }
+    }
+
+    /// Get the display prefix strip config for a file
+    pub fn get_file_strip_prefix(&self, path: &PathBuf) -> Option<&StripPrefixConfig> {
+        let path_str = path.to_string_lossy().to_string();
+        self.file_strip_prefixes.get(&path_str)
+    }
+
+    /// Set the display prefix strip config for a file
+    pub fn set_file_strip_prefix(&mut self, path: PathBuf, config: Option<StripPrefixConfig>) {
+        let path_str = path.to_string_lossy().to_string();
+        if let Some(cfg) = config {
+            self.file_strip_prefixes.insert(path_str, cfg);
+        } else {
+            self.file_strip_prefixes.remove(&path_str);
+        }
+    }
+
+    /// Get the alias for a source (local file or remote stream cache path)
+    pub fn get_source_alias(&self, path: &Path) -> Option<&str> {
+        let path_str = path.to_string_lossy().to_string();
+        self.source_aliases.get(&path_str).map(|s| s.as_str())
+    }
+
+    /// Set the alias for a source (None or empty clears it)
+    pub fn set_source_alias(&mut self, path: &Path, alias: Option<String>) {
+        let path_str = path.to_string_lossy().to_string();
+        match alias.map(|a| a.trim().to_string()).filter(|a| !a.is_empty()) {
+            Some(alias) => {
+                self.source_aliases.insert(path_str, alias);
+            }
+            None => {
+                self.source_aliases.remove(&path_str);
+            }
+        }
+    }
+
+    /// Get the notes for a file (empty if none)
+    pub fn get_file_notes(&self, path: &Path) -> &str {
+        self.file_notes
+            .get(path.to_string_lossy().as_ref())
+            .map_or("", |s| s.as_str())
+    }
+
+    /// Set the notes for a file (blank notes are removed)
+    pub fn set_file_notes(&mut self, path: &Path, notes: &str) {
+        let path_str = path.to_string_lossy().to_string();
+        if notes.trim().is_empty() {
+            self.file_notes.remove(&path_str);
+        } else {
+            self.file_notes.insert(path_str, notes.to_string());
+        }
+    }
+}
+
+/// Per-file prefix stripped from each line at display time
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StripPrefixConfig {
+    /// Literal prefix or regex pattern
+    pub pattern: String,
+    /// Whether the pattern is a regular expression (anchored at line start)
+    pub is_regex: bool,
 }
 
 /// Per-file Grok configuration
@@ -159,7 +339,7 @@ impl AppConfig {
 pub struct FileGrokConfig {
     /// Whether grok parsing is enabled for this file
     pub enabled: bool,
-    /// Pattern type: "builtin" or "custom"
+    /// Pattern type: "builtin", "custom", "inline" or "json"
     pub pattern_type: String,
     /// For builtin patterns: the pattern name
     pub builtin_pattern: Option<String>,
@@ -172,6 +352,32 @@ pub struct FileGrokConfig {
     pub pre_processor: crate::grok_parser::PreProcessor,
 }
 
+impl FileGrokConfig {
+    /// Config parsing the file's lines as JSON objects
+    pub fn json() -> Self {
+        Self {
+            enabled: true,
+            pattern_type: "json".to_string(),
+            builtin_pattern: None,
+            custom_pattern_name: None,
+            inline_pattern: None,
+            pre_processor: crate::grok_parser::PreProcessor::None,
+        }
+    }
+
+    /// Config applying a builtin pattern
+    pub fn builtin(pattern: crate::grok_parser::BuiltinPattern) -> Self {
+        Self {
+            enabled: true,
+            pattern_type: "builtin".to_string(),
+            builtin_pattern: Some(pattern.display_name().to_string()),
+            custom_pattern_name: None,
+            inline_pattern: None,
+            pre_processor: crate::grok_parser::PreProcessor::None,
+        }
+    }
+}
+
 /// Inline grok pattern (for AI-generated patterns that are file-specific)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InlineGrokPattern {
@@ -258,6 +464,40 @@ pub struct DisplayConfig {
     pub show_row_separator: bool,
     /// Show grok parsed fields
     pub show_grok_fields: bool,
+    /// Row density (scales the row height)
+    pub row_density: RowDensity,
+    /// Context lines shown around each error in the errors-with-context view
+    pub error_context_lines: usize,
+    /// What a single click on a log line does
+    pub click_action: ClickAction,
+    /// Timestamp to use for lines without an embedded one
+    pub timestamp_fallback: TimestampFallback,
+    /// Prefix copied lines with their line numbers by default
+    pub copy_with_line_numbers: bool,
+    /// Show each source's size in the explorer
+    pub show_source_sizes: bool,
+    /// Show the timeline sparkline above the log view
+    pub show_timeline: bool,
+    /// Keep search matches highlighted after the search bar is closed
+    pub keep_search_highlights: bool,
+    /// Render ANSI color sequences in log lines (off shows them raw)
+    pub render_ansi_colors: bool,
+    /// Show grok fields as aligned columns instead of the display template
+    pub grok_columns: bool,
+    /// Custom RGB colors per level; levels without one use the built-in color
+    pub level_colors: HashMap<LogLevel, [u8; 3]>,
+    /// Wrap long lines to the view width instead of scrolling horizontally
+    pub wrap_lines: bool,
+    /// Show the overview strip with error and bookmark markers
+    pub show_overview: bool,
+    /// Show a sticky line with the timestamp and context of the topmost visible line
+    pub show_context_header: bool,
+    /// Stop following the tail when an ERROR or FATAL line arrives
+    pub pause_tail_on_error: bool,
+    /// How timestamps embedded in lines are shown
+    pub timestamp_mode: TimestampMode,
+    /// Text that is always highlighted, on top of the level and syntax colors
+    pub highlight_rules: Vec<HighlightRule>,
 }
 
 impl Default for DisplayConfig {
@@ -273,10 +513,154 @@ impl Default for DisplayConfig {
             tab_size: 4,
             show_row_separator: true,
             show_grok_fields: true,
+            row_density: RowDensity::default(),
+            error_context_lines: 3,
+            click_action: ClickAction::default(),
+            timestamp_fallback: TimestampFallback::default(),
+            copy_with_line_numbers: false,
+            show_source_sizes: true,
+            show_timeline: true,
+            keep_search_highlights: true,
+            render_ansi_colors: true,
+            grok_columns: false,
+            level_colors: HashMap::new(),
+            wrap_lines: false,
+            show_overview: true,
+            show_context_header: false,
+            pause_tail_on_error: false,
+            timestamp_mode: TimestampMode::default(),
+            highlight_rules: Vec::new(),
+        }
+    }
+}
+
+impl DisplayConfig {
+    /// Height of a log row in pixels
+    pub fn row_height(&self) -> f32 {
+        // Never shrink the row below the glyph height
+        (self.font_size * self.line_height * self.row_density.scale()).max(self.font_size * 1.1)
+    }
+
+    /// Color for a level, honoring a custom color if one is set
+    pub fn level_color(&self, level: LogLevel) -> egui::Color32 {
+        self.level_colors
+            .get(&level)
+            .map_or(level.color(), |&[r, g, b]| egui::Color32::from_rgb(r, g, b))
+    }
+
+    /// Colors of all levels, indexed by `level as usize`
+    pub fn level_palette(&self) -> [egui::Color32; 6] {
+        LogLevel::all().map(|level| self.level_color(level))
+    }
+}
+
+/// Row density for the log view
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum RowDensity {
+    #[default]
+    Comfortable,
+    Compact,
+    Dense,
+}
+
+impl RowDensity {
+    /// Get all densities
+    pub fn all() -> &'static [RowDensity] {
+        &[RowDensity::Comfortable, RowDensity::Compact, RowDensity::Dense]
+    }
+
+    /// Row height scale factor
+    pub fn scale(&self) -> f32 {
+        match self {
+            RowDensity::Comfortable => 1.0,
+            RowDensity::Compact => 0.85,
+            RowDensity::Dense => 0.7,
+        }
+    }
+}
+
+/// Action performed when a log line is clicked
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum ClickAction {
+    /// Only select the line
+    #[default]
+    Select,
+    /// Select the line and open the detail pane
+    OpenDetail,
+}
+
+impl ClickAction {
+    /// Get all click actions
+    pub fn all() -> &'static [ClickAction] {
+        &[ClickAction::Select, ClickAction::OpenDetail]
+    }
+}
+
+/// Timestamp fallback for lines without an embedded timestamp
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum TimestampFallback {
+    /// Only use timestamps parsed from the line
+    #[default]
+    None,
+    /// Use the time the line was appended, then the file modification time
+    IngestionTime,
+    /// Use the file modification time
+    FileMtime,
+}
+
+impl TimestampFallback {
+    /// Get all fallbacks
+    pub fn all() -> &'static [TimestampFallback] {
+        &[
+            TimestampFallback::None,
+            TimestampFallback::IngestionTime,
+            TimestampFallback::FileMtime,
+        ]
+    }
+}
+
+/// How timestamps embedded in log lines are shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum TimestampMode {
+    /// As written in the line
+    #[default]
+    Absolute,
+    /// As the time elapsed until now, e.g. "2m ago"
+    RelativeToNow,
+}
+
+impl TimestampMode {
+    /// Get all modes
+    pub fn all() -> &'static [TimestampMode] {
+        &[TimestampMode::Absolute, TimestampMode::RelativeToNow]
+    }
+
+    /// The other mode
+    pub fn toggled(self) -> Self {
+        match self {
+            TimestampMode::Absolute => TimestampMode::RelativeToNow,
+            TimestampMode::RelativeToNow => TimestampMode::Absolute,
         }
     }
 }
 
+/// Which lines are dropped when the buffer is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum TrimStrategy {
+    /// Drop the oldest lines
+    #[default]
+    DropOldest,
+    /// Keep the first lines of the file and drop the ones after them
+    KeepHeadAndTail,
+}
+
+impl TrimStrategy {
+    /// Get all strategies
+    pub fn all() -> &'static [TrimStrategy] {
+        &[TrimStrategy::DropOldest, TrimStrategy::KeepHeadAndTail]
+    }
+}
+
 /// Buffer configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -285,10 +669,37 @@ pub struct BufferConfig {
     pub max_lines: usize,
     /// Auto-trim old entries
     pub auto_trim: bool,
+    /// Which lines auto-trim drops
+    pub trim_strategy: TrimStrategy,
+    /// Lines kept at the start of the file with [`TrimStrategy::KeepHeadAndTail`]
+    pub trim_head_lines: usize,
     /// Batch update interval in milliseconds
     pub update_interval_ms: u64,
     /// Maximum batch size for UI updates
     pub max_batch_size: usize,
+    /// Lines loaded per chunk when scrolling up in a large file
+    pub chunk_size: usize,
+    /// Minimum distance (rows) from the top that triggers loading a chunk
+    pub load_threshold: usize,
+    /// Frames of scrolling to look ahead when prefetching the next chunk
+    pub prefetch_frames: usize,
+    /// Pause monitoring of tabs inactive for this many seconds (0 = never)
+    pub idle_pause_secs: u64,
+    /// Lines to follow in a bounded capture before monitoring stops
+    pub capture_lines: usize,
+    /// Hold back a partially written last line for up to this many
+    /// milliseconds while tailing (0 = show it immediately)
+    pub partial_line_timeout_ms: u64,
+    /// Reduced-update mode: while tailing, only lines at or above this level
+    /// repaint right away and the rest are batched (None = always repaint promptly)
+    pub repaint_min_level: Option<LogLevel>,
+    /// Repaint interval in milliseconds for batched lines in reduced-update mode
+    pub reduced_repaint_interval_ms: u64,
+    /// Memory-map large files that are no longer written to as read-only
+    /// tabs instead of following them (from a copy in the temp directory)
+    pub mmap_large_files: bool,
+    /// Size in megabytes from which files are memory-mapped
+    pub mmap_threshold_mb: u64,
 }
 
 impl Default for BufferConfig {
@@ -296,12 +707,45 @@ impl Default for BufferConfig {
         Self {
             max_lines: 100_000,
             auto_trim: true,
+            trim_strategy: TrimStrategy::DropOldest,
+            trim_head_lines: 1_000,
             update_interval_ms: 16, // ~60 FPS
             max_batch_size: 1000,
+            chunk_size: 5_000,
+            load_threshold: 100,
+            prefetch_frames: 30,
+            idle_pause_secs: 0,
+            capture_lines: 1000,
+            partial_line_timeout_ms: 500,
+            repaint_min_level: None,
+            reduced_repaint_interval_ms: 1000,
+            mmap_large_files: false,
+            mmap_threshold_mb: 512,
         }
     }
 }
 
+impl BufferConfig {
+    /// Repaint interval while tailing outside reduced-update mode
+    const TAILING_REPAINT_INTERVAL: Duration = Duration::from_millis(50);
+
+    /// Delay before the next repaint while tailing, given the highest level
+    /// among the lines that just arrived
+    pub fn tailing_repaint_delay(&self, arrived: Option<LogLevel>) -> Duration {
+        match self.repaint_min_level {
+            None => Self::TAILING_REPAINT_INTERVAL,
+            Some(min) if arrived.is_some_and(|level| level.is_at_least(min)) => Duration::ZERO,
+            Some(_) => Duration::from_millis(self.reduced_repaint_interval_ms),
+        }
+    }
+
+    /// File size in bytes from which files are memory-mapped (None = never)
+    pub fn mmap_threshold(&self) -> Option<u64> {
+        self.mmap_large_files
+            .then(|| self.mmap_threshold_mb.saturating_mul(1024 * 1024))
+    }
+}
+
 /// MCP server configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -324,6 +768,297 @@ impl Default for McpServerConfig {
     }
 }
 
+/// Split view layout (panes are identified by file path, since tab IDs
+/// are not stable across restarts)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SplitLayoutConfig {
+    /// Whether split view was active
+    pub is_split: bool,
+    /// Divider position (0.0-1.0)
+    pub split_ratio: f32,
+    /// File shown in the left pane
+    pub left_path: Option<PathBuf>,
+    /// File shown in the right pane
+    pub right_path: Option<PathBuf>,
+}
+
+impl Default for SplitLayoutConfig {
+    fn default() -> Self {
+        Self {
+            is_split: false,
+            split_ratio: 0.5,
+            left_path: None,
+            right_path: None,
+        }
+    }
+}
+
+/// Keyboard behavior configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeyboardConfig {
+    /// What the Space key does in the log view
+    pub space_key_action: SpaceKeyAction,
+}
+
+/// Accessibility configuration
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AccessibilityConfig {
+    /// Use the high-contrast variant of the theme
+    pub high_contrast: bool,
+    /// Enlarge buttons, hit targets and UI text
+    pub large_targets: bool,
+}
+
+/// Action bound to the Space key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum SpaceKeyAction {
+    /// Pause/resume monitoring
+    #[default]
+    ToggleMonitoring,
+    /// Scroll down one page
+    PageDown,
+    /// Do nothing
+    Nothing,
+}
+
+impl SpaceKeyAction {
+    /// Get all space key actions
+    pub fn all() -> &'static [SpaceKeyAction] {
+        &[
+            SpaceKeyAction::ToggleMonitoring,
+            SpaceKeyAction::PageDown,
+            SpaceKeyAction::Nothing,
+        ]
+    }
+}
+
+/// Folder drop configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FolderDropConfig {
+    /// Which log files of a dropped folder are opened
+    pub behavior: FolderDropBehavior,
+    /// How many levels of subfolders are searched for log files
+    pub max_depth: usize,
+    /// Ask for confirmation before opening more files than this
+    pub confirm_above: usize,
+}
+
+impl Default for FolderDropConfig {
+    fn default() -> Self {
+        Self {
+            behavior: FolderDropBehavior::default(),
+            max_depth: 2,
+            confirm_above: 10,
+        }
+    }
+}
+
+/// Periodic autosave configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AutosaveConfig {
+    /// Whether bookmarks and session state are saved periodically
+    pub enabled: bool,
+    /// Seconds between autosaves
+    pub interval_secs: u64,
+}
+
+impl Default for AutosaveConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_secs: 30,
+        }
+    }
+}
+
+impl AutosaveConfig {
+    /// Time between autosaves (at least one second)
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs(self.interval_secs.max(1))
+    }
+}
+
+/// Default regex for correlation ids: the value after a `request_id=`,
+/// `traceId:`, `"correlation-id": "..."` style key
+pub const DEFAULT_CORRELATION_PATTERN: &str =
+    r#"(?i)\b(?:request|req|trace|correlation)[_-]?id["']?\s*[=:]\s*["']?([\w.-]+)"#;
+
+/// Correlation (request) id configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CorrelationConfig {
+    /// Where the id is read from
+    pub source: CorrelationSource,
+    /// Grok field holding the id
+    pub field: String,
+    /// Regex extracting the id (first capture group, or the whole match)
+    pub pattern: String,
+}
+
+impl Default for CorrelationConfig {
+    fn default() -> Self {
+        Self {
+            source: CorrelationSource::default(),
+            field: "request_id".to_string(),
+            pattern: DEFAULT_CORRELATION_PATTERN.to_string(),
+        }
+    }
+}
+
+/// Where correlation ids are read from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum CorrelationSource {
+    /// Extract with a regex from the raw line
+    #[default]
+    Regex,
+    /// Read a parsed grok field
+    GrokField,
+}
+
+impl CorrelationSource {
+    /// Get all correlation id sources
+    pub fn all() -> &'static [CorrelationSource] {
+        &[CorrelationSource::Regex, CorrelationSource::GrokField]
+    }
+}
+
+/// Default regex for stack trace continuation lines: indented lines, Java
+/// `at ...` frames and `Caused by:` / `... N more` lines, Python tracebacks
+pub const DEFAULT_CONTINUATION_PATTERN: &str =
+    r"^(?:\s+\S|at \S|Caused by:|\.\.\. \d+ more|Traceback \(most recent call last\))";
+
+/// Multiline stack trace grouping
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StackTraceConfig {
+    /// Group continuation lines into the entry before them
+    pub enabled: bool,
+    /// Regex matching continuation lines
+    pub continuation_pattern: String,
+}
+
+impl Default for StackTraceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            continuation_pattern: DEFAULT_CONTINUATION_PATTERN.to_string(),
+        }
+    }
+}
+
+impl StackTraceConfig {
+    /// Compile the continuation regex (None when grouping is off)
+    pub fn continuation_regex(&self) -> Result<Option<regex::Regex>, regex::Error> {
+        if !self.enabled {
+            return Ok(None);
+        }
+        regex::Regex::new(&self.continuation_pattern).map(Some)
+    }
+}
+
+/// Which log files of a dropped folder are opened
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum FolderDropBehavior {
+    /// Open every log file found
+    #[default]
+    OpenAll,
+    /// Open only the most recently modified log file
+    OpenNewest,
+    /// Open nothing; pin the folder in the explorer as a workspace
+    OpenAsWorkspace,
+}
+
+impl FolderDropBehavior {
+    /// Get all folder drop behaviors
+    pub fn all() -> &'static [FolderDropBehavior] {
+        &[
+            FolderDropBehavior::OpenAll,
+            FolderDropBehavior::OpenNewest,
+            FolderDropBehavior::OpenAsWorkspace,
+        ]
+    }
+}
+
+/// Secret redaction configuration
+///
+/// Redaction only applies to text leaving the app (clipboard copy and file
+/// export); the on-screen view always shows the original content.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RedactionConfig {
+    /// Whether copied/exported text is redacted
+    pub enabled: bool,
+    /// Regex patterns matching secrets. If a pattern has capture groups,
+    /// only the first group is replaced; otherwise the whole match is.
+    pub patterns: Vec<String>,
+    /// Replacement text for matched secrets
+    pub replacement: String,
+    /// Whether the rewrite rules are applied to copied/exported text
+    pub rewrite_enabled: bool,
+    /// Regex search-and-replace rules, applied in order after redaction
+    pub rewrite_rules: Vec<RewriteRule>,
+}
+
+/// Text to always highlight in the log view (e.g. "deadlock" or a request id)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HighlightRule {
+    /// Text (or regex) to highlight
+    pub pattern: String,
+    /// Highlight color
+    pub color: [u8; 3],
+    /// Only match whole words
+    pub whole_word: bool,
+    /// Treat the pattern as a regex instead of plain text
+    pub regex: bool,
+    /// Whether the rule is applied
+    pub enabled: bool,
+}
+
+impl Default for HighlightRule {
+    fn default() -> Self {
+        Self {
+            pattern: String::new(),
+            color: [255, 112, 67],
+            whole_word: false,
+            regex: false,
+            enabled: true,
+        }
+    }
+}
+
+/// A regex search-and-replace rule for copied/exported text
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RewriteRule {
+    /// Regex pattern to search for
+    pub pattern: String,
+    /// Replacement text; `$1`, `${name}` expand capture groups
+    pub replacement: String,
+}
+
+impl Default for RedactionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            patterns: vec![
+                r#"(?i)\b(?:password|passwd|pwd|secret|token|api[_-]?key)\s*[=:]\s*"?([^\s",;]+)"#
+                    .to_string(),
+                r"(?i)\bbearer\s+([A-Za-z0-9\-._~+/]+=*)".to_string(),
+                r"\bAKIA[0-9A-Z]{16}\b".to_string(),
+            ],
+            replacement: "******".to_string(),
+            rewrite_enabled: false,
+            rewrite_rules: Vec::new(),
+        }
+    }
+}
+
 /// Remote server configuration for receiving logs from agents
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -332,6 +1067,31 @@ pub struct RemoteServerConfig {
     pub port: u16,
     /// Whether remote server is enabled
     pub enabled: bool,
+    /// PEM certificate chain; with `tls_key_path` set, agents connect over TLS
+    pub tls_cert_path: Option<PathBuf>,
+    /// PEM private key for `tls_cert_path`
+    pub tls_key_path: Option<PathBuf>,
+    /// Shared secret agents must send to connect (anyone may connect when unset)
+    pub auth_token: Option<String>,
+    /// Largest a stream's cache file may grow, in megabytes (0 = unlimited)
+    pub max_cache_mb: u64,
+    /// Returning agents resume their earlier stream instead of starting a new one
+    pub reuse_existing_streams: bool,
+    /// Seconds without data or heartbeats before an agent that sends
+    /// heartbeats counts as disconnected (0 = never)
+    pub stale_timeout_secs: u64,
+}
+
+impl RemoteServerConfig {
+    /// Cache file limit per stream in bytes (0 = unlimited)
+    pub fn max_cache_bytes(&self) -> u64 {
+        self.max_cache_mb.saturating_mul(1024 * 1024)
+    }
+
+    /// Time an agent may stay silent before it counts as disconnected
+    pub fn stale_timeout(&self) -> Option<Duration> {
+        (self.stale_timeout_secs > 0).then(|| Duration::from_secs(self.stale_timeout_secs))
+    }
 }
 
 impl Default for RemoteServerConfig {
@@ -339,6 +1099,12 @@ impl Default for RemoteServerConfig {
         Self {
             port: 12500,
             enabled: false,
+            tls_cert_path: None,
+            tls_key_path: None,
+            auth_token: None,
+            max_cache_mb: 100,
+            reuse_existing_streams: true,
+            stale_timeout_secs: 60,
         }
     }
 }
@@ -370,16 +1136,55 @@ impl Theme {
     }
 }
 
+/// Display flags that can be flipped with a shortcut
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisplayToggle {
+    /// Line number gutter
+    LineNumbers,
+    /// Separator lines between rows
+    RowSeparator,
+    /// Grok parsed fields instead of the raw line
+    GrokFields,
+    /// Wrapping long lines to the view width
+    WrapLines,
+}
+
+impl DisplayToggle {
+    pub fn all() -> &'static [DisplayToggle] {
+        &[
+            DisplayToggle::LineNumbers,
+            DisplayToggle::RowSeparator,
+            DisplayToggle::GrokFields,
+            DisplayToggle::WrapLines,
+        ]
+    }
+
+    /// Flip the flag in the display config and return its new value
+    pub fn apply(&self, display: &mut DisplayConfig) -> bool {
+        let flag = match self {
+            DisplayToggle::LineNumbers => &mut display.show_line_numbers,
+            DisplayToggle::RowSeparator => &mut display.show_row_separator,
+            DisplayToggle::GrokFields => &mut display.show_grok_fields,
+            DisplayToggle::WrapLines => &mut display.wrap_lines,
+        };
+        *flag = !*flag;
+        *flag
+    }
+}
+
 /// Keyboard shortcuts configuration
 #[derive(Debug, Clone)]
 pub struct Shortcuts {
     pub open_file: egui::KeyboardShortcut,
+    pub open_recent: egui::KeyboardShortcut,
     pub reload_file: egui::KeyboardShortcut,
     pub find: egui::KeyboardShortcut,
     pub find_next: egui::KeyboardShortcut,
     pub find_prev: egui::KeyboardShortcut,
+    pub clear_highlights: egui::KeyboardShortcut,
     pub clear: egui::KeyboardShortcut,
     pub goto_line: egui::KeyboardShortcut,
+    pub goto_time: egui::KeyboardShortcut,
     pub toggle_auto_scroll: egui::KeyboardShortcut,
     pub toggle_reverse_order: egui::KeyboardShortcut,
     pub goto_top: egui::KeyboardShortcut,
@@ -387,6 +1192,14 @@ pub struct Shortcuts {
     pub copy: egui::KeyboardShortcut,
     pub toggle_bookmark: egui::KeyboardShortcut,
     pub select_all: egui::KeyboardShortcut,
+    pub next_problem: egui::KeyboardShortcut,
+    pub prev_problem: egui::KeyboardShortcut,
+    pub next_bookmark: egui::KeyboardShortcut,
+    pub prev_bookmark: egui::KeyboardShortcut,
+    pub toggle_line_numbers: egui::KeyboardShortcut,
+    pub toggle_row_separator: egui::KeyboardShortcut,
+    pub toggle_grok_fields: egui::KeyboardShortcut,
+    pub toggle_wrap_lines: egui::KeyboardShortcut,
 }
 
 impl Default for Shortcuts {
@@ -395,12 +1208,18 @@ impl Default for Shortcuts {
 
         Self {
             open_file: KeyboardShortcut::new(Modifiers::COMMAND, Key::O),
+            open_recent: KeyboardShortcut::new(Modifiers::COMMAND.plus(Modifiers::SHIFT), Key::O),
             reload_file: KeyboardShortcut::new(Modifiers::COMMAND.plus(Modifiers::SHIFT), Key::R),
             find: KeyboardShortcut::new(Modifiers::COMMAND, Key::F),
             find_next: KeyboardShortcut::new(Modifiers::NONE, Key::F3),
             find_prev: KeyboardShortcut::new(Modifiers::SHIFT, Key::F3),
+            clear_highlights: KeyboardShortcut::new(
+                Modifiers::COMMAND.plus(Modifiers::SHIFT),
+                Key::L,
+            ),
             clear: KeyboardShortcut::new(Modifiers::COMMAND, Key::L),
             goto_line: KeyboardShortcut::new(Modifiers::COMMAND, Key::G),
+            goto_time: KeyboardShortcut::new(Modifiers::COMMAND.plus(Modifiers::SHIFT), Key::G),
             toggle_auto_scroll: KeyboardShortcut::new(Modifiers::NONE, Key::Space),
             toggle_reverse_order: KeyboardShortcut::new(Modifiers::COMMAND, Key::R),
             goto_top: KeyboardShortcut::new(Modifiers::NONE, Key::Home),
@@ -408,6 +1227,26 @@ impl Default for Shortcuts {
             copy: KeyboardShortcut::new(Modifiers::COMMAND, Key::C),
             toggle_bookmark: KeyboardShortcut::new(Modifiers::COMMAND, Key::B),
             select_all: KeyboardShortcut::new(Modifiers::COMMAND, Key::A),
+            next_problem: KeyboardShortcut::new(Modifiers::NONE, Key::F8),
+            prev_problem: KeyboardShortcut::new(Modifiers::SHIFT, Key::F8),
+            next_bookmark: KeyboardShortcut::new(Modifiers::NONE, Key::F2),
+            prev_bookmark: KeyboardShortcut::new(Modifiers::SHIFT, Key::F2),
+            toggle_line_numbers: KeyboardShortcut::new(Modifiers::ALT, Key::L),
+            toggle_row_separator: KeyboardShortcut::new(Modifiers::ALT, Key::S),
+            toggle_grok_fields: KeyboardShortcut::new(Modifiers::ALT, Key::G),
+            toggle_wrap_lines: KeyboardShortcut::new(Modifiers::ALT, Key::Z),
+        }
+    }
+}
+
+impl Shortcuts {
+    /// Shortcut flipping a display flag
+    pub fn display_toggle(&self, toggle: DisplayToggle) -> &egui::KeyboardShortcut {
+        match toggle {
+            DisplayToggle::LineNumbers => &self.toggle_line_numbers,
+            DisplayToggle::RowSeparator => &self.toggle_row_separator,
+            DisplayToggle::GrokFields => &self.toggle_grok_fields,
+            DisplayToggle::WrapLines => &self.toggle_wrap_lines,
         }
     }
 }
@@ -415,6 +1254,7 @@ impl Default for Shortcuts {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::search::SavedFilter;
 
     #[test]
     fn test_config_serialization() {
@@ -426,6 +1266,23 @@ mod tests {
         assert_eq!(deserialized.theme, config.theme);
     }
 
+    #[test]
+    fn test_level_colors_override_defaults_and_persist() {
+        let mut config = AppConfig::default();
+        config.display.level_colors.insert(LogLevel::Warn, [255, 140, 0]);
+
+        let serialized = toml::to_string(&config).unwrap();
+        let deserialized: AppConfig = toml::from_str(&serialized).unwrap();
+        let display = &deserialized.display;
+
+        assert_eq!(display.level_color(LogLevel::Warn), egui::Color32::from_rgb(255, 140, 0));
+        assert_eq!(display.level_color(LogLevel::Error), LogLevel::Error.color());
+        assert_eq!(
+            display.level_palette()[LogLevel::Warn as usize],
+            display.level_color(LogLevel::Warn)
+        );
+    }
+
     #[test]
     fn test_recent_files() {
         let mut config = AppConfig::default();
@@ -440,6 +1297,147 @@ mod tests {
         assert_eq!(config.recent_files[0], PathBuf::from("/d"));
     }
 
+    #[test]
+    fn test_filter_presets_replace_by_name_and_persist() {
+        let mut config = AppConfig::default();
+        let preset = |name: &str, bookmarks_only| NamedFilter {
+            name: name.to_string(),
+            filter: SavedFilter {
+                levels: vec![LogLevel::Warn, LogLevel::Error],
+                bookmarks_only,
+                ..Default::default()
+            },
+        };
+        config.save_filter_preset(preset("errors", false));
+        config.save_filter_preset(preset("starred", true));
+        config.save_filter_preset(preset("errors", true));
+        assert_eq!(config.filter_presets.len(), 2);
+        assert!(config.filter_presets[0].filter.bookmarks_only);
+
+        let restored: AppConfig = toml::from_str(&config.to_toml().unwrap()).unwrap();
+        assert_eq!(restored.filter_presets, config.filter_presets);
+
+        config.delete_filter_preset("errors");
+        assert_eq!(config.filter_presets, [preset("starred", true)]);
+    }
+
+    #[test]
+    fn test_source_alias_round_trip() {
+        let mut config = AppConfig::default();
+        let path = PathBuf::from("/var/cache/logline/api@10.0.0.2.log");
+
+        config.set_source_alias(&path, Some("  API prod  ".to_string()));
+        assert_eq!(config.get_source_alias(&path), Some("API prod"));
+
+        let serialized = toml::to_string(&config).unwrap();
+        let deserialized: AppConfig = toml::from_str(&serialized).unwrap();
+        assert_eq!(deserialized.get_source_alias(&path), Some("API prod"));
+
+        // Empty alias clears it
+        config.set_source_alias(&path, Some(String::new()));
+        assert_eq!(config.get_source_alias(&path), None);
+    }
+
+    #[test]
+    fn test_file_notes_persist_per_path() {
+        let mut config = AppConfig::default();
+        let app = PathBuf::from("/var/log/app.log");
+        let db = PathBuf::from("/var/log/db.log");
+
+        config.set_file_notes(&app, "timeouts start at 10:42\nsee request 7f3a");
+        config.set_file_notes(&db, "vacuum ran overnight");
+
+        let serialized = toml::to_string(&config).unwrap();
+        let mut restored: AppConfig = toml::from_str(&serialized).unwrap();
+        assert_eq!(
+            restored.get_file_notes(&app),
+            "timeouts start at 10:42\nsee request 7f3a"
+        );
+        assert_eq!(restored.get_file_notes(&db), "vacuum ran overnight");
+        assert_eq!(restored.get_file_notes(Path::new("/var/log/other.log")), "");
+
+        // Clearing the notes removes the entry
+        restored.set_file_notes(&db, "  \n");
+        assert!(!restored.file_notes.contains_key("/var/log/db.log"));
+    }
+
+    #[test]
+    fn test_grok_glob_rules_defer_to_saved_config() {
+        use crate::grok_parser::GrokGlobRule;
+
+        let mut config = AppConfig::default();
+        config.grok.glob_rules = vec![
+            GrokGlobRule {
+                glob: "*.access.log".to_string(),
+                pattern: "Combined Log".to_string(),
+            },
+            GrokGlobRule {
+                glob: "*.log".to_string(),
+                pattern: "Syslog".to_string(),
+            },
+        ];
+
+        // First matching rule wins
+        let access = PathBuf::from("/var/log/site.access.log");
+        let cfg = config.grok_config_for(&access).unwrap();
+        assert_eq!(cfg.pattern_type, "builtin");
+        assert_eq!(cfg.builtin_pattern.as_deref(), Some("Combined Log"));
+
+        let other = PathBuf::from("/var/log/kern.log");
+        let cfg = config.grok_config_for(&other).unwrap();
+        assert_eq!(cfg.builtin_pattern.as_deref(), Some("Syslog"));
+        assert!(config.grok_config_for(Path::new("/tmp/data.csv")).is_none());
+
+        // An explicitly saved config takes precedence over the rules
+        config.set_file_grok_config(
+            access.clone(),
+            Some(FileGrokConfig {
+                enabled: false,
+                pattern_type: "builtin".to_string(),
+                builtin_pattern: None,
+                custom_pattern_name: None,
+                inline_pattern: None,
+                pre_processor: Default::default(),
+            }),
+        );
+        assert!(!config.grok_config_for(&access).unwrap().enabled);
+    }
+
+    #[test]
+    fn test_display_toggle_shortcuts() {
+        let shortcuts = Shortcuts::default();
+        let alt_l = egui::KeyboardShortcut::new(egui::Modifiers::ALT, egui::Key::L);
+        let toggle = DisplayToggle::all()
+            .iter()
+            .find(|t| *shortcuts.display_toggle(**t) == alt_l)
+            .copied();
+        assert_eq!(toggle, Some(DisplayToggle::LineNumbers));
+
+        let mut display = DisplayConfig::default();
+        assert!(display.show_line_numbers);
+        assert!(!DisplayToggle::LineNumbers.apply(&mut display));
+        assert!(!display.show_line_numbers);
+        // Other flags are untouched
+        assert!(display.show_row_separator && display.show_grok_fields);
+        assert!(DisplayToggle::LineNumbers.apply(&mut display));
+    }
+
+    #[test]
+    fn test_reduced_updates_repaint_only_for_important_lines() {
+        let mut buffer = BufferConfig::default();
+        assert_eq!(
+            buffer.tailing_repaint_delay(Some(LogLevel::Debug)),
+            Duration::from_millis(50)
+        );
+
+        buffer.repaint_min_level = Some(LogLevel::Error);
+        let batched = Duration::from_millis(buffer.reduced_repaint_interval_ms);
+        assert_eq!(buffer.tailing_repaint_delay(None), batched);
+        assert_eq!(buffer.tailing_repaint_delay(Some(LogLevel::Warn)), batched);
+        assert_eq!(buffer.tailing_repaint_delay(Some(LogLevel::Error)), Duration::ZERO);
+        assert_eq!(buffer.tailing_repaint_delay(Some(LogLevel::Fatal)), Duration::ZERO);
+    }
+
     #[test]
     fn test_theme_toggle() {
         let mut theme = Theme::Dark;
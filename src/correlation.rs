@@ -0,0 +1,181 @@
+//! Correlation (request) ids
+//!
+//! A correlation id ties together the lines of one request. It is read from
+//! a grok field or extracted with a regex, and used to filter the view down
+//! to a single request or to step between its lines.
+
+use crate::config::{CorrelationConfig, CorrelationSource};
+use crate::log_buffer::LogBuffer;
+use crate::log_entry::LogEntry;
+use regex::Regex;
+
+/// Reads the correlation id of a line
+#[derive(Debug, Clone)]
+pub enum CorrelationExtractor {
+    /// Value of a parsed grok field
+    Field(String),
+    /// First capture group of a regex (or the whole match without groups)
+    Regex(Regex),
+}
+
+impl CorrelationExtractor {
+    /// Build the extractor for a configuration
+    pub fn new(config: &CorrelationConfig) -> Result<Self, regex::Error> {
+        Ok(match config.source {
+            CorrelationSource::GrokField => Self::Field(config.field.clone()),
+            CorrelationSource::Regex => Self::Regex(Regex::new(&config.pattern)?),
+        })
+    }
+
+    /// Whether ids come from grok fields (which must be parsed first)
+    pub fn needs_grok_fields(&self) -> bool {
+        matches!(self, Self::Field(_))
+    }
+
+    /// Correlation id of a line, if it has one
+    pub fn extract<'a>(&self, entry: &'a LogEntry) -> Option<&'a str> {
+        let id = match self {
+            Self::Field(name) => entry.grok_fields.as_ref()?.get(name)?.as_str(),
+            Self::Regex(regex) => {
+                let captures = regex.captures(&entry.content)?;
+                captures.get(1).or_else(|| captures.get(0))?.as_str()
+            }
+        };
+        Some(id).filter(|id| !id.is_empty())
+    }
+}
+
+/// Keeps only the lines of one correlation id
+#[derive(Debug, Clone)]
+pub struct CorrelationFilter {
+    /// How ids are read
+    pub extractor: CorrelationExtractor,
+    /// The id to keep
+    pub id: String,
+}
+
+impl CorrelationFilter {
+    /// Whether a line belongs to the filtered id
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        self.extractor.extract(entry) == Some(self.id.as_str())
+    }
+}
+
+/// Next (or previous) line after `current` sharing its correlation id.
+///
+/// `view` is the filtered index set when a filter is active, so only lines
+/// on screen are visited. Wraps around at either end. Returns
+/// `(buffer_index, position, total)` with a 1-based position.
+pub fn cycle_correlated_line(
+    buffer: &LogBuffer,
+    extractor: &CorrelationExtractor,
+    view: Option<&[usize]>,
+    current: usize,
+    forward: bool,
+) -> Option<(usize, usize, usize)> {
+    let id = extractor.extract(buffer.get(current)?)?;
+    let shares_id = |idx: usize| {
+        buffer
+            .get(idx)
+            .is_some_and(|e| extractor.extract(e) == Some(id))
+    };
+
+    let lines: Vec<usize> = match view {
+        Some(indices) => indices.iter().copied().filter(|&i| shares_id(i)).collect(),
+        None => (0..buffer.len()).filter(|&i| shares_id(i)).collect(),
+    };
+    if lines.is_empty() {
+        return None;
+    }
+
+    let position = if forward {
+        lines.iter().position(|&i| i > current).unwrap_or(0)
+    } else {
+        lines
+            .iter()
+            .rposition(|&i| i < current)
+            .unwrap_or(lines.len() - 1)
+    };
+    Some((lines[position], position + 1, lines.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::LogFilter;
+
+    fn request_buffer() -> LogBuffer {
+        let lines = [
+            "10:00:01 INFO request_id=a1 GET /users",
+            "10:00:01 INFO request_id=b2 GET /orders",
+            "10:00:02 DEBUG request_id=a1 query users",
+            "10:00:02 INFO health check",
+            "10:00:03 ERROR request_id=b2 timeout",
+            "10:00:03 INFO request_id=a1 200 OK",
+        ];
+        let mut buffer = LogBuffer::new();
+        for (i, line) in lines.iter().enumerate() {
+            buffer.push(LogEntry::new(i + 1, line.to_string(), 0));
+        }
+        buffer
+    }
+
+    #[test]
+    fn test_selected_line_filters_to_its_correlation_id() {
+        let buffer = request_buffer();
+        let extractor = CorrelationExtractor::new(&CorrelationConfig::default()).unwrap();
+
+        // Selecting the "query users" line
+        let id = extractor
+            .extract(buffer.get(2).unwrap())
+            .unwrap()
+            .to_string();
+        assert_eq!(id, "a1");
+
+        let mut filter = LogFilter::new();
+        filter.filter.correlation = Some(CorrelationFilter { extractor, id });
+        assert!(filter.is_filtering());
+        assert_eq!(filter.apply(&buffer), &[0, 2, 5]);
+    }
+
+    #[test]
+    fn test_cycle_between_lines_of_a_request() {
+        let buffer = request_buffer();
+        let extractor = CorrelationExtractor::new(&CorrelationConfig::default()).unwrap();
+
+        let next = cycle_correlated_line(&buffer, &extractor, None, 1, true);
+        assert_eq!(next, Some((4, 2, 2)));
+        // Wraps around
+        assert_eq!(
+            cycle_correlated_line(&buffer, &extractor, None, 4, true),
+            Some((1, 1, 2))
+        );
+        assert_eq!(
+            cycle_correlated_line(&buffer, &extractor, None, 2, false),
+            Some((0, 1, 3))
+        );
+        // Lines without an id have nothing to cycle through
+        assert_eq!(
+            cycle_correlated_line(&buffer, &extractor, None, 3, true),
+            None
+        );
+    }
+
+    #[test]
+    fn test_grok_field_and_json_ids() {
+        let mut entry = LogEntry::new(1, r#"{"traceId": "7f3a-01", "msg": "hi"}"#.to_string(), 0);
+        let regex = CorrelationExtractor::new(&CorrelationConfig::default()).unwrap();
+        assert_eq!(regex.extract(&entry), Some("7f3a-01"));
+
+        let config = CorrelationConfig {
+            source: CorrelationSource::GrokField,
+            field: "trace".to_string(),
+            ..Default::default()
+        };
+        let field = CorrelationExtractor::new(&config).unwrap();
+        assert!(field.needs_grok_fields());
+        assert_eq!(field.extract(&entry), None);
+        entry.set_grok_fields([("trace".to_string(), "t-9".to_string())].into());
+        assert_eq!(field.extract(&entry), Some("t-9"));
+    }
+}
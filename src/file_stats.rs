@@ -0,0 +1,254 @@
+//! Background full-file statistics
+//!
+//! Lazily-loaded files only keep a window of lines in memory, so the buffer
+//! can't report accurate totals. `FileStatsScanner` walks the whole file on a
+//! background thread and progressively publishes line counts and a level
+//! breakdown. The scan can be cancelled at any time (e.g. when the tab closes).
+
+use crate::log_buffer::LevelCounts;
+use crate::log_entry::LogEntry;
+use encoding_rs::Encoding;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Read buffer size for the scan
+const READ_BUFFER_SIZE: usize = 1024 * 1024;
+/// How often partial results are published
+const PUBLISH_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Statistics for a whole file
+#[derive(Debug, Clone, Default)]
+pub struct FileStats {
+    /// Number of lines scanned so far
+    pub total_lines: usize,
+    /// Number of lines per detected level
+    pub level_counts: LevelCounts,
+    /// Bytes scanned so far
+    pub bytes_scanned: u64,
+    /// File size when the scan started
+    pub total_bytes: u64,
+    /// Whether the scan finished
+    pub complete: bool,
+}
+
+impl FileStats {
+    /// Scan progress (0.0 - 1.0)
+    pub fn progress(&self) -> f32 {
+        if self.complete || self.total_bytes == 0 {
+            1.0
+        } else {
+            (self.bytes_scanned as f64 / self.total_bytes as f64).min(1.0) as f32
+        }
+    }
+}
+
+/// Cancellable background scanner producing `FileStats`
+pub struct FileStatsScanner {
+    /// Shared, progressively updated statistics
+    stats: Arc<Mutex<FileStats>>,
+    /// Cancellation flag
+    cancel: Arc<AtomicBool>,
+}
+
+impl FileStatsScanner {
+    /// Start scanning the file in a background thread, decoding lines with
+    /// `encoding`. Returns `None` for UTF-16, whose lines don't end in a
+    /// single `\n` byte.
+    pub fn start(path: impl AsRef<Path>, encoding: &'static Encoding) -> Option<Self> {
+        if encoding == encoding_rs::UTF_16LE || encoding == encoding_rs::UTF_16BE {
+            return None;
+        }
+
+        let path = path.as_ref().to_path_buf();
+        let stats = Arc::new(Mutex::new(FileStats::default()));
+        let cancel = Arc::new(AtomicBool::new(false));
+
+        let thread_stats = stats.clone();
+        let thread_cancel = cancel.clone();
+        thread::spawn(move || {
+            if let Err(e) = Self::scan(&path, encoding, &thread_stats, &thread_cancel) {
+                tracing::warn!("File stats scan failed for {}: {}", path.display(), e);
+            }
+        });
+
+        Some(Self { stats, cancel })
+    }
+
+    /// Get a snapshot of the current statistics
+    pub fn snapshot(&self) -> FileStats {
+        self.stats.lock().map(|s| s.clone()).unwrap_or_default()
+    }
+
+    /// Cancel the scan
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    /// Check if the scan is still running
+    #[allow(dead_code)]
+    pub fn is_running(&self) -> bool {
+        !self.cancel.load(Ordering::Relaxed) && !self.snapshot().complete
+    }
+
+    /// Scan the file, publishing partial results as it goes
+    fn scan(
+        path: &Path,
+        encoding: &'static Encoding,
+        stats: &Mutex<FileStats>,
+        cancel: &AtomicBool,
+    ) -> std::io::Result<()> {
+        let file = File::open(path)?;
+        let total_bytes = file.metadata()?.len();
+
+        if let Ok(mut s) = stats.lock() {
+            s.total_bytes = total_bytes;
+        }
+
+        let mut reader = BufReader::with_capacity(READ_BUFFER_SIZE, file);
+        let mut local = FileStats {
+            total_bytes,
+            ..Default::default()
+        };
+        let mut line_buffer = Vec::new();
+        let mut last_publish = Instant::now();
+        let mut lines_since_publish = 0usize;
+
+        loop {
+            if cancel.load(Ordering::Relaxed) {
+                return Ok(());
+            }
+
+            line_buffer.clear();
+            let bytes_read = reader.read_until(b'\n', &mut line_buffer)?;
+            if bytes_read == 0 {
+                break;
+            }
+
+            local.total_lines += 1;
+            local.bytes_scanned += bytes_read as u64;
+            lines_since_publish += 1;
+
+            let (line, _, _) = encoding.decode(&line_buffer);
+            local.level_counts.add(LogEntry::detect_level(&line));
+
+            // Only check the clock every few thousand lines
+            if lines_since_publish >= 4096 {
+                lines_since_publish = 0;
+                if last_publish.elapsed() >= PUBLISH_INTERVAL {
+                    last_publish = Instant::now();
+                    if let Ok(mut s) = stats.lock() {
+                        *s = local.clone();
+                    }
+                }
+            }
+        }
+
+        local.complete = true;
+        if let Ok(mut s) = stats.lock() {
+            *s = local;
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for FileStatsScanner {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log_entry::LogLevel;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_totals_converge_to_true_count() {
+        let mut file = NamedTempFile::new().unwrap();
+        for i in 0..20_000 {
+            let level = match i % 4 {
+                0 => "INFO",
+                1 => "DEBUG",
+                2 => "WARN",
+                _ => "ERROR",
+            };
+            writeln!(file, "2024-01-15 10:30:45 {} line {}", level, i).unwrap();
+        }
+        file.flush().unwrap();
+
+        let scanner = FileStatsScanner::start(file.path(), encoding_rs::UTF_8).unwrap();
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let mut last_total = 0;
+        loop {
+            let stats = scanner.snapshot();
+            // Partial totals never go backwards
+            assert!(stats.total_lines >= last_total);
+            last_total = stats.total_lines;
+            if stats.complete || Instant::now() > deadline {
+                break;
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+
+        let stats = scanner.snapshot();
+        assert!(stats.complete);
+        assert_eq!(stats.total_lines, 20_000);
+        assert_eq!(stats.level_counts.get(LogLevel::Error), 5_000);
+        assert_eq!(stats.level_counts.get(LogLevel::Warn), 5_000);
+        assert_eq!(stats.progress(), 1.0);
+    }
+
+    #[test]
+    fn test_cancel_stops_scan() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "INFO one").unwrap();
+        file.flush().unwrap();
+
+        let scanner = FileStatsScanner::start(file.path(), encoding_rs::UTF_8).unwrap();
+        scanner.cancel();
+        assert!(!scanner.is_running());
+    }
+
+    #[test]
+    fn test_scan_decodes_with_file_encoding() {
+        // In GBK `81 45` is one character, so there is no "ERROR" in these
+        // lines; decoded as UTF-8 the trail byte would read as an `E`
+        let mut file = NamedTempFile::new().unwrap();
+        for _ in 0..10 {
+            file.write_all(b"2024-01-15 \x81\x45RROR\n").unwrap();
+        }
+        file.flush().unwrap();
+
+        let scan = |encoding| {
+            let scanner = FileStatsScanner::start(file.path(), encoding).unwrap();
+            let deadline = Instant::now() + Duration::from_secs(10);
+            while !scanner.snapshot().complete && Instant::now() < deadline {
+                thread::sleep(Duration::from_millis(5));
+            }
+            scanner.snapshot()
+        };
+
+        let stats = scan(encoding_rs::GBK);
+        assert_eq!(stats.total_lines, 10);
+        assert_eq!(stats.level_counts.get(LogLevel::Error), 0);
+        assert_eq!(
+            scan(encoding_rs::UTF_8).level_counts.get(LogLevel::Error),
+            10
+        );
+    }
+
+    #[test]
+    fn test_utf16_is_not_scanned() {
+        let file = NamedTempFile::new().unwrap();
+        assert!(FileStatsScanner::start(file.path(), encoding_rs::UTF_16LE).is_none());
+        assert!(FileStatsScanner::start(file.path(), encoding_rs::UTF_16BE).is_none());
+    }
+}
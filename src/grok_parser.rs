@@ -24,6 +24,37 @@ pub enum PreProcessor {
     JsonField(String),
 }
 
+/// Name of the JSON parsing mode, shown where a pattern name would be
+pub const JSON_MODE_NAME: &str = "JSON";
+
+/// Structured log keys (JSON, logfmt) renamed to the common field names,
+/// by field name
+const FIELD_KEY_ALIASES: [(&str, &[&str]); 3] = [
+    ("timestamp", &["timestamp", "ts", "time", "@timestamp"]),
+    ("level", &["level", "lvl", "severity", "loglevel"]),
+    ("message", &["message", "msg"]),
+];
+
+/// Lines sampled for the columns of JSON mode
+const JSON_COLUMN_SAMPLE: usize = 200;
+
+/// Lines sampled when auto-detecting a builtin pattern
+pub const DETECT_SAMPLE_LINES: usize = 50;
+
+/// Match rate a builtin pattern needs to be picked automatically
+pub const DETECT_MIN_MATCH_RATE: f32 = 0.6;
+
+/// A builtin pattern scored against sample lines
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PatternCandidate {
+    /// The scored pattern
+    pub pattern: BuiltinPattern,
+    /// Share of sample lines it matched (0.0-1.0)
+    pub match_rate: f32,
+    /// Average number of non-empty fields captured per matched line
+    pub avg_fields: f32,
+}
+
 /// Built-in grok pattern templates
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum BuiltinPattern {
@@ -37,6 +68,8 @@ pub enum BuiltinPattern {
     Log4j,
     /// JSON log format
     JsonLog,
+    /// logfmt `key=value` pairs (tokenized rather than matched)
+    Logfmt,
     /// Simple timestamp + level + message
     SimpleLog,
     /// Docker/Container logs
@@ -62,6 +95,7 @@ impl BuiltinPattern {
             BuiltinPattern::Syslog,
             BuiltinPattern::Log4j,
             BuiltinPattern::JsonLog,
+            BuiltinPattern::Logfmt,
             BuiltinPattern::SimpleLog,
             BuiltinPattern::DockerLog,
             BuiltinPattern::KubernetesLog,
@@ -72,6 +106,18 @@ impl BuiltinPattern {
         ]
     }
 
+    /// Whether the pattern describes a whole line format, rather than
+    /// extracting a fragment that appears in many formats
+    pub fn is_line_format(&self) -> bool {
+        !matches!(
+            self,
+            BuiltinPattern::JavaStackTrace
+                | BuiltinPattern::PythonException
+                | BuiltinPattern::Timestamp
+                | BuiltinPattern::IpAddress
+        )
+    }
+
     /// Get the display name for the pattern
     pub fn display_name(&self) -> &'static str {
         match self {
@@ -80,6 +126,7 @@ impl BuiltinPattern {
             BuiltinPattern::Syslog => "Syslog",
             BuiltinPattern::Log4j => "Log4j/Logback",
             BuiltinPattern::JsonLog => "JSON Log",
+            BuiltinPattern::Logfmt => "Logfmt",
             BuiltinPattern::SimpleLog => "Simple Log",
             BuiltinPattern::DockerLog => "Docker Log",
             BuiltinPattern::KubernetesLog => "Kubernetes Log",
@@ -108,6 +155,8 @@ impl BuiltinPattern {
             BuiltinPattern::JsonLog => {
                 r#"\{.*"timestamp"\s*:\s*"%{TIMESTAMP_ISO8601:timestamp}".*"level"\s*:\s*"%{LOGLEVEL:level}".*"message"\s*:\s*"%{DATA:message}".*\}"#
             }
+            // Tokenized by parse_logfmt_line, no grok pattern
+            BuiltinPattern::Logfmt => "",
             BuiltinPattern::SimpleLog => {
                 r#"%{TIMESTAMP_ISO8601:timestamp}\s+%{LOGLEVEL:level}\s+%{GREEDYDATA:message}"#
             }
@@ -140,6 +189,7 @@ impl BuiltinPattern {
             BuiltinPattern::Syslog => "%{timestamp} %{hostname} %{program}: %{message}",
             BuiltinPattern::Log4j => "%{timestamp} [%{level}] %{logger} - %{message}",
             BuiltinPattern::JsonLog => "%{timestamp} [%{level}] %{message}",
+            BuiltinPattern::Logfmt => "", // Laid out from the parsed pairs
             BuiltinPattern::SimpleLog => "%{timestamp} [%{level}] %{message}",
             BuiltinPattern::DockerLog => "%{timestamp} [%{stream}] %{message}",
             BuiltinPattern::KubernetesLog => {
@@ -172,6 +222,243 @@ pub struct CustomPattern {
     /// Pre-processor to apply before Grok matching (e.g., extract "log" field from JSON)
     #[serde(default)]
     pub pre_processor: PreProcessor,
+    /// Row colors set by numeric field thresholds
+    #[serde(default)]
+    pub color_rules: Vec<FieldColorRule>,
+}
+
+/// Colors a row when a numeric grok field exceeds a threshold
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldColorRule {
+    /// Field name (e.g. "duration_ms")
+    pub field: String,
+    /// The rule applies when the value is greater than this
+    pub above: f64,
+    /// Row color (RGB)
+    pub color: [u8; 3],
+}
+
+impl Default for FieldColorRule {
+    fn default() -> Self {
+        Self {
+            field: String::new(),
+            above: 0.0,
+            color: [244, 67, 54],
+        }
+    }
+}
+
+/// Row color for the parsed fields: the matching rule with the highest
+/// threshold wins. Missing or non-numeric field values never match.
+pub fn threshold_color(
+    rules: &[FieldColorRule],
+    fields: &HashMap<String, String>,
+) -> Option<[u8; 3]> {
+    rules
+        .iter()
+        .filter(|rule| {
+            fields
+                .get(&rule.field)
+                .and_then(|value| value.trim().parse::<f64>().ok())
+                .is_some_and(|value| value > rule.above)
+        })
+        .max_by(|a, b| a.above.total_cmp(&b.above))
+        .map(|rule| rule.color)
+}
+
+/// Names of the fields a grok pattern captures (`%{SYNTAX:name}` and
+/// `(?<name>...)`), in pattern order without duplicates
+pub fn capture_names(pattern: &str) -> Vec<String> {
+    static RE: std::sync::LazyLock<regex::Regex> = std::sync::LazyLock::new(|| {
+        regex::Regex::new(r"%\{[^:}]+:([^:}]+)(?::[^}]+)?\}|\(\?P?<([A-Za-z_][A-Za-z0-9_]*)>")
+            .unwrap()
+    });
+
+    let mut names: Vec<String> = Vec::new();
+    for cap in RE.captures_iter(pattern) {
+        let name = cap.get(1).or_else(|| cap.get(2)).unwrap().as_str();
+        if !names.iter().any(|n| n == name) {
+            names.push(name.to_string());
+        }
+    }
+    names
+}
+
+/// Fields of a line holding a JSON object, or None for any other line.
+/// The usual time, level and message keys (`ts`, `lvl`, `msg`, ...) become
+/// `timestamp`, `level` and `message`, with epoch times turned into local
+/// times; other top-level keys are kept as they are.
+pub fn parse_json_line(text: &str) -> Option<HashMap<String, String>> {
+    let text = text.trim();
+    if !text.starts_with('{') {
+        return None;
+    }
+    let serde_json::Value::Object(object) = serde_json::from_str(text).ok()? else {
+        return None;
+    };
+
+    let mut fields = HashMap::with_capacity(object.len());
+    for (key, value) in object {
+        let alias = field_alias(&key, &fields);
+        let value = match (alias, value) {
+            (Some("timestamp"), serde_json::Value::Number(n)) => n
+                .as_f64()
+                .and_then(epoch_to_local)
+                .unwrap_or_else(|| n.to_string()),
+            (_, serde_json::Value::String(s)) => s,
+            (_, other) => other.to_string(),
+        };
+        fields.insert(alias.map_or(key, str::to_string), value);
+    }
+    Some(fields)
+}
+
+/// Fields of a logfmt line (`key=value key2="value 2" flag`), or None when
+/// the line has no `key=value` pair. Quoted values may hold spaces, `=` and
+/// `\"` escapes, unquoted values run to the next space (`=` included) and
+/// bare keys get an empty value. Keys are renamed as in [`parse_json_line`].
+pub fn parse_logfmt_line(text: &str) -> Option<HashMap<String, String>> {
+    let mut fields = HashMap::new();
+    let mut has_pair = false;
+    let mut chars = text.chars().peekable();
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        if chars.peek().is_none() {
+            break;
+        }
+
+        let mut key = String::new();
+        while let Some(c) = chars.next_if(|c| !c.is_whitespace() && *c != '=') {
+            key.push(c);
+        }
+        let mut value = String::new();
+        let has_value = chars.next_if_eq(&'=').is_some();
+        if has_value {
+            if chars.next_if_eq(&'"').is_some() {
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' => match chars.next() {
+                            Some('n') => value.push('\n'),
+                            Some('t') => value.push('\t'),
+                            Some(escaped) => value.push(escaped),
+                            None => {}
+                        },
+                        c => value.push(c),
+                    }
+                }
+            } else {
+                while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+                    value.push(c);
+                }
+            }
+        }
+        if key.is_empty() {
+            continue;
+        }
+        has_pair |= has_value;
+
+        let alias = field_alias(&key, &fields);
+        if alias == Some("timestamp") {
+            if let Some(local) = value.parse().ok().and_then(epoch_to_local) {
+                value = local;
+            }
+        }
+        fields.insert(alias.map_or(key, str::to_string), value);
+    }
+    has_pair.then_some(fields)
+}
+
+/// Common field name for a structured log key (`ts` → `timestamp`, ...),
+/// unless that field is already set
+fn field_alias(key: &str, fields: &HashMap<String, String>) -> Option<&'static str> {
+    FIELD_KEY_ALIASES
+        .iter()
+        .find(|(_, keys)| keys.contains(&key))
+        .map(|(name, _)| *name)
+        .filter(|name| !fields.contains_key(*name))
+}
+
+/// Local time of seconds (or, for large values, milliseconds) since the epoch
+fn epoch_to_local(epoch: f64) -> Option<String> {
+    let millis = if epoch.abs() >= 1e11 {
+        epoch
+    } else {
+        epoch * 1000.0
+    };
+    let time = chrono::DateTime::from_timestamp_millis(millis as i64)?;
+    Some(
+        time.with_timezone(&chrono::Local)
+            .format("%Y-%m-%dT%H:%M:%S%.3f")
+            .to_string(),
+    )
+}
+
+/// Display of a parsed JSON or logfmt line: time, level and message, then
+/// the other fields as dimmed `key=value` pairs in key order
+fn structured_display(
+    fields: &HashMap<String, String>,
+) -> (String, Vec<crate::log_entry::FormattedSegment>) {
+    use crate::log_entry::FormattedSegment;
+
+    let mut parts: Vec<(String, Option<(u8, u8, u8)>)> = Vec::new();
+    if let Some(timestamp) = fields.get("timestamp") {
+        parts.push((timestamp.clone(), None));
+    }
+    if let Some(level) = fields.get("level") {
+        parts.push((format!("[{}]", level), None));
+    }
+    if let Some(message) = fields.get("message") {
+        parts.push((message.clone(), None));
+    }
+    let mut extra: Vec<_> = fields
+        .iter()
+        .filter(|(key, _)| {
+            !FIELD_KEY_ALIASES
+                .iter()
+                .any(|(name, _)| *name == key.as_str())
+        })
+        .collect();
+    extra.sort();
+    for (key, value) in extra {
+        parts.push((format!("{}={}", key, value), Some((158, 158, 158))));
+    }
+
+    let mut text = String::new();
+    let mut segments = Vec::with_capacity(parts.len() * 2);
+    for (i, (part, color)) in parts.into_iter().enumerate() {
+        if i > 0 {
+            text.push(' ');
+            segments.push(FormattedSegment {
+                text: " ".to_string(),
+                color: None,
+            });
+        }
+        text.push_str(&part);
+        segments.push(FormattedSegment { text: part, color });
+    }
+    (text, segments)
+}
+
+/// Columns for JSON mode: time, level and message, then the other keys
+/// found in the first lines, sorted
+pub fn json_columns<'a>(lines: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let mut keys = std::collections::BTreeSet::new();
+    for fields in lines
+        .into_iter()
+        .take(JSON_COLUMN_SAMPLE)
+        .filter_map(parse_json_line)
+    {
+        keys.extend(fields.into_keys());
+    }
+
+    let mut columns: Vec<String> = FIELD_KEY_ALIASES
+        .iter()
+        .map(|(name, _)| name.to_string())
+        .filter(|name| keys.remove(name))
+        .collect();
+    columns.extend(keys);
+    columns
 }
 
 /// Result of parsing a log line with grok
@@ -200,14 +487,26 @@ pub enum TemplatePart {
     },
 }
 
+/// How a compiled pattern matches lines
+enum Matcher {
+    /// A compiled grok pattern
+    Grok(grok::Pattern),
+    /// Lines that are JSON objects, see [`parse_json_line`]
+    Json,
+    /// logfmt lines, see [`parse_logfmt_line`]
+    Logfmt,
+}
+
 /// Compiled grok pattern for efficient matching
 pub struct CompiledPattern {
-    /// Compiled grok pattern
-    pattern: grok::Pattern,
+    /// How lines are matched
+    pattern: Matcher,
     /// Display template for formatting output
     pub display_template: Option<String>,
     /// Parsed display template (cached for performance)
     pub parsed_template: Option<Vec<TemplatePart>>,
+    /// Captured field names, shown as columns in the column view
+    pub columns: Vec<String>,
 }
 
 impl CompiledPattern {
@@ -253,7 +552,14 @@ impl CompiledPattern {
     /// Match against a log line and extract fields
     /// Match against a log line and extract fields
     pub fn parse(&self, text: &str) -> Option<ParsedFields> {
-        self.pattern.match_against(text).map(|matches| {
+        let pattern = match &self.pattern {
+            Matcher::Grok(pattern) => pattern,
+            Matcher::Json => return parse_json_line(text).map(|fields| ParsedFields { fields }),
+            Matcher::Logfmt => {
+                return parse_logfmt_line(text).map(|fields| ParsedFields { fields })
+            }
+        };
+        pattern.match_against(text).map(|matches| {
             let mut fields = HashMap::new();
             for (name, value) in matches.iter() {
                 fields.insert(name.to_string(), value.to_string());
@@ -262,12 +568,27 @@ impl CompiledPattern {
         })
     }
 
+    /// Whether this is the JSON mode rather than a grok pattern
+    pub fn is_json(&self) -> bool {
+        matches!(self.pattern, Matcher::Json)
+    }
+
+    /// Whether lines are key/value records (JSON, logfmt) whose fields vary
+    /// beyond the columns
+    pub fn is_structured(&self) -> bool {
+        !matches!(self.pattern, Matcher::Grok(_))
+    }
+
     /// Format a log line and generate styled segments
     /// Returns (plain_text, segments) where segments contain styling info
     pub fn format_with_style(
         &self,
         fields: &HashMap<String, String>,
     ) -> Option<(String, Vec<crate::log_entry::FormattedSegment>)> {
+        if self.is_structured() {
+            return Some(structured_display(fields));
+        }
+
         // Use parsed template if available
         if let Some(parts) = &self.parsed_template {
             if parts.is_empty() {
@@ -487,6 +808,7 @@ impl CompiledPattern {
 }
 
 /// Grok parser for log analysis
+#[derive(Clone)]
 pub struct GrokParser {
     /// Grok instance with loaded patterns
     grok: Grok,
@@ -536,9 +858,10 @@ impl GrokParser {
             None
         };
         Some(Arc::new(CompiledPattern {
-            pattern: compiled,
+            pattern: Matcher::Grok(compiled),
             display_template: Some(template),
             parsed_template,
+            columns: capture_names(pattern.pattern()),
         }))
     }
 
@@ -551,6 +874,20 @@ impl GrokParser {
 
     /// Set the active pattern from a builtin
     pub fn set_builtin_pattern(&mut self, pattern: BuiltinPattern) -> Result<()> {
+        if pattern == BuiltinPattern::Logfmt {
+            self.active_pattern = Some(Arc::new(CompiledPattern {
+                pattern: Matcher::Logfmt,
+                display_template: None,
+                parsed_template: None,
+                columns: FIELD_KEY_ALIASES
+                    .iter()
+                    .map(|(name, _)| name.to_string())
+                    .collect(),
+            }));
+            self.active_pattern_name = Some(pattern.display_name().to_string());
+            return Ok(());
+        }
+
         let pattern_str = pattern.pattern();
         let compiled = self
             .grok
@@ -565,9 +902,10 @@ impl GrokParser {
         };
 
         self.active_pattern = Some(Arc::new(CompiledPattern {
-            pattern: compiled,
+            pattern: Matcher::Grok(compiled),
             display_template: Some(template),
             parsed_template,
+            columns: capture_names(pattern_str),
         }));
         self.active_pattern_name = Some(pattern.display_name().to_string());
 
@@ -602,17 +940,97 @@ impl GrokParser {
         };
 
         self.active_pattern = Some(Arc::new(CompiledPattern {
-            pattern: compiled,
+            pattern: Matcher::Grok(compiled),
             display_template: display_template
                 .filter(|s| !s.is_empty())
                 .map(|s| s.to_string()),
             parsed_template,
+            columns: capture_names(pattern_str),
         }));
         self.active_pattern_name = Some(name.to_string());
 
         Ok(())
     }
 
+    /// Score the builtin line formats against sample lines, best first
+    ///
+    /// Patterns are ranked by match rate, then by how many fields they
+    /// capture, so e.g. Combined Log wins over Common Log on combined lines.
+    /// Patterns that match no line are left out.
+    pub fn rank_builtin_patterns(&self, lines: &[&str]) -> Vec<PatternCandidate> {
+        let lines: Vec<&str> = lines
+            .iter()
+            .copied()
+            .filter(|line| !line.trim().is_empty())
+            .collect();
+        if lines.is_empty() {
+            return Vec::new();
+        }
+
+        let mut candidates: Vec<PatternCandidate> = BuiltinPattern::all()
+            .iter()
+            .filter(|p| p.is_line_format())
+            .filter_map(|&pattern| {
+                let compiled = match pattern {
+                    BuiltinPattern::Logfmt => None,
+                    _ => Some(Self::compile_builtin(&self.grok, pattern)?),
+                };
+                let field_counts: Vec<usize> = lines
+                    .iter()
+                    .filter_map(|line| {
+                        let fields = match &compiled {
+                            Some(compiled) => compiled.parse(line)?.fields,
+                            None => parse_logfmt_line(line)?,
+                        };
+                        Some(fields.values().filter(|v| !v.is_empty()).count())
+                    })
+                    .collect();
+                if field_counts.is_empty() {
+                    return None;
+                }
+                Some(PatternCandidate {
+                    pattern,
+                    match_rate: field_counts.len() as f32 / lines.len() as f32,
+                    avg_fields: field_counts.iter().sum::<usize>() as f32
+                        / field_counts.len() as f32,
+                })
+            })
+            .collect();
+
+        candidates.sort_by(|a, b| {
+            b.match_rate
+                .total_cmp(&a.match_rate)
+                .then(b.avg_fields.total_cmp(&a.avg_fields))
+        });
+        candidates
+    }
+
+    /// A copy of this parser with `pattern` active, so a pattern can be
+    /// previewed through exactly the same path used for loaded files
+    pub fn preview_parser(&self, pattern: &CustomPattern) -> Result<GrokParser> {
+        let mut grok = Grok::default();
+        for (def_name, def_pattern) in &self.custom_definitions {
+            grok.add_pattern(def_name, def_pattern);
+        }
+        let fallback_access_pattern = Self::compile_builtin(&grok, BuiltinPattern::CombinedLog);
+
+        let mut parser = GrokParser {
+            grok,
+            active_pattern: None,
+            fallback_access_pattern,
+            active_pattern_name: None,
+            custom_patterns: Vec::new(),
+            custom_definitions: self.custom_definitions.clone(),
+            pre_processor: pattern.pre_processor.clone(),
+        };
+        parser.set_custom_pattern_with_template(
+            &pattern.name,
+            &pattern.pattern,
+            Some(&pattern.display_template),
+        )?;
+        Ok(parser)
+    }
+
     /// Test a custom pattern against a line without mutating parser state
     pub fn test_custom_pattern(
         &self,
@@ -642,16 +1060,60 @@ impl GrokParser {
         };
 
         let compiled_pattern = CompiledPattern {
-            pattern: compiled,
+            pattern: Matcher::Grok(compiled),
             display_template: display_template
                 .filter(|s| !s.is_empty())
                 .map(|s| s.to_string()),
             parsed_template,
+            columns: capture_names(pattern_str),
         };
 
         Ok(compiled_pattern.parse(text))
     }
 
+    /// Parse JSON object lines directly instead of matching a grok pattern,
+    /// with `columns` shown in the column view
+    pub fn set_json_mode(&mut self, columns: Vec<String>) {
+        self.active_pattern = Some(Arc::new(CompiledPattern {
+            pattern: Matcher::Json,
+            display_template: None,
+            parsed_template: None,
+            columns,
+        }));
+        self.active_pattern_name = Some(JSON_MODE_NAME.to_string());
+        self.pre_processor = PreProcessor::None;
+    }
+
+    /// Check if JSON lines are parsed instead of a grok pattern
+    pub fn is_json_mode(&self) -> bool {
+        self.active_pattern.as_ref().is_some_and(|p| p.is_json())
+    }
+
+    /// In JSON mode, give the keys of parsed lines that have no column yet
+    /// (first seen past the lines the columns were sampled from) a column
+    /// after the existing ones. Returns true if any was added.
+    pub fn add_json_columns<'a>(
+        &mut self,
+        parsed: impl IntoIterator<Item = &'a HashMap<String, String>>,
+    ) -> bool {
+        let Some(pattern) = self.active_pattern.as_ref().filter(|p| p.is_json()) else {
+            return false;
+        };
+        let new_keys: std::collections::BTreeSet<&String> = parsed
+            .into_iter()
+            .flat_map(|fields| fields.keys())
+            .filter(|key| !pattern.columns.contains(key))
+            .collect();
+        if new_keys.is_empty() {
+            return false;
+        }
+
+        let mut columns = pattern.columns.clone();
+        columns.extend(new_keys.into_iter().cloned());
+        self.set_json_mode(columns);
+        true
+    }
+
     /// Clear the active pattern
     pub fn clear_pattern(&mut self) {
         self.active_pattern = None;
@@ -673,6 +1135,12 @@ impl GrokParser {
 
     fn parse_with_pattern(&self, text: &str) -> Option<(ParsedFields, Arc<CompiledPattern>)> {
         let active_pattern = self.active_pattern.as_ref()?;
+        if active_pattern.is_json() {
+            // Malformed lines stay raw, there is no fallback
+            return active_pattern
+                .parse(text)
+                .map(|fields| (fields, active_pattern.clone()));
+        }
         let text_to_parse = self.apply_pre_processor(text)?;
 
         if let Some(result) = active_pattern.parse(&text_to_parse) {
@@ -805,9 +1273,10 @@ impl GrokParser {
             .context("Failed to compile pattern")?;
 
         let pattern = CompiledPattern {
-            pattern: compiled,
+            pattern: Matcher::Grok(compiled),
             display_template: None,
             parsed_template: None,
+            columns: capture_names(pattern_str),
         };
 
         // Apply pre-processor before testing the pattern
@@ -851,10 +1320,21 @@ impl GrokParser {
     pub fn import_custom_patterns(&mut self, patterns: Vec<CustomPattern>) {
         self.custom_patterns = patterns;
     }
+
+    /// Color rules of the active custom pattern
+    pub fn active_color_rules(&self) -> &[FieldColorRule] {
+        let Some(name) = self.active_pattern_name.as_deref() else {
+            return &[];
+        };
+        self.custom_patterns
+            .iter()
+            .find(|p| p.name == name)
+            .map_or(&[], |p| p.color_rules.as_slice())
+    }
 }
 
 /// Grok configuration for persistence
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GrokConfig {
     /// Whether grok parsing is enabled
     pub enabled: bool,
@@ -869,4 +1349,322 @@ pub struct GrokConfig {
     /// Pre-processor to apply before Grok matching
     #[serde(default)]
     pub pre_processor: PreProcessor,
+    /// Patterns applied automatically to files matching a glob
+    #[serde(default)]
+    pub glob_rules: Vec<GrokGlobRule>,
+    /// Files with up to this many lines are parsed in full instead of only
+    /// the visible lines (0 = always visible-only)
+    #[serde(default = "default_eager_parse_max_lines")]
+    pub eager_parse_max_lines: usize,
+    /// Column view widths by pattern name, then field name
+    #[serde(default)]
+    pub column_widths: HashMap<String, HashMap<String, f32>>,
+}
+
+fn default_eager_parse_max_lines() -> usize {
+    20_000
+}
+
+impl Default for GrokConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            builtin_pattern: None,
+            custom_pattern_name: None,
+            custom_patterns: Vec::new(),
+            custom_definitions: HashMap::new(),
+            pre_processor: PreProcessor::default(),
+            glob_rules: Vec::new(),
+            eager_parse_max_lines: default_eager_parse_max_lines(),
+            column_widths: HashMap::new(),
+        }
+    }
+}
+
+/// Applies a pattern to files whose name (or path) matches a glob, e.g.
+/// `*.access.log` → "Combined Log". Globs containing `/` match the full path.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GrokGlobRule {
+    /// Glob with `*` (any run of characters) and `?` (one character)
+    pub glob: String,
+    /// Custom pattern name or builtin pattern display name
+    pub pattern: String,
+}
+
+impl GrokGlobRule {
+    /// Whether the rule applies to a file
+    pub fn matches(&self, path: &std::path::Path) -> bool {
+        let glob = self.glob.trim();
+        if glob.is_empty() {
+            return false;
+        }
+        let target = if glob.contains('/') {
+            path.to_string_lossy().replace('\\', "/")
+        } else {
+            match path.file_name() {
+                Some(name) => name.to_string_lossy().to_string(),
+                None => return false,
+            }
+        };
+        wildcard_match(glob, &target)
+    }
+}
+
+/// Match `text` against a glob with `*` and `?` wildcards
+fn wildcard_match(glob: &str, text: &str) -> bool {
+    let glob: Vec<char> = glob.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut g, mut t) = (0, 0);
+    // Position of the last `*` and the text position it currently covers up to
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match glob.get(g) {
+            Some('*') => {
+                backtrack = Some((g, t));
+                g += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                g += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star, covered)) => {
+                    // Let the last `*` swallow one more character
+                    g = star + 1;
+                    t = covered + 1;
+                    backtrack = Some((star, covered + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    glob[g..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_rule_matching() {
+        let rule = |glob: &str| GrokGlobRule {
+            glob: glob.to_string(),
+            pattern: "Combined Log".to_string(),
+        };
+        let path = std::path::Path::new("/var/log/nginx/site.access.log");
+
+        assert!(rule("*.access.log").matches(path));
+        assert!(rule("site.*").matches(path));
+        assert!(rule("s?te*log").matches(path));
+        assert!(rule("*").matches(path));
+        assert!(!rule("*.error.log").matches(path));
+        assert!(!rule("access.log").matches(path));
+        assert!(!rule("").matches(path));
+        // Globs with a slash match the whole path
+        assert!(rule("/var/log/nginx/*").matches(path));
+        assert!(!rule("/var/log/app/*").matches(path));
+    }
+
+    #[test]
+    fn test_threshold_color_mapping() {
+        let red = [244, 67, 54];
+        let yellow = [255, 193, 7];
+        let rules = vec![
+            FieldColorRule {
+                field: "duration_ms".to_string(),
+                above: 500.0,
+                color: yellow,
+            },
+            FieldColorRule {
+                field: "duration_ms".to_string(),
+                above: 1000.0,
+                color: red,
+            },
+        ];
+        let fields = |value: &str| HashMap::from([("duration_ms".to_string(), value.to_string())]);
+
+        assert_eq!(threshold_color(&rules, &fields("1500")), Some(red));
+        assert_eq!(threshold_color(&rules, &fields(" 750.5 ")), Some(yellow));
+        // Thresholds are exclusive
+        assert_eq!(threshold_color(&rules, &fields("500")), None);
+        assert_eq!(threshold_color(&rules, &fields("12")), None);
+
+        // Non-numeric or missing values get no color
+        assert_eq!(threshold_color(&rules, &fields("slow")), None);
+        assert_eq!(threshold_color(&rules, &fields("1500ms")), None);
+        assert_eq!(threshold_color(&rules, &HashMap::new()), None);
+        assert_eq!(threshold_color(&[], &fields("1500")), None);
+    }
+
+    #[test]
+    fn test_capture_names_follow_pattern_order() {
+        assert_eq!(
+            capture_names(BuiltinPattern::Log4j.pattern()),
+            ["timestamp", "thread", "level", "logger", "message"]
+        );
+        // Repeated captures appear once; unnamed and regex groups are handled
+        assert_eq!(
+            capture_names(BuiltinPattern::JavaStackTrace.pattern()),
+            ["class", "message", "method", "file", "line"]
+        );
+        assert_eq!(
+            capture_names(r"%{IP} (?<user>\w+) %{NUMBER:ms:float}"),
+            ["user", "ms"]
+        );
+    }
+
+    #[test]
+    fn test_json_columns_grow_with_new_keys() {
+        let mut parser = GrokParser::new();
+        parser.set_json_mode(json_columns([r#"{"level":"info","msg":"up","port":80}"#]));
+
+        let (known, _) = parser
+            .parse_with_format(r#"{"level":"warn","msg":"slow"}"#)
+            .unwrap();
+        assert!(!parser.add_json_columns([&known.fields]));
+
+        // Keys first seen later get columns after the sampled ones
+        let (later, _) = parser
+            .parse_with_format(r#"{"level":"error","msg":"down","user":"bob","code":7}"#)
+            .unwrap();
+        assert!(parser.add_json_columns([&later.fields]));
+        assert_eq!(
+            parser.active_pattern().unwrap().columns,
+            ["level", "message", "port", "code", "user"]
+        );
+        assert!(parser.is_json_mode());
+
+        // Grok patterns keep their capture names
+        parser
+            .set_builtin_pattern(BuiltinPattern::all()[0])
+            .unwrap();
+        let columns = parser.active_pattern().unwrap().columns.clone();
+        assert!(!parser.add_json_columns([&later.fields]));
+        assert_eq!(parser.active_pattern().unwrap().columns, columns);
+    }
+
+    #[test]
+    fn test_json_mode_extracts_common_fields() {
+        let lines = [
+            r#"{"ts":"2024-01-15T10:00:00Z","lvl":"warn","msg":"slow query","ms":812,"db":{"name":"users"}}"#,
+            r#"{"level":"error","message":"boom","host":"a1"}"#,
+            "plain text line",
+            r#"{"level":"info", truncated"#,
+        ];
+        let mut parser = GrokParser::new();
+        parser.set_json_mode(json_columns(lines));
+        assert!(parser.is_json_mode());
+        assert_eq!(
+            parser.active_pattern().unwrap().columns,
+            ["timestamp", "level", "message", "db", "host", "ms"]
+        );
+
+        let (fields, formatted) = parser.parse_with_format(lines[0]).unwrap();
+        assert_eq!(fields.fields["timestamp"], "2024-01-15T10:00:00Z");
+        assert_eq!(fields.fields["level"], "warn");
+        assert_eq!(fields.fields["message"], "slow query");
+        assert_eq!(fields.fields["ms"], "812");
+        assert_eq!(fields.fields["db"], r#"{"name":"users"}"#);
+        assert_eq!(
+            formatted.unwrap().0,
+            r#"2024-01-15T10:00:00Z [warn] slow query db={"name":"users"} ms=812"#
+        );
+
+        // Malformed and non-JSON lines are left raw
+        assert!(parser.parse_with_format(lines[2]).is_none());
+        assert!(parser.parse_with_format(lines[3]).is_none());
+
+        // Epoch times become local timestamps the entry picks up
+        let fields = parse_json_line(r#"{"time":1705312800123,"msg":"tick"}"#).unwrap();
+        let mut entry = crate::log_entry::LogEntry::new(1, "tick".to_string(), 0);
+        assert!(entry.timestamp.is_none());
+        entry.set_grok_fields(fields);
+        let expected = chrono::DateTime::from_timestamp_millis(1705312800123)
+            .unwrap()
+            .with_timezone(&chrono::Local)
+            .naive_local();
+        assert_eq!(entry.timestamp.unwrap().naive_utc(), expected);
+        entry.clear_grok_fields();
+        assert!(entry.timestamp.is_none());
+    }
+
+    #[test]
+    fn test_logfmt_pattern_tokenizes_pairs() {
+        let line = r#"ts=2024-01-15T10:00:00Z level=warn msg="slow \"users\" query" query=a=b retry dur=812ms"#;
+        let fields = parse_logfmt_line(line).unwrap();
+        assert_eq!(fields["timestamp"], "2024-01-15T10:00:00Z");
+        assert_eq!(fields["level"], "warn");
+        assert_eq!(fields["message"], r#"slow "users" query"#);
+        assert_eq!(fields["query"], "a=b");
+        assert_eq!(fields["retry"], "");
+        assert_eq!(fields["dur"], "812ms");
+
+        assert!(parse_logfmt_line("Starting server on port 8080").is_none());
+        assert!(parse_logfmt_line("").is_none());
+
+        let mut parser = GrokParser::new();
+        parser.set_builtin_pattern(BuiltinPattern::Logfmt).unwrap();
+        assert!(BuiltinPattern::all().contains(&BuiltinPattern::Logfmt));
+        assert_eq!(parser.active_pattern_name(), Some("Logfmt"));
+        let (fields, formatted) = parser.parse_with_format(line).unwrap();
+        assert_eq!(fields.fields["query"], "a=b");
+        assert_eq!(
+            formatted.unwrap().0,
+            r#"2024-01-15T10:00:00Z [warn] slow "users" query dur=812ms query=a=b retry="#
+        );
+    }
+
+    #[test]
+    fn test_rank_builtin_patterns_prefers_the_matching_format() {
+        let parser = GrokParser::new();
+        let lines = [
+            "2024-01-15 10:00:00,123 [main] INFO com.example.App - Server started",
+            "2024-01-15 10:00:01,456 [worker-1] WARN com.example.Db - Slow query",
+            "    continuation of the previous message",
+            "2024-01-15 10:00:02,789 [worker-2] ERROR com.example.Api - Request failed",
+        ];
+        let ranked = parser.rank_builtin_patterns(&lines);
+        assert_eq!(ranked[0].pattern, BuiltinPattern::Log4j);
+        assert_eq!(ranked[0].match_rate, 0.75);
+        assert!(ranked[0].match_rate >= DETECT_MIN_MATCH_RATE);
+        assert!(ranked.iter().all(|c| c.pattern.is_line_format()));
+
+        let prose = ["hello there", "nothing to see here", "just words"];
+        let ranked = parser.rank_builtin_patterns(&prose);
+        assert!(ranked
+            .first()
+            .is_none_or(|c| c.match_rate < DETECT_MIN_MATCH_RATE));
+    }
+
+    #[test]
+    fn test_preview_parser_applies_pattern_like_a_loaded_file() {
+        let parser = GrokParser::new();
+        let pattern = CustomPattern {
+            name: "draft".to_string(),
+            description: String::new(),
+            pattern: "%{WORD:level} %{GREEDYDATA:message}".to_string(),
+            example: String::new(),
+            enabled: true,
+            display_template: "[%{level}] %{message}".to_string(),
+            pre_processor: PreProcessor::JsonField("log".to_string()),
+            color_rules: Vec::new(),
+        };
+        let preview = parser.preview_parser(&pattern).unwrap();
+
+        let (fields, formatted) = preview
+            .parse_with_format(r#"{"log":"INFO server started\n"}"#)
+            .unwrap();
+        assert_eq!(fields.fields["level"], "INFO");
+        assert_eq!(formatted.unwrap().0, "[INFO] server started");
+        assert!(preview.parse_with_format("!!!").is_none());
+        // The parser being edited is left untouched
+        assert!(!parser.has_active_pattern());
+
+        let broken = CustomPattern {
+            pattern: "%{NOT_A_PATTERN:x}".to_string(),
+            ..pattern
+        };
+        assert!(parser.preview_parser(&broken).is_err());
+    }
 }
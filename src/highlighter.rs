@@ -1,10 +1,18 @@
 //! Syntax highlighting for log entries
 
+use crate::ansi;
+use crate::config::{HighlightRule, StripPrefixConfig};
 use crate::log_entry::LogLevel;
+use crate::search::fuzzy_line_match;
 use egui::{text::LayoutJob, Color32, TextFormat};
 use regex::Regex;
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
 use std::sync::LazyLock;
 
+/// Maximum number of entries kept in the highlight cache
+const HIGHLIGHT_CACHE_CAPACITY: usize = 8192;
+
 /// Theme colors for syntax highlighting
 #[derive(Debug, Clone)]
 pub struct HighlightTheme {
@@ -80,32 +88,184 @@ impl Default for HighlightTheme {
     }
 }
 
+/// Cached highlight results per entry, keyed by the inputs that produced them
+#[derive(Debug, Default)]
+struct HighlightCache {
+    /// Line number -> (input key, highlighted job)
+    entries: HashMap<usize, (u64, LayoutJob)>,
+}
+
+impl HighlightCache {
+    /// Return the cached job for a line if its inputs are unchanged,
+    /// otherwise compute and store it
+    fn get_or_compute(
+        &mut self,
+        line_number: usize,
+        key: u64,
+        compute: impl FnOnce() -> LayoutJob,
+    ) -> LayoutJob {
+        if let Some((cached_key, job)) = self.entries.get(&line_number) {
+            if *cached_key == key {
+                return job.clone();
+            }
+        }
+
+        // Keep memory bounded; visible rows repopulate the cache quickly
+        if self.entries.len() >= HIGHLIGHT_CACHE_CAPACITY {
+            self.entries.clear();
+        }
+
+        let job = compute();
+        self.entries.insert(line_number, (key, job.clone()));
+        job
+    }
+
+    /// Drop all cached results
+    fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
 /// Syntax highlighter for log content
 pub struct Highlighter {
     /// Current theme
     pub theme: HighlightTheme,
     /// Whether highlighting is enabled
     pub enabled: bool,
+    /// Render ANSI color sequences instead of showing them raw
+    pub render_ansi: bool,
+    /// Highlight the characters of a fuzzy match instead of the exact query
+    pub fuzzy_search: bool,
+    /// Base text color of each level, indexed by `level as usize`
+    pub level_colors: [Color32; 6],
+    /// User highlight rules the compiled ones were built from
+    rules: Vec<HighlightRule>,
+    /// Enabled, valid highlight rules
+    compiled_rules: Vec<(Regex, Color32)>,
+    /// Highlight results of recently rendered entries
+    cache: HighlightCache,
 }
 
 impl Highlighter {
     /// Create a new highlighter with default theme
     pub fn new() -> Self {
-        Self {
-            theme: HighlightTheme::default(),
-            enabled: true,
-        }
+        Self::with_theme(HighlightTheme::default())
     }
 
     /// Create with specific theme
-    #[allow(dead_code)]
     pub fn with_theme(theme: HighlightTheme) -> Self {
         Self {
             theme,
             enabled: true,
+            render_ansi: true,
+            fuzzy_search: false,
+            level_colors: LogLevel::all().map(|level| level.color()),
+            rules: Vec::new(),
+            compiled_rules: Vec::new(),
+            cache: HighlightCache::default(),
         }
     }
 
+    /// Change the theme (invalidates cached highlights)
+    pub fn set_theme(&mut self, theme: HighlightTheme) {
+        self.theme = theme;
+        self.cache.clear();
+    }
+
+    /// Use these highlight rules (invalidates cached highlights when they changed).
+    /// Disabled rules and invalid regexes are skipped.
+    pub fn set_rules(&mut self, rules: &[HighlightRule]) {
+        if self.rules == rules {
+            return;
+        }
+        self.rules = rules.to_vec();
+        self.compiled_rules = rules
+            .iter()
+            .filter(|rule| rule.enabled && !rule.pattern.is_empty())
+            .filter_map(|rule| {
+                let pattern = if rule.regex {
+                    rule.pattern.clone()
+                } else {
+                    regex::escape(&rule.pattern)
+                };
+                let pattern = if rule.whole_word {
+                    format!(r"\b(?:{})\b", pattern)
+                } else {
+                    pattern
+                };
+                let [r, g, b] = rule.color;
+                Some((Regex::new(&pattern).ok()?, Color32::from_rgb(r, g, b)))
+            })
+            .collect();
+        self.cache.clear();
+    }
+
+    /// Base text color for a line of the given level
+    fn level_color(&self, level: Option<LogLevel>) -> Color32 {
+        level.map_or(self.theme.text, |level| self.level_colors[level as usize])
+    }
+
+    /// Highlight an entry's line, reusing the previous result when the
+    /// content, query and settings haven't changed since the last frame
+    #[allow(clippy::too_many_arguments)]
+    pub fn highlight_line_cached(
+        &mut self,
+        line_number: usize,
+        content: &str,
+        level: Option<LogLevel>,
+        search_query: Option<&str>,
+        case_sensitive: bool,
+        wrap_width: f32,
+        letter_spacing: f32,
+    ) -> LayoutJob {
+        let key = self.cache_key(
+            content,
+            level,
+            search_query,
+            case_sensitive,
+            wrap_width,
+            letter_spacing,
+        );
+
+        let mut cache = std::mem::take(&mut self.cache);
+        let job = cache.get_or_compute(line_number, key, || {
+            self.highlight_line_with_wrap(
+                content,
+                level,
+                search_query,
+                case_sensitive,
+                wrap_width,
+                letter_spacing,
+            )
+        });
+        self.cache = cache;
+        job
+    }
+
+    /// Hash of everything a highlighted row depends on
+    fn cache_key(
+        &self,
+        content: &str,
+        level: Option<LogLevel>,
+        search_query: Option<&str>,
+        case_sensitive: bool,
+        wrap_width: f32,
+        letter_spacing: f32,
+    ) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        content.hash(&mut hasher);
+        level.hash(&mut hasher);
+        search_query.filter(|q| !q.is_empty()).hash(&mut hasher);
+        case_sensitive.hash(&mut hasher);
+        wrap_width.to_bits().hash(&mut hasher);
+        letter_spacing.to_bits().hash(&mut hasher);
+        self.enabled.hash(&mut hasher);
+        self.render_ansi.hash(&mut hasher);
+        self.fuzzy_search.hash(&mut hasher);
+        self.level_colors.hash(&mut hasher);
+        hasher.finish()
+    }
+
     /// Highlight a log line and return a LayoutJob for egui
     #[allow(dead_code)]
     pub fn highlight_line(
@@ -135,6 +295,17 @@ impl Highlighter {
         wrap_width: f32,
         letter_spacing: f32,
     ) -> LayoutJob {
+        if self.render_ansi && ansi::contains_ansi(content) {
+            return self.highlight_ansi_line(
+                content,
+                level,
+                search_query,
+                case_sensitive,
+                wrap_width,
+                letter_spacing,
+            );
+        }
+
         let mut job = LayoutJob::default();
         job.wrap.max_width = wrap_width;
 
@@ -143,7 +314,7 @@ impl Highlighter {
                 content,
                 0.0,
                 TextFormat {
-                    color: level.map(|l| l.color()).unwrap_or(self.theme.text),
+                    color: self.level_color(level),
                     extra_letter_spacing: letter_spacing,
                     ..Default::default()
                 },
@@ -152,7 +323,7 @@ impl Highlighter {
         }
 
         // Get base color from log level
-        let base_color = level.map(|l| l.color()).unwrap_or(self.theme.text);
+        let base_color = self.level_color(level);
 
         // Find all highlight ranges
         let mut ranges: Vec<(usize, usize, HighlightType)> = Vec::new();
@@ -169,10 +340,13 @@ impl Highlighter {
         // Find JSON braces and brackets
         Self::find_json_syntax(content, &mut ranges);
 
+        // Find user highlight rule matches
+        self.find_rule_matches(content, &mut ranges);
+
         // Find search matches (highest priority)
         if let Some(query) = search_query {
             if !query.is_empty() {
-                Self::find_search_matches(content, query, case_sensitive, &mut ranges);
+                self.find_search_matches(content, query, case_sensitive, &mut ranges);
             }
         }
 
@@ -182,16 +356,48 @@ impl Highlighter {
         // Remove overlapping ranges (keep higher priority)
         let mut filtered_ranges: Vec<(usize, usize, HighlightType)> = Vec::new();
         for range in ranges {
-            let overlaps = filtered_ranges
-                .iter()
-                .any(|r| range.0 < r.1 && range.1 > r.0);
-            if !overlaps || range.2 == HighlightType::SearchMatch {
-                // Search matches can overlap
-                if range.2 == HighlightType::SearchMatch {
-                    // Remove ranges that search match overlaps with
-                    filtered_ranges.retain(|r| !(range.0 < r.1 && range.1 > r.0));
+            let overlaps = |r: &(usize, usize, HighlightType)| range.0 < r.1 && range.1 > r.0;
+            match range.2 {
+                HighlightType::SearchMatch => {
+                    // Search matches replace what they overlap, except that
+                    // rule highlights stay visible around them
+                    let mut kept = Vec::with_capacity(filtered_ranges.len());
+                    for r in filtered_ranges.drain(..) {
+                        if !overlaps(&r) {
+                            kept.push(r);
+                        } else if let HighlightType::Rule(_) = r.2 {
+                            if r.0 < range.0 {
+                                kept.push((r.0, range.0, r.2));
+                            }
+                            if range.1 < r.1 {
+                                kept.push((range.1, r.1, r.2));
+                            }
+                        }
+                    }
+                    filtered_ranges = kept;
+                    filtered_ranges.push(range);
+                }
+                HighlightType::Rule(_) => {
+                    // Keep only the part after an earlier match or rule it starts in
+                    let start = filtered_ranges
+                        .iter()
+                        .filter(|r| {
+                            matches!(r.2, HighlightType::SearchMatch | HighlightType::Rule(_))
+                                && overlaps(r)
+                        })
+                        .map(|r| r.1)
+                        .fold(range.0, usize::max);
+                    if start < range.1 {
+                        let range = (start, range.1, range.2);
+                        filtered_ranges.retain(|r| !(range.0 < r.1 && range.1 > r.0));
+                        filtered_ranges.push(range);
+                    }
+                }
+                _ => {
+                    if !filtered_ranges.iter().any(overlaps) {
+                        filtered_ranges.push(range);
+                    }
                 }
-                filtered_ranges.push(range);
             }
         }
         filtered_ranges.sort_by_key(|r| r.0);
@@ -234,6 +440,17 @@ impl Highlighter {
                     extra_letter_spacing: letter_spacing,
                     ..Default::default()
                 },
+                HighlightType::Rule(color) => TextFormat {
+                    color,
+                    background: Color32::from_rgba_unmultiplied(
+                        color.r(),
+                        color.g(),
+                        color.b(),
+                        40,
+                    ),
+                    extra_letter_spacing: letter_spacing,
+                    ..Default::default()
+                },
                 HighlightType::SearchMatch => TextFormat {
                     color: Color32::BLACK,
                     background: self.theme.search_highlight,
@@ -262,6 +479,64 @@ impl Highlighter {
         job
     }
 
+    /// Lay out a line using its own ANSI colors, with search matches on top
+    fn highlight_ansi_line(
+        &self,
+        content: &str,
+        level: Option<LogLevel>,
+        search_query: Option<&str>,
+        case_sensitive: bool,
+        wrap_width: f32,
+        letter_spacing: f32,
+    ) -> LayoutJob {
+        let parsed = ansi::parse_ansi(content);
+        let base_color = self.level_color(level);
+
+        let mut matches = Vec::new();
+        if let Some(query) = search_query.filter(|q| !q.is_empty()) {
+            self.find_search_matches(&parsed.text, query, case_sensitive, &mut matches);
+        }
+
+        let mut job = LayoutJob::default();
+        job.wrap.max_width = wrap_width;
+        for (range, style) in &parsed.spans {
+            // Split each colored span at search match boundaries
+            let mut pos = range.start;
+            while pos < range.end {
+                let current = matches.iter().find(|(start, end, _)| *start <= pos && pos < *end);
+                let end = match current {
+                    Some((_, end, _)) => *end,
+                    None => matches
+                        .iter()
+                        .map(|(start, _, _)| *start)
+                        .filter(|start| *start > pos)
+                        .min()
+                        .unwrap_or(range.end),
+                }
+                .min(range.end);
+
+                let format = if current.is_some() {
+                    TextFormat {
+                        color: Color32::BLACK,
+                        background: self.theme.search_highlight,
+                        extra_letter_spacing: letter_spacing,
+                        ..Default::default()
+                    }
+                } else {
+                    TextFormat {
+                        color: style.display_fg().unwrap_or(base_color),
+                        background: style.bg.unwrap_or(Color32::TRANSPARENT),
+                        extra_letter_spacing: letter_spacing,
+                        ..Default::default()
+                    }
+                };
+                job.append(&parsed.text[pos..end], 0.0, format);
+                pos = end;
+            }
+        }
+        job
+    }
+
     /// Find timestamp patterns in content
     fn find_timestamps(content: &str, ranges: &mut Vec<(usize, usize, HighlightType)>) {
         static TIMESTAMP_REGEX: LazyLock<Regex> = LazyLock::new(|| {
@@ -304,14 +579,32 @@ impl Highlighter {
         }
     }
 
+    /// Find matches of the user highlight rules
+    fn find_rule_matches(&self, content: &str, ranges: &mut Vec<(usize, usize, HighlightType)>) {
+        for (regex, color) in &self.compiled_rules {
+            for m in regex.find_iter(content).filter(|m| !m.is_empty()) {
+                ranges.push((m.start(), m.end(), HighlightType::Rule(*color)));
+            }
+        }
+    }
+
     /// Find search query matches
     fn find_search_matches(
+        &self,
         content: &str,
         query: &str,
         case_sensitive: bool,
         ranges: &mut Vec<(usize, usize, HighlightType)>,
     ) {
-        if case_sensitive {
+        if self.fuzzy_search {
+            if let Some((_, matched)) = fuzzy_line_match(query, content, case_sensitive) {
+                ranges.extend(
+                    matched
+                        .into_iter()
+                        .map(|(start, end)| (start, end, HighlightType::SearchMatch)),
+                );
+            }
+        } else if case_sensitive {
             for (i, _) in content.match_indices(query) {
                 ranges.push((i, i + query.len(), HighlightType::SearchMatch));
             }
@@ -354,6 +647,8 @@ enum HighlightType {
     Number,
     String,
     JsonSyntax,
+    /// User highlight rule, in its color
+    Rule(Color32),
     SearchMatch,
 }
 
@@ -362,6 +657,7 @@ impl HighlightType {
     fn priority(&self) -> u8 {
         match self {
             HighlightType::SearchMatch => 100,
+            HighlightType::Rule(_) => 60,
             HighlightType::String => 50,
             HighlightType::Timestamp => 40,
             HighlightType::Number => 30,
@@ -385,6 +681,42 @@ pub fn colored_text(text: &str, color: Color32) -> LayoutJob {
     job
 }
 
+/// Removes a configured leading portion from lines at display time.
+/// The underlying entry content is left untouched for search and copy.
+#[derive(Debug, Clone)]
+pub struct PrefixStripper {
+    /// Source configuration
+    pub config: StripPrefixConfig,
+    /// Compiled regex (anchored at line start) for regex mode
+    regex: Option<Regex>,
+}
+
+impl PrefixStripper {
+    /// Build a stripper from config, compiling the regex if needed
+    pub fn new(config: StripPrefixConfig) -> Result<Self, regex::Error> {
+        let regex = if config.is_regex {
+            Some(Regex::new(&format!("^(?:{})", config.pattern))?)
+        } else {
+            None
+        };
+        Ok(Self { config, regex })
+    }
+
+    /// Return the line with the configured prefix removed (if present)
+    pub fn strip<'a>(&self, line: &'a str) -> &'a str {
+        if let Some(ref regex) = self.regex {
+            match regex.find(line) {
+                Some(m) => &line[m.end()..],
+                None => line,
+            }
+        } else if self.config.pattern.is_empty() {
+            line
+        } else {
+            line.strip_prefix(self.config.pattern.as_str()).unwrap_or(line)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -408,4 +740,180 @@ mod tests {
         // Should contain the search term
         assert!(job.text.contains("world"));
     }
+
+    #[test]
+    fn test_prefix_stripper_literal() {
+        let stripper = PrefixStripper::new(StripPrefixConfig {
+            pattern: "[pod-1] ".to_string(),
+            is_regex: false,
+        })
+        .unwrap();
+        assert_eq!(stripper.strip("[pod-1] INFO started"), "INFO started");
+        assert_eq!(stripper.strip("INFO no prefix"), "INFO no prefix");
+    }
+
+    #[test]
+    fn test_prefix_stripper_regex_is_display_only() {
+        use crate::log_buffer::LogBuffer;
+        use crate::log_entry::LogEntry;
+        use crate::search::SearchEngine;
+
+        let stripper = PrefixStripper::new(StripPrefixConfig {
+            pattern: r"\S+ [0-9a-f]{12} ".to_string(),
+            is_regex: true,
+        })
+        .unwrap();
+
+        let line = "api-7d9f abcdef012345 ERROR request failed";
+        assert_eq!(stripper.strip(line), "ERROR request failed");
+        // Only matches at the start of the line
+        let unprefixed = "x ERROR api-7d9f abcdef012345 ";
+        assert_eq!(stripper.strip(unprefixed), unprefixed);
+
+        // Original content is kept, so search still matches the stripped portion
+        let mut buffer = LogBuffer::new();
+        buffer.push(LogEntry::new(1, line.to_string(), 0));
+        assert_eq!(buffer.get(0).unwrap().content, line);
+
+        let mut engine = SearchEngine::new();
+        engine.set_query("abcdef012345".to_string());
+        engine.search(&buffer);
+        assert_eq!(engine.result_count(), 1);
+    }
+
+    #[test]
+    fn test_prefix_stripper_invalid_regex() {
+        assert!(PrefixStripper::new(StripPrefixConfig {
+            pattern: "(".to_string(),
+            is_regex: true,
+        })
+        .is_err());
+    }
+
+    /// Render a row through the cache, counting recomputations
+    fn render_cached(
+        cache: &mut HighlightCache,
+        computed: &mut usize,
+        line_number: usize,
+        query: &str,
+    ) -> LayoutJob {
+        let highlighter = Highlighter::new();
+        let key =
+            highlighter.cache_key("ERROR failed", None, Some(query), false, f32::INFINITY, 0.0);
+        cache.get_or_compute(line_number, key, || {
+            *computed += 1;
+            highlighter.highlight_line("ERROR failed", None, Some(query), false)
+        })
+    }
+
+    #[test]
+    fn test_highlight_cache_reuse_and_invalidation() {
+        let mut cache = HighlightCache::default();
+        let mut computed = 0;
+
+        // Two rows rendered over several frames with unchanged inputs
+        for _ in 0..3 {
+            render_cached(&mut cache, &mut computed, 1, "fail");
+            render_cached(&mut cache, &mut computed, 2, "fail");
+        }
+        assert_eq!(computed, 2);
+
+        // Changing the query invalidates the cached row
+        let job = render_cached(&mut cache, &mut computed, 1, "ERROR");
+        render_cached(&mut cache, &mut computed, 1, "ERROR");
+        assert_eq!(computed, 3);
+        assert!(job
+            .sections
+            .iter()
+            .any(|s| s.format.background == HighlightTheme::dark().search_highlight));
+    }
+
+    #[test]
+    fn test_highlight_line_cached_matches_uncached() {
+        let mut highlighter = Highlighter::new();
+        let line = "2024-01-15 10:30:45 ERROR \"boom\" 42";
+        let level = Some(LogLevel::Error);
+
+        let cached =
+            highlighter.highlight_line_cached(1, line, level, Some("boom"), false, f32::INFINITY, 0.0);
+        let direct = highlighter.highlight_line(line, level, Some("boom"), false);
+        assert_eq!(cached.text, direct.text);
+        assert_eq!(cached.sections.len(), direct.sections.len());
+        assert_eq!(highlighter.cache.entries.len(), 1);
+
+        // A theme change drops cached results
+        highlighter.set_theme(HighlightTheme::light());
+        assert!(highlighter.cache.entries.is_empty());
+    }
+
+    #[test]
+    fn test_ansi_colors_render_with_search_matches() {
+        let mut highlighter = Highlighter::new();
+        let line = "\x1b[31mdisk full\x1b[0m on /var";
+
+        let job = highlighter.highlight_line(line, None, Some("full"), false);
+        assert_eq!(job.text, "disk full on /var");
+        let sections: Vec<(&str, Color32)> = job
+            .sections
+            .iter()
+            .map(|s| (&job.text[s.byte_range.clone()], s.format.color))
+            .collect();
+        assert_eq!(
+            sections,
+            vec![
+                ("disk ", Color32::from_rgb(205, 49, 49)),
+                ("full", Color32::BLACK),
+                (" on /var", highlighter.theme.text),
+            ]
+        );
+
+        highlighter.render_ansi = false;
+        let raw = highlighter.highlight_line(line, None, None, false);
+        assert!(raw.text.contains('\x1b'));
+    }
+
+    #[test]
+    fn test_highlight_rules_layer_under_search_matches() {
+        let mut highlighter = Highlighter::new();
+        let red = Color32::from_rgb(255, 0, 0);
+        highlighter.set_rules(&[
+            HighlightRule {
+                pattern: "deadlock".to_string(),
+                color: [255, 0, 0],
+                whole_word: true,
+                ..Default::default()
+            },
+            HighlightRule {
+                pattern: "req-\\d+".to_string(),
+                regex: true,
+                enabled: false,
+                ..Default::default()
+            },
+        ]);
+
+        let line = "deadlock in \"deadlocked\" req-42";
+        let colored = |job: &LayoutJob| -> Vec<(String, Color32)> {
+            job.sections
+                .iter()
+                .filter(|s| s.format.color == red || s.format.color == Color32::BLACK)
+                .map(|s| (job.text[s.byte_range.clone()].to_string(), s.format.color))
+                .collect()
+        };
+
+        // Whole word only; the disabled regex rule is not applied
+        let job = highlighter.highlight_line(line, None, None, false);
+        assert_eq!(colored(&job), vec![("deadlock".to_string(), red)]);
+
+        // A search match inside a rule splits it instead of hiding it
+        let job = highlighter.highlight_line(line, None, Some("dl"), false);
+        assert_eq!(
+            colored(&job),
+            vec![
+                ("dea".to_string(), red),
+                ("dl".to_string(), Color32::BLACK),
+                ("ock".to_string(), red),
+                ("dl".to_string(), Color32::BLACK),
+            ]
+        );
+    }
 }
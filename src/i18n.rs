@@ -1,20 +1,25 @@
 //! Internationalization (i18n) module
 //!
 //! Provides multi-language support for the application.
-//! Currently supports English and Chinese.
+//! Strings live in one TOML file per language under `assets/locales`,
+//! embedded at build time and keyed by the `Translations` function name.
+//! Adding a language is a new locale file plus a `Language` variant.
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::OnceLock;
 use std::sync::RwLock;
 
 /// Supported languages
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
 pub enum Language {
     /// English (default)
     #[default]
     English,
     /// Chinese (Simplified)
     Chinese,
+    /// Japanese
+    Japanese,
 }
 
 impl Language {
@@ -23,27 +28,40 @@ impl Language {
         match self {
             Language::English => "English",
             Language::Chinese => "中文",
+            Language::Japanese => "日本語",
         }
     }
 
     /// Get language code
-    #[allow(dead_code)]
     pub fn code(&self) -> &'static str {
         match self {
             Language::English => "en",
             Language::Chinese => "zh",
+            Language::Japanese => "ja",
         }
     }
 
     /// Get all available languages
     pub fn all() -> &'static [Language] {
-        &[Language::English, Language::Chinese]
+        &[Language::English, Language::Chinese, Language::Japanese]
+    }
+
+    /// Embedded locale file for this language
+    fn locale_source(&self) -> &'static str {
+        match self {
+            Language::English => include_str!("../assets/locales/en.toml"),
+            Language::Chinese => include_str!("../assets/locales/zh.toml"),
+            Language::Japanese => include_str!("../assets/locales/ja.toml"),
+        }
     }
 }
 
 /// Global language instance
 static CURRENT_LANGUAGE: OnceLock<RwLock<Language>> = OnceLock::new();
 
+/// Parsed locale files, by language
+static STRINGS: OnceLock<HashMap<Language, HashMap<String, String>>> = OnceLock::new();
+
 /// Get the current language
 pub fn current_language() -> Language {
     *CURRENT_LANGUAGE
@@ -58,1879 +76,2356 @@ pub fn set_language(lang: Language) {
     *lock.write().unwrap() = lang;
 }
 
+/// Parse a locale file into its string table
+fn parse_locale(lang: Language) -> HashMap<String, String> {
+    toml::from_str(lang.locale_source()).unwrap_or_else(|e| {
+        tracing::error!("Invalid locale file for {}: {}", lang.code(), e);
+        HashMap::new()
+    })
+}
+
+/// Look up a string in the current language, falling back to English and
+/// then to the key itself
+fn tr(key: &'static str) -> &'static str {
+    let strings = STRINGS.get_or_init(|| {
+        Language::all()
+            .iter()
+            .map(|&lang| (lang, parse_locale(lang)))
+            .collect()
+    });
+    [current_language(), Language::English]
+        .iter()
+        .find_map(|lang| strings.get(lang)?.get(key))
+        .map(String::as_str)
+        .unwrap_or(key)
+}
+
 /// Translation strings
 pub struct Translations;
 
 impl Translations {
     // ============ Toolbar ============
     pub fn open() -> &'static str {
-        match current_language() {
-            Language::English => "Open",
-            Language::Chinese => "打开",
-        }
+        tr("open")
     }
 
     pub fn open_file_tooltip() -> &'static str {
-        match current_language() {
-            Language::English => "Open file (Cmd+O)",
-            Language::Chinese => "打开文件 (Cmd+O)",
-        }
+        tr("open_file_tooltip")
     }
 
     pub fn stop() -> &'static str {
-        match current_language() {
-            Language::English => "Stop",
-            Language::Chinese => "停止",
-        }
+        tr("stop")
     }
 
     pub fn start() -> &'static str {
-        match current_language() {
-            Language::English => "Start",
-            Language::Chinese => "开始",
-        }
+        tr("start")
     }
 
     pub fn toggle_monitoring_tooltip() -> &'static str {
-        match current_language() {
-            Language::English => "Start/Stop monitoring new logs (Space)",
-            Language::Chinese => "开始/停止监听新日志 (Space)",
-        }
+        tr("toggle_monitoring_tooltip")
+    }
+
+    pub fn capture() -> &'static str {
+        tr("capture")
+    }
+
+    pub fn capture_tooltip() -> &'static str {
+        tr("capture_tooltip")
+    }
+
+    pub fn capture_lines_suffix() -> &'static str {
+        tr("capture_lines_suffix")
+    }
+
+    pub fn capture_lines_tooltip() -> &'static str {
+        tr("capture_lines_tooltip")
+    }
+
+    pub fn capture_started() -> &'static str {
+        tr("capture_started")
+    }
+
+    pub fn capture_complete() -> &'static str {
+        tr("capture_complete")
     }
 
     pub fn clear() -> &'static str {
-        match current_language() {
-            Language::English => "Clear",
-            Language::Chinese => "清空",
-        }
+        tr("clear")
     }
 
     pub fn clear_display_tooltip() -> &'static str {
-        match current_language() {
-            Language::English => "Clear display (Cmd+L)",
-            Language::Chinese => "清空显示 (Cmd+L)",
-        }
+        tr("clear_display_tooltip")
     }
 
     pub fn reload() -> &'static str {
-        match current_language() {
-            Language::English => "Reload",
-            Language::Chinese => "重新加载",
-        }
+        tr("reload")
     }
 
     pub fn reload_file_tooltip() -> &'static str {
-        match current_language() {
-            Language::English => "Reload file (Cmd+Shift+R)",
-            Language::Chinese => "重新加载文件 (Cmd+Shift+R)",
-        }
+        tr("reload_file_tooltip")
     }
 
     pub fn newest_first() -> &'static str {
-        match current_language() {
-            Language::English => "Reverse",
-            Language::Chinese => "倒序",
-        }
+        tr("newest_first")
     }
 
     pub fn oldest_first() -> &'static str {
-        match current_language() {
-            Language::English => "Normal",
-            Language::Chinese => "正序",
-        }
+        tr("oldest_first")
     }
 
     pub fn toggle_order_tooltip() -> &'static str {
-        match current_language() {
-            Language::English => "Toggle display order (Cmd+R)",
-            Language::Chinese => "切换显示顺序 (Cmd+R)",
-        }
+        tr("toggle_order_tooltip")
+    }
+
+    pub fn wrap_lines() -> &'static str {
+        tr("wrap_lines")
+    }
+
+    pub fn wrap_lines_tooltip() -> &'static str {
+        tr("wrap_lines_tooltip")
     }
 
     pub fn search() -> &'static str {
-        match current_language() {
-            Language::English => "Search",
-            Language::Chinese => "搜索",
-        }
+        tr("search")
     }
 
     pub fn toggle_search_tooltip() -> &'static str {
-        match current_language() {
-            Language::English => "Toggle search (Cmd+F)",
-            Language::Chinese => "切换搜索 (Cmd+F)",
-        }
+        tr("toggle_search_tooltip")
     }
 
     pub fn go_to() -> &'static str {
-        match current_language() {
-            Language::English => "Go to",
-            Language::Chinese => "跳转",
-        }
+        tr("go_to")
     }
 
     pub fn go_to_line_tooltip() -> &'static str {
-        match current_language() {
-            Language::English => "Go to line (Cmd+G)",
-            Language::Chinese => "跳转到行 (Cmd+G)",
-        }
+        tr("go_to_line_tooltip")
+    }
+
+    pub fn go_to_time() -> &'static str {
+        tr("go_to_time")
+    }
+
+    pub fn go_to_time_tooltip() -> &'static str {
+        tr("go_to_time_tooltip")
+    }
+
+    pub fn time_query_label() -> &'static str {
+        tr("time_query_label")
+    }
+
+    pub fn time_query_hint() -> &'static str {
+        tr("time_query_hint")
+    }
+
+    pub fn no_timestamps_found() -> &'static str {
+        tr("no_timestamps_found")
+    }
+
+    pub fn invalid_time_query() -> &'static str {
+        tr("invalid_time_query")
     }
 
     pub fn go_to_top_tooltip() -> &'static str {
-        match current_language() {
-            Language::English => "Go to top (Home)",
-            Language::Chinese => "跳转到顶部 (Home)",
-        }
+        tr("go_to_top_tooltip")
     }
 
     pub fn go_to_bottom_tooltip() -> &'static str {
-        match current_language() {
-            Language::English => "Go to bottom (End)",
-            Language::Chinese => "跳转到底部 (End)",
-        }
+        tr("go_to_bottom_tooltip")
     }
 
     pub fn toggle_theme() -> &'static str {
-        match current_language() {
-            Language::English => "Toggle theme",
-            Language::Chinese => "切换主题",
-        }
+        tr("toggle_theme")
     }
 
     pub fn settings() -> &'static str {
-        match current_language() {
-            Language::English => "Settings",
-            Language::Chinese => "设置",
-        }
+        tr("settings")
     }
 
     // ============ Search Bar ============
     pub fn search_placeholder() -> &'static str {
-        match current_language() {
-            Language::English => "Search...",
-            Language::Chinese => "搜索...",
-        }
+        tr("search_placeholder")
     }
 
     pub fn case_sensitive() -> &'static str {
-        match current_language() {
-            Language::English => "Case sensitive",
-            Language::Chinese => "区分大小写",
-        }
+        tr("case_sensitive")
     }
 
     pub fn use_regex() -> &'static str {
-        match current_language() {
-            Language::English => "Use regular expression",
-            Language::Chinese => "使用正则表达式",
-        }
+        tr("use_regex")
     }
 
     pub fn match_whole_word() -> &'static str {
-        match current_language() {
-            Language::English => "Match whole word",
-            Language::Chinese => "全字匹配",
-        }
+        tr("match_whole_word")
+    }
+
+    pub fn fuzzy_search() -> &'static str {
+        tr("fuzzy_search")
     }
 
     pub fn previous_match_tooltip() -> &'static str {
-        match current_language() {
-            Language::English => "Previous match (Shift+F3)",
-            Language::Chinese => "上一个匹配 (Shift+F3)",
-        }
+        tr("previous_match_tooltip")
     }
 
     pub fn next_match_tooltip() -> &'static str {
-        match current_language() {
-            Language::English => "Next match (F3)",
-            Language::Chinese => "下一个匹配 (F3)",
-        }
+        tr("next_match_tooltip")
+    }
+
+    pub fn clear_search_highlights() -> &'static str {
+        tr("clear_search_highlights")
     }
 
     pub fn no_results() -> &'static str {
-        match current_language() {
-            Language::English => "No results",
-            Language::Chinese => "无结果",
-        }
+        tr("no_results")
+    }
+
+    pub fn match_of() -> &'static str {
+        tr("match_of")
+    }
+
+    pub fn search_in_progress() -> &'static str {
+        tr("search_in_progress")
+    }
+
+    pub fn export_matches() -> &'static str {
+        tr("export_matches")
+    }
+
+    pub fn bookmark_all_matches() -> &'static str {
+        tr("bookmark_all_matches")
+    }
+
+    pub fn matches_bookmarked() -> &'static str {
+        tr("matches_bookmarked")
+    }
+
+    pub fn matches_unbookmarked() -> &'static str {
+        tr("matches_unbookmarked")
+    }
+
+    pub fn recent_contexts() -> &'static str {
+        tr("recent_contexts")
+    }
+
+    pub fn recent_context_applied() -> &'static str {
+        tr("recent_context_applied")
+    }
+
+    pub fn export_matches_context() -> &'static str {
+        tr("export_matches_context")
+    }
+
+    pub fn matches_exported() -> &'static str {
+        tr("matches_exported")
+    }
+
+    pub fn matches_export_failed() -> &'static str {
+        tr("matches_export_failed")
     }
 
     pub fn close_tooltip() -> &'static str {
-        match current_language() {
-            Language::English => "Close (Esc)",
-            Language::Chinese => "关闭 (Esc)",
-        }
+        tr("close_tooltip")
     }
 
     // ============ Filter Panel ============
     pub fn levels() -> &'static str {
-        match current_language() {
-            Language::English => "Levels:",
-            Language::Chinese => "级别:",
-        }
+        tr("levels")
     }
 
     pub fn all() -> &'static str {
-        match current_language() {
-            Language::English => "All",
-            Language::Chinese => "全部",
-        }
+        tr("all")
     }
 
     pub fn show_all_levels() -> &'static str {
-        match current_language() {
-            Language::English => "Show all levels",
-            Language::Chinese => "显示所有级别",
-        }
+        tr("show_all_levels")
     }
 
     pub fn errors() -> &'static str {
-        match current_language() {
-            Language::English => "Errors",
-            Language::Chinese => "错误",
-        }
+        tr("errors")
     }
 
     pub fn errors_and_warnings_only() -> &'static str {
-        match current_language() {
-            Language::English => "Show only errors and warnings",
-            Language::Chinese => "仅显示错误和警告",
-        }
+        tr("errors_and_warnings_only")
+    }
+
+    pub fn errors_with_context() -> &'static str {
+        tr("errors_with_context")
+    }
+
+    pub fn errors_with_context_tooltip() -> &'static str {
+        tr("errors_with_context_tooltip")
+    }
+
+    pub fn pin_filter() -> &'static str {
+        tr("pin_filter")
+    }
+
+    pub fn pin_filter_tooltip() -> &'static str {
+        tr("pin_filter_tooltip")
+    }
+
+    pub fn filter_pinned() -> &'static str {
+        tr("filter_pinned")
+    }
+
+    pub fn filter_unpinned() -> &'static str {
+        tr("filter_unpinned")
+    }
+
+    pub fn error_context_lines() -> &'static str {
+        tr("error_context_lines")
+    }
+
+    pub fn grok_eager_max_lines() -> &'static str {
+        tr("grok_eager_max_lines")
+    }
+
+    pub fn grok_eager_max_lines_hint() -> &'static str {
+        tr("grok_eager_max_lines_hint")
+    }
+
+    pub fn trim_strategy() -> &'static str {
+        tr("trim_strategy")
+    }
+
+    pub fn trim_strategy_tooltip() -> &'static str {
+        tr("trim_strategy_tooltip")
+    }
+
+    pub fn trim_drop_oldest() -> &'static str {
+        tr("trim_drop_oldest")
+    }
+
+    pub fn trim_keep_head_and_tail() -> &'static str {
+        tr("trim_keep_head_and_tail")
+    }
+
+    pub fn trim_head_lines() -> &'static str {
+        tr("trim_head_lines")
+    }
+
+    pub fn repaint_min_level() -> &'static str {
+        tr("repaint_min_level")
+    }
+
+    pub fn repaint_every_line() -> &'static str {
+        tr("repaint_every_line")
+    }
+
+    pub fn repaint_min_level_tooltip() -> &'static str {
+        tr("repaint_min_level_tooltip")
+    }
+
+    pub fn reduced_repaint_interval() -> &'static str {
+        tr("reduced_repaint_interval")
+    }
+
+    pub fn lines_omitted() -> &'static str {
+        tr("lines_omitted")
     }
 
     pub fn advanced_filters() -> &'static str {
-        match current_language() {
-            Language::English => "Advanced Filters",
-            Language::Chinese => "高级过滤",
-        }
+        tr("advanced_filters")
     }
 
     pub fn bookmarks_only() -> &'static str {
-        match current_language() {
-            Language::English => "Bookmarks only",
-            Language::Chinese => "仅显示书签",
-        }
+        tr("bookmarks_only")
     }
 
     pub fn exclude_patterns() -> &'static str {
-        match current_language() {
-            Language::English => "Exclude patterns:",
-            Language::Chinese => "排除模式:",
-        }
+        tr("exclude_patterns")
     }
 
     pub fn exclude_pattern_hint() -> &'static str {
-        match current_language() {
-            Language::English => "Enter pattern to exclude...",
-            Language::Chinese => "输入要排除的模式...",
-        }
+        tr("exclude_pattern_hint")
     }
 
     pub fn add() -> &'static str {
-        match current_language() {
-            Language::English => "Add",
-            Language::Chinese => "添加",
-        }
+        tr("add")
     }
 
     pub fn clear_all_filters() -> &'static str {
-        match current_language() {
-            Language::English => "Clear All Filters",
-            Language::Chinese => "清除所有过滤",
-        }
+        tr("clear_all_filters")
+    }
+
+    pub fn filter_conditions() -> &'static str {
+        tr("filter_conditions")
+    }
+
+    pub fn time_range_filter() -> &'static str {
+        tr("time_range_filter")
+    }
+
+    pub fn time_range_from() -> &'static str {
+        tr("time_range_from")
+    }
+
+    pub fn time_range_to() -> &'static str {
+        tr("time_range_to")
+    }
+
+    pub fn time_range_hint() -> &'static str {
+        tr("time_range_hint")
+    }
+
+    pub fn time_range_last() -> &'static str {
+        tr("time_range_last")
+    }
+
+    pub fn time_range_last_tooltip() -> &'static str {
+        tr("time_range_last_tooltip")
+    }
+
+    pub fn keep_untimestamped_lines() -> &'static str {
+        tr("keep_untimestamped_lines")
+    }
+
+    pub fn keep_untimestamped_lines_tooltip() -> &'static str {
+        tr("keep_untimestamped_lines_tooltip")
+    }
+
+    pub fn filter_conditions_hint() -> &'static str {
+        tr("filter_conditions_hint")
+    }
+
+    pub fn match_all_conditions() -> &'static str {
+        tr("match_all_conditions")
+    }
+
+    pub fn match_any_condition() -> &'static str {
+        tr("match_any_condition")
+    }
+
+    pub fn condition_not() -> &'static str {
+        tr("condition_not")
+    }
+
+    pub fn condition_level() -> &'static str {
+        tr("condition_level")
+    }
+
+    pub fn condition_contains() -> &'static str {
+        tr("condition_contains")
+    }
+
+    pub fn condition_value_hint() -> &'static str {
+        tr("condition_value_hint")
+    }
+
+    pub fn add_condition() -> &'static str {
+        tr("add_condition")
+    }
+
+    pub fn add_condition_group() -> &'static str {
+        tr("add_condition_group")
+    }
+
+    pub fn remove_condition_group() -> &'static str {
+        tr("remove_condition_group")
     }
 
     // ============ Status Bar ============
     pub fn copy_path() -> &'static str {
-        match current_language() {
-            Language::English => "Copy path",
-            Language::Chinese => "复制路径",
-        }
+        tr("copy_path")
     }
 
     pub fn lines() -> &'static str {
-        match current_language() {
-            Language::English => "lines",
-            Language::Chinese => "行",
-        }
+        tr("lines")
     }
 
     pub fn selected() -> &'static str {
-        match current_language() {
-            Language::English => "selected",
-            Language::Chinese => "已选",
-        }
+        tr("selected")
     }
 
     pub fn auto() -> &'static str {
-        match current_language() {
-            Language::English => "Auto",
-            Language::Chinese => "自动",
-        }
+        tr("auto")
     }
 
     pub fn manual() -> &'static str {
-        match current_language() {
-            Language::English => "Manual",
-            Language::Chinese => "手动",
-        }
+        tr("manual")
     }
 
     pub fn memory() -> &'static str {
-        match current_language() {
-            Language::English => "Mem",
-            Language::Chinese => "内存",
-        }
+        tr("memory")
     }
 
     // ============ Activity Bar ============
     pub fn explorer() -> &'static str {
-        match current_language() {
-            Language::English => "Explorer",
-            Language::Chinese => "资源管理器",
-        }
+        tr("explorer")
     }
 
     pub fn filters() -> &'static str {
-        match current_language() {
-            Language::English => "Filters",
-            Language::Chinese => "过滤器",
-        }
+        tr("filters")
     }
 
     pub fn bookmarks() -> &'static str {
-        match current_language() {
-            Language::English => "Bookmarks",
-            Language::Chinese => "书签",
-        }
+        tr("bookmarks")
     }
 
     pub fn server_running() -> &'static str {
-        match current_language() {
-            Language::English => "Server running (port {})\n{} connections",
-            Language::Chinese => "服务运行中 (端口 {})\n{} 个连接",
-        }
+        tr("server_running")
     }
 
     pub fn server_waiting() -> &'static str {
-        match current_language() {
-            Language::English => "Server running (port {})\nWaiting for connections...",
-            Language::Chinese => "服务运行中 (端口 {})\n等待连接...",
-        }
+        tr("server_waiting")
+    }
+
+    pub fn server_tls_enabled() -> &'static str {
+        tr("server_tls_enabled")
     }
 
     pub fn server_stopped() -> &'static str {
-        match current_language() {
-            Language::English => "Server stopped",
-            Language::Chinese => "服务未启动",
-        }
+        tr("server_stopped")
     }
 
     // ============ Explorer Panel ============
     pub fn remote_streams() -> &'static str {
-        match current_language() {
-            Language::English => "REMOTE STREAMS",
-            Language::Chinese => "远程流",
-        }
+        tr("remote_streams")
     }
 
     pub fn waiting_for_connections() -> &'static str {
-        match current_language() {
-            Language::English => "Waiting for connections...",
-            Language::Chinese => "等待连接...",
-        }
+        tr("waiting_for_connections")
     }
 
     pub fn project() -> &'static str {
-        match current_language() {
-            Language::English => "Project",
-            Language::Chinese => "项目",
-        }
+        tr("project")
+    }
+
+    pub fn hostname() -> &'static str {
+        tr("hostname")
+    }
+
+    pub fn app_version() -> &'static str {
+        tr("app_version")
+    }
+
+    pub fn log_format() -> &'static str {
+        tr("log_format")
     }
 
     pub fn address() -> &'static str {
-        match current_language() {
-            Language::English => "Address",
-            Language::Chinese => "地址",
-        }
+        tr("address")
     }
 
     pub fn status() -> &'static str {
-        match current_language() {
-            Language::English => "Status",
-            Language::Chinese => "状态",
-        }
+        tr("status")
     }
 
     pub fn received() -> &'static str {
-        match current_language() {
-            Language::English => "Received",
-            Language::Chinese => "接收",
-        }
+        tr("received")
     }
 
     pub fn local_files() -> &'static str {
-        match current_language() {
-            Language::English => "LOCAL FILES",
-            Language::Chinese => "本地文件",
-        }
+        tr("local_files")
+    }
+
+    pub fn workspaces() -> &'static str {
+        tr("workspaces")
+    }
+
+    pub fn remove_workspace() -> &'static str {
+        tr("remove_workspace")
+    }
+
+    pub fn no_log_files_in_workspace() -> &'static str {
+        tr("no_log_files_in_workspace")
     }
 
     pub fn no_recent_files() -> &'static str {
-        match current_language() {
-            Language::English => "No recent files",
-            Language::Chinese => "无最近文件",
-        }
+        tr("no_recent_files")
     }
 
     pub fn file_path_input_hint() -> &'static str {
-        match current_language() {
-            Language::English => "Enter file path or start typing to search...",
-            Language::Chinese => "输入文件路径或开始输入搜索文件...",
-        }
+        tr("file_path_input_hint")
     }
 
     pub fn browse_button() -> &'static str {
-        match current_language() {
-            Language::English => "Browse...",
-            Language::Chinese => "浏览...",
-        }
+        tr("browse_button")
     }
 
     pub fn recent_files_label() -> &'static str {
-        match current_language() {
-            Language::English => "Recent files:",
-            Language::Chinese => "最近打开:",
-        }
+        tr("recent_files_label")
     }
 
     pub fn start_typing_hint() -> &'static str {
-        match current_language() {
-            Language::English => "Start typing to search files...",
-            Language::Chinese => "开始输入以搜索文件...",
-        }
+        tr("start_typing_hint")
     }
 
     pub fn file_encoding() -> &'static str {
-        match current_language() {
-            Language::English => "Encoding:",
-            Language::Chinese => "文件编码:",
-        }
+        tr("file_encoding")
     }
 
     pub fn file_encoding_hint() -> &'static str {
-        match current_language() {
-            Language::English => "(Auto-detect if not specified)",
-            Language::Chinese => "(未指定时自动检测)",
-        }
+        tr("file_encoding_hint")
     }
 
     // ============ Settings Panel ============
     pub fn settings_title() -> &'static str {
-        match current_language() {
-            Language::English => "Settings",
-            Language::Chinese => "设置",
-        }
+        tr("settings_title")
     }
 
     pub fn remote_service() -> &'static str {
-        match current_language() {
-            Language::English => "Remote Service",
-            Language::Chinese => "远程服务",
-        }
+        tr("remote_service")
     }
 
     pub fn listen_port() -> &'static str {
-        match current_language() {
-            Language::English => "Listen port:",
-            Language::Chinese => "监听端口:",
-        }
+        tr("listen_port")
+    }
+
+    pub fn agent_auth_token() -> &'static str {
+        tr("agent_auth_token")
+    }
+
+    pub fn agent_auth_token_hint() -> &'static str {
+        tr("agent_auth_token_hint")
+    }
+
+    pub fn generate_auth_token() -> &'static str {
+        tr("generate_auth_token")
+    }
+
+    pub fn copy_auth_token() -> &'static str {
+        tr("copy_auth_token")
+    }
+
+    pub fn auth_token_updated() -> &'static str {
+        tr("auth_token_updated")
     }
 
     pub fn enable_remote_service() -> &'static str {
-        match current_language() {
-            Language::English => "Enable Remote Service",
-            Language::Chinese => "启用远程服务",
-        }
+        tr("enable_remote_service")
+    }
+
+    pub fn reuse_agent_streams() -> &'static str {
+        tr("reuse_agent_streams")
+    }
+
+    pub fn reuse_agent_streams_tooltip() -> &'static str {
+        tr("reuse_agent_streams_tooltip")
     }
 
     pub fn mcp_service() -> &'static str {
-        match current_language() {
-            Language::English => "MCP Service (AI Integration)",
-            Language::Chinese => "MCP服务 (AI集成)",
-        }
+        tr("mcp_service")
     }
 
     pub fn enable_mcp_service() -> &'static str {
-        match current_language() {
-            Language::English => "Enable MCP Service",
-            Language::Chinese => "启用MCP服务",
-        }
+        tr("enable_mcp_service")
     }
 
     pub fn mcp_port() -> &'static str {
-        match current_language() {
-            Language::English => "MCP port:",
-            Language::Chinese => "MCP端口:",
-        }
+        tr("mcp_port")
     }
 
     pub fn mcp_endpoint() -> &'static str {
-        match current_language() {
-            Language::English => "Endpoint:",
-            Language::Chinese => "端点:",
-        }
+        tr("mcp_endpoint")
     }
 
     pub fn cache_directory() -> &'static str {
-        match current_language() {
-            Language::English => "Cache directory:",
-            Language::Chinese => "缓存目录:",
-        }
+        tr("cache_directory")
+    }
+
+    pub fn max_cache_size() -> &'static str {
+        tr("max_cache_size")
+    }
+
+    pub fn max_cache_size_tooltip() -> &'static str {
+        tr("max_cache_size_tooltip")
     }
 
     pub fn appearance() -> &'static str {
-        match current_language() {
-            Language::English => "Appearance",
-            Language::Chinese => "外观",
-        }
+        tr("appearance")
     }
 
     pub fn dark_theme() -> &'static str {
-        match current_language() {
-            Language::English => "Dark theme",
-            Language::Chinese => "深色主题",
-        }
+        tr("dark_theme")
     }
 
-    pub fn language() -> &'static str {
-        match current_language() {
-            Language::English => "Language",
-            Language::Chinese => "语言",
-        }
+    pub fn high_contrast() -> &'static str {
+        tr("high_contrast")
     }
 
-    pub fn about() -> &'static str {
-        match current_language() {
-            Language::English => "About",
-            Language::Chinese => "关于",
-        }
+    pub fn high_contrast_tooltip() -> &'static str {
+        tr("high_contrast_tooltip")
     }
 
-    pub fn app_description() -> &'static str {
-        match current_language() {
-            Language::English => "High-performance real-time log viewer",
-            Language::Chinese => "高性能实时日志查看器",
-        }
+    pub fn large_targets() -> &'static str {
+        tr("large_targets")
     }
 
-    pub fn documentation() -> &'static str {
-        match current_language() {
-            Language::English => "Documentation",
-            Language::Chinese => "文档",
-        }
+    pub fn large_targets_tooltip() -> &'static str {
+        tr("large_targets_tooltip")
+    }
+
+    pub fn presentation_mode() -> &'static str {
+        tr("presentation_mode")
+    }
+
+    pub fn presentation_mode_tooltip() -> &'static str {
+        tr("presentation_mode_tooltip")
+    }
+
+    pub fn language() -> &'static str {
+        tr("language")
+    }
+
+    pub fn about() -> &'static str {
+        tr("about")
+    }
+
+    pub fn app_description() -> &'static str {
+        tr("app_description")
+    }
+
+    pub fn documentation() -> &'static str {
+        tr("documentation")
     }
 
     // ============ Go to Line Dialog ============
     #[allow(dead_code)]
     pub fn go_to_line() -> &'static str {
-        match current_language() {
-            Language::English => "Go to Line",
-            Language::Chinese => "跳转到行",
-        }
+        tr("go_to_line")
     }
 
     #[allow(dead_code)]
     pub fn line_number() -> &'static str {
-        match current_language() {
-            Language::English => "Line number:",
-            Language::Chinese => "行号:",
-        }
+        tr("line_number")
     }
 
     #[allow(dead_code)]
     pub fn go() -> &'static str {
-        match current_language() {
-            Language::English => "Go",
-            Language::Chinese => "跳转",
-        }
+        tr("go")
     }
 
     #[allow(dead_code)]
     pub fn cancel() -> &'static str {
-        match current_language() {
-            Language::English => "Cancel",
-            Language::Chinese => "取消",
-        }
+        tr("cancel")
     }
 
     // ============ Messages ============
     #[allow(dead_code)]
     pub fn file_opened() -> &'static str {
-        match current_language() {
-            Language::English => "File opened",
-            Language::Chinese => "文件已打开",
-        }
+        tr("file_opened")
     }
 
     #[allow(dead_code)]
     pub fn file_reloaded() -> &'static str {
-        match current_language() {
-            Language::English => "File reloaded",
-            Language::Chinese => "文件已重新加载",
-        }
+        tr("file_reloaded")
     }
 
     #[allow(dead_code)]
     pub fn reload_failed() -> &'static str {
-        match current_language() {
-            Language::English => "Reload failed",
-            Language::Chinese => "重新加载失败",
-        }
+        tr("reload_failed")
     }
 
     #[allow(dead_code)]
     pub fn no_file_to_reload() -> &'static str {
-        match current_language() {
-            Language::English => "No file to reload",
-            Language::Chinese => "无文件可重新加载",
-        }
+        tr("no_file_to_reload")
     }
 
-    #[allow(dead_code)]
     pub fn file_rotated() -> &'static str {
-        match current_language() {
-            Language::English => "File rotated, reloading...",
-            Language::Chinese => "文件已轮转，正在重新加载...",
-        }
+        tr("file_rotated")
     }
 
     #[allow(dead_code)]
     pub fn error() -> &'static str {
-        match current_language() {
-            Language::English => "Error",
-            Language::Chinese => "错误",
-        }
+        tr("error")
     }
 
     #[allow(dead_code)]
     pub fn server_started() -> &'static str {
-        match current_language() {
-            Language::English => "Server started on port {}",
-            Language::Chinese => "服务已在端口 {} 启动",
-        }
+        tr("server_started")
     }
 
     #[allow(dead_code)]
     pub fn server_start_failed() -> &'static str {
-        match current_language() {
-            Language::English => "Failed to start server",
-            Language::Chinese => "启动服务失败",
-        }
+        tr("server_start_failed")
     }
 
     #[allow(dead_code)]
     pub fn agent_connected() -> &'static str {
-        match current_language() {
-            Language::English => "Agent connected: {}",
-            Language::Chinese => "Agent 已连接: {}",
-        }
+        tr("agent_connected")
     }
 
     #[allow(dead_code)]
     pub fn agent_disconnected() -> &'static str {
-        match current_language() {
-            Language::English => "Agent disconnected: {}",
-            Language::Chinese => "Agent 已断开: {}",
-        }
+        tr("agent_disconnected")
+    }
+
+    pub fn agent_reconnected() -> &'static str {
+        tr("agent_reconnected")
     }
 
     #[allow(dead_code)]
     pub fn lines_copied() -> &'static str {
-        match current_language() {
-            Language::English => "Copied {} lines",
-            Language::Chinese => "已复制 {} 行",
-        }
+        tr("lines_copied")
     }
 
     #[allow(dead_code)]
     pub fn display_cleared() -> &'static str {
-        match current_language() {
-            Language::English => "Display cleared",
-            Language::Chinese => "显示已清空",
-        }
+        tr("display_cleared")
     }
 
     #[allow(dead_code)]
     pub fn bookmarks_cleared() -> &'static str {
-        match current_language() {
-            Language::English => "Bookmarks cleared",
-            Language::Chinese => "书签已清除",
-        }
+        tr("bookmarks_cleared")
     }
 
     // ============ Display Settings ============
     pub fn display() -> &'static str {
-        match current_language() {
-            Language::English => "Display",
-            Language::Chinese => "显示",
-        }
+        tr("display")
     }
 
     pub fn font_size() -> &'static str {
-        match current_language() {
-            Language::English => "Font size:",
-            Language::Chinese => "字体大小:",
-        }
+        tr("font_size")
     }
 
     pub fn line_height() -> &'static str {
-        match current_language() {
-            Language::English => "Line height:",
-            Language::Chinese => "行高:",
-        }
+        tr("line_height")
     }
 
     pub fn letter_spacing() -> &'static str {
-        match current_language() {
-            Language::English => "Letter spacing:",
-            Language::Chinese => "字符间距:",
-        }
+        tr("letter_spacing")
+    }
+
+    pub fn row_density() -> &'static str {
+        tr("row_density")
+    }
+
+    pub fn density_comfortable() -> &'static str {
+        tr("density_comfortable")
+    }
+
+    pub fn density_compact() -> &'static str {
+        tr("density_compact")
+    }
+
+    pub fn density_dense() -> &'static str {
+        tr("density_dense")
+    }
+
+    pub fn click_action() -> &'static str {
+        tr("click_action")
+    }
+
+    pub fn click_select() -> &'static str {
+        tr("click_select")
+    }
+
+    pub fn click_open_detail() -> &'static str {
+        tr("click_open_detail")
+    }
+
+    pub fn timestamp_fallback() -> &'static str {
+        tr("timestamp_fallback")
+    }
+
+    pub fn timestamp_fallback_tooltip() -> &'static str {
+        tr("timestamp_fallback_tooltip")
+    }
+
+    pub fn timestamp_display() -> &'static str {
+        tr("timestamp_display")
+    }
+
+    pub fn timestamp_absolute() -> &'static str {
+        tr("timestamp_absolute")
+    }
+
+    pub fn timestamp_relative() -> &'static str {
+        tr("timestamp_relative")
+    }
+
+    pub fn timestamp_mode_tooltip() -> &'static str {
+        tr("timestamp_mode_tooltip")
+    }
+
+    pub fn ago() -> &'static str {
+        tr("ago")
+    }
+
+    pub fn timestamp_fallback_none() -> &'static str {
+        tr("timestamp_fallback_none")
+    }
+
+    pub fn timestamp_fallback_ingestion() -> &'static str {
+        tr("timestamp_fallback_ingestion")
+    }
+
+    pub fn timestamp_fallback_mtime() -> &'static str {
+        tr("timestamp_fallback_mtime")
+    }
+
+    pub fn timestamp_from_ingestion() -> &'static str {
+        tr("timestamp_from_ingestion")
+    }
+
+    pub fn timestamp_from_file_mtime() -> &'static str {
+        tr("timestamp_from_file_mtime")
     }
 
     pub fn show_line_numbers() -> &'static str {
-        match current_language() {
-            Language::English => "Show line numbers",
-            Language::Chinese => "显示行号",
-        }
+        tr("show_line_numbers")
     }
 
     pub fn show_row_separator() -> &'static str {
-        match current_language() {
-            Language::English => "Show row separator",
-            Language::Chinese => "显示行分隔线",
-        }
+        tr("show_row_separator")
     }
 
     pub fn show_grok_fields() -> &'static str {
-        match current_language() {
-            Language::English => "Show Grok formatted output",
-            Language::Chinese => "显示 Grok 格式化输出",
-        }
+        tr("show_grok_fields")
+    }
+
+    pub fn grok_columns() -> &'static str {
+        tr("grok_columns")
+    }
+
+    pub fn render_ansi_colors() -> &'static str {
+        tr("render_ansi_colors")
+    }
+
+    pub fn wrap_long_lines() -> &'static str {
+        tr("wrap_long_lines")
+    }
+
+    pub fn level_colors() -> &'static str {
+        tr("level_colors")
+    }
+
+    pub fn reset_level_color() -> &'static str {
+        tr("reset_level_color")
+    }
+
+    pub fn highlight_rules() -> &'static str {
+        tr("highlight_rules")
+    }
+
+    pub fn highlight_rules_tooltip() -> &'static str {
+        tr("highlight_rules_tooltip")
+    }
+
+    pub fn highlight_rule_pattern() -> &'static str {
+        tr("highlight_rule_pattern")
+    }
+
+    pub fn add_highlight_rule() -> &'static str {
+        tr("add_highlight_rule")
+    }
+
+    pub fn toggle_on() -> &'static str {
+        tr("toggle_on")
+    }
+
+    pub fn toggle_off() -> &'static str {
+        tr("toggle_off")
     }
 
     // ============ Global Search ============
     pub fn global_search_placeholder() -> &'static str {
-        match current_language() {
-            Language::English => "Search in logs...",
-            Language::Chinese => "搜索日志内容...",
-        }
+        tr("global_search_placeholder")
     }
 
     pub fn results() -> &'static str {
-        match current_language() {
-            Language::English => "results",
-            Language::Chinese => "个结果",
-        }
+        tr("results")
     }
 
     pub fn global_no_results() -> &'static str {
-        match current_language() {
-            Language::English => "No results found",
-            Language::Chinese => "未找到结果",
-        }
+        tr("global_no_results")
+    }
+
+    pub fn search_all_tabs() -> &'static str {
+        tr("search_all_tabs")
+    }
+
+    pub fn search_all_tabs_hint() -> &'static str {
+        tr("search_all_tabs_hint")
+    }
+
+    pub fn global_search_scanning() -> &'static str {
+        tr("global_search_scanning")
     }
 
     pub fn enter_search_query() -> &'static str {
-        match current_language() {
-            Language::English => "Enter a search query to find logs",
-            Language::Chinese => "输入关键词搜索日志",
-        }
+        tr("enter_search_query")
     }
 
     pub fn level_filter() -> &'static str {
-        match current_language() {
-            Language::English => "Level:",
-            Language::Chinese => "级别:",
-        }
+        tr("level_filter")
     }
 
     // ============ Advanced Filters Panel ============
     pub fn log_levels() -> &'static str {
-        match current_language() {
-            Language::English => "Log Levels",
-            Language::Chinese => "日志级别",
-        }
+        tr("log_levels")
+    }
+
+    pub fn min_level() -> &'static str {
+        tr("min_level")
+    }
+
+    pub fn any_level() -> &'static str {
+        tr("any_level")
     }
 
     pub fn no_exclude_patterns() -> &'static str {
-        match current_language() {
-            Language::English => "No exclude patterns",
-            Language::Chinese => "无排除模式",
-        }
+        tr("no_exclude_patterns")
     }
 
     pub fn text_pattern() -> &'static str {
-        match current_language() {
-            Language::English => "Text pattern",
-            Language::Chinese => "文本模式",
-        }
+        tr("text_pattern")
     }
 
     pub fn regex_pattern() -> &'static str {
-        match current_language() {
-            Language::English => "Regex pattern",
-            Language::Chinese => "正则表达式模式",
-        }
+        tr("regex_pattern")
     }
 
     pub fn add_pattern() -> &'static str {
-        match current_language() {
-            Language::English => "Add Pattern",
-            Language::Chinese => "添加模式",
-        }
+        tr("add_pattern")
     }
 
     pub fn pattern_type() -> &'static str {
-        match current_language() {
-            Language::English => "Type:",
-            Language::Chinese => "类型:",
-        }
+        tr("pattern_type")
     }
 
     pub fn text() -> &'static str {
-        match current_language() {
-            Language::English => "Text",
-            Language::Chinese => "文本",
-        }
+        tr("text")
     }
 
     pub fn regex() -> &'static str {
-        match current_language() {
-            Language::English => "Regex",
-            Language::Chinese => "正则",
-        }
+        tr("regex")
     }
 
     pub fn exclude_regex_hint() -> &'static str {
-        match current_language() {
-            Language::English => "Enter regex pattern to exclude...",
-            Language::Chinese => "输入要排除的正则表达式...",
-        }
+        tr("exclude_regex_hint")
     }
 
     pub fn regex_help() -> &'static str {
-        match current_language() {
-            Language::English => "Regex syntax: . * + ? [] {} () | ^ $ \\",
-            Language::Chinese => "正则语法: . * + ? [] {} () | ^ $ \\",
-        }
+        tr("regex_help")
     }
 
     // ============ Bookmarks Panel ============
     pub fn no_bookmarks() -> &'static str {
-        match current_language() {
-            Language::English => "No bookmarks",
-            Language::Chinese => "无书签",
-        }
+        tr("no_bookmarks")
+    }
+
+    pub fn bookmark_position() -> &'static str {
+        tr("bookmark_position")
+    }
+
+    pub fn error_position() -> &'static str {
+        tr("error_position")
+    }
+
+    pub fn warning_position() -> &'static str {
+        tr("warning_position")
+    }
+
+    pub fn no_bookmarks_in_view() -> &'static str {
+        tr("no_bookmarks_in_view")
+    }
+
+    pub fn no_problems_in_view() -> &'static str {
+        tr("no_problems_in_view")
     }
 
     pub fn bookmark_hint() -> &'static str {
-        match current_language() {
-            Language::English => "Press Cmd+B or right-click to add bookmarks",
-            Language::Chinese => "按 Cmd+B 或右键点击添加书签",
-        }
+        tr("bookmark_hint")
     }
 
     pub fn total_segments() -> &'static str {
-        match current_language() {
-            Language::English => "Segments",
-            Language::Chinese => "分段",
-        }
+        tr("total_segments")
     }
 
     pub fn total_bookmarks() -> &'static str {
-        match current_language() {
-            Language::English => "Total",
-            Language::Chinese => "总计",
-        }
+        tr("total_bookmarks")
     }
 
     pub fn line() -> &'static str {
-        match current_language() {
-            Language::English => "Line",
-            Language::Chinese => "行",
-        }
+        tr("line")
     }
 
     pub fn remove_segment() -> &'static str {
-        match current_language() {
-            Language::English => "Remove segment",
-            Language::Chinese => "移除分段",
-        }
+        tr("remove_segment")
+    }
+
+    pub fn edit_bookmark_note() -> &'static str {
+        tr("edit_bookmark_note")
+    }
+
+    pub fn bookmark_note_hint() -> &'static str {
+        tr("bookmark_note_hint")
+    }
+
+    pub fn default_bookmark_color() -> &'static str {
+        tr("default_bookmark_color")
+    }
+
+    pub fn export_report() -> &'static str {
+        tr("export_report")
+    }
+
+    pub fn export_report_tooltip() -> &'static str {
+        tr("export_report_tooltip")
+    }
+
+    pub fn report_exported() -> &'static str {
+        tr("report_exported")
+    }
+
+    pub fn report_export_failed() -> &'static str {
+        tr("report_export_failed")
+    }
+
+    pub fn no_bookmarks_to_export() -> &'static str {
+        tr("no_bookmarks_to_export")
     }
 
     #[allow(dead_code)]
     pub fn and() -> &'static str {
-        match current_language() {
-            Language::English => "and",
-            Language::Chinese => "及",
-        }
+        tr("and")
     }
 
     #[allow(dead_code)]
     pub fn more_lines() -> &'static str {
-        match current_language() {
-            Language::English => "more lines",
-            Language::Chinese => "行",
-        }
+        tr("more_lines")
     }
 
     pub fn clear_all_bookmarks() -> &'static str {
-        match current_language() {
-            Language::English => "Clear All Bookmarks",
-            Language::Chinese => "清除所有书签",
-        }
+        tr("clear_all_bookmarks")
     }
 
     // ============ Tab Bar ============
     pub fn close() -> &'static str {
-        match current_language() {
-            Language::English => "Close",
-            Language::Chinese => "关闭",
-        }
+        tr("close")
     }
 
     pub fn close_others() -> &'static str {
-        match current_language() {
-            Language::English => "Close Others",
-            Language::Chinese => "关闭其他",
-        }
+        tr("close_others")
     }
 
     pub fn close_tabs_to_right() -> &'static str {
-        match current_language() {
-            Language::English => "Close Tabs to the Right",
-            Language::Chinese => "关闭右侧标签",
-        }
+        tr("close_tabs_to_right")
     }
 
     pub fn close_all() -> &'static str {
-        match current_language() {
-            Language::English => "Close All",
-            Language::Chinese => "关闭全部",
-        }
+        tr("close_all")
     }
 
     pub fn remote_stream() -> &'static str {
-        match current_language() {
-            Language::English => "Remote Stream",
-            Language::Chinese => "远程流",
-        }
+        tr("remote_stream")
     }
 
     pub fn no_open_tabs() -> &'static str {
-        match current_language() {
-            Language::English => "No open files. Open a file or connect a remote stream.",
-            Language::Chinese => "没有打开的文件。请打开文件或连接远程流。",
-        }
+        tr("no_open_tabs")
     }
-
     // Split view related translations
     pub fn split_view() -> &'static str {
-        match current_language() {
-            Language::English => "Split",
-            Language::Chinese => "分屏",
-        }
+        tr("split_view")
     }
 
     pub fn close_split() -> &'static str {
-        match current_language() {
-            Language::English => "Close Split",
-            Language::Chinese => "关闭分屏",
-        }
+        tr("close_split")
     }
 
     pub fn toggle_split_tooltip() -> &'static str {
-        match current_language() {
-            Language::English => "Toggle split view to show two files side by side",
-            Language::Chinese => "切换分屏视图，并排显示两个文件",
-        }
+        tr("toggle_split_tooltip")
     }
 
     pub fn open_in_split() -> &'static str {
-        match current_language() {
-            Language::English => "Open in Split View",
-            Language::Chinese => "在分屏中打开",
-        }
+        tr("open_in_split")
+    }
+
+    pub fn split_mode_plain() -> &'static str {
+        tr("split_mode_plain")
+    }
+
+    pub fn split_mode_diff() -> &'static str {
+        tr("split_mode_diff")
+    }
+
+    pub fn split_mode_diff_tooltip() -> &'static str {
+        tr("split_mode_diff_tooltip")
     }
 
     // ============ Explorer Context Menu ============
     pub fn copy_absolute_path() -> &'static str {
-        match current_language() {
-            Language::English => "Copy Absolute Path",
-            Language::Chinese => "复制绝对路径",
-        }
+        tr("copy_absolute_path")
     }
 
     pub fn copy_filename() -> &'static str {
-        match current_language() {
-            Language::English => "Copy Filename",
-            Language::Chinese => "复制文件名",
-        }
+        tr("copy_filename")
+    }
+
+    pub fn copy_source_id() -> &'static str {
+        tr("copy_source_id")
     }
 
     #[cfg(target_os = "macos")]
     pub fn reveal_in_finder() -> &'static str {
-        match current_language() {
-            Language::English => "Reveal in Finder",
-            Language::Chinese => "在访达中显示",
-        }
+        tr("reveal_in_finder")
     }
 
     pub fn open_file_context() -> &'static str {
-        match current_language() {
-            Language::English => "Open File",
-            Language::Chinese => "打开文件",
-        }
+        tr("open_file_context")
     }
 
     pub fn remove_from_recent() -> &'static str {
-        match current_language() {
-            Language::English => "Remove from Recent Files",
-            Language::Chinese => "从最近文件中移除",
-        }
+        tr("remove_from_recent")
     }
 
     pub fn clear_recent_files() -> &'static str {
-        match current_language() {
-            Language::English => "Clear Recent Files",
-            Language::Chinese => "清空最近文件",
-        }
+        tr("clear_recent_files")
     }
 
     // ============ Welcome/Empty State ============
     pub fn welcome_title() -> &'static str {
-        match current_language() {
-            Language::English => "Welcome to Logline",
-            Language::Chinese => "欢迎使用 Logline",
-        }
+        tr("welcome_title")
     }
 
     pub fn keyboard_shortcuts_title() -> &'static str {
-        match current_language() {
-            Language::English => "⌨ Keyboard Shortcuts",
-            Language::Chinese => "⌨ 快捷键",
-        }
+        tr("keyboard_shortcuts_title")
     }
 
     pub fn shortcut_open_file() -> &'static str {
-        match current_language() {
-            Language::English => "Cmd+O - Open file",
-            Language::Chinese => "Cmd+O - 打开文件",
-        }
+        tr("shortcut_open_file")
+    }
+
+    pub fn shortcut_open_recent() -> &'static str {
+        tr("shortcut_open_recent")
+    }
+
+    pub fn open_recent_title() -> &'static str {
+        tr("open_recent_title")
+    }
+
+    pub fn open_recent_hint() -> &'static str {
+        tr("open_recent_hint")
+    }
+
+    pub fn open_recent_empty() -> &'static str {
+        tr("open_recent_empty")
     }
 
     pub fn shortcut_find() -> &'static str {
-        match current_language() {
-            Language::English => "Cmd+F - Search in file",
-            Language::Chinese => "Cmd+F - 文件内搜索",
-        }
+        tr("shortcut_find")
     }
 
     pub fn shortcut_goto_line() -> &'static str {
-        match current_language() {
-            Language::English => "Cmd+G - Go to line",
-            Language::Chinese => "Cmd+G - 跳转到行",
-        }
+        tr("shortcut_goto_line")
+    }
+
+    pub fn shortcut_goto_time() -> &'static str {
+        tr("shortcut_goto_time")
     }
 
     pub fn shortcut_reload() -> &'static str {
-        match current_language() {
-            Language::English => "Cmd+Shift+R - Reload file",
-            Language::Chinese => "Cmd+Shift+R - 重新加载文件",
-        }
+        tr("shortcut_reload")
     }
 
     pub fn shortcut_clear() -> &'static str {
-        match current_language() {
-            Language::English => "Cmd+L - Clear buffer",
-            Language::Chinese => "Cmd+L - 清空缓冲区",
-        }
+        tr("shortcut_clear")
     }
 
     pub fn shortcut_bookmark() -> &'static str {
-        match current_language() {
-            Language::English => "Cmd+B - Toggle bookmark",
-            Language::Chinese => "Cmd+B - 切换书签",
-        }
+        tr("shortcut_bookmark")
     }
 
     pub fn shortcut_auto_scroll() -> &'static str {
-        match current_language() {
-            Language::English => "Space - Toggle auto-scroll",
-            Language::Chinese => "Space - 切换自动滚动",
-        }
+        tr("shortcut_auto_scroll")
+    }
+
+    pub fn shortcut_next_problem() -> &'static str {
+        tr("shortcut_next_problem")
+    }
+
+    pub fn shortcut_next_bookmark() -> &'static str {
+        tr("shortcut_next_bookmark")
     }
 
     // ============ Grok Parser ============
     pub fn grok_parser() -> &'static str {
-        match current_language() {
-            Language::English => "Grok Parser",
-            Language::Chinese => "Grok 解析器",
-        }
+        tr("grok_parser")
     }
 
     pub fn grok_custom_patterns() -> &'static str {
-        match current_language() {
-            Language::English => "Custom Patterns",
-            Language::Chinese => "自定义模板",
-        }
+        tr("grok_custom_patterns")
     }
 
     pub fn grok_pattern_name() -> &'static str {
-        match current_language() {
-            Language::English => "Pattern Name",
-            Language::Chinese => "模板名称",
-        }
+        tr("grok_pattern_name")
     }
 
     pub fn grok_pattern_string() -> &'static str {
-        match current_language() {
-            Language::English => "Pattern",
-            Language::Chinese => "模板表达式",
-        }
+        tr("grok_pattern_string")
     }
 
     pub fn grok_pattern_description() -> &'static str {
-        match current_language() {
-            Language::English => "Description",
-            Language::Chinese => "描述",
-        }
+        tr("grok_pattern_description")
     }
 
     pub fn grok_pattern_example() -> &'static str {
-        match current_language() {
-            Language::English => "Example",
-            Language::Chinese => "示例",
-        }
+        tr("grok_pattern_example")
     }
 
     pub fn grok_add_pattern() -> &'static str {
-        match current_language() {
-            Language::English => "Add Pattern",
-            Language::Chinese => "添加模板",
-        }
+        tr("grok_add_pattern")
     }
 
     pub fn grok_edit_pattern() -> &'static str {
-        match current_language() {
-            Language::English => "Edit Pattern",
-            Language::Chinese => "编辑模板",
-        }
+        tr("grok_edit_pattern")
     }
 
     pub fn grok_delete_pattern() -> &'static str {
-        match current_language() {
-            Language::English => "Delete Pattern",
-            Language::Chinese => "删除模板",
-        }
+        tr("grok_delete_pattern")
     }
 
     pub fn grok_save_pattern() -> &'static str {
-        match current_language() {
-            Language::English => "Save Pattern",
-            Language::Chinese => "保存模板",
-        }
+        tr("grok_save_pattern")
     }
 
     pub fn grok_cancel() -> &'static str {
-        match current_language() {
-            Language::English => "Cancel",
-            Language::Chinese => "取消",
-        }
+        tr("grok_cancel")
     }
 
     pub fn grok_no_match() -> &'static str {
-        match current_language() {
-            Language::English => "No match",
-            Language::Chinese => "无匹配",
-        }
+        tr("grok_no_match")
     }
 
     pub fn grok_pattern_error() -> &'static str {
-        match current_language() {
-            Language::English => "Pattern Error",
-            Language::Chinese => "模板错误",
-        }
+        tr("grok_pattern_error")
     }
 
     pub fn grok_display_template() -> &'static str {
-        match current_language() {
-            Language::English => "Display Template",
-            Language::Chinese => "展示模板",
-        }
+        tr("grok_display_template")
     }
 
     pub fn grok_display_template_hint() -> &'static str {
-        match current_language() {
-            Language::English => {
-                "Use %{field} to reference parsed fields. Supports colors: %{field:color=red} or %{field:color=#FF0000}. Date formatting: %{timestamp:format=%Y-%m-%d}. Leave empty to show original."
-            }
-            Language::Chinese => "使用 %{字段名} 引用解析的字段。支持颜色：%{字段名:color=red} 或 %{字段名:color=#FF0000}。日期格式化：%{timestamp:format=%Y-%m-%d}。留空则显示原始内容。",
-        }
+        tr("grok_display_template_hint")
     }
 
-    pub fn grok_active_pattern() -> &'static str {
-        match current_language() {
-            Language::English => "Active Pattern",
-            Language::Chinese => "当前模板",
-        }
+    pub fn grok_color_rules() -> &'static str {
+        tr("grok_color_rules")
     }
 
-    pub fn grok_no_custom_patterns() -> &'static str {
-        match current_language() {
-            Language::English => "No custom patterns defined",
-            Language::Chinese => "暂无自定义模板",
-        }
+    pub fn grok_color_rules_hint() -> &'static str {
+        tr("grok_color_rules_hint")
     }
 
-    pub fn grok_none() -> &'static str {
-        match current_language() {
-            Language::English => "None",
-            Language::Chinese => "无",
-        }
+    pub fn grok_add_color_rule() -> &'static str {
+        tr("grok_add_color_rule")
     }
 
-    pub fn grok_pattern_cleared() -> &'static str {
-        match current_language() {
-            Language::English => "Grok pattern cleared",
-            Language::Chinese => "Grok 模板已清除",
-        }
+    pub fn grok_tester() -> &'static str {
+        tr("grok_tester")
     }
 
-    pub fn grok_panel_hint() -> &'static str {
-        match current_language() {
-            Language::English => "Configure patterns here. Select pattern in status bar.",
-            Language::Chinese => "在此配置模板，通过状态栏选择使用的模板。",
-        }
+    pub fn grok_tester_hint() -> &'static str {
+        tr("grok_tester_hint")
     }
 
-    // ============ AI Assist ============
-    pub fn grok_ai_assist() -> &'static str {
-        match current_language() {
-            Language::English => "AI Assist",
-            Language::Chinese => "AI辅助",
-        }
+    pub fn grok_tester_use_file_lines() -> &'static str {
+        tr("grok_tester_use_file_lines")
     }
 
-    pub fn grok_ai_prompt_hint() -> &'static str {
-        match current_language() {
-            Language::English => "Click 'Copy Prompt' to copy the prompt, paste it to your LLM (ChatGPT, Claude, etc.), then click 'Paste Prompt' and paste the JSON response.",
-            Language::Chinese => "点击「复制提示词」后会自动复制，请粘贴给LLM（如ChatGPT、Claude等），再点击「粘贴提示词」并粘贴JSON响应。",
-        }
+    pub fn grok_tester_summary() -> &'static str {
+        tr("grok_tester_summary")
     }
 
-    pub fn grok_ai_copy_prompt() -> &'static str {
-        match current_language() {
-            Language::English => "Copy Prompt",
-            Language::Chinese => "复制提示词",
-        }
+    pub fn grok_active_pattern() -> &'static str {
+        tr("grok_active_pattern")
     }
 
-    pub fn grok_ai_prompt_copied() -> &'static str {
-        match current_language() {
-            Language::English => "Prompt copied. Paste it to your LLM.",
-            Language::Chinese => "提示词已复制到剪贴板，请粘贴给LLM。",
-        }
+    pub fn grok_no_custom_patterns() -> &'static str {
+        tr("grok_no_custom_patterns")
     }
 
-    pub fn grok_ai_paste_json() -> &'static str {
-        match current_language() {
-            Language::English => "Paste Prompt Result",
-            Language::Chinese => "粘贴提示词",
-        }
+    pub fn grok_file_rules() -> &'static str {
+        tr("grok_file_rules")
     }
 
-    pub fn grok_ai_json_placeholder() -> &'static str {
-        match current_language() {
-            Language::English => "Paste the JSON response from LLM here...",
-            Language::Chinese => "在此粘贴LLM返回的JSON模板...",
-        }
+    pub fn grok_file_rules_hint() -> &'static str {
+        tr("grok_file_rules_hint")
     }
 
-    pub fn grok_ai_parse_preview() -> &'static str {
-        match current_language() {
-            Language::English => "Parse Preview",
-            Language::Chinese => "解析预览",
-        }
+    pub fn grok_add_file_rule() -> &'static str {
+        tr("grok_add_file_rule")
     }
 
-    pub fn grok_ai_sample_log() -> &'static str {
-        match current_language() {
-            Language::English => "Sample Log:",
-            Language::Chinese => "示例日志:",
-        }
+    pub fn grok_remove_file_rule() -> &'static str {
+        tr("grok_remove_file_rule")
     }
 
-    pub fn grok_ai_formatted_result() -> &'static str {
-        match current_language() {
-            Language::English => "Formatted Result:",
-            Language::Chinese => "格式化结果:",
-        }
+    pub fn grok_none() -> &'static str {
+        tr("grok_none")
+    }
+
+    pub fn json_mode() -> &'static str {
+        tr("json_mode")
+    }
+
+    pub fn json_mode_tooltip() -> &'static str {
+        tr("json_mode_tooltip")
+    }
+
+    pub fn grok_auto_detect() -> &'static str {
+        tr("grok_auto_detect")
+    }
+
+    pub fn grok_auto_detect_tooltip() -> &'static str {
+        tr("grok_auto_detect_tooltip")
+    }
+
+    pub fn grok_auto_detect_none() -> &'static str {
+        tr("grok_auto_detect_none")
+    }
+
+    pub fn grok_auto_detect_candidates() -> &'static str {
+        tr("grok_auto_detect_candidates")
+    }
+
+    pub fn grok_export_csv() -> &'static str {
+        tr("grok_export_csv")
+    }
+
+    pub fn grok_export_csv_tooltip() -> &'static str {
+        tr("grok_export_csv_tooltip")
+    }
+
+    pub fn grok_pattern_cleared() -> &'static str {
+        tr("grok_pattern_cleared")
+    }
+
+    pub fn grok_panel_hint() -> &'static str {
+        tr("grok_panel_hint")
+    }
+
+    // ============ AI Assist ============
+    pub fn grok_ai_assist() -> &'static str {
+        tr("grok_ai_assist")
+    }
+
+    pub fn grok_ai_prompt_hint() -> &'static str {
+        tr("grok_ai_prompt_hint")
+    }
+
+    pub fn grok_ai_copy_prompt() -> &'static str {
+        tr("grok_ai_copy_prompt")
+    }
+
+    pub fn grok_ai_prompt_copied() -> &'static str {
+        tr("grok_ai_prompt_copied")
+    }
+
+    pub fn grok_ai_paste_json() -> &'static str {
+        tr("grok_ai_paste_json")
+    }
+
+    pub fn grok_ai_json_placeholder() -> &'static str {
+        tr("grok_ai_json_placeholder")
+    }
+
+    pub fn grok_ai_parse_preview() -> &'static str {
+        tr("grok_ai_parse_preview")
+    }
+
+    pub fn grok_ai_sample_log() -> &'static str {
+        tr("grok_ai_sample_log")
+    }
+
+    pub fn grok_ai_formatted_result() -> &'static str {
+        tr("grok_ai_formatted_result")
     }
 
     pub fn grok_ai_parse_success() -> &'static str {
-        match current_language() {
-            Language::English => "Pattern parsed successfully!",
-            Language::Chinese => "模板解析成功！",
-        }
+        tr("grok_ai_parse_success")
     }
 
     pub fn grok_ai_invalid_json() -> &'static str {
-        match current_language() {
-            Language::English => "Invalid JSON format. Please check the response from LLM.",
-            Language::Chinese => "无效的JSON格式。请检查LLM的响应。",
-        }
+        tr("grok_ai_invalid_json")
     }
 
     pub fn grok_ai_no_file_open() -> &'static str {
-        match current_language() {
-            Language::English => "Please open a log file first",
-            Language::Chinese => "请先打开一个日志文件",
-        }
+        tr("grok_ai_no_file_open")
     }
 
     pub fn agent_usage_title() -> &'static str {
-        match current_language() {
-            Language::English => "🔧 Remote Agent Usage",
-            Language::Chinese => "🔧 远程 Agent 使用方式",
-        }
+        tr("agent_usage_title")
     }
 
     pub fn agent_install_command() -> &'static str {
-        match current_language() {
-            Language::English => "Install:",
-            Language::Chinese => "安装：",
-        }
+        tr("agent_install_command")
     }
 
     pub fn agent_basic_usage() -> &'static str {
-        match current_language() {
-            Language::English => "Basic usage:",
-            Language::Chinese => "基本用法：",
-        }
+        tr("agent_basic_usage")
     }
 
     pub fn agent_server_address() -> &'static str {
-        match current_language() {
-            Language::English => "Default server port: 12500",
-            Language::Chinese => "默认服务器端口：12500",
-        }
+        tr("agent_server_address")
+    }
+
+    pub fn agent_tls_flag() -> &'static str {
+        tr("agent_tls_flag")
     }
 
     pub fn local_network_addresses() -> &'static str {
-        match current_language() {
-            Language::English => "Local network addresses (for agent connection):",
-            Language::Chinese => "本地网络地址（用于 Agent 连接）：",
-        }
+        tr("local_network_addresses")
     }
 
     pub fn agent_more_info() -> &'static str {
-        match current_language() {
-            Language::English => "More info: github.com/zibo-chen/logline-agent",
-            Language::Chinese => "更多信息：github.com/zibo-chen/logline-agent",
-        }
+        tr("agent_more_info")
     }
 
     // ============ Status Messages ============
     pub fn file_opened_success() -> &'static str {
-        match current_language() {
-            Language::English => "File opened",
-            Language::Chinese => "已打开文件",
-        }
+        tr("file_opened_success")
     }
 
     pub fn file_open_failed() -> &'static str {
-        match current_language() {
-            Language::English => "Failed to open file",
-            Language::Chinese => "打开文件失败",
-        }
+        tr("file_open_failed")
+    }
+
+    pub fn file_rotated_during_open() -> &'static str {
+        tr("file_rotated_during_open")
     }
 
     pub fn file_open_in_split_failed() -> &'static str {
-        match current_language() {
-            Language::English => "Failed to open file in split view",
-            Language::Chinese => "在分屏中打开文件失败",
-        }
+        tr("file_open_in_split_failed")
     }
 
     pub fn remote_stream_failed() -> &'static str {
-        match current_language() {
-            Language::English => "Failed to open remote stream",
-            Language::Chinese => "打开远程流失败",
-        }
+        tr("remote_stream_failed")
     }
 
     pub fn absolute_path_copied() -> &'static str {
-        match current_language() {
-            Language::English => "Absolute path copied",
-            Language::Chinese => "已复制绝对路径",
-        }
+        tr("absolute_path_copied")
     }
 
     pub fn filename_copied() -> &'static str {
-        match current_language() {
-            Language::English => "Filename copied",
-            Language::Chinese => "已复制文件名",
-        }
+        tr("filename_copied")
+    }
+
+    pub fn source_id_copied() -> &'static str {
+        tr("source_id_copied")
+    }
+
+    pub fn source_id_unavailable() -> &'static str {
+        tr("source_id_unavailable")
     }
 
     #[cfg(target_os = "macos")]
     pub fn finder_open_failed() -> &'static str {
-        match current_language() {
-            Language::English => "Failed to open Finder",
-            Language::Chinese => "打开访达失败",
-        }
+        tr("finder_open_failed")
     }
 
     #[allow(dead_code)]
     pub fn file_manager_open_failed() -> &'static str {
-        match current_language() {
-            Language::English => "Failed to open file manager",
-            Language::Chinese => "打开文件管理器失败",
-        }
+        tr("file_manager_open_failed")
     }
 
     #[cfg(target_os = "macos")]
     pub fn file_shown_in_finder() -> &'static str {
-        match current_language() {
-            Language::English => "Shown in Finder",
-            Language::Chinese => "已在访达中显示",
-        }
+        tr("file_shown_in_finder")
     }
 
     #[allow(dead_code)]
     pub fn file_shown_in_explorer() -> &'static str {
-        match current_language() {
-            Language::English => "Shown in Explorer",
-            Language::Chinese => "已在资源管理器中显示",
-        }
+        tr("file_shown_in_explorer")
     }
 
     #[cfg(target_os = "linux")]
     pub fn file_shown_in_file_manager() -> &'static str {
-        match current_language() {
-            Language::English => "Shown in file manager",
-            Language::Chinese => "已在文件管理器中显示",
-        }
+        tr("file_shown_in_file_manager")
     }
 
     pub fn removed_from_recent_files() -> &'static str {
-        match current_language() {
-            Language::English => "Removed from recent files",
-            Language::Chinese => "已从最近文件中移除",
-        }
+        tr("removed_from_recent_files")
     }
 
     pub fn recent_files_cleared() -> &'static str {
-        match current_language() {
-            Language::English => "Recent files cleared",
-            Language::Chinese => "已清空最近文件列表",
-        }
+        tr("recent_files_cleared")
     }
 
     pub fn all_bookmarks_cleared() -> &'static str {
-        match current_language() {
-            Language::English => "All bookmarks cleared",
-            Language::Chinese => "所有书签已清除",
-        }
+        tr("all_bookmarks_cleared")
     }
 
     pub fn port_change_requires_restart() -> &'static str {
-        match current_language() {
-            Language::English => "Port change will take effect after service restart",
-            Language::Chinese => "端口变更将在重启服务后生效",
-        }
+        tr("port_change_requires_restart")
+    }
+
+    pub fn cache_limit_requires_restart() -> &'static str {
+        tr("cache_limit_requires_restart")
+    }
+
+    pub fn server_change_requires_restart() -> &'static str {
+        tr("server_change_requires_restart")
+    }
+
+    pub fn remote_cache_trimmed() -> &'static str {
+        tr("remote_cache_trimmed")
     }
 
     pub fn mcp_port_change_requires_restart() -> &'static str {
-        match current_language() {
-            Language::English => "MCP port change will take effect after service restart",
-            Language::Chinese => "MCP端口变更将在重启服务后生效",
-        }
+        tr("mcp_port_change_requires_restart")
     }
 
     pub fn mcp_server_stopped() -> &'static str {
-        match current_language() {
-            Language::English => "MCP server stopped",
-            Language::Chinese => "MCP服务已停止",
-        }
+        tr("mcp_server_stopped")
     }
 
     pub fn remote_server_stopped() -> &'static str {
-        match current_language() {
-            Language::English => "Remote server stopped",
-            Language::Chinese => "远程服务已停止",
-        }
+        tr("remote_server_stopped")
     }
 
     pub fn config_save_failed() -> &'static str {
-        match current_language() {
-            Language::English => "Failed to save config",
-            Language::Chinese => "保存配置失败",
-        }
+        tr("config_save_failed")
+    }
+
+    pub fn config_not_saved() -> &'static str {
+        tr("config_not_saved")
     }
 
     pub fn server_error() -> &'static str {
-        match current_language() {
-            Language::English => "Server error",
-            Language::Chinese => "服务器错误",
-        }
+        tr("server_error")
     }
 
     pub fn mcp_server_started() -> &'static str {
-        match current_language() {
-            Language::English => "MCP server started",
-            Language::Chinese => "MCP服务已启动",
-        }
+        tr("mcp_server_started")
     }
 
     pub fn mcp_server_start_failed() -> &'static str {
-        match current_language() {
-            Language::English => "Failed to start MCP server",
-            Language::Chinese => "MCP服务启动失败",
-        }
+        tr("mcp_server_start_failed")
     }
 
     pub fn mcp_running() -> &'static str {
-        match current_language() {
-            Language::English => "MCP Service: Running on port {}",
-            Language::Chinese => "MCP服务: 运行中 端口 {}",
-        }
+        tr("mcp_running")
     }
 
     pub fn mcp_stopped() -> &'static str {
-        match current_language() {
-            Language::English => "MCP Service: Stopped",
-            Language::Chinese => "MCP服务: 已停止",
-        }
+        tr("mcp_stopped")
     }
 
     pub fn open_file_button() -> &'static str {
-        match current_language() {
-            Language::English => "📁 Open File",
-            Language::Chinese => "📁 打开文件",
-        }
+        tr("open_file_button")
     }
 
     // ============ System Tray ============
     pub fn tray_tooltip() -> &'static str {
-        match current_language() {
-            Language::English => "Logline - Log Viewer",
-            Language::Chinese => "Logline - 日志查看器",
-        }
+        tr("tray_tooltip")
     }
 
     pub fn tray_show_window() -> &'static str {
-        match current_language() {
-            Language::English => "Show Window",
-            Language::Chinese => "显示窗口",
-        }
+        tr("tray_show_window")
     }
 
     pub fn tray_hide_window() -> &'static str {
-        match current_language() {
-            Language::English => "Hide Window",
-            Language::Chinese => "隐藏窗口",
-        }
+        tr("tray_hide_window")
     }
 
     pub fn tray_open_file() -> &'static str {
-        match current_language() {
-            Language::English => "Open File...",
-            Language::Chinese => "打开文件...",
-        }
+        tr("tray_open_file")
     }
 
     pub fn tray_settings() -> &'static str {
-        match current_language() {
-            Language::English => "Settings",
-            Language::Chinese => "设置",
-        }
+        tr("tray_settings")
     }
 
     pub fn tray_about() -> &'static str {
-        match current_language() {
-            Language::English => "About",
-            Language::Chinese => "关于",
-        }
+        tr("tray_about")
     }
 
     pub fn tray_quit() -> &'static str {
-        match current_language() {
-            Language::English => "Quit",
-            Language::Chinese => "退出",
-        }
+        tr("tray_quit")
     }
 
     // ============ Close Dialog ============
     pub fn close_dialog_title() -> &'static str {
-        match current_language() {
-            Language::English => "Close Application",
-            Language::Chinese => "关闭应用",
-        }
+        tr("close_dialog_title")
     }
 
     pub fn close_dialog_message() -> &'static str {
-        match current_language() {
-            Language::English => "What would you like to do?",
-            Language::Chinese => "请选择操作：",
-        }
+        tr("close_dialog_message")
     }
 
     pub fn close_dialog_exit() -> &'static str {
-        match current_language() {
-            Language::English => "Exit Application",
-            Language::Chinese => "退出应用",
-        }
+        tr("close_dialog_exit")
     }
 
     pub fn close_dialog_minimize() -> &'static str {
-        match current_language() {
-            Language::English => "Minimize to Tray",
-            Language::Chinese => "缩小到托盘",
-        }
+        tr("close_dialog_minimize")
     }
 
     pub fn close_dialog_remember() -> &'static str {
-        match current_language() {
-            Language::English => "Remember my choice",
-            Language::Chinese => "记住我的选择",
-        }
+        tr("close_dialog_remember")
     }
 
     pub fn close_dialog_cancel() -> &'static str {
-        match current_language() {
-            Language::English => "Cancel",
-            Language::Chinese => "取消",
-        }
+        tr("close_dialog_cancel")
     }
 
     pub fn close_button_behavior() -> &'static str {
-        match current_language() {
-            Language::English => "Close Button Behavior",
-            Language::Chinese => "关闭按钮操作",
-        }
+        tr("close_button_behavior")
     }
 
     pub fn close_behavior_exit() -> &'static str {
-        match current_language() {
-            Language::English => "Exit",
-            Language::Chinese => "退出",
-        }
+        tr("close_behavior_exit")
     }
 
     pub fn close_behavior_minimize() -> &'static str {
-        match current_language() {
-            Language::English => "Minimize to Tray",
-            Language::Chinese => "缩小到托盘",
-        }
+        tr("close_behavior_minimize")
     }
 
     pub fn close_behavior_ask() -> &'static str {
-        match current_language() {
-            Language::English => "Ask",
-            Language::Chinese => "询问",
-        }
+        tr("close_behavior_ask")
+    }
+
+    pub fn keyboard_settings() -> &'static str {
+        tr("keyboard_settings")
+    }
+
+    pub fn space_key_action() -> &'static str {
+        tr("space_key_action")
+    }
+
+    pub fn space_toggle_monitoring() -> &'static str {
+        tr("space_toggle_monitoring")
+    }
+
+    pub fn space_page_down() -> &'static str {
+        tr("space_page_down")
+    }
+
+    pub fn space_nothing() -> &'static str {
+        tr("space_nothing")
+    }
+
+    pub fn folder_drop_settings() -> &'static str {
+        tr("folder_drop_settings")
+    }
+
+    pub fn folder_drop_behavior() -> &'static str {
+        tr("folder_drop_behavior")
+    }
+
+    pub fn folder_drop_open_all_files() -> &'static str {
+        tr("folder_drop_open_all_files")
+    }
+
+    pub fn folder_drop_open_newest() -> &'static str {
+        tr("folder_drop_open_newest")
+    }
+
+    pub fn folder_drop_open_as_workspace() -> &'static str {
+        tr("folder_drop_open_as_workspace")
+    }
+
+    pub fn folder_drop_max_depth() -> &'static str {
+        tr("folder_drop_max_depth")
+    }
+
+    pub fn folder_drop_confirm_above() -> &'static str {
+        tr("folder_drop_confirm_above")
+    }
+
+    pub fn autosave_settings() -> &'static str {
+        tr("autosave_settings")
+    }
+
+    pub fn autosave_enabled() -> &'static str {
+        tr("autosave_enabled")
+    }
+
+    pub fn autosave_interval() -> &'static str {
+        tr("autosave_interval")
+    }
+
+    pub fn autosave_failed() -> &'static str {
+        tr("autosave_failed")
+    }
+
+    pub fn folder_drop_title() -> &'static str {
+        tr("folder_drop_title")
+    }
+
+    pub fn folder_drop_confirm() -> &'static str {
+        tr("folder_drop_confirm")
+    }
+
+    pub fn folder_drop_open_all() -> &'static str {
+        tr("folder_drop_open_all")
+    }
+
+    pub fn folder_drop_no_logs() -> &'static str {
+        tr("folder_drop_no_logs")
+    }
+
+    pub fn folder_drop_opened() -> &'static str {
+        tr("folder_drop_opened")
+    }
+
+    pub fn folder_drop_workspace_added() -> &'static str {
+        tr("folder_drop_workspace_added")
     }
 
     pub fn window_settings() -> &'static str {
-        match current_language() {
-            Language::English => "Window",
-            Language::Chinese => "窗口",
-        }
+        tr("window_settings")
     }
 
     pub fn settings_saved() -> &'static str {
-        match current_language() {
-            Language::English => "Settings saved",
-            Language::Chinese => "设置已保存",
-        }
+        tr("settings_saved")
     }
 
     // ============ Source Picker Dialog ============
     pub fn open_source_dialog_title() -> &'static str {
-        match current_language() {
-            Language::English => "Open Source",
-            Language::Chinese => "打开数据源",
-        }
+        tr("open_source_dialog_title")
     }
 
     pub fn local_files_tab() -> &'static str {
-        match current_language() {
-            Language::English => "Local Files",
-            Language::Chinese => "本地文件",
-        }
+        tr("local_files_tab")
     }
 
     pub fn android_devices_tab() -> &'static str {
-        match current_language() {
-            Language::English => "Android Devices",
-            Language::Chinese => "Android 设备",
-        }
+        tr("android_devices_tab")
     }
 
     pub fn connected_devices() -> &'static str {
-        match current_language() {
-            Language::English => "Connected Devices",
-            Language::Chinese => "已连接设备",
-        }
+        tr("connected_devices")
     }
 
     pub fn refresh() -> &'static str {
-        match current_language() {
-            Language::English => "Refresh",
-            Language::Chinese => "刷新",
-        }
+        tr("refresh")
     }
 
     pub fn connect_tcp() -> &'static str {
-        match current_language() {
-            Language::English => "Connect TCP",
-            Language::Chinese => "TCP 连接",
-        }
+        tr("connect_tcp")
     }
 
     pub fn connect_via_tcp() -> &'static str {
-        match current_language() {
-            Language::English => "Connect via WiFi / TCP",
-            Language::Chinese => "通过 WiFi / TCP 连接",
-        }
+        tr("connect_via_tcp")
     }
 
     pub fn ip_address() -> &'static str {
-        match current_language() {
-            Language::English => "IP Address:",
-            Language::Chinese => "IP 地址:",
-        }
+        tr("ip_address")
     }
 
     pub fn connect() -> &'static str {
-        match current_language() {
-            Language::English => "Connect",
-            Language::Chinese => "连接",
-        }
+        tr("connect")
     }
 
     pub fn tcp_address_hint() -> &'static str {
-        match current_language() {
-            Language::English => "e.g. 192.168.1.100 or 192.168.1.100:5555",
-            Language::Chinese => "例如 192.168.1.100 或 192.168.1.100:5555",
-        }
+        tr("tcp_address_hint")
     }
 
     pub fn no_devices_connected() -> &'static str {
-        match current_language() {
-            Language::English => "No devices connected",
-            Language::Chinese => "无已连接设备",
-        }
+        tr("no_devices_connected")
     }
 
     pub fn connect_device_hint() -> &'static str {
-        match current_language() {
-            Language::English => "Connect a device via USB or WiFi (TCP/IP)",
-            Language::Chinese => "通过 USB 或 WiFi (TCP/IP) 连接设备",
-        }
+        tr("connect_device_hint")
     }
 
     pub fn online() -> &'static str {
-        match current_language() {
-            Language::English => "Online",
-            Language::Chinese => "在线",
-        }
+        tr("online")
     }
 
     pub fn device_offline() -> &'static str {
-        match current_language() {
-            Language::English => "Device offline",
-            Language::Chinese => "设备离线",
-        }
+        tr("device_offline")
     }
 
     pub fn view_logcat() -> &'static str {
-        match current_language() {
-            Language::English => "View Logcat",
-            Language::Chinese => "查看日志",
-        }
+        tr("view_logcat")
     }
 
     pub fn disconnect() -> &'static str {
-        match current_language() {
-            Language::English => "Disconnect",
-            Language::Chinese => "断开连接",
-        }
+        tr("disconnect")
+    }
+
+    pub fn clear_logcat() -> &'static str {
+        tr("clear_logcat")
+    }
+
+    pub fn clear_logcat_tooltip() -> &'static str {
+        tr("clear_logcat_tooltip")
+    }
+
+    pub fn logcat_cleared() -> &'static str {
+        tr("logcat_cleared")
+    }
+
+    pub fn logcat_clear_failed() -> &'static str {
+        tr("logcat_clear_failed")
+    }
+
+    pub fn logcat_app_not_running() -> &'static str {
+        tr("logcat_app_not_running")
+    }
+
+    pub fn logcat_app_filter_hint() -> &'static str {
+        tr("logcat_app_filter_hint")
+    }
+
+    pub fn logcat_app_filter_tooltip() -> &'static str {
+        tr("logcat_app_filter_tooltip")
     }
 
     // ============ Explorer Panel ============
     pub fn explorer_header() -> &'static str {
-        match current_language() {
-            Language::English => "EXPLORER",
-            Language::Chinese => "资源管理器",
-        }
+        tr("explorer_header")
     }
 
     pub fn open_source() -> &'static str {
-        match current_language() {
-            Language::English => "Open Source (Cmd+O)",
-            Language::Chinese => "打开数据源 (Cmd+O)",
-        }
+        tr("open_source")
     }
 
     pub fn android_devices() -> &'static str {
-        match current_language() {
-            Language::English => "Android Devices",
-            Language::Chinese => "Android 设备",
-        }
+        tr("android_devices")
     }
 
     pub fn serial() -> &'static str {
-        match current_language() {
-            Language::English => "Serial",
-            Language::Chinese => "序列号",
-        }
+        tr("serial")
     }
 
     pub fn connection() -> &'static str {
-        match current_language() {
-            Language::English => "Connection",
-            Language::Chinese => "连接方式",
-        }
+        tr("connection")
     }
 
     pub fn state_label() -> &'static str {
-        match current_language() {
-            Language::English => "State: ",
-            Language::Chinese => "状态: ",
-        }
+        tr("state_label")
     }
 
     pub fn click_to_view_logcat() -> &'static str {
-        match current_language() {
-            Language::English => "Click to view logcat",
-            Language::Chinese => "点击查看日志",
-        }
+        tr("click_to_view_logcat")
     }
 
     pub fn copy_serial() -> &'static str {
-        match current_language() {
-            Language::English => "Copy Serial",
-            Language::Chinese => "复制序列号",
-        }
+        tr("copy_serial")
     }
 
     pub fn manage_devices() -> &'static str {
-        match current_language() {
-            Language::English => "Manage Devices...",
-            Language::Chinese => "管理设备...",
-        }
+        tr("manage_devices")
+    }
+
+    // ============ Display Prefix Strip ============
+    pub fn strip_prefix() -> &'static str {
+        tr("strip_prefix")
+    }
+
+    pub fn strip_prefix_hint() -> &'static str {
+        tr("strip_prefix_hint")
+    }
+
+    pub fn strip_prefix_tooltip() -> &'static str {
+        tr("strip_prefix_tooltip")
+    }
+
+    pub fn apply() -> &'static str {
+        tr("apply")
+    }
+
+    // ============ File Statistics ============
+    pub fn scanning_file() -> &'static str {
+        tr("scanning_file")
+    }
+
+    pub fn decompressing() -> &'static str {
+        tr("decompressing")
+    }
+
+    // ============ Source Aliases ============
+    pub fn rename() -> &'static str {
+        tr("rename")
+    }
+
+    pub fn reset_name() -> &'static str {
+        tr("reset_name")
+    }
+
+    // ============ Redaction ============
+    pub fn privacy() -> &'static str {
+        tr("privacy")
+    }
+
+    pub fn redact_copied_text() -> &'static str {
+        tr("redact_copied_text")
+    }
+
+    pub fn redact_copied_text_tooltip() -> &'static str {
+        tr("redact_copied_text_tooltip")
+    }
+
+    pub fn redaction_patterns() -> &'static str {
+        tr("redaction_patterns")
+    }
+
+    pub fn redaction_replacement() -> &'static str {
+        tr("redaction_replacement")
+    }
+
+    pub fn rewrite_copied_text() -> &'static str {
+        tr("rewrite_copied_text")
+    }
+
+    pub fn rewrite_copied_text_tooltip() -> &'static str {
+        tr("rewrite_copied_text_tooltip")
+    }
+
+    pub fn rewrite_pattern() -> &'static str {
+        tr("rewrite_pattern")
+    }
+
+    pub fn rewrite_replacement() -> &'static str {
+        tr("rewrite_replacement")
+    }
+
+    pub fn add_rewrite_rule() -> &'static str {
+        tr("add_rewrite_rule")
+    }
+
+    // ============ Line Detail ============
+    pub fn line_detail() -> &'static str {
+        tr("line_detail")
+    }
+
+    pub fn line_detail_line() -> &'static str {
+        tr("line_detail_line")
+    }
+
+    pub fn line_detail_fields() -> &'static str {
+        tr("line_detail_fields")
+    }
+
+    pub fn line_detail_context() -> &'static str {
+        tr("line_detail_context")
+    }
+
+    pub fn line_detail_no_selection() -> &'static str {
+        tr("line_detail_no_selection")
+    }
+
+    pub fn copy_line() -> &'static str {
+        tr("copy_line")
+    }
+
+    pub fn copy_with_line_numbers() -> &'static str {
+        tr("copy_with_line_numbers")
+    }
+
+    pub fn copy_without_line_numbers() -> &'static str {
+        tr("copy_without_line_numbers")
+    }
+
+    pub fn copy_as_markdown() -> &'static str {
+        tr("copy_as_markdown")
+    }
+
+    pub fn copied_as_markdown() -> &'static str {
+        tr("copied_as_markdown")
+    }
+
+    pub fn copy_with_line_numbers_default() -> &'static str {
+        tr("copy_with_line_numbers_default")
+    }
+
+    pub fn show_source_sizes() -> &'static str {
+        tr("show_source_sizes")
+    }
+
+    pub fn show_timeline() -> &'static str {
+        tr("show_timeline")
+    }
+
+    pub fn show_overview() -> &'static str {
+        tr("show_overview")
+    }
+
+    pub fn show_context_header() -> &'static str {
+        tr("show_context_header")
+    }
+
+    pub fn show_context_header_tooltip() -> &'static str {
+        tr("show_context_header_tooltip")
+    }
+
+    pub fn pause_tail_on_error() -> &'static str {
+        tr("pause_tail_on_error")
+    }
+
+    pub fn pause_tail_on_error_tooltip() -> &'static str {
+        tr("pause_tail_on_error_tooltip")
+    }
+
+    pub fn tail_paused_on_error() -> &'static str {
+        tr("tail_paused_on_error")
+    }
+
+    pub fn context_line_range() -> &'static str {
+        tr("context_line_range")
+    }
+
+    pub fn keep_search_highlights() -> &'static str {
+        tr("keep_search_highlights")
+    }
+
+    // ============ Tabs ============
+    pub fn tab_idle_paused() -> &'static str {
+        tr("tab_idle_paused")
+    }
+
+    pub fn tab_loading() -> &'static str {
+        tr("tab_loading")
+    }
+
+    // ============ Snapshots ============
+    pub fn snapshot() -> &'static str {
+        tr("snapshot")
+    }
+
+    pub fn take_snapshot() -> &'static str {
+        tr("take_snapshot")
+    }
+
+    pub fn diff_against_snapshot() -> &'static str {
+        tr("diff_against_snapshot")
+    }
+
+    pub fn snapshot_diff_title() -> &'static str {
+        tr("snapshot_diff_title")
+    }
+
+    pub fn snapshot_diff_no_changes() -> &'static str {
+        tr("snapshot_diff_no_changes")
+    }
+
+    pub fn snapshot_diff_only_changes() -> &'static str {
+        tr("snapshot_diff_only_changes")
+    }
+
+    pub fn snapshot_taken() -> &'static str {
+        tr("snapshot_taken")
+    }
+
+    pub fn no_snapshot() -> &'static str {
+        tr("no_snapshot")
+    }
+
+    // ============ Notes ============
+    pub fn notes() -> &'static str {
+        tr("notes")
+    }
+
+    pub fn notes_hint() -> &'static str {
+        tr("notes_hint")
+    }
+
+    pub fn notes_no_file() -> &'static str {
+        tr("notes_no_file")
+    }
+
+    pub fn toggle_notes_tooltip() -> &'static str {
+        tr("toggle_notes_tooltip")
+    }
+
+    // ============ Timeline ============
+    pub fn timeline_lines() -> &'static str {
+        tr("timeline_lines")
+    }
+
+    pub fn timeline_hint() -> &'static str {
+        tr("timeline_hint")
+    }
+
+    // ============ Correlation IDs ============
+    pub fn filter_by_correlation_id() -> &'static str {
+        tr("filter_by_correlation_id")
+    }
+
+    pub fn clear_correlation_filter() -> &'static str {
+        tr("clear_correlation_filter")
+    }
+
+    pub fn next_correlated_line() -> &'static str {
+        tr("next_correlated_line")
+    }
+
+    pub fn prev_correlated_line() -> &'static str {
+        tr("prev_correlated_line")
+    }
+
+    pub fn correlation_filter_applied() -> &'static str {
+        tr("correlation_filter_applied")
+    }
+
+    pub fn correlation_filter_cleared() -> &'static str {
+        tr("correlation_filter_cleared")
+    }
+
+    pub fn no_correlation_id() -> &'static str {
+        tr("no_correlation_id")
+    }
+
+    pub fn invalid_correlation_pattern() -> &'static str {
+        tr("invalid_correlation_pattern")
+    }
+
+    pub fn correlated_line() -> &'static str {
+        tr("correlated_line")
+    }
+
+    pub fn correlation_settings() -> &'static str {
+        tr("correlation_settings")
+    }
+
+    pub fn correlation_source() -> &'static str {
+        tr("correlation_source")
+    }
+
+    pub fn correlation_source_regex() -> &'static str {
+        tr("correlation_source_regex")
+    }
+
+    pub fn correlation_source_field() -> &'static str {
+        tr("correlation_source_field")
+    }
+
+    pub fn correlation_field() -> &'static str {
+        tr("correlation_field")
+    }
+
+    pub fn correlation_pattern() -> &'static str {
+        tr("correlation_pattern")
+    }
+
+    // ============ Stdin ============
+    pub fn streaming_stdin() -> &'static str {
+        tr("streaming_stdin")
+    }
+
+    pub fn stdin_ended() -> &'static str {
+        tr("stdin_ended")
+    }
+
+    // ============ Compressed files ============
+    pub fn compressed_read_only() -> &'static str {
+        tr("compressed_read_only")
+    }
+
+    pub fn mapped_read_only() -> &'static str {
+        tr("mapped_read_only")
+    }
+
+    pub fn mapped_read_only_tooltip() -> &'static str {
+        tr("mapped_read_only_tooltip")
+    }
+
+    // ============ Filter presets ============
+    pub fn filter_presets() -> &'static str {
+        tr("filter_presets")
+    }
+
+    pub fn choose_filter_preset() -> &'static str {
+        tr("choose_filter_preset")
+    }
+
+    pub fn no_filter_presets() -> &'static str {
+        tr("no_filter_presets")
+    }
+
+    pub fn filter_preset_name_hint() -> &'static str {
+        tr("filter_preset_name_hint")
+    }
+
+    pub fn save_filter_preset() -> &'static str {
+        tr("save_filter_preset")
+    }
+
+    pub fn delete_filter_preset() -> &'static str {
+        tr("delete_filter_preset")
+    }
+
+    pub fn filter_preset_saved() -> &'static str {
+        tr("filter_preset_saved")
+    }
+
+    pub fn filter_preset_deleted() -> &'static str {
+        tr("filter_preset_deleted")
+    }
+
+    // ============ Line export ============
+    pub fn export_filtered() -> &'static str {
+        tr("export_filtered")
+    }
+
+    pub fn export_filtered_tooltip() -> &'static str {
+        tr("export_filtered_tooltip")
+    }
+
+    pub fn no_lines_to_export() -> &'static str {
+        tr("no_lines_to_export")
+    }
+
+    pub fn export_in_progress() -> &'static str {
+        tr("export_in_progress")
+    }
+
+    pub fn lines_exported() -> &'static str {
+        tr("lines_exported")
+    }
+
+    pub fn lines_export_failed() -> &'static str {
+        tr("lines_export_failed")
+    }
+
+    // ============ Merged sources ============
+    pub fn merged_sources() -> &'static str {
+        tr("merged_sources")
+    }
+
+    pub fn merge_sources_tooltip() -> &'static str {
+        tr("merge_sources_tooltip")
+    }
+
+    pub fn merge_needs_two_files() -> &'static str {
+        tr("merge_needs_two_files")
+    }
+
+    pub fn sources_merged() -> &'static str {
+        tr("sources_merged")
+    }
+
+    pub fn merge_failed() -> &'static str {
+        tr("merge_failed")
+    }
+
+    // ============ Level histogram ============
+    pub fn lines_without_level() -> &'static str {
+        tr("lines_without_level")
+    }
+
+    // ============ Stack traces ============
+    pub fn stack_traces() -> &'static str {
+        tr("stack_traces")
+    }
+
+    pub fn group_stack_traces() -> &'static str {
+        tr("group_stack_traces")
+    }
+
+    pub fn group_stack_traces_tooltip() -> &'static str {
+        tr("group_stack_traces_tooltip")
+    }
+
+    pub fn continuation_pattern() -> &'static str {
+        tr("continuation_pattern")
+    }
+
+    pub fn invalid_continuation_pattern() -> &'static str {
+        tr("invalid_continuation_pattern")
+    }
+
+    pub fn collapse_block() -> &'static str {
+        tr("collapse_block")
+    }
+
+    pub fn expand_block() -> &'static str {
+        tr("expand_block")
     }
 }
 
@@ -1941,3 +2436,58 @@ macro_rules! t {
         $crate::i18n::Translations::$key()
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locales_have_the_same_keys() {
+        let english = parse_locale(Language::English);
+        assert!(!english.is_empty());
+
+        for &lang in Language::all() {
+            let strings = parse_locale(lang);
+            let mut missing: Vec<_> = english
+                .keys()
+                .filter(|k| !strings.contains_key(*k))
+                .collect();
+            let mut unknown: Vec<_> = strings
+                .keys()
+                .filter(|k| !english.contains_key(*k))
+                .collect();
+            missing.sort();
+            unknown.sort();
+            assert!(
+                missing.is_empty(),
+                "{} is missing {:?}",
+                lang.code(),
+                missing
+            );
+            assert!(
+                unknown.is_empty(),
+                "{} has unknown keys {:?}",
+                lang.code(),
+                unknown
+            );
+        }
+    }
+
+    #[test]
+    fn test_every_translation_has_an_english_string() {
+        let english = parse_locale(Language::English);
+        let source = include_str!("i18n.rs");
+        let keys: Vec<&str> = source
+            .split("tr(\"")
+            .skip(1)
+            .filter_map(|rest| rest.split_once('"').map(|(key, _)| key))
+            .collect();
+
+        assert!(keys.len() > 400);
+        for key in keys {
+            assert!(english.contains_key(key), "no English string for {}", key);
+        }
+        let unknown = "no_such_key";
+        assert_eq!(tr(unknown), unknown);
+    }
+}
@@ -0,0 +1,447 @@
+//! Export of the lines shown in a tab
+//!
+//! The entries are snapshotted on the UI thread; a background thread parses,
+//! redacts and writes them, reporting its progress so exporting a huge view
+//! doesn't freeze the UI.
+
+use crate::ansi::strip_ansi;
+use crate::grok_parser::GrokParser;
+use crate::log_entry::{LogEntry, LogLevel};
+use crate::redactor::Redactor;
+use chrono::{DateTime, Local};
+use crossbeam_channel::{unbounded, Receiver, TryRecvError};
+use std::collections::HashMap;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// Lines written between progress updates
+const PROGRESS_LINES: usize = 10_000;
+
+/// Leading CSV columns, filled from the entry rather than its grok fields
+const CSV_ENTRY_COLUMNS: [&str; 3] = ["line_number", "timestamp", "level"];
+
+/// CSV column holding the content of lines that weren't parsed
+const CSV_RAW_COLUMN: &str = "raw";
+
+/// Output format of a line export
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineExportFormat {
+    /// The lines as they appear in the log
+    Text,
+    /// One JSON object per line
+    JsonLines,
+    /// One CSV row per line, with a column per grok field
+    Csv,
+}
+
+impl LineExportFormat {
+    /// Get all export formats
+    pub fn all() -> &'static [LineExportFormat] {
+        &[
+            LineExportFormat::Text,
+            LineExportFormat::JsonLines,
+            LineExportFormat::Csv,
+        ]
+    }
+
+    /// File extensions for the format
+    pub fn extensions(&self) -> &'static [&'static str] {
+        match self {
+            LineExportFormat::Text => &["log", "txt"],
+            LineExportFormat::JsonLines => &["jsonl", "ndjson"],
+            LineExportFormat::Csv => &["csv"],
+        }
+    }
+
+    /// Filter name for save dialogs
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            LineExportFormat::Text => "Text",
+            LineExportFormat::JsonLines => "JSON Lines",
+            LineExportFormat::Csv => "CSV",
+        }
+    }
+
+    /// Format picked in the save dialog, judged by the file extension
+    pub fn for_path(path: &Path) -> Self {
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        Self::all()
+            .iter()
+            .copied()
+            .find(|format| {
+                format
+                    .extensions()
+                    .iter()
+                    .any(|ext| ext.eq_ignore_ascii_case(extension))
+            })
+            .unwrap_or(LineExportFormat::Text)
+    }
+}
+
+/// A line to export
+#[derive(Debug, Clone)]
+pub struct ExportLine {
+    pub line_number: usize,
+    pub level: Option<LogLevel>,
+    pub timestamp: Option<DateTime<Local>>,
+    /// Content as it should be written (escape sequences stripped, redacted)
+    pub content: String,
+    /// Grok fields, if the line was parsed
+    pub fields: Option<HashMap<String, String>>,
+}
+
+impl ExportLine {
+    /// Export line of an entry with the content to write
+    pub fn new(entry: &LogEntry, content: String) -> Self {
+        Self {
+            line_number: entry.line_number,
+            level: entry.level,
+            timestamp: entry.timestamp,
+            content,
+            fields: entry.grok_fields.clone(),
+        }
+    }
+}
+
+/// Entries to export, turned into [`ExportLine`]s on the export thread
+pub struct ExportSource {
+    pub entries: Vec<LogEntry>,
+    /// Parses the entries that have no grok fields yet (CSV exports)
+    pub grok_parser: Option<GrokParser>,
+    /// Applied to the content and the field values
+    pub redactor: Redactor,
+}
+
+impl ExportSource {
+    fn into_lines(self) -> Vec<ExportLine> {
+        let Self {
+            entries,
+            grok_parser,
+            redactor,
+        } = self;
+        entries
+            .into_iter()
+            .map(|mut entry| {
+                if let Some(parser) = grok_parser.as_ref().filter(|_| entry.grok_fields.is_none()) {
+                    if let Some(parsed) = parser.parse(&entry.content).filter(|p| !p.is_empty()) {
+                        entry.set_grok_fields(parsed.fields);
+                    }
+                }
+                let content = redactor.redact(&strip_ansi(&entry.content)).into_owned();
+                let mut line = ExportLine::new(&entry, content);
+                for value in line.fields.iter_mut().flat_map(|f| f.values_mut()) {
+                    *value = redactor.redact(value).into_owned();
+                }
+                line
+            })
+            .collect()
+    }
+}
+
+/// Write the lines, calling `progress` with the number written so far
+/// every [`PROGRESS_LINES`] lines. Returns the number of lines written.
+///
+/// `columns` are the grok fields written as CSV columns.
+pub fn write_lines<W: Write>(
+    mut out: W,
+    lines: &[ExportLine],
+    format: LineExportFormat,
+    columns: &[String],
+    mut progress: impl FnMut(usize),
+) -> io::Result<usize> {
+    if format == LineExportFormat::Csv {
+        return write_csv(out, lines, columns, progress);
+    }
+    for (i, line) in lines.iter().enumerate() {
+        match format {
+            LineExportFormat::Text => writeln!(out, "{}", line.content)?,
+            LineExportFormat::JsonLines => {
+                let json = serde_json::json!({
+                    "line_number": line.line_number,
+                    "level": line.level.map(|l| l.as_str()),
+                    "timestamp": line.timestamp.map(|t| t.to_rfc3339()),
+                    "content": line.content,
+                });
+                writeln!(out, "{}", json)?;
+            }
+            LineExportFormat::Csv => unreachable!("CSV is written by write_csv"),
+        }
+        if (i + 1) % PROGRESS_LINES == 0 {
+            progress(i + 1);
+        }
+    }
+    out.flush()?;
+    Ok(lines.len())
+}
+
+/// Write the lines as CSV: the entry columns, a column per grok field, and
+/// a raw column with the content of lines that weren't parsed
+fn write_csv<W: Write>(
+    out: W,
+    lines: &[ExportLine],
+    columns: &[String],
+    mut progress: impl FnMut(usize),
+) -> io::Result<usize> {
+    // The entry's own timestamp and level stand in for the captured ones
+    let columns: Vec<&str> = columns
+        .iter()
+        .map(String::as_str)
+        .filter(|c| !CSV_ENTRY_COLUMNS.contains(c) && *c != CSV_RAW_COLUMN)
+        .collect();
+
+    let mut writer = csv::Writer::from_writer(out);
+    writer.write_record(
+        CSV_ENTRY_COLUMNS
+            .iter()
+            .chain(&columns)
+            .chain(&[CSV_RAW_COLUMN]),
+    )?;
+    for (i, line) in lines.iter().enumerate() {
+        let mut record = vec![
+            line.line_number.to_string(),
+            line.timestamp.map(|t| t.to_rfc3339()).unwrap_or_default(),
+            line.level
+                .map(|l| l.as_str().to_string())
+                .unwrap_or_default(),
+        ];
+        match &line.fields {
+            Some(fields) => {
+                record.extend(
+                    columns
+                        .iter()
+                        .map(|c| fields.get(*c).cloned().unwrap_or_default()),
+                );
+                record.push(String::new());
+            }
+            None => {
+                record.extend(columns.iter().map(|_| String::new()));
+                record.push(line.content.clone());
+            }
+        }
+        writer.write_record(&record)?;
+        if (i + 1) % PROGRESS_LINES == 0 {
+            progress(i + 1);
+        }
+    }
+    writer.flush()?;
+    Ok(lines.len())
+}
+
+/// Messages from the export thread
+enum ExportMessage {
+    /// Lines written so far
+    Progress(usize),
+    /// Export finished: lines written, or the error
+    Done(io::Result<usize>),
+}
+
+/// An export running on a background thread
+pub struct LineExport {
+    /// Messages from the export thread
+    rx: Receiver<ExportMessage>,
+    /// File being written
+    path: PathBuf,
+    /// Lines to write
+    total: usize,
+    /// Lines written so far
+    written: usize,
+}
+
+impl LineExport {
+    /// Write the entries of `source` to `path` on a new thread, with
+    /// `columns` as the grok field columns of a CSV export
+    pub fn start(
+        path: PathBuf,
+        source: ExportSource,
+        format: LineExportFormat,
+        columns: Vec<String>,
+    ) -> Self {
+        let (tx, rx) = unbounded();
+        let total = source.entries.len();
+
+        let file_path = path.clone();
+        std::thread::spawn(move || {
+            let lines = source.into_lines();
+            let result = std::fs::File::create(&file_path).and_then(|file| {
+                write_lines(BufWriter::new(file), &lines, format, &columns, |written| {
+                    let _ = tx.send(ExportMessage::Progress(written));
+                })
+            });
+            let _ = tx.send(ExportMessage::Done(result));
+        });
+
+        Self {
+            rx,
+            path,
+            total,
+            written: 0,
+        }
+    }
+
+    /// File being written
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Fraction of the lines written so far
+    pub fn progress(&self) -> f32 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.written as f32 / self.total as f32
+        }
+    }
+
+    /// Take progress updates; returns the result once the export finished
+    pub fn poll(&mut self) -> Option<io::Result<usize>> {
+        loop {
+            match self.rx.try_recv() {
+                Ok(ExportMessage::Progress(written)) => self.written = written,
+                Ok(ExportMessage::Done(result)) => return Some(result),
+                Err(TryRecvError::Empty) => return None,
+                Err(TryRecvError::Disconnected) => {
+                    return Some(Err(io::Error::other("Export stopped unexpectedly")))
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries() -> Vec<LogEntry> {
+        ["2024-01-15 10:00:00 ERROR disk full", "    at write()"]
+            .iter()
+            .enumerate()
+            .map(|(i, line)| LogEntry::new(i + 41, line.to_string(), 0))
+            .collect()
+    }
+
+    fn lines() -> Vec<ExportLine> {
+        entries()
+            .iter()
+            .map(|entry| ExportLine::new(entry, entry.content.to_string()))
+            .collect()
+    }
+
+    fn wait(export: &mut LineExport) -> io::Result<usize> {
+        loop {
+            if let Some(result) = export.poll() {
+                break result;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn test_format_follows_chosen_extension() {
+        assert_eq!(
+            LineExportFormat::for_path(Path::new("errors.jsonl")),
+            LineExportFormat::JsonLines
+        );
+        assert_eq!(
+            LineExportFormat::for_path(Path::new("errors.log")),
+            LineExportFormat::Text
+        );
+        assert_eq!(
+            LineExportFormat::for_path(Path::new("errors")),
+            LineExportFormat::Text
+        );
+    }
+
+    #[test]
+    fn test_json_lines_carry_entry_fields() {
+        let mut out = Vec::new();
+        let written =
+            write_lines(&mut out, &lines(), LineExportFormat::JsonLines, &[], |_| {}).unwrap();
+        assert_eq!(written, 2);
+
+        let out = String::from_utf8(out).unwrap();
+        let rows: Vec<serde_json::Value> = out
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(rows[0]["line_number"], 41);
+        assert_eq!(rows[0]["level"], "ERROR");
+        assert!(rows[0]["timestamp"]
+            .as_str()
+            .unwrap()
+            .starts_with("2024-01-15T10:00:00"));
+        assert_eq!(rows[0]["content"], "2024-01-15 10:00:00 ERROR disk full");
+        assert!(rows[1]["timestamp"].is_null());
+    }
+
+    #[test]
+    fn test_csv_has_a_column_per_field_and_raw_unparsed_lines() {
+        let mut lines = lines();
+        lines[0].fields = Some(HashMap::from([
+            ("timestamp".to_string(), "2024-01-15 10:00:00".to_string()),
+            ("level".to_string(), "ERROR".to_string()),
+            ("message".to_string(), "disk full, retrying".to_string()),
+        ]));
+        let columns = ["timestamp", "level", "message"].map(String::from);
+
+        let mut out = Vec::new();
+        let written =
+            write_lines(&mut out, &lines, LineExportFormat::Csv, &columns, |_| {}).unwrap();
+        assert_eq!(written, 2);
+
+        let out = String::from_utf8(out).unwrap();
+        let rows: Vec<&str> = out.lines().collect();
+        assert_eq!(rows[0], "line_number,timestamp,level,message,raw");
+        assert!(rows[1].starts_with("41,2024-01-15T10:00:00"));
+        assert!(rows[1].ends_with(",ERROR,\"disk full, retrying\","));
+        assert_eq!(rows[2], "42,,,,    at write()");
+        assert_eq!(
+            LineExportFormat::for_path(Path::new("parsed.CSV")),
+            LineExportFormat::Csv
+        );
+    }
+
+    #[test]
+    fn test_background_export_writes_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("filtered.log");
+        let source = ExportSource {
+            entries: entries(),
+            grok_parser: None,
+            redactor: Redactor::default(),
+        };
+        let mut export =
+            LineExport::start(path.clone(), source, LineExportFormat::Text, Vec::new());
+
+        assert_eq!(wait(&mut export).unwrap(), 2);
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "2024-01-15 10:00:00 ERROR disk full\n    at write()\n"
+        );
+    }
+
+    #[test]
+    fn test_background_csv_export_parses_unparsed_entries() {
+        use crate::grok_parser::BuiltinPattern;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("parsed.csv");
+        let mut parser = GrokParser::new();
+        parser
+            .set_builtin_pattern(BuiltinPattern::SimpleLog)
+            .unwrap();
+        let columns = parser.active_pattern().unwrap().columns.clone();
+        let source = ExportSource {
+            entries: entries(),
+            grok_parser: Some(parser),
+            redactor: Redactor::default(),
+        };
+        let mut export = LineExport::start(path.clone(), source, LineExportFormat::Csv, columns);
+
+        assert_eq!(wait(&mut export).unwrap(), 2);
+        let out = std::fs::read_to_string(&path).unwrap();
+        let rows: Vec<&str> = out.lines().collect();
+        assert!(rows[1].contains("disk full"));
+        assert!(rows[1].ends_with(','), "parsed lines leave raw empty");
+        assert!(rows[2].ends_with("    at write()"));
+    }
+}
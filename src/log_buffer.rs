@@ -1,6 +1,9 @@
 //! Log buffer management with support for large files
 
-use crate::log_entry::{LogEntry, LogLevel};
+use crate::config::{TimestampFallback, TrimStrategy};
+use crate::log_entry::{LogEntry, LogLevel, TimestampSource};
+use chrono::{DateTime, Local, NaiveDateTime};
+use regex::Regex;
 use std::collections::VecDeque;
 use std::ops::Range;
 
@@ -11,8 +14,20 @@ pub struct LogBufferConfig {
     pub max_lines: usize,
     /// Whether to automatically trim old entries when limit is reached
     pub auto_trim: bool,
+    /// Which entries are trimmed
+    pub trim_strategy: TrimStrategy,
+    /// Entries kept at the start with [`TrimStrategy::KeepHeadAndTail`]
+    pub head_lines: usize,
     /// Number of lines to load when scrolling up (chunk size)
     pub chunk_size: usize,
+    /// Minimum distance (rows) from the top edge that triggers loading more
+    pub load_threshold: usize,
+    /// Frames of scrolling to look ahead when prefetching the next chunk
+    pub prefetch_frames: usize,
+    /// Lines matching this continue the entry before them (stack trace frames)
+    pub continuation: Option<Regex>,
+    /// Timestamp used for lines without an embedded one
+    pub timestamp_fallback: TimestampFallback,
 }
 
 impl Default for LogBufferConfig {
@@ -20,11 +35,73 @@ impl Default for LogBufferConfig {
         Self {
             max_lines: 100_000,
             auto_trim: true,
+            trim_strategy: TrimStrategy::DropOldest,
+            head_lines: 1_000,
             chunk_size: 5_000, // Load 5k lines per chunk when scrolling up
+            load_threshold: 100,
+            prefetch_frames: 30,
+            continuation: None,
+            timestamp_fallback: TimestampFallback::None,
         }
     }
 }
 
+/// Number of lines at each log level
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LevelCounts {
+    /// Counts indexed by level, in ascending severity
+    counts: [usize; 6],
+}
+
+impl LevelCounts {
+    /// Count the levels of some entries
+    fn of<'a>(entries: impl IntoIterator<Item = &'a LogEntry>) -> Self {
+        let mut counts = Self::default();
+        for entry in entries {
+            counts.add(entry.level);
+        }
+        counts
+    }
+
+    pub fn add(&mut self, level: Option<LogLevel>) {
+        if let Some(level) = level {
+            self.counts[level as usize] += 1;
+        }
+    }
+
+    fn remove(&mut self, level: Option<LogLevel>) {
+        if let Some(level) = level {
+            self.counts[level as usize] = self.counts[level as usize].saturating_sub(1);
+        }
+    }
+
+    /// Lines at a level
+    pub fn get(&self, level: LogLevel) -> usize {
+        self.counts[level as usize]
+    }
+
+    /// Lines with a detected level
+    pub fn total(&self) -> usize {
+        self.counts.iter().sum()
+    }
+
+    /// Add the counts of `other`
+    pub fn merge(&mut self, other: &LevelCounts) {
+        for (count, more) in self.counts.iter_mut().zip(other.counts) {
+            *count += more;
+        }
+    }
+}
+
+/// Lines trimmed from the middle of the buffer by [`TrimStrategy::KeepHeadAndTail`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OmittedLines {
+    /// Index of the first entry after the gap
+    pub index: usize,
+    /// Number of lines left out
+    pub count: usize,
+}
+
 /// Lazy loading state for large files
 #[derive(Debug, Clone, Default)]
 pub struct LazyLoadState {
@@ -40,6 +117,8 @@ pub struct LazyLoadState {
     pub loading_in_progress: bool,
     /// Request to load more data (set by UI, processed by background thread)
     pub load_more_requested: bool,
+    /// Row checked for lazy loading on the previous frame (for scroll speed)
+    pub last_check_row: Option<usize>,
 }
 
 /// Buffer for storing log entries with efficient operations
@@ -58,6 +137,18 @@ pub struct LogBuffer {
     using_shadow: bool,
     /// Lazy loading state
     pub lazy_load: LazyLoadState,
+    /// Level counts of `entries`, kept up to date as lines come and go
+    level_counts: LevelCounts,
+    /// Level counts of `shadow_entries`
+    shadow_level_counts: LevelCounts,
+    /// Gap left in `entries` by trimming between head and tail
+    omitted: Option<OmittedLines>,
+    /// Gap in `shadow_entries`
+    shadow_omitted: Option<OmittedLines>,
+    /// Modification time of the source file, for the timestamp fallback
+    file_mtime: Option<DateTime<Local>>,
+    /// Bumped by every change other than appending and trimming lines
+    revision: u64,
 }
 
 impl LogBuffer {
@@ -76,21 +167,110 @@ impl LogBuffer {
             first_line_number: 1,
             using_shadow: false,
             lazy_load: LazyLoadState::default(),
+            level_counts: LevelCounts::default(),
+            shadow_level_counts: LevelCounts::default(),
+            omitted: None,
+            shadow_omitted: None,
+            file_mtime: None,
+            revision: 0,
         }
     }
 
     /// Add a single log entry
-    pub fn push(&mut self, entry: LogEntry) {
+    pub fn push(&mut self, mut entry: LogEntry) {
         self.total_lines_added += 1;
+        self.flag_continuation(&mut entry);
 
         if self.config.auto_trim && self.entries.len() >= self.config.max_lines {
-            self.entries.pop_front();
-            self.first_line_number += 1;
+            self.trim_one();
         }
 
+        self.level_counts.add(entry.level);
         self.entries.push_back(entry);
     }
 
+    /// Make room for one entry according to the trim strategy
+    fn trim_one(&mut self) {
+        let head = match self.config.trim_strategy {
+            TrimStrategy::DropOldest => 0,
+            TrimStrategy::KeepHeadAndTail => self.head_len(self.config.head_lines),
+        };
+
+        if head == 0 && self.omitted.is_none() {
+            if let Some(trimmed) = self.entries.pop_front() {
+                self.level_counts.remove(trimmed.level);
+            }
+            self.first_line_number += 1;
+        } else if head == 0 {
+            // A gap left by the other strategy moves up until it reaches the front
+            if let Some(trimmed) = self.entries.pop_front() {
+                self.level_counts.remove(trimmed.level);
+            }
+            self.first_line_number += 1;
+            if let Some(gap) = self.omitted.as_mut() {
+                gap.index -= 1;
+            }
+            self.close_leading_gap();
+        } else {
+            self.trim_middle(head);
+        }
+    }
+
+    /// Entries kept in front of the gap: those before it, or `head` while
+    /// there is none yet
+    fn head_len(&self, head: usize) -> usize {
+        self.omitted
+            .map_or(head, |gap| gap.index)
+            .min(self.config.max_lines.saturating_sub(1))
+    }
+
+    /// Trim the entry at the gap, keeping the first `head` entries: the one
+    /// before the gap while it starts after them, else the one after it
+    fn trim_middle(&mut self, head: usize) {
+        let (index, count) = match self.omitted {
+            Some(gap) if gap.index > head => (gap.index - 1, gap.count),
+            Some(gap) => (gap.index, gap.count),
+            None => (head, 0),
+        };
+        if let Some(trimmed) = self.entries.remove(index) {
+            self.level_counts.remove(trimmed.level);
+            self.omitted = Some(OmittedLines {
+                index,
+                count: count + 1,
+            });
+            self.close_leading_gap();
+        }
+    }
+
+    /// A gap in front of every entry only moves the first line number
+    fn close_leading_gap(&mut self) {
+        if let Some(gap) = self.omitted.filter(|gap| gap.index == 0) {
+            self.first_line_number += gap.count;
+            self.omitted = None;
+            self.revision += 1;
+        }
+    }
+
+    /// Change which entries are trimmed once the buffer is full
+    pub fn set_trim_strategy(&mut self, strategy: TrimStrategy, head_lines: usize) {
+        if (strategy, head_lines) == (self.config.trim_strategy, self.config.head_lines) {
+            return;
+        }
+        self.config.trim_strategy = strategy;
+        self.config.head_lines = head_lines;
+        // Views placing the gap marker see the change
+        self.revision += 1;
+    }
+
+    /// The gap left by [`TrimStrategy::KeepHeadAndTail`], if lines were trimmed
+    pub fn omitted(&self) -> Option<OmittedLines> {
+        if self.using_shadow && self.entries.is_empty() {
+            self.shadow_omitted
+        } else {
+            self.omitted
+        }
+    }
+
     /// Add multiple log entries
     pub fn extend(&mut self, entries: impl IntoIterator<Item = LogEntry>) {
         for entry in entries {
@@ -106,6 +286,7 @@ impl LogBuffer {
             if self.entries.len() >= 10 {
                 self.shadow_entries.clear();
                 self.using_shadow = false;
+                self.revision += 1;
             }
         }
     }
@@ -115,8 +296,11 @@ impl LogBuffer {
     pub fn clear(&mut self) {
         // Move current entries to shadow buffer before clearing
         self.shadow_entries = std::mem::take(&mut self.entries);
+        self.shadow_level_counts = std::mem::take(&mut self.level_counts);
+        self.shadow_omitted = self.omitted.take();
         self.using_shadow = true;
         self.first_line_number = self.total_lines_added + 1;
+        self.revision += 1;
         // Reset lazy load state
         self.lazy_load = LazyLoadState::default();
     }
@@ -132,18 +316,37 @@ impl LogBuffer {
         if let Some(first) = entries.first() {
             self.first_line_number = first.line_number;
         }
+        self.revision += 1;
 
         // Prepend entries (insert at front)
-        for entry in entries.into_iter().rev() {
+        let prepended = entries.len();
+        for mut entry in entries.into_iter().rev() {
+            self.flag_continuation(&mut entry);
+            self.level_counts.add(entry.level);
             self.entries.push_front(entry);
         }
+        if let Some(gap) = self.omitted.as_mut() {
+            gap.index += prepended;
+        }
 
         // Trim from the back if we exceed max_lines (keep older entries, drop newest)
         // Actually, we want to keep newest, so trim from front after prepend
         // But for lazy loading, we want to keep the view stable
         // So we trim from the back (newest) when prepending
         while self.config.auto_trim && self.entries.len() > self.config.max_lines {
-            self.entries.pop_back();
+            if self.config.trim_strategy == TrimStrategy::KeepHeadAndTail {
+                // Keep the loaded lines and the head, trimming after them
+                self.trim_middle(self.head_len(prepended));
+            } else if let Some(trimmed) = self.entries.pop_back() {
+                self.level_counts.remove(trimmed.level);
+            }
+        }
+        // Everything after the gap may have been trimmed with it
+        if self
+            .omitted
+            .is_some_and(|gap| gap.index >= self.entries.len())
+        {
+            self.omitted = None;
         }
     }
 
@@ -158,6 +361,9 @@ impl LogBuffer {
         self.entries.clear();
         self.shadow_entries.clear();
         self.using_shadow = false;
+        self.omitted = None;
+        self.shadow_omitted = None;
+        self.revision += 1;
 
         if let Some(first) = entries.first() {
             self.first_line_number = first.line_number;
@@ -166,9 +372,11 @@ impl LogBuffer {
         let loaded_count = entries.len();
         self.total_lines_added = total_lines;
 
-        for entry in entries {
+        for mut entry in entries {
+            self.flag_continuation(&mut entry);
             self.entries.push_back(entry);
         }
+        self.level_counts = LevelCounts::of(&self.entries);
 
         // Set up lazy load state
         self.lazy_load = LazyLoadState {
@@ -178,12 +386,46 @@ impl LogBuffer {
             fully_loaded: loaded_count >= total_lines,
             loading_in_progress: false,
             load_more_requested: false,
+            last_check_row: None,
         };
     }
 
+    /// Set the pattern of continuation lines and re-flag every entry
+    pub fn set_continuation_pattern(&mut self, pattern: Option<Regex>) {
+        for entry in self
+            .entries
+            .iter_mut()
+            .chain(self.shadow_entries.iter_mut())
+        {
+            entry.set_continuation(
+                pattern
+                    .as_ref()
+                    .is_some_and(|regex| regex.is_match(&entry.content)),
+            );
+        }
+        self.config.continuation = pattern;
+        // Continuation lines don't count towards a level of their own
+        self.level_counts = LevelCounts::of(&self.entries);
+        self.revision += 1;
+    }
+
+    /// Whether lines are grouped into blocks by a continuation pattern
+    pub fn groups_continuations(&self) -> bool {
+        self.config.continuation.is_some()
+    }
+
+    fn flag_continuation(&self, entry: &mut LogEntry) {
+        entry.set_continuation(
+            self.config
+                .continuation
+                .as_ref()
+                .is_some_and(|regex| regex.is_match(&entry.content)),
+        );
+    }
+
     /// Check if we need to load more data (user is near the top)
     /// Returns true if load_more_requested should be set
-    pub fn should_load_more(&self, visible_start_row: usize) -> bool {
+    pub fn should_load_more(&self, visible_start_row: usize, rows_per_frame: usize) -> bool {
         if !self.lazy_load.enabled
             || self.lazy_load.fully_loaded
             || self.lazy_load.loading_in_progress
@@ -191,9 +433,24 @@ impl LogBuffer {
             return false;
         }
 
-        // Trigger load when user is within first 10% of loaded data or within 100 rows of top
-        let threshold = (self.entries.len() / 10).max(100).min(500);
-        visible_start_row < threshold
+        visible_start_row < self.load_trigger_distance(rows_per_frame)
+    }
+
+    /// Distance (rows) from the top edge at which the next chunk is requested.
+    /// Fast scrolling prefetches earlier so the chunk arrives before the edge.
+    pub fn load_trigger_distance(&self, rows_per_frame: usize) -> usize {
+        // Within the first 10% of loaded data, bounded by the configured threshold
+        let threshold = self.config.load_threshold;
+        let base = (self.entries.len() / 10).clamp(threshold, threshold.max(500));
+        let prefetch = rows_per_frame
+            .saturating_mul(self.config.prefetch_frames)
+            .min(self.config.chunk_size);
+        base + prefetch
+    }
+
+    /// Maximum number of lines kept in memory
+    pub fn max_lines(&self) -> usize {
+        self.config.max_lines
     }
 
     /// Get the chunk size for loading
@@ -226,19 +483,42 @@ impl LogBuffer {
         self.entries.get(index)
     }
 
-    /// Get mutable entry by index (0-indexed into current buffer)
-    pub fn get_mut(&mut self, index: usize) -> Option<&mut LogEntry> {
-        self.entries.get_mut(index)
+    /// Change the entry at an index (0-indexed into current buffer),
+    /// keeping the level counts in step if its level changes
+    pub fn update<R>(&mut self, index: usize, f: impl FnOnce(&mut LogEntry) -> R) -> Option<R> {
+        let entry = self.entries.get_mut(index)?;
+        let (level, timestamp, bookmarked) = (entry.level, entry.timestamp, entry.bookmarked);
+        let result = f(entry);
+        if (entry.timestamp, entry.bookmarked) != (timestamp, bookmarked) {
+            self.revision += 1;
+        }
+        if entry.level != level {
+            let new_level = entry.level;
+            self.level_counts.remove(level);
+            self.level_counts.add(new_level);
+            self.revision += 1;
+        }
+        Some(result)
     }
 
     /// Get entry by line number (1-indexed from file start)
     #[allow(dead_code)]
     pub fn get_by_line_number(&self, line_number: usize) -> Option<&LogEntry> {
+        self.index_of_line_number(line_number)
+            .and_then(|index| self.entries.get(index))
+    }
+
+    /// Index of the entry with a line number, if it is still in the buffer
+    pub fn index_of_line_number(&self, line_number: usize) -> Option<usize> {
         if line_number < self.first_line_number {
             return None;
         }
-        let index = line_number - self.first_line_number;
-        self.entries.get(index)
+        let mut index = line_number - self.first_line_number;
+        if let Some(gap) = self.omitted.filter(|gap| index >= gap.index) {
+            // Lines in the gap are gone, the ones after it moved up
+            index = index.checked_sub(gap.count).filter(|&i| i >= gap.index)?;
+        }
+        (index < self.entries.len()).then_some(index)
     }
 
     /// Get a range of entries
@@ -273,13 +553,56 @@ impl LogBuffer {
         }
     }
 
-    /// Iterate over all entries mutably
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut LogEntry> {
+    /// Change every entry, then recount the levels
+    pub fn update_all(&mut self, f: impl FnMut(&mut LogEntry)) {
+        if self.using_shadow && self.entries.is_empty() {
+            self.shadow_entries.iter_mut().for_each(f);
+            self.shadow_level_counts = LevelCounts::of(&self.shadow_entries);
+        } else {
+            self.entries.iter_mut().for_each(f);
+            self.level_counts = LevelCounts::of(&self.entries);
+        }
+        self.revision += 1;
+    }
+
+    /// Counter bumped by every change to the loaded entries other than
+    /// appending and trimming lines (reloads, prepends, edited levels,
+    /// timestamps or bookmarks, timestamp fallback). Caches can extend
+    /// themselves while it stays the same and rebuild once it moves.
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Number of lines at each level
+    pub fn level_counts(&self) -> &LevelCounts {
         if self.using_shadow && self.entries.is_empty() {
-            Box::new(self.shadow_entries.iter_mut()) as Box<dyn Iterator<Item = &mut LogEntry>>
+            &self.shadow_level_counts
         } else {
-            Box::new(self.entries.iter_mut()) as Box<dyn Iterator<Item = &mut LogEntry>>
+            &self.level_counts
+        }
+    }
+
+    /// Number of entries starting at or after byte `offset`, with their
+    /// levels (the lines tailed past the end of a background scan)
+    pub fn level_counts_since(&self, offset: u64) -> (usize, LevelCounts) {
+        let entries = if self.using_shadow && self.entries.is_empty() {
+            &self.shadow_entries
+        } else {
+            &self.entries
+        };
+        if entries
+            .front()
+            .is_none_or(|first| first.byte_offset >= offset)
+        {
+            return (entries.len(), *self.level_counts());
+        }
+        let mut counts = LevelCounts::default();
+        let mut lines = 0;
+        for entry in entries.iter().rev().take_while(|e| e.byte_offset >= offset) {
+            counts.add(entry.level);
+            lines += 1;
         }
+        (lines, counts)
     }
 
     /// Get first line number in buffer
@@ -302,6 +625,98 @@ impl LogBuffer {
             .unwrap_or(self.first_line_number.saturating_sub(1))
     }
 
+    /// Set the timestamp used for lines without an embedded one
+    pub fn set_timestamp_fallback(&mut self, fallback: TimestampFallback) {
+        if self.config.timestamp_fallback != fallback {
+            self.config.timestamp_fallback = fallback;
+            self.revision += 1;
+        }
+    }
+
+    /// Set the source file's modification time, used by the timestamp fallback
+    pub fn set_file_mtime(&mut self, mtime: Option<DateTime<Local>>) {
+        if self.file_mtime != mtime {
+            self.file_mtime = mtime;
+            if self.config.timestamp_fallback != TimestampFallback::None {
+                self.revision += 1;
+            }
+        }
+    }
+
+    /// Timestamp of an entry for time-based features and where it came from:
+    /// the line's own, or the configured fallback for lines without one
+    pub fn entry_timestamp_source(
+        &self,
+        entry: &LogEntry,
+    ) -> Option<(DateTime<Local>, TimestampSource)> {
+        entry.effective_timestamp(self.config.timestamp_fallback, self.file_mtime)
+    }
+
+    /// Timestamp of an entry for time-based features, see [`Self::entry_timestamp_source`]
+    pub fn entry_timestamp(&self, entry: &LogEntry) -> Option<DateTime<Local>> {
+        self.entry_timestamp_source(entry).map(|(ts, _)| ts)
+    }
+
+    /// Wall-clock timestamp of an entry, in the terms it was written in the log
+    fn naive_timestamp(&self, index: usize) -> Option<NaiveDateTime> {
+        // Detected timestamps keep the log's wall-clock time as their UTC value
+        self.entry_timestamp(self.get(index)?)
+            .map(|ts| ts.naive_utc())
+    }
+
+    /// Timestamp of the first line that carries one
+    pub fn first_timestamp(&self) -> Option<NaiveDateTime> {
+        (0..self.len()).find_map(|idx| self.naive_timestamp(idx))
+    }
+
+    /// Timestamp of the last line that carries one
+    pub fn last_timestamp(&self) -> Option<NaiveDateTime> {
+        (0..self.len())
+            .rev()
+            .find_map(|idx| self.naive_timestamp(idx))
+    }
+
+    /// Index of the timestamped line closest to `target`
+    ///
+    /// Binary-searches on the assumption that timestamps increase through the
+    /// buffer. Lines without a timestamp are skipped, so the result is always
+    /// a timestamped line; `None` when there are none.
+    pub fn nearest_timestamp(&self, target: NaiveDateTime) -> Option<usize> {
+        let stamped_from = |from: usize, to: usize| {
+            (from..to).find_map(|idx| self.naive_timestamp(idx).map(|ts| (idx, ts)))
+        };
+
+        // Every timestamped line before `lo` is earlier than the target
+        let (mut lo, mut hi) = (0, self.len());
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            match stamped_from(mid, hi) {
+                Some((idx, ts)) if ts < target => lo = idx + 1,
+                _ => hi = mid,
+            }
+        }
+
+        let before = (0..lo)
+            .rev()
+            .find_map(|idx| self.naive_timestamp(idx).map(|ts| (idx, ts)));
+        let after = stamped_from(lo, self.len());
+        match (before, after) {
+            (Some((b, b_ts)), Some((a, a_ts))) => {
+                Some(if target - b_ts <= a_ts - target { b } else { a })
+            }
+            (Some((idx, _)), None) | (None, Some((idx, _))) => Some(idx),
+            (None, None) => None,
+        }
+    }
+
+    /// Timestamp of the line at `index`, or of the closest line before it
+    /// that has one
+    pub fn timestamp_at_or_before(&self, index: usize) -> Option<DateTime<Local>> {
+        (0..=index.min(self.len().checked_sub(1)?))
+            .rev()
+            .find_map(|idx| self.entry_timestamp(self.get(idx)?))
+    }
+
     /// Filter entries by log level
     #[allow(dead_code)]
     pub fn filter_by_level(&self, levels: &[LogLevel]) -> Vec<&LogEntry> {
@@ -360,6 +775,8 @@ impl LogBuffer {
     pub fn toggle_bookmark(&mut self, index: usize) -> bool {
         if let Some(entry) = self.entries.get_mut(index) {
             entry.bookmarked = !entry.bookmarked;
+            entry.bookmark_color = 0;
+            self.revision += 1;
             entry.bookmarked
         } else {
             false
@@ -386,10 +803,14 @@ impl LogBuffer {
 
         for &idx in indices {
             if let Some(entry) = self.entries.get_mut(idx) {
+                if !new_state {
+                    entry.bookmark_color = 0;
+                }
                 entry.bookmarked = new_state;
                 count += 1;
             }
         }
+        self.revision += 1;
 
         count
     }
@@ -404,11 +825,70 @@ impl LogBuffer {
             .collect()
     }
 
+    /// Cycle to the next/previous warning-or-error line within the displayed rows.
+    ///
+    /// `view` is the filtered index set when a filter is active (otherwise the whole
+    /// buffer is used), so navigation respects what is on screen. Wraps around at
+    /// either end. Returns `(buffer_index, position, total)` with a 1-based position.
+    pub fn cycle_problem_line(
+        &self,
+        view: Option<&[usize]>,
+        current: Option<usize>,
+        forward: bool,
+    ) -> Option<(usize, usize, usize)> {
+        self.cycle_line(view, current, forward, |e| {
+            e.level.is_some_and(|level| level >= LogLevel::Warn)
+        })
+    }
+
+    /// Find the next (or previous) bookmarked line relative to `current`,
+    /// like [`Self::cycle_problem_line`]
+    pub fn cycle_bookmark(
+        &self,
+        view: Option<&[usize]>,
+        current: Option<usize>,
+        forward: bool,
+    ) -> Option<(usize, usize, usize)> {
+        self.cycle_line(view, current, forward, |e| e.bookmarked)
+    }
+
+    /// Next (or previous) line in view accepted by `wanted`, wrapping around
+    fn cycle_line(
+        &self,
+        view: Option<&[usize]>,
+        current: Option<usize>,
+        forward: bool,
+        wanted: impl Fn(&LogEntry) -> bool,
+    ) -> Option<(usize, usize, usize)> {
+        let is_wanted = |idx: usize| self.get(idx).is_some_and(&wanted);
+
+        let lines: Vec<usize> = match view {
+            Some(indices) => indices.iter().copied().filter(|&i| is_wanted(i)).collect(),
+            None => (0..self.len()).filter(|&i| is_wanted(i)).collect(),
+        };
+
+        if lines.is_empty() {
+            return None;
+        }
+
+        let position = match (current, forward) {
+            (Some(cur), true) => lines.iter().position(|&i| i > cur).unwrap_or(0),
+            (Some(cur), false) => lines
+                .iter()
+                .rposition(|&i| i < cur)
+                .unwrap_or(lines.len() - 1),
+            (None, true) => 0,
+            (None, false) => lines.len() - 1,
+        };
+
+        Some((lines[position], position + 1, lines.len()))
+    }
+
     /// Get memory usage estimate in bytes
     pub fn memory_usage(&self) -> usize {
         self.entries
             .iter()
-            .map(|e| std::mem::size_of::<LogEntry>() + e.content.len())
+            .map(|e| std::mem::size_of::<LogEntry>() + e.content.heap_size())
             .sum()
     }
 }
@@ -536,6 +1016,7 @@ mod tests {
             max_lines: 3,
             auto_trim: true,
             chunk_size: 5_000,
+            ..Default::default()
         };
         let mut buffer = LogBuffer::with_config(config);
 
@@ -548,6 +1029,202 @@ mod tests {
         assert_eq!(buffer.get(0).unwrap().line_number, 3);
     }
 
+    #[test]
+    fn test_keep_head_and_tail_trims_the_middle() {
+        let config = LogBufferConfig {
+            max_lines: 5,
+            trim_strategy: TrimStrategy::KeepHeadAndTail,
+            head_lines: 2,
+            ..Default::default()
+        };
+        let mut buffer = LogBuffer::with_config(config);
+        for i in 1..=5 {
+            buffer.push(LogEntry::new(i, format!("Line {}", i), 0));
+        }
+        assert_eq!(buffer.omitted(), None);
+
+        for i in 6..=9 {
+            buffer.push(LogEntry::new(i, format!("Line {}", i), 0));
+        }
+        let lines: Vec<_> = buffer.iter().map(|e| e.line_number).collect();
+        assert_eq!(lines, [1, 2, 7, 8, 9]);
+        assert_eq!(buffer.first_line_number(), 1);
+        assert_eq!(buffer.omitted(), Some(OmittedLines { index: 2, count: 4 }));
+
+        // Line numbers still resolve across the gap
+        assert_eq!(buffer.get_by_line_number(2).unwrap().line_number, 2);
+        assert!(buffer.get_by_line_number(4).is_none());
+        assert_eq!(buffer.get_by_line_number(8).unwrap().line_number, 8);
+    }
+
+    #[test]
+    fn test_dropping_oldest_moves_the_gap_to_the_front() {
+        let config = LogBufferConfig {
+            max_lines: 4,
+            trim_strategy: TrimStrategy::KeepHeadAndTail,
+            head_lines: 2,
+            ..Default::default()
+        };
+        let mut buffer = LogBuffer::with_config(config);
+        for i in 1..=6 {
+            buffer.push(LogEntry::new(i, format!("Line {}", i), 0));
+        }
+        assert_eq!(buffer.omitted(), Some(OmittedLines { index: 2, count: 2 }));
+
+        let revision = buffer.revision();
+        buffer.set_trim_strategy(TrimStrategy::DropOldest, 2);
+        assert!(buffer.revision() > revision);
+
+        buffer.push(LogEntry::new(7, "Line 7".to_string(), 0));
+        assert_eq!(buffer.omitted(), Some(OmittedLines { index: 1, count: 2 }));
+        assert_eq!(buffer.get_by_line_number(5).unwrap().line_number, 5);
+
+        // Once the head is gone, the gap is just lines before the first one
+        buffer.push(LogEntry::new(8, "Line 8".to_string(), 0));
+        assert_eq!(buffer.omitted(), None);
+        assert_eq!(buffer.first_line_number(), 5);
+        assert_eq!(buffer.get_by_line_number(6).unwrap().line_number, 6);
+        let lines: Vec<_> = buffer.iter().map(|e| e.line_number).collect();
+        assert_eq!(lines, [5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn test_prepend_keeps_head_and_loaded_lines() {
+        let config = LogBufferConfig {
+            max_lines: 4,
+            trim_strategy: TrimStrategy::KeepHeadAndTail,
+            head_lines: 2,
+            ..Default::default()
+        };
+        let mut buffer = LogBuffer::with_config(config);
+        for i in 11..=15 {
+            buffer.push(LogEntry::new(i, format!("Line {}", i), 0));
+        }
+        assert_eq!(buffer.omitted(), Some(OmittedLines { index: 2, count: 1 }));
+
+        buffer.prepend(vec![LogEntry::new(10, "Line 10".to_string(), 0)]);
+        let lines: Vec<_> = buffer.iter().map(|e| e.line_number).collect();
+        assert_eq!(lines, [10, 11, 12, 15]);
+        assert_eq!(buffer.first_line_number(), 10);
+        assert_eq!(buffer.omitted(), Some(OmittedLines { index: 3, count: 2 }));
+        assert_eq!(buffer.get_by_line_number(15).unwrap().line_number, 15);
+    }
+
+    #[test]
+    fn test_nearest_timestamp_snaps_to_timestamped_lines() {
+        let mut buffer = LogBuffer::new();
+        let lines = [
+            "continuation without a time",
+            "2024-01-15 10:00:00 INFO start",
+            "  at frame one",
+            "  at frame two",
+            "2024-01-15 10:05:00 WARN slow",
+            "2024-01-15 10:20:00 ERROR failed",
+        ];
+        for (i, line) in lines.iter().enumerate() {
+            buffer.push(LogEntry::new(i + 1, line.to_string(), 0));
+        }
+        let at = |time: &str| {
+            NaiveDateTime::parse_from_str(&format!("2024-01-15 {}", time), "%Y-%m-%d %H:%M:%S")
+                .unwrap()
+        };
+
+        assert_eq!(buffer.first_timestamp(), Some(at("10:00:00")));
+        assert_eq!(buffer.last_timestamp(), Some(at("10:20:00")));
+        assert_eq!(buffer.nearest_timestamp(at("09:00:00")), Some(1));
+        assert_eq!(buffer.nearest_timestamp(at("10:02:00")), Some(1));
+        assert_eq!(buffer.nearest_timestamp(at("10:04:00")), Some(4));
+        assert_eq!(buffer.nearest_timestamp(at("10:15:00")), Some(5));
+        assert_eq!(buffer.nearest_timestamp(at("23:00:00")), Some(5));
+        let naive = |ts: Option<DateTime<Local>>| ts.map(|ts| ts.naive_utc());
+        assert_eq!(
+            naive(buffer.timestamp_at_or_before(3)),
+            Some(at("10:00:00"))
+        );
+        assert_eq!(naive(buffer.timestamp_at_or_before(0)), None);
+
+        let mut plain = LogBuffer::new();
+        plain.push(LogEntry::new(1, "no time here".to_string(), 0));
+        assert_eq!(plain.first_timestamp(), None);
+        assert_eq!(plain.nearest_timestamp(at("10:00:00")), None);
+
+        // Time-based features see the fallback once one is chosen
+        let mtime = Local::now();
+        plain.set_file_mtime(Some(mtime));
+        assert_eq!(plain.first_timestamp(), None);
+        plain.set_timestamp_fallback(TimestampFallback::FileMtime);
+        assert_eq!(plain.first_timestamp(), Some(mtime.naive_local()));
+        assert_eq!(plain.nearest_timestamp(at("10:00:00")), Some(0));
+    }
+
+    #[test]
+    fn test_level_counts_follow_buffer() {
+        let config = LogBufferConfig {
+            max_lines: 3,
+            auto_trim: true,
+            ..Default::default()
+        };
+        let mut buffer = LogBuffer::with_config(config);
+        buffer.push(LogEntry::new(1, "ERROR first".to_string(), 0));
+        buffer.push(LogEntry::new(2, "INFO second".to_string(), 0));
+        buffer.push(LogEntry::new(3, "plain line".to_string(), 0));
+        assert_eq!(buffer.level_counts().get(LogLevel::Error), 1);
+        assert_eq!(buffer.level_counts().total(), 2);
+
+        // Trimming the oldest line drops its level
+        buffer.push(LogEntry::new(4, "WARN fourth".to_string(), 0));
+        assert_eq!(buffer.level_counts().get(LogLevel::Error), 0);
+        assert_eq!(buffer.level_counts().get(LogLevel::Warn), 1);
+
+        // A level changed in place moves between buckets
+        buffer.update(0, |entry| entry.level = Some(LogLevel::Error));
+        assert_eq!(buffer.level_counts().get(LogLevel::Info), 0);
+        assert_eq!(buffer.level_counts().get(LogLevel::Error), 1);
+        assert_eq!(buffer.level_counts().total(), 2);
+    }
+
+    #[test]
+    fn test_level_counts_since_offset() {
+        let mut buffer = LogBuffer::new();
+        buffer.push(LogEntry::new(1, "ERROR first".to_string(), 0));
+        buffer.push(LogEntry::new(2, "WARN second".to_string(), 12));
+        buffer.push(LogEntry::new(3, "plain third".to_string(), 24));
+        buffer.push(LogEntry::new(4, "ERROR fourth".to_string(), 36));
+
+        let (lines, counts) = buffer.level_counts_since(24);
+        assert_eq!(lines, 2);
+        assert_eq!(counts.get(LogLevel::Error), 1);
+        assert_eq!(counts.get(LogLevel::Warn), 0);
+
+        // Nothing tailed past the offset yet
+        assert_eq!(buffer.level_counts_since(49).0, 0);
+
+        // Everything loaded lies past the offset
+        let (lines, counts) = buffer.level_counts_since(0);
+        assert_eq!(lines, 4);
+        assert_eq!(counts, *buffer.level_counts());
+    }
+
+    #[test]
+    fn test_continuation_lines_have_no_level_of_their_own() {
+        let config = LogBufferConfig {
+            continuation: Some(Regex::new(r"^\s+at ").unwrap()),
+            ..Default::default()
+        };
+        let mut buffer = LogBuffer::with_config(config);
+        buffer.push(LogEntry::new(1, "WARN retrying".to_string(), 0));
+        let frame = "    at Retry.error(Retry.java:7)";
+        buffer.push(LogEntry::new(2, frame.to_string(), 0));
+        assert_eq!(buffer.get(1).unwrap().level, None);
+        assert_eq!(buffer.level_counts().get(LogLevel::Error), 0);
+        assert_eq!(buffer.level_counts().total(), 1);
+
+        // Lines standing alone again get their level back
+        buffer.set_continuation_pattern(None);
+        assert_eq!(buffer.get(1).unwrap().level, Some(LogLevel::Error));
+        assert_eq!(buffer.level_counts().get(LogLevel::Error), 1);
+    }
+
     #[test]
     fn test_search() {
         let mut buffer = LogBuffer::new();
@@ -558,4 +1235,79 @@ mod tests {
         let results = buffer.search("Hello", false, false);
         assert_eq!(results.len(), 2);
     }
+
+    #[test]
+    fn test_cycle_problem_line_filtered() {
+        let mut buffer = LogBuffer::new();
+        buffer.push(LogEntry::new(1, "INFO start".to_string(), 0));
+        buffer.push(LogEntry::new(2, "ERROR first".to_string(), 0));
+        buffer.push(LogEntry::new(3, "WARN second".to_string(), 0));
+        buffer.push(LogEntry::new(4, "INFO middle".to_string(), 0));
+        buffer.push(LogEntry::new(5, "ERROR third".to_string(), 0));
+
+        // Filtered view hides the WARN line (index 2)
+        let filtered = vec![0, 1, 3, 4];
+
+        assert_eq!(buffer.cycle_problem_line(Some(&filtered), None, true), Some((1, 1, 2)));
+        assert_eq!(buffer.cycle_problem_line(Some(&filtered), Some(1), true), Some((4, 2, 2)));
+        // Wraps around forward
+        assert_eq!(buffer.cycle_problem_line(Some(&filtered), Some(4), true), Some((1, 1, 2)));
+        // Wraps around backward
+        assert_eq!(buffer.cycle_problem_line(Some(&filtered), Some(1), false), Some((4, 2, 2)));
+
+        // Unfiltered view includes the WARN line
+        assert_eq!(buffer.cycle_problem_line(None, Some(1), true), Some((2, 2, 3)));
+
+        // No problems in view
+        assert_eq!(buffer.cycle_problem_line(Some(&[0, 3]), None, true), None);
+    }
+
+    #[test]
+    fn test_cycle_bookmark_filtered() {
+        let mut buffer = LogBuffer::new();
+        for n in 1..=6 {
+            buffer.push(LogEntry::new(n, format!("INFO line {}", n), 0));
+        }
+        buffer.toggle_bookmarks(&[1]);
+        buffer.toggle_bookmarks(&[3]);
+        buffer.toggle_bookmarks(&[5]);
+
+        // Filtered view hides the bookmark at index 3
+        let filtered = vec![0, 1, 2, 4, 5];
+        assert_eq!(buffer.cycle_bookmark(Some(&filtered), Some(2), true), Some((5, 2, 2)));
+        assert_eq!(buffer.cycle_bookmark(Some(&filtered), Some(5), true), Some((1, 1, 2)));
+        assert_eq!(buffer.cycle_bookmark(Some(&filtered), Some(1), false), Some((5, 2, 2)));
+        assert_eq!(buffer.cycle_bookmark(None, Some(2), true), Some((3, 2, 3)));
+        assert_eq!(buffer.cycle_bookmark(Some(&[0, 2]), None, true), None);
+    }
+
+    #[test]
+    fn test_prefetch_trigger_distance() {
+        let config = LogBufferConfig {
+            chunk_size: 1_000,
+            load_threshold: 100,
+            prefetch_frames: 10,
+            ..Default::default()
+        };
+        let mut buffer = LogBuffer::with_config(config);
+        let entries = (1..=2_000)
+            .map(|i| LogEntry::new(i + 8_000, format!("line {}", i), 0))
+            .collect();
+        buffer.init_with_tail(entries, 4_096, 10_000);
+
+        // Idle: 10% of the loaded rows
+        assert_eq!(buffer.load_trigger_distance(0), 200);
+        assert!(!buffer.should_load_more(300, 0));
+
+        // Scrolling up 20 rows per frame prefetches 10 frames ahead
+        assert_eq!(buffer.load_trigger_distance(20), 400);
+        assert!(buffer.should_load_more(300, 20));
+
+        // Prefetch never looks further ahead than one chunk
+        assert_eq!(buffer.load_trigger_distance(10_000), 1_200);
+
+        // Nothing is requested while a chunk is loading
+        buffer.lazy_load.loading_in_progress = true;
+        assert!(!buffer.should_load_more(0, 20));
+    }
 }
@@ -0,0 +1,330 @@
+//! Line-based diff between two log buffers
+//!
+//! A snapshot and its live tab come from the same file, so their lines are
+//! aligned by line number rather than by content: lines trimmed from the
+//! front of the live buffer are skipped instead of showing up as removed.
+//! Side-by-side rows of two unrelated panes use an LCS diff on line contents.
+
+use crate::log_buffer::LogBuffer;
+use crossbeam_channel::{bounded, Receiver};
+use std::thread;
+
+/// Largest LCS table (rows x columns) computed before giving up on alignment
+const MAX_LCS_CELLS: usize = 16_000_000;
+
+/// How a line differs between the old and new side
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffKind {
+    /// Present on both sides
+    Unchanged,
+    /// Only in the new side
+    Added,
+    /// Only in the old side
+    Removed,
+    /// Replaced by a different line (side-by-side rows only)
+    Changed,
+}
+
+/// A line of the diff
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffLine {
+    /// Kind of change
+    pub kind: DiffKind,
+    /// Line number on the old side
+    pub old_line: Option<usize>,
+    /// Line number on the new side
+    pub new_line: Option<usize>,
+    /// Line content
+    pub content: String,
+}
+
+/// A row of a side-by-side diff, indexing into each side
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiffRow {
+    /// Kind of change
+    pub kind: DiffKind,
+    /// Index on the old side
+    pub old: Option<usize>,
+    /// Index on the new side
+    pub new: Option<usize>,
+}
+
+/// Result of diffing two buffers
+#[derive(Debug, Clone, Default)]
+pub struct LogDiff {
+    /// Lines in display order
+    pub lines: Vec<DiffLine>,
+    /// Number of added lines
+    pub added: usize,
+    /// Number of removed lines
+    pub removed: usize,
+}
+
+impl LogDiff {
+    /// Diff the loaded entries of two buffers on a background thread.
+    /// The receiver gets the diff once it's computed.
+    pub fn between_in_background(old: &LogBuffer, new: &LogBuffer) -> Receiver<LogDiff> {
+        let lines = |buffer: &LogBuffer| -> Vec<(usize, String)> {
+            buffer
+                .iter()
+                .map(|e| (e.line_number, e.content.clone()))
+                .collect()
+        };
+        let (old_lines, new_lines) = (lines(old), lines(new));
+        let (tx, rx) = bounded(1);
+        thread::spawn(move || {
+            let _ = tx.send(Self::from_lines(&old_lines, &new_lines));
+        });
+        rx
+    }
+
+    /// Diff two lists of (line number, content), each sorted by line number
+    ///
+    /// Lines with the same number are unchanged if their contents match and
+    /// removed + added otherwise. Old lines before the first new line were
+    /// trimmed from the new side and are left out.
+    pub fn from_lines<S: AsRef<str>>(old: &[(usize, S)], new: &[(usize, S)]) -> Self {
+        let first_new = new.first().map_or(usize::MAX, |(line, _)| *line);
+        let old = &old[old.partition_point(|(line, _)| *line < first_new)..];
+
+        let mut diff = LogDiff::default();
+        let mut push = |kind, old_line, new_line, content: &S| {
+            match kind {
+                DiffKind::Added => diff.added += 1,
+                DiffKind::Removed => diff.removed += 1,
+                DiffKind::Unchanged | DiffKind::Changed => {}
+            }
+            diff.lines.push(DiffLine {
+                kind,
+                old_line,
+                new_line,
+                content: content.as_ref().to_string(),
+            });
+        };
+
+        let (mut i, mut j) = (0, 0);
+        while i < old.len() || j < new.len() {
+            match (old.get(i), new.get(j)) {
+                (Some((o, old_text)), Some((n, new_text))) if o == n => {
+                    if old_text.as_ref() == new_text.as_ref() {
+                        push(DiffKind::Unchanged, Some(*o), Some(*n), new_text);
+                    } else {
+                        push(DiffKind::Removed, Some(*o), None, old_text);
+                        push(DiffKind::Added, None, Some(*n), new_text);
+                    }
+                    i += 1;
+                    j += 1;
+                }
+                (Some((o, old_text)), Some((n, _))) if o < n => {
+                    push(DiffKind::Removed, Some(*o), None, old_text);
+                    i += 1;
+                }
+                (Some((o, old_text)), None) => {
+                    push(DiffKind::Removed, Some(*o), None, old_text);
+                    i += 1;
+                }
+                (_, Some((n, new_text))) => {
+                    push(DiffKind::Added, None, Some(*n), new_text);
+                    j += 1;
+                }
+                (None, None) => break,
+            }
+        }
+        diff
+    }
+
+    /// Whether both sides are identical
+    pub fn is_empty(&self) -> bool {
+        self.added == 0 && self.removed == 0
+    }
+}
+
+/// Align two line lists side by side
+///
+/// Runs of removed lines followed by added lines are paired up as changed
+/// rows, so a replaced line sits next to its replacement.
+pub fn align_side_by_side(old: &[&str], new: &[&str]) -> Vec<DiffRow> {
+    let mut rows = Vec::new();
+    let mut removed = Vec::new();
+    let mut added = Vec::new();
+    let flush = |rows: &mut Vec<DiffRow>, removed: &mut Vec<usize>, added: &mut Vec<usize>| {
+        for k in 0..removed.len().max(added.len()) {
+            let (old, new) = (removed.get(k).copied(), added.get(k).copied());
+            let kind = match (old, new) {
+                (Some(_), Some(_)) => DiffKind::Changed,
+                (Some(_), None) => DiffKind::Removed,
+                _ => DiffKind::Added,
+            };
+            rows.push(DiffRow { kind, old, new });
+        }
+        removed.clear();
+        added.clear();
+    };
+
+    for (kind, old_idx, new_idx) in diff_ops(old, new) {
+        match kind {
+            DiffKind::Removed => removed.extend(old_idx),
+            DiffKind::Added => added.extend(new_idx),
+            _ => {
+                flush(&mut rows, &mut removed, &mut added);
+                rows.push(DiffRow {
+                    kind,
+                    old: old_idx,
+                    new: new_idx,
+                });
+            }
+        }
+    }
+    flush(&mut rows, &mut removed, &mut added);
+    rows
+}
+
+/// Compute diff operations as (kind, old index, new index)
+fn diff_ops(old: &[&str], new: &[&str]) -> Vec<(DiffKind, Option<usize>, Option<usize>)> {
+    let prefix = old
+        .iter()
+        .zip(new.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut ops: Vec<_> = (0..prefix)
+        .map(|i| (DiffKind::Unchanged, Some(i), Some(i)))
+        .collect();
+
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+    ops.extend(
+        lcs_ops(old_mid, new_mid)
+            .into_iter()
+            .map(|(kind, o, n)| (kind, o.map(|i| i + prefix), n.map(|i| i + prefix))),
+    );
+
+    let old_tail = old.len() - suffix;
+    let new_tail = new.len() - suffix;
+    ops.extend((0..suffix).map(|i| (DiffKind::Unchanged, Some(old_tail + i), Some(new_tail + i))));
+    ops
+}
+
+/// LCS alignment of the changed middle section
+fn lcs_ops(old: &[&str], new: &[&str]) -> Vec<(DiffKind, Option<usize>, Option<usize>)> {
+    let (n, m) = (old.len(), new.len());
+    if n.saturating_mul(m) > MAX_LCS_CELLS {
+        // Too large to align: report the whole section as replaced
+        return (0..n)
+            .map(|i| (DiffKind::Removed, Some(i), None))
+            .chain((0..m).map(|j| (DiffKind::Added, None, Some(j))))
+            .collect();
+    }
+
+    // lengths[i][j] = LCS length of old[i..] and new[j..]
+    let width = m + 1;
+    let mut lengths = vec![0u32; (n + 1) * width];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lengths[i * width + j] = if old[i] == new[j] {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::with_capacity(n.max(m));
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old[i] == new[j] {
+            ops.push((DiffKind::Unchanged, Some(i), Some(j)));
+            i += 1;
+            j += 1;
+        } else if lengths[(i + 1) * width + j] >= lengths[i * width + j + 1] {
+            ops.push((DiffKind::Removed, Some(i), None));
+            i += 1;
+        } else {
+            ops.push((DiffKind::Added, None, Some(j)));
+            j += 1;
+        }
+    }
+    ops.extend((i..n).map(|i| (DiffKind::Removed, Some(i), None)));
+    ops.extend((j..m).map(|j| (DiffKind::Added, None, Some(j))));
+    ops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbered<'a>(lines: &[&'a str]) -> Vec<(usize, &'a str)> {
+        lines.iter().enumerate().map(|(i, l)| (i + 1, *l)).collect()
+    }
+
+    #[test]
+    fn test_changed_lines_are_aligned() {
+        let old = numbered(&["a", "b", "c", "d"]);
+        let new = numbered(&["a", "x", "c", "d", "e"]);
+        let diff = LogDiff::from_lines(&old, &new);
+
+        let kinds: Vec<_> = diff
+            .lines
+            .iter()
+            .map(|l| (l.kind, l.content.as_str()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (DiffKind::Unchanged, "a"),
+                (DiffKind::Removed, "b"),
+                (DiffKind::Added, "x"),
+                (DiffKind::Unchanged, "c"),
+                (DiffKind::Unchanged, "d"),
+                (DiffKind::Added, "e"),
+            ]
+        );
+        assert_eq!((diff.added, diff.removed), (2, 1));
+        assert_eq!(diff.lines[2].new_line, Some(2));
+        assert_eq!(diff.lines[1].old_line, Some(2));
+    }
+
+    #[test]
+    fn test_lines_trimmed_from_the_new_side_are_skipped() {
+        let old = numbered(&["a", "b", "c", "d"]);
+        let new = vec![(3, "c"), (4, "d"), (5, "e")];
+        let diff = LogDiff::from_lines(&old, &new);
+
+        let lines: Vec<_> = diff
+            .lines
+            .iter()
+            .map(|l| (l.kind, l.old_line, l.new_line))
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                (DiffKind::Unchanged, Some(3), Some(3)),
+                (DiffKind::Unchanged, Some(4), Some(4)),
+                (DiffKind::Added, None, Some(5)),
+            ]
+        );
+        assert_eq!((diff.added, diff.removed), (1, 0));
+    }
+
+    #[test]
+    fn test_side_by_side_pairs_replaced_lines() {
+        let rows = align_side_by_side(&["a", "b", "c", "d"], &["a", "x", "y", "d", "e"]);
+        let row = |kind, old, new| DiffRow { kind, old, new };
+        assert_eq!(
+            rows,
+            vec![
+                row(DiffKind::Unchanged, Some(0), Some(0)),
+                row(DiffKind::Changed, Some(1), Some(1)),
+                row(DiffKind::Changed, Some(2), Some(2)),
+                row(DiffKind::Unchanged, Some(3), Some(3)),
+                row(DiffKind::Added, None, Some(4)),
+            ]
+        );
+    }
+}
@@ -1,11 +1,19 @@
 //! Log entry data structures and parsing
 
-use chrono::{DateTime, Local, NaiveDateTime};
+use crate::config::TimestampFallback;
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime};
+use memmap2::Mmap;
 use regex::Regex;
-use std::sync::LazyLock;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::{Deref, Range};
+use std::sync::{Arc, LazyLock};
 
-/// Log severity level
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+/// Log severity level, ordered from least to most severe
+/// (Trace < Debug < Info < Warn < Error < Fatal)
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
 pub enum LogLevel {
     Trace,
     Debug,
@@ -17,6 +25,23 @@ pub enum LogLevel {
 }
 
 impl LogLevel {
+    /// All levels in ascending severity
+    pub fn all() -> [LogLevel; 6] {
+        [
+            LogLevel::Trace,
+            LogLevel::Debug,
+            LogLevel::Info,
+            LogLevel::Warn,
+            LogLevel::Error,
+            LogLevel::Fatal,
+        ]
+    }
+
+    /// Whether this level is at or above the given severity threshold
+    pub fn is_at_least(self, threshold: LogLevel) -> bool {
+        self >= threshold
+    }
+
     /// Parse log level from string
     pub fn from_str(s: &str) -> Option<Self> {
         let s = s.to_uppercase();
@@ -56,20 +81,107 @@ impl LogLevel {
     }
 }
 
+/// Text of a line: shared on the heap, or a byte range of a memory-mapped
+/// snapshot that is only read when the line is shown or searched. Either
+/// way a clone only copies a pointer, so background work can take a snapshot.
+#[derive(Clone)]
+pub enum LineText {
+    Owned(Arc<str>),
+    /// UTF-8 bytes of a mapped snapshot, see [`LineText::mapped`]
+    Mapped(MappedText),
+}
+
+/// Byte range of a mapped snapshot that held valid UTF-8 when indexed
+#[derive(Clone)]
+pub struct MappedText {
+    map: Arc<Mmap>,
+    range: Range<usize>,
+}
+
+impl LineText {
+    /// Text pointing into a mapping, or `None` when the bytes aren't UTF-8
+    pub fn mapped(map: Arc<Mmap>, range: Range<usize>) -> Option<Self> {
+        std::str::from_utf8(map.get(range.clone())?).ok()?;
+        Some(LineText::Mapped(MappedText { map, range }))
+    }
+
+    /// The text; mapped bytes are checked on every access and show as a
+    /// replacement character if they stopped being UTF-8
+    pub fn as_str(&self) -> &str {
+        match self {
+            LineText::Owned(text) => text,
+            LineText::Mapped(text) => text
+                .map
+                .get(text.range.clone())
+                .and_then(|bytes| std::str::from_utf8(bytes).ok())
+                .unwrap_or("\u{FFFD}"),
+        }
+    }
+
+    /// Bytes of text held on the heap (none for mapped lines)
+    pub fn heap_size(&self) -> usize {
+        match self {
+            LineText::Owned(text) => text.len(),
+            LineText::Mapped(..) => 0,
+        }
+    }
+}
+
+impl Deref for LineText {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl From<String> for LineText {
+    fn from(text: String) -> Self {
+        LineText::Owned(text.into())
+    }
+}
+
+impl fmt::Display for LineText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl fmt::Debug for LineText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl PartialEq for LineText {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl PartialEq<&str> for LineText {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
 /// A single log entry
 #[derive(Debug, Clone)]
 pub struct LogEntry {
     /// Line number in the original file (1-indexed)
     pub line_number: usize,
     /// Raw text content
-    pub content: String,
+    pub content: LineText,
     /// Detected log level
     pub level: Option<LogLevel>,
     /// Parsed timestamp (if detected)
-    #[allow(dead_code)]
     pub timestamp: Option<DateTime<Local>>,
+    /// Byte range of the parsed timestamp in `content`, when the line shows one
+    pub timestamp_span: Option<Range<usize>>,
     /// Whether this entry is bookmarked
     pub bookmarked: bool,
+    /// Bookmark color (see `bookmarks::BOOKMARK_COLORS`, 0 is the default)
+    pub bookmark_color: u8,
     /// Byte offset in file where this line starts
     #[allow(dead_code)]
     pub byte_offset: u64,
@@ -79,6 +191,21 @@ pub struct LogEntry {
     pub formatted_content: Option<String>,
     /// Formatted display segments with style info
     pub formatted_segments: Option<Vec<FormattedSegment>>,
+    /// When the line was appended while tailing (None for lines read at open)
+    pub ingested_at: Option<DateTime<Local>>,
+    /// Whether the line continues the entry before it (e.g. a stack trace frame)
+    pub continuation: bool,
+}
+
+/// Where an entry's effective timestamp came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimestampSource {
+    /// Parsed from the line itself
+    Line,
+    /// Time the line was appended while tailing
+    Ingestion,
+    /// Modification time of the file
+    FileMtime,
 }
 
 /// A segment of formatted text with styling information
@@ -92,52 +219,120 @@ pub struct FormattedSegment {
 
 impl LogEntry {
     /// Create a new log entry from raw text
-    pub fn new(line_number: usize, content: String, byte_offset: u64) -> Self {
+    pub fn new(line_number: usize, content: impl Into<LineText>, byte_offset: u64) -> Self {
+        let content = content.into();
         let level = Self::detect_level(&content);
-        let timestamp = Self::detect_timestamp(&content);
+        let (timestamp, timestamp_span) = Self::detect_timestamp_span(&content).unzip();
 
         Self {
             line_number,
             content,
             level,
             timestamp,
+            timestamp_span,
             bookmarked: false,
+            bookmark_color: 0,
             byte_offset,
             grok_fields: None,
             formatted_content: None,
             formatted_segments: None,
+            ingested_at: None,
+            continuation: false,
+        }
+    }
+
+    /// Get the timestamp to use for time-based features.
+    /// An embedded timestamp always wins; lines without one fall back
+    /// according to the configured preference. Fallback times are returned
+    /// like detected ones, with their wall-clock time as the UTC value.
+    pub fn effective_timestamp(
+        &self,
+        fallback: TimestampFallback,
+        file_mtime: Option<DateTime<Local>>,
+    ) -> Option<(DateTime<Local>, TimestampSource)> {
+        if let Some(ts) = self.timestamp {
+            return Some((ts, TimestampSource::Line));
+        }
+
+        let wall_clock = |ts: DateTime<Local>| {
+            DateTime::from_naive_utc_and_offset(ts.naive_local(), *ts.offset())
+        };
+        let mtime = file_mtime.map(|ts| (wall_clock(ts), TimestampSource::FileMtime));
+        match fallback {
+            TimestampFallback::None => None,
+            TimestampFallback::IngestionTime => self
+                .ingested_at
+                .map(|ts| (wall_clock(ts), TimestampSource::Ingestion))
+                .or(mtime),
+            TimestampFallback::FileMtime => mtime,
         }
     }
 
     /// Set grok parsed fields and optionally formatted content
-    /// If a 'level' field is present, it will also update the entry's log level
+    /// If a 'level' field is present, it will also update the entry's log level,
+    /// and a 'timestamp' field fills in a timestamp the raw line did not show
     pub fn set_grok_fields(&mut self, fields: std::collections::HashMap<String, String>) {
         // If grok extracted a level field, sync it to entry.level for filtering
-        if let Some(level_str) = fields.get("level") {
-            if let Some(level) = LogLevel::from_str(level_str) {
-                self.level = Some(level);
-            }
+        if let Some(level) = Self::grok_level(&fields).filter(|_| !self.continuation) {
+            self.level = Some(level);
+        }
+        if self.timestamp.is_none() {
+            self.timestamp = fields
+                .get("timestamp")
+                .and_then(|timestamp| Self::detect_timestamp(timestamp));
         }
         self.grok_fields = Some(fields);
     }
 
     /// Clear grok parsed fields and formatted content
-    /// Also restores the log level to the originally detected value
+    /// Also restores the log level and timestamp to the originally detected values
     pub fn clear_grok_fields(&mut self) {
         self.grok_fields = None;
         self.formatted_content = None;
         self.formatted_segments = None;
-        // Re-detect level from original content
-        self.level = Self::detect_level(&self.content);
+        // Re-detect level and timestamp from original content
+        self.level = if self.continuation {
+            None
+        } else {
+            Self::detect_level(&self.content)
+        };
+        (self.timestamp, self.timestamp_span) = Self::detect_timestamp_span(&self.content).unzip();
+    }
+
+    /// Mark the line as continuing the entry before it. Continuation lines
+    /// belong to their block's level, so their own is dropped, and restored
+    /// once they stand alone again.
+    pub fn set_continuation(&mut self, continuation: bool) {
+        if continuation == self.continuation {
+            return;
+        }
+        self.continuation = continuation;
+        self.level = if continuation {
+            None
+        } else {
+            self.grok_fields
+                .as_ref()
+                .and_then(Self::grok_level)
+                .or_else(|| Self::detect_level(&self.content))
+        };
+    }
+
+    /// Level named by a grok `level` field
+    fn grok_level(fields: &std::collections::HashMap<String, String>) -> Option<LogLevel> {
+        fields
+            .get("level")
+            .and_then(|level| LogLevel::from_str(level))
     }
 
     /// Get the display content (formatted if available, otherwise original)
     pub fn display_content(&self) -> &str {
-        self.formatted_content.as_deref().unwrap_or(&self.content)
+        self.formatted_content
+            .as_deref()
+            .unwrap_or(self.content.as_str())
     }
 
     /// Detect log level from content
-    fn detect_level(content: &str) -> Option<LogLevel> {
+    pub fn detect_level(content: &str) -> Option<LogLevel> {
         // Common log level patterns
         static LEVEL_REGEX: LazyLock<Regex> = LazyLock::new(|| {
             Regex::new(r"(?i)\b(TRACE|DEBUG|DBG|INFO|INF|WARN|WARNING|WRN|ERROR|ERR|FATAL|CRITICAL|CRIT)\b").unwrap()
@@ -150,6 +345,11 @@ impl LogEntry {
 
     /// Detect timestamp from content
     fn detect_timestamp(content: &str) -> Option<DateTime<Local>> {
+        Self::detect_timestamp_span(content).map(|(timestamp, _)| timestamp)
+    }
+
+    /// Detect timestamp from content along with where it was found
+    fn detect_timestamp_span(content: &str) -> Option<(DateTime<Local>, Range<usize>)> {
         // Common timestamp patterns
         static TIMESTAMP_PATTERNS: LazyLock<Vec<(Regex, &'static str)>> = LazyLock::new(|| {
             vec![
@@ -181,9 +381,9 @@ impl LogEntry {
                 if let Some(m) = cap.get(1) {
                     // Try parsing with the format
                     if let Ok(dt) = NaiveDateTime::parse_from_str(m.as_str(), format) {
-                        return Some(DateTime::from_naive_utc_and_offset(
-                            dt,
-                            *Local::now().offset(),
+                        return Some((
+                            DateTime::from_naive_utc_and_offset(dt, *Local::now().offset()),
+                            m.range(),
                         ));
                     }
                     // Try parsing date only formats
@@ -191,9 +391,9 @@ impl LogEntry {
                         &format!("{} 00:00:00", m.as_str()),
                         "%Y-%m-%d %H:%M:%S",
                     ) {
-                        return Some(DateTime::from_naive_utc_and_offset(
-                            dt,
-                            *Local::now().offset(),
+                        return Some((
+                            DateTime::from_naive_utc_and_offset(dt, *Local::now().offset()),
+                            m.range(),
                         ));
                     }
                 }
@@ -220,10 +420,55 @@ impl LogEntry {
     }
 }
 
+/// Parse a go-to-time query: an ISO-8601 date and time, or a time of day
+/// (`HH:MM[:SS]`) taken on `date`
+///
+/// The result is in the same wall-clock terms as the timestamps detected in
+/// log lines.
+pub fn parse_time_query(input: &str, date: NaiveDate) -> Option<NaiveDateTime> {
+    let input = input.trim();
+    if let Ok(dt) = DateTime::parse_from_rfc3339(input) {
+        return Some(dt.naive_local());
+    }
+    const DATE_TIME_FORMATS: [&str; 4] = [
+        "%Y-%m-%dT%H:%M:%S%.f",
+        "%Y-%m-%d %H:%M:%S%.f",
+        "%Y-%m-%dT%H:%M",
+        "%Y-%m-%d %H:%M",
+    ];
+    if let Some(dt) = DATE_TIME_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(input, format).ok())
+    {
+        return Some(dt);
+    }
+    ["%H:%M:%S%.f", "%H:%M"]
+        .iter()
+        .find_map(|format| NaiveTime::parse_from_str(input, format).ok())
+        .map(|time| date.and_time(time))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_time_query() {
+        let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        let expected = date.and_hms_opt(10, 30, 45).unwrap();
+        assert_eq!(parse_time_query("10:30:45", date), Some(expected));
+        assert_eq!(
+            parse_time_query(" 2024-01-15T10:30:45 ", date),
+            Some(expected)
+        );
+        assert_eq!(
+            parse_time_query("2024-01-15T10:30:45+08:00", date),
+            Some(expected)
+        );
+        assert_eq!(parse_time_query("10:30", date), date.and_hms_opt(10, 30, 0));
+        assert_eq!(parse_time_query("yesterday", date), None);
+    }
+
     #[test]
     fn test_log_level_detection() {
         assert_eq!(
@@ -246,4 +491,55 @@ mod tests {
         assert_eq!(LogLevel::from_str("error"), Some(LogLevel::Error));
         assert_eq!(LogLevel::from_str("WRN"), Some(LogLevel::Warn));
     }
+
+    #[test]
+    fn test_effective_timestamp_fallback() {
+        let ingested = Local::now();
+        let mtime = ingested - chrono::Duration::hours(1);
+
+        let mut plain = LogEntry::new(1, "no time here".to_string(), 0);
+        assert!(plain.timestamp.is_none());
+        // Fallback times come back in the terms of detected timestamps
+        let naive = |found: Option<(DateTime<Local>, TimestampSource)>| {
+            found.map(|(ts, source)| (ts.naive_utc(), source))
+        };
+
+        // Nothing recorded yet: ingestion preference falls back to file mtime
+        assert_eq!(
+            naive(plain.effective_timestamp(TimestampFallback::IngestionTime, Some(mtime))),
+            Some((mtime.naive_local(), TimestampSource::FileMtime))
+        );
+
+        plain.ingested_at = Some(ingested);
+        assert_eq!(
+            naive(plain.effective_timestamp(TimestampFallback::IngestionTime, Some(mtime))),
+            Some((ingested.naive_local(), TimestampSource::Ingestion))
+        );
+        assert_eq!(
+            naive(plain.effective_timestamp(TimestampFallback::FileMtime, Some(mtime))),
+            Some((mtime.naive_local(), TimestampSource::FileMtime))
+        );
+        assert_eq!(
+            plain.effective_timestamp(TimestampFallback::None, Some(mtime)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_embedded_timestamp_takes_priority() {
+        let mut entry = LogEntry::new(1, "2024-01-15 10:30:45 INFO started".to_string(), 0);
+        let embedded = entry.timestamp.expect("timestamp should be detected");
+        entry.ingested_at = Some(Local::now());
+
+        for fallback in [
+            TimestampFallback::None,
+            TimestampFallback::IngestionTime,
+            TimestampFallback::FileMtime,
+        ] {
+            assert_eq!(
+                entry.effective_timestamp(fallback, Some(Local::now())),
+                Some((embedded, TimestampSource::Line))
+            );
+        }
+    }
 }
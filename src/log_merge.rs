@@ -0,0 +1,180 @@
+//! Merging several logs into one chronological view
+//!
+//! Lines are ordered by the timestamp `LogEntry::new` detected in their
+//! source. A line without a timestamp stays right after the timestamped line
+//! before it (the rest of a multi-line message), and every line is prefixed
+//! with a tag naming its source.
+
+use crate::log_entry::LogEntry;
+use crate::log_reader::LogReader;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use std::path::{Path, PathBuf};
+
+/// Lines of one source to merge
+pub struct MergeSource {
+    /// Tag prefixed to the source's lines
+    pub tag: String,
+    /// The source's lines, in file order
+    pub entries: Vec<LogEntry>,
+}
+
+/// Tags for the sources: their file names, with the parent directory
+/// added when two sources share a name
+pub fn source_tags(paths: &[PathBuf]) -> Vec<String> {
+    let file_name = |path: &Path| {
+        path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.display().to_string())
+    };
+    paths
+        .iter()
+        .map(|path| {
+            let name = file_name(path);
+            let shared = paths.iter().filter(|p| file_name(p) == name).count() > 1;
+            match path.parent().and_then(|p| p.file_name()) {
+                Some(parent) if shared => format!("{}/{}", parent.to_string_lossy(), name),
+                _ => name,
+            }
+        })
+        .collect()
+}
+
+/// Merge the sources into one list ordered by timestamp, renumbering the
+/// lines. Lines with equal timestamps keep the order of the sources.
+pub fn merge_by_timestamp(sources: Vec<MergeSource>) -> Vec<LogEntry> {
+    // A block is a timestamped line and the untimestamped lines after it;
+    // lines before a source's first timestamp form a block sorted first
+    let mut blocks: Vec<(Option<DateTime<Local>>, Vec<LogEntry>)> = Vec::new();
+    for source in sources {
+        let mut source_started = false;
+        for mut entry in source.entries {
+            let tag = format!("[{}] ", source.tag);
+            entry.timestamp_span = entry
+                .timestamp_span
+                .map(|span| span.start + tag.len()..span.end + tag.len());
+            entry.content = format!("{}{}", tag, entry.content).into();
+            match blocks.last_mut() {
+                Some((_, lines)) if source_started && entry.timestamp.is_none() => {
+                    lines.push(entry)
+                }
+                _ => blocks.push((entry.timestamp, vec![entry])),
+            }
+            source_started = true;
+        }
+    }
+
+    // Stable, so equal timestamps keep their source order
+    blocks.sort_by_key(|(timestamp, _)| *timestamp);
+    blocks
+        .into_iter()
+        .flat_map(|(_, lines)| lines)
+        .enumerate()
+        .map(|(i, mut entry)| {
+            entry.line_number = i + 1;
+            entry.byte_offset = 0;
+            entry
+        })
+        .collect()
+}
+
+/// Read the last `max_lines` lines of each file and merge them, keeping
+/// the newest `max_lines` of the result. Runs off the UI thread.
+pub fn read_and_merge(
+    paths: &[PathBuf],
+    tags: &[String],
+    max_lines: usize,
+) -> Result<Vec<LogEntry>> {
+    let mut sources = Vec::with_capacity(paths.len());
+    for (path, tag) in paths.iter().zip(tags) {
+        let entries = LogReader::new(path)
+            .and_then(|mut reader| {
+                if reader.is_compressed() {
+                    reader
+                        .read_compressed(max_lines)
+                        .map(|(entries, _)| entries)
+                } else {
+                    reader.read_tail(max_lines).map(|(entries, _, _)| entries)
+                }
+            })
+            .with_context(|| path.display().to_string())?;
+        sources.push(MergeSource {
+            tag: tag.clone(),
+            entries,
+        });
+    }
+
+    let mut entries = merge_by_timestamp(sources);
+    entries.drain(..entries.len().saturating_sub(max_lines));
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(tag: &str, lines: &[&str]) -> MergeSource {
+        MergeSource {
+            tag: tag.to_string(),
+            entries: lines
+                .iter()
+                .enumerate()
+                .map(|(i, line)| LogEntry::new(i + 1, line.to_string(), 0))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_sources_interleave_by_timestamp() {
+        let api = source(
+            "api",
+            &[
+                "2024-01-15 10:00:01 INFO request start",
+                "2024-01-15 10:00:04 ERROR upstream failed",
+                "    at proxy()",
+                "    at handle()",
+            ],
+        );
+        let db = source(
+            "db",
+            &[
+                "starting up",
+                "2024-01-15 10:00:02 INFO query",
+                "2024-01-15 10:00:04 WARN slow query",
+            ],
+        );
+
+        let merged = merge_by_timestamp(vec![api, db]);
+        let lines: Vec<&str> = merged.iter().map(|e| e.content.as_str()).collect();
+        assert_eq!(
+            lines,
+            [
+                "[db] starting up",
+                "[api] 2024-01-15 10:00:01 INFO request start",
+                "[db] 2024-01-15 10:00:02 INFO query",
+                "[api] 2024-01-15 10:00:04 ERROR upstream failed",
+                "[api]     at proxy()",
+                "[api]     at handle()",
+                "[db] 2024-01-15 10:00:04 WARN slow query",
+            ]
+        );
+        let numbers: Vec<usize> = merged.iter().map(|e| e.line_number).collect();
+        assert_eq!(numbers, [1, 2, 3, 4, 5, 6, 7]);
+        // Detected fields are kept from the source line
+        assert!(merged[3].timestamp.is_some());
+        assert_eq!(merged[3].level, Some(crate::log_entry::LogLevel::Error));
+    }
+
+    #[test]
+    fn test_tags_disambiguate_shared_file_names() {
+        let paths = [
+            PathBuf::from("/srv/orders/app.log"),
+            PathBuf::from("/srv/billing/app.log"),
+            PathBuf::from("/srv/gateway.log"),
+        ];
+        assert_eq!(
+            source_tags(&paths),
+            ["orders/app.log", "billing/app.log", "gateway.log"]
+        );
+    }
+}
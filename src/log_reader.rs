@@ -1,12 +1,18 @@
 //! Log file reading and parsing module
 
-use crate::log_entry::LogEntry;
+use crate::log_entry::{LineText, LogEntry};
 use anyhow::{Context, Result};
 use chardetng::EncodingDetector;
 use encoding_rs::Encoding;
+use flate2::read::MultiGzDecoder;
+use memmap2::Mmap;
+use std::collections::VecDeque;
 use std::fs::File;
 use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 /// Configuration for the log reader
 #[derive(Debug, Clone)]
@@ -17,6 +23,9 @@ pub struct LogReaderConfig {
     pub encoding: Option<&'static Encoding>,
     /// Maximum line length before truncation
     pub max_line_length: usize,
+    /// How long a last line without a terminator is held back while tailing,
+    /// waiting for the rest of it (zero shows partial lines immediately)
+    pub partial_line_timeout: Duration,
 }
 
 impl Default for LogReaderConfig {
@@ -25,10 +34,85 @@ impl Default for LogReaderConfig {
             buffer_size: 64 * 1024, // 64KB buffer
             encoding: None,
             max_line_length: 10_000, // 10KB max line
+            partial_line_timeout: Duration::from_millis(500),
         }
     }
 }
 
+/// Compressed formats, decoded whole into a read-only tab
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    Gzip,
+    Zstd,
+}
+
+impl Compression {
+    pub fn all() -> &'static [Compression] {
+        &[Compression::Gzip, Compression::Zstd]
+    }
+
+    /// File extensions of the format
+    fn extensions(self) -> &'static [&'static str] {
+        match self {
+            Compression::Gzip => &["gz"],
+            Compression::Zstd => &["zst", "zstd"],
+        }
+    }
+
+    /// First bytes of every stream of the format
+    fn magic(self) -> &'static [u8] {
+        match self {
+            Compression::Gzip => &[0x1f, 0x8b],
+            Compression::Zstd => &[0x28, 0xb5, 0x2f, 0xfd],
+        }
+    }
+
+    /// Detect the format of a file by its extension, then by its magic bytes
+    pub fn detect(path: &Path) -> Option<Self> {
+        if let Some(ext) = path.extension() {
+            let by_extension = Self::all().iter().find(|c| {
+                c.extensions()
+                    .iter()
+                    .any(|known| ext.eq_ignore_ascii_case(known))
+            });
+            if let Some(compression) = by_extension {
+                return Some(*compression);
+            }
+        }
+        let mut head = [0u8; 4];
+        let mut file = File::open(path).ok()?;
+        let len = file.read(&mut head).ok()?;
+        Self::all()
+            .iter()
+            .copied()
+            .find(|c| head[..len].starts_with(c.magic()))
+    }
+
+    /// Wrap `input` in a decoder for the format
+    pub fn decoder<'a>(self, input: impl Read + 'a) -> Result<Box<dyn Read + 'a>> {
+        Ok(match self {
+            Compression::Gzip => Box::new(MultiGzDecoder::new(input)),
+            Compression::Zstd => {
+                Box::new(zstd::Decoder::new(input).context("Failed to start zstd decoder")?)
+            }
+        })
+    }
+}
+
+/// Counts the bytes read through it, for progress while decoding
+struct CountingReader<'a, R> {
+    inner: R,
+    count: &'a AtomicU64,
+}
+
+impl<R: Read> Read for CountingReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.count.fetch_add(n as u64, Ordering::Relaxed);
+        Ok(n)
+    }
+}
+
 /// Log file reader with incremental reading support
 pub struct LogReader {
     /// Path to the log file
@@ -43,6 +127,156 @@ pub struct LogReader {
     encoding: &'static Encoding,
     /// File size at last read
     last_file_size: u64,
+    /// Identity of the file the reader is attached to
+    identity: Option<FileIdentity>,
+    /// Whether the file was rotated while opening and the reader reattached
+    reattached: bool,
+    /// Unterminated last line being held back: its offset and when it was first seen
+    partial_line: Option<(u64, Instant)>,
+    /// Compression of the file, read whole with [`Self::read_compressed`]
+    compression: Option<Compression>,
+    /// Whether the file was memory-mapped by [`Self::read_mapped`]
+    mapped: bool,
+}
+
+/// Identity of an opened file (device, inode)
+pub type FileIdentity = (u64, u64);
+
+/// How often to retry opening a file that keeps being replaced
+const MAX_ATTACH_ATTEMPTS: usize = 3;
+
+/// Files modified more recently than this are taken to be still written to,
+/// so they are followed rather than memory-mapped (a mapped tab is a
+/// snapshot that never sees new lines)
+const LIVE_FILE_WINDOW: Duration = Duration::from_secs(5 * 60);
+
+/// Get the identity of a file from its metadata
+fn file_identity(metadata: &std::fs::Metadata) -> Option<FileIdentity> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        Some((metadata.dev(), metadata.ino()))
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = metadata;
+        None
+    }
+}
+
+/// A file opened in a way that is stable against rotation
+struct Attachment {
+    file: File,
+    metadata: std::fs::Metadata,
+    identity: Option<FileIdentity>,
+    reattached: bool,
+}
+
+/// How lines are terminated in the file's encoding
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LineEnding {
+    /// Single `\n` byte (UTF-8 and other ASCII-compatible encodings)
+    Byte,
+    /// `\n` as a little-endian UTF-16 code unit (`0A 00`)
+    Utf16Le,
+    /// `\n` as a big-endian UTF-16 code unit (`00 0A`)
+    Utf16Be,
+}
+
+impl LineEnding {
+    fn for_encoding(encoding: &'static Encoding) -> Self {
+        if encoding == encoding_rs::UTF_16LE {
+            LineEnding::Utf16Le
+        } else if encoding == encoding_rs::UTF_16BE {
+            LineEnding::Utf16Be
+        } else {
+            LineEnding::Byte
+        }
+    }
+
+    /// Byte range of the line terminator if the `\n` byte at `i` ends a line.
+    /// `absolute` is the file offset of `bytes[0]`; UTF-16 code units start
+    /// at even file offsets.
+    fn terminator_at(self, bytes: &[u8], i: usize, absolute: u64) -> Option<(usize, usize)> {
+        if bytes[i] != b'\n' {
+            return None;
+        }
+        let even = (absolute + i as u64) % 2 == 0;
+        match self {
+            LineEnding::Byte => Some((i, i + 1)),
+            LineEnding::Utf16Le => (even && bytes.get(i + 1) == Some(&0)).then_some((i, i + 2)),
+            LineEnding::Utf16Be => (!even && i > 0 && bytes[i - 1] == 0).then_some((i - 1, i + 1)),
+        }
+    }
+
+    /// Read one line including its terminator into `buf`.
+    /// `offset` is the file offset the line starts at.
+    fn read_line<R: BufRead>(
+        self,
+        reader: &mut R,
+        offset: u64,
+        buf: &mut Vec<u8>,
+    ) -> Result<usize> {
+        let start = buf.len();
+        loop {
+            if reader.read_until(b'\n', buf)? == 0 || self == LineEnding::Byte {
+                break;
+            }
+            let newline_at = buf.len() - 1 - start;
+            if buf[start + newline_at] != b'\n' {
+                break; // EOF without a terminator
+            }
+            let even = (offset + newline_at as u64) % 2 == 0;
+            if self == LineEnding::Utf16Le && even {
+                // The high byte of the code unit decides whether this is a newline
+                let mut high = [0u8; 1];
+                if reader.read(&mut high)? == 0 {
+                    break;
+                }
+                buf.push(high[0]);
+            }
+            if self
+                .terminator_at(&buf[start..], newline_at, offset)
+                .is_some()
+            {
+                break;
+            }
+        }
+        Ok(buf.len() - start)
+    }
+
+    /// Whether `line` (starting at file offset `offset`) ends with a terminator
+    fn is_terminated(self, line: &[u8], offset: u64) -> bool {
+        let len = line.len();
+        (len.saturating_sub(2)..len).any(|i| {
+            self.terminator_at(line, i, offset)
+                .is_some_and(|(_, end)| end == len)
+        })
+    }
+
+    /// Split a chunk into lines (without terminators), last line first.
+    /// `chunk_start` is the file offset of the chunk.
+    fn split_backwards(self, chunk: &[u8], chunk_start: u64) -> Vec<(u64, Vec<u8>)> {
+        let mut lines = Vec::new();
+        let mut line_end = chunk.len();
+
+        for i in (0..chunk.len()).rev() {
+            let terminator = self.terminator_at(chunk, i, chunk_start);
+            if terminator.is_none() && i != 0 {
+                continue;
+            }
+            let (term_start, line_start) = terminator.unwrap_or((i, i));
+            if line_start < line_end {
+                lines.push((
+                    chunk_start + line_start as u64,
+                    chunk[line_start..line_end].to_vec(),
+                ));
+            }
+            line_end = term_start;
+        }
+
+        lines
+    }
 }
 
 impl LogReader {
@@ -53,44 +287,162 @@ impl LogReader {
 
     /// Create a new log reader with custom configuration
     pub fn with_config(path: impl AsRef<Path>, config: LogReaderConfig) -> Result<Self> {
-        let path = path.as_ref().to_path_buf();
+        Self::open_attached(path.as_ref(), config, |_| {})
+    }
 
-        // Detect encoding from the first few bytes
+    /// Open the reader, calling `before_recheck` between opening the file and
+    /// re-checking the path (lets tests race a rotation against the open)
+    fn open_attached(
+        path: &Path,
+        config: LogReaderConfig,
+        before_recheck: impl FnMut(usize),
+    ) -> Result<Self> {
+        let mut attachment = Self::attach(path, before_recheck)?;
+
+        // Detect encoding from the first few bytes of the attached file
+        // (of its decompressed contents for compressed files)
+        let compression = Compression::detect(path);
         let encoding = if let Some(enc) = config.encoding {
             enc
+        } else if let Some(compression) = compression {
+            Self::detect_encoding(&mut compression.decoder(&mut attachment.file)?)?
         } else {
-            Self::detect_encoding(&path)?
+            Self::detect_encoding(&mut attachment.file)?
         };
 
-        let metadata = std::fs::metadata(&path).context("Failed to get file metadata")?;
-
+        // Keep the path as given (not canonicalized) so a symlink that is
+        // re-pointed on rotation is still followed on later reads
         Ok(Self {
-            path,
+            path: path.to_path_buf(),
             offset: 0,
             line_count: 0,
             config,
             encoding,
-            last_file_size: metadata.len(),
+            last_file_size: attachment.metadata.len(),
+            identity: attachment.identity,
+            reattached: attachment.reattached,
+            partial_line: None,
+            compression,
+            mapped: false,
         })
     }
 
-    /// Detect the encoding of a file
-    fn detect_encoding(path: &Path) -> Result<&'static Encoding> {
-        let mut file = File::open(path).context("Failed to open file for encoding detection")?;
-        let mut buffer = [0u8; 8192];
-        let bytes_read = file.read(&mut buffer)?;
+    /// Open the file behind `path`, making sure it wasn't replaced mid-open
+    ///
+    /// The path is resolved and opened, then resolved again. If it now points
+    /// to another file (rotation raced the open), the live file is opened
+    /// instead.
+    fn attach(path: &Path, mut before_recheck: impl FnMut(usize)) -> Result<Attachment> {
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+
+            let canonical = path
+                .canonicalize()
+                .with_context(|| format!("Failed to resolve path {}", path.display()))?;
+            let file = File::open(&canonical).context("Failed to open log file")?;
+            let metadata = file.metadata().context("Failed to get file metadata")?;
+            let identity = file_identity(&metadata);
+
+            before_recheck(attempt);
+
+            let stable = match path.canonicalize() {
+                Ok(live) if live == canonical => std::fs::metadata(&live)
+                    .map(|m| file_identity(&m) == identity)
+                    .unwrap_or(false),
+                // Re-pointed or removed: the open raced a rotation
+                _ => false,
+            };
+
+            if stable {
+                if attempt > 1 {
+                    tracing::info!(
+                        "Reattached to {} after it was rotated while opening",
+                        path.display()
+                    );
+                }
+                return Ok(Attachment {
+                    file,
+                    metadata,
+                    identity,
+                    reattached: attempt > 1,
+                });
+            }
+
+            if attempt >= MAX_ATTACH_ATTEMPTS {
+                tracing::warn!(
+                    "{} kept changing while opening; using the last opened file",
+                    path.display()
+                );
+                return Ok(Attachment {
+                    file,
+                    metadata,
+                    identity,
+                    reattached: true,
+                });
+            }
+
+            tracing::warn!("{} was rotated while opening, reattaching", path.display());
+        }
+    }
 
-        if bytes_read == 0 {
+    /// Detect the encoding of a file
+    fn detect_encoding(input: &mut impl Read) -> Result<&'static Encoding> {
+        let mut sample = Vec::with_capacity(8192);
+        input
+            .take(8192)
+            .read_to_end(&mut sample)
+            .context("Failed to read file for encoding detection")?;
+
+        if sample.is_empty() {
             return Ok(encoding_rs::UTF_8);
         }
 
+        let sample = sample.as_slice();
+        if let Some((encoding, _)) = Encoding::for_bom(sample) {
+            return Ok(encoding);
+        }
+        if let Some(encoding) = Self::detect_utf16_without_bom(sample) {
+            return Ok(encoding);
+        }
+
         let mut detector = EncodingDetector::new();
-        detector.feed(&buffer[..bytes_read], true);
+        detector.feed(sample, true);
         let encoding = detector.guess(None, true);
 
         Ok(encoding)
     }
 
+    /// Recognize BOM-less UTF-16 by its alternating null bytes
+    /// (mostly-ASCII text has a zero high byte in every code unit)
+    fn detect_utf16_without_bom(sample: &[u8]) -> Option<&'static Encoding> {
+        let units = sample.len() / 2;
+        if units < 2 {
+            return None;
+        }
+
+        let (mut even_zeros, mut odd_zeros) = (0, 0);
+        for pair in sample.chunks_exact(2) {
+            even_zeros += usize::from(pair[0] == 0);
+            odd_zeros += usize::from(pair[1] == 0);
+        }
+
+        let mostly = |zeros: usize| zeros * 10 >= units * 4;
+        let rarely = |zeros: usize| zeros * 10 < units;
+        if mostly(odd_zeros) && rarely(even_zeros) {
+            Some(encoding_rs::UTF_16LE)
+        } else if mostly(even_zeros) && rarely(odd_zeros) {
+            Some(encoding_rs::UTF_16BE)
+        } else {
+            None
+        }
+    }
+
+    /// How lines end in the reader's encoding
+    fn line_ending(&self) -> LineEnding {
+        LineEnding::for_encoding(self.encoding)
+    }
+
     /// Read all lines from the current offset to the end
     pub fn read_new_lines(&mut self) -> Result<Vec<LogEntry>> {
         let file = File::open(&self.path).context("Failed to open log file")?;
@@ -115,32 +467,28 @@ impl LogReader {
 
         let mut entries = Vec::new();
         let mut line_buffer = Vec::new();
+        let line_ending = self.line_ending();
 
         loop {
             line_buffer.clear();
-            let bytes_read = reader.read_until(b'\n', &mut line_buffer)?;
+            let bytes_read = line_ending.read_line(&mut reader, self.offset, &mut line_buffer)?;
 
             if bytes_read == 0 {
                 break;
             }
 
             let line_offset = self.offset;
+            if !line_ending.is_terminated(&line_buffer, line_offset)
+                && self.hold_partial_line(line_offset)
+            {
+                // Still being written; read it again once it is complete
+                break;
+            }
+            self.partial_line = None;
             self.offset += bytes_read as u64;
             self.line_count += 1;
 
-            // Decode the line
-            let content = self.decode_line(&line_buffer);
-
-            // Truncate if too long
-            let content = if content.len() > self.config.max_line_length {
-                format!(
-                    "{}... [truncated, {} bytes total]",
-                    &content[..self.config.max_line_length],
-                    content.len()
-                )
-            } else {
-                content
-            };
+            let content = self.truncate_line(self.decode_line(&line_buffer));
 
             entries.push(LogEntry::new(self.line_count, content, line_offset));
         }
@@ -148,6 +496,36 @@ impl LogReader {
         Ok(entries)
     }
 
+    /// How long the unterminated line at the read offset has been held back
+    pub fn partial_line_held(&self) -> Option<Duration> {
+        self.partial_line
+            .filter(|(offset, _)| *offset == self.offset)
+            .map(|(_, since)| since.elapsed())
+    }
+
+    /// Continue holding back the line at the read offset, which an earlier
+    /// reader already held for `held`
+    pub fn restore_partial_line(&mut self, held: Duration) {
+        let since = Instant::now()
+            .checked_sub(held)
+            .unwrap_or_else(Instant::now);
+        self.partial_line = Some((self.offset, since));
+    }
+
+    /// Whether to hold back an unterminated last line starting at `offset`.
+    /// It is shown anyway once it stayed incomplete for the configured timeout.
+    fn hold_partial_line(&mut self, offset: u64) -> bool {
+        let now = Instant::now();
+        let since = match self.partial_line {
+            Some((pending, since)) if pending == offset => since,
+            _ => {
+                self.partial_line = Some((offset, now));
+                now
+            }
+        };
+        now.duration_since(since) < self.config.partial_line_timeout
+    }
+
     /// Decode a line from bytes to string
     fn decode_line(&self, bytes: &[u8]) -> String {
         let (decoded, _, had_errors) = self.encoding.decode(bytes);
@@ -170,6 +548,19 @@ impl LogReader {
         line
     }
 
+    /// Shorten a line longer than the configured maximum
+    fn truncate_line(&self, content: String) -> String {
+        if content.len() > self.config.max_line_length {
+            format!(
+                "{}... [truncated, {} bytes total]",
+                &content[..self.config.max_line_length],
+                content.len()
+            )
+        } else {
+            content
+        }
+    }
+
     /// Seek to a specific byte offset
     #[allow(dead_code)]
     pub fn seek(&mut self, offset: u64) {
@@ -211,17 +602,215 @@ impl LogReader {
         self.last_file_size
     }
 
+    /// Identity of the file the reader is attached to
+    pub fn identity(&self) -> Option<FileIdentity> {
+        self.identity
+    }
+
+    /// Whether the file was rotated while opening and the reader reattached
+    pub fn reattached(&self) -> bool {
+        self.reattached
+    }
+
+    /// Check whether the path now points to a different file than the one
+    /// the reader is attached to. If so, reattach and restart from the top.
+    pub fn detect_rotation(&mut self) -> bool {
+        let Some(identity) = self.identity else {
+            return false;
+        };
+        let Ok(metadata) = std::fs::metadata(&self.path) else {
+            // Mid-rotation; try again on the next poll
+            return false;
+        };
+        let current = file_identity(&metadata);
+        if current == Some(identity) {
+            return false;
+        }
+
+        tracing::info!(
+            "{} was rotated, reattaching to the new file",
+            self.path.display()
+        );
+        self.identity = current;
+        self.offset = 0;
+        self.line_count = 0;
+        self.partial_line = None;
+        true
+    }
+
+    /// Check whether the file shrank below the read offset (truncated in
+    /// place, e.g. copytruncate). If so, restart from the top.
+    pub fn detect_truncation(&mut self) -> bool {
+        let Ok(metadata) = std::fs::metadata(&self.path) else {
+            return false;
+        };
+        if metadata.len() >= self.offset {
+            return false;
+        }
+
+        tracing::info!(
+            "{} was truncated, reading from the top",
+            self.path.display()
+        );
+        self.offset = 0;
+        self.line_count = 0;
+        self.partial_line = None;
+        self.last_file_size = metadata.len();
+        true
+    }
+
     /// Check if there's new content available
     pub fn has_new_content(&self) -> Result<bool> {
         let metadata = std::fs::metadata(&self.path)?;
         Ok(metadata.len() > self.offset || metadata.len() < self.offset)
     }
 
+    /// Whether the file is compressed (read whole, not followed)
+    pub fn is_compressed(&self) -> bool {
+        self.compression.is_some()
+    }
+
+    /// Whether the file is memory-mapped (not followed)
+    pub fn is_mapped(&self) -> bool {
+        self.mapped
+    }
+
+    /// Whether the file can be memory-mapped: uncompressed, with an
+    /// ASCII-compatible encoding so lines split on `\n` bytes, and no longer
+    /// being written to
+    pub fn can_map(&self) -> bool {
+        !self.is_compressed() && self.line_ending() == LineEnding::Byte && !self.is_live()
+    }
+
+    /// Whether the file was modified within [`LIVE_FILE_WINDOW`]; unknown
+    /// modification times count as live
+    fn is_live(&self) -> bool {
+        let settled = std::fs::metadata(&self.path)
+            .and_then(|metadata| metadata.modified())
+            .is_ok_and(|modified| modified.elapsed().is_ok_and(|age| age >= LIVE_FILE_WINDOW));
+        !settled
+    }
+
+    /// Copy the file to a private snapshot, map it and index its lines,
+    /// keeping the last `max_lines`. UTF-8 lines point into the mapping
+    /// instead of being copied; lines in other encodings, invalid or
+    /// overlong ones are decoded as usual.
+    /// Returns (entries, total_lines_in_file).
+    ///
+    /// The log file itself is never mapped: another process may truncate or
+    /// rewrite it at any time, and a mapping faults once its file shrinks.
+    pub fn read_mapped(&mut self, max_lines: usize) -> Result<(Vec<LogEntry>, usize)> {
+        let mut file = File::open(&self.path).context("Failed to open log file")?;
+        let mut snapshot = tempfile::tempfile().context("Failed to create snapshot file")?;
+        std::io::copy(&mut file, &mut snapshot).context("Failed to snapshot log file")?;
+        // SAFETY: the snapshot is an unnamed temporary file created above and
+        // not written to again. Another process could still change it through
+        // the file system, which `LineText` survives except for truncation.
+        let map = Arc::new(unsafe { Mmap::map(&snapshot) }.context("Failed to map log file")?);
+
+        let newlines = map.iter().filter(|&&b| b == b'\n').count();
+        let total_lines = newlines + usize::from(!map.is_empty() && !map.ends_with(b"\n"));
+        let skip = total_lines.saturating_sub(max_lines);
+
+        let utf8 = self.encoding == encoding_rs::UTF_8;
+        let mut entries = Vec::with_capacity(total_lines - skip);
+        let mut start = 0;
+        for (i, line) in map.split_inclusive(|&b| b == b'\n').enumerate() {
+            let line_start = start;
+            start += line.len();
+            if i < skip {
+                continue;
+            }
+            let text = line.strip_suffix(b"\n").unwrap_or(line);
+            let text = text.strip_suffix(b"\r").unwrap_or(text);
+            // A leading BOM is left to the decoder, which drops it
+            let mapped = (utf8
+                && text.len() <= self.config.max_line_length
+                && !text.starts_with(b"\xEF\xBB\xBF"))
+            .then(|| LineText::mapped(map.clone(), line_start..line_start + text.len()))
+            .flatten();
+            let content = mapped.unwrap_or_else(|| {
+                LineText::Owned(self.truncate_line(self.decode_line(text)).into())
+            });
+            entries.push(LogEntry::new(i + 1, content, line_start as u64));
+        }
+
+        self.offset = map.len() as u64;
+        self.line_count = total_lines;
+        self.mapped = true;
+        Ok((entries, total_lines))
+    }
+
+    /// Decompress the whole file, keeping its last `max_lines` lines.
+    /// Offsets are positions in the decompressed stream.
+    /// Returns (entries, total_lines_in_file).
+    pub fn read_compressed(&mut self, max_lines: usize) -> Result<(Vec<LogEntry>, usize)> {
+        self.read_compressed_with_progress(max_lines, &AtomicU64::new(0))
+    }
+
+    /// [`Self::read_compressed`], adding the compressed bytes consumed to
+    /// `progress` as it goes
+    pub fn read_compressed_with_progress(
+        &mut self,
+        max_lines: usize,
+        progress: &AtomicU64,
+    ) -> Result<(Vec<LogEntry>, usize)> {
+        let compression = self.compression.context("Log file is not compressed")?;
+        let file = File::open(&self.path).context("Failed to open log file")?;
+        let input = CountingReader {
+            inner: file,
+            count: progress,
+        };
+        let mut reader =
+            BufReader::with_capacity(self.config.buffer_size, compression.decoder(input)?);
+        let line_ending = self.line_ending();
+
+        let mut lines = VecDeque::with_capacity(max_lines.min(10_000));
+        let mut line_buffer = Vec::new();
+        let (mut offset, mut total_lines) = (0u64, 0usize);
+        loop {
+            line_buffer.clear();
+            let bytes_read = line_ending
+                .read_line(&mut reader, offset, &mut line_buffer)
+                .context("Failed to decompress log file")?;
+            if bytes_read == 0 {
+                break;
+            }
+            total_lines += 1;
+            if lines.len() == max_lines {
+                lines.pop_front();
+            }
+            if max_lines > 0 {
+                lines.push_back((offset, line_buffer.clone()));
+            }
+            offset += bytes_read as u64;
+        }
+
+        let first_line = total_lines - lines.len() + 1;
+        let entries = lines
+            .into_iter()
+            .enumerate()
+            .map(|(i, (line_offset, bytes))| {
+                let content = self.truncate_line(self.decode_line(&bytes));
+                LogEntry::new(first_line + i, content, line_offset)
+            })
+            .collect();
+
+        self.offset = offset;
+        self.line_count = total_lines;
+        Ok((entries, total_lines))
+    }
+
     /// Get the detected/configured encoding name
     pub fn encoding_name(&self) -> &'static str {
         self.encoding.name()
     }
 
+    /// Get the detected/configured encoding
+    pub fn encoding(&self) -> &'static Encoding {
+        self.encoding
+    }
+
     /// Read a specific line range (1-indexed, inclusive)
     #[allow(dead_code)]
     pub fn read_line_range(&mut self, start: usize, end: usize) -> Result<Vec<LogEntry>> {
@@ -233,10 +822,11 @@ impl LogReader {
         let mut reader = BufReader::with_capacity(self.config.buffer_size, file);
         let mut entries = Vec::new();
         let mut line_buffer = Vec::new();
+        let line_ending = self.line_ending();
 
         while self.line_count < end {
             line_buffer.clear();
-            let bytes_read = reader.read_until(b'\n', &mut line_buffer)?;
+            let bytes_read = line_ending.read_line(&mut reader, self.offset, &mut line_buffer)?;
 
             if bytes_read == 0 {
                 break;
@@ -293,20 +883,7 @@ impl LogReader {
             reader.read_exact(&mut chunk)?;
 
             // Parse lines from this chunk (backwards)
-            let mut lines_in_chunk: Vec<(u64, Vec<u8>)> = Vec::new();
-            let mut line_end = chunk.len();
-
-            for (i, &byte) in chunk.iter().enumerate().rev() {
-                if byte == b'\n' || i == 0 {
-                    let line_start = if byte == b'\n' { i + 1 } else { i };
-                    if line_start < line_end {
-                        let line_bytes = chunk[line_start..line_end].to_vec();
-                        let byte_offset = chunk_start + line_start as u64;
-                        lines_in_chunk.push((byte_offset, line_bytes));
-                    }
-                    line_end = i;
-                }
-            }
+            let lines_in_chunk = self.line_ending().split_backwards(&chunk, chunk_start);
 
             // lines_in_chunk is in reverse order (last line first), which is what we want
             all_lines.extend(lines_in_chunk);
@@ -335,9 +912,11 @@ impl LogReader {
         let mut current_offset: u64 = 0;
         let mut line_buffer = Vec::new();
 
+        let line_ending = self.line_ending();
         loop {
             line_buffer.clear();
-            let bytes_read = count_reader.read_until(b'\n', &mut line_buffer)?;
+            let bytes_read =
+                line_ending.read_line(&mut count_reader, current_offset, &mut line_buffer)?;
             if bytes_read == 0 {
                 break;
             }
@@ -355,17 +934,7 @@ impl LogReader {
             .enumerate()
             .map(|(i, (byte_offset, line_bytes))| {
                 let line_number = lines_before_start + i + 1;
-                let content = self.decode_line(&line_bytes);
-                // Truncate if too long
-                let content = if content.len() > self.config.max_line_length {
-                    format!(
-                        "{}... [truncated, {} bytes total]",
-                        &content[..self.config.max_line_length],
-                        content.len()
-                    )
-                } else {
-                    content
-                };
+                let content = self.truncate_line(self.decode_line(&line_bytes));
                 LogEntry::new(line_number, content, byte_offset)
             })
             .collect();
@@ -409,20 +978,7 @@ impl LogReader {
             reader.read_exact(&mut chunk)?;
 
             // Parse lines from this chunk (backwards)
-            let mut lines_in_chunk: Vec<(u64, Vec<u8>)> = Vec::new();
-            let mut line_end = chunk.len();
-
-            for (i, &byte) in chunk.iter().enumerate().rev() {
-                if byte == b'\n' || i == 0 {
-                    let line_start = if byte == b'\n' { i + 1 } else { i };
-                    if line_start < line_end {
-                        let line_bytes = chunk[line_start..line_end].to_vec();
-                        let byte_offset = chunk_start + line_start as u64;
-                        lines_in_chunk.push((byte_offset, line_bytes));
-                    }
-                    line_end = i;
-                }
-            }
+            let lines_in_chunk = self.line_ending().split_backwards(&chunk, chunk_start);
 
             all_lines.extend(lines_in_chunk);
             search_start = chunk_start;
@@ -441,9 +997,11 @@ impl LogReader {
         let mut current_offset: u64 = 0;
         let mut line_buffer = Vec::new();
 
+        let line_ending = self.line_ending();
         while current_offset < new_start_offset {
             line_buffer.clear();
-            let bytes_read = count_reader.read_until(b'\n', &mut line_buffer)?;
+            let bytes_read =
+                line_ending.read_line(&mut count_reader, current_offset, &mut line_buffer)?;
             if bytes_read == 0 {
                 break;
             }
@@ -458,16 +1016,7 @@ impl LogReader {
             .enumerate()
             .map(|(i, (byte_offset, line_bytes))| {
                 let line_number = lines_before + i + 1;
-                let content = self.decode_line(&line_bytes);
-                let content = if content.len() > self.config.max_line_length {
-                    format!(
-                        "{}... [truncated, {} bytes total]",
-                        &content[..self.config.max_line_length],
-                        content.len()
-                    )
-                } else {
-                    content
-                };
+                let content = self.truncate_line(self.decode_line(&line_bytes));
                 LogEntry::new(line_number, content, byte_offset)
             })
             .collect();
@@ -498,23 +1047,35 @@ impl AsyncLogReader {
         let line_count = self.reader.line_count;
         let encoding = self.reader.encoding;
         let config = self.reader.config.clone();
-
-        let (entries, new_offset, new_line_count) = tokio::task::spawn_blocking(move || {
-            let mut reader = LogReader {
-                path,
-                offset,
-                line_count,
-                config,
-                encoding,
-                last_file_size: 0,
-            };
-            let entries = reader.read_new_lines()?;
-            Ok::<_, anyhow::Error>((entries, reader.offset, reader.line_count))
-        })
-        .await??;
+        let identity = self.reader.identity;
+        let partial_line = self.reader.partial_line;
+
+        let (entries, new_offset, new_line_count, partial_line) =
+            tokio::task::spawn_blocking(move || {
+                let mut reader = LogReader {
+                    path,
+                    offset,
+                    line_count,
+                    config,
+                    encoding,
+                    last_file_size: 0,
+                    identity,
+                    reattached: false,
+                    partial_line,
+                };
+                let entries = reader.read_new_lines()?;
+                Ok::<_, anyhow::Error>((
+                    entries,
+                    reader.offset,
+                    reader.line_count,
+                    reader.partial_line,
+                ))
+            })
+            .await??;
 
         self.reader.offset = new_offset;
         self.reader.line_count = new_line_count;
+        self.reader.partial_line = partial_line;
 
         Ok(entries)
     }
@@ -569,4 +1130,264 @@ mod tests {
         assert_eq!(entries[0].content, "Line 2");
         assert_eq!(entries[0].line_number, 2);
     }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_rotation_racing_open_attaches_to_live_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        std::fs::write(&path, "old 1\nold 2\n").unwrap();
+
+        // Rotate the file right after the first open, before the re-check
+        let rotated = dir.path().join("app.log.1");
+        let mut reader = LogReader::open_attached(&path, LogReaderConfig::default(), |attempt| {
+            if attempt == 1 {
+                std::fs::rename(&path, &rotated).unwrap();
+                std::fs::write(&path, "live 1\n").unwrap();
+            }
+        })
+        .unwrap();
+
+        let live = std::fs::metadata(&path).unwrap();
+        assert!(reader.reattached());
+        assert_eq!(reader.identity(), file_identity(&live));
+        assert_ne!(
+            reader.identity(),
+            file_identity(&std::fs::metadata(&rotated).unwrap())
+        );
+        assert_eq!(reader.file_size(), live.len());
+
+        let entries = reader.read_new_lines().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].content, "live 1");
+        assert!(!reader.detect_rotation());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_detect_rotation_after_open() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        std::fs::write(&path, "old 1\n").unwrap();
+
+        let mut reader = LogReader::new(&path).unwrap();
+        assert!(!reader.reattached());
+        assert_eq!(reader.read_new_lines().unwrap().len(), 1);
+
+        std::fs::rename(&path, dir.path().join("app.log.1")).unwrap();
+        std::fs::write(&path, "new 1\nnew 2\n").unwrap();
+
+        assert!(reader.detect_rotation());
+        let entries = reader.read_new_lines().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].content, "new 1");
+        assert_eq!(entries[0].line_number, 1);
+    }
+
+    #[test]
+    fn test_detect_truncation_restarts_from_top() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        std::fs::write(&path, "old 1\nold 2\nold 3\n").unwrap();
+
+        let mut reader = LogReader::new(&path).unwrap();
+        assert_eq!(reader.read_new_lines().unwrap().len(), 3);
+        assert!(!reader.detect_truncation());
+
+        // Truncated in place (copytruncate), then written again
+        std::fs::write(&path, "new 1\n").unwrap();
+        assert!(reader.detect_truncation());
+        assert_eq!(reader.offset(), 0);
+        let entries = reader.read_new_lines().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].content, "new 1");
+        assert_eq!(entries[0].line_number, 1);
+        assert!(!reader.detect_truncation());
+    }
+
+    #[test]
+    fn test_partial_line_is_held_until_complete() {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(file, "INFO first\nWARN half wr").unwrap();
+        file.flush().unwrap();
+
+        let mut reader = LogReader::new(file.path()).unwrap();
+        let entries = reader.read_new_lines().unwrap();
+        let contents: Vec<_> = entries.iter().map(|e| e.content.as_str()).collect();
+        assert_eq!(contents, ["INFO first"]);
+        assert!(reader.read_new_lines().unwrap().is_empty());
+
+        // The rest of the line arrives: it shows up once, complete
+        writeln!(file, "itten").unwrap();
+        file.flush().unwrap();
+        let entries = reader.read_new_lines().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].content, "WARN half written");
+        assert_eq!(entries[0].line_number, 2);
+
+        // Without a hold-back timeout partial lines show immediately
+        let config = LogReaderConfig {
+            partial_line_timeout: Duration::ZERO,
+            ..Default::default()
+        };
+        let mut reader = LogReader::with_config(file.path(), config).unwrap();
+        reader.seek_with_line_count(reader.file_size(), 2);
+        write!(file, "ERROR cut").unwrap();
+        file.flush().unwrap();
+        let entries = reader.read_new_lines().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].content, "ERROR cut");
+    }
+
+    fn utf16_bytes(text: &str, little_endian: bool, bom: bool) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let units = bom.then_some(0xFEFF).into_iter().chain(text.encode_utf16());
+        for unit in units {
+            if little_endian {
+                bytes.extend_from_slice(&unit.to_le_bytes());
+            } else {
+                bytes.extend_from_slice(&unit.to_be_bytes());
+            }
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_utf16_files_are_detected_and_split() {
+        // U+010A and U+0A05 contain a 0x0A byte that is not a newline
+        let text = "INFO one\r\nWARN \u{fc} \u{10a}\u{a05} two\nERROR three\n";
+        let expected = ["INFO one", "WARN \u{fc} \u{10a}\u{a05} two", "ERROR three"];
+
+        for (little_endian, bom) in [(true, true), (true, false), (false, true), (false, false)] {
+            let mut file = NamedTempFile::new().unwrap();
+            file.write_all(&utf16_bytes(text, little_endian, bom))
+                .unwrap();
+            file.flush().unwrap();
+
+            let mut reader = LogReader::new(file.path()).unwrap();
+            let encoding = if little_endian {
+                "UTF-16LE"
+            } else {
+                "UTF-16BE"
+            };
+            assert_eq!(
+                reader.encoding_name(),
+                encoding,
+                "le={little_endian} bom={bom}"
+            );
+
+            let entries = reader.read_new_lines().unwrap();
+            let contents: Vec<_> = entries.iter().map(|e| e.content.as_str()).collect();
+            assert_eq!(contents, expected, "le={little_endian} bom={bom}");
+
+            let (tail, _, total) = reader.read_tail(2).unwrap();
+            assert_eq!(total, 3);
+            let contents: Vec<_> = tail.iter().map(|e| e.content.as_str()).collect();
+            assert_eq!(contents, expected[1..]);
+            assert_eq!(tail[0].line_number, 2);
+        }
+    }
+
+    #[test]
+    fn test_gzip_files_are_decompressed_up_to_max_lines() {
+        use flate2::write::GzEncoder;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+        for i in 1..=5 {
+            writeln!(encoder, "rotated line {}", i).unwrap();
+        }
+        let compressed = encoder.finish().unwrap();
+
+        let gz_path = dir.path().join("app.log.1.gz");
+        std::fs::write(&gz_path, &compressed).unwrap();
+        // Detected by magic bytes even without the extension
+        let bare_path = dir.path().join("app.log.1");
+        std::fs::write(&bare_path, &compressed).unwrap();
+        let plain_path = dir.path().join("app.log");
+        std::fs::write(&plain_path, "plain\n").unwrap();
+
+        assert_eq!(Compression::detect(&gz_path), Some(Compression::Gzip));
+        assert_eq!(Compression::detect(&bare_path), Some(Compression::Gzip));
+        assert_eq!(Compression::detect(&plain_path), None);
+        assert!(!LogReader::new(&plain_path).unwrap().is_compressed());
+
+        let mut reader = LogReader::new(&bare_path).unwrap();
+        assert!(reader.is_compressed());
+        let (entries, total_lines) = reader.read_compressed(3).unwrap();
+        assert_eq!(total_lines, 5);
+        let contents: Vec<&str> = entries.iter().map(|e| e.content.as_str()).collect();
+        assert_eq!(
+            contents,
+            ["rotated line 3", "rotated line 4", "rotated line 5"]
+        );
+        assert_eq!(entries[0].line_number, 3);
+        assert_eq!(reader.line_count(), 5);
+    }
+
+    #[test]
+    fn test_mapped_lines_point_into_the_file() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"INFO first\r\nWARN bad \xff byte\nERROR last")
+            .unwrap();
+        file.flush().unwrap();
+        let config = LogReaderConfig {
+            encoding: Some(encoding_rs::UTF_8),
+            ..Default::default()
+        };
+
+        // Files still being written to are followed, not mapped
+        let reader = LogReader::with_config(file.path(), config.clone()).unwrap();
+        assert!(!reader.can_map());
+        let written = std::time::SystemTime::now() - LIVE_FILE_WINDOW * 2;
+        file.as_file().set_modified(written).unwrap();
+
+        let mut reader = LogReader::with_config(file.path(), config).unwrap();
+        assert!(reader.can_map());
+        let (entries, total_lines) = reader.read_mapped(2).unwrap();
+        assert!(reader.is_mapped());
+        assert_eq!(total_lines, 3);
+        assert_eq!(entries.len(), 2);
+
+        // Invalid UTF-8 is decoded into an owned line
+        assert_eq!(entries[0].line_number, 2);
+        assert_eq!(entries[0].content, "WARN bad ? byte");
+        assert!(entries[0].content.heap_size() > 0);
+        assert_eq!(entries[0].byte_offset, 12);
+
+        // Valid lines borrow from the mapping, without their terminator
+        assert_eq!(entries[1].content, "ERROR last");
+        assert_eq!(entries[1].content.heap_size(), 0);
+        assert_eq!(entries[1].level, Some(crate::log_entry::LogLevel::Error));
+        assert_eq!(reader.line_count(), 3);
+
+        // They come from a snapshot, which outlives truncating the file
+        file.as_file().set_len(0).unwrap();
+        assert_eq!(entries[1].content, "ERROR last");
+    }
+
+    #[test]
+    fn test_zstd_files_are_decompressed_with_progress() {
+        let dir = tempfile::tempdir().unwrap();
+        let text: String = (1..=4).map(|i| format!("archived line {}\n", i)).collect();
+        let compressed = zstd::encode_all(text.as_bytes(), 0).unwrap();
+
+        let zst_path = dir.path().join("app.log.zst");
+        std::fs::write(&zst_path, &compressed).unwrap();
+        // Detected by magic bytes even without the extension
+        let bare_path = dir.path().join("app.log.2");
+        std::fs::write(&bare_path, &compressed).unwrap();
+
+        assert_eq!(Compression::detect(&zst_path), Some(Compression::Zstd));
+        assert_eq!(Compression::detect(&bare_path), Some(Compression::Zstd));
+
+        let mut reader = LogReader::new(&bare_path).unwrap();
+        assert!(reader.is_compressed());
+        let progress = AtomicU64::new(0);
+        let (entries, total_lines) = reader.read_compressed_with_progress(10, &progress).unwrap();
+        assert_eq!(total_lines, 4);
+        assert_eq!(entries[3].content, "archived line 4");
+        assert_eq!(entries[1].byte_offset, "archived line 1\n".len() as u64);
+        assert_eq!(progress.load(Ordering::Relaxed), compressed.len() as u64);
+    }
 }
@@ -6,19 +6,30 @@
 //! designed for efficient real-time log monitoring and analysis.
 
 mod android_logcat;
+mod ansi;
 mod app;
+mod autosave;
+mod bookmark_report;
 mod bookmarks;
 mod config;
+mod correlation;
+mod file_stats;
 mod file_watcher;
 mod grok_parser;
 mod highlighter;
 mod i18n;
+mod line_export;
 mod log_buffer;
+mod log_diff;
 mod log_entry;
+mod log_merge;
 mod log_reader;
+mod match_export;
 mod protocol;
+mod redactor;
 mod remote_server;
 mod search;
+mod stdin_reader;
 mod tray;
 mod ui;
 mod virtual_scroll;
@@ -34,6 +45,9 @@ fn main() -> eframe::Result<()> {
 
     tracing::info!("Starting Logline");
 
+    // `mycmd | logline -` streams stdin into a tab
+    let read_stdin = stdin_reader::stdin_requested(std::env::args());
+
     // Load icon for window
     let icon_bytes = include_bytes!("../res/icon.png");
     let icon = match image::load_from_memory(icon_bytes) {
@@ -96,7 +110,13 @@ fn main() -> eframe::Result<()> {
             // Install image loaders for egui (required for egui-desktop SVG assets)
             egui_extras::install_image_loaders(&cc.egui_ctx);
 
-            Ok(Box::new(LoglineApp::new(cc)))
+            let mut app = LoglineApp::new(cc);
+            if read_stdin {
+                if let Err(e) = app.open_stdin() {
+                    tracing::error!("Failed to read stdin: {:#}", e);
+                }
+            }
+            Ok(Box::new(app))
         }),
     )
 }
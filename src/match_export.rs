@@ -0,0 +1,161 @@
+//! Export of search matches with surrounding context
+//!
+//! Writes each match plus a few lines of context, like `grep -C`.
+//! Non-contiguous groups are separated, and lines are streamed to the
+//! writer one by one so large result sets are never built up in memory.
+
+use crate::log_buffer::LogBuffer;
+use crate::redactor::Redactor;
+use crate::search::expand_with_context;
+use std::collections::HashSet;
+use std::io::{self, Write};
+
+/// Output format of a match export
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchExportFormat {
+    /// `grep -C` style text (`N:` for matches, `N-` for context, `--` between groups)
+    Text,
+    /// JSON array of groups, each holding its lines
+    Json,
+}
+
+impl MatchExportFormat {
+    /// File extension for the format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            MatchExportFormat::Text => "txt",
+            MatchExportFormat::Json => "json",
+        }
+    }
+
+    /// Filter name for save dialogs
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            MatchExportFormat::Text => "Text",
+            MatchExportFormat::Json => "JSON",
+        }
+    }
+}
+
+/// Write the matching lines with `context` lines before and after each match.
+/// `matches` are sorted buffer indices; every line goes through the redactor.
+/// Returns the number of groups written.
+pub fn write_matches<W: Write>(
+    mut out: W,
+    buffer: &LogBuffer,
+    matches: &[usize],
+    context: usize,
+    format: MatchExportFormat,
+    redactor: &Redactor,
+) -> io::Result<usize> {
+    let matched: HashSet<usize> = matches.iter().copied().collect();
+    let mut groups = 0;
+    let mut previous: Option<usize> = None;
+
+    if format == MatchExportFormat::Json {
+        write!(out, "[")?;
+    }
+
+    for index in expand_with_context(matches, context, buffer.len()) {
+        let Some(entry) = buffer.get(index) else {
+            continue;
+        };
+        let new_group = previous.is_none_or(|p| index != p + 1);
+        previous = Some(index);
+        let is_match = matched.contains(&index);
+        let content = redactor.redact(&entry.content);
+
+        match format {
+            MatchExportFormat::Text => {
+                if new_group && groups > 0 {
+                    writeln!(out, "--")?;
+                }
+                let separator = if is_match { ':' } else { '-' };
+                writeln!(out, "{}{}{}", entry.line_number, separator, content)?;
+            }
+            MatchExportFormat::Json => {
+                if new_group {
+                    if groups > 0 {
+                        write!(out, "\n  ]}},")?;
+                    }
+                    write!(out, "\n  {{\"lines\": [")?;
+                } else {
+                    write!(out, ",")?;
+                }
+                let line = serde_json::json!({
+                    "line": entry.line_number,
+                    "match": is_match,
+                    "content": content,
+                });
+                write!(out, "\n    {}", line)?;
+            }
+        }
+
+        if new_group {
+            groups += 1;
+        }
+    }
+
+    if format == MatchExportFormat::Json {
+        if groups > 0 {
+            write!(out, "\n  ]}}")?;
+        }
+        writeln!(out, "\n]")?;
+    }
+    out.flush()?;
+    Ok(groups)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log_entry::LogEntry;
+
+    fn buffer(count: usize) -> LogBuffer {
+        let mut buffer = LogBuffer::new();
+        for n in 1..=count {
+            buffer.push(LogEntry::new(n, format!("line {}", n), 0));
+        }
+        buffer
+    }
+
+    #[test]
+    fn test_matches_are_exported_with_context_groups() {
+        let buffer = buffer(10);
+        let matches = [2, 3, 8];
+
+        let mut text = Vec::new();
+        let groups = write_matches(
+            &mut text,
+            &buffer,
+            &matches,
+            1,
+            MatchExportFormat::Text,
+            &Redactor::default(),
+        )
+        .unwrap();
+        assert_eq!(groups, 2);
+        assert_eq!(
+            String::from_utf8(text).unwrap(),
+            "2-line 2\n3:line 3\n4:line 4\n5-line 5\n--\n8-line 8\n9:line 9\n10-line 10\n"
+        );
+
+        let mut json = Vec::new();
+        write_matches(
+            &mut json,
+            &buffer,
+            &matches,
+            0,
+            MatchExportFormat::Json,
+            &Redactor::default(),
+        )
+        .unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        let groups = value.as_array().unwrap();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0]["lines"].as_array().unwrap().len(), 2);
+        assert_eq!(groups[1]["lines"][0]["line"], 9);
+        assert_eq!(groups[1]["lines"][0]["match"], true);
+        assert_eq!(groups[1]["lines"][0]["content"], "line 9");
+    }
+}
@@ -9,4 +9,5 @@ mod tools;
 mod types;
 
 pub use server::McpServer;
+pub use tools::{local_source_id, remote_source_id};
 pub use types::*;
@@ -10,10 +10,20 @@
 //! - list_bookmarks: List all bookmarked lines
 //! - manage_bookmarks: Add/remove bookmarks
 //! - analyze_timeline: Analyze log frequency over time
-
+//! - tail_log_source: Poll a source for lines appended since a cursor
+//! - diff_log_sources: Compare the line patterns of two sources
+//! - summarize_log_source: Digest of statistics, top errors and busiest periods
+//! - set_source_context / get_source_context / clear_source_context:
+//!   Manage a per-source grok pattern + standing filter used by
+//!   get_log_entries and search_logs
+
+use crate::bookmarks::{bookmark_color_name, BookmarksStore};
+use crate::grok_parser::{BuiltinPattern, GrokParser};
 use crate::log_entry::{LogEntry, LogLevel};
+use crate::log_reader::LogReader;
 use crate::mcp::types::*;
 use crate::remote_server::{ConnectionStatus, RemoteStream};
+use crate::search::FilterLogic;
 
 use chrono::{DateTime, Local, TimeZone};
 use regex::Regex;
@@ -24,8 +34,9 @@ use rmcp::{tool, tool_handler, tool_router, ServerHandler};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
-use std::sync::{Arc, RwLock};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, RwLock};
+use std::time::Duration;
 
 // ============================================================================
 // Tool Parameter Structures
@@ -132,8 +143,11 @@ fn default_include_context() -> bool {
 pub struct AdvancedFilterParams {
     /// Log source ID
     pub source_id: String,
-    /// Filter conditions (all must match - AND logic)
+    /// Filter conditions, combined according to `logic`
     pub conditions: Vec<FilterConditionParam>,
+    /// How conditions combine: "and" (all must match, default) or "or" (any may match)
+    #[serde(default)]
+    pub logic: Option<String>,
     /// Maximum number of results (default: 100)
     #[serde(default = "default_count")]
     pub max_results: usize,
@@ -207,6 +221,96 @@ fn default_bucket_size() -> u64 {
     60
 }
 
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SetSourceContextParams {
+    /// Log source ID
+    pub source_id: String,
+    /// Grok pattern: a builtin name (e.g. "Log4j", "Syslog") or a custom grok pattern
+    #[serde(default)]
+    pub grok_pattern: Option<String>,
+    /// Standing level filter (e.g., ["ERROR", "WARN"]). Empty means all levels.
+    #[serde(default)]
+    pub levels: Vec<String>,
+    /// Standing content filter (keyword or regex)
+    #[serde(default)]
+    pub query: Option<String>,
+    /// Whether the query is a regex pattern
+    #[serde(default)]
+    pub is_regex: bool,
+    /// Case sensitive query matching
+    #[serde(default)]
+    pub case_sensitive: bool,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SourceContextParams {
+    /// Log source ID
+    pub source_id: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SummarizeLogSourceParams {
+    /// Log source ID
+    pub source_id: String,
+    /// Maximum number of error groups to return (default: 5)
+    #[serde(default = "default_summary_limit")]
+    pub max_error_groups: usize,
+    /// Maximum number of busiest timeline buckets to return (default: 5)
+    #[serde(default = "default_summary_limit")]
+    pub max_timeline_buckets: usize,
+    /// Time bucket size in seconds (default: 60)
+    #[serde(default = "default_bucket_size")]
+    pub bucket_size_seconds: u64,
+}
+
+fn default_summary_limit() -> usize {
+    5
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct DiffLogSourcesParams {
+    /// Baseline log source ID (e.g. a known-good run)
+    pub base_source_id: String,
+    /// Log source ID to compare against the baseline (e.g. a failing run)
+    pub other_source_id: String,
+    /// Maximum number of groups to return per side
+    #[serde(default = "default_max_groups")]
+    pub max_groups: usize,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct TailLogSourceParams {
+    /// Log source ID
+    pub source_id: String,
+    /// Number of newest lines to return (default: 100, max: 1000)
+    #[serde(default = "default_count")]
+    pub count: usize,
+    /// Cursor from a previous call; omit to start from the end of the source
+    #[serde(default)]
+    pub cursor: Option<TailCursor>,
+    /// Log levels to include (e.g., ["ERROR", "WARN"]). Empty means all levels.
+    #[serde(default)]
+    pub levels: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct GetEntryContextParams {
+    /// Log source ID
+    pub source_id: String,
+    /// Line number of the entry (1-indexed)
+    pub line_number: usize,
+    /// Lines to return before the entry (default: 10, max: 500)
+    #[serde(default = "default_context_count")]
+    pub before: usize,
+    /// Lines to return after the entry (default: 10, max: 500)
+    #[serde(default = "default_context_count")]
+    pub after: usize,
+}
+
+fn default_context_count() -> usize {
+    10
+}
+
 // ============================================================================
 // Tool Response Structures
 // ============================================================================
@@ -233,6 +337,8 @@ pub struct GetLogEntriesResponse {
     pub has_more: bool,
     /// Next line number for pagination
     pub next_line: usize,
+    /// Whether the source context was applied
+    pub context_applied: bool,
 }
 
 /// Response for search_logs tool
@@ -246,6 +352,8 @@ pub struct SearchLogsResponse {
     pub query: String,
     /// Whether regex was used
     pub is_regex: bool,
+    /// Whether the source context was applied
+    pub context_applied: bool,
 }
 
 /// Response for get_log_statistics tool
@@ -308,6 +416,187 @@ pub struct AnalyzeTimelineResponse {
     pub summary: String,
 }
 
+/// Response for set/get/clear_source_context tools
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct SourceContextResponse {
+    /// Log source ID
+    pub source_id: String,
+    /// Current context (absent when none is set)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<SourceContext>,
+    /// Message describing what happened
+    pub message: String,
+}
+
+/// Response for summarize_log_source tool
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct SummarizeLogSourceResponse {
+    /// Statistics summary
+    pub statistics: LogStatistics,
+    /// Most frequent error patterns
+    pub top_error_groups: Vec<ErrorGroup>,
+    /// Busiest time buckets, most entries first
+    pub timeline_peaks: Vec<FrequencyDataPoint>,
+    /// Bucket size used for the timeline
+    pub bucket_size_seconds: u64,
+    /// Human-readable digest
+    pub summary: String,
+}
+
+/// Response for diff_log_sources tool
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct DiffLogSourcesResponse {
+    /// Patterns only found in the other source
+    pub added: Vec<DiffGroup>,
+    /// Patterns only found in the base source
+    pub removed: Vec<DiffGroup>,
+    /// Lines of the other source with a pattern missing from the base
+    pub total_added_lines: usize,
+    /// Lines of the base source with a pattern missing from the other
+    pub total_removed_lines: usize,
+    /// Number of patterns found in both sources
+    pub common_patterns: usize,
+}
+
+/// Response for tail_log_source tool
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct TailLogSourceResponse {
+    /// Newest matching entries since the cursor
+    pub entries: Vec<LogEntryResult>,
+    /// Cursor to pass to the next call
+    pub cursor: TailCursor,
+    /// Whether lines were appended since the given cursor
+    pub has_new_data: bool,
+    /// Total lines in the source
+    pub current_total_lines: usize,
+    /// Whether the source shrank (rotated or truncated) and was read from the top
+    pub restarted: bool,
+    /// Whether the source context was applied
+    pub context_applied: bool,
+}
+
+/// Response for get_entry_context tool
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct GetEntryContextResponse {
+    /// The entry and its neighbors, in file order
+    pub entries: Vec<LogEntryResult>,
+    /// First line returned (clamped to the start of the source)
+    pub start_line: usize,
+    /// Last line returned (clamped to the end of the source)
+    pub end_line: usize,
+    /// Total lines in the source
+    pub total_lines: usize,
+    /// Whether the source context's grok pattern was applied
+    pub context_applied: bool,
+}
+
+// ============================================================================
+// Source Context
+// ============================================================================
+
+/// Compiled form of a `SourceContext`, applied to each line
+struct ContextMatcher {
+    /// Grok parser with the context's pattern active
+    grok: Option<GrokParser>,
+    /// Levels to include (empty means all)
+    levels: Vec<LogLevel>,
+    /// Content filter
+    query: Option<Regex>,
+}
+
+impl ContextMatcher {
+    /// Compile a context, validating its grok pattern and query
+    fn compile(context: &SourceContext) -> Result<Self, String> {
+        let grok = match context.grok_pattern.as_deref().map(str::trim) {
+            Some(pattern) if !pattern.is_empty() => {
+                let mut parser = GrokParser::new();
+                let builtin = BuiltinPattern::all().iter().find(|p| {
+                    p.display_name().eq_ignore_ascii_case(pattern)
+                        || format!("{:?}", p).eq_ignore_ascii_case(pattern)
+                });
+                let result = match builtin {
+                    Some(builtin) => parser.set_builtin_pattern(*builtin),
+                    None => parser.set_custom_pattern("mcp-context", pattern),
+                };
+                result.map_err(|e| format!("Invalid grok pattern: {}", e))?;
+                Some(parser)
+            }
+            _ => None,
+        };
+
+        let mut levels = Vec::new();
+        for level in &context.levels {
+            levels.push(
+                LogLevel::from_str(level).ok_or_else(|| format!("Unknown log level: {}", level))?,
+            );
+        }
+
+        let query = match context.query.as_deref() {
+            Some(query) if !query.is_empty() => {
+                let pattern = if context.is_regex {
+                    query.to_string()
+                } else {
+                    regex::escape(query)
+                };
+                let pattern = if context.case_sensitive {
+                    pattern
+                } else {
+                    format!("(?i){}", pattern)
+                };
+                Some(Regex::new(&pattern).map_err(|e| format!("Invalid regex: {}", e))?)
+            }
+            _ => None,
+        };
+
+        Ok(Self {
+            grok,
+            levels,
+            query,
+        })
+    }
+
+    /// Build an entry for a line, or None if the context filters it out
+    fn apply(&self, line_number: usize, line: &str) -> Option<LogEntry> {
+        if let Some(ref query) = self.query {
+            if !query.is_match(line) {
+                return None;
+            }
+        }
+
+        let entry = self.parse(line_number, line);
+        if !self.levels.is_empty() && !entry.level.is_some_and(|l| self.levels.contains(&l)) {
+            return None;
+        }
+
+        Some(entry)
+    }
+
+    /// Build an entry for a line with its grok fields, without filtering
+    fn parse(&self, line_number: usize, line: &str) -> LogEntry {
+        let mut entry = LogEntry::new(line_number, line.to_string(), 0);
+        if let Some(ref grok) = self.grok {
+            if let Some((parsed, _)) = grok.parse_with_format(line) {
+                entry.set_grok_fields(parsed.fields);
+            }
+        }
+        entry
+    }
+}
+
+// ============================================================================
+// Source IDs
+// ============================================================================
+
+/// MCP source ID of a local file
+pub fn local_source_id(path: &Path) -> String {
+    format!("local:{}", path.display())
+}
+
+/// MCP source ID of a remote stream
+pub fn remote_source_id(stream: &RemoteStream) -> String {
+    stream.stream_id.clone()
+}
+
 // ============================================================================
 // Shared State for Tools
 // ============================================================================
@@ -323,6 +612,8 @@ pub struct LoglineToolState {
     pub cache_dir: PathBuf,
     /// Bookmarks storage: source_id -> line_numbers with optional notes
     bookmarks: Arc<RwLock<HashMap<String, HashMap<usize, Option<String>>>>>,
+    /// Analysis contexts: source_id -> context (in-memory, not persisted)
+    source_contexts: Arc<RwLock<HashMap<String, SourceContext>>>,
 }
 
 impl LoglineToolState {
@@ -332,6 +623,7 @@ impl LoglineToolState {
             local_files: Arc::new(RwLock::new(Vec::new())),
             cache_dir,
             bookmarks: Arc::new(RwLock::new(HashMap::new())),
+            source_contexts: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
@@ -364,7 +656,7 @@ impl LoglineToolState {
                     .unwrap_or_else(|| path.to_string_lossy().to_string());
 
                 sources.push(LogSource {
-                    id: format!("local:{}", path.display()),
+                    id: local_source_id(path),
                     name,
                     source_type: LogSourceType::Local,
                     status: None,
@@ -391,7 +683,7 @@ impl LoglineToolState {
                 }
 
                 sources.push(LogSource {
-                    id: stream.stream_id.clone(),
+                    id: remote_source_id(stream),
                     name: stream.project_name.clone(),
                     source_type: LogSourceType::Remote,
                     status: Some(stream_status.to_string()),
@@ -431,6 +723,7 @@ impl LoglineToolState {
         count: usize,
         levels: &[String],
         from_end: bool,
+        context: Option<&ContextMatcher>,
     ) -> Result<(Vec<LogEntryResult>, usize, bool), String> {
         let content =
             fs::read_to_string(path).map_err(|e| format!("Failed to read file: {}", e))?;
@@ -450,7 +743,10 @@ impl LoglineToolState {
                 .iter()
                 .enumerate()
                 .filter_map(|(i, line)| {
-                    let entry = LogEntry::new(start + i + 1, line.to_string(), 0);
+                    let entry = match context {
+                        Some(context) => context.apply(start + i + 1, line)?,
+                        None => LogEntry::new(start + i + 1, line.to_string(), 0),
+                    };
                     if !level_filter.is_empty() {
                         if let Some(level) = entry.level {
                             if !level_filter.contains(&level) {
@@ -471,7 +767,10 @@ impl LoglineToolState {
                 .iter()
                 .enumerate()
                 .filter_map(|(i, line)| {
-                    let entry = LogEntry::new(start + i + 1, line.to_string(), 0);
+                    let entry = match context {
+                        Some(context) => context.apply(start + i + 1, line)?,
+                        None => LogEntry::new(start + i + 1, line.to_string(), 0),
+                    };
                     if !level_filter.is_empty() {
                         if let Some(level) = entry.level {
                             if !level_filter.contains(&level) {
@@ -496,7 +795,118 @@ impl LoglineToolState {
         Ok((entries, total_lines, has_more))
     }
 
+    /// Read a line and up to `before`/`after` lines around it, clamped to
+    /// the file. Returns (entries, first line, last line, total lines).
+    /// The source context's grok pattern applies but its filters don't, so
+    /// the neighbors are always the lines next to the entry.
+    fn read_entry_context(
+        &self,
+        path: &Path,
+        line_number: usize,
+        before: usize,
+        after: usize,
+        context: Option<&ContextMatcher>,
+    ) -> Result<(Vec<LogEntryResult>, usize, usize, usize), String> {
+        let content =
+            fs::read_to_string(path).map_err(|e| format!("Failed to read file: {}", e))?;
+        let lines: Vec<&str> = content.lines().collect();
+        let total_lines = lines.len();
+        if line_number == 0 || line_number > total_lines {
+            return Err(format!(
+                "Line {} is out of range (1-{})",
+                line_number, total_lines
+            ));
+        }
+
+        let start_line = line_number.saturating_sub(before).max(1);
+        let end_line = line_number.saturating_add(after).min(total_lines);
+        let entries = (start_line..=end_line)
+            .map(|number| {
+                let line = lines[number - 1];
+                let entry = match context {
+                    Some(context) => context.parse(number, line),
+                    None => LogEntry::new(number, line.to_string(), 0),
+                };
+                entry_to_result(&entry)
+            })
+            .collect();
+        Ok((entries, start_line, end_line, total_lines))
+    }
+
+    /// Read the lines appended after a cursor, or the last `count` lines
+    /// without one. Returns (entries, next cursor, has new data, restarted).
+    /// A last line that is still being written is left for the next call.
+    fn tail_log_source(
+        &self,
+        path: &Path,
+        count: usize,
+        cursor: Option<TailCursor>,
+        levels: &[String],
+        context: Option<&ContextMatcher>,
+    ) -> Result<(Vec<LogEntryResult>, TailCursor, bool, bool), String> {
+        let mut reader =
+            LogReader::new(path).map_err(|e| format!("Failed to open file: {}", e))?;
+
+        let (lines, restarted) = match cursor {
+            Some(cursor) => {
+                let size = fs::metadata(path)
+                    .map_err(|e| format!("Failed to read file: {}", e))?
+                    .len();
+                reader.seek_with_line_count(cursor.byte_offset, cursor.line_count);
+                // A fresh reader would hold a partial last line back forever
+                if let Some(since) = cursor.partial_since_ms {
+                    let held = (Local::now().timestamp_millis() - since).max(0);
+                    reader.restore_partial_line(Duration::from_millis(held as u64));
+                }
+                let lines = reader
+                    .read_new_lines()
+                    .map_err(|e| format!("Failed to read file: {}", e))?;
+                (lines, size < cursor.byte_offset)
+            }
+            None => {
+                let (lines, _, _) = reader
+                    .read_tail(count)
+                    .map_err(|e| format!("Failed to read file: {}", e))?;
+                (lines, false)
+            }
+        };
+
+        let level_filter: Vec<LogLevel> = levels
+            .iter()
+            .filter_map(|l| LogLevel::from_str(l))
+            .collect();
+
+        let has_new_data = !lines.is_empty();
+        let mut entries: Vec<LogEntryResult> = lines
+            .into_iter()
+            .filter_map(|line| {
+                let entry = match context {
+                    Some(context) => context.apply(line.line_number, &line.content)?,
+                    None => line,
+                };
+                if !level_filter.is_empty()
+                    && !entry.level.is_some_and(|level| level_filter.contains(&level))
+                {
+                    return None;
+                }
+                Some(entry_to_result(&entry))
+            })
+            .collect();
+        // Keep the newest lines when more were appended than requested
+        entries.drain(..entries.len().saturating_sub(count));
+
+        let next = TailCursor {
+            byte_offset: reader.offset(),
+            line_count: reader.line_count(),
+            partial_since_ms: reader
+                .partial_line_held()
+                .map(|held| Local::now().timestamp_millis() - held.as_millis() as i64),
+        };
+        Ok((entries, next, has_new_data, restarted))
+    }
+
     /// Search logs
+    #[allow(clippy::too_many_arguments)]
     fn search_logs(
         &self,
         path: &PathBuf,
@@ -506,6 +916,7 @@ impl LoglineToolState {
         levels: &[String],
         context_lines: usize,
         max_results: usize,
+        context: Option<&ContextMatcher>,
     ) -> Result<Vec<SearchMatch>, String> {
         let content =
             fs::read_to_string(path).map_err(|e| format!("Failed to read file: {}", e))?;
@@ -542,7 +953,15 @@ impl LoglineToolState {
                 continue;
             }
 
-            let entry = LogEntry::new(i + 1, line.to_string(), 0);
+            let entry = match context {
+                Some(context) => {
+                    let Some(entry) = context.apply(i + 1, line) else {
+                        continue;
+                    };
+                    entry
+                }
+                None => LogEntry::new(i + 1, line.to_string(), 0),
+            };
 
             // Level filter
             if !level_filter.is_empty() {
@@ -697,11 +1116,76 @@ impl LoglineToolState {
         Ok(groups)
     }
 
+    /// Group the non-empty lines of a file by simplified pattern.
+    /// Returns pattern -> (line count, first few entries).
+    fn pattern_groups(
+        &self,
+        path: &PathBuf,
+    ) -> Result<HashMap<String, (usize, Vec<LogEntry>)>, String> {
+        // Lossy, so a stray non-UTF-8 byte doesn't fail the whole diff
+        let bytes = fs::read(path).map_err(|e| format!("Failed to read file: {}", e))?;
+        let content = String::from_utf8_lossy(&bytes);
+
+        let mut groups: HashMap<String, (usize, Vec<LogEntry>)> = HashMap::new();
+        for (i, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (count, samples) = groups.entry(simplify_error_pattern(line)).or_default();
+            *count += 1;
+            if samples.len() < 3 {
+                samples.push(LogEntry::new(i + 1, line.to_string(), 0));
+            }
+        }
+
+        Ok(groups)
+    }
+
+    /// Compare two files by line pattern.
+    /// Returns (added groups, removed groups, common pattern count).
+    fn diff_sources(
+        &self,
+        base: &PathBuf,
+        other: &PathBuf,
+    ) -> Result<(Vec<DiffGroup>, Vec<DiffGroup>, usize), String> {
+        let base_groups = self.pattern_groups(base)?;
+        let other_groups = self.pattern_groups(other)?;
+
+        let only_in = |groups: &HashMap<String, (usize, Vec<LogEntry>)>,
+                       reference: &HashMap<String, (usize, Vec<LogEntry>)>| {
+            let mut diff: Vec<DiffGroup> = groups
+                .iter()
+                .filter(|(pattern, _)| !reference.contains_key(*pattern))
+                .map(|(pattern, (count, samples))| DiffGroup {
+                    pattern: pattern.clone(),
+                    count: *count,
+                    sample_entries: samples.iter().map(entry_to_result).collect(),
+                })
+                .collect();
+            diff.sort_by(|a, b| {
+                b.count
+                    .cmp(&a.count)
+                    .then_with(|| a.pattern.cmp(&b.pattern))
+            });
+            diff
+        };
+
+        let added = only_in(&other_groups, &base_groups);
+        let removed = only_in(&base_groups, &other_groups);
+        let common = base_groups
+            .keys()
+            .filter(|pattern| other_groups.contains_key(*pattern))
+            .count();
+
+        Ok((added, removed, common))
+    }
+
     /// Apply advanced filters to log entries
     fn apply_advanced_filter(
         &self,
         path: &PathBuf,
         conditions: &[FilterConditionParam],
+        logic: FilterLogic,
         max_results: usize,
         start_time: Option<&str>,
         end_time: Option<&str>,
@@ -733,6 +1217,10 @@ impl LoglineToolState {
             ));
         }
 
+        if logic == FilterLogic::Or && conditions.len() > 1 {
+            filters_applied.push("Any condition (OR)".to_string());
+        }
+
         // Build condition matchers
         let mut condition_matchers: Vec<Box<dyn Fn(&str, &LogEntry) -> bool>> = Vec::new();
 
@@ -837,12 +1325,16 @@ impl LoglineToolState {
                 }
             }
 
-            // Check all conditions (AND logic)
-            let matches_all = condition_matchers
+            // Check the conditions (AND or OR logic)
+            let mut checks = condition_matchers
                 .iter()
-                .all(|matcher| matcher(line, &entry));
+                .map(|matcher| matcher(line, &entry));
+            let matches = match logic {
+                FilterLogic::And => checks.all(|matched| matched),
+                FilterLogic::Or => condition_matchers.is_empty() || checks.any(|matched| matched),
+            };
 
-            if matches_all {
+            if matches {
                 let result = entry_to_result(&entry);
                 results.push(result);
             }
@@ -851,6 +1343,33 @@ impl LoglineToolState {
         Ok((results, filters_applied))
     }
 
+    /// Set the analysis context for a source (validated before storing)
+    fn set_source_context(&self, source_id: &str, context: SourceContext) -> Result<(), String> {
+        ContextMatcher::compile(&context)?;
+        let mut contexts = self.source_contexts.write().unwrap();
+        contexts.insert(source_id.to_string(), context);
+        Ok(())
+    }
+
+    /// Get the analysis context for a source
+    fn get_source_context(&self, source_id: &str) -> Option<SourceContext> {
+        let contexts = self.source_contexts.read().unwrap();
+        contexts.get(source_id).cloned()
+    }
+
+    /// Clear the analysis context for a source
+    fn clear_source_context(&self, source_id: &str) -> Option<SourceContext> {
+        let mut contexts = self.source_contexts.write().unwrap();
+        contexts.remove(source_id)
+    }
+
+    /// Compile the active context for a source, if any
+    fn context_matcher(&self, source_id: &str) -> Result<Option<ContextMatcher>, String> {
+        self.get_source_context(source_id)
+            .map(|context| ContextMatcher::compile(&context))
+            .transpose()
+    }
+
     /// Get bookmarks for a source
     fn get_bookmarks(&self, source_id: &str) -> HashMap<usize, Option<String>> {
         let bookmarks = self.bookmarks.read().unwrap();
@@ -1102,12 +1621,14 @@ impl LoglineTools {
             .get_source_path(&params.source_id)
             .ok_or_else(|| format!("Source not found: {}", params.source_id))?;
 
+        let context = self.state.context_matcher(&params.source_id)?;
         let (entries, total_lines, has_more) = self.state.read_log_entries(
             &path,
             params.start_line,
             count,
             &params.levels,
             params.from_end,
+            context.as_ref(),
         )?;
 
         let next_line = if params.from_end {
@@ -1121,6 +1642,7 @@ impl LoglineTools {
             total_lines,
             has_more,
             next_line,
+            context_applied: context.is_some(),
         }))
     }
 
@@ -1138,6 +1660,7 @@ impl LoglineTools {
             .get_source_path(&params.source_id)
             .ok_or_else(|| format!("Source not found: {}", params.source_id))?;
 
+        let context = self.state.context_matcher(&params.source_id)?;
         let matches = self.state.search_logs(
             &path,
             &params.query,
@@ -1146,6 +1669,7 @@ impl LoglineTools {
             &params.levels,
             params.context_lines,
             params.max_results,
+            context.as_ref(),
         )?;
 
         Ok(Json(SearchLogsResponse {
@@ -1153,6 +1677,7 @@ impl LoglineTools {
             total_matches: matches.len(),
             query: params.query,
             is_regex: params.is_regex,
+            context_applied: context.is_some(),
         }))
     }
 
@@ -1206,7 +1731,7 @@ impl LoglineTools {
     /// Apply advanced multi-condition filters to logs
     #[tool(
         name = "advanced_filter",
-        description = "Filter logs with multiple conditions combined with AND logic. Supports content filters (contains, not_contains, regex, starts_with, ends_with) and level filters. Can also filter by time range using ISO 8601 timestamps."
+        description = "Filter logs with multiple conditions combined with AND logic, or OR logic with logic=\"or\". Supports content filters (contains, not_contains, regex, starts_with, ends_with) and level filters. Can also filter by time range using ISO 8601 timestamps."
     )]
     fn advanced_filter(
         &self,
@@ -1217,9 +1742,16 @@ impl LoglineTools {
             .get_source_path(&params.source_id)
             .ok_or_else(|| format!("Source not found: {}", params.source_id))?;
 
+        let logic = match params.logic.as_deref() {
+            None => FilterLogic::And,
+            Some(logic) => FilterLogic::parse(logic)
+                .ok_or_else(|| format!("Unsupported logic: {} (use \"and\" or \"or\")", logic))?,
+        };
+
         let (entries, filters_applied) = self.state.apply_advanced_filter(
             &path,
             &params.conditions,
+            logic,
             params.max_results,
             params.start_time.as_deref(),
             params.end_time.as_deref(),
@@ -1236,7 +1768,7 @@ impl LoglineTools {
     /// List all bookmarked log entries
     #[tool(
         name = "list_bookmarks",
-        description = "List all bookmarked log entries from a source. Bookmarks are used to mark important lines for later reference. Returns bookmarked entries with their line numbers, content, and optional notes."
+        description = "List all bookmarked log entries from a source, including those the user bookmarked in the viewer. Bookmarks are used to mark important lines for later reference. Returns bookmarked entries with their line numbers, content, and optional notes and colors."
     )]
     fn list_bookmarks(
         &self,
@@ -1251,7 +1783,19 @@ impl LoglineTools {
             fs::read_to_string(&path).map_err(|e| format!("Failed to read file: {}", e))?;
 
         let lines: Vec<&str> = content.lines().collect();
-        let bookmarks_map = self.state.get_bookmarks(&params.source_id);
+        let mut bookmarks_map = self.state.get_bookmarks(&params.source_id);
+
+        // Bookmarks saved from the viewer, with their notes and colors
+        let store = BookmarksStore::load().unwrap_or_default();
+        let saved = store.get_bookmarks(&path);
+        if let Some(saved) = saved {
+            for line in &saved.lines {
+                let note = bookmarks_map.entry(*line).or_default();
+                if note.is_none() {
+                    *note = saved.notes.get(line).cloned();
+                }
+            }
+        }
 
         let mut bookmarks: Vec<BookmarkEntry> = bookmarks_map
             .iter()
@@ -1265,6 +1809,10 @@ impl LoglineTools {
                         level: entry.level.map(|l| l.as_str().to_string()),
                         timestamp: entry.timestamp.map(|t| t.to_rfc3339()),
                         note: note.clone(),
+                        color: saved
+                            .and_then(|s| s.colors.get(&line_num))
+                            .and_then(|&color| bookmark_color_name(color))
+                            .map(String::from),
                     })
                 } else {
                     None
@@ -1352,6 +1900,250 @@ impl LoglineTools {
 
         Ok(Json(AnalyzeTimelineResponse { timeline, summary }))
     }
+
+    /// Set the analysis context for a source
+    #[tool(
+        name = "set_source_context",
+        description = "Set a per-source analysis context: an active grok pattern (builtin name like 'Log4j' or a custom grok pattern) and a standing filter (levels and/or keyword/regex query). Subsequent get_log_entries and search_logs calls on that source apply the context automatically. Contexts are kept in memory only and are lost when Logline exits."
+    )]
+    fn set_source_context(
+        &self,
+        Parameters(params): Parameters<SetSourceContextParams>,
+    ) -> Result<Json<SourceContextResponse>, String> {
+        // Verify source exists
+        let _ = self
+            .state
+            .get_source_path(&params.source_id)
+            .ok_or_else(|| format!("Source not found: {}", params.source_id))?;
+
+        let context = SourceContext {
+            grok_pattern: params.grok_pattern,
+            levels: params.levels,
+            query: params.query,
+            is_regex: params.is_regex,
+            case_sensitive: params.case_sensitive,
+        };
+        self.state
+            .set_source_context(&params.source_id, context.clone())?;
+
+        Ok(Json(SourceContextResponse {
+            source_id: params.source_id,
+            context: Some(context),
+            message: "Source context set".to_string(),
+        }))
+    }
+
+    /// Get the analysis context for a source
+    #[tool(
+        name = "get_source_context",
+        description = "Get the analysis context (grok pattern and standing filter) currently set for a source, if any."
+    )]
+    fn get_source_context(
+        &self,
+        Parameters(params): Parameters<SourceContextParams>,
+    ) -> Result<Json<SourceContextResponse>, String> {
+        let context = self.state.get_source_context(&params.source_id);
+        let message = if context.is_some() {
+            "Source context is active".to_string()
+        } else {
+            "No source context set".to_string()
+        };
+
+        Ok(Json(SourceContextResponse {
+            source_id: params.source_id,
+            context,
+            message,
+        }))
+    }
+
+    /// Clear the analysis context for a source
+    #[tool(
+        name = "clear_source_context",
+        description = "Clear the analysis context for a source so later calls read the raw log again."
+    )]
+    fn clear_source_context(
+        &self,
+        Parameters(params): Parameters<SourceContextParams>,
+    ) -> Result<Json<SourceContextResponse>, String> {
+        let message = if self.state.clear_source_context(&params.source_id).is_some() {
+            "Source context cleared".to_string()
+        } else {
+            "No source context set".to_string()
+        };
+
+        Ok(Json(SourceContextResponse {
+            source_id: params.source_id,
+            context: None,
+            message,
+        }))
+    }
+
+    /// Summarize a source in one call
+    #[tool(
+        name = "summarize_log_source",
+        description = "Quick digest of a log source without reading it all: statistics (line count, level distribution, error rate, time range), the most frequent error patterns and the busiest time buckets, plus a human-readable summary. Use get_log_entries, analyze_errors or analyze_timeline to drill down."
+    )]
+    fn summarize_log_source(
+        &self,
+        Parameters(params): Parameters<SummarizeLogSourceParams>,
+    ) -> Result<Json<SummarizeLogSourceResponse>, String> {
+        let path = self
+            .state
+            .get_source_path(&params.source_id)
+            .ok_or_else(|| format!("Source not found: {}", params.source_id))?;
+
+        let bucket_size_seconds = params.bucket_size_seconds.max(1);
+        let statistics = self.state.get_statistics(&path)?;
+        let top_error_groups = self
+            .state
+            .analyze_errors(&path, params.max_error_groups, false)?;
+        let timeline =
+            self.state
+                .analyze_log_timeline(&path, bucket_size_seconds, &[], None, None)?;
+
+        let mut timeline_peaks = timeline.data_points;
+        timeline_peaks.sort_by(|a, b| {
+            b.count
+                .cmp(&a.count)
+                .then_with(|| a.timestamp.cmp(&b.timestamp))
+        });
+        timeline_peaks.truncate(params.max_timeline_buckets);
+
+        let mut summary = format!(
+            "{} lines, {} errors ({} error rate).",
+            statistics.total_lines,
+            statistics.level_distribution.error + statistics.level_distribution.fatal,
+            statistics.error_rate
+        );
+        if let Some(TimeRange {
+            start: Some(start),
+            end: Some(end),
+        }) = &statistics.time_range
+        {
+            summary.push_str(&format!(" Covers {} to {}.", start, end));
+        }
+        if let Some(group) = top_error_groups.first() {
+            summary.push_str(&format!(
+                " Most frequent error ({} times): {}.",
+                group.count, group.pattern
+            ));
+        }
+        if let Some(peak) = timeline_peaks.first() {
+            summary.push_str(&format!(
+                " Busiest {}s bucket: {} entries at {}.",
+                bucket_size_seconds, peak.count, peak.timestamp
+            ));
+        }
+
+        Ok(Json(SummarizeLogSourceResponse {
+            statistics,
+            top_error_groups,
+            timeline_peaks,
+            bucket_size_seconds,
+            summary,
+        }))
+    }
+
+    /// Compare two sources by normalized line pattern
+    #[tool(
+        name = "diff_log_sources",
+        description = "Compare two log sources, e.g. a known-good run against a failing one. Lines are normalized (timestamps, UUIDs, long numbers and IPs masked) and grouped; returns the patterns found in only one of the sources with counts and sample lines."
+    )]
+    fn diff_log_sources(
+        &self,
+        Parameters(params): Parameters<DiffLogSourcesParams>,
+    ) -> Result<Json<DiffLogSourcesResponse>, String> {
+        let base = self
+            .state
+            .get_source_path(&params.base_source_id)
+            .ok_or_else(|| format!("Source not found: {}", params.base_source_id))?;
+        let other = self
+            .state
+            .get_source_path(&params.other_source_id)
+            .ok_or_else(|| format!("Source not found: {}", params.other_source_id))?;
+
+        let (mut added, mut removed, common_patterns) = self.state.diff_sources(&base, &other)?;
+
+        let total_added_lines = added.iter().map(|g| g.count).sum();
+        let total_removed_lines = removed.iter().map(|g| g.count).sum();
+        added.truncate(params.max_groups);
+        removed.truncate(params.max_groups);
+
+        Ok(Json(DiffLogSourcesResponse {
+            added,
+            removed,
+            total_added_lines,
+            total_removed_lines,
+            common_patterns,
+        }))
+    }
+
+    /// Poll a source for new lines
+    #[tool(
+        name = "tail_log_source",
+        description = "Return the last lines of a source plus a cursor. Call again with the cursor to get only the lines appended since, e.g. to watch a log during an incident. Can filter by log levels."
+    )]
+    fn tail_log_source(
+        &self,
+        Parameters(params): Parameters<TailLogSourceParams>,
+    ) -> Result<Json<TailLogSourceResponse>, String> {
+        let count = params.count.min(1000); // Max 1000 entries
+
+        let path = self
+            .state
+            .get_source_path(&params.source_id)
+            .ok_or_else(|| format!("Source not found: {}", params.source_id))?;
+
+        let context = self.state.context_matcher(&params.source_id)?;
+        let (entries, cursor, has_new_data, restarted) = self.state.tail_log_source(
+            &path,
+            count,
+            params.cursor,
+            &params.levels,
+            context.as_ref(),
+        )?;
+
+        Ok(Json(TailLogSourceResponse {
+            entries,
+            current_total_lines: cursor.line_count,
+            cursor,
+            has_new_data,
+            restarted,
+            context_applied: context.is_some(),
+        }))
+    }
+
+    /// Get the lines around an entry
+    #[tool(
+        name = "get_entry_context",
+        description = "Return a line of a source together with the lines before and after it, e.g. to look around a search match. The range is clamped to the source and the actual start_line and end_line are reported."
+    )]
+    fn get_entry_context(
+        &self,
+        Parameters(params): Parameters<GetEntryContextParams>,
+    ) -> Result<Json<GetEntryContextResponse>, String> {
+        let path = self
+            .state
+            .get_source_path(&params.source_id)
+            .ok_or_else(|| format!("Source not found: {}", params.source_id))?;
+
+        let context = self.state.context_matcher(&params.source_id)?;
+        let (entries, start_line, end_line, total_lines) = self.state.read_entry_context(
+            &path,
+            params.line_number,
+            params.before.min(500),
+            params.after.min(500),
+            context.as_ref(),
+        )?;
+
+        Ok(Json(GetEntryContextResponse {
+            entries,
+            start_line,
+            end_line,
+            total_lines,
+            context_applied: context.is_some(),
+        }))
+    }
 }
 
 #[tool_handler]
@@ -1369,7 +2161,14 @@ impl ServerHandler for LoglineTools {
                 - advanced_filter: Filter logs with multiple conditions\n\
                 - list_bookmarks: List all bookmarked entries\n\
                 - manage_bookmarks: Add/remove/toggle bookmarks\n\
-                - analyze_timeline: Analyze log frequency over time"
+                - analyze_timeline: Analyze log frequency over time\n\
+                - tail_log_source: Poll a source for lines appended since a cursor\n\
+                - get_entry_context: Read the lines around a line of a source\n\
+                - diff_log_sources: Compare the line patterns of two sources\n\
+                - summarize_log_source: Digest of statistics, top errors and busiest periods\n\
+                - set_source_context: Set a grok pattern + standing filter for a source\n\
+                - get_source_context: Show the context set for a source\n\
+                - clear_source_context: Remove the context for a source"
                     .into(),
             ),
             capabilities: ServerCapabilities::builder().enable_tools().build(),
@@ -1386,36 +2185,45 @@ impl ServerHandler for LoglineTools {
 fn entry_to_result(entry: &LogEntry) -> LogEntryResult {
     LogEntryResult {
         line_number: entry.line_number,
-        content: entry.content.clone(),
+        content: entry.content.to_string(),
         level: entry.level.map(|l| l.as_str().to_string()),
         timestamp: entry.timestamp.map(|t| t.to_rfc3339()),
+        fields: entry.grok_fields.clone(),
     }
 }
 
 /// Simplify error message to create grouping pattern
 fn simplify_error_pattern(line: &str) -> String {
+    // Compiled once; diff_log_sources runs this on every line of two files
+    // Time of day with optional date and fraction: "2024-01-15 10:00:00.123",
+    // "10:00:00,5", ...
+    static TIMESTAMP_RE: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r"(?:\d{4}[-/]\d{2}[-/]\d{2}[T ])?\d{2}:\d{2}:\d{2}(?:[.,]\d+)?").unwrap()
+    });
+    static UUID_RE: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r"[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}")
+            .unwrap()
+    });
+    static NUMBER_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\b\d{5,}\b").unwrap());
+    static IP_RE: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"\d{1,3}\.\d{1,3}\.\d{1,3}\.\d{1,3}").unwrap());
+
     // Remove timestamps, numbers, IDs, etc. to group similar errors
     let mut pattern = line.to_string();
 
     // Remove common timestamp patterns
-    let timestamp_re = Regex::new(r"\d{4}[-/]\d{2}[-/]\d{2}[T ]\d{2}:\d{2}:\d{2}").unwrap();
-    pattern = timestamp_re
+    pattern = TIMESTAMP_RE
         .replace_all(&pattern, "[TIMESTAMP]")
         .to_string();
 
     // Replace UUIDs
-    let uuid_re =
-        Regex::new(r"[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}")
-            .unwrap();
-    pattern = uuid_re.replace_all(&pattern, "[UUID]").to_string();
+    pattern = UUID_RE.replace_all(&pattern, "[UUID]").to_string();
 
     // Replace numbers (but keep short ones like error codes)
-    let number_re = Regex::new(r"\b\d{5,}\b").unwrap();
-    pattern = number_re.replace_all(&pattern, "[NUM]").to_string();
+    pattern = NUMBER_RE.replace_all(&pattern, "[NUM]").to_string();
 
     // Replace IP addresses
-    let ip_re = Regex::new(r"\d{1,3}\.\d{1,3}\.\d{1,3}\.\d{1,3}").unwrap();
-    pattern = ip_re.replace_all(&pattern, "[IP]").to_string();
+    pattern = IP_RE.replace_all(&pattern, "[IP]").to_string();
 
     // Truncate if too long
     if pattern.len() > 200 {
@@ -1425,3 +2233,336 @@ fn simplify_error_pattern(line: &str) -> String {
 
     pattern
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn tools_with_file() -> (LoglineTools, String, NamedTempFile) {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "2024-01-15 10:30:45 INFO service started").unwrap();
+        writeln!(file, "2024-01-15 10:30:46 ERROR db timeout").unwrap();
+        writeln!(file, "2024-01-15 10:30:47 WARN db slow").unwrap();
+        writeln!(file, "2024-01-15 10:30:48 ERROR cache miss").unwrap();
+        file.flush().unwrap();
+
+        let state = LoglineToolState::new(PathBuf::from("."));
+        state.add_local_file(file.path().to_path_buf());
+        let source_id = format!("local:{}", file.path().display());
+        (LoglineTools::new(Arc::new(state)), source_id, file)
+    }
+
+    fn get_entries(tools: &LoglineTools, source_id: &str) -> GetLogEntriesResponse {
+        tools
+            .get_log_entries(Parameters(GetLogEntriesParams {
+                source_id: source_id.to_string(),
+                start_line: 1,
+                count: 100,
+                levels: Vec::new(),
+                from_end: false,
+            }))
+            .unwrap()
+            .0
+    }
+
+    #[test]
+    fn test_source_context_filters_later_calls() {
+        let (tools, source_id, _file) = tools_with_file();
+        assert_eq!(get_entries(&tools, &source_id).entries.len(), 4);
+
+        tools
+            .set_source_context(Parameters(SetSourceContextParams {
+                source_id: source_id.clone(),
+                grok_pattern: Some("SimpleLog".to_string()),
+                levels: vec!["ERROR".to_string()],
+                query: None,
+                is_regex: false,
+                case_sensitive: false,
+            }))
+            .unwrap();
+
+        let response = get_entries(&tools, &source_id);
+        assert!(response.context_applied);
+        let lines: Vec<usize> = response.entries.iter().map(|e| e.line_number).collect();
+        assert_eq!(lines, vec![2, 4]);
+        let fields = response.entries[0].fields.as_ref().unwrap();
+        assert_eq!(fields.get("message").map(String::as_str), Some("db timeout"));
+
+        let search = tools
+            .search_logs(Parameters(SearchLogsParams {
+                source_id: source_id.clone(),
+                query: "db".to_string(),
+                is_regex: false,
+                case_sensitive: false,
+                levels: Vec::new(),
+                context_lines: 0,
+                max_results: 50,
+            }))
+            .unwrap()
+            .0;
+        // The WARN "db slow" line is outside the standing ERROR filter
+        assert_eq!(search.total_matches, 1);
+        assert_eq!(search.matches[0].line_number, 2);
+    }
+
+    #[test]
+    fn test_clear_source_context() {
+        let (tools, source_id, _file) = tools_with_file();
+        let params = || SourceContextParams {
+            source_id: source_id.clone(),
+        };
+
+        tools
+            .set_source_context(Parameters(SetSourceContextParams {
+                source_id: source_id.clone(),
+                grok_pattern: None,
+                levels: Vec::new(),
+                query: Some("cache".to_string()),
+                is_regex: false,
+                case_sensitive: false,
+            }))
+            .unwrap();
+        assert!(tools.get_source_context(Parameters(params())).unwrap().0.context.is_some());
+        assert_eq!(get_entries(&tools, &source_id).entries.len(), 1);
+
+        tools.clear_source_context(Parameters(params())).unwrap();
+        assert!(tools.get_source_context(Parameters(params())).unwrap().0.context.is_none());
+        let response = get_entries(&tools, &source_id);
+        assert!(!response.context_applied);
+        assert_eq!(response.entries.len(), 4);
+    }
+
+    #[test]
+    fn test_invalid_context_rejected() {
+        let (tools, source_id, _file) = tools_with_file();
+        let result = tools.set_source_context(Parameters(SetSourceContextParams {
+            source_id: source_id.clone(),
+            grok_pattern: None,
+            levels: Vec::new(),
+            query: Some("(unclosed".to_string()),
+            is_regex: true,
+            case_sensitive: false,
+        }));
+        assert!(result.is_err());
+        assert!(tools.state.get_source_context(&source_id).is_none());
+    }
+
+    #[test]
+    fn test_advanced_filter_and_or_logic() {
+        let (tools, source_id, _file) = tools_with_file();
+        let condition = |field: &str, operator: &str, value: &str| FilterConditionParam {
+            field: field.to_string(),
+            operator: operator.to_string(),
+            value: value.to_string(),
+            case_sensitive: false,
+        };
+        let filter = |logic: Option<&str>| {
+            tools.advanced_filter(Parameters(AdvancedFilterParams {
+                source_id: source_id.clone(),
+                conditions: vec![
+                    condition("level", "equals", "ERROR"),
+                    condition("content", "contains", "db"),
+                ],
+                logic: logic.map(str::to_string),
+                max_results: 100,
+                start_time: None,
+                end_time: None,
+                context_lines: 0,
+            }))
+        };
+        let lines = |logic| -> Vec<usize> {
+            filter(logic)
+                .unwrap()
+                .0
+                .entries
+                .iter()
+                .map(|e| e.line_number)
+                .collect()
+        };
+
+        // AND stays the default
+        assert_eq!(lines(None), vec![2]);
+        assert_eq!(lines(Some("and")), vec![2]);
+        assert_eq!(lines(Some("OR")), vec![2, 3, 4]);
+        assert!(filter(Some("xor")).is_err());
+    }
+
+    #[test]
+    fn test_tail_log_source_returns_appended_lines() {
+        let (tools, source_id, mut file) = tools_with_file();
+        let tail = |cursor, levels: &[&str]| {
+            tools
+                .tail_log_source(Parameters(TailLogSourceParams {
+                    source_id: source_id.clone(),
+                    count: 2,
+                    cursor,
+                    levels: levels.iter().map(|l| l.to_string()).collect(),
+                }))
+                .unwrap()
+                .0
+        };
+
+        let first = tail(None, &[]);
+        let lines: Vec<usize> = first.entries.iter().map(|e| e.line_number).collect();
+        assert_eq!(lines, vec![3, 4]);
+        assert!(first.has_new_data);
+        assert_eq!(first.current_total_lines, 4);
+
+        // Nothing appended yet
+        let idle = tail(Some(first.cursor), &[]);
+        assert!(!idle.has_new_data);
+        assert!(idle.entries.is_empty());
+        assert_eq!(idle.cursor, first.cursor);
+
+        writeln!(file, "2024-01-15 10:30:49 INFO retrying").unwrap();
+        writeln!(file, "2024-01-15 10:30:50 ERROR db down").unwrap();
+        write!(file, "2024-01-15 10:30:51 INFO half writ").unwrap();
+        file.flush().unwrap();
+
+        let next = tail(Some(idle.cursor), &["ERROR"]);
+        assert!(next.has_new_data);
+        assert!(!next.restarted);
+        let lines: Vec<usize> = next.entries.iter().map(|e| e.line_number).collect();
+        assert_eq!(lines, vec![6]);
+        // The unterminated line is left for the next call
+        assert_eq!(next.current_total_lines, 6);
+        assert!(next.cursor.partial_since_ms.is_some());
+
+        // ...and returned once it has been held back past the timeout
+        let held = TailCursor {
+            partial_since_ms: Some(Local::now().timestamp_millis() - 60_000),
+            ..next.cursor
+        };
+        let late = tail(Some(held), &[]);
+        let lines: Vec<usize> = late.entries.iter().map(|e| e.line_number).collect();
+        assert_eq!(lines, vec![7]);
+        assert_eq!(late.cursor.partial_since_ms, None);
+    }
+
+    #[test]
+    fn test_get_entry_context_clamps_to_the_source() {
+        let (tools, source_id, _file) = tools_with_file();
+        let context = |line_number, before, after| {
+            tools.get_entry_context(Parameters(GetEntryContextParams {
+                source_id: source_id.clone(),
+                line_number,
+                before,
+                after,
+            }))
+        };
+
+        let middle = context(3, 1, 1).unwrap().0;
+        let lines: Vec<usize> = middle.entries.iter().map(|e| e.line_number).collect();
+        assert_eq!(lines, vec![2, 3, 4]);
+
+        // Near the start and end the range is cut short and reported
+        let clamped = context(2, 5, 5).unwrap().0;
+        assert_eq!((clamped.start_line, clamped.end_line), (1, 4));
+        assert_eq!(clamped.entries.len(), 4);
+        assert_eq!(clamped.total_lines, 4);
+
+        assert!(context(5, 1, 1).is_err());
+        assert!(context(0, 1, 1).is_err());
+    }
+
+    #[test]
+    fn test_diff_log_sources_groups_unmatched_patterns() {
+        let (tools, base_id, _base) = tools_with_file();
+        let mut other = NamedTempFile::new().unwrap();
+        writeln!(other, "2024-02-01 08:00:00 INFO service started").unwrap();
+        writeln!(other, "2024-02-01 08:00:01 ERROR db timeout").unwrap();
+        writeln!(other, "2024-02-01 08:00:02 ERROR disk full on 10.0.0.7").unwrap();
+        writeln!(other, "2024-02-01 08:00:03 ERROR disk full on 10.0.0.8").unwrap();
+        other.flush().unwrap();
+        let other_id = format!("local:{}", other.path().display());
+
+        // Fractional seconds and bare times are masked too, and a byte that
+        // isn't valid UTF-8 doesn't fail the diff
+        writeln!(other, "2024-02-01 08:00:04.250 ERROR disk full on 10.0.0.9").unwrap();
+        other
+            .write_all(b"08:00:05,7 ERROR disk full on 10.0.1.1 \xff\n")
+            .unwrap();
+        other.flush().unwrap();
+
+        let diff = tools
+            .diff_log_sources(Parameters(DiffLogSourcesParams {
+                base_source_id: base_id,
+                other_source_id: other_id,
+                max_groups: 20,
+            }))
+            .unwrap()
+            .0;
+
+        // Timestamps and IPs are masked, so only new messages differ
+        assert_eq!(diff.common_patterns, 2);
+        assert_eq!(diff.added.len(), 2);
+        assert_eq!(diff.added[0].pattern, "[TIMESTAMP] ERROR disk full on [IP]");
+        assert_eq!(diff.added[0].count, 3);
+        assert_eq!(diff.added[0].sample_entries[0].line_number, 3);
+        assert_eq!(
+            diff.added[1].pattern,
+            "[TIMESTAMP] ERROR disk full on [IP] \u{fffd}"
+        );
+        assert_eq!(diff.total_added_lines, 4);
+
+        let removed: Vec<&str> = diff.removed.iter().map(|g| g.pattern.as_str()).collect();
+        assert_eq!(
+            removed,
+            vec!["[TIMESTAMP] ERROR cache miss", "[TIMESTAMP] WARN db slow"]
+        );
+        assert_eq!(diff.total_removed_lines, 2);
+    }
+
+    #[test]
+    fn test_summarize_log_source_caps_groups_and_buckets() {
+        let (tools, source_id, _file) = tools_with_file();
+        let response = tools
+            .summarize_log_source(Parameters(SummarizeLogSourceParams {
+                source_id,
+                max_error_groups: 1,
+                max_timeline_buckets: 5,
+                bucket_size_seconds: 60,
+            }))
+            .unwrap()
+            .0;
+
+        assert_eq!(response.statistics.total_lines, 4);
+        assert_eq!(response.statistics.level_distribution.error, 2);
+        assert_eq!(response.top_error_groups.len(), 1);
+        // All four lines fall into the same minute
+        assert_eq!(response.timeline_peaks.len(), 1);
+        assert_eq!(response.timeline_peaks[0].count, 4);
+        assert!(response
+            .summary
+            .starts_with("4 lines, 2 errors (50.00% error rate)."));
+    }
+
+    #[test]
+    fn test_copied_source_ids_match_listed_sources() {
+        let state = LoglineToolState::new(PathBuf::from("."));
+        let path = PathBuf::from("/var/log/app.log");
+        state.add_local_file(path.clone());
+        let stream = RemoteStream {
+            stream_id: "payments@10.0.0.5:40123".to_string(),
+            project_name: "payments".to_string(),
+            status: ConnectionStatus::Online,
+            cache_path: PathBuf::from("/tmp/payments.log"),
+            last_activity: std::time::Instant::now(),
+            remote_addr: "10.0.0.5:40123".parse().unwrap(),
+            bytes_received: 0,
+            metadata: Default::default(),
+        };
+        state.update_remote_streams(vec![stream.clone()]);
+
+        let ids: Vec<String> = state
+            .get_sources("all", "all")
+            .into_iter()
+            .map(|source| source.id)
+            .collect();
+        assert_eq!(ids, vec![local_source_id(&path), remote_source_id(&stream)]);
+        assert_eq!(state.get_source_path(&ids[0]), Some(path));
+        assert_eq!(state.get_source_path(&ids[1]), Some(stream.cache_path));
+    }
+}
@@ -2,6 +2,7 @@
 
 use rmcp::schemars::{self, JsonSchema};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 /// Log source type
@@ -52,6 +53,45 @@ pub struct LogEntryResult {
     /// Parsed timestamp (ISO 8601)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub timestamp: Option<String>,
+    /// Grok-extracted fields (when a source context with a grok pattern is active)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fields: Option<HashMap<String, String>>,
+}
+
+/// Per-source analysis context
+///
+/// Stored in memory only; contexts are lost when the application exits.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct SourceContext {
+    /// Active grok pattern: a builtin name (e.g. "Log4j", "Syslog") or a custom grok pattern
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grok_pattern: Option<String>,
+    /// Standing level filter (e.g. ["ERROR", "WARN"]); empty means all levels
+    #[serde(default)]
+    pub levels: Vec<String>,
+    /// Standing content filter (keyword or regex)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+    /// Whether the query is a regex pattern
+    #[serde(default)]
+    pub is_regex: bool,
+    /// Case sensitive query matching
+    #[serde(default)]
+    pub case_sensitive: bool,
+}
+
+/// Position reached by tail_log_source; pass it back to get only newer lines
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct TailCursor {
+    /// Byte offset just past the last line returned
+    pub byte_offset: u64,
+    /// Number of lines before `byte_offset`
+    pub line_count: usize,
+    /// When the unterminated line at `byte_offset` was first held back
+    /// (Unix milliseconds); it is returned once it stays incomplete past
+    /// the hold-back timeout
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partial_since_ms: Option<i64>,
 }
 
 /// Log level distribution statistics
@@ -130,6 +170,17 @@ pub struct ErrorGroup {
     pub sample_entries: Vec<LogEntryResult>,
 }
 
+/// Normalized line pattern found in only one of two diffed sources
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DiffGroup {
+    /// Normalized line pattern
+    pub pattern: String,
+    /// Number of lines with this pattern
+    pub count: usize,
+    /// Sample log entries
+    pub sample_entries: Vec<LogEntryResult>,
+}
+
 /// Bookmark entry information
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct BookmarkEntry {
@@ -146,6 +197,9 @@ pub struct BookmarkEntry {
     /// Optional note/comment for this bookmark
     #[serde(skip_serializing_if = "Option::is_none")]
     pub note: Option<String>,
+    /// Bookmark color name, when not the default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
 }
 
 /// Log frequency data point
@@ -6,9 +6,14 @@
 //! - Length: Total length of Type + Payload (big-endian)
 //! - Type: Message type identifier
 //! - Payload: Message body
+//!
+//! Agents that announce `heartbeat` in their handshake send a keepalive
+//! frame every [`HEARTBEAT_INTERVAL`] while they have no log data to send,
+//! so the server can tell a quiet agent from a dead one.
 
 use serde::{Deserialize, Serialize};
 use std::io::{self, Read, Write};
+use std::time::Duration;
 use thiserror::Error;
 
 /// Protocol version
@@ -17,6 +22,9 @@ pub const PROTOCOL_VERSION: u8 = 1;
 /// Default server port
 pub const DEFAULT_PORT: u16 = 12500;
 
+/// How often an idle agent sends a keepalive frame
+pub const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(15);
+
 /// Message type identifiers
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
@@ -25,7 +33,7 @@ pub enum MessageType {
     Handshake = 0x01,
     /// Agent -> App: Log data stream
     LogData = 0x02,
-    /// Bidirectional: Keepalive/heartbeat
+    /// Bidirectional: Keepalive/heartbeat, sent every [`HEARTBEAT_INTERVAL`]
     Keepalive = 0xFF,
 }
 
@@ -59,6 +67,9 @@ pub enum ProtocolError {
 
     #[error("Frame too large: {0} bytes (max: {1})")]
     FrameTooLarge(usize, usize),
+
+    #[error("Authentication failed: {0}")]
+    Unauthorized(String),
 }
 
 /// Maximum frame size (10MB)
@@ -75,12 +86,47 @@ pub struct HandshakePayload {
     /// Unique agent ID (hash of log file path)
     #[serde(default)]
     pub agent_id: Option<String>,
+    /// Optional details about the agent (missing for older agents)
+    #[serde(default)]
+    pub metadata: AgentMetadata,
+    /// Shared secret, required when the server has an auth token set
+    #[serde(default)]
+    pub auth_token: Option<String>,
+    /// The agent sends keepalives while idle (older agents don't)
+    #[serde(default)]
+    pub heartbeat: bool,
+}
+
+/// Optional agent details sent with the handshake
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentMetadata {
+    /// Hostname of the machine the agent runs on
+    #[serde(default)]
+    pub hostname: Option<String>,
+    /// Version of the application producing the logs
+    #[serde(default)]
+    pub app_version: Option<String>,
+    /// Declared log format: a grok pattern name known to the viewer
+    #[serde(default)]
+    pub log_format: Option<String>,
+}
+
+impl AgentMetadata {
+    /// Whether no metadata was sent
+    pub fn is_empty(&self) -> bool {
+        self.hostname.is_none() && self.app_version.is_none() && self.log_format.is_none()
+    }
 }
 
 fn default_version() -> u8 {
     PROTOCOL_VERSION
 }
 
+/// Compare secrets without bailing out at the first differing byte
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
 impl HandshakePayload {
     #[allow(dead_code)]
     pub fn new(project_name: impl Into<String>) -> Self {
@@ -88,8 +134,44 @@ impl HandshakePayload {
             project_name: project_name.into(),
             version: PROTOCOL_VERSION,
             agent_id: None,
+            metadata: AgentMetadata::default(),
+            auth_token: None,
+            heartbeat: false,
         }
     }
+
+    /// Announce that the agent sends keepalives while idle
+    #[allow(dead_code)]
+    pub fn with_heartbeat(mut self) -> Self {
+        self.heartbeat = true;
+        self
+    }
+
+    /// Attach the shared secret expected by the server
+    #[allow(dead_code)]
+    pub fn with_auth_token(mut self, token: impl Into<String>) -> Self {
+        self.auth_token = Some(token.into());
+        self
+    }
+
+    /// Check the agent's token against the one the server expects, if any
+    pub fn authorize(&self, expected: Option<&str>) -> Result<(), ProtocolError> {
+        let Some(expected) = expected else {
+            return Ok(());
+        };
+        match &self.auth_token {
+            Some(token) if constant_time_eq(token.as_bytes(), expected.as_bytes()) => Ok(()),
+            Some(_) => Err(ProtocolError::Unauthorized("invalid token".to_string())),
+            None => Err(ProtocolError::Unauthorized("missing token".to_string())),
+        }
+    }
+
+    /// Attach agent metadata
+    #[allow(dead_code)]
+    pub fn with_metadata(mut self, metadata: AgentMetadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
 }
 
 /// Log data message payload
@@ -127,8 +209,13 @@ impl Frame {
     /// Create a handshake frame
     #[allow(dead_code)]
     pub fn handshake(project_name: impl Into<String>) -> Result<Self, ProtocolError> {
-        let payload = HandshakePayload::new(project_name);
-        let bytes = serde_json::to_vec(&payload)
+        Self::handshake_with(&HandshakePayload::new(project_name))
+    }
+
+    /// Create a handshake frame from a full payload
+    #[allow(dead_code)]
+    pub fn handshake_with(payload: &HandshakePayload) -> Result<Self, ProtocolError> {
+        let bytes = serde_json::to_vec(payload)
             .map_err(|e| ProtocolError::Serialization(e.to_string()))?;
         Ok(Self::new(MessageType::Handshake, bytes))
     }
@@ -269,6 +356,57 @@ mod tests {
         assert_eq!(payload.project_name, "test-project");
     }
 
+    #[test]
+    fn test_handshake_auth_token() {
+        let payload = HandshakePayload::new("api").with_auth_token("s3cret");
+        let frame = Frame::handshake_with(&payload).unwrap();
+        let parsed = frame.parse_handshake().unwrap();
+
+        assert!(parsed.authorize(None).is_ok());
+        assert!(parsed.authorize(Some("s3cret")).is_ok());
+        assert!(matches!(
+            parsed.authorize(Some("other")),
+            Err(ProtocolError::Unauthorized(_))
+        ));
+        // Older agents send no token at all
+        let anonymous = Frame::handshake("api").unwrap().parse_handshake().unwrap();
+        assert!(anonymous.authorize(Some("s3cret")).is_err());
+        assert!(anonymous.authorize(None).is_ok());
+    }
+
+    #[test]
+    fn test_handshake_metadata() {
+        let payload = HandshakePayload::new("api").with_metadata(AgentMetadata {
+            hostname: Some("web-01".to_string()),
+            app_version: Some("2.4.1".to_string()),
+            log_format: Some("Combined Log".to_string()),
+        });
+        let frame = Frame::handshake_with(&payload).unwrap();
+
+        let mut cursor = Cursor::new(frame.encode());
+        let decoded = Frame::decode(&mut cursor).unwrap();
+        let parsed = decoded.parse_handshake().unwrap();
+        assert_eq!(parsed.metadata, payload.metadata);
+        assert_eq!(parsed.metadata.hostname.as_deref(), Some("web-01"));
+
+        // Older agents send no metadata
+        let old = Frame::new(
+            MessageType::Handshake,
+            br#"{"project_name":"api","version":1}"#.to_vec(),
+        );
+        let parsed = old.parse_handshake().unwrap();
+        assert_eq!(parsed.project_name, "api");
+        assert!(parsed.metadata.is_empty());
+        assert!(!parsed.heartbeat);
+    }
+
+    #[test]
+    fn test_handshake_heartbeat() {
+        let payload = HandshakePayload::new("api").with_heartbeat();
+        let frame = Frame::handshake_with(&payload).unwrap();
+        assert!(frame.parse_handshake().unwrap().heartbeat);
+    }
+
     #[test]
     fn test_log_data_frame() {
         let data = b"2024-01-01 12:00:00 INFO test log message".to_vec();
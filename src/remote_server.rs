@@ -2,22 +2,28 @@
 //!
 //! This module implements a TCP server that listens for connections from
 //! logline-agent instances and writes received logs to local cache files.
+//! Connections are wrapped in TLS when a certificate and key are configured.
 
-use crate::protocol::{Frame, MessageType, ProtocolError, DEFAULT_PORT};
+use crate::protocol::{
+    AgentMetadata, Frame, HandshakePayload, MessageType, ProtocolError, DEFAULT_PORT,
+    HEARTBEAT_INTERVAL,
+};
 use anyhow::{Context, Result};
 use crossbeam_channel::{bounded, Receiver, Sender};
 use std::collections::HashMap;
-use std::fs::{self, OpenOptions};
-use std::io::Write;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, Seek, SeekFrom, Write};
 use std::net::SocketAddr;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, RwLock};
 use std::thread::JoinHandle;
 use std::time::{Duration, Instant};
-use tokio::io::{AsyncReadExt, BufReader};
+use tokio::io::{AsyncRead, AsyncReadExt, BufReader};
 use tokio::net::{TcpListener, TcpStream};
 use tokio::sync::mpsc;
+use tokio_rustls::{rustls, TlsAcceptor};
+use tokio_util::sync::CancellationToken;
 
 /// Remote stream connection status
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -45,6 +51,13 @@ pub struct RemoteStream {
     pub remote_addr: SocketAddr,
     /// Total bytes received
     pub bytes_received: u64,
+    /// Details the agent sent with its handshake
+    pub metadata: AgentMetadata,
+    /// Cancels the connection task writing the stream, when another
+    /// connection resumes it
+    pub cancel: CancellationToken,
+    /// Held by the connection task while the cache file is open
+    pub cache_lock: Arc<tokio::sync::Mutex<()>>,
 }
 
 impl RemoteStream {
@@ -71,6 +84,11 @@ pub enum ServerEvent {
         #[allow(dead_code)]
         cache_path: PathBuf,
     },
+    /// Agent came back and resumed its earlier stream
+    AgentReconnected {
+        project_name: String,
+        stream_id: String,
+    },
     /// Agent disconnected
     AgentDisconnected {
         project_name: String,
@@ -79,6 +97,12 @@ pub enum ServerEvent {
     /// Log data received (for UI refresh notification)
     #[allow(dead_code)]
     LogDataReceived { project_name: String, bytes: usize },
+    /// Oldest bytes of a stream's cache file were dropped to stay under the limit
+    CacheTrimmed {
+        project_name: String,
+        stream_id: String,
+        bytes_removed: u64,
+    },
     /// Server error
     Error(String),
     /// Server started
@@ -96,6 +120,54 @@ pub struct ServerConfig {
     pub cache_dir: PathBuf,
     /// Read timeout for client connections
     pub read_timeout: Duration,
+    /// PEM certificate chain for TLS (plaintext TCP when unset)
+    pub tls_cert_path: Option<PathBuf>,
+    /// PEM private key for `tls_cert_path`
+    pub tls_key_path: Option<PathBuf>,
+    /// Shared secret agents must send in their handshake (open when unset)
+    pub auth_token: Option<String>,
+    /// Largest a stream's cache file may grow, in bytes (0 = unlimited)
+    pub max_cache_bytes: u64,
+    /// Resume the stream and cache file of a returning agent instead of
+    /// starting a new one
+    pub reuse_existing: bool,
+    /// Agents announcing heartbeats that send nothing, not even a heartbeat,
+    /// for this long are disconnected as stale (None = wait forever)
+    pub stale_timeout: Option<Duration>,
+}
+
+impl ServerConfig {
+    /// Whether a certificate and key are configured for TLS
+    pub fn tls_enabled(&self) -> bool {
+        self.tls_cert_path.is_some() && self.tls_key_path.is_some()
+    }
+
+    /// Stale timeout of an agent; agents that don't send heartbeats may be
+    /// silent for any time
+    fn stale_timeout_for(&self, handshake: &HandshakePayload) -> Option<Duration> {
+        self.stale_timeout.filter(|_| handshake.heartbeat)
+    }
+
+    /// Whether an agent silent for `idle` has gone stale
+    fn is_stale(&self, handshake: &HandshakePayload, idle: Duration) -> bool {
+        self.stale_timeout_for(handshake)
+            .is_some_and(|timeout| idle >= timeout)
+    }
+
+    /// How long a single read waits before the connection is checked
+    fn read_wait(&self, handshake: &HandshakePayload) -> Duration {
+        self.stale_timeout_for(handshake)
+            .map_or(self.read_timeout, |timeout| timeout.min(self.read_timeout))
+    }
+
+    /// Build the TLS acceptor, or None for plaintext connections
+    fn tls_acceptor(&self) -> Result<Option<TlsAcceptor>> {
+        match (&self.tls_cert_path, &self.tls_key_path) {
+            (Some(cert), Some(key)) => load_tls_acceptor(cert, key).map(Some),
+            (None, None) => Ok(None),
+            _ => anyhow::bail!("TLS needs both a certificate and a private key"),
+        }
+    }
 }
 
 impl Default for ServerConfig {
@@ -109,10 +181,19 @@ impl Default for ServerConfig {
             port: DEFAULT_PORT,
             cache_dir,
             read_timeout: Duration::from_secs(60),
+            tls_cert_path: None,
+            tls_key_path: None,
+            auth_token: None,
+            max_cache_bytes: DEFAULT_MAX_CACHE_BYTES,
+            reuse_existing: true,
+            stale_timeout: Some(HEARTBEAT_INTERVAL * 4),
         }
     }
 }
 
+/// Default limit of a stream's cache file (100 MB)
+pub const DEFAULT_MAX_CACHE_BYTES: u64 = 100 * 1024 * 1024;
+
 /// Remote log server
 pub struct RemoteServer {
     config: ServerConfig,
@@ -151,12 +232,32 @@ impl RemoteServer {
         self.config.port = port;
     }
 
+    /// Update the token agents must present (takes effect on next start)
+    pub fn set_auth_token(&mut self, token: Option<String>) {
+        self.config.auth_token = token;
+    }
+
+    /// Update whether returning agents resume their stream (takes effect on next start)
+    pub fn set_reuse_existing(&mut self, reuse: bool) {
+        self.config.reuse_existing = reuse;
+    }
+
+    /// Update the cache file limit per stream (takes effect on next start)
+    pub fn set_max_cache_bytes(&mut self, bytes: u64) {
+        self.config.max_cache_bytes = bytes;
+    }
+
     /// Get the current configured port
     #[allow(dead_code)]
     pub fn port(&self) -> u16 {
         self.config.port
     }
 
+    /// Whether agents connect over TLS
+    pub fn tls_enabled(&self) -> bool {
+        self.config.tls_enabled()
+    }
+
     /// Start the server
     pub fn start(&mut self) -> Result<()> {
         if self.running.load(Ordering::Relaxed) {
@@ -166,6 +267,9 @@ impl RemoteServer {
         // Ensure cache directory exists
         fs::create_dir_all(&self.config.cache_dir).context("Failed to create cache directory")?;
 
+        // Load certificates up front so a bad TLS setup fails the start
+        let tls = self.config.tls_acceptor()?;
+
         self.running.store(true, Ordering::Relaxed);
 
         let (shutdown_tx, shutdown_rx) = mpsc::channel(1);
@@ -185,7 +289,7 @@ impl RemoteServer {
 
             rt.block_on(async move {
                 if let Err(e) =
-                    Self::run_server(config, running, streams, event_tx, shutdown_rx).await
+                    Self::run_server(config, tls, running, streams, event_tx, shutdown_rx).await
                 {
                     tracing::error!("Server error: {}", e);
                 }
@@ -264,6 +368,7 @@ impl RemoteServer {
     /// Async server main loop
     async fn run_server(
         config: ServerConfig,
+        tls: Option<TlsAcceptor>,
         running: Arc<AtomicBool>,
         streams: Arc<RwLock<HashMap<String, RemoteStream>>>,
         event_tx: Sender<ServerEvent>,
@@ -273,7 +378,11 @@ impl RemoteServer {
             .await
             .context("Failed to bind to port")?;
 
-        tracing::info!("Remote server listening on port {}", config.port);
+        tracing::info!(
+            "Remote server listening on port {}{}",
+            config.port,
+            if tls.is_some() { " (TLS)" } else { "" }
+        );
         let _ = event_tx.send(ServerEvent::Started { port: config.port });
 
         loop {
@@ -294,11 +403,12 @@ impl RemoteServer {
                             let streams = streams.clone();
                             let event_tx = event_tx.clone();
                             let config = config.clone();
+                            let tls = tls.clone();
 
                             // Spawn a task for each connection
                             tokio::spawn(async move {
                                 if let Err(e) = Self::handle_connection_async(
-                                    stream, addr, running, streams, event_tx, config
+                                    stream, tls, addr, running, streams, event_tx, config
                                 ).await {
                                     tracing::error!("Connection error from {}: {}", addr, e);
                                 }
@@ -325,6 +435,7 @@ impl RemoteServer {
     /// Handle a single client connection asynchronously
     async fn handle_connection_async(
         stream: TcpStream,
+        tls: Option<TlsAcceptor>,
         addr: SocketAddr,
         running: Arc<AtomicBool>,
         streams: Arc<RwLock<HashMap<String, RemoteStream>>>,
@@ -334,6 +445,34 @@ impl RemoteServer {
         // Set TCP nodelay for lower latency
         stream.set_nodelay(true)?;
 
+        let Some(acceptor) = tls else {
+            return Self::receive_logs(stream, addr, running, streams, event_tx, config).await;
+        };
+
+        let stream =
+            match tokio::time::timeout(Duration::from_secs(30), acceptor.accept(stream)).await {
+                Ok(Ok(stream)) => stream,
+                Ok(Err(e)) => {
+                    tracing::warn!("TLS handshake failed from {}: {}", addr, e);
+                    return Ok(());
+                }
+                Err(_) => {
+                    tracing::warn!("TLS handshake timeout from {}", addr);
+                    return Ok(());
+                }
+            };
+        Self::receive_logs(stream, addr, running, streams, event_tx, config).await
+    }
+
+    /// Run the agent protocol over an established (plain or TLS) stream
+    async fn receive_logs<S: AsyncRead + Unpin>(
+        stream: S,
+        addr: SocketAddr,
+        running: Arc<AtomicBool>,
+        streams: Arc<RwLock<HashMap<String, RemoteStream>>>,
+        event_tx: Sender<ServerEvent>,
+        config: ServerConfig,
+    ) -> Result<()> {
         let mut reader = BufReader::new(stream);
 
         // Wait for handshake with timeout
@@ -354,63 +493,50 @@ impl RemoteServer {
             }
         };
 
-        let project_name = handshake_payload.project_name.clone();
-        tracing::info!("Agent '{}' connected from {}", project_name, addr);
-
-        // Generate unique stream ID
-        // Use agent_id if available, otherwise fall back to ip:port
-        let stream_id = if let Some(ref agent_id) = handshake_payload.agent_id {
-            tracing::info!("Agent ID: {}", agent_id);
-            format!("{}@{}", project_name, agent_id)
-        } else {
+        // Reject before any stream or cache file is created
+        if let Err(e) = handshake_payload.authorize(config.auth_token.as_deref()) {
             tracing::warn!(
-                "No agent ID provided, using ip:port (may cause duplicates on reconnect)"
+                "Rejected agent '{}' from {}: {}",
+                handshake_payload.project_name,
+                addr,
+                e
             );
-            format!("{}@{}:{}", project_name, addr.ip(), addr.port())
-        };
-
-        // Setup cache file
-        // Use agent_id in filename if available, so reconnections write to the same file
-        let cache_path = if let Some(ref agent_id) = handshake_payload.agent_id {
-            config.cache_dir.join(format!(
-                "{}_{}.log",
-                sanitize_filename(&project_name),
-                agent_id
-            ))
-        } else {
-            // Fallback for old agents without agent_id
-            config.cache_dir.join(format!(
-                "{}_{}_{}.log",
-                sanitize_filename(&project_name),
-                addr.ip().to_string().replace(['.', ':'], "_"),
-                addr.port()
-            ))
-        };
+            let _ = event_tx.send(ServerEvent::Error(format!(
+                "{} ({}@{})",
+                e, handshake_payload.project_name, addr
+            )));
+            return Ok(());
+        }
 
-        let cache_file = OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&cache_path)
-            .context("Failed to open cache file")?;
+        let project_name = handshake_payload.project_name.clone();
+        tracing::info!("Agent '{}' connected from {}", project_name, addr);
 
-        let mut writer = std::io::BufWriter::new(cache_file);
+        match &handshake_payload.agent_id {
+            Some(agent_id) => tracing::info!("Agent ID: {}", agent_id),
+            None => tracing::warn!("No agent ID provided, matching reconnects by project and IP"),
+        }
 
         // Register or update stream
-        let is_reconnection = {
+        let cancel = CancellationToken::new();
+        let (stream_id, cache_path, cache_lock, resumed_bytes) = {
             let mut streams_guard = streams.write().unwrap();
-            let is_reconnection = streams_guard.contains_key(&stream_id);
+            let (stream_id, cache_path) =
+                resolve_stream(&config, &streams_guard, &handshake_payload, addr);
 
-            if let Some(existing_stream) = streams_guard.get_mut(&stream_id) {
-                // Reconnection: update existing stream
+            let resumed_bytes = if let Some(existing_stream) = streams_guard.get_mut(&stream_id) {
+                // Reconnection: resume the existing stream and its cache file
                 tracing::info!(
                     "Agent '{}' reconnected (stream_id: {})",
                     project_name,
                     stream_id
                 );
+                // The old connection may still be open; it stops writing
+                std::mem::replace(&mut existing_stream.cancel, cancel.clone()).cancel();
                 existing_stream.status = ConnectionStatus::Online;
                 existing_stream.last_activity = Instant::now();
                 existing_stream.remote_addr = addr;
-                // Keep existing bytes_received
+                existing_stream.metadata = handshake_payload.metadata.clone();
+                Some(existing_stream.bytes_received)
             } else {
                 // New connection: create new stream
                 tracing::info!(
@@ -428,15 +554,31 @@ impl RemoteServer {
                         last_activity: Instant::now(),
                         remote_addr: addr,
                         bytes_received: 0,
+                        metadata: handshake_payload.metadata.clone(),
+                        cancel: cancel.clone(),
+                        cache_lock: Arc::default(),
                     },
                 );
-            }
+                None
+            };
 
-            is_reconnection
+            let cache_lock = streams_guard[&stream_id].cache_lock.clone();
+            (stream_id, cache_path, cache_lock, resumed_bytes)
         };
-
-        // Only send AgentConnected event for new connections, not reconnections
-        if !is_reconnection {
+        // Wait for a cancelled connection of the stream to close the file
+        let _cache_guard = cache_lock.lock_owned().await;
+        let writer = open_cache_writer(&cache_path)?;
+        // Size of the cache file, checked against the limit as data arrives
+        let mut cache_size = writer.get_ref().metadata().map_or(0, |m| m.len());
+        // Closed while the cache file is trimmed, and for good if reopening it failed
+        let mut writer = Some(writer);
+
+        if resumed_bytes.is_some() {
+            let _ = event_tx.send(ServerEvent::AgentReconnected {
+                project_name: project_name.clone(),
+                stream_id: stream_id.clone(),
+            });
+        } else {
             let _ = event_tx.send(ServerEvent::AgentConnected {
                 project_name: project_name.clone(),
                 stream_id: stream_id.clone(),
@@ -446,7 +588,9 @@ impl RemoteServer {
         }
 
         // Main receive loop
-        let mut total_bytes = 0u64;
+        let mut last_seen = Instant::now();
+        let read_wait = config.read_wait(&handshake_payload);
+        let mut total_bytes = resumed_bytes.unwrap_or(0);
         let mut last_flush = Instant::now();
         let mut last_stream_update = Instant::now();
         let flush_interval = Duration::from_millis(100);
@@ -456,19 +600,29 @@ impl RemoteServer {
 
         while running.load(Ordering::Relaxed) {
             // Read with timeout to allow checking running flag periodically
-            let frame_result =
-                tokio::time::timeout(config.read_timeout, Self::read_frame_async(&mut reader))
-                    .await;
+            let frame_result = tokio::select! {
+                _ = cancel.cancelled() => {
+                    tracing::info!("Agent '{}' resumed on another connection", project_name);
+                    break;
+                }
+                result = tokio::time::timeout(read_wait, Self::read_frame_async(&mut reader)) => {
+                    result
+                }
+            };
 
             match frame_result {
                 Ok(Ok(frame)) => {
+                    last_seen = Instant::now();
                     match frame.message_type {
                         MessageType::LogData => {
                             let data_len = frame.payload.len();
                             tracing::debug!("Received {} bytes from '{}'", data_len, project_name);
 
                             // Write to cache file
-                            if let Err(e) = writer.write_all(&frame.payload) {
+                            let Some(out) = writer.as_mut() else {
+                                break;
+                            };
+                            if let Err(e) = out.write_all(&frame.payload) {
                                 tracing::error!("Failed to write to cache: {}", e);
                                 break;
                             }
@@ -476,7 +630,7 @@ impl RemoteServer {
                             // Flush periodically
                             let now = Instant::now();
                             if now.duration_since(last_flush) >= flush_interval {
-                                if let Err(e) = writer.flush() {
+                                if let Err(e) = out.flush() {
                                     tracing::error!("Failed to flush cache: {}", e);
                                     break;
                                 }
@@ -484,6 +638,50 @@ impl RemoteServer {
                             }
 
                             total_bytes += data_len as u64;
+                            cache_size += data_len as u64;
+
+                            // Drop the oldest data once the file outgrows its limit
+                            if config.max_cache_bytes > 0 && cache_size > config.max_cache_bytes {
+                                if let Err(e) = out.flush() {
+                                    tracing::error!("Failed to flush cache: {}", e);
+                                    break;
+                                }
+                                // An open file can't be replaced on Windows
+                                writer = None;
+                                let keep = config.max_cache_bytes / 4 * 3;
+                                let path = cache_path.clone();
+                                let trimmed = tokio::task::spawn_blocking(move || {
+                                    trim_cache_file(&path, keep)
+                                })
+                                .await
+                                .context("Cache trim task failed")
+                                .and_then(|trimmed| trimmed);
+                                match trimmed {
+                                    Ok(bytes_removed) => {
+                                        cache_size = cache_size.saturating_sub(bytes_removed);
+                                        let _ = event_tx.send(ServerEvent::CacheTrimmed {
+                                            project_name: project_name.clone(),
+                                            stream_id: stream_id.clone(),
+                                            bytes_removed,
+                                        });
+                                    }
+                                    Err(e) => {
+                                        tracing::error!("Failed to trim cache: {:#}", e);
+                                        let _ =
+                                            event_tx.send(ServerEvent::Error(format!("{:#}", e)));
+                                        // Try again once another limit's worth has arrived
+                                        cache_size = 0;
+                                    }
+                                }
+                                // The trimmed file replaced the one being written
+                                match open_cache_writer(&cache_path) {
+                                    Ok(reopened) => writer = Some(reopened),
+                                    Err(e) => {
+                                        tracing::error!("Failed to reopen cache: {:#}", e);
+                                        break;
+                                    }
+                                }
+                            }
 
                             // Update stream info periodically
                             if now.duration_since(last_stream_update) >= stream_update_interval {
@@ -496,7 +694,7 @@ impl RemoteServer {
                             }
                         }
                         MessageType::Keepalive => {
-                            // Just continue the loop
+                            // Heartbeat; only proves the agent is alive
                         }
                         MessageType::Handshake => {
                             // Ignore duplicate handshakes
@@ -515,33 +713,53 @@ impl RemoteServer {
                 }
                 Err(_) => {
                     // Timeout - flush any pending data and continue
-                    let _ = writer.flush();
+                    if let Some(out) = writer.as_mut() {
+                        let _ = out.flush();
+                    }
+                    if config.is_stale(&handshake_payload, last_seen.elapsed()) {
+                        tracing::warn!(
+                            "Agent '{}' sent nothing for {:?}, treating it as gone",
+                            project_name,
+                            last_seen.elapsed()
+                        );
+                        break;
+                    }
                     continue;
                 }
             }
         }
 
         // Final flush before closing
-        let _ = writer.flush();
+        if let Some(out) = writer.as_mut() {
+            let _ = out.flush();
+        }
 
-        // Mark as offline
-        {
+        // Mark as offline, unless the agent already reconnected on a new socket
+        let disconnected = {
             let mut streams_guard = streams.write().unwrap();
-            if let Some(stream) = streams_guard.get_mut(&stream_id) {
-                stream.status = ConnectionStatus::Offline;
+            match streams_guard.get_mut(&stream_id) {
+                Some(stream) if stream.remote_addr == addr => {
+                    stream.status = ConnectionStatus::Offline;
+                    true
+                }
+                _ => false,
             }
-        }
+        };
 
-        let _ = event_tx.send(ServerEvent::AgentDisconnected {
-            project_name,
-            stream_id,
-        });
+        if disconnected {
+            let _ = event_tx.send(ServerEvent::AgentDisconnected {
+                project_name,
+                stream_id,
+            });
+        }
 
         Ok(())
     }
 
     /// Read a frame asynchronously
-    async fn read_frame_async(reader: &mut BufReader<TcpStream>) -> Result<Frame, ProtocolError> {
+    async fn read_frame_async<S: AsyncRead + Unpin>(
+        reader: &mut BufReader<S>,
+    ) -> Result<Frame, ProtocolError> {
         // Read length (4 bytes, big-endian)
         let mut len_buf = [0u8; 4];
         reader
@@ -586,8 +804,8 @@ impl RemoteServer {
     }
 
     /// Wait for and process handshake frame asynchronously
-    async fn wait_for_handshake_async(
-        reader: &mut BufReader<TcpStream>,
+    async fn wait_for_handshake_async<S: AsyncRead + Unpin>(
+        reader: &mut BufReader<S>,
     ) -> Result<crate::protocol::HandshakePayload, ProtocolError> {
         let frame = Self::read_frame_async(reader).await?;
 
@@ -607,6 +825,127 @@ impl Drop for RemoteServer {
     }
 }
 
+/// Build a TLS acceptor from a PEM certificate chain and private key
+fn load_tls_acceptor(cert_path: &Path, key_path: &Path) -> Result<TlsAcceptor> {
+    let cert_file = File::open(cert_path)
+        .with_context(|| format!("Failed to open TLS certificate {}", cert_path.display()))?;
+    let certs = rustls_pemfile::certs(&mut std::io::BufReader::new(cert_file))
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to parse TLS certificate")?;
+    if certs.is_empty() {
+        anyhow::bail!("No certificate found in {}", cert_path.display());
+    }
+
+    let key_file = File::open(key_path)
+        .with_context(|| format!("Failed to open TLS key {}", key_path.display()))?;
+    let key = rustls_pemfile::private_key(&mut std::io::BufReader::new(key_file))
+        .context("Failed to parse TLS key")?
+        .with_context(|| format!("No private key found in {}", key_path.display()))?;
+
+    // Name the provider explicitly: the process-wide default is ambiguous
+    // when other dependencies enable another crypto backend
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let config = rustls::ServerConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .context("TLS provider does not support the default protocol versions")?
+        .with_no_client_auth()
+        .with_single_cert(certs, key)
+        .context("TLS certificate and key do not match")?;
+    Ok(TlsAcceptor::from(Arc::new(config)))
+}
+
+/// Stream id and cache file for a connecting agent
+///
+/// With `reuse_existing` a returning agent resumes its earlier stream: the
+/// one with its agent id, or for agents without one, the latest offline
+/// stream of the same project from the same IP. Otherwise each connection
+/// gets a stream of its own. A resumed stream that is still online has its
+/// old connection cancelled by the caller.
+fn resolve_stream(
+    config: &ServerConfig,
+    streams: &HashMap<String, RemoteStream>,
+    handshake: &HandshakePayload,
+    addr: SocketAddr,
+) -> (String, PathBuf) {
+    let project_name = &handshake.project_name;
+    if config.reuse_existing {
+        if let Some(agent_id) = &handshake.agent_id {
+            let cache_name = format!("{}_{}.log", sanitize_filename(project_name), agent_id);
+            return (
+                format!("{}@{}", project_name, agent_id),
+                config.cache_dir.join(cache_name),
+            );
+        }
+        let previous = streams
+            .values()
+            .filter(|s| s.status == ConnectionStatus::Offline)
+            .filter(|s| s.project_name == *project_name && s.remote_addr.ip() == addr.ip())
+            .max_by_key(|s| s.last_activity);
+        if let Some(stream) = previous {
+            return (stream.stream_id.clone(), stream.cache_path.clone());
+        }
+    }
+
+    let cache_name = format!(
+        "{}_{}_{}.log",
+        sanitize_filename(project_name),
+        addr.ip().to_string().replace(['.', ':'], "_"),
+        addr.port()
+    );
+    (
+        format!("{}@{}:{}", project_name, addr.ip(), addr.port()),
+        config.cache_dir.join(cache_name),
+    )
+}
+
+/// Open a stream's cache file for appending
+fn open_cache_writer(path: &Path) -> Result<std::io::BufWriter<File>> {
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .context("Failed to open cache file")?;
+    Ok(std::io::BufWriter::new(file))
+}
+
+/// Drop the oldest bytes of a cache file so at most `keep` bytes remain,
+/// starting on a line boundary. Returns the number of bytes removed.
+fn trim_cache_file(path: &Path, keep: u64) -> Result<u64> {
+    let mut file = File::open(path).context("Failed to open cache file")?;
+    let len = file
+        .metadata()
+        .context("Failed to read cache file size")?
+        .len();
+    if len <= keep {
+        return Ok(0);
+    }
+
+    // Start reading one byte early so a cut right after a newline keeps the next line
+    let start = len - keep - 1;
+    file.seek(SeekFrom::Start(start))
+        .context("Failed to seek in cache file")?;
+    let mut reader = std::io::BufReader::new(file);
+    let skipped = reader
+        .read_until(b'\n', &mut Vec::new())
+        .context("Failed to read cache file")? as u64;
+
+    let trimmed_path = path.with_extension("log.trim");
+    let mut trimmed = File::create(&trimmed_path).context("Failed to create trimmed cache")?;
+    std::io::copy(&mut reader, &mut trimmed).context("Failed to write trimmed cache")?;
+    drop(trimmed);
+    fs::rename(&trimmed_path, path).context("Failed to replace cache file")?;
+
+    Ok(start + skipped)
+}
+
+/// Generate a random token for agent authentication: 128 bits from the
+/// OS random source, hex encoded
+pub fn generate_auth_token() -> Result<String> {
+    let mut bytes = [0u8; 16];
+    getrandom::fill(&mut bytes).map_err(|e| anyhow::anyhow!("No OS random source: {}", e))?;
+    Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
 /// Sanitize a filename to be safe for filesystem
 fn sanitize_filename(name: &str) -> String {
     name.chars()
@@ -674,6 +1013,14 @@ pub fn get_local_ip_addresses() -> Vec<String> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_generate_auth_token() {
+        let token = generate_auth_token().unwrap();
+        assert_eq!(token.len(), 32);
+        assert!(token.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(token, generate_auth_token().unwrap());
+    }
+
     #[test]
     fn test_sanitize_filename() {
         assert_eq!(sanitize_filename("test-project"), "test-project");
@@ -682,6 +1029,95 @@ mod tests {
         assert_eq!(sanitize_filename("test.project"), "test_project");
     }
 
+    #[test]
+    fn test_trim_cache_file_keeps_whole_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app_agent.log");
+        fs::write(&path, "first line\nsecond line\nthird\n").unwrap();
+
+        // The cut falls inside "second line", which is dropped whole
+        assert_eq!(trim_cache_file(&path, 10).unwrap(), 23);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "third\n");
+
+        // A cut on a line boundary keeps the line after it
+        fs::write(&path, "first line\nsecond line\nthird\n").unwrap();
+        assert_eq!(trim_cache_file(&path, 18).unwrap(), 11);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "second line\nthird\n");
+
+        // Files under the limit are left alone
+        assert_eq!(trim_cache_file(&path, 100).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_returning_agent_resumes_its_stream() {
+        let config = ServerConfig {
+            cache_dir: PathBuf::from("/cache"),
+            ..Default::default()
+        };
+        let first: SocketAddr = "10.0.0.5:40001".parse().unwrap();
+        let again: SocketAddr = "10.0.0.5:40002".parse().unwrap();
+        let handshake = HandshakePayload::new("api");
+
+        let (stream_id, cache_path) = resolve_stream(&config, &HashMap::new(), &handshake, first);
+        assert_eq!(stream_id, "api@10.0.0.5:40001");
+        let mut streams = HashMap::new();
+        streams.insert(
+            stream_id.clone(),
+            RemoteStream {
+                stream_id: stream_id.clone(),
+                project_name: "api".to_string(),
+                status: ConnectionStatus::Offline,
+                cache_path: cache_path.clone(),
+                last_activity: Instant::now(),
+                remote_addr: first,
+                bytes_received: 42,
+                metadata: AgentMetadata::default(),
+                cancel: CancellationToken::new(),
+                cache_lock: Arc::default(),
+            },
+        );
+
+        // Same project from the same host picks up the offline stream
+        assert_eq!(
+            resolve_stream(&config, &streams, &handshake, again),
+            (stream_id.clone(), cache_path.clone())
+        );
+
+        // Opting out gives every connection a stream of its own
+        let separate = ServerConfig {
+            reuse_existing: false,
+            ..config
+        };
+        let (new_id, new_path) = resolve_stream(&separate, &streams, &handshake, again);
+        assert_eq!(new_id, "api@10.0.0.5:40002");
+        assert_ne!(new_path, cache_path);
+    }
+
+    #[test]
+    fn test_stale_timeout() {
+        let config = ServerConfig {
+            read_timeout: Duration::from_secs(60),
+            stale_timeout: Some(Duration::from_secs(30)),
+            ..Default::default()
+        };
+        let agent = HandshakePayload::new("api").with_heartbeat();
+        assert_eq!(config.read_wait(&agent), Duration::from_secs(30));
+        assert!(!config.is_stale(&agent, Duration::from_secs(29)));
+        assert!(config.is_stale(&agent, Duration::from_secs(30)));
+
+        // Agents that don't send heartbeats may stay quiet
+        let quiet = HandshakePayload::new("api");
+        assert_eq!(config.read_wait(&quiet), Duration::from_secs(60));
+        assert!(!config.is_stale(&quiet, Duration::from_secs(3600)));
+
+        let never = ServerConfig {
+            stale_timeout: None,
+            ..config
+        };
+        assert_eq!(never.read_wait(&agent), Duration::from_secs(60));
+        assert!(!never.is_stale(&agent, Duration::from_secs(3600)));
+    }
+
     #[test]
     fn test_get_local_ip_addresses() {
         let ips = get_local_ip_addresses();
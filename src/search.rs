@@ -1,9 +1,22 @@
 //! Search and filter functionality
 
+use crate::correlation::CorrelationFilter;
 use crate::log_buffer::LogBuffer;
 use crate::log_entry::LogLevel;
+use chrono::{DateTime, Local, NaiveDateTime};
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+/// Most fuzzy matches kept (the best scoring ones) so huge buffers stay responsive
+pub const FUZZY_MAX_RESULTS: usize = 10_000;
+
+/// Most lines a search scans per step; bigger buffers finish over later frames
+const SEARCH_STEP_LINES: usize = 200_000;
+
+/// Time a search step may take before yielding to the next frame
+const SEARCH_STEP_BUDGET: Duration = Duration::from_millis(8);
 
 /// Search configuration
 #[derive(Debug, Clone, Default)]
@@ -16,6 +29,8 @@ pub struct SearchConfig {
     pub use_regex: bool,
     /// Whole word match
     pub whole_word: bool,
+    /// Fuzzy (subsequence) match, ranked by score
+    pub fuzzy: bool,
 }
 
 impl SearchConfig {
@@ -66,6 +81,20 @@ pub struct SearchMatch {
     /// Start positions of matches within the line
     #[allow(dead_code)]
     pub match_positions: Vec<(usize, usize)>,
+    /// Fuzzy match score (0 for exact searches)
+    pub score: i64,
+}
+
+/// A search still scanning the buffer
+struct PendingSearch {
+    /// Query regex, or None for a fuzzy search
+    regex: Option<Regex>,
+    /// Buffer lines scanned so far
+    scanned: usize,
+    /// Buffer length at the last step
+    total: usize,
+    /// First line number when scanning started, to notice shifted indices
+    first_line: usize,
 }
 
 /// Search engine for log content
@@ -78,6 +107,10 @@ pub struct SearchEngine {
     current_index: Option<usize>,
     /// Whether results need refresh
     dirty: bool,
+    /// Search over a large buffer that hasn't finished yet
+    pending: Option<PendingSearch>,
+    /// Lines scanned per step
+    step_lines: usize,
 }
 
 impl SearchEngine {
@@ -88,6 +121,8 @@ impl SearchEngine {
             results: Vec::new(),
             current_index: None,
             dirty: true,
+            pending: None,
+            step_lines: SEARCH_STEP_LINES,
         }
     }
 
@@ -124,36 +159,123 @@ impl SearchEngine {
         }
     }
 
-    /// Execute search on buffer
+    /// Set fuzzy mode (results are then ordered by score, best first)
+    pub fn set_fuzzy(&mut self, fuzzy: bool) {
+        if self.config.fuzzy != fuzzy {
+            self.config.fuzzy = fuzzy;
+            self.dirty = true;
+            self.current_index = None;
+        }
+    }
+
+    /// Execute search on buffer, starting again at the first match.
+    /// Large buffers are scanned in steps, see [`Self::continue_search`].
     pub fn search(&mut self, buffer: &LogBuffer) {
         self.results.clear();
+        self.current_index = None;
+        // Cancels a scan still running for the previous query
+        self.pending = None;
+        self.dirty = false;
 
         if !self.config.is_active() {
-            self.dirty = false;
             return;
         }
 
-        let regex = match self.config.build_regex() {
-            Some(r) => r,
-            None => {
-                self.dirty = false;
-                return;
+        let regex = if self.config.fuzzy {
+            None
+        } else {
+            match self.config.build_regex() {
+                Some(r) => Some(r),
+                None => return,
             }
         };
 
-        for (idx, entry) in buffer.iter().enumerate() {
-            let matches: Vec<(usize, usize)> = regex
-                .find_iter(&entry.content)
-                .map(|m| (m.start(), m.end()))
-                .collect();
+        self.pending = Some(PendingSearch {
+            regex,
+            scanned: 0,
+            total: buffer.len(),
+            first_line: buffer.first_line_number(),
+        });
+        self.continue_search(buffer);
+    }
 
-            if !matches.is_empty() {
-                self.results.push(SearchMatch {
-                    buffer_index: idx,
-                    line_number: entry.line_number,
-                    match_positions: matches,
-                });
+    /// Scan the next step of an unfinished search, for at most
+    /// [`SEARCH_STEP_BUDGET`], streaming its matches into the results.
+    /// Returns whether it is still unfinished afterwards.
+    pub fn continue_search(&mut self, buffer: &LogBuffer) -> bool {
+        let Some(pending) = self.pending.as_mut() else {
+            return false;
+        };
+        let first_line = buffer.first_line_number();
+        if first_line > pending.first_line {
+            // Lines were trimmed from the front; shift what was found so far
+            let dropped = first_line - pending.first_line;
+            let selected = self.current_index.and_then(|i| self.results.get(i));
+            let selected = selected.map(|m| m.buffer_index);
+            self.results.retain(|m| m.buffer_index >= dropped);
+            for m in &mut self.results {
+                m.buffer_index -= dropped;
             }
+            self.current_index = selected.and_then(|selected| {
+                let selected = selected.checked_sub(dropped)?;
+                self.results.iter().position(|m| m.buffer_index == selected)
+            });
+            pending.scanned = pending.scanned.saturating_sub(dropped);
+        } else if first_line < pending.first_line {
+            // Lines were prepended; start over
+            pending.scanned = 0;
+            self.results.clear();
+            self.current_index = None;
+        }
+        pending.first_line = first_line;
+
+        let deadline = Instant::now() + SEARCH_STEP_BUDGET;
+        let mut fuzzy = Vec::new();
+        let mut idx = pending.scanned;
+        for entry in buffer.iter().skip(idx).take(self.step_lines) {
+            match &pending.regex {
+                Some(regex) => {
+                    let matches: Vec<(usize, usize)> = regex
+                        .find_iter(&entry.content)
+                        .map(|m| (m.start(), m.end()))
+                        .collect();
+
+                    if !matches.is_empty() {
+                        self.results.push(SearchMatch {
+                            buffer_index: idx,
+                            line_number: entry.line_number,
+                            match_positions: matches,
+                            score: 0,
+                        });
+                    }
+                }
+                None => {
+                    if let Some((score, positions)) = fuzzy_line_match(
+                        &self.config.query,
+                        &entry.content,
+                        self.config.case_sensitive,
+                    ) {
+                        fuzzy.push(SearchMatch {
+                            buffer_index: idx,
+                            line_number: entry.line_number,
+                            match_positions: positions,
+                            score,
+                        });
+                    }
+                }
+            }
+            idx += 1;
+            if idx % 1024 == 0 && Instant::now() >= deadline {
+                break;
+            }
+        }
+        pending.scanned = idx;
+        pending.total = buffer.len();
+        if pending.scanned >= pending.total {
+            self.pending = None;
+        }
+        if !fuzzy.is_empty() {
+            self.rank_fuzzy(fuzzy);
         }
 
         // Set current index to first result
@@ -161,7 +283,37 @@ impl SearchEngine {
             self.current_index = Some(0);
         }
 
-        self.dirty = false;
+        self.pending.is_some()
+    }
+
+    /// Share of the buffer scanned so far while a search is unfinished
+    pub fn search_progress(&self) -> Option<f32> {
+        self.pending
+            .as_ref()
+            .map(|pending| pending.scanned as f32 / pending.total.max(1) as f32)
+    }
+
+    /// Merge fuzzy matches into the results, ranked best first and keeping
+    /// the best [`FUZZY_MAX_RESULTS`]. Equal scores keep file order, and the
+    /// current match stays selected while it is kept.
+    fn rank_fuzzy(&mut self, found: Vec<SearchMatch>) {
+        let selected = self.current_match().map(|m| m.buffer_index);
+        let by_rank = |a: &SearchMatch, b: &SearchMatch| {
+            b.score
+                .cmp(&a.score)
+                .then(a.buffer_index.cmp(&b.buffer_index))
+        };
+
+        self.results.extend(found);
+        if self.results.len() > FUZZY_MAX_RESULTS {
+            self.results
+                .select_nth_unstable_by(FUZZY_MAX_RESULTS - 1, by_rank);
+            self.results.truncate(FUZZY_MAX_RESULTS);
+        }
+        self.results.sort_by(by_rank);
+
+        self.current_index = selected
+            .and_then(|selected| self.results.iter().position(|m| m.buffer_index == selected));
     }
 
     /// Update search if dirty
@@ -260,11 +412,11 @@ impl SearchEngine {
     }
 
     /// Clear search
-    #[allow(dead_code)]
     pub fn clear(&mut self) {
         self.config.query.clear();
         self.results.clear();
         self.current_index = None;
+        self.pending = None;
         self.dirty = false;
     }
 
@@ -289,17 +441,232 @@ pub struct ExcludePattern {
     pub is_regex: bool,
 }
 
+/// Inclusive range of timestamps to show
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeRange {
+    pub start: DateTime<Local>,
+    pub end: DateTime<Local>,
+}
+
+impl TimeRange {
+    /// Range between two wall-clock times, in the terms of the timestamps
+    /// detected in log lines
+    pub fn from_naive(start: NaiveDateTime, end: NaiveDateTime) -> Self {
+        let offset = *Local::now().offset();
+        Self {
+            start: DateTime::from_naive_utc_and_offset(start.min(end), offset),
+            end: DateTime::from_naive_utc_and_offset(start.max(end), offset),
+        }
+    }
+
+    /// The `minutes` up to and including `latest`
+    pub fn last_minutes(latest: NaiveDateTime, minutes: i64) -> Self {
+        Self::from_naive(latest - chrono::Duration::minutes(minutes), latest)
+    }
+
+    /// Whether a time falls within the range
+    pub fn contains(&self, time: DateTime<Local>) -> bool {
+        self.start <= time && time <= self.end
+    }
+}
+
+/// Order of the view by a grok field (column view header)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldSort {
+    /// Field to sort by
+    pub field: String,
+    /// Largest values first
+    pub descending: bool,
+}
+
+impl FieldSort {
+    /// Sort after clicking a field's header: ascending, then descending,
+    /// then back to file order
+    pub fn next(current: Option<&FieldSort>, field: &str) -> Option<FieldSort> {
+        match current {
+            Some(sort) if sort.field == field && sort.descending => None,
+            Some(sort) if sort.field == field => Some(FieldSort {
+                field: field.to_string(),
+                descending: true,
+            }),
+            _ => Some(FieldSort {
+                field: field.to_string(),
+                descending: false,
+            }),
+        }
+    }
+}
+
+/// How the conditions of a filter group combine
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum FilterLogic {
+    /// Every condition must match
+    #[default]
+    And,
+    /// Any condition may match
+    Or,
+}
+
+impl FilterLogic {
+    /// Parse "and" / "or" (case-insensitive)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "and" => Some(FilterLogic::And),
+            "or" => Some(FilterLogic::Or),
+            _ => None,
+        }
+    }
+
+    /// Get all logic options
+    pub fn all() -> &'static [FilterLogic] {
+        &[FilterLogic::And, FilterLogic::Or]
+    }
+}
+
+/// What a filter condition tests on a line
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConditionKind {
+    /// The line has exactly this level
+    Level(LogLevel),
+    /// The line contains the text (case-insensitive)
+    Contains(String),
+    /// The line matches the regex (case-insensitive)
+    Regex(String),
+}
+
+/// A single condition of a filter group
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FilterCondition {
+    /// What the condition tests
+    pub kind: ConditionKind,
+    /// Match lines that do not satisfy it instead
+    pub negate: bool,
+}
+
+/// Conditions and nested groups combined with AND or OR
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FilterGroup {
+    /// How the conditions and groups combine
+    pub logic: FilterLogic,
+    /// Conditions of this group
+    pub conditions: Vec<FilterCondition>,
+    /// Nested groups, each counting as one condition
+    pub groups: Vec<FilterGroup>,
+}
+
+impl FilterGroup {
+    /// Whether the group has no conditions at any depth
+    pub fn is_empty(&self) -> bool {
+        self.conditions.is_empty() && self.groups.iter().all(FilterGroup::is_empty)
+    }
+
+    /// Build the matchers, leaving out invalid regexes and empty text so a
+    /// condition being typed doesn't hide every line
+    fn compile(&self) -> CompiledGroup {
+        let conditions = self
+            .conditions
+            .iter()
+            .filter_map(|condition| {
+                let matcher = match &condition.kind {
+                    ConditionKind::Level(level) => Matcher::Level(*level),
+                    ConditionKind::Contains(text) if text.is_empty() => return None,
+                    ConditionKind::Contains(text) => {
+                        Matcher::Regex(Regex::new(&format!("(?i){}", regex::escape(text))).ok()?)
+                    }
+                    ConditionKind::Regex(pattern) if pattern.is_empty() => return None,
+                    ConditionKind::Regex(pattern) => {
+                        Matcher::Regex(Regex::new(&format!("(?i){}", pattern)).ok()?)
+                    }
+                };
+                Some((matcher, condition.negate))
+            })
+            .collect();
+        let groups = self
+            .groups
+            .iter()
+            .filter(|group| !group.is_empty())
+            .map(FilterGroup::compile)
+            .collect();
+        CompiledGroup {
+            logic: self.logic,
+            conditions,
+            groups,
+        }
+    }
+}
+
+/// Condition test ready to run against lines
+enum Matcher {
+    Level(LogLevel),
+    Regex(Regex),
+}
+
+/// A filter group with its regexes built
+struct CompiledGroup {
+    logic: FilterLogic,
+    conditions: Vec<(Matcher, bool)>,
+    groups: Vec<CompiledGroup>,
+}
+
+impl CompiledGroup {
+    /// Whether a line (with its effective level) passes the group; an empty
+    /// group passes everything
+    fn matches(&self, content: &str, level: Option<LogLevel>) -> bool {
+        if self.conditions.is_empty() && self.groups.is_empty() {
+            return true;
+        }
+        let mut results = self
+            .conditions
+            .iter()
+            .map(|(matcher, negate)| {
+                let matched = match matcher {
+                    Matcher::Level(wanted) => level == Some(*wanted),
+                    Matcher::Regex(regex) => regex.is_match(content),
+                };
+                matched != *negate
+            })
+            .chain(
+                self.groups
+                    .iter()
+                    .map(|group| group.matches(content, level)),
+            );
+        match self.logic {
+            FilterLogic::And => results.all(|matched| matched),
+            FilterLogic::Or => results.any(|matched| matched),
+        }
+    }
+}
+
 /// Filter configuration for log levels and other criteria
 #[derive(Debug, Clone)]
 pub struct FilterConfig {
     /// Enabled log levels
     pub levels: HashSet<LogLevel>,
+    /// Only show levels at or above this severity (None = no threshold)
+    pub min_level: Option<LogLevel>,
     /// Text to exclude (inverted filter) - simple strings for backward compatibility
     pub exclude_patterns: Vec<String>,
     /// Advanced exclude patterns with type information
     pub exclude_patterns_advanced: Vec<ExcludePattern>,
     /// Only show bookmarked lines
     pub bookmarks_only: bool,
+    /// Lines of context to show around each matching line (0 = none)
+    pub context_lines: usize,
+    /// Only show lines within this time range; lines without a timestamp
+    /// are hidden unless `keep_untimestamped` (continuation lines always
+    /// follow their block)
+    pub time_range: Option<TimeRange>,
+    /// With a time range, keep lines without a timestamp by having them
+    /// follow the last timestamped line before them
+    pub keep_untimestamped: bool,
+    /// Only show the lines of one correlation (request) id
+    pub correlation: Option<CorrelationFilter>,
+    /// Order the shown lines by a grok field instead of file order
+    pub sort_by: Option<FieldSort>,
+    /// Conditions combined with AND/OR, applied on top of the filters above
+    /// (None = only the filters above)
+    pub expression: Option<FilterGroup>,
 }
 
 impl Default for FilterConfig {
@@ -314,9 +681,16 @@ impl Default for FilterConfig {
 
         Self {
             levels,
+            min_level: None,
             exclude_patterns: Vec::new(),
             exclude_patterns_advanced: Vec::new(),
             bookmarks_only: false,
+            context_lines: 0,
+            time_range: None,
+            keep_untimestamped: true,
+            correlation: None,
+            sort_by: None,
+            expression: None,
         }
     }
 }
@@ -327,6 +701,11 @@ impl FilterConfig {
         self.levels.contains(&level)
     }
 
+    /// Check if a level passes both the per-level toggles and the severity threshold
+    pub fn passes_level(&self, level: LogLevel) -> bool {
+        self.is_level_enabled(level) && self.min_level.is_none_or(|min| level.is_at_least(min))
+    }
+
     /// Toggle a level
     pub fn toggle_level(&mut self, level: LogLevel) {
         if self.levels.contains(&level) {
@@ -338,6 +717,7 @@ impl FilterConfig {
 
     /// Enable all levels
     pub fn enable_all_levels(&mut self) {
+        self.min_level = None;
         self.levels.insert(LogLevel::Trace);
         self.levels.insert(LogLevel::Debug);
         self.levels.insert(LogLevel::Info);
@@ -360,6 +740,14 @@ impl FilterConfig {
         self.levels.insert(LogLevel::Fatal);
     }
 
+    /// Show only errors (Error/Fatal) with surrounding context lines
+    pub fn errors_with_context(&mut self, context_lines: usize) {
+        self.levels.clear();
+        self.levels.insert(LogLevel::Error);
+        self.levels.insert(LogLevel::Fatal);
+        self.context_lines = context_lines;
+    }
+
     /// Add exclude pattern
     pub fn add_exclude(&mut self, pattern: String) {
         if !pattern.is_empty() && !self.exclude_patterns.contains(&pattern) {
@@ -374,12 +762,29 @@ impl FilterConfig {
         }
     }
 
+    /// Whether the filter reads grok fields, so every line needs parsing
+    pub fn needs_grok_fields(&self) -> bool {
+        self.sort_by.is_some()
+            || self
+                .correlation
+                .as_ref()
+                .is_some_and(|c| c.extractor.needs_grok_fields())
+    }
+
     /// Check if any filters are active
     pub fn is_filtering(&self) -> bool {
         self.levels.len() < 6
+            || self.min_level.is_some()
             || !self.exclude_patterns.is_empty()
             || !self.exclude_patterns_advanced.is_empty()
             || self.bookmarks_only
+            || self.time_range.is_some()
+            || self.correlation.is_some()
+            || self.sort_by.is_some()
+            || self
+                .expression
+                .as_ref()
+                .is_some_and(|group| !group.is_empty())
     }
 
     /// Add advanced exclude pattern
@@ -398,6 +803,60 @@ impl FilterConfig {
             self.exclude_patterns_advanced.remove(index);
         }
     }
+
+    /// The parts of the filter that presets and recent contexts keep
+    pub fn to_saved(&self) -> SavedFilter {
+        let mut levels: Vec<LogLevel> = self.levels.iter().copied().collect();
+        levels.sort();
+        let exclude_patterns = self
+            .exclude_patterns
+            .iter()
+            .map(|p| (p.clone(), false))
+            .chain(
+                self.exclude_patterns_advanced
+                    .iter()
+                    .map(|p| (p.pattern.clone(), p.is_regex)),
+            )
+            .collect();
+        SavedFilter {
+            levels,
+            min_level: self.min_level,
+            exclude_patterns,
+            bookmarks_only: self.bookmarks_only,
+            expression: self.expression.clone().filter(|group| !group.is_empty()),
+        }
+    }
+
+    /// Apply a saved filter. Plain patterns go to the simple list (shown in
+    /// the filters panel), regex patterns to the advanced list.
+    pub fn apply_saved(&mut self, saved: &SavedFilter) {
+        self.levels = saved.levels.iter().copied().collect();
+        self.min_level = saved.min_level;
+        self.exclude_patterns.clear();
+        self.exclude_patterns_advanced.clear();
+        for (pattern, is_regex) in &saved.exclude_patterns {
+            if *is_regex {
+                self.add_exclude_advanced(pattern.clone(), true);
+            } else {
+                self.exclude_patterns.push(pattern.clone());
+            }
+        }
+        self.bookmarks_only = saved.bookmarks_only;
+        self.expression = saved.expression.clone();
+    }
+
+    /// Save the filter as a named preset
+    pub fn to_preset(&self, name: String) -> NamedFilter {
+        NamedFilter {
+            name,
+            filter: self.to_saved(),
+        }
+    }
+
+    /// Apply a preset's filter
+    pub fn apply_preset(&mut self, preset: &NamedFilter) {
+        self.apply_saved(&preset.filter);
+    }
 }
 
 /// Combined filter that applies both search and level filtering
@@ -410,6 +869,10 @@ pub struct LogFilter {
     filtered_indices: Vec<usize>,
     /// Whether filter needs refresh
     dirty: bool,
+    /// Filter to restore when the errors-with-context view is toggled off
+    saved_filter: Option<FilterConfig>,
+    /// Line numbers of block parents whose continuation lines are hidden
+    collapsed_blocks: HashSet<usize>,
 }
 
 impl LogFilter {
@@ -419,9 +882,38 @@ impl LogFilter {
             filter: FilterConfig::default(),
             filtered_indices: Vec::new(),
             dirty: true,
+            saved_filter: None,
+            collapsed_blocks: HashSet::new(),
+        }
+    }
+
+    /// Capture the current search and filter, plus the tab's grok pattern
+    pub fn context(&self, grok_pattern: Option<String>) -> AnalysisContext {
+        let search = &self.search.config;
+        AnalysisContext {
+            query: search.query.clone(),
+            case_sensitive: search.case_sensitive,
+            use_regex: search.use_regex,
+            whole_word: search.whole_word,
+            fuzzy: search.fuzzy,
+            filter: self.filter.to_saved(),
+            grok_pattern,
         }
     }
 
+    /// Restore the search and filter of a context (the grok pattern is
+    /// applied by the caller)
+    pub fn apply_context(&mut self, context: &AnalysisContext) {
+        self.search.set_query(context.query.clone());
+        self.search.set_case_sensitive(context.case_sensitive);
+        self.search.set_use_regex(context.use_regex);
+        self.search.set_whole_word(context.whole_word);
+        self.search.set_fuzzy(context.fuzzy);
+
+        self.filter.apply_saved(&context.filter);
+        self.mark_dirty();
+    }
+
     /// Apply filters to buffer and return filtered indices
     pub fn apply(&mut self, buffer: &LogBuffer) -> &[usize] {
         if !self.dirty {
@@ -455,10 +947,50 @@ impl LogFilter {
             })
             .collect();
 
+        let expression = self.filter.expression.as_ref().map(FilterGroup::compile);
+
+        // Whether the last timestamped line was within the time range
+        let mut in_time_range = false;
+        // Line number and level of the block the current line belongs to
+        let mut block = (0, None);
+        // Continuation lines of collapsed blocks, removed after context expansion
+        let mut folded = Vec::new();
+
         for (idx, entry) in buffer.iter().enumerate() {
-            // Level filter
-            if let Some(level) = entry.level {
-                if !self.filter.is_level_enabled(level) {
+            if !entry.continuation {
+                block = (entry.line_number, entry.level);
+            } else if self.collapsed_blocks.contains(&block.0) {
+                folded.push(idx);
+                continue;
+            }
+
+            // Time range filter (checked first so every timestamp is seen)
+            if let Some(range) = &self.filter.time_range {
+                if let Some(timestamp) = buffer.entry_timestamp(entry) {
+                    in_time_range = range.contains(timestamp);
+                } else if !entry.continuation && !self.filter.keep_untimestamped {
+                    continue;
+                }
+                if !in_time_range {
+                    continue;
+                }
+            }
+
+            // Correlation id filter
+            if let Some(correlation) = &self.filter.correlation {
+                if !correlation.matches(entry) {
+                    continue;
+                }
+            }
+
+            // Level filter (continuation lines take their block's level)
+            let level = if entry.continuation {
+                block.1
+            } else {
+                entry.level
+            };
+            if let Some(level) = level {
+                if !self.filter.passes_level(level) {
                     continue;
                 }
             }
@@ -482,8 +1014,20 @@ impl LogFilter {
                 continue;
             }
 
+            // AND/OR condition groups
+            if let Some(expression) = &expression {
+                if !expression.matches(&entry.content, level) {
+                    continue;
+                }
+            }
+
             // Search filter (if active)
-            if self.search.is_active() {
+            if self.search.is_active() && self.search.config.fuzzy {
+                let config = &self.search.config;
+                if fuzzy_line_match(&config.query, &entry.content, config.case_sensitive).is_none() {
+                    continue;
+                }
+            } else if self.search.is_active() {
                 if let Some(regex) = self.search.config.build_regex() {
                     if !regex.is_match(&entry.content) {
                         continue;
@@ -494,6 +1038,23 @@ impl LogFilter {
             self.filtered_indices.push(idx);
         }
 
+        if self.filter.context_lines > 0 && self.is_filtering() {
+            self.filtered_indices = expand_with_context(
+                &self.filtered_indices,
+                self.filter.context_lines,
+                buffer.len(),
+            );
+        }
+
+        if !folded.is_empty() {
+            self.filtered_indices
+                .retain(|idx| folded.binary_search(idx).is_err());
+        }
+
+        if let Some(sort) = &self.filter.sort_by {
+            sort_by_field(&mut self.filtered_indices, buffer, sort);
+        }
+
         self.dirty = false;
         &self.filtered_indices
     }
@@ -504,6 +1065,12 @@ impl LogFilter {
         self.search.mark_dirty();
     }
 
+    /// Clear the search query along with its matches and highlights
+    pub fn clear_search(&mut self) {
+        self.search.clear();
+        self.mark_dirty();
+    }
+
     /// Get filtered line count
     #[allow(dead_code)]
     pub fn filtered_count(&self) -> usize {
@@ -518,19 +1085,272 @@ impl LogFilter {
 
     /// Check if filtering is active
     pub fn is_filtering(&self) -> bool {
-        self.filter.is_filtering() || self.search.is_active()
+        self.filter.is_filtering() || self.search.is_active() || !self.collapsed_blocks.is_empty()
+    }
+
+    /// Collapse or expand the continuation lines of the block starting at a line
+    pub fn toggle_block(&mut self, line_number: usize) {
+        if !self.collapsed_blocks.remove(&line_number) {
+            self.collapsed_blocks.insert(line_number);
+        }
+        self.mark_dirty();
+    }
+
+    /// Line numbers of the collapsed blocks
+    pub fn collapsed_blocks(&self) -> &HashSet<usize> {
+        &self.collapsed_blocks
+    }
+
+    /// Toggle the errors-with-context view, restoring the previous filter when turned off
+    /// Returns whether the view is now active
+    pub fn toggle_errors_with_context(&mut self, context_lines: usize) -> bool {
+        if let Some(saved) = self.saved_filter.take() {
+            self.filter = saved;
+        } else {
+            self.saved_filter = Some(self.filter.clone());
+            self.filter.errors_with_context(context_lines);
+        }
+        self.mark_dirty();
+        self.saved_filter.is_some()
+    }
+
+    /// Check if the errors-with-context view is active
+    pub fn is_errors_with_context(&self) -> bool {
+        self.saved_filter.is_some()
     }
 }
 
+/// A search, filter and grok pattern applied together, kept in the recent
+/// contexts list so a whole analysis setup can be re-applied at once
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AnalysisContext {
+    /// Search query
+    pub query: String,
+    /// Case sensitive search
+    pub case_sensitive: bool,
+    /// Search query is a regex
+    pub use_regex: bool,
+    /// Whole word search
+    pub whole_word: bool,
+    /// Fuzzy search
+    pub fuzzy: bool,
+    /// Level, exclude, bookmark and expression filters
+    #[serde(flatten)]
+    pub filter: SavedFilter,
+    /// Grok pattern name (custom name or builtin display name)
+    pub grok_pattern: Option<String>,
+}
+
+impl AnalysisContext {
+    /// Whether the context changes nothing compared to a fresh tab
+    pub fn is_empty(&self) -> bool {
+        self.query.is_empty() && self.filter.is_empty() && self.grok_pattern.is_none()
+    }
+
+    /// Short description for menus
+    pub fn label(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.query.is_empty() {
+            let fuzzy = if self.fuzzy { "~" } else { "" };
+            parts.push(format!("{}\"{}\"", fuzzy, self.query));
+        }
+        let filter = &self.filter;
+        if let Some(level) = filter.min_level {
+            parts.push(format!("{}+", level.as_str()));
+        } else if filter.levels.len() < LogLevel::all().len() {
+            let names: Vec<&str> = filter.levels.iter().map(|l| l.as_str()).collect();
+            parts.push(names.join("/"));
+        }
+        if !filter.exclude_patterns.is_empty() {
+            let excluded: Vec<&str> = filter
+                .exclude_patterns
+                .iter()
+                .map(|(p, _)| p.as_str())
+                .collect();
+            parts.push(format!("-{}", excluded.join(" -")));
+        }
+        if filter.expression.is_some() {
+            parts.push("ƒ".to_string());
+        }
+        if filter.bookmarks_only {
+            parts.push("★".to_string());
+        }
+        if let Some(pattern) = &self.grok_pattern {
+            parts.push(format!("[{}]", pattern));
+        }
+        parts.join(" · ")
+    }
+}
+
+/// The filter settings kept by presets and recent analysis contexts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SavedFilter {
+    /// Enabled log levels
+    pub levels: Vec<LogLevel>,
+    /// Minimum severity shown
+    pub min_level: Option<LogLevel>,
+    /// Excluded text, with whether each pattern is a regex
+    pub exclude_patterns: Vec<(String, bool)>,
+    /// Only show bookmarked lines
+    pub bookmarks_only: bool,
+    /// Filter expression of the advanced filters panel
+    pub expression: Option<FilterGroup>,
+}
+
+impl Default for SavedFilter {
+    fn default() -> Self {
+        FilterConfig::default().to_saved()
+    }
+}
+
+impl SavedFilter {
+    /// Whether the filter lets every line through
+    pub fn is_empty(&self) -> bool {
+        self.levels.len() == LogLevel::all().len()
+            && self.min_level.is_none()
+            && self.exclude_patterns.is_empty()
+            && !self.bookmarks_only
+            && self.expression.is_none()
+    }
+}
+
+/// A filter saved under a name so it can be re-applied later
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NamedFilter {
+    /// Preset name
+    pub name: String,
+    /// The saved filter
+    #[serde(flatten)]
+    pub filter: SavedFilter,
+}
+
+/// Expand sorted matching indices with `context` lines before and after each match
+pub fn expand_with_context(indices: &[usize], context: usize, total: usize) -> Vec<usize> {
+    let mut expanded = Vec::with_capacity(indices.len() * (context * 2 + 1));
+    for &idx in indices {
+        let start = idx.saturating_sub(context).max(expanded.last().map_or(0, |&last| last + 1));
+        let end = (idx + context + 1).min(total);
+        expanded.extend(start..end);
+    }
+    expanded
+}
+
+/// Order indices by a grok field value, numerically when both values are
+/// numbers. Lines without the field keep their order at the end.
+pub fn sort_by_field(indices: &mut [usize], buffer: &LogBuffer, sort: &FieldSort) {
+    use std::cmp::Ordering;
+
+    let value = |idx: usize| {
+        buffer
+            .get(idx)
+            .and_then(|e| e.grok_fields.as_ref())
+            .and_then(|fields| fields.get(&sort.field))
+    };
+    indices.sort_by(|&a, &b| match (value(a), value(b)) {
+        (Some(a), Some(b)) => {
+            let ordering = match (a.trim().parse::<f64>(), b.trim().parse::<f64>()) {
+                (Ok(a), Ok(b)) => a.total_cmp(&b),
+                _ => a.cmp(b),
+            };
+            if sort.descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        }
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    });
+}
+
 impl Default for LogFilter {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// Score a fuzzy (subsequence) match of `pattern` against `candidate`.
+/// Returns `None` if the pattern characters do not all appear in order.
+/// Matches at word boundaries and consecutive runs score higher, gaps
+/// lower the score. Matching is case-insensitive.
+pub fn fuzzy_score(pattern: &str, candidate: &str) -> Option<i64> {
+    fuzzy_match(pattern, candidate, false).map(|(score, _)| score)
+}
+
+/// Fuzzy match as [`fuzzy_score`], also returning the byte range of each
+/// matched character
+fn fuzzy_match(
+    pattern: &str,
+    candidate: &str,
+    case_sensitive: bool,
+) -> Option<(i64, Vec<(usize, usize)>)> {
+    const MATCH: i64 = 16;
+    const CONSECUTIVE: i64 = 24;
+    const BOUNDARY: i64 = 20;
+
+    let same = |a: char, b: char| {
+        a == b || (!case_sensitive && a.to_lowercase().eq(b.to_lowercase()))
+    };
+    let candidate: Vec<(usize, char)> = candidate.char_indices().collect();
+
+    let mut score = 0;
+    let mut positions = Vec::new();
+    let mut next = 0;
+    let mut prev: Option<usize> = None;
+    for pc in pattern.chars() {
+        let idx = next + candidate[next..].iter().position(|&(_, c)| same(c, pc))?;
+
+        score += MATCH;
+        if prev.is_some_and(|p| p + 1 == idx) {
+            score += CONSECUTIVE;
+        }
+        if idx == 0 || matches!(candidate[idx - 1].1, '/' | '\\' | '_' | '-' | '.' | ' ') {
+            score += BOUNDARY;
+        }
+        score -= (idx - prev.map_or(0, |p| p + 1)) as i64;
+
+        let (start, c) = candidate[idx];
+        positions.push((start, start + c.len_utf8()));
+        prev = Some(idx);
+        next = idx + 1;
+    }
+
+    Some((score, positions))
+}
+
+/// Fuzzy match of a search query in a log line: the score ignores where in
+/// the line the match starts, and scattered matches scoring below zero are
+/// rejected. Adjacent matched characters are merged into one range.
+pub fn fuzzy_line_match(
+    query: &str,
+    line: &str,
+    case_sensitive: bool,
+) -> Option<(i64, Vec<(usize, usize)>)> {
+    let (score, positions) = fuzzy_match(query, line, case_sensitive)?;
+    let leading = positions.first().map_or(0, |&(start, _)| line[..start].chars().count());
+    let score = score + leading as i64;
+    if score <= 0 {
+        return None;
+    }
+
+    let mut ranges: Vec<(usize, usize)> = Vec::with_capacity(positions.len());
+    for (start, end) in positions {
+        match ranges.last_mut() {
+            Some(last) if last.1 == start => last.1 = end,
+            _ => ranges.push((start, end)),
+        }
+    }
+    Some((score, ranges))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::log_buffer::LogBufferConfig;
     use crate::log_entry::LogEntry;
 
     #[test]
@@ -583,4 +1403,461 @@ mod tests {
         engine.previous(); // Wrap around
         assert_eq!(engine.current_result_number(), Some(5));
     }
+
+    #[test]
+    fn test_new_query_starts_at_first_match() {
+        let mut buffer = LogBuffer::new();
+        for i in 1..=5 {
+            buffer.push(LogEntry::new(i, format!("Match {}", i), 0));
+        }
+
+        let mut engine = SearchEngine::new();
+        engine.set_query("Match".to_string());
+        engine.search(&buffer);
+        assert_eq!(engine.result_count(), 5);
+        engine.next();
+        engine.next();
+        assert_eq!(engine.current_result_number(), Some(3));
+
+        engine.set_query("Match [45]".to_string());
+        engine.set_use_regex(true);
+        engine.search(&buffer);
+        assert_eq!(engine.result_count(), 2);
+        assert_eq!(engine.current_result_number(), Some(1));
+        assert_eq!(engine.current_match().unwrap().line_number, 4);
+    }
+
+    #[test]
+    fn test_search_large_buffer_in_steps() {
+        let mut buffer = LogBuffer::new();
+        for i in 1..=5 {
+            buffer.push(LogEntry::new(i, format!("Match {}", i), 0));
+        }
+
+        let mut engine = SearchEngine::new();
+        engine.step_lines = 2;
+        engine.set_query("Match".to_string());
+        engine.search(&buffer);
+        assert_eq!(engine.search_progress(), Some(0.4));
+        assert_eq!(engine.result_count(), 2);
+        assert_eq!(engine.current_result_number(), Some(1));
+        engine.next();
+
+        assert!(engine.continue_search(&buffer));
+        assert!(!engine.continue_search(&buffer));
+        assert_eq!(engine.search_progress(), None);
+        assert_eq!(engine.result_count(), 5);
+        assert_eq!(engine.current_result_number(), Some(2));
+        assert!(!engine.continue_search(&buffer));
+
+        // A new query starts again at the first match
+        engine.set_query("Match [45]".to_string());
+        engine.set_use_regex(true);
+        engine.search(&buffer);
+        while engine.continue_search(&buffer) {}
+        assert_eq!(engine.result_count(), 2);
+        assert_eq!(engine.current_result_number(), Some(1));
+        assert_eq!(engine.current_match().unwrap().line_number, 4);
+    }
+
+    #[test]
+    fn test_partial_search_survives_trimming_and_cancels() {
+        let mut buffer = LogBuffer::with_config(LogBufferConfig {
+            max_lines: 6,
+            ..Default::default()
+        });
+        for i in 1..=6 {
+            buffer.push(LogEntry::new(i, format!("Match {}", i), 0));
+        }
+
+        let mut engine = SearchEngine::new();
+        engine.step_lines = 2;
+        engine.set_query("mtch".to_string());
+        engine.set_fuzzy(true);
+        engine.search(&buffer);
+        assert_eq!(engine.result_count(), 2);
+        // Navigation wraps within the matches found so far
+        assert_eq!(engine.next().unwrap().line_number, 2);
+        assert_eq!(engine.next().unwrap().line_number, 1);
+        engine.next();
+
+        // The oldest line is trimmed while scanning; the selection follows
+        buffer.push(LogEntry::new(7, "Match 7".to_string(), 0));
+        assert!(engine.continue_search(&buffer));
+        assert_eq!(engine.current_match().unwrap().line_number, 2);
+        assert_eq!(engine.current_match().unwrap().buffer_index, 0);
+        while engine.continue_search(&buffer) {}
+        let lines: Vec<usize> = engine.results().iter().map(|m| m.line_number).collect();
+        assert_eq!(lines, [2, 3, 4, 5, 6, 7]);
+
+        // A new query cancels the scan in progress
+        engine.set_query("match 3".to_string());
+        engine.search(&buffer);
+        assert!(engine.search_progress().is_some());
+        engine.set_query(String::new());
+        engine.search(&buffer);
+        assert!(engine.search_progress().is_none());
+        assert!(!engine.continue_search(&buffer));
+        assert!(engine.next().is_none());
+    }
+
+    #[test]
+    fn test_errors_with_context_indices() {
+        let mut buffer = LogBuffer::new();
+        let lines = [
+            "INFO a",   // 0
+            "INFO b",   // 1
+            "ERROR c",  // 2
+            "INFO d",   // 3
+            "DEBUG e",  // 4
+            "INFO f",   // 5
+            "INFO g",   // 6
+            "FATAL h",  // 7
+            "WARN i",   // 8
+            "INFO j",   // 9
+            "INFO k",   // 10
+            "INFO l",   // 11
+            "INFO m",   // 12
+            "ERROR n",  // 13
+        ];
+        for (i, line) in lines.iter().enumerate() {
+            buffer.push(LogEntry::new(i + 1, line.to_string(), 0));
+        }
+
+        let mut filter = LogFilter::new();
+        assert!(filter.toggle_errors_with_context(2));
+        // Adjacent context windows are merged, and the last window is clipped at the end
+        assert_eq!(
+            filter.apply(&buffer).to_vec(),
+            vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 11, 12, 13]
+        );
+
+        // Toggling off restores the previous (unfiltered) view
+        assert!(!filter.toggle_errors_with_context(2));
+        assert!(!filter.is_filtering());
+        assert_eq!(filter.apply(&buffer).len(), lines.len());
+    }
+
+    #[test]
+    fn test_min_level_threshold_includes_at_or_above() {
+        assert!(LogLevel::Trace < LogLevel::Debug && LogLevel::Error < LogLevel::Fatal);
+        assert!(LogLevel::Warn.is_at_least(LogLevel::Warn));
+        assert!(!LogLevel::Info.is_at_least(LogLevel::Warn));
+
+        let mut buffer = LogBuffer::new();
+        let lines = [
+            "TRACE poll",        // 0
+            "DBG state",         // 1
+            "INF started",       // 2
+            "WARNING slow disk", // 3
+            "WRN retrying",      // 4
+            "ERR timeout",       // 5
+            "CRITICAL oom",      // 6
+            "no level here",     // 7
+        ];
+        for (i, line) in lines.iter().enumerate() {
+            buffer.push(LogEntry::new(i + 1, line.to_string(), 0));
+        }
+
+        let mut filter = LogFilter::new();
+        filter.filter.min_level = Some(LogLevel::Warn);
+        filter.mark_dirty();
+        assert!(filter.filter.is_filtering());
+        // Aliases map onto the ordered levels; lines without a level are kept
+        assert_eq!(filter.apply(&buffer).to_vec(), vec![3, 4, 5, 6, 7]);
+
+        // The per-level toggles still apply on top of the threshold
+        filter.filter.toggle_level(LogLevel::Error);
+        filter.mark_dirty();
+        assert_eq!(filter.apply(&buffer).to_vec(), vec![3, 4, 6, 7]);
+
+        // Showing all levels clears the threshold
+        filter.filter.enable_all_levels();
+        filter.mark_dirty();
+        assert_eq!(filter.apply(&buffer).len(), lines.len());
+    }
+
+    #[test]
+    fn test_filter_expression_or_and_nested_groups() {
+        let mut buffer = LogBuffer::new();
+        let lines = [
+            "INFO started",             // 0
+            "ERROR db timeout",         // 1
+            "WARN thread panic caught", // 2
+            "INFO panic handler ready", // 3
+            "ERROR cache miss",         // 4
+            "DEBUG heartbeat",          // 5
+        ];
+        for (i, line) in lines.iter().enumerate() {
+            buffer.push(LogEntry::new(i + 1, line.to_string(), 0));
+        }
+
+        let condition = |kind, negate| FilterCondition { kind, negate };
+        let mut filter = LogFilter::new();
+
+        // level=ERROR OR contains('panic')
+        filter.filter.expression = Some(FilterGroup {
+            logic: FilterLogic::Or,
+            conditions: vec![
+                condition(ConditionKind::Level(LogLevel::Error), false),
+                condition(ConditionKind::Contains("PANIC".to_string()), false),
+            ],
+            groups: Vec::new(),
+        });
+        filter.mark_dirty();
+        assert!(filter.filter.is_filtering());
+        assert_eq!(filter.apply(&buffer).to_vec(), vec![1, 2, 3, 4]);
+
+        // not INFO AND (regex 'db|cache' OR level=WARN)
+        filter.filter.expression = Some(FilterGroup {
+            logic: FilterLogic::And,
+            conditions: vec![condition(ConditionKind::Level(LogLevel::Info), true)],
+            groups: vec![FilterGroup {
+                logic: FilterLogic::Or,
+                conditions: vec![
+                    condition(ConditionKind::Regex("db|cache".to_string()), false),
+                    condition(ConditionKind::Level(LogLevel::Warn), false),
+                ],
+                groups: Vec::new(),
+            }],
+        });
+        filter.mark_dirty();
+        assert_eq!(filter.apply(&buffer).to_vec(), vec![1, 2, 4]);
+
+        // The simple filters still apply on top
+        filter.filter.toggle_level(LogLevel::Warn);
+        filter.mark_dirty();
+        assert_eq!(filter.apply(&buffer).to_vec(), vec![1, 4]);
+
+        // Invalid or half-typed conditions are ignored rather than hiding everything
+        filter.filter.enable_all_levels();
+        filter.filter.expression = Some(FilterGroup {
+            logic: FilterLogic::And,
+            conditions: vec![condition(ConditionKind::Regex("db(".to_string()), false)],
+            groups: vec![FilterGroup::default()],
+        });
+        filter.mark_dirty();
+        assert_eq!(filter.apply(&buffer).len(), lines.len());
+
+        assert_eq!(FilterLogic::parse("OR"), Some(FilterLogic::Or));
+        assert_eq!(FilterLogic::parse("xor"), None);
+    }
+
+    #[test]
+    fn test_recent_context_restores_search_and_filter() {
+        let mut filter = LogFilter::new();
+        filter.search.set_query("timeout".to_string());
+        filter.search.set_whole_word(true);
+        filter.filter.min_level = Some(LogLevel::Warn);
+        filter.filter.add_exclude("healthcheck".to_string());
+        filter.filter.add_exclude_advanced("GET /metrics".to_string(), true);
+        filter.filter.bookmarks_only = true;
+        filter.filter.expression = Some(FilterGroup {
+            conditions: vec![FilterCondition {
+                kind: ConditionKind::Contains("db".to_string()),
+                negate: false,
+            }],
+            ..Default::default()
+        });
+
+        let context = filter.context(Some("Combined Log".to_string()));
+        assert!(!context.is_empty());
+        assert_eq!(
+            context.label(),
+            "\"timeout\" · WARN+ · -healthcheck -GET /metrics · ƒ · ★ · [Combined Log]"
+        );
+        let toml = toml::to_string(&context).unwrap();
+        assert_eq!(toml::from_str::<AnalysisContext>(&toml).unwrap(), context);
+
+        let mut other = LogFilter::new();
+        assert!(other.context(None).is_empty());
+        other.apply_context(&context);
+        assert_eq!(other.search.config.query, "timeout");
+        assert!(other.search.config.whole_word);
+        assert!(!other.search.config.use_regex);
+        assert_eq!(other.filter.min_level, Some(LogLevel::Warn));
+        assert!(other.filter.bookmarks_only);
+        // Patterns are split the same way as for presets
+        assert_eq!(other.filter.exclude_patterns, ["healthcheck"]);
+        assert_eq!(other.filter.exclude_patterns_advanced.len(), 1);
+        assert!(other.filter.exclude_patterns_advanced[0].is_regex);
+        assert_eq!(other.filter.expression, filter.filter.expression);
+        assert_eq!(other.context(context.grok_pattern.clone()), context);
+    }
+
+    #[test]
+    fn test_filter_preset_round_trip() {
+        let mut filter = FilterConfig::default();
+        filter.errors_and_warnings_only();
+        filter.add_exclude("healthcheck".to_string());
+        filter.add_exclude_advanced(r"GET /metrics\b".to_string(), true);
+        filter.bookmarks_only = true;
+        filter.context_lines = 3;
+        filter.expression = Some(FilterGroup {
+            logic: FilterLogic::Or,
+            conditions: vec![FilterCondition {
+                kind: ConditionKind::Level(LogLevel::Error),
+                negate: false,
+            }],
+            ..Default::default()
+        });
+
+        let preset = filter.to_preset("errors, no noise".to_string());
+        let toml = toml::to_string(&preset).unwrap();
+        let preset: NamedFilter = toml::from_str(&toml).unwrap();
+
+        let mut other = FilterConfig::default();
+        other.add_exclude("stale".to_string());
+        other.apply_preset(&preset);
+        assert_eq!(other.levels, filter.levels);
+        assert_eq!(other.exclude_patterns, ["healthcheck"]);
+        assert_eq!(other.exclude_patterns_advanced.len(), 1);
+        assert!(other.exclude_patterns_advanced[0].is_regex);
+        assert!(other.bookmarks_only);
+        assert_eq!(other.expression, filter.expression);
+        // Settings outside the preset are left alone
+        assert_eq!(other.context_lines, 0);
+        assert_eq!(other.to_preset(preset.name.clone()), preset);
+    }
+
+    #[test]
+    fn test_sort_by_grok_field() {
+        let mut buffer = LogBuffer::new();
+        for (i, duration) in ["250", "", "1200", "75", "1200"].iter().enumerate() {
+            let mut entry = LogEntry::new(i + 1, format!("GET /api {}", duration), 0);
+            if !duration.is_empty() {
+                entry.set_grok_fields([("duration".to_string(), duration.to_string())].into());
+            }
+            buffer.push(entry);
+        }
+
+        // Header clicks go ascending, descending, then back to file order
+        let ascending = FieldSort::next(None, "duration");
+        let descending = FieldSort::next(ascending.as_ref(), "duration");
+        assert!(descending.as_ref().is_some_and(|s| s.descending));
+        assert_eq!(FieldSort::next(descending.as_ref(), "duration"), None);
+
+        let mut filter = LogFilter::new();
+        filter.filter.sort_by = ascending;
+        assert!(filter.is_filtering());
+        // Numeric order, lines without the field last
+        assert_eq!(filter.apply(&buffer), &[3, 0, 2, 4, 1]);
+
+        filter.filter.sort_by = descending;
+        filter.mark_dirty();
+        // Equal values keep file order
+        assert_eq!(filter.apply(&buffer), &[2, 4, 0, 3, 1]);
+    }
+
+    #[test]
+    fn test_fuzzy_ranks_path_candidates() {
+        let candidates = [
+            "readme.md",
+            "api/backend/catalog.log",
+            "app/error.log",
+            "archive/events/old.log",
+        ];
+        let mut ranked: Vec<(i64, &str)> = candidates
+            .iter()
+            .filter_map(|c| fuzzy_score("aelog", c).map(|s| (s, *c)))
+            .collect();
+        ranked.sort_by(|a, b| b.0.cmp(&a.0));
+
+        let order: Vec<&str> = ranked.iter().map(|(_, c)| *c).collect();
+        // Boundary hits and the contiguous "log" put app/error.log first;
+        // readme.md is not a subsequence match at all
+        assert_eq!(order[0], "app/error.log");
+        assert!(!order.contains(&"readme.md"));
+        assert_eq!(order.len(), 3);
+    }
+
+    #[test]
+    fn test_fuzzy_prefers_contiguous_and_case_insensitive() {
+        assert_eq!(fuzzy_score("", "anything"), Some(0));
+        assert!(fuzzy_score("xyz", "error.log").is_none());
+        assert!(fuzzy_score("ERR", "error.log").is_some());
+
+        let contiguous = fuzzy_score("error", "error.log").unwrap();
+        let scattered = fuzzy_score("error", "e-r-r-o-r.log").unwrap();
+        assert!(contiguous > scattered);
+    }
+
+    #[test]
+    fn test_fuzzy_search_ranks_and_traverses_by_score() {
+        let mut buffer = LogBuffer::new();
+        let lines = [
+            "INFO connection pool ready",
+            "ERROR connection refused",
+            "DEBUG c-o-n-n r-e-f scattered",
+            "ERROR conn refused by upstream",
+            "WARN nothing relevant here",
+        ];
+        for (i, line) in lines.iter().enumerate() {
+            buffer.push(LogEntry::new(i + 1, line.to_string(), 0));
+        }
+
+        let mut engine = SearchEngine::new();
+        engine.set_query("conrefused".to_string());
+        engine.set_fuzzy(true);
+        engine.search(&buffer);
+
+        let ranked: Vec<usize> = engine.results().iter().map(|m| m.buffer_index).collect();
+        assert_eq!(ranked, [3, 1]);
+        assert_eq!(engine.current_match().unwrap().buffer_index, 3);
+        assert_eq!(engine.next().unwrap().buffer_index, 1);
+        assert_eq!(engine.next().unwrap().buffer_index, 3);
+        assert_eq!(engine.previous().unwrap().buffer_index, 1);
+
+        // Matched characters, adjacent ones merged
+        let (_, ranges) = fuzzy_line_match("conrefused", lines[1], false).unwrap();
+        assert_eq!(ranges, [(6, 9), (17, 24)]);
+
+        // Filtering keeps the fuzzy matches in file order
+        let mut filter = LogFilter::new();
+        filter.search.set_query("conrefused".to_string());
+        filter.search.set_fuzzy(true);
+        assert_eq!(filter.apply(&buffer), &[1, 3]);
+    }
+
+    #[test]
+    fn test_stack_trace_blocks_follow_parent_level_and_collapse() {
+        let mut buffer = LogBuffer::with_config(crate::log_buffer::LogBufferConfig {
+            continuation: crate::config::StackTraceConfig::default()
+                .continuation_regex()
+                .unwrap(),
+            ..Default::default()
+        });
+        let lines = [
+            "INFO starting",
+            "ERROR java.lang.IllegalStateException: boom",
+            "\tat com.example.Handler.run(Handler.java:42)",
+            "Caused by: java.io.IOException: DEBUG socket closed",
+            "\t... 3 more",
+            "INFO recovered",
+        ];
+        for (i, line) in lines.iter().enumerate() {
+            buffer.push(LogEntry::new(i + 1, line.to_string(), 0));
+        }
+        let flags: Vec<bool> = buffer.iter().map(|e| e.continuation).collect();
+        assert_eq!(flags, [false, false, true, true, true, false]);
+
+        // Frames are shown with their ERROR line
+        let mut filter = LogFilter::new();
+        filter.filter.min_level = Some(LogLevel::Error);
+        filter.mark_dirty();
+        assert_eq!(filter.apply(&buffer), &[1, 2, 3, 4]);
+
+        // Collapsing keeps only the parent line
+        filter.toggle_block(2);
+        assert!(filter.is_filtering());
+        assert_eq!(filter.apply(&buffer), &[1]);
+
+        filter.filter.min_level = None;
+        filter.mark_dirty();
+        assert_eq!(filter.apply(&buffer), &[0, 1, 5]);
+
+        filter.toggle_block(2);
+        assert!(!filter.is_filtering());
+        assert_eq!(filter.apply(&buffer), &[0, 1, 2, 3, 4, 5]);
+    }
 }
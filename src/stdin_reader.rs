@@ -0,0 +1,213 @@
+//! Standard input as a log source (`mycmd | logline -`)
+//!
+//! A background thread appends each line read from stdin to a cache file,
+//! which is opened as a streaming tab and followed like any growing log.
+//! The cache file is removed when the reader is dropped.
+
+use anyhow::{Context, Result};
+use std::fs::OpenOptions;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+
+/// Command line argument selecting stdin as the source
+pub const STDIN_ARG: &str = "-";
+
+/// Tab label of the stdin source
+pub const STDIN_TAB_NAME: &str = "stdin";
+
+/// Whether the command line asks to read stdin
+pub fn stdin_requested(args: impl IntoIterator<Item = String>) -> bool {
+    args.into_iter().skip(1).any(|arg| arg == STDIN_ARG)
+}
+
+/// Cache file the lines of this process's stdin are written to
+pub fn stdin_cache_path() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("logline")
+        .join("stdin")
+        .join(format!("stdin-{}.log", std::process::id()))
+}
+
+/// Background reader copying stdin lines into a cache file
+pub struct StdinReader {
+    /// Cache file the lines are appended to
+    cache_path: PathBuf,
+    /// Tells the thread to stop
+    stop_signal: Arc<AtomicBool>,
+    /// Reader thread (taken when stopped)
+    handle: Option<JoinHandle<()>>,
+}
+
+impl StdinReader {
+    /// Start reading the process's stdin into `cache_path`
+    pub fn spawn(cache_path: PathBuf) -> Result<Self> {
+        // The lock isn't Send, so the thread reads through its own buffer
+        Self::spawn_from(BufReader::new(std::io::stdin()), cache_path)
+    }
+
+    /// Start reading lines from `input` into `cache_path` (truncated first)
+    pub fn spawn_from(
+        mut input: impl BufRead + Send + 'static,
+        cache_path: PathBuf,
+    ) -> Result<Self> {
+        if let Some(parent) = cache_path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&cache_path)
+            .with_context(|| format!("Failed to create {}", cache_path.display()))?;
+
+        let stop_signal = Arc::new(AtomicBool::new(false));
+        let stop = stop_signal.clone();
+        let handle = std::thread::spawn(move || {
+            let mut line = Vec::new();
+            loop {
+                line.clear();
+                // Bytes rather than `lines()`, which ends the stream at the
+                // first line that isn't valid UTF-8
+                if !matches!(input.read_until(b'\n', &mut line), Ok(n) if n > 0) {
+                    break;
+                }
+                if stop.load(Ordering::Relaxed) {
+                    break;
+                }
+                let text = String::from_utf8_lossy(&line);
+                if writeln!(file, "{}", text.trim_end_matches(['\n', '\r'])).is_err() {
+                    break;
+                }
+                // Flush so the tab sees each line as soon as it arrives
+                let _ = file.flush();
+            }
+        });
+
+        Ok(Self {
+            cache_path,
+            stop_signal,
+            handle: Some(handle),
+        })
+    }
+
+    /// Cache file the lines are written to
+    pub fn cache_path(&self) -> &Path {
+        &self.cache_path
+    }
+
+    /// Whether the input is still being read (false after EOF or stop)
+    pub fn is_running(&self) -> bool {
+        self.handle.as_ref().is_some_and(|h| !h.is_finished())
+    }
+
+    /// Stop the reader. A thread blocked waiting for input exits on the next
+    /// line or at EOF; it is joined here only if it has already finished, so
+    /// closing the tab never waits on the upstream command.
+    pub fn stop(&mut self) {
+        self.stop_signal.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take_if(|h| h.is_finished()) {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for StdinReader {
+    fn drop(&mut self) {
+        self.stop();
+        // The cache only lives as long as the stdin tab
+        let _ = std::fs::remove_file(&self.cache_path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+    use std::sync::mpsc::{channel, Receiver};
+    use std::time::{Duration, Instant};
+
+    /// Pipe-like input fed chunk by chunk from the test
+    struct ChannelInput(Receiver<Vec<u8>>, Vec<u8>);
+
+    impl Read for ChannelInput {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.1.is_empty() {
+                match self.0.recv() {
+                    Ok(chunk) => self.1 = chunk,
+                    Err(_) => return Ok(0),
+                }
+            }
+            let n = buf.len().min(self.1.len());
+            buf[..n].copy_from_slice(&self.1[..n]);
+            self.1.drain(..n);
+            Ok(n)
+        }
+    }
+
+    fn wait_for(mut done: impl FnMut() -> bool) -> bool {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while Instant::now() < deadline {
+            if done() {
+                return true;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        false
+    }
+
+    #[test]
+    fn test_stdin_argument() {
+        let args = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert!(stdin_requested(args(&["logline", "-"])));
+        assert!(!stdin_requested(args(&["logline"])));
+        assert!(!stdin_requested(args(&["-"])));
+    }
+
+    #[test]
+    fn test_lines_stream_into_cache_until_stopped() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("stdin").join("stdin.log");
+        let (tx, rx) = channel();
+        let input = BufReader::new(ChannelInput(rx, Vec::new()));
+        let mut reader = StdinReader::spawn_from(input, path.clone()).unwrap();
+
+        tx.send(b"first line\nsecond ".to_vec()).unwrap();
+        tx.send(b"line\n".to_vec()).unwrap();
+        assert!(wait_for(|| {
+            std::fs::read_to_string(&path).unwrap() == "first line\nsecond line\n"
+        }));
+        assert!(reader.is_running());
+
+        // Lines arriving after the tab is closed are not written
+        reader.stop();
+        tx.send(b"after stop\n".to_vec()).unwrap();
+        assert!(wait_for(|| !reader.is_running()));
+        assert_eq!(
+            std::fs::read_to_string(reader.cache_path()).unwrap(),
+            "first line\nsecond line\n"
+        );
+
+        // Closing the tab removes the cache
+        drop(reader);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_invalid_utf8_does_not_end_the_stream() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("stdin.log");
+        let input = &b"caf\xe9 latin-1\r\nnext line\nno newline"[..];
+        let reader = StdinReader::spawn_from(input, path.clone()).unwrap();
+
+        assert!(wait_for(|| !reader.is_running()));
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "caf\u{fffd} latin-1\nnext line\nno newline\n"
+        );
+    }
+}
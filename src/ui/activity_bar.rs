@@ -26,6 +26,8 @@ pub struct ActivityBar {
     pub server_running: bool,
     /// Server port
     pub server_port: u16,
+    /// Whether agents connect over TLS
+    pub server_tls: bool,
     /// Number of connected agents
     pub connected_agents: usize,
     /// MCP server running status
@@ -47,6 +49,7 @@ impl ActivityBar {
             sidebar_visible: true,
             server_running: false,
             server_port: 12500,
+            server_tls: false,
             connected_agents: 0,
             mcp_running: false,
             mcp_port: 12600,
@@ -140,7 +143,7 @@ impl ActivityBar {
             ui.add_space(ui.available_height() - 120.0);
 
             // Server status indicator
-            let (status_icon, status_color, tooltip) = if self.server_running {
+            let (status_icon, status_color, mut tooltip) = if self.server_running {
                 if self.connected_agents > 0 {
                     (
                         "📡",
@@ -160,6 +163,11 @@ impl ActivityBar {
                 ("📡", Color32::GRAY, t::server_stopped().to_string())
             };
 
+            if self.server_running && self.server_tls {
+                tooltip.push('\n');
+                tooltip.push_str(t::server_tls_enabled());
+            }
+
             let response = ui.add(
                 egui::Button::new(RichText::new(status_icon).size(20.0).color(status_color))
                     .frame(false)
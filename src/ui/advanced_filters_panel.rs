@@ -3,10 +3,19 @@
 //! Provides comprehensive filtering options in the sidebar.
 
 use crate::i18n::Translations as t;
-use crate::log_entry::LogLevel;
-use crate::search::FilterConfig;
+use crate::log_entry::{parse_time_query, LogLevel};
+use crate::search::{
+    ConditionKind, FilterCondition, FilterConfig, FilterGroup, FilterLogic, NamedFilter, TimeRange,
+};
+use chrono::NaiveDateTime;
 use egui::{self, Color32, RichText, Ui};
 
+/// Quick time range choices: label and minutes before the newest timestamp
+const TIME_RANGE_PRESETS: [(&str, i64); 4] = [("5m", 5), ("15m", 15), ("1h", 60), ("24h", 1440)];
+
+/// How time range bounds are shown in the inputs
+const TIME_INPUT_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
 /// Pattern type for exclude patterns
 #[derive(Debug, Clone, PartialEq)]
 pub enum PatternType {
@@ -27,6 +36,15 @@ pub struct ExcludePattern {
     pub enabled: bool,
 }
 
+/// Preset change for the app to persist
+#[derive(Debug, Clone)]
+pub enum PresetAction {
+    /// Save (or overwrite) a preset
+    Save(NamedFilter),
+    /// Delete the preset with a name
+    Delete(String),
+}
+
 /// Advanced Filters Panel component
 pub struct AdvancedFiltersPanel {
     /// New exclude pattern input
@@ -35,6 +53,20 @@ pub struct AdvancedFiltersPanel {
     new_pattern_type: PatternType,
     /// Extended exclude patterns (with type and enabled state)
     exclude_patterns: Vec<ExcludePattern>,
+    /// Name input for saving a preset
+    preset_name_input: String,
+    /// Preset last saved or applied
+    selected_preset: Option<String>,
+    /// Preset change waiting to be persisted
+    preset_action: Option<PresetAction>,
+    /// Start of the time range being edited
+    time_start_input: String,
+    /// End of the time range being edited
+    time_end_input: String,
+    /// Whether the last time range entered failed to parse
+    time_range_invalid: bool,
+    /// Time range the inputs were last filled from
+    shown_time_range: Option<TimeRange>,
 }
 
 impl Default for AdvancedFiltersPanel {
@@ -50,9 +82,36 @@ impl AdvancedFiltersPanel {
             exclude_input: String::new(),
             new_pattern_type: PatternType::Text,
             exclude_patterns: Vec::new(),
+            preset_name_input: String::new(),
+            selected_preset: None,
+            preset_action: None,
+            time_start_input: String::new(),
+            time_end_input: String::new(),
+            time_range_invalid: false,
+            shown_time_range: None,
         }
     }
 
+    /// Take the preset change made in the last frame
+    pub fn take_preset_action(&mut self) -> Option<PresetAction> {
+        self.preset_action.take()
+    }
+
+    /// Apply a preset to the filter and show its exclude patterns
+    fn apply_preset(&mut self, filter: &mut FilterConfig, preset: &NamedFilter) {
+        filter.apply_preset(preset);
+        self.exclude_patterns = filter
+            .exclude_patterns
+            .iter()
+            .map(|p| ExcludePattern {
+                pattern: p.clone(),
+                pattern_type: PatternType::Text,
+                enabled: true,
+            })
+            .collect();
+        self.selected_preset = Some(preset.name.clone());
+    }
+
     /// Sync patterns from filter config
     pub fn sync_from_filter(&mut self, filter: &FilterConfig) {
         // Only sync if lengths differ or patterns changed
@@ -69,6 +128,48 @@ impl AdvancedFiltersPanel {
         }
     }
 
+    /// Refill the time inputs when the range was changed elsewhere (e.g. on
+    /// the timeline)
+    fn sync_time_range(&mut self, filter: &FilterConfig) {
+        if filter.time_range == self.shown_time_range {
+            return;
+        }
+        let format = |time: chrono::DateTime<chrono::Local>| {
+            time.naive_utc().format(TIME_INPUT_FORMAT).to_string()
+        };
+        self.time_start_input = filter
+            .time_range
+            .map(|r| format(r.start))
+            .unwrap_or_default();
+        self.time_end_input = filter.time_range.map(|r| format(r.end)).unwrap_or_default();
+        self.time_range_invalid = false;
+        self.shown_time_range = filter.time_range;
+    }
+
+    /// Parse the time inputs; an empty bound extends to the log's first or
+    /// last timestamp, a time of day is taken on the first day of the log
+    fn parse_time_range(
+        &self,
+        bounds: Option<(NaiveDateTime, NaiveDateTime)>,
+    ) -> Option<Option<TimeRange>> {
+        let (start, end) = (self.time_start_input.trim(), self.time_end_input.trim());
+        if start.is_empty() && end.is_empty() {
+            return Some(None);
+        }
+        let (first, last) = bounds?;
+        let parse = |input: &str, default: NaiveDateTime| {
+            if input.is_empty() {
+                Some(default)
+            } else {
+                parse_time_query(input, first.date())
+            }
+        };
+        Some(Some(TimeRange::from_naive(
+            parse(start, first)?,
+            parse(end, last)?,
+        )))
+    }
+
     /// Get enabled patterns to sync back to filter config
     pub fn get_enabled_patterns(&self) -> Vec<String> {
         self.exclude_patterns
@@ -79,7 +180,13 @@ impl AdvancedFiltersPanel {
     }
 
     /// Show the advanced filters panel
-    pub fn show(&mut self, ui: &mut Ui, filter: &mut FilterConfig) -> bool {
+    pub fn show(
+        &mut self,
+        ui: &mut Ui,
+        filter: &mut FilterConfig,
+        presets: &[NamedFilter],
+        time_bounds: Option<(NaiveDateTime, NaiveDateTime)>,
+    ) -> bool {
         let mut changed = false;
 
         // Set minimum width to prevent panel from shrinking
@@ -92,6 +199,73 @@ impl AdvancedFiltersPanel {
                 ui.heading(RichText::new(t::advanced_filters()).strong());
                 ui.add_space(12.0);
 
+                // === Presets ===
+                ui.group(|ui| {
+                    ui.set_min_width(ui.available_width());
+                    ui.label(RichText::new(t::filter_presets()).strong());
+                    ui.add_space(4.0);
+
+                    ui.horizontal(|ui| {
+                        let selected = self
+                            .selected_preset
+                            .clone()
+                            .unwrap_or_else(|| t::choose_filter_preset().to_string());
+                        egui::ComboBox::from_id_salt("filter_presets")
+                            .selected_text(selected)
+                            .width(ui.available_width() - 32.0)
+                            .show_ui(ui, |ui| {
+                                if presets.is_empty() {
+                                    ui.label(
+                                        RichText::new(t::no_filter_presets()).weak().italics(),
+                                    );
+                                }
+                                for preset in presets {
+                                    let is_selected = self.selected_preset.as_deref()
+                                        == Some(preset.name.as_str());
+                                    if ui.selectable_label(is_selected, &preset.name).clicked() {
+                                        self.apply_preset(filter, preset);
+                                        changed = true;
+                                    }
+                                }
+                            });
+
+                        let can_delete = self
+                            .selected_preset
+                            .as_ref()
+                            .is_some_and(|name| presets.iter().any(|p| &p.name == name));
+                        if ui
+                            .add_enabled(can_delete, egui::Button::new("🗑").small())
+                            .on_hover_text(t::delete_filter_preset())
+                            .clicked()
+                        {
+                            self.preset_action =
+                                self.selected_preset.take().map(PresetAction::Delete);
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.preset_name_input)
+                                .hint_text(t::filter_preset_name_hint())
+                                .desired_width(ui.available_width() - 60.0),
+                        );
+                        let name = self.preset_name_input.trim().to_string();
+                        if ui
+                            .add_enabled(
+                                !name.is_empty(),
+                                egui::Button::new(t::save_filter_preset()),
+                            )
+                            .clicked()
+                        {
+                            self.selected_preset = Some(name.clone());
+                            self.preset_action = Some(PresetAction::Save(filter.to_preset(name)));
+                            self.preset_name_input.clear();
+                        }
+                    });
+                });
+
+                ui.add_space(12.0);
+
                 // === Log Level Filters ===
                 ui.group(|ui| {
                     ui.set_min_width(ui.available_width());
@@ -139,6 +313,30 @@ impl AdvancedFiltersPanel {
 
                     ui.add_space(4.0);
 
+                    // Severity threshold
+                    ui.horizontal(|ui| {
+                        ui.label(t::min_level());
+                        let selected = filter.min_level.map_or(t::any_level(), |l| l.as_str());
+                        egui::ComboBox::from_id_salt("min_level_threshold")
+                            .selected_text(selected)
+                            .show_ui(ui, |ui| {
+                                changed |= ui
+                                    .selectable_value(&mut filter.min_level, None, t::any_level())
+                                    .changed();
+                                for level in LogLevel::all() {
+                                    changed |= ui
+                                        .selectable_value(
+                                            &mut filter.min_level,
+                                            Some(level),
+                                            RichText::new(level.as_str()).color(level.color()),
+                                        )
+                                        .changed();
+                                }
+                            });
+                    });
+
+                    ui.add_space(4.0);
+
                     // Quick filter buttons
                     ui.horizontal(|ui| {
                         if ui.button(t::all()).clicked() {
@@ -176,6 +374,113 @@ impl AdvancedFiltersPanel {
 
                 ui.add_space(12.0);
 
+                // === Time Range ===
+                ui.group(|ui| {
+                    ui.set_min_width(ui.available_width());
+                    ui.label(RichText::new(t::time_range_filter()).strong());
+                    ui.add_space(4.0);
+
+                    self.sync_time_range(filter);
+
+                    let mut submitted = false;
+                    egui::Grid::new("time_range_grid")
+                        .num_columns(2)
+                        .spacing([8.0, 4.0])
+                        .show(ui, |ui| {
+                            for (label, input) in [
+                                (t::time_range_from(), &mut self.time_start_input),
+                                (t::time_range_to(), &mut self.time_end_input),
+                            ] {
+                                ui.label(label);
+                                let response = ui.add(
+                                    egui::TextEdit::singleline(input)
+                                        .hint_text(t::time_range_hint())
+                                        .desired_width(ui.available_width()),
+                                );
+                                submitted |= response.lost_focus()
+                                    && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                                ui.end_row();
+                            }
+                        });
+
+                    ui.horizontal(|ui| {
+                        submitted |= ui.button(t::apply()).clicked();
+                        if ui
+                            .add_enabled(filter.time_range.is_some(), egui::Button::new(t::clear()))
+                            .clicked()
+                        {
+                            self.time_start_input.clear();
+                            self.time_end_input.clear();
+                            submitted = true;
+                        }
+                    });
+
+                    if submitted {
+                        match self.parse_time_range(time_bounds) {
+                            Some(range) => {
+                                filter.time_range = range;
+                                self.shown_time_range = range;
+                                self.time_range_invalid = false;
+                                changed = true;
+                            }
+                            None => self.time_range_invalid = true,
+                        }
+                    }
+                    if self.time_range_invalid {
+                        ui.label(
+                            RichText::new(t::invalid_time_query())
+                                .color(Color32::from_rgb(244, 67, 54))
+                                .small(),
+                        );
+                    }
+
+                    ui.horizontal(|ui| {
+                        ui.label(t::time_range_last())
+                            .on_hover_text(t::time_range_last_tooltip());
+                        for (label, minutes) in TIME_RANGE_PRESETS {
+                            let latest = time_bounds.map(|(_, last)| last);
+                            if ui
+                                .add_enabled(latest.is_some(), egui::Button::new(label).small())
+                                .on_hover_text(t::time_range_last_tooltip())
+                                .clicked()
+                            {
+                                if let Some(latest) = latest {
+                                    filter.time_range =
+                                        Some(TimeRange::last_minutes(latest, minutes));
+                                    changed = true;
+                                }
+                            }
+                        }
+                    });
+
+                    changed |= ui
+                        .checkbox(
+                            &mut filter.keep_untimestamped,
+                            t::keep_untimestamped_lines(),
+                        )
+                        .on_hover_text(t::keep_untimestamped_lines_tooltip())
+                        .changed();
+                });
+
+                ui.add_space(12.0);
+
+                // === AND/OR Conditions ===
+                ui.group(|ui| {
+                    ui.set_min_width(ui.available_width());
+                    ui.label(RichText::new(t::filter_conditions()).strong())
+                        .on_hover_text(t::filter_conditions_hint());
+                    ui.add_space(4.0);
+
+                    let group = filter.expression.get_or_insert_with(FilterGroup::default);
+                    let (group_changed, _) = show_condition_group(ui, group, "conditions", false);
+                    changed |= group_changed;
+                    if group.conditions.is_empty() && group.groups.is_empty() {
+                        filter.expression = None;
+                    }
+                });
+
+                ui.add_space(12.0);
+
                 // === Exclude Patterns ===
                 ui.group(|ui| {
                     ui.set_min_width(ui.available_width());
@@ -318,6 +623,8 @@ impl AdvancedFiltersPanel {
                     filter.enable_all_levels();
                     self.exclude_patterns.clear();
                     filter.bookmarks_only = false;
+                    filter.expression = None;
+                    filter.time_range = None;
                     changed = true;
                 }
 
@@ -332,3 +639,149 @@ impl AdvancedFiltersPanel {
         changed
     }
 }
+
+/// Show a condition group: its AND/OR choice, conditions and nested groups.
+/// Returns whether it changed and whether a nested group asked to be removed.
+fn show_condition_group(
+    ui: &mut Ui,
+    group: &mut FilterGroup,
+    id: &str,
+    removable: bool,
+) -> (bool, bool) {
+    let mut changed = false;
+    let mut remove_self = false;
+
+    ui.horizontal(|ui| {
+        for logic in FilterLogic::all() {
+            let label = match logic {
+                FilterLogic::And => t::match_all_conditions(),
+                FilterLogic::Or => t::match_any_condition(),
+            };
+            changed |= ui
+                .selectable_value(&mut group.logic, *logic, label)
+                .changed();
+        }
+        if removable
+            && ui
+                .small_button("✕")
+                .on_hover_text(t::remove_condition_group())
+                .clicked()
+        {
+            remove_self = true;
+        }
+    });
+
+    let mut to_remove = None;
+    for (i, condition) in group.conditions.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            changed |= ui
+                .checkbox(&mut condition.negate, t::condition_not())
+                .changed();
+
+            let kind_label = match &condition.kind {
+                ConditionKind::Level(_) => t::condition_level(),
+                ConditionKind::Contains(_) => t::condition_contains(),
+                ConditionKind::Regex(_) => t::regex(),
+            };
+            egui::ComboBox::from_id_salt(format!("{}_kind_{}", id, i))
+                .selected_text(kind_label)
+                .width(80.0)
+                .show_ui(ui, |ui| {
+                    let text = match &condition.kind {
+                        ConditionKind::Level(_) => String::new(),
+                        ConditionKind::Contains(text) | ConditionKind::Regex(text) => text.clone(),
+                    };
+                    let kinds = [
+                        (ConditionKind::Level(LogLevel::Error), t::condition_level()),
+                        (
+                            ConditionKind::Contains(text.clone()),
+                            t::condition_contains(),
+                        ),
+                        (ConditionKind::Regex(text), t::regex()),
+                    ];
+                    for (kind, label) in kinds {
+                        let selected = std::mem::discriminant(&kind)
+                            == std::mem::discriminant(&condition.kind);
+                        if ui.selectable_label(selected, label).clicked() && !selected {
+                            condition.kind = kind;
+                            changed = true;
+                        }
+                    }
+                });
+
+            match &mut condition.kind {
+                ConditionKind::Level(level) => {
+                    egui::ComboBox::from_id_salt(format!("{}_level_{}", id, i))
+                        .selected_text(RichText::new(level.as_str()).color(level.color()))
+                        .width(70.0)
+                        .show_ui(ui, |ui| {
+                            for option in LogLevel::all() {
+                                changed |= ui
+                                    .selectable_value(
+                                        level,
+                                        option,
+                                        RichText::new(option.as_str()).color(option.color()),
+                                    )
+                                    .changed();
+                            }
+                        });
+                }
+                ConditionKind::Contains(text) | ConditionKind::Regex(text) => {
+                    changed |= ui
+                        .add(
+                            egui::TextEdit::singleline(text)
+                                .hint_text(t::condition_value_hint())
+                                .desired_width(ui.available_width() - 28.0),
+                        )
+                        .changed();
+                }
+            }
+
+            if ui.small_button("✕").clicked() {
+                to_remove = Some(i);
+            }
+        });
+    }
+    if let Some(i) = to_remove {
+        group.conditions.remove(i);
+        changed = true;
+    }
+
+    let mut group_to_remove = None;
+    for (i, nested) in group.groups.iter_mut().enumerate() {
+        ui.indent(format!("{}_group_{}", id, i), |ui| {
+            let (nested_changed, remove) =
+                show_condition_group(ui, nested, &format!("{}_{}", id, i), true);
+            changed |= nested_changed;
+            if remove {
+                group_to_remove = Some(i);
+            }
+        });
+    }
+    if let Some(i) = group_to_remove {
+        group.groups.remove(i);
+        changed = true;
+    }
+
+    ui.horizontal(|ui| {
+        if ui.small_button(t::add_condition()).clicked() {
+            group.conditions.push(FilterCondition {
+                kind: ConditionKind::Contains(String::new()),
+                negate: false,
+            });
+            changed = true;
+        }
+        if ui.small_button(t::add_condition_group()).clicked() {
+            group.groups.push(FilterGroup {
+                logic: match group.logic {
+                    FilterLogic::And => FilterLogic::Or,
+                    FilterLogic::Or => FilterLogic::And,
+                },
+                ..Default::default()
+            });
+            changed = true;
+        }
+    });
+
+    (changed, remove_self)
+}
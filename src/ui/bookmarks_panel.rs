@@ -2,9 +2,23 @@
 //!
 //! Shows all bookmarked lines grouped into continuous segments.
 
+use crate::bookmark_report::ReportFormat;
+use crate::bookmarks::{bookmark_color_name, BOOKMARK_COLORS};
 use crate::i18n::Translations as t;
 use crate::log_buffer::LogBuffer;
-use egui::{self, Color32, RichText, Ui};
+use egui::{self, Color32, RichText, Sense, Ui, Vec2};
+use std::collections::HashMap;
+
+/// Swatch shown for the default color (the theme picks the actual one)
+const DEFAULT_SWATCH: Color32 = Color32::from_rgb(255, 193, 7);
+
+/// Color of a bookmark, None for the theme's default
+pub fn bookmark_color(color: u8) -> Option<Color32> {
+    let index = (color as usize).checked_sub(1)?;
+    BOOKMARK_COLORS
+        .get(index)
+        .map(|(_, (r, g, b))| Color32::from_rgb(*r, *g, *b))
+}
 
 /// A continuous segment of bookmarked lines
 #[derive(Debug, Clone)]
@@ -30,12 +44,28 @@ pub enum BookmarkAction {
     RemoveSegment(Vec<usize>),
     /// Clear all bookmarks
     ClearAll,
+    /// Set the note (or clear it) and color of a segment; the note goes on
+    /// its first line, the color on every line (buffer indices)
+    EditBookmark {
+        line: usize,
+        indices: Vec<usize>,
+        note: Option<String>,
+        color: u8,
+    },
+    /// Export an annotated report of all bookmarks
+    ExportReport(ReportFormat),
 }
 
 /// Bookmarks Panel component
 pub struct BookmarksPanel {
     /// Currently selected segment index
     selected_segment: Option<usize>,
+    /// Line whose note is being edited
+    editing_note: Option<usize>,
+    /// Note being edited
+    note_input: String,
+    /// Color being edited
+    color_input: u8,
 }
 
 impl Default for BookmarksPanel {
@@ -49,6 +79,9 @@ impl BookmarksPanel {
     pub fn new() -> Self {
         Self {
             selected_segment: None,
+            editing_note: None,
+            note_input: String::new(),
+            color_input: 0,
         }
     }
 
@@ -97,7 +130,12 @@ impl BookmarksPanel {
     }
 
     /// Render the bookmarks panel
-    pub fn show(&mut self, ui: &mut Ui, buffer: &LogBuffer) -> BookmarkAction {
+    pub fn show(
+        &mut self,
+        ui: &mut Ui,
+        buffer: &LogBuffer,
+        notes: Option<&HashMap<usize, String>>,
+    ) -> BookmarkAction {
         let mut action = BookmarkAction::None;
 
         // Set minimum width to prevent panel from shrinking
@@ -144,8 +182,16 @@ impl BookmarksPanel {
                         let _is_selected = self.selected_segment == Some(seg_idx);
 
                         // Simple horizontal layout, no background
+                        let color = segment
+                            .indices
+                            .first()
+                            .and_then(|&idx| buffer.get(idx))
+                            .map_or(0, |e| e.bookmark_color);
                         ui.horizontal(|ui| {
-                            ui.label(RichText::new("⭐").size(14.0));
+                            match bookmark_color(color) {
+                                Some(fill) => ui.label(RichText::new("★").size(14.0).color(fill)),
+                                None => ui.label(RichText::new("⭐").size(14.0)),
+                            };
 
                             let segment_text = if segment.start_line == segment.end_line {
                                 format!("{} {}", t::line(), segment.start_line)
@@ -180,13 +226,76 @@ impl BookmarksPanel {
                             {
                                 action = BookmarkAction::RemoveSegment(segment.indices.clone());
                             }
+
+                            // Edit note button
+                            if ui
+                                .small_button("✎")
+                                .on_hover_text(t::edit_bookmark_note())
+                                .clicked()
+                            {
+                                self.editing_note = Some(segment.start_line);
+                                self.note_input = notes
+                                    .and_then(|n| n.get(&segment.start_line))
+                                    .cloned()
+                                    .unwrap_or_default();
+                                self.color_input = color;
+                            }
                         });
 
+                        if self.editing_note == Some(segment.start_line) {
+                            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                                let response = ui.add(
+                                    egui::TextEdit::singleline(&mut self.note_input)
+                                        .hint_text(t::bookmark_note_hint())
+                                        .desired_width(160.0),
+                                );
+                                let submitted = response.lost_focus()
+                                    && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                                ui.horizontal(|ui| {
+                                    for color in 0..=BOOKMARK_COLORS.len() as u8 {
+                                        self.color_swatch(ui, color);
+                                    }
+                                });
+                                ui.horizontal(|ui| {
+                                    if ui.small_button("✓").clicked() || submitted {
+                                        action = BookmarkAction::EditBookmark {
+                                            line: segment.start_line,
+                                            indices: segment.indices.clone(),
+                                            note: Some(std::mem::take(&mut self.note_input)),
+                                            color: self.color_input,
+                                        };
+                                        self.editing_note = None;
+                                    }
+                                    if ui.small_button("✕").clicked() {
+                                        self.editing_note = None;
+                                    }
+                                });
+                            });
+                        } else if let Some(note) = notes.and_then(|n| n.get(&segment.start_line)) {
+                            ui.label(RichText::new(note).small().italics().weak());
+                        }
+
                         ui.add_space(4.0);
                     }
 
                     ui.add_space(12.0);
 
+                    // Export an annotated report
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new(t::export_report()).small());
+                        for format in [ReportFormat::Markdown, ReportFormat::Html] {
+                            if ui
+                                .small_button(format.display_name())
+                                .on_hover_text(t::export_report_tooltip())
+                                .clicked()
+                            {
+                                action = BookmarkAction::ExportReport(format);
+                            }
+                        }
+                    });
+
+                    ui.add_space(8.0);
+
                     // Clear all bookmarks button
                     if ui
                         .button(
@@ -205,6 +314,22 @@ impl BookmarksPanel {
         action
     }
 
+    /// A clickable color dot, ringed when it is the color being edited
+    fn color_swatch(&mut self, ui: &mut Ui, color: u8) {
+        let (rect, response) = ui.allocate_exact_size(Vec2::splat(16.0), Sense::click());
+        let fill = bookmark_color(color).unwrap_or(DEFAULT_SWATCH);
+        ui.painter().circle_filled(rect.center(), 6.0, fill);
+        if self.color_input == color {
+            let stroke = ui.visuals().strong_text_color();
+            ui.painter()
+                .circle_stroke(rect.center(), 7.5, egui::Stroke::new(1.5, stroke));
+        }
+        let name = bookmark_color_name(color).unwrap_or(t::default_bookmark_color());
+        if response.on_hover_text(name).clicked() {
+            self.color_input = color;
+        }
+    }
+
     /// Truncate content for preview
     #[allow(dead_code)]
     fn truncate_content(content: &str, max_len: usize) -> String {
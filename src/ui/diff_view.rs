@@ -0,0 +1,155 @@
+//! Diff view - shows the line diff between a snapshot and its live tab
+//!
+//! Added lines are tinted green and removed lines red. Unchanged lines can be
+//! hidden to list only what changed since the snapshot.
+
+use crate::i18n::Translations as t;
+use crate::log_diff::{DiffKind, DiffLine, LogDiff};
+use crossbeam_channel::{Receiver, TryRecvError};
+use egui::{Color32, RichText};
+use std::time::Duration;
+
+/// Diff window state
+#[derive(Default)]
+pub struct DiffView {
+    /// Whether the window is open
+    pub open: bool,
+    /// Names of the compared tabs (old, new)
+    sides: (String, String),
+    /// Current diff
+    diff: LogDiff,
+    /// Diff still being computed in the background
+    pending: Option<Receiver<LogDiff>>,
+    /// Hide unchanged lines
+    only_changes: bool,
+}
+
+impl DiffView {
+    /// Open the window for a diff being computed in the background
+    pub fn show_diff(&mut self, old_name: String, new_name: String, pending: Receiver<LogDiff>) {
+        self.sides = (old_name, new_name);
+        self.diff = LogDiff::default();
+        self.pending = Some(pending);
+        self.only_changes = true;
+        self.open = true;
+    }
+
+    /// Take the diff once the background thread delivers it
+    fn poll_pending(&mut self) {
+        let Some(rx) = &self.pending else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(diff) => {
+                self.diff = diff;
+                self.pending = None;
+            }
+            Err(TryRecvError::Empty) => {}
+            Err(TryRecvError::Disconnected) => self.pending = None,
+        }
+    }
+
+    /// Render the window
+    pub fn show(&mut self, ctx: &egui::Context) {
+        if !self.open {
+            return;
+        }
+        self.poll_pending();
+        if self.pending.is_some() {
+            ctx.request_repaint_after(Duration::from_millis(50));
+        }
+
+        let dark = ctx.style().visuals.dark_mode;
+        let mut open = self.open;
+        egui::Window::new(t::snapshot_diff_title())
+            .open(&mut open)
+            .default_size([720.0, 420.0])
+            .resizable(true)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(
+                        RichText::new(format!("{} → {}", self.sides.0, self.sides.1)).strong(),
+                    );
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.checkbox(&mut self.only_changes, t::snapshot_diff_only_changes());
+                        ui.label(
+                            RichText::new(format!("-{}", self.diff.removed))
+                                .color(removed_color(dark)),
+                        );
+                        ui.label(
+                            RichText::new(format!("+{}", self.diff.added)).color(added_color(dark)),
+                        );
+                    });
+                });
+                ui.separator();
+
+                if self.pending.is_some() {
+                    ui.spinner();
+                    return;
+                }
+                if self.diff.is_empty() {
+                    ui.label(
+                        RichText::new(t::snapshot_diff_no_changes())
+                            .weak()
+                            .italics(),
+                    );
+                    return;
+                }
+
+                let rows: Vec<&DiffLine> = self
+                    .diff
+                    .lines
+                    .iter()
+                    .filter(|l| !self.only_changes || l.kind != DiffKind::Unchanged)
+                    .collect();
+                let row_height = ui.text_style_height(&egui::TextStyle::Monospace) + 2.0;
+                egui::ScrollArea::both()
+                    .auto_shrink([false, false])
+                    .show_rows(ui, row_height, rows.len(), |ui, range| {
+                        for line in &rows[range] {
+                            show_line(ui, line, dark);
+                        }
+                    });
+            });
+        self.open = open;
+    }
+}
+
+/// Render a single diff line with its marker and line numbers
+fn show_line(ui: &mut egui::Ui, line: &DiffLine, dark: bool) {
+    let (marker, fill) = match line.kind {
+        DiffKind::Added => ("+", added_color(dark).gamma_multiply(0.2)),
+        DiffKind::Removed => ("-", removed_color(dark).gamma_multiply(0.2)),
+        DiffKind::Changed => ("~", Color32::TRANSPARENT),
+        DiffKind::Unchanged => (" ", Color32::TRANSPARENT),
+    };
+    let number = |n: Option<usize>| n.map(|n| n.to_string()).unwrap_or_default();
+    let text = format!(
+        "{:>7} {:>7} {} {}",
+        number(line.old_line),
+        number(line.new_line),
+        marker,
+        line.content
+    );
+
+    egui::Frame::new().fill(fill).show(ui, |ui| {
+        ui.set_min_width(ui.available_width());
+        ui.label(RichText::new(text).monospace());
+    });
+}
+
+pub(crate) fn added_color(dark: bool) -> Color32 {
+    if dark {
+        Color32::from_rgb(80, 200, 120)
+    } else {
+        Color32::from_rgb(20, 130, 50)
+    }
+}
+
+pub(crate) fn removed_color(dark: bool) -> Color32 {
+    if dark {
+        Color32::from_rgb(240, 100, 100)
+    } else {
+        Color32::from_rgb(190, 30, 30)
+    }
+}
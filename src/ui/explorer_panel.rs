@@ -5,19 +5,60 @@
 
 use crate::android_logcat::{AndroidDevice, ConnectionType};
 use crate::i18n::Translations as t;
+use crate::mcp::{local_source_id, remote_source_id};
 use crate::remote_server::{ConnectionStatus, RemoteStream};
+use crate::ui::presentation::display_path;
 use egui::{CollapsingHeader, Color32, RichText, Ui};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// How long a file size shown in the explorer stays cached
+const SIZE_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// File sizes looked up lazily when a source is drawn and refreshed at
+/// most once per interval, so the explorer doesn't stat every frame
+#[derive(Default)]
+struct SizeCache {
+    /// Size (None if the file can't be read) and when it was looked up
+    sizes: HashMap<PathBuf, (Option<u64>, Instant)>,
+}
+
+impl SizeCache {
+    /// Size of a file, looking it up again once the cached value is stale
+    fn size(&mut self, path: &Path, now: Instant) -> Option<u64> {
+        if let Some((size, checked)) = self.sizes.get(path) {
+            if now.duration_since(*checked) < SIZE_REFRESH_INTERVAL {
+                return *size;
+            }
+        }
+        let size = fs::metadata(path).ok().map(|m| m.len());
+        self.sizes.insert(path.to_path_buf(), (size, now));
+        size
+    }
+}
 
 /// Explorer panel state
 pub struct ExplorerPanel {
     /// Recent local files
     pub local_files: Vec<PathBuf>,
+    /// Workspace folders and the log files found in them
+    pub workspaces: Vec<(PathBuf, Vec<PathBuf>)>,
     /// Remote streams
     pub remote_streams: Vec<RemoteStream>,
     /// Android devices
     pub android_devices: Vec<AndroidDevice>,
+    /// User-assigned source aliases (path -> alias)
+    pub aliases: HashMap<String, String>,
+    /// Source whose alias is being edited inline
+    renaming: Option<PathBuf>,
+    /// Alias input for inline rename
+    rename_input: String,
+    /// Show each source's size next to its name
+    pub show_sizes: bool,
+    /// Cached source sizes
+    sizes: SizeCache,
 }
 
 impl Default for ExplorerPanel {
@@ -30,8 +71,70 @@ impl ExplorerPanel {
     pub fn new() -> Self {
         Self {
             local_files: Vec::new(),
+            workspaces: Vec::new(),
             remote_streams: Vec::new(),
             android_devices: Vec::new(),
+            aliases: HashMap::new(),
+            renaming: None,
+            rename_input: String::new(),
+            show_sizes: true,
+            sizes: SizeCache::default(),
+        }
+    }
+
+    /// Show a workspace folder with its log files, replacing its old listing
+    pub fn set_workspace(&mut self, dir: PathBuf, files: Vec<PathBuf>) {
+        match self.workspaces.iter_mut().find(|(d, _)| *d == dir) {
+            Some((_, existing)) => *existing = files,
+            None => self.workspaces.push((dir, files)),
+        }
+    }
+
+    /// Get the alias for a source path
+    fn alias_for(&self, path: &Path) -> Option<&String> {
+        self.aliases.get(&path.to_string_lossy().to_string())
+    }
+
+    /// Show the inline rename editor for a source.
+    /// Returns Some(alias) when the edit is committed (None alias clears it).
+    fn show_rename_editor(&mut self, ui: &mut Ui) -> Option<Option<String>> {
+        let mut result = None;
+        ui.horizontal(|ui| {
+            ui.add_space(4.0);
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut self.rename_input)
+                    .desired_width(ui.available_width() - 8.0)
+                    .font(egui::TextStyle::Small),
+            );
+            if !response.has_focus() && !response.lost_focus() {
+                response.request_focus();
+            }
+            if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                self.renaming = None;
+            } else if response.lost_focus() {
+                // Enter or click-away commits the edit
+                result = Some(Some(self.rename_input.clone()));
+                self.renaming = None;
+            }
+        });
+        result
+    }
+
+    /// Begin inline rename for a source
+    fn start_rename(&mut self, path: &Path, current_name: &str) {
+        self.renaming = Some(path.to_path_buf());
+        self.rename_input = current_name.to_string();
+    }
+
+    /// Draw a source's size after its name, if enabled
+    fn show_size(&mut self, ui: &mut Ui, path: &Path) {
+        if !self.show_sizes {
+            return;
+        }
+        if let Some(size) = self.sizes.size(path, Instant::now()) {
+            ui.label(RichText::new(format_bytes(size)).weak().size(10.0));
+            // Pick up growth without waiting for unrelated input
+            ui.ctx().request_repaint_after(SIZE_REFRESH_INTERVAL);
         }
     }
 
@@ -71,6 +174,15 @@ impl ExplorerPanel {
                             action = ExplorerAction::OpenSourcePicker;
                         }
                         btn.on_hover_text(t::open_source());
+
+                        let merge_btn = ui.add(
+                            egui::Button::new(RichText::new("🔀").size(14.0))
+                                .frame(false)
+                        );
+                        if merge_btn.clicked() {
+                            action = ExplorerAction::MergeSources;
+                        }
+                        merge_btn.on_hover_text(t::merge_sources_tooltip());
                     });
                 });
 
@@ -86,22 +198,33 @@ impl ExplorerPanel {
                             ui.label(RichText::new(t::no_recent_files()).weak().italics().small());
                         } else {
                             for path in &self.local_files.clone() {
-                                let name = path
-                                    .file_name()
-                                    .map(|n| n.to_string_lossy().to_string())
-                                    .unwrap_or_else(|| path.display().to_string());
+                                let name = self.alias_for(path).cloned().unwrap_or_else(|| {
+                                    path.file_name()
+                                        .map(|n| n.to_string_lossy().to_string())
+                                        .unwrap_or_else(|| path.display().to_string())
+                                });
+
+                                if self.renaming.as_ref() == Some(path) {
+                                    if let Some(alias) = self.show_rename_editor(ui) {
+                                        action = ExplorerAction::SetAlias(path.clone(), alias);
+                                    }
+                                    continue;
+                                }
 
                                 let response = ui.horizontal(|ui| {
                                     ui.add_space(4.0);
                                     ui.label(RichText::new("📄").size(11.0));
-                                    ui.selectable_label(false, RichText::new(&name).size(11.0))
+                                    let response =
+                                        ui.selectable_label(false, RichText::new(&name).size(11.0));
+                                    self.show_size(ui, path);
+                                    response
                                 }).inner;
 
                                 if response.clicked() {
                                     action = ExplorerAction::OpenLocalFile(path.clone());
                                 }
 
-                                let response = response.on_hover_text(path.display().to_string());
+                                let response = response.on_hover_text(display_path(path));
 
                                 // Context menu for file
                                 response.context_menu(|ui| {
@@ -129,6 +252,25 @@ impl ExplorerPanel {
                                         ui.close();
                                     }
 
+                                    if ui.button(format!("🔗  {}", t::copy_source_id())).clicked() {
+                                        action = ExplorerAction::CopySourceId(local_source_id(path));
+                                        ui.close();
+                                    }
+
+                                    ui.separator();
+
+                                    if ui.button(format!("✏  {}", t::rename())).clicked() {
+                                        self.start_rename(path, &name);
+                                        ui.close();
+                                    }
+
+                                    if self.alias_for(path).is_some()
+                                        && ui.button(format!("↺  {}", t::reset_name())).clicked()
+                                    {
+                                        action = ExplorerAction::SetAlias(path.clone(), None);
+                                        ui.close();
+                                    }
+
                                     ui.separator();
 
                                     #[cfg(target_os = "macos")]
@@ -168,6 +310,69 @@ impl ExplorerPanel {
 
                 ui.add_space(8.0);
 
+                // WORKSPACES section
+                if !self.workspaces.is_empty() {
+                    CollapsingHeader::new(
+                        RichText::new(format!("🗂 {}", t::workspaces())).size(12.0),
+                    )
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        for (dir, files) in &self.workspaces {
+                            let name = dir
+                                .file_name()
+                                .map(|n| n.to_string_lossy().to_string())
+                                .unwrap_or_else(|| dir.display().to_string());
+                            let header = CollapsingHeader::new(
+                                RichText::new(format!("📁 {} ({})", name, files.len())).size(11.0),
+                            )
+                            .id_salt(dir)
+                            .show(ui, |ui| {
+                                if files.is_empty() {
+                                    ui.label(
+                                        RichText::new(t::no_log_files_in_workspace())
+                                            .weak()
+                                            .italics()
+                                            .small(),
+                                    );
+                                }
+                                for path in files {
+                                    let label = path.strip_prefix(dir).unwrap_or(path);
+                                    let response = ui
+                                        .selectable_label(
+                                            false,
+                                            RichText::new(label.display().to_string()).size(11.0),
+                                        )
+                                        .on_hover_text(display_path(path));
+                                    if response.clicked() {
+                                        action = ExplorerAction::OpenLocalFile(path.clone());
+                                    }
+                                }
+                            });
+
+                            header
+                                .header_response
+                                .on_hover_text(display_path(dir))
+                                .context_menu(|ui| {
+                                    if !files.is_empty()
+                                        && ui
+                                            .button(format!("📂  {}", t::folder_drop_open_all()))
+                                            .clicked()
+                                    {
+                                        action = ExplorerAction::OpenFiles(files.clone());
+                                        ui.close();
+                                    }
+                                    if ui.button(format!("✕  {}", t::remove_workspace())).clicked()
+                                    {
+                                        action = ExplorerAction::RemoveWorkspace(dir.clone());
+                                        ui.close();
+                                    }
+                                });
+                        }
+                    });
+
+                    ui.add_space(8.0);
+                }
+
                 // ANDROID DEVICES section
                 let online_devices = self.android_devices.iter().filter(|d| d.is_online).count();
                 let devices_header = if online_devices > 0 {
@@ -280,8 +485,9 @@ impl ExplorerPanel {
                             ui.label(RichText::new(t::waiting_for_connections()).weak().italics().small());
                         } else {
                             // Group streams by IP address
+                            let remote_streams = self.remote_streams.clone();
                             let mut streams_by_ip: HashMap<String, Vec<&RemoteStream>> = HashMap::new();
-                            for stream in &self.remote_streams {
+                            for stream in &remote_streams {
                                 let ip = stream.ip_address();
                                 streams_by_ip.entry(ip).or_default().push(stream);
                             }
@@ -309,6 +515,21 @@ impl ExplorerPanel {
                                 .default_open(true)
                                 .show(ui, |ui| {
                                     for stream in streams {
+                                        let stream_name = self
+                                            .alias_for(&stream.cache_path)
+                                            .cloned()
+                                            .unwrap_or_else(|| stream.project_name.clone());
+
+                                        if self.renaming.as_ref() == Some(&stream.cache_path) {
+                                            if let Some(alias) = self.show_rename_editor(ui) {
+                                                action = ExplorerAction::SetAlias(
+                                                    stream.cache_path.clone(),
+                                                    alias,
+                                                );
+                                            }
+                                            continue;
+                                        }
+
                                         let (status_icon, status_color) = match stream.status {
                                             ConnectionStatus::Online => {
                                                 ("●", Color32::from_rgb(50, 205, 50))
@@ -319,21 +540,59 @@ impl ExplorerPanel {
                                         let response = ui.horizontal(|ui| {
                                             ui.add_space(8.0);
                                             ui.label(RichText::new(status_icon).color(status_color).size(8.0));
-                                            ui.selectable_label(
+                                            let response = ui.selectable_label(
                                                 false,
-                                                RichText::new(&stream.project_name).size(11.0),
-                                            )
+                                                RichText::new(&stream_name).size(11.0),
+                                            );
+                                            self.show_size(ui, &stream.cache_path);
+                                            response
                                         }).inner;
 
                                         if response.clicked() {
                                             action = ExplorerAction::OpenRemoteStream((*stream).clone());
                                         }
 
-                                        response.on_hover_ui(|ui| {
+                                        let response = response.on_hover_ui(|ui| {
                                             ui.label(format!("{}: {}", t::project(), stream.project_name));
                                             ui.label(format!("{}: {}", t::address(), stream.remote_addr));
                                             ui.label(format!("{}: {:?}", t::status(), stream.status));
                                             ui.label(format!("{}: {}", t::received(), format_bytes(stream.bytes_received)));
+                                            let metadata = &stream.metadata;
+                                            if let Some(hostname) = &metadata.hostname {
+                                                ui.label(format!("{}: {}", t::hostname(), hostname));
+                                            }
+                                            if let Some(version) = &metadata.app_version {
+                                                ui.label(format!("{}: {}", t::app_version(), version));
+                                            }
+                                            if let Some(format) = &metadata.log_format {
+                                                ui.label(format!("{}: {}", t::log_format(), format));
+                                            }
+                                        });
+
+                                        response.context_menu(|ui| {
+                                            ui.set_min_width(160.0);
+
+                                            if ui.button(format!("🔗  {}", t::copy_source_id())).clicked() {
+                                                action = ExplorerAction::CopySourceId(remote_source_id(stream));
+                                                ui.close();
+                                            }
+
+                                            ui.separator();
+
+                                            if ui.button(format!("✏  {}", t::rename())).clicked() {
+                                                self.start_rename(&stream.cache_path, &stream_name);
+                                                ui.close();
+                                            }
+
+                                            if self.alias_for(&stream.cache_path).is_some()
+                                                && ui.button(format!("↺  {}", t::reset_name())).clicked()
+                                            {
+                                                action = ExplorerAction::SetAlias(
+                                                    stream.cache_path.clone(),
+                                                    None,
+                                                );
+                                                ui.close();
+                                            }
                                         });
 
                                         // Show bytes received
@@ -358,17 +617,27 @@ impl ExplorerPanel {
 pub enum ExplorerAction {
     None,
     OpenLocalFile(PathBuf),
+    /// Open several files, e.g. all log files of a workspace
+    OpenFiles(Vec<PathBuf>),
+    /// Unpin a workspace folder
+    RemoveWorkspace(PathBuf),
     OpenRemoteStream(RemoteStream),
     OpenSourcePicker,
     OpenSourcePickerAndroid,
+    /// Merge several files by timestamp into one read-only tab
+    MergeSources,
     OpenInSplit(PathBuf),
     CopyAbsolutePath(PathBuf),
     CopyFilename(PathBuf),
+    /// Copy the MCP source ID of a local file or stream
+    CopySourceId(String),
     RevealInFinder(PathBuf),
     RemoveFromRecent(PathBuf),
     ClearRecentFiles,
     OpenAndroidLogcat(AndroidDevice),
     DisconnectAndroidDevice(String),
+    /// Set or clear the alias of a source (local file or stream cache path)
+    SetAlias(PathBuf, Option<String>),
 }
 
 /// Format bytes to human-readable string
@@ -387,3 +656,34 @@ fn format_bytes(bytes: u64) -> String {
         format!("{} B", bytes)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KB");
+        assert_eq!(format_bytes(250 * 1024 * 1024), "250.0 MB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3.0 GB");
+    }
+
+    #[test]
+    fn test_size_cache_refreshes_after_interval() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        fs::write(&path, "0123456789").unwrap();
+
+        let mut cache = SizeCache::default();
+        let start = Instant::now();
+        assert_eq!(cache.size(&path, start), Some(10));
+
+        // Growth is not picked up until the cached size is stale
+        fs::write(&path, "01234567890123456789").unwrap();
+        assert_eq!(cache.size(&path, start + Duration::from_secs(1)), Some(10));
+        assert_eq!(cache.size(&path, start + SIZE_REFRESH_INTERVAL), Some(20));
+
+        assert_eq!(cache.size(&dir.path().join("missing.log"), start), None);
+    }
+}
@@ -0,0 +1,177 @@
+//! Folder drop handling
+//!
+//! Expands a folder dropped on the window into the log files it contains,
+//! searching subfolders up to a depth cap, and asks for confirmation before
+//! opening a large number of files at once. Folders can instead be pinned
+//! in the explorer as workspaces listing the same files.
+
+use crate::config::{FolderDropBehavior, FolderDropConfig};
+use crate::i18n::Translations as t;
+use egui::RichText;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Whether a path looks like a log file (including rotated ones like `app.log.1`)
+fn is_log_file(path: &Path) -> bool {
+    let ext_matches = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| matches!(ext.to_lowercase().as_str(), "log" | "txt" | "json"));
+    let rotated = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|name| name.to_lowercase().contains(".log."));
+    ext_matches || rotated
+}
+
+/// Collect the log files in `dir`, descending at most `max_depth` levels of
+/// subfolders (0 = only the folder itself). Hidden entries are skipped.
+pub fn collect_log_files(dir: &Path, max_depth: usize) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut pending = vec![(dir.to_path_buf(), 0)];
+
+    while let Some((current, depth)) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&current) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            if entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            let path = entry.path();
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                if depth < max_depth {
+                    pending.push((path, depth + 1));
+                }
+            } else if is_log_file(&path) {
+                files.push(path);
+            }
+        }
+    }
+
+    files.sort();
+    files
+}
+
+/// The files of a dropped folder to open, according to the configuration
+/// (a workspace lists all of them)
+pub fn files_to_open(dir: &Path, config: &FolderDropConfig) -> Vec<PathBuf> {
+    let files = collect_log_files(dir, config.max_depth);
+    match config.behavior {
+        FolderDropBehavior::OpenAll | FolderDropBehavior::OpenAsWorkspace => files,
+        FolderDropBehavior::OpenNewest => files
+            .into_iter()
+            .max_by_key(|path| fs::metadata(path).and_then(|m| m.modified()).ok())
+            .into_iter()
+            .collect(),
+    }
+}
+
+/// Confirmation before opening many files from a dropped folder
+#[derive(Default)]
+pub struct FolderDropDialog {
+    /// Dropped folder and the files waiting for confirmation
+    pending: Option<(PathBuf, Vec<PathBuf>)>,
+}
+
+impl FolderDropDialog {
+    /// Ask whether to open `files` found in `dir`
+    pub fn ask(&mut self, dir: PathBuf, files: Vec<PathBuf>) {
+        self.pending = Some((dir, files));
+    }
+
+    /// Render the dialog. Returns the files to open once confirmed.
+    pub fn show(&mut self, ctx: &egui::Context) -> Option<Vec<PathBuf>> {
+        let (dir, files) = self.pending.as_ref()?;
+
+        let mut confirmed = false;
+        let mut cancelled = false;
+        egui::Window::new(t::folder_drop_title())
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(
+                    RichText::new(format!("{} ({})", t::folder_drop_confirm(), files.len()))
+                        .size(14.0),
+                );
+                ui.label(RichText::new(dir.display().to_string()).weak());
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    if ui.button(t::folder_drop_open_all()).clicked() {
+                        confirmed = true;
+                    }
+                    if ui.button(t::cancel()).clicked() {
+                        cancelled = true;
+                    }
+                });
+            });
+
+        if confirmed {
+            return self.pending.take().map(|(_, files)| files);
+        }
+        if cancelled {
+            self.pending = None;
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_folder_expansion_respects_depth_cap() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        for path in [
+            "app.log",
+            "app.log.1",
+            "readme.md",
+            ".hidden/secret.log",
+            "a/worker.log",
+            "a/b/deep.txt",
+            "a/b/c/deeper.log",
+        ] {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "INFO hello\n").unwrap();
+        }
+
+        let names = |max_depth| {
+            collect_log_files(root, max_depth)
+                .iter()
+                .map(|p| {
+                    p.strip_prefix(root)
+                        .unwrap()
+                        .to_string_lossy()
+                        .replace('\\', "/")
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(0), ["app.log", "app.log.1"]);
+        assert_eq!(names(1), ["a/worker.log", "app.log", "app.log.1"]);
+        assert_eq!(
+            names(2),
+            ["a/b/deep.txt", "a/worker.log", "app.log", "app.log.1"]
+        );
+        assert_eq!(names(3).len(), 5);
+
+        let newest = FolderDropConfig {
+            behavior: FolderDropBehavior::OpenNewest,
+            max_depth: 3,
+            ..Default::default()
+        };
+        assert_eq!(files_to_open(root, &newest).len(), 1);
+
+        let workspace = FolderDropConfig {
+            behavior: FolderDropBehavior::OpenAsWorkspace,
+            ..newest
+        };
+        assert_eq!(files_to_open(root, &workspace).len(), 5);
+    }
+}
@@ -1,20 +1,46 @@
 //! Global search panel for sidebar
+//!
+//! Searches run on a background thread over a snapshot of the buffer and
+//! stream their results back in chunks, so huge buffers don't stall the UI.
 
 use crate::i18n::Translations;
 use crate::log_buffer::LogBuffer;
-use crate::log_entry::LogLevel;
+use crate::log_entry::{LineText, LogLevel};
+use crate::ui::tab_bar::TabId;
+use crossbeam_channel::{bounded, Receiver, TryRecvError};
 use eframe::egui::{self, Color32, RichText, ScrollArea, TextEdit, Ui};
 use regex::Regex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+/// Lines scanned by the search thread between progress updates
+const SEARCH_CHUNK_LINES: usize = 5_000;
+
+/// Chunks the search thread may run ahead of the UI before it waits
+const SEARCH_CHUNKS_BUFFERED: usize = 16;
+
+/// An open tab's buffer to search
+pub struct SearchSource<'a> {
+    /// Tab the buffer belongs to
+    pub tab_id: TabId,
+    /// Tab name shown above its results
+    pub name: &'a str,
+    /// Lines to search
+    pub buffer: &'a LogBuffer,
+}
 
 /// Search result item
 #[derive(Debug, Clone)]
 pub struct SearchResultItem {
+    /// Tab the line belongs to
+    pub tab_id: TabId,
     /// Line number in the file
     pub line_number: usize,
     /// Buffer index
     pub buffer_index: usize,
-    /// Line content
-    pub content: String,
+    /// Line content (shares the buffer's text)
+    pub content: LineText,
     /// Log level if detected
     pub level: Option<LogLevel>,
     /// Match positions (start, end) in content
@@ -23,6 +49,127 @@ pub struct SearchResultItem {
     pub bookmarked: bool,
 }
 
+/// Results of one chunk scanned by the search thread
+struct SearchChunk {
+    /// Matching lines found in the chunk
+    results: Vec<SearchResultItem>,
+    /// Lines scanned so far, including this chunk
+    scanned: usize,
+}
+
+/// A search running on a background thread
+pub struct BackgroundSearch {
+    /// Chunks delivered by the search thread
+    rx: Receiver<SearchChunk>,
+    /// Set to stop the search thread early
+    cancel: Arc<AtomicBool>,
+    /// Lines to scan
+    total: usize,
+    /// Lines scanned so far
+    scanned: usize,
+    /// Whether the search thread has finished
+    done: bool,
+}
+
+impl BackgroundSearch {
+    /// Search `lines` on a new thread, stopping after `max_results` matches
+    pub fn start(lines: Vec<SearchResultItem>, regex: Regex, max_results: usize) -> Self {
+        let (tx, rx) = bounded(SEARCH_CHUNKS_BUFFERED);
+        let cancel = Arc::new(AtomicBool::new(false));
+        let total = lines.len();
+
+        let cancel_flag = cancel.clone();
+        thread::spawn(move || {
+            let mut found = 0;
+            let mut scanned = 0;
+            for chunk in lines.chunks(SEARCH_CHUNK_LINES) {
+                if cancel_flag.load(Ordering::Relaxed) || found >= max_results {
+                    break;
+                }
+                let mut results = Vec::new();
+                for line in chunk {
+                    if found >= max_results {
+                        break;
+                    }
+                    let matches: Vec<(usize, usize)> = regex
+                        .find_iter(&line.content)
+                        .map(|m| (m.start(), m.end()))
+                        .collect();
+                    if !matches.is_empty() {
+                        results.push(SearchResultItem {
+                            match_positions: matches,
+                            ..line.clone()
+                        });
+                        found += 1;
+                    }
+                }
+                scanned += chunk.len();
+                // The panel dropped the search
+                if tx.send(SearchChunk { results, scanned }).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Self {
+            rx,
+            cancel,
+            total,
+            scanned: 0,
+            done: false,
+        }
+    }
+
+    /// Stop the search thread; results found so far are kept
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    /// Collect the results delivered since the last poll
+    pub fn poll(&mut self) -> Vec<SearchResultItem> {
+        let mut results = Vec::new();
+        loop {
+            match self.rx.try_recv() {
+                Ok(chunk) => {
+                    self.scanned = chunk.scanned;
+                    results.extend(chunk.results);
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.done = true;
+                    break;
+                }
+            }
+        }
+        results
+    }
+
+    /// Fraction of the lines scanned so far
+    pub fn progress(&self) -> f32 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.scanned as f32 / self.total as f32
+        }
+    }
+
+    /// Lines scanned so far
+    pub fn scanned(&self) -> usize {
+        self.scanned
+    }
+
+    /// Whether the search thread has finished
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+}
+
+impl Drop for BackgroundSearch {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
 /// Global search panel state
 pub struct GlobalSearchPanel {
     /// Search query
@@ -35,6 +182,8 @@ pub struct GlobalSearchPanel {
     pub whole_word: bool,
     /// Include bookmarks only
     pub bookmarks_only: bool,
+    /// Search every open tab instead of only the active one
+    pub all_tabs: bool,
     /// Filter by log levels
     pub level_filter: Vec<LogLevel>,
     /// Show level filter dropdown
@@ -50,6 +199,10 @@ pub struct GlobalSearchPanel {
     pub max_results: usize,
     /// Whether dark theme is enabled
     pub dark_theme: bool,
+    /// Search in progress, if any
+    running: Option<BackgroundSearch>,
+    /// Names of the tabs the results came from
+    tab_names: Vec<(TabId, String)>,
 }
 
 impl Default for GlobalSearchPanel {
@@ -66,6 +219,7 @@ impl GlobalSearchPanel {
             use_regex: false,
             whole_word: false,
             bookmarks_only: false,
+            all_tabs: false,
             level_filter: vec![],
             show_level_filter: false,
             results: Vec::new(),
@@ -73,6 +227,8 @@ impl GlobalSearchPanel {
             dirty: false,
             max_results: 1000,
             dark_theme: true,
+            running: None,
+            tab_names: Vec::new(),
         }
     }
 
@@ -81,10 +237,14 @@ impl GlobalSearchPanel {
         self.dark_theme = dark;
     }
 
-    /// Execute search on buffer
-    pub fn search(&mut self, buffer: &LogBuffer) {
+    /// Start searching the active tab (or every tab in all-tabs mode) in
+    /// the background, cancelling any search still running
+    pub fn search(&mut self, sources: &[SearchSource], active: Option<TabId>) {
+        // Dropping the previous search cancels it
+        self.running = None;
         self.results.clear();
         self.selected_index = None;
+        self.tab_names.clear();
 
         if self.query.is_empty() {
             self.dirty = false;
@@ -101,11 +261,26 @@ impl GlobalSearchPanel {
             }
         };
 
-        for (idx, entry) in buffer.iter().enumerate() {
-            if self.results.len() >= self.max_results {
-                break;
-            }
+        // Snapshot the candidate lines, tab by tab so results come out
+        // grouped; the snapshot shares the line text, and matching happens
+        // on the search thread
+        let mut lines = Vec::new();
+        let sources = sources
+            .iter()
+            .filter(|source| self.all_tabs || Some(source.tab_id) == active);
+        for source in sources {
+            self.tab_names
+                .push((source.tab_id, source.name.to_string()));
+            self.collect_lines(source, &mut lines);
+        }
 
+        self.running = Some(BackgroundSearch::start(lines, regex, self.max_results));
+        self.dirty = false;
+    }
+
+    /// Add the lines of a tab that pass the level and bookmark filters
+    fn collect_lines(&self, source: &SearchSource, lines: &mut Vec<SearchResultItem>) {
+        for (idx, entry) in source.buffer.iter().enumerate() {
             // Level filter
             if !self.level_filter.is_empty() {
                 if let Some(level) = entry.level {
@@ -122,25 +297,37 @@ impl GlobalSearchPanel {
                 continue;
             }
 
-            // Search match
-            let matches: Vec<(usize, usize)> = regex
-                .find_iter(&entry.content)
-                .map(|m| (m.start(), m.end()))
-                .collect();
-
-            if !matches.is_empty() {
-                self.results.push(SearchResultItem {
-                    line_number: entry.line_number,
-                    buffer_index: idx,
-                    content: entry.content.clone(),
-                    level: entry.level,
-                    match_positions: matches,
-                    bookmarked: entry.bookmarked,
-                });
-            }
+            lines.push(SearchResultItem {
+                tab_id: source.tab_id,
+                line_number: entry.line_number,
+                buffer_index: idx,
+                content: entry.content.clone(),
+                level: entry.level,
+                match_positions: Vec::new(),
+                bookmarked: entry.bookmarked,
+            });
         }
+    }
 
-        self.dirty = false;
+    /// Name of a searched tab
+    fn tab_name(&self, tab_id: TabId) -> &str {
+        self.tab_names
+            .iter()
+            .find(|(id, _)| *id == tab_id)
+            .map_or("", |(_, name)| name.as_str())
+    }
+
+    /// Take the results the background search delivered since the last frame
+    fn poll_search(&mut self, ctx: &egui::Context) {
+        let Some(running) = self.running.as_mut() else {
+            return;
+        };
+        self.results.extend(running.poll());
+        if running.is_done() {
+            self.running = None;
+        } else {
+            ctx.request_repaint();
+        }
     }
 
     /// Build regex from search config
@@ -173,13 +360,21 @@ impl GlobalSearchPanel {
         Regex::new(&pattern).ok()
     }
 
-    /// Show the panel UI
-    pub fn show(&mut self, ui: &mut Ui, buffer: &LogBuffer) -> GlobalSearchAction {
+    /// Show the panel UI. `sources` are all open tabs; only the active one
+    /// is searched unless all-tabs mode is on.
+    pub fn show(
+        &mut self,
+        ui: &mut Ui,
+        sources: &[SearchSource],
+        active: Option<TabId>,
+    ) -> GlobalSearchAction {
         let mut action = GlobalSearchAction::None;
 
         // Set minimum width to prevent panel from shrinking
         ui.set_min_width(200.0);
 
+        self.poll_search(ui.ctx());
+
         ui.vertical(|ui| {
             ui.add_space(8.0);
 
@@ -204,7 +399,7 @@ impl GlobalSearchPanel {
 
             // Press Enter to search
             if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
-                self.search(buffer);
+                self.search(sources, active);
             }
 
             ui.add_space(4.0);
@@ -248,6 +443,14 @@ impl GlobalSearchPanel {
                 }
             });
 
+            if ui
+                .checkbox(&mut self.all_tabs, Translations::search_all_tabs())
+                .on_hover_text(Translations::search_all_tabs_hint())
+                .changed()
+            {
+                self.dirty = true;
+            }
+
             ui.add_space(4.0);
 
             // Level filter
@@ -304,7 +507,7 @@ impl GlobalSearchPanel {
                     .clicked()
                     || self.dirty
                 {
-                    self.search(buffer);
+                    self.search(sources, active);
                 }
 
                 if !self.results.is_empty() {
@@ -316,15 +519,38 @@ impl GlobalSearchPanel {
                 }
             });
 
+            if let Some(running) = &self.running {
+                let mut cancel = false;
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::ProgressBar::new(running.progress())
+                            .desired_width(ui.available_width() - 28.0)
+                            .text(format!(
+                                "{} {}",
+                                Translations::global_search_scanning(),
+                                running.scanned()
+                            )),
+                    );
+                    cancel = ui
+                        .small_button("✕")
+                        .on_hover_text(Translations::cancel())
+                        .clicked();
+                });
+                if cancel {
+                    running.cancel();
+                }
+            }
+
             ui.add_space(8.0);
             ui.separator();
             ui.add_space(4.0);
 
             // Results list
             if self.results.is_empty() {
-                if !self.query.is_empty() {
+                // While searching, results appear as they are found
+                if self.running.is_none() && !self.query.is_empty() {
                     ui.label(RichText::new(Translations::global_no_results()).color(Color32::GRAY));
-                } else {
+                } else if self.query.is_empty() {
                     ui.label(
                         RichText::new(Translations::enter_search_query()).color(Color32::GRAY),
                     );
@@ -333,7 +559,26 @@ impl GlobalSearchPanel {
                 ScrollArea::vertical()
                     .auto_shrink([false, false])
                     .show(ui, |ui| {
+                        let mut current_tab = None;
                         for (idx, result) in self.results.iter().enumerate() {
+                            // Group header whenever the results move on to another tab
+                            if self.all_tabs && current_tab != Some(result.tab_id) {
+                                current_tab = Some(result.tab_id);
+                                let count = self.results[idx..]
+                                    .iter()
+                                    .take_while(|r| r.tab_id == result.tab_id)
+                                    .count();
+                                ui.add_space(4.0);
+                                ui.label(
+                                    RichText::new(format!(
+                                        "📄 {} ({})",
+                                        self.tab_name(result.tab_id),
+                                        count
+                                    ))
+                                    .strong(),
+                                );
+                            }
+
                             let is_selected = self.selected_index == Some(idx);
 
                             let response = ui
@@ -342,7 +587,10 @@ impl GlobalSearchPanel {
 
                             if response.clicked() {
                                 self.selected_index = Some(idx);
-                                action = GlobalSearchAction::JumpToLine(result.buffer_index);
+                                action = GlobalSearchAction::JumpToLine(
+                                    result.tab_id,
+                                    result.buffer_index,
+                                );
                             }
                         }
                     });
@@ -431,7 +679,8 @@ impl GlobalSearchPanel {
 #[derive(Debug, Clone)]
 pub enum GlobalSearchAction {
     None,
-    JumpToLine(usize),
+    /// Show a line (buffer index) of a tab
+    JumpToLine(TabId, usize),
 }
 
 /// Get colors for log level
@@ -454,3 +703,108 @@ fn truncate_string(s: &str, max_len: usize) -> String {
         format!("{}...", &s[..max_len])
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(count: usize) -> Vec<SearchResultItem> {
+        (0..count)
+            .map(|i| SearchResultItem {
+                tab_id: 0,
+                line_number: i + 1,
+                buffer_index: i,
+                content: format!("request {} took {}ms", i, i % 1000).into(),
+                level: None,
+                match_positions: Vec::new(),
+                bookmarked: false,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_background_search_reports_progress_and_cancels() {
+        let regex = Regex::new("took 999ms").unwrap();
+        let total = SEARCH_CHUNK_LINES * SEARCH_CHUNKS_BUFFERED * 4;
+
+        // Runs to completion with the matches in order
+        let mut search = BackgroundSearch::start(lines(total), regex.clone(), usize::MAX);
+        let mut results = Vec::new();
+        while !search.is_done() {
+            results.extend(search.poll());
+        }
+        assert_eq!(search.progress(), 1.0);
+        assert_eq!(results.len(), total / 1000);
+        assert_eq!(results[0].line_number, 1000);
+        assert_eq!(results[0].match_positions, vec![(12, 22)]);
+
+        // Cancelled after the first chunk arrives
+        let mut search = BackgroundSearch::start(lines(total), regex, usize::MAX);
+        while search.scanned() == 0 {
+            search.poll();
+        }
+        assert!(search.progress() > 0.0);
+        search.cancel();
+        while !search.is_done() {
+            search.poll();
+        }
+        // The thread can only run a bounded number of chunks ahead
+        assert!(search.scanned() < total);
+        assert!(search.progress() < 1.0);
+    }
+
+    /// Wait for the running search and list (tab, line number) of its results
+    fn finish(panel: &mut GlobalSearchPanel) -> Vec<(TabId, usize)> {
+        while let Some(running) = panel.running.as_mut() {
+            panel.results.extend(running.poll());
+            if running.is_done() {
+                panel.running = None;
+            }
+        }
+        panel
+            .results
+            .iter()
+            .map(|r| (r.tab_id, r.line_number))
+            .collect()
+    }
+
+    #[test]
+    fn test_all_tabs_mode_groups_results_by_tab() {
+        use crate::log_entry::LogEntry;
+
+        let buffer = |lines: &[&str]| {
+            let mut buffer = LogBuffer::new();
+            for (i, line) in lines.iter().enumerate() {
+                buffer.push(LogEntry::new(i + 1, line.to_string(), 0));
+            }
+            buffer
+        };
+        let api = buffer(&["GET /users 200", "GET /orders 504 upstream timeout"]);
+        let db = buffer(&["query timeout", "connection ok", "retry timeout"]);
+        let sources = [
+            SearchSource {
+                tab_id: 1,
+                name: "api.log",
+                buffer: &api,
+            },
+            SearchSource {
+                tab_id: 2,
+                name: "db.log",
+                buffer: &db,
+            },
+        ];
+
+        let mut panel = GlobalSearchPanel::new();
+        panel.query = "timeout".to_string();
+
+        // Only the active tab by default
+        panel.search(&sources, Some(2));
+        assert_eq!(finish(&mut panel), [(2, 1), (2, 3)]);
+
+        panel.all_tabs = true;
+        panel.search(&sources, Some(2));
+        assert_eq!(finish(&mut panel), [(1, 2), (2, 1), (2, 3)]);
+        assert_eq!(panel.tab_name(1), "api.log");
+        assert_eq!(panel.tab_name(2), "db.log");
+    }
+}
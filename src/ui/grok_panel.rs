@@ -3,11 +3,17 @@
 //! Provides UI for configuring and testing grok patterns for log parsing.
 
 use crate::config::FileGrokConfig;
-use crate::grok_parser::{CustomPattern, GrokConfig, GrokParser};
+use crate::grok_parser::{
+    BuiltinPattern, CustomPattern, FieldColorRule, GrokConfig, GrokGlobRule, GrokParser,
+    PatternCandidate, PreProcessor, DETECT_MIN_MATCH_RATE,
+};
 use crate::i18n::Translations as t;
 use egui::{self, Color32, RichText, Ui};
 use std::path::PathBuf;
 
+/// Most sample lines previewed by the pattern tester
+const MAX_TESTER_LINES: usize = 200;
+
 /// Actions from the grok panel
 #[derive(Debug, Clone)]
 pub enum GrokPanelAction {
@@ -25,6 +31,14 @@ pub enum GrokPanelAction {
     },
     /// Request sample log lines for AI assist
     RequestSampleLines,
+    /// JSON mode toggled for the current file
+    JsonModeChanged(bool),
+    /// Score the builtin patterns against the current file
+    AutoDetectPattern,
+    /// Apply a builtin pattern to the current file
+    ApplyBuiltinPattern(BuiltinPattern),
+    /// Export the current file's parsed lines as CSV
+    ExportCsv,
 }
 
 /// State for editing a custom pattern
@@ -42,6 +56,8 @@ struct PatternEditor {
     display_template: String,
     /// Pre-processor to apply before Grok matching
     pre_processor: crate::grok_parser::PreProcessor,
+    /// Row colors set by numeric field thresholds
+    color_rules: Vec<FieldColorRule>,
     /// Whether we're editing an existing pattern
     editing_index: Option<usize>,
     /// Whether the editor is open
@@ -56,6 +72,7 @@ impl PatternEditor {
         self.example.clear();
         self.display_template.clear();
         self.pre_processor = crate::grok_parser::PreProcessor::None;
+        self.color_rules.clear();
         self.editing_index = None;
         self.is_open = false;
     }
@@ -67,9 +84,153 @@ impl PatternEditor {
         self.example = pattern.example.clone();
         self.display_template = pattern.display_template.clone();
         self.pre_processor = pattern.pre_processor.clone();
+        self.color_rules = pattern.color_rules.clone();
         self.editing_index = Some(index);
         self.is_open = true;
     }
+
+    /// The pattern as it would be saved
+    fn to_pattern(&self) -> CustomPattern {
+        CustomPattern {
+            name: self.name.clone(),
+            pattern: self.pattern.clone(),
+            description: self.description.clone(),
+            example: self.example.clone(),
+            enabled: true,
+            display_template: self.display_template.clone(),
+            pre_processor: self.pre_processor.clone(),
+            color_rules: self.color_rules.clone(),
+        }
+    }
+}
+
+/// Live preview of the edited pattern against sample lines
+#[derive(Debug, Clone, Default)]
+struct PatternTester {
+    /// Sample lines, one per line
+    samples: String,
+    /// Whether requested sample lines should replace `samples`
+    awaiting_samples: bool,
+    /// Pattern, template, pre-processor and samples the results are for
+    key: Option<(String, String, PreProcessor, String)>,
+    /// Result for each sample line
+    results: Vec<TesterResult>,
+    /// Compile error of the pattern
+    error: Option<String>,
+}
+
+/// Outcome of parsing one sample line
+#[derive(Debug, Clone)]
+struct TesterResult {
+    /// The sample line
+    line: String,
+    /// Captured fields sorted by name, `None` if the line didn't match
+    fields: Option<Vec<(String, String)>>,
+    /// Line formatted by the display template
+    formatted: Option<String>,
+}
+
+impl PatternTester {
+    /// Re-run the samples if the pattern or samples changed
+    fn refresh(&mut self, parser: &GrokParser, pattern: &CustomPattern) {
+        let is_current = self.key.as_ref().is_some_and(|(p, tpl, pre, samples)| {
+            *p == pattern.pattern
+                && *tpl == pattern.display_template
+                && *pre == pattern.pre_processor
+                && *samples == self.samples
+        });
+        if is_current {
+            return;
+        }
+        self.key = Some((
+            pattern.pattern.clone(),
+            pattern.display_template.clone(),
+            pattern.pre_processor.clone(),
+            self.samples.clone(),
+        ));
+        self.results.clear();
+        self.error = None;
+        if pattern.pattern.trim().is_empty() {
+            return;
+        }
+
+        // Same parse path as loaded files, so the preview can't drift from it
+        let preview = match parser.preview_parser(pattern) {
+            Ok(preview) => preview,
+            Err(e) => {
+                self.error = Some(format!("{:#}", e));
+                return;
+            }
+        };
+        self.results = self
+            .samples
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .take(MAX_TESTER_LINES)
+            .map(|line| {
+                let parsed = preview.parse_with_format(line);
+                let formatted = parsed
+                    .as_ref()
+                    .and_then(|(_, formatted)| formatted.as_ref())
+                    .map(|(text, _)| text.clone());
+                let fields = parsed.map(|(parsed, _)| {
+                    let mut fields: Vec<_> = parsed.fields.into_iter().collect();
+                    fields.sort();
+                    fields
+                });
+                TesterResult {
+                    line: line.to_string(),
+                    fields,
+                    formatted,
+                }
+            })
+            .collect();
+    }
+
+    /// Show which samples matched and what they captured
+    fn show_results(&self, ui: &mut Ui) {
+        if let Some(error) = &self.error {
+            ui.label(RichText::new(error).color(Color32::RED));
+            return;
+        }
+        if self.results.is_empty() {
+            return;
+        }
+
+        let matched = self.results.iter().filter(|r| r.fields.is_some()).count();
+        ui.label(
+            t::grok_tester_summary()
+                .replacen("{}", &matched.to_string(), 1)
+                .replacen("{}", &self.results.len().to_string(), 1),
+        );
+        for result in &self.results {
+            ui.horizontal(|ui| match &result.fields {
+                Some(_) => {
+                    ui.label(RichText::new("✓").color(Color32::from_rgb(100, 200, 100)));
+                    ui.label(RichText::new(&result.line).monospace());
+                }
+                None => {
+                    ui.label(RichText::new("✗").color(Color32::RED))
+                        .on_hover_text(t::grok_no_match());
+                    ui.label(RichText::new(&result.line).monospace().color(Color32::RED));
+                }
+            });
+            let Some(fields) = &result.fields else {
+                continue;
+            };
+            ui.indent(result.line.as_str(), |ui| {
+                if let Some(formatted) = &result.formatted {
+                    ui.label(RichText::new(format!("→ {}", formatted)).monospace());
+                }
+                let captured = fields
+                    .iter()
+                    .map(|(name, value)| format!("{}={}", name, value))
+                    .collect::<Vec<_>>()
+                    .join("  ");
+                ui.label(RichText::new(captured).monospace().small().weak());
+            });
+        }
+    }
 }
 
 /// Grok parser panel component
@@ -82,10 +243,18 @@ pub struct GrokPanel {
     pattern_editor: PatternEditor,
     /// AI assist state
     ai_assist: AiAssistState,
+    /// Live pattern tester in the editor
+    tester: PatternTester,
     /// Current file path (for per-file settings)
     pub current_file_path: Option<PathBuf>,
     /// Whether current file uses file-specific pattern
     pub use_file_specific: bool,
+    /// Whether the current file's lines are parsed as JSON objects
+    pub json_mode: bool,
+    /// Patterns applied automatically to files by glob
+    glob_rules: Vec<GrokGlobRule>,
+    /// Ranked builtin patterns from the last auto-detect, by file
+    detected_patterns: Option<(PathBuf, Vec<PatternCandidate>)>,
 }
 
 /// State for AI-assisted pattern generation
@@ -165,14 +334,19 @@ impl GrokPanel {
             selected_custom: None,
             pattern_editor: PatternEditor::default(),
             ai_assist: AiAssistState::default(),
+            tester: PatternTester::default(),
             current_file_path: None,
             use_file_specific: false,
+            json_mode: false,
+            glob_rules: Vec::new(),
+            detected_patterns: None,
         }
     }
 
     /// Load state from config
     pub fn load_from_config(&mut self, config: &GrokConfig) {
         self.enabled = config.enabled;
+        self.glob_rules = config.glob_rules.clone();
         // Custom pattern selection would need to be matched by name
     }
 
@@ -180,10 +354,20 @@ impl GrokPanel {
     pub fn save_to_config(&self, config: &mut GrokConfig) {
         config.enabled = self.enabled;
         config.builtin_pattern = None;
+        config.glob_rules = self.glob_rules.clone();
+    }
+
+    /// Set the ranked builtin patterns detected for a file
+    pub fn set_detected_patterns(&mut self, path: PathBuf, candidates: Vec<PatternCandidate>) {
+        self.detected_patterns = Some((path, candidates));
     }
 
     /// Set sample lines for AI assist
     pub fn set_sample_lines(&mut self, lines: Vec<String>) {
+        if self.tester.awaiting_samples {
+            self.tester.samples = lines.join("\n");
+            self.tester.awaiting_samples = false;
+        }
         self.ai_assist.sample_lines = lines;
         self.generate_ai_prompt();
     }
@@ -438,8 +622,40 @@ Output:
                     ui.add_space(8.0);
                 }
 
+                // JSON mode for the current file
+                if self.current_file_path.is_some() {
+                    if ui
+                        .checkbox(&mut self.json_mode, t::json_mode())
+                        .on_hover_text(t::json_mode_tooltip())
+                        .changed()
+                    {
+                        action = GrokPanelAction::JsonModeChanged(self.json_mode);
+                    }
+                    ui.horizontal(|ui| {
+                        if ui
+                            .button(t::grok_auto_detect())
+                            .on_hover_text(t::grok_auto_detect_tooltip())
+                            .clicked()
+                        {
+                            action = GrokPanelAction::AutoDetectPattern;
+                        }
+                        if ui
+                            .button(t::grok_export_csv())
+                            .on_hover_text(t::grok_export_csv_tooltip())
+                            .clicked()
+                        {
+                            action = GrokPanelAction::ExportCsv;
+                        }
+                    });
+                    self.show_detected_patterns(ui, &mut action);
+                    ui.add_space(8.0);
+                }
+
                 // Custom patterns section (main focus - for creating and editing patterns)
                 self.show_custom_patterns(ui, parser, &mut action);
+
+                ui.add_space(12.0);
+                self.show_glob_rules(ui, parser, &mut action);
             });
 
         // Pattern editor dialog
@@ -450,6 +666,37 @@ Output:
         action
     }
 
+    /// Show the ranked auto-detect candidates for the current file
+    fn show_detected_patterns(&self, ui: &mut Ui, action: &mut GrokPanelAction) {
+        let Some((path, candidates)) = &self.detected_patterns else {
+            return;
+        };
+        if self.current_file_path.as_ref() != Some(path) {
+            return;
+        }
+
+        if candidates
+            .first()
+            .is_none_or(|c| c.match_rate < DETECT_MIN_MATCH_RATE)
+        {
+            ui.label(RichText::new(t::grok_auto_detect_none()).weak().italics());
+        }
+        if candidates.is_empty() {
+            return;
+        }
+        ui.label(RichText::new(t::grok_auto_detect_candidates()).weak());
+        for candidate in candidates {
+            let label = format!(
+                "{}  {:.0}%",
+                candidate.pattern.display_name(),
+                candidate.match_rate * 100.0
+            );
+            if ui.small_button(label).clicked() {
+                *action = GrokPanelAction::ApplyBuiltinPattern(candidate.pattern);
+            }
+        }
+    }
+
     /// Show custom patterns
     fn show_custom_patterns(
         &mut self,
@@ -551,6 +798,60 @@ Output:
         }
     }
 
+    /// Show the rules applying a pattern to files by glob
+    fn show_glob_rules(&mut self, ui: &mut Ui, parser: &GrokParser, action: &mut GrokPanelAction) {
+        ui.label(RichText::new(t::grok_file_rules()).strong());
+        ui.label(RichText::new(t::grok_file_rules_hint()).weak().small());
+        ui.add_space(4.0);
+
+        let pattern_names: Vec<String> = parser
+            .custom_patterns()
+            .iter()
+            .map(|p| p.name.clone())
+            .chain(BuiltinPattern::all().iter().map(|p| p.display_name().to_string()))
+            .collect();
+
+        let mut to_delete = None;
+        for (i, rule) in self.glob_rules.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                let glob = ui.add(
+                    egui::TextEdit::singleline(&mut rule.glob)
+                        .hint_text("*.access.log")
+                        .desired_width(120.0),
+                );
+                if glob.lost_focus() {
+                    *action = GrokPanelAction::ConfigChanged;
+                }
+                egui::ComboBox::from_id_salt(("grok_glob_rule", i))
+                    .selected_text(rule.pattern.as_str())
+                    .show_ui(ui, |ui| {
+                        for name in &pattern_names {
+                            if ui
+                                .selectable_value(&mut rule.pattern, name.clone(), name)
+                                .changed()
+                            {
+                                *action = GrokPanelAction::ConfigChanged;
+                            }
+                        }
+                    });
+                if ui.small_button("✕").on_hover_text(t::grok_remove_file_rule()).clicked() {
+                    to_delete = Some(i);
+                }
+            });
+        }
+        if let Some(i) = to_delete {
+            self.glob_rules.remove(i);
+            *action = GrokPanelAction::ConfigChanged;
+        }
+
+        if ui.button(t::grok_add_file_rule()).clicked() {
+            self.glob_rules.push(GrokGlobRule {
+                glob: String::new(),
+                pattern: pattern_names.first().cloned().unwrap_or_default(),
+            });
+        }
+    }
+
     /// Show pattern editor dialog
     fn show_pattern_editor(
         &mut self,
@@ -631,6 +932,34 @@ Output:
 
                         ui.add_space(8.0);
 
+                        // Field threshold color rules
+                        ui.label(RichText::new(t::grok_color_rules()).strong());
+                        ui.label(RichText::new(t::grok_color_rules_hint()).weak().italics());
+                        let mut rule_to_remove = None;
+                        for (i, rule) in self.pattern_editor.color_rules.iter_mut().enumerate() {
+                            ui.horizontal(|ui| {
+                                ui.add(
+                                    egui::TextEdit::singleline(&mut rule.field)
+                                        .hint_text("duration_ms")
+                                        .desired_width(140.0),
+                                );
+                                ui.label(">");
+                                ui.add(egui::DragValue::new(&mut rule.above).speed(10.0));
+                                ui.color_edit_button_srgb(&mut rule.color);
+                                if ui.small_button("✕").clicked() {
+                                    rule_to_remove = Some(i);
+                                }
+                            });
+                        }
+                        if let Some(i) = rule_to_remove {
+                            self.pattern_editor.color_rules.remove(i);
+                        }
+                        if ui.button(t::grok_add_color_rule()).clicked() {
+                            self.pattern_editor.color_rules.push(FieldColorRule::default());
+                        }
+
+                        ui.add_space(8.0);
+
                         // AI Assist section
                         ui.collapsing(RichText::new(t::grok_ai_assist()).strong(), |ui| {
                             self.show_ai_assist_in_editor(ui, parser, action);
@@ -659,23 +988,31 @@ Output:
 
                         ui.add_space(8.0);
 
+                        // Live tester over sample lines
+                        ui.label(RichText::new(t::grok_tester()).strong());
+                        ui.label(RichText::new(t::grok_tester_hint()).weak().italics());
+                        if ui.button(t::grok_tester_use_file_lines()).clicked() {
+                            self.tester.awaiting_samples = true;
+                            *action = GrokPanelAction::RequestSampleLines;
+                        }
+                        ui.add(
+                            egui::TextEdit::multiline(&mut self.tester.samples)
+                                .desired_width(ui.available_width())
+                                .desired_rows(4)
+                                .font(egui::TextStyle::Monospace),
+                        );
+                        self.tester
+                            .refresh(parser, &self.pattern_editor.to_pattern());
+                        self.tester.show_results(ui);
+
+                        ui.add_space(8.0);
+
                         ui.horizontal(|ui| {
                             if ui.button(t::grok_save_pattern()).clicked()
                                 && !self.pattern_editor.name.is_empty()
                                     && !self.pattern_editor.pattern.is_empty()
                                 {
-                                    let new_pattern = CustomPattern {
-                                        name: self.pattern_editor.name.clone(),
-                                        pattern: self.pattern_editor.pattern.clone(),
-                                        description: self.pattern_editor.description.clone(),
-                                        example: self.pattern_editor.example.clone(),
-                                        enabled: true,
-                                        display_template: self
-                                            .pattern_editor
-                                            .display_template
-                                            .clone(),
-                                        pre_processor: self.pattern_editor.pre_processor.clone(),
-                                    };
+                                    let new_pattern = self.pattern_editor.to_pattern();
 
                                     if let Some(idx) = self.pattern_editor.editing_index {
                                         // Update existing
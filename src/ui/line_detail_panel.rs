@@ -0,0 +1,238 @@
+//! Line detail panel
+//!
+//! Shows rich information for the focused log line: level, timestamp,
+//! parsed grok fields and a few surrounding lines. The panel follows the
+//! selection, so moving the selection updates its content.
+
+use crate::ansi::strip_ansi;
+use crate::i18n::Translations as t;
+use crate::log_buffer::LogBuffer;
+use crate::log_entry::{LogLevel, TimestampSource};
+use egui::{self, RichText, Ui};
+
+/// Number of surrounding lines shown on each side of the focused line
+pub const DETAIL_CONTEXT_LINES: usize = 2;
+
+/// Everything the detail panel shows for one line
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineDetail {
+    /// Line number in the original file (1-indexed)
+    pub line_number: usize,
+    /// Text content, without ANSI escape sequences
+    pub content: String,
+    /// Detected log level
+    pub level: Option<LogLevel>,
+    /// Effective timestamp, formatted for display
+    pub timestamp: Option<String>,
+    /// Where the timestamp came from
+    pub timestamp_source: Option<TimestampSource>,
+    /// Whether the line is bookmarked
+    pub bookmarked: bool,
+    /// Grok parsed fields, sorted by name
+    pub fields: Vec<(String, String)>,
+    /// Lines before the focused line (line number, content)
+    pub context_before: Vec<(usize, String)>,
+    /// Lines after the focused line (line number, content)
+    pub context_after: Vec<(usize, String)>,
+}
+
+impl LineDetail {
+    /// Assemble the detail for the entry at `index` in the buffer.
+    /// Lines without an embedded timestamp show the buffer's fallback, if any.
+    pub fn from_buffer(buffer: &LogBuffer, index: usize, context_lines: usize) -> Option<Self> {
+        let entry = buffer.get(index)?;
+        let effective = buffer.entry_timestamp_source(entry);
+
+        let mut fields: Vec<(String, String)> = entry
+            .grok_fields
+            .as_ref()
+            .map(|f| f.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+            .unwrap_or_default();
+        fields.sort();
+
+        let context_before = buffer
+            .get_range(index.saturating_sub(context_lines)..index)
+            .map(|e| (e.line_number, strip_ansi(&e.content).into_owned()))
+            .collect();
+        let context_after = buffer
+            .get_range(index + 1..(index + 1 + context_lines).min(buffer.len()))
+            .map(|e| (e.line_number, strip_ansi(&e.content).into_owned()))
+            .collect();
+
+        Some(Self {
+            line_number: entry.line_number,
+            content: strip_ansi(&entry.content).into_owned(),
+            level: entry.level,
+            // Timestamps keep the line's wall-clock time as their UTC value
+            timestamp: effective
+                .map(|(ts, _)| ts.naive_utc().format("%Y-%m-%d %H:%M:%S%.3f").to_string()),
+            timestamp_source: effective.map(|(_, source)| source),
+            bookmarked: entry.bookmarked,
+            fields,
+            context_before,
+            context_after,
+        })
+    }
+}
+
+/// Actions from the line detail panel
+#[derive(Debug, Clone, PartialEq)]
+pub enum LineDetailAction {
+    None,
+    /// Close the panel
+    Close,
+    /// Copy the focused line
+    CopyLine(String),
+}
+
+/// Line detail panel state
+#[derive(Default)]
+pub struct LineDetailPanel {
+    /// Whether the panel is shown
+    pub visible: bool,
+}
+
+impl LineDetailPanel {
+    /// Render the panel for the focused line (if any)
+    pub fn show(&mut self, ui: &mut Ui, detail: Option<&LineDetail>) -> LineDetailAction {
+        let mut action = LineDetailAction::None;
+
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(t::line_detail()).strong());
+
+            if let Some(detail) = detail {
+                ui.separator();
+                ui.label(
+                    RichText::new(format!("{} {}", t::line_detail_line(), detail.line_number))
+                        .monospace(),
+                );
+                if let Some(level) = detail.level {
+                    ui.label(RichText::new(level.as_str()).color(level.color()).strong());
+                }
+                if let Some(ref timestamp) = detail.timestamp {
+                    let label = ui.label(RichText::new(timestamp).monospace().weak());
+                    match detail.timestamp_source {
+                        Some(TimestampSource::Ingestion) => {
+                            label.on_hover_text(t::timestamp_from_ingestion());
+                        }
+                        Some(TimestampSource::FileMtime) => {
+                            label.on_hover_text(t::timestamp_from_file_mtime());
+                        }
+                        _ => {}
+                    }
+                }
+                if detail.bookmarked {
+                    ui.label("⭐");
+                }
+            }
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.small_button("✕").on_hover_text(t::close()).clicked() {
+                    action = LineDetailAction::Close;
+                }
+                if let Some(detail) = detail {
+                    if ui.small_button(t::copy_line()).clicked() {
+                        action = LineDetailAction::CopyLine(detail.content.clone());
+                    }
+                }
+            });
+        });
+
+        ui.separator();
+
+        let Some(detail) = detail else {
+            ui.label(RichText::new(t::line_detail_no_selection()).weak().italics());
+            return action;
+        };
+
+        egui::ScrollArea::vertical()
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                ui.add(egui::Label::new(RichText::new(&detail.content).monospace()).wrap());
+
+                if !detail.fields.is_empty() {
+                    ui.add_space(6.0);
+                    ui.label(RichText::new(t::line_detail_fields()).strong());
+                    egui::Grid::new("line_detail_fields")
+                        .num_columns(2)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for (name, value) in &detail.fields {
+                                ui.label(RichText::new(name).monospace().weak());
+                                ui.label(RichText::new(value).monospace());
+                                ui.end_row();
+                            }
+                        });
+                }
+
+                if !detail.context_before.is_empty() || !detail.context_after.is_empty() {
+                    ui.add_space(6.0);
+                    ui.label(RichText::new(t::line_detail_context()).strong());
+                    for (line_number, content) in &detail.context_before {
+                        context_row(ui, *line_number, content, false);
+                    }
+                    context_row(ui, detail.line_number, &detail.content, true);
+                    for (line_number, content) in &detail.context_after {
+                        context_row(ui, *line_number, content, false);
+                    }
+                }
+            });
+
+        action
+    }
+}
+
+/// Render one line of the context listing
+fn context_row(ui: &mut Ui, line_number: usize, content: &str, focused: bool) {
+    let text = RichText::new(format!("{:>6}  {}", line_number, content)).monospace();
+    ui.label(if focused { text.strong() } else { text.weak() });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log_entry::LogEntry;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_detail_from_entry() {
+        let mut buffer = LogBuffer::new();
+        for i in 1..=5 {
+            buffer.push(LogEntry::new(
+                i,
+                format!("2024-01-15 10:30:4{} INFO line {}", i, i),
+                0,
+            ));
+        }
+        let mut fields = HashMap::new();
+        fields.insert("msg".to_string(), "line 3".to_string());
+        fields.insert("level".to_string(), "ERROR".to_string());
+        buffer.update(2, |entry| entry.set_grok_fields(fields));
+        buffer.toggle_bookmark(2);
+
+        let detail = LineDetail::from_buffer(&buffer, 2, 2).unwrap();
+        assert_eq!(detail.line_number, 3);
+        assert_eq!(detail.content, "2024-01-15 10:30:43 INFO line 3");
+        // The grok level wins over the detected one
+        assert_eq!(detail.level, Some(LogLevel::Error));
+        assert!(detail.timestamp.is_some());
+        assert!(detail.bookmarked);
+        assert_eq!(
+            detail.fields,
+            vec![
+                ("level".to_string(), "ERROR".to_string()),
+                ("msg".to_string(), "line 3".to_string()),
+            ]
+        );
+        let before: Vec<usize> = detail.context_before.iter().map(|(n, _)| *n).collect();
+        let after: Vec<usize> = detail.context_after.iter().map(|(n, _)| *n).collect();
+        assert_eq!(before, vec![1, 2]);
+        assert_eq!(after, vec![4, 5]);
+
+        // Context is clipped at the buffer edges
+        let first = LineDetail::from_buffer(&buffer, 0, 2).unwrap();
+        assert!(first.context_before.is_empty());
+        assert_eq!(first.context_after.len(), 2);
+        assert!(LineDetail::from_buffer(&buffer, 10, 2).is_none());
+    }
+}
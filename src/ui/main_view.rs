@@ -1,20 +1,44 @@
 //! Main log view component with virtual scrolling
 
-use crate::config::DisplayConfig;
-use crate::grok_parser::CompiledPattern;
-use crate::highlighter::Highlighter;
+use crate::ansi::strip_ansi;
+use crate::config::{DisplayConfig, TimestampMode};
+use crate::grok_parser::{threshold_color, CompiledPattern, FieldColorRule, GrokParser};
+use crate::highlighter::{Highlighter, PrefixStripper};
+use crate::i18n::Translations as t;
 use crate::log_buffer::LogBuffer;
-use crate::log_entry::{LogEntry, LogLevel};
-use crate::search::SearchEngine;
-use crate::virtual_scroll::VirtualScroll;
+use crate::log_entry::{LogEntry, LogLevel, TimestampSource};
+use crate::search::{FieldSort, SearchEngine};
+use crate::ui::bookmarks_panel::bookmark_color;
+use crate::virtual_scroll::{OverviewMark, OverviewMarks, RowMetrics, VirtualScroll, WrappedRows};
+use chrono::{DateTime, Local, NaiveDateTime, TimeDelta};
 use egui::{self, Color32, Rect, Response, Sense, Ui, UiKind, Vec2};
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
 use std::sync::Arc;
+use std::time::Duration;
+
+/// Width of a grok field column that was never resized
+const DEFAULT_COLUMN_WIDTH: f32 = 140.0;
+/// Narrowest a grok field column can be dragged
+const MIN_COLUMN_WIDTH: f32 = 40.0;
+/// Width of the overview strip beside the scrollbar
+const OVERVIEW_WIDTH: f32 = 12.0;
+/// Grok fields left out of the context header (already visible on the row)
+const CONTEXT_SKIPPED_FIELDS: [&str; 5] = ["timestamp", "level", "message", "msg", "content"];
 
 /// Context menu actions
 #[derive(Clone, Debug, PartialEq)]
 pub enum ContextMenuAction {
-    /// Copy selected lines
+    /// Copy selected lines (with line numbers if that is the default)
     Copy,
+    /// Copy selected lines prefixed with their line numbers
+    CopyWithLineNumbers,
+    /// Copy selected lines without line numbers
+    CopyPlain,
+    /// Copy selected lines as a Markdown table of their grok fields
+    CopyAsMarkdown,
     /// Copy all visible lines
     CopyAll,
     /// Toggle bookmark on selected line
@@ -27,6 +51,23 @@ pub enum ContextMenuAction {
     ScrollToTop,
     /// Scroll to bottom
     ScrollToBottom,
+    /// Show only the lines sharing the selected line's correlation id
+    FilterByCorrelationId,
+    /// Remove the correlation id filter
+    ClearCorrelationFilter,
+    /// Select the next line with the selected line's correlation id
+    NextCorrelatedLine,
+    /// Select the previous line with the selected line's correlation id
+    PrevCorrelatedLine,
+}
+
+/// Column view header interactions (consumed by the app)
+#[derive(Clone, Debug, PartialEq)]
+pub enum ColumnAction {
+    /// A field's header was clicked
+    Sort(String),
+    /// A field's column was dragged to a new width (`done` when released)
+    Resize { field: String, width: f32, done: bool },
 }
 
 /// Selection range for multi-line selection
@@ -62,6 +103,17 @@ impl SelectionRange {
     }
 }
 
+/// Overview strip marks and their downsampled slots
+#[derive(Default)]
+struct OverviewCache {
+    marks: OverviewMarks,
+    /// Merged marks per slot, in logical order
+    slots: Vec<Option<OverviewMark>>,
+}
+
+/// Buffer shape and top line index, and the timestamp found for them
+type ContextTimestamp = ((u64, usize, usize, usize), Option<DateTime<Local>>);
+
 /// Main view for displaying log entries
 pub struct MainView {
     /// Unique ID for this view instance (to avoid ID collisions in split view)
@@ -85,6 +137,31 @@ pub struct MainView {
     current_total_rows: usize,
     /// Pending scroll to bottom request
     pending_scroll_to_bottom: bool,
+    /// Pending page scroll (number of pages down)
+    pending_page_scroll: usize,
+    /// Prefix stripped from each line at display time (per file)
+    pub strip_prefix: Option<PrefixStripper>,
+    /// Set when a line was selected by a single click (consumed by the app)
+    line_clicked: bool,
+    /// Correlation id the view is filtered to (offered for removal in the context menu)
+    pub correlation_id: Option<String>,
+    /// Field the view is sorted by (marked in the column view header)
+    pub sort_by: Option<FieldSort>,
+    /// The sort waits for lines to be grok-parsed; rows are in file order
+    pub sort_pending: bool,
+    /// Header interaction from the last frame
+    column_action: Option<ColumnAction>,
+    /// Visual line layout of the rows while long lines are wrapped
+    wrapped_rows: WrappedRows,
+    /// Marks painted in the overview strip
+    overview: OverviewCache,
+    /// Line numbers of stack trace blocks whose continuation lines are hidden
+    pub collapsed_blocks: HashSet<usize>,
+    /// Block whose fold triangle was clicked (consumed by the app)
+    fold_toggle: Option<usize>,
+    /// Context header timestamp, with the buffer revision, first and last
+    /// line numbers and top line index it was looked up for
+    context_timestamp: Cell<Option<ContextTimestamp>>,
 }
 
 impl MainView {
@@ -106,6 +183,18 @@ impl MainView {
             current_row_height: 18.0,
             current_total_rows: 0,
             pending_scroll_to_bottom: false,
+            pending_page_scroll: 0,
+            strip_prefix: None,
+            line_clicked: false,
+            correlation_id: None,
+            sort_by: None,
+            sort_pending: false,
+            column_action: None,
+            wrapped_rows: WrappedRows::default(),
+            overview: OverviewCache::default(),
+            collapsed_blocks: HashSet::new(),
+            fold_toggle: None,
+            context_timestamp: Cell::new(None),
         }
     }
 
@@ -113,6 +202,12 @@ impl MainView {
     ///
     /// If `grok_pattern` is provided and `display_config.show_grok_fields` is true,
     /// the display template will be applied lazily during rendering for visible entries only.
+    /// `color_rules` tint visible rows by their numeric grok field values.
+    /// With `display_config.grok_columns` the fields are laid out as columns
+    /// sized by `column_widths` (field name to width).
+    /// With `display_config.show_overview` an overview strip with error and
+    /// bookmark markers is shown on the right.
+    #[allow(clippy::too_many_arguments)]
     pub fn show(
         &mut self,
         ui: &mut Ui,
@@ -121,18 +216,204 @@ impl MainView {
         search: &SearchEngine,
         display_config: &DisplayConfig,
         grok_pattern: Option<&Arc<CompiledPattern>>,
+        color_rules: &[FieldColorRule],
+        column_widths: Option<&HashMap<String, f32>>,
+    ) -> (Response, Option<ContextMenuAction>) {
+        if !display_config.show_overview {
+            return self.show_rows(
+                ui,
+                buffer,
+                filtered_indices,
+                search,
+                display_config,
+                grok_pattern,
+                color_rules,
+                column_widths,
+            );
+        }
+
+        let full_rect = ui.available_rect_before_wrap();
+        let (rows_rect, overview_rect) =
+            full_rect.split_left_right_at_x(full_rect.right() - OVERVIEW_WIDTH);
+        let result = ui
+            .scope_builder(egui::UiBuilder::new().max_rect(rows_rect), |ui| {
+                self.show_rows(
+                    ui,
+                    buffer,
+                    filtered_indices,
+                    search,
+                    display_config,
+                    grok_pattern,
+                    color_rules,
+                    column_widths,
+                )
+            })
+            .inner;
+        self.show_overview(ui, overview_rect, buffer, filtered_indices, display_config);
+        result
+    }
+
+    /// Render the log rows in a scroll area
+    #[allow(clippy::too_many_arguments)]
+    fn show_rows(
+        &mut self,
+        ui: &mut Ui,
+        buffer: &LogBuffer,
+        filtered_indices: Option<&[usize]>,
+        search: &SearchEngine,
+        display_config: &DisplayConfig,
+        grok_pattern: Option<&Arc<CompiledPattern>>,
+        color_rules: &[FieldColorRule],
+        column_widths: Option<&HashMap<String, f32>>,
     ) -> (Response, Option<ContextMenuAction>) {
         let total_rows = filtered_indices.map(|f| f.len()).unwrap_or(buffer.len());
+        self.highlighter.render_ansi = display_config.render_ansi_colors;
+        self.highlighter.fuzzy_search = search.config.fuzzy;
+        self.highlighter.level_colors = display_config.level_palette();
+        self.highlighter.set_rules(&display_config.highlight_rules);
+
+        // Relative timestamps are measured against this frame's time and kept
+        // fresh while idle
+        let now = Local::now().naive_local();
+        let timestamp_mode = display_config.timestamp_mode;
+        if timestamp_mode == TimestampMode::RelativeToNow {
+            ui.ctx().request_repaint_after(Duration::from_secs(1));
+        }
+
+        // Sticky context of the topmost visible entry, above the rows
+        if display_config.show_context_header && total_rows > 0 {
+            self.show_context_header(
+                ui,
+                buffer,
+                filtered_indices,
+                grok_pattern,
+                display_config,
+                total_rows,
+            );
+        }
 
         // Calculate layout
         let available_size = ui.available_size();
-        let row_height = display_config.font_size * display_config.line_height;
+        let row_height = display_config.row_height();
 
         // Update virtual scroll config
         self.virtual_scroll.config.row_height = row_height;
         self.current_row_height = row_height;
         self.current_total_rows = total_rows;
 
+        // Column view: grok fields as aligned columns under a sticky header
+        let columns = grok_pattern
+            .filter(|_| display_config.show_grok_fields && display_config.grok_columns)
+            .map(|pattern| pattern.columns.as_slice())
+            .filter(|columns| !columns.is_empty());
+        let header_height = if columns.is_some() { row_height } else { 0.0 };
+        let column_width = |field: &str| {
+            column_widths
+                .and_then(|widths| widths.get(field))
+                .copied()
+                .unwrap_or(DEFAULT_COLUMN_WIDTH)
+        };
+
+        // Get line number width
+        let max_line_num = buffer.last_line_number().max(1);
+        let line_num_width = format!("{}", max_line_num)
+            .len()
+            .max(display_config.line_number_width);
+        let line_num_pixel_width = if display_config.show_line_numbers {
+            (line_num_width as f32 + 2.0) * display_config.font_size * 0.6
+        } else {
+            0.0
+        };
+        // Fold triangles of stack trace blocks sit between the gutter and the text
+        let fold_width = if buffer.groups_continuations() {
+            display_config.font_size
+        } else {
+            0.0
+        };
+
+        // Word wrap: whole monospace cells that fit right of the gutter, and
+        // the galley width holding exactly that many
+        let wrap_width = (display_config.wrap_lines && columns.is_none()).then(|| {
+            let char_width = ui
+                .painter()
+                .layout_no_wrap(
+                    "M".to_owned(),
+                    egui::FontId::monospace(display_config.font_size),
+                    Color32::WHITE,
+                )
+                .size()
+                .x
+                + display_config.letter_spacing;
+            let text_width = available_size.x
+                - (8.0 + line_num_pixel_width + fold_width + 4.0)
+                - ui.spacing().scroll.allocated_width();
+            let wrap_columns = ((text_width / char_width).floor() as usize).max(1);
+            (wrap_columns, (wrap_columns as f32 + 0.5) * char_width)
+        });
+
+        // Taken out for the frame so row positions can be read while rendering borrows self
+        let mut wrapped_rows = std::mem::take(&mut self.wrapped_rows);
+        if let Some((wrap_columns, _)) = wrap_width {
+            let strip_prefix = self.strip_prefix.as_ref();
+            let key = (
+                wrap_columns,
+                display_config.show_grok_fields,
+                display_config.render_ansi_colors,
+                strip_prefix.is_some(),
+            );
+            let measure = |row: usize| {
+                let entry = filtered_indices
+                    .map_or(Some(row), |indices| indices.get(row).copied())
+                    .and_then(|idx| buffer.get(idx));
+                let Some(entry) = entry else {
+                    return (0, 1);
+                };
+                let text = if display_config.show_grok_fields {
+                    entry.display_content()
+                } else {
+                    entry.content.as_str()
+                };
+                let text = strip_prefix.map_or(text, |stripper| stripper.strip(text));
+                let chars = if display_config.render_ansi_colors {
+                    strip_ansi(text).chars().count()
+                } else {
+                    text.chars().count()
+                };
+                (entry.line_number, chars.div_ceil(wrap_columns))
+            };
+            wrapped_rows.update(key, total_rows, measure);
+
+            // Rows around the view may have gained grok fields since they were measured
+            let nearby = self.get_visible_range(total_rows);
+            let nearby = if self.virtual_scroll.state.reverse_order {
+                total_rows - nearby.end..total_rows - nearby.start
+            } else {
+                nearby
+            };
+            wrapped_rows.remeasure(nearby, measure);
+        }
+        // Lines trimmed between head and tail get a marker row where the view
+        // crosses them: above the first row shown after the gap
+        let gap = buffer.omitted().filter(|_| self.sort_by.is_none());
+        let gap_row = gap.and_then(|gap| {
+            let after = filtered_indices.map_or(gap.index.min(total_rows), |indices| {
+                indices.partition_point(|&idx| idx < gap.index)
+            });
+            (after > 0 && after < total_rows).then(|| {
+                if self.virtual_scroll.state.reverse_order {
+                    total_rows - after
+                } else {
+                    after
+                }
+            })
+        });
+        let metrics = RowMetrics {
+            row_height,
+            wrapped: wrap_width.map(|_| &wrapped_rows),
+            reverse: self.virtual_scroll.state.reverse_order,
+            gap_row,
+        };
+
         // Calculate target scroll offset if we need to scroll to a specific row
         let mut scroll_to_y = self.scroll_to_row.take().map(|buffer_idx| {
             // Convert buffer_idx to display_row
@@ -153,7 +434,7 @@ impl MainView {
                 logical_row
             };
 
-            display_row as f32 * row_height
+            metrics.top(display_row)
         });
 
         // Track if this is a scroll to bottom operation
@@ -187,16 +468,33 @@ impl MainView {
                     logical_row
                 };
 
-                scroll_to_y = Some(display_row as f32 * row_height);
+                scroll_to_y = Some(metrics.top(display_row));
             }
         }
 
+        // Paging moves the view like scrolling does, so it can resume following
+        let page_scrolled = self.pending_page_scroll > 0;
+
+        // Handle pending page down (one viewport minus a row of overlap)
+        if self.pending_page_scroll > 0 {
+            let viewport = self.virtual_scroll.state.viewport_height;
+            let page = (viewport - row_height).max(row_height);
+            let max_offset = (metrics.content_height(total_rows) - viewport).max(0.0);
+            let target = self.virtual_scroll.state.scroll_offset
+                + page * self.pending_page_scroll as f32;
+            scroll_to_y = Some(target.min(max_offset));
+            self.pending_page_scroll = 0;
+        }
+
         // When user scrolls to bottom, enable auto-scroll to follow new logs
         // When user scrolls to other positions, disable auto-scroll to view specific content
         // Only enable auto-scroll when jumping to the location where new logs appear:
         // - Normal mode: bottom is where new logs appear
         // - Reverse mode: top (buffer_idx max) is where new logs appear, not bottom
-        if is_scroll_to_bottom && !self.virtual_scroll.state.reverse_order {
+        if is_scroll_to_bottom
+            && !self.virtual_scroll.state.reverse_order
+            && !self.virtual_scroll.state.follow_paused
+        {
             self.virtual_scroll.state.auto_scroll = true;
         } else if scroll_to_y.is_some() {
             self.virtual_scroll.state.auto_scroll = false;
@@ -222,21 +520,27 @@ impl MainView {
         }
 
         // Apply scroll offset when explicitly requested
+        let mut jumped = false;
         if let Some(y) = scroll_to_y {
             // For scroll to bottom with auto-scroll, we need to explicitly set the offset
             // to ensure immediate scrolling (stick_to_bottom alone won't scroll if already at bottom)
             // For other positions, only scroll if auto-scroll is disabled
             if is_scroll_to_bottom || !self.virtual_scroll.state.auto_scroll {
                 scroll_area = scroll_area.vertical_scroll_offset(y);
+                jumped = !page_scrolled;
             }
         }
 
         let response = scroll_area.show(ui, |ui| {
             // Reserve space for all content
-            let content_height = total_rows as f32 * row_height;
+            let content_height = metrics.content_height(total_rows) + header_height;
 
-            // Use a large width to allow horizontal scrolling
-            let content_width = available_size.x.max(3000.0);
+            // Use a large width to allow horizontal scrolling, unless lines wrap
+            let content_width = if wrap_width.is_some() {
+                available_size.x
+            } else {
+                available_size.x.max(3000.0)
+            };
 
             let (rect, response) = ui.allocate_exact_size(
                 Vec2::new(content_width, content_height.max(available_size.y)),
@@ -246,17 +550,21 @@ impl MainView {
             // Get clip rect (the visible viewport)
             let clip_rect = ui.clip_rect();
 
+            // Rows start below the column header (if any)
+            let rows_top = rect.min.y + header_height;
+            let in_header = |pos: egui::Pos2| pos.y < clip_rect.min.y + header_height;
+
             // Calculate which rows are actually visible based on clip_rect
             // The clip_rect tells us what part of the content is visible
-            let first_visible_row = if rect.min.y < clip_rect.min.y {
+            let first_visible_row = if rows_top < clip_rect.min.y {
                 // Content is scrolled up, some rows are above viewport
-                ((clip_rect.min.y - rect.min.y) / row_height).floor() as usize
+                metrics.row_at(clip_rect.min.y - rows_top)
             } else {
                 0
             };
 
-            let last_visible_row = if rect.min.y < clip_rect.max.y {
-                ((clip_rect.max.y - rect.min.y) / row_height).ceil() as usize
+            let last_visible_row = if rows_top < clip_rect.max.y {
+                metrics.row_at(clip_rect.max.y - rows_top) + 1
             } else {
                 0
             };
@@ -266,17 +574,6 @@ impl MainView {
             let start_row = first_visible_row.saturating_sub(overscan);
             let end_row = (last_visible_row + overscan).min(total_rows);
 
-            // Get line number width
-            let max_line_num = buffer.last_line_number().max(1);
-            let line_num_width = format!("{}", max_line_num)
-                .len()
-                .max(display_config.line_number_width);
-            let line_num_pixel_width = if display_config.show_line_numbers {
-                (line_num_width as f32 + 2.0) * display_config.font_size * 0.6
-            } else {
-                0.0
-            };
-
             // Render visible rows
             let painter = ui.painter();
             let reverse_order = self.virtual_scroll.state.reverse_order;
@@ -304,12 +601,22 @@ impl MainView {
                 };
 
                 // Calculate row position
-                let row_y = rect.min.y + row_idx as f32 * row_height;
+                let row_y = rows_top + metrics.top(row_idx);
+                let row_span = metrics.height(row_idx);
                 let row_rect = Rect::from_min_size(
                     egui::pos2(rect.min.x, row_y),
-                    Vec2::new(rect.width(), row_height),
+                    Vec2::new(rect.width(), row_span),
                 );
 
+                // Draw threshold row color from numeric grok fields
+                if let Some([r, g, b]) = entry
+                    .grok_fields
+                    .as_ref()
+                    .and_then(|fields| threshold_color(color_rules, fields))
+                {
+                    painter.rect_filled(row_rect, 0.0, Color32::from_rgba_unmultiplied(r, g, b, 48));
+                }
+
                 // Draw selection background (multi-line selection)
                 // selection_range stores logical rows, so we need to check display_row
                 let is_in_selection = self
@@ -331,9 +638,10 @@ impl MainView {
 
                 // Draw bookmark indicator
                 if entry.bookmarked {
-                    let bookmark_rect =
-                        Rect::from_min_size(row_rect.min, Vec2::new(4.0, row_height));
-                    painter.rect_filled(bookmark_rect, 0.0, self.highlighter.theme.bookmark);
+                    let bookmark_rect = Rect::from_min_size(row_rect.min, Vec2::new(4.0, row_span));
+                    let color = bookmark_color(entry.bookmark_color)
+                        .unwrap_or(self.highlighter.theme.bookmark);
+                    painter.rect_filled(bookmark_rect, 0.0, color);
                 }
 
                 // Draw line number
@@ -356,12 +664,95 @@ impl MainView {
                     painter.line_segment(
                         [
                             egui::pos2(text_x - 4.0, row_y),
-                            egui::pos2(text_x - 4.0, row_y + row_height),
+                            egui::pos2(text_x - 4.0, row_y + row_span),
                         ],
                         egui::Stroke::new(1.0, Color32::from_gray(60)),
                     );
                 }
 
+                // Draw a fold triangle on lines followed by continuation lines
+                if fold_width > 0.0 {
+                    let starts_block = !entry.continuation
+                        && buffer
+                            .get(buffer_idx + 1)
+                            .is_some_and(|next| next.continuation);
+                    if starts_block {
+                        let collapsed = self.collapsed_blocks.contains(&entry.line_number);
+                        let fold_rect = Rect::from_min_size(
+                            egui::pos2(text_x, row_y),
+                            Vec2::new(fold_width, row_height),
+                        );
+                        let fold = ui
+                            .interact(
+                                fold_rect,
+                                self.view_id.with(("fold", entry.line_number)),
+                                Sense::click(),
+                            )
+                            .on_hover_cursor(egui::CursorIcon::PointingHand)
+                            .on_hover_text(if collapsed {
+                                t::expand_block()
+                            } else {
+                                t::collapse_block()
+                            });
+                        let color = if fold.hovered() {
+                            self.highlighter.theme.text
+                        } else {
+                            self.highlighter.theme.line_number
+                        };
+                        painter.text(
+                            fold_rect.center(),
+                            egui::Align2::CENTER_CENTER,
+                            if collapsed { "▶" } else { "▼" },
+                            egui::FontId::proportional(display_config.font_size * 0.7),
+                            color,
+                        );
+                        if fold.clicked() {
+                            self.fold_toggle = Some(entry.line_number);
+                        }
+                    }
+                    text_x += fold_width;
+                }
+
+                // Draw row separator line at the bottom of each row
+                if display_config.show_row_separator {
+                    painter.line_segment(
+                        [
+                            egui::pos2(rect.min.x, row_y + row_span - 0.5),
+                            egui::pos2(rect.max.x, row_y + row_span - 0.5),
+                        ],
+                        egui::Stroke::new(0.5, Color32::from_gray(45)),
+                    );
+                }
+
+                // Draw one cell per grok field; rows without the fields fall
+                // through to their raw content spanning all columns
+                if let (Some(columns), Some(fields)) = (columns, entry.grok_fields.as_ref()) {
+                    if columns.iter().any(|column| fields.contains_key(column)) {
+                        let color = entry
+                            .level
+                            .map_or(self.highlighter.theme.text, |l| display_config.level_color(l));
+                        let mut cell_x = text_x + 4.0;
+                        for column in columns {
+                            let width = column_width(column);
+                            if let Some(value) = fields.get(column) {
+                                let cell = Rect::from_min_size(
+                                    egui::pos2(cell_x, row_y),
+                                    Vec2::new(width - 8.0, row_height),
+                                );
+                                painter.with_clip_rect(cell.intersect(clip_rect)).text(
+                                    egui::pos2(cell_x, row_y + row_height * 0.5),
+                                    egui::Align2::LEFT_CENTER,
+                                    value,
+                                    egui::FontId::monospace(display_config.font_size),
+                                    color,
+                                );
+                            }
+                            cell_x += width;
+                        }
+                        continue;
+                    }
+                }
+
                 // Draw log content with highlighting
                 let search_query = if search.is_active() {
                     Some(search.config.query.as_str())
@@ -369,12 +760,13 @@ impl MainView {
                     None
                 };
 
-                // Use infinite width for text (no wrapping, allows horizontal scrolling)
-                let text_available_width = f32::INFINITY;
+                // Infinite width unless wrapping (allows horizontal scrolling)
+                let text_available_width = wrap_width.map_or(f32::INFINITY, |(_, width)| width);
+                let stamp = buffer.entry_timestamp_source(entry);
 
                 // Create layout job based on whether we have grok fields and template
                 // Lazy formatting: apply display template during rendering for visible entries only
-                let layout_job = if display_config.show_grok_fields {
+                let mut layout_job = if display_config.show_grok_fields {
                     // Try to get formatted segments - either from cache or generate on-the-fly
                     let formatted_segments = if entry.formatted_segments.is_some() {
                         entry.formatted_segments.as_ref()
@@ -469,8 +861,17 @@ impl MainView {
                             job
                         } else {
                             // Fallback to original content if template formatting fails
-                            self.highlighter.highlight_line_with_wrap(
-                                &entry.content,
+                            let display_text = self.strip_for_display(&entry.content);
+                            let display_text = apply_timestamp_mode(
+                                display_text,
+                                stamp,
+                                shown_timestamp_span(entry, display_text),
+                                timestamp_mode,
+                                now,
+                            );
+                            self.highlighter.highlight_line_cached(
+                                entry.line_number,
+                                &display_text,
                                 entry.level,
                                 search_query,
                                 search.config.case_sensitive,
@@ -480,9 +881,17 @@ impl MainView {
                         }
                     } else {
                         // No grok fields available, use display_content or original content
-                        let display_text = entry.display_content();
-                        self.highlighter.highlight_line_with_wrap(
-                            display_text,
+                        let display_text = self.strip_for_display(entry.display_content());
+                        let span = entry
+                            .formatted_content
+                            .is_none()
+                            .then(|| shown_timestamp_span(entry, display_text))
+                            .flatten();
+                        let display_text =
+                            apply_timestamp_mode(display_text, stamp, span, timestamp_mode, now);
+                        self.highlighter.highlight_line_cached(
+                            entry.line_number,
+                            &display_text,
                             entry.level,
                             search_query,
                             search.config.case_sensitive,
@@ -492,8 +901,17 @@ impl MainView {
                     }
                 } else {
                     // show_grok_fields is disabled, use original content
-                    self.highlighter.highlight_line_with_wrap(
-                        &entry.content,
+                    let display_text = self.strip_for_display(&entry.content);
+                    let display_text = apply_timestamp_mode(
+                        display_text,
+                        stamp,
+                        shown_timestamp_span(entry, display_text),
+                        timestamp_mode,
+                        now,
+                    );
+                    self.highlighter.highlight_line_cached(
+                        entry.line_number,
+                        &display_text,
                         entry.level,
                         search_query,
                         search.config.case_sensitive,
@@ -502,28 +920,123 @@ impl MainView {
                     )
                 };
 
+                // Wrapped lines break at any character and take a full row
+                // height each, matching the measured layout
+                if wrap_width.is_some() {
+                    layout_job.wrap.max_width = text_available_width;
+                    layout_job.wrap.break_anywhere = true;
+                    for section in &mut layout_job.sections {
+                        section.format.line_height = Some(row_height);
+                    }
+                }
+
                 // Layout the text with the context fonts
                 let galley = ui.painter().layout_job(layout_job);
-                let text_pos =
-                    egui::pos2(text_x + 4.0, row_y + (row_height - galley.size().y) * 0.5);
-                painter.galley(text_pos, galley.clone(), Color32::WHITE);
+                let text_pos = egui::pos2(text_x + 4.0, row_y + (row_span - galley.size().y) * 0.5);
+                if wrap_width.is_some() {
+                    painter
+                        .with_clip_rect(row_rect.intersect(clip_rect))
+                        .galley(text_pos, galley, Color32::WHITE);
+                } else {
+                    painter.galley(text_pos, galley.clone(), Color32::WHITE);
+                }
+            }
 
-                // Draw row separator line at the bottom of each row
-                if display_config.show_row_separator {
+            // The marker row is not a line and can't be selected
+            let gap_marker = gap
+                .zip(metrics.gap_top())
+                .map(|(gap, top)| (rows_top + top + row_height / 2.0, gap.count))
+                .filter(|(y, _)| clip_rect.y_range().contains(*y));
+            if let Some((y, count)) = gap_marker {
+                let color = self.highlighter.theme.line_number;
+                painter.hline(clip_rect.x_range(), y, egui::Stroke::new(1.0, color));
+                let label = painter.layout_no_wrap(
+                    t::lines_omitted().replacen("{}", &count.to_string(), 1),
+                    egui::FontId::proportional(display_config.font_size * 0.85),
+                    color,
+                );
+                let label_rect = Rect::from_center_size(
+                    egui::pos2(clip_rect.center().x, y),
+                    label.size() + Vec2::new(12.0, 2.0),
+                );
+                painter.rect_filled(label_rect, 4.0, ui.visuals().extreme_bg_color);
+                painter.galley(label_rect.min + Vec2::new(6.0, 1.0), label, color);
+            }
+
+            // Draw the column header pinned to the top of the viewport
+            if let Some(columns) = columns {
+                let header_y = clip_rect.min.y;
+                let header_rect = Rect::from_min_size(
+                    egui::pos2(rect.min.x, header_y),
+                    Vec2::new(rect.width(), header_height),
+                );
+                painter.rect_filled(
+                    header_rect,
+                    0.0,
+                    ui.visuals().widgets.noninteractive.bg_fill,
+                );
+                painter.line_segment(
+                    [header_rect.left_bottom(), header_rect.right_bottom()],
+                    egui::Stroke::new(1.0, Color32::from_gray(60)),
+                );
+
+                let mut cell_x = rect.min.x + 8.0 + line_num_pixel_width + fold_width + 4.0;
+                for (i, column) in columns.iter().enumerate() {
+                    let width = column_width(column);
+                    let cell = Rect::from_min_size(
+                        egui::pos2(cell_x, header_y),
+                        Vec2::new(width, header_height),
+                    );
+                    let marker = match &self.sort_by {
+                        Some(sort) if &sort.field == column && self.sort_pending => " …",
+                        Some(sort) if &sort.field == column && sort.descending => " ▼",
+                        Some(sort) if &sort.field == column => " ▲",
+                        _ => "",
+                    };
+                    painter.with_clip_rect(cell.intersect(clip_rect)).text(
+                        egui::pos2(cell_x, header_y + header_height * 0.5),
+                        egui::Align2::LEFT_CENTER,
+                        format!("{}{}", column, marker),
+                        egui::FontId::proportional(display_config.font_size),
+                        ui.visuals().strong_text_color(),
+                    );
+
+                    let column_id = self.view_id.with(("grok_column", i));
+                    if ui.interact(cell, column_id, Sense::click()).clicked() {
+                        self.column_action = Some(ColumnAction::Sort(column.clone()));
+                    }
+
+                    // Drag the right edge to resize
+                    let handle = Rect::from_center_size(
+                        egui::pos2(cell.max.x, cell.center().y),
+                        Vec2::new(6.0, header_height),
+                    );
                     painter.line_segment(
                         [
-                            egui::pos2(rect.min.x, row_y + row_height - 0.5),
-                            egui::pos2(rect.max.x, row_y + row_height - 0.5),
+                            egui::pos2(cell.max.x - 4.0, header_y + 3.0),
+                            egui::pos2(cell.max.x - 4.0, header_y + header_height - 3.0),
                         ],
-                        egui::Stroke::new(0.5, Color32::from_gray(45)),
+                        egui::Stroke::new(1.0, Color32::from_gray(90)),
                     );
+                    let handle = ui
+                        .interact(handle, column_id.with("resize"), Sense::drag())
+                        .on_hover_cursor(egui::CursorIcon::ResizeHorizontal);
+                    if handle.dragged() || handle.drag_stopped() {
+                        self.column_action = Some(ColumnAction::Resize {
+                            field: column.clone(),
+                            width: (width + handle.drag_delta().x).max(MIN_COLUMN_WIDTH),
+                            done: handle.drag_stopped(),
+                        });
+                    }
+
+                    cell_x += width;
                 }
             }
 
             // Handle drag for multi-line selection
             if response.drag_started() {
-                if let Some(pos) = response.interact_pointer_pos() {
-                    let clicked_row = ((pos.y - rect.min.y) / row_height).floor() as usize;
+                if let Some(pos) = response.interact_pointer_pos().filter(|&p| !in_header(p)) {
+                    let clicked_row = metrics.row_at(pos.y - rows_top);
                     if clicked_row < total_rows {
                         // In reverse order mode, flip the row index to get the correct logical row
                         let logical_row = if reverse_order {
@@ -545,9 +1058,9 @@ impl MainView {
             // Update selection during drag
             if response.dragged() {
                 if let Some(pos) = response.interact_pointer_pos() {
-                    let current_row = ((pos.y - rect.min.y) / row_height).floor() as isize;
-                    let current_row = current_row.max(0) as usize;
-                    let current_row = current_row.min(total_rows.saturating_sub(1));
+                    let current_row = metrics
+                        .row_at(pos.y - rows_top)
+                        .min(total_rows.saturating_sub(1));
 
                     // In reverse order mode, flip the row index to get the correct logical row
                     let logical_row = if reverse_order {
@@ -573,8 +1086,8 @@ impl MainView {
 
             // Handle click for single selection (only if not dragging)
             if response.clicked() && self.selection_range.map(|s| !s.is_dragging).unwrap_or(true) {
-                if let Some(pos) = response.interact_pointer_pos() {
-                    let clicked_row = ((pos.y - rect.min.y) / row_height).floor() as usize;
+                if let Some(pos) = response.interact_pointer_pos().filter(|&p| !in_header(p)) {
+                    let clicked_row = metrics.row_at(pos.y - rows_top);
                     if clicked_row < total_rows {
                         // In reverse order mode, flip the row index to get the correct logical row
                         let logical_row = if reverse_order {
@@ -590,6 +1103,7 @@ impl MainView {
                         } else {
                             Some(logical_row)
                         };
+                        self.line_clicked = self.selected_line.is_some();
                     }
                 }
             }
@@ -597,23 +1111,25 @@ impl MainView {
             (response, total_rows)
         });
 
-        // In reverse mode, detect if user scrolled away from top to disable auto-scroll
-        // In normal mode, stick_to_bottom handles this automatically
-        if self.virtual_scroll.state.reverse_order && self.virtual_scroll.state.auto_scroll {
-            // Get the current scroll offset from the ScrollArea state
-            let current_offset = response.state.offset.y;
-            // If user scrolled away from top (offset > small threshold), disable auto-scroll
-            if current_offset > row_height * 0.5 {
-                self.virtual_scroll.state.auto_scroll = false;
-            }
-        }
+        // Stop following when the user scrolls away from where new lines
+        // appear, and follow again once they scroll back to it
+        let current_offset = response.state.offset.y;
+        let max_offset = (response.content_size.y - response.inner_rect.height()).max(0.0);
+        let user_scrolled =
+            !jumped && (current_offset - self.virtual_scroll.state.scroll_offset).abs() > 0.5;
+        self.virtual_scroll.state.track_follow(
+            current_offset,
+            max_offset,
+            row_height * 0.5,
+            user_scrolled,
+        );
 
         // Update virtual scroll state for external access (e.g., lazy loading, on-demand parsing)
-        let current_offset = response.state.offset.y;
         self.virtual_scroll.state.scroll_offset = current_offset;
         self.virtual_scroll.state.viewport_height = available_size.y;
-        self.virtual_scroll.state.first_visible_row = (current_offset / row_height).floor() as usize;
+        self.virtual_scroll.state.first_visible_row = metrics.row_at(current_offset);
         self.virtual_scroll.state.visible_row_count = (available_size.y / row_height).ceil() as usize;
+        self.wrapped_rows = wrapped_rows;
 
         // Context menu
         let mut context_action = None;
@@ -634,6 +1150,28 @@ impl MainView {
                 ui.close_kind(UiKind::Menu);
             }
 
+            // The copy variant that is not the default
+            let (alternate_copy, alternate_label) = if display_config.copy_with_line_numbers {
+                (ContextMenuAction::CopyPlain, t::copy_without_line_numbers())
+            } else {
+                (ContextMenuAction::CopyWithLineNumbers, t::copy_with_line_numbers())
+            };
+            if ui
+                .add_enabled(has_selection, egui::Button::new(alternate_label))
+                .clicked()
+            {
+                context_action = Some(alternate_copy);
+                ui.close_kind(UiKind::Menu);
+            }
+
+            if ui
+                .add_enabled(has_selection, egui::Button::new(t::copy_as_markdown()))
+                .clicked()
+            {
+                context_action = Some(ContextMenuAction::CopyAsMarkdown);
+                ui.close_kind(UiKind::Menu);
+            }
+
             if ui.button("📄 复制全部").clicked() {
                 context_action = Some(ContextMenuAction::CopyAll);
                 ui.close_kind(UiKind::Menu)
@@ -654,6 +1192,52 @@ impl MainView {
 
             ui.separator();
 
+            if ui
+                .add_enabled(
+                    has_selection,
+                    egui::Button::new(format!("🔗 {}", t::filter_by_correlation_id())),
+                )
+                .clicked()
+            {
+                context_action = Some(ContextMenuAction::FilterByCorrelationId);
+                ui.close_kind(UiKind::Menu)
+            }
+
+            if let Some(id) = &self.correlation_id {
+                if ui
+                    .button(format!("✗ {} ({})", t::clear_correlation_filter(), id))
+                    .clicked()
+                {
+                    context_action = Some(ContextMenuAction::ClearCorrelationFilter);
+                    ui.close_kind(UiKind::Menu)
+                }
+            }
+
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(
+                        has_selection,
+                        egui::Button::new(format!("⬆ {}", t::prev_correlated_line())),
+                    )
+                    .clicked()
+                {
+                    context_action = Some(ContextMenuAction::PrevCorrelatedLine);
+                    ui.close_kind(UiKind::Menu)
+                }
+                if ui
+                    .add_enabled(
+                        has_selection,
+                        egui::Button::new(format!("⬇ {}", t::next_correlated_line())),
+                    )
+                    .clicked()
+                {
+                    context_action = Some(ContextMenuAction::NextCorrelatedLine);
+                    ui.close_kind(UiKind::Menu)
+                }
+            });
+
+            ui.separator();
+
             if ui.button("✓ 全选                        ⌘A").clicked() {
                 context_action = Some(ContextMenuAction::SelectAll);
                 ui.close_kind(UiKind::Menu)
@@ -693,6 +1277,132 @@ impl MainView {
         (response.inner.0, context_action)
     }
 
+    /// Take the column header interaction of the last frame
+    pub fn take_column_action(&mut self) -> Option<ColumnAction> {
+        self.column_action.take()
+    }
+
+    /// Take the line number of the block whose fold triangle was clicked
+    pub fn take_fold_toggle(&mut self) -> Option<usize> {
+        self.fold_toggle.take()
+    }
+
+    /// Strip the configured prefix for display only (content is kept for search/copy)
+    fn strip_for_display<'a>(&self, text: &'a str) -> &'a str {
+        match &self.strip_prefix {
+            Some(stripper) => stripper.strip(text),
+            None => text,
+        }
+    }
+
+    /// Paint the overview strip: the whole view downsampled to error and
+    /// bookmark ticks, plus the visible region. Clicking or dragging scrolls
+    /// the clicked spot into the middle of the view.
+    fn show_overview(
+        &mut self,
+        ui: &mut Ui,
+        rect: Rect,
+        buffer: &LogBuffer,
+        filtered_indices: Option<&[usize]>,
+        display_config: &DisplayConfig,
+    ) {
+        let response = ui
+            .allocate_rect(rect, Sense::click_and_drag())
+            .on_hover_cursor(egui::CursorIcon::PointingHand);
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 0.0, ui.visuals().extreme_bg_color);
+
+        let total_rows = filtered_indices.map_or(buffer.len(), |indices| indices.len());
+        if total_rows == 0 {
+            return;
+        }
+
+        // One slot per pixel; rows are aggregated when there are more
+        let buckets = rect.height().max(1.0) as usize;
+        let changed = self
+            .overview
+            .marks
+            .update(buffer.revision(), total_rows, |row| {
+                filtered_indices
+                    .map_or(Some(row), |indices| indices.get(row).copied())
+                    .and_then(|idx| buffer.get(idx))
+                    .map_or((0, None), |entry| {
+                        (entry.line_number, OverviewMark::of(entry))
+                    })
+            });
+        if changed || self.overview.slots.len() != buckets.min(total_rows) {
+            self.overview.slots = self.overview.marks.slots(buckets);
+        }
+
+        let reverse = self.virtual_scroll.state.reverse_order;
+        let slots = self.overview.slots.len();
+        let slot_height = rect.height() / slots as f32;
+        let tick_width = rect.width() - 4.0;
+        for (slot, mark) in self.overview.slots.iter().enumerate() {
+            let Some(mark) = mark else {
+                continue;
+            };
+            // Slots are in logical order; newest first puts the last on top
+            let position = if reverse { slots - 1 - slot } else { slot };
+            let tick = Rect::from_min_size(
+                egui::pos2(
+                    rect.left() + 2.0,
+                    rect.top() + position as f32 * slot_height,
+                ),
+                Vec2::new(tick_width, slot_height.max(2.0)),
+            );
+            // A bookmark sharing the slot with a problem takes the right half
+            let (level_tick, bookmark_tick) = if mark.level.is_some() && mark.bookmark {
+                tick.split_left_right_at_fraction(0.5)
+            } else {
+                (tick, tick)
+            };
+            if let Some(level) = mark.level {
+                painter.rect_filled(level_tick, 0.0, display_config.level_color(level));
+            }
+            if mark.bookmark {
+                painter.rect_filled(bookmark_tick, 0.0, self.highlighter.theme.bookmark);
+            }
+        }
+
+        // Visible region, in display order like the strip
+        let state = &self.virtual_scroll.state;
+        let row_span = rect.height() / total_rows as f32;
+        let viewport = Rect::from_min_size(
+            egui::pos2(
+                rect.left(),
+                rect.top() + state.first_visible_row as f32 * row_span,
+            ),
+            Vec2::new(
+                rect.width(),
+                (state.visible_row_count as f32 * row_span).max(4.0),
+            ),
+        );
+        painter.rect_filled(
+            viewport,
+            0.0,
+            ui.visuals().selection.bg_fill.gamma_multiply(0.35),
+        );
+
+        if response.clicked() || response.dragged() {
+            if let Some(pos) = response.interact_pointer_pos() {
+                let fraction = ((pos.y - rect.top()) / rect.height()).clamp(0.0, 1.0);
+                let display_row = ((fraction * total_rows as f32) as usize)
+                    .saturating_sub(state.visible_row_count / 2)
+                    .min(total_rows - 1);
+                let logical_row = if reverse {
+                    total_rows - 1 - display_row
+                } else {
+                    display_row
+                };
+                self.scroll_to_row = filtered_indices.map_or(Some(logical_row), |indices| {
+                    indices.get(logical_row).copied()
+                });
+                ui.ctx().request_repaint();
+            }
+        }
+    }
+
     /// Scroll to a specific line (by buffer index)
     pub fn scroll_to_line(&mut self, line_index: usize) {
         self.scroll_to_row = Some(line_index);
@@ -705,6 +1415,80 @@ impl MainView {
         self.scroll_to_row = Some(0);
     }
 
+    /// Scroll down by one page
+    pub fn page_down(&mut self) {
+        self.pending_page_scroll += 1;
+    }
+
+    /// Number of page-down requests not yet applied
+    #[allow(dead_code)]
+    pub fn pending_page_scroll(&self) -> usize {
+        self.pending_page_scroll
+    }
+
+    /// Sticky line with the timestamp and grok context of the topmost visible
+    /// entry, as of the last frame's scroll position
+    fn show_context_header(
+        &self,
+        ui: &mut Ui,
+        buffer: &LogBuffer,
+        filtered_indices: Option<&[usize]>,
+        grok_pattern: Option<&Arc<CompiledPattern>>,
+        display_config: &DisplayConfig,
+        total_rows: usize,
+    ) {
+        let state = &self.virtual_scroll.state;
+        let index_at = |row: usize| {
+            let row = if state.reverse_order {
+                total_rows - 1 - row
+            } else {
+                row
+            };
+            filtered_indices.map_or(Some(row), |indices| indices.get(row).copied())
+        };
+        let first = state.first_visible_row.min(total_rows - 1);
+        let last = (first + state.visible_row_count.saturating_sub(1)).min(total_rows - 1);
+        let Some((top_index, top)) = index_at(first).and_then(|idx| Some((idx, buffer.get(idx)?)))
+        else {
+            return;
+        };
+        let last_line = index_at(last)
+            .and_then(|idx| buffer.get(idx))
+            .map_or(top.line_number, |e| e.line_number);
+
+        // Lines without a timestamp show the one of the last line before them
+        // that has one; the walk back is only redone once the buffer changed
+        let key = (
+            buffer.revision(),
+            buffer.first_line_number(),
+            buffer.last_line_number(),
+            top_index,
+        );
+        let timestamp = match self.context_timestamp.get() {
+            Some((cached, timestamp)) if cached == key => timestamp,
+            _ => {
+                let timestamp = buffer.timestamp_at_or_before(top_index);
+                self.context_timestamp.set(Some((key, timestamp)));
+                timestamp
+            }
+        };
+        let columns = grok_pattern.map(|pattern| pattern.columns.as_slice());
+        let text = context_header_text(top, timestamp, last_line, columns);
+
+        egui::Frame::new()
+            .fill(ui.visuals().faint_bg_color)
+            .inner_margin(egui::Margin::symmetric(8, 2))
+            .show(ui, |ui| {
+                ui.set_width(ui.available_width());
+                ui.label(
+                    egui::RichText::new(text)
+                        .monospace()
+                        .size(display_config.font_size)
+                        .color(self.highlighter.theme.timestamp),
+                );
+            });
+    }
+
     /// Scroll to bottom
     pub fn scroll_to_bottom(&mut self) {
         // Use pending flag so we can use actual total_rows in show()
@@ -721,11 +1505,23 @@ impl MainView {
         self.virtual_scroll.state.toggle_reverse_order();
     }
 
+    /// Set reverse order
+    pub fn set_reverse_order(&mut self, reverse: bool) {
+        if self.is_reverse_order() != reverse {
+            self.toggle_reverse_order();
+        }
+    }
+
     /// Check if reverse order is enabled
     pub fn is_reverse_order(&self) -> bool {
         self.virtual_scroll.state.reverse_order
     }
 
+    /// Take the single-click flag (true once after a line was clicked)
+    pub fn take_line_clicked(&mut self) -> bool {
+        std::mem::take(&mut self.line_clicked)
+    }
+
     /// Get selected entry
     #[allow(dead_code)]
     pub fn get_selected<'a>(&self, buffer: &'a LogBuffer) -> Option<&'a LogEntry> {
@@ -750,7 +1546,7 @@ impl MainView {
 
                 if let Some(idx) = buffer_idx {
                     if let Some(entry) = buffer.get(idx) {
-                        lines.push(entry.content.clone());
+                        lines.push(strip_ansi(&entry.content).into_owned());
                     }
                 }
             }
@@ -760,12 +1556,114 @@ impl MainView {
         } else if let Some(selected) = self.selected_line {
             // Single line selection
             if let Some(entry) = buffer.get(selected) {
-                return Some(entry.content.clone());
+                return Some(strip_ansi(&entry.content).into_owned());
             }
         }
         None
     }
 
+    /// Get selected text with each line prefixed by its line number, in
+    /// display order (newest first in reverse order mode)
+    pub fn get_selected_numbered_text(
+        &self,
+        buffer: &LogBuffer,
+        filtered_indices: Option<&[usize]>,
+    ) -> Option<String> {
+        let mut indices = self.get_selected_indices(filtered_indices);
+        if self.is_reverse_order() {
+            indices.reverse();
+        }
+        let lines: Vec<(usize, Cow<str>)> = indices
+            .iter()
+            .filter_map(|&i| buffer.get(i))
+            .map(|e| (e.line_number, strip_ansi(&e.content)))
+            .collect();
+        if lines.is_empty() {
+            return None;
+        }
+        let lines: Vec<(usize, &str)> = lines.iter().map(|(n, text)| (*n, text.as_ref())).collect();
+        Some(format_numbered_lines(&lines))
+    }
+
+    /// Get the selection as Markdown in display order: a table of the grok
+    /// fields when a pattern is active, otherwise a fenced code block
+    pub fn get_selected_markdown(
+        &self,
+        buffer: &LogBuffer,
+        filtered_indices: Option<&[usize]>,
+        parser: Option<&GrokParser>,
+    ) -> Option<String> {
+        let mut indices = self.get_selected_indices(filtered_indices);
+        if self.is_reverse_order() {
+            indices.reverse();
+        }
+        let lines: Vec<(Cow<str>, Option<&HashMap<String, String>>)> = indices
+            .iter()
+            .filter_map(|&i| buffer.get(i))
+            .map(|e| (strip_ansi(&e.content), e.grok_fields.as_ref()))
+            .collect();
+        if lines.is_empty() {
+            return None;
+        }
+
+        let Some((parser, pattern)) = parser.and_then(|p| Some((p, p.active_pattern()?))) else {
+            let lines: Vec<&str> = lines.iter().map(|(text, _)| text.as_ref()).collect();
+            return Some(format_code_block(&lines));
+        };
+        // Lines scrolled out of view may not have been parsed yet
+        let rows: Vec<Result<HashMap<String, String>, &str>> = lines
+            .iter()
+            .map(|(text, fields)| match fields {
+                Some(fields) => Ok((*fields).clone()),
+                None => parser
+                    .parse_with_format(text)
+                    .map(|(parsed, _)| parsed.fields)
+                    .ok_or(text.as_ref()),
+            })
+            .collect();
+
+        // The pattern's captures, then for JSON and logfmt any other keys sorted
+        let mut extra: Vec<&String> = rows
+            .iter()
+            .flatten()
+            .flat_map(|fields| fields.keys())
+            .filter(|key| pattern.is_structured() && !pattern.columns.contains(*key))
+            .collect();
+        extra.sort();
+        extra.dedup();
+        let columns: Vec<&str> = pattern
+            .columns
+            .iter()
+            .chain(extra)
+            .map(String::as_str)
+            .collect();
+        if columns.is_empty() {
+            let lines: Vec<&str> = lines.iter().map(|(text, _)| text.as_ref()).collect();
+            return Some(format_code_block(&lines));
+        }
+
+        // Unmatched lines go whole into the message (or last) column
+        let raw_column = columns
+            .iter()
+            .position(|c| *c == "message")
+            .unwrap_or(columns.len() - 1);
+        let rows: Vec<Vec<String>> = rows
+            .into_iter()
+            .map(|row| match row {
+                Ok(fields) => columns
+                    .iter()
+                    .map(|c| fields.get(*c).cloned().unwrap_or_default())
+                    .collect(),
+                Err(text) => {
+                    let mut cells = vec![String::new(); columns.len()];
+                    cells[raw_column] = text.to_string();
+                    cells
+                }
+            })
+            .collect();
+        Some(format_markdown_table(&columns, &rows))
+    }
+
     /// Check if there is any selection
     pub fn has_selection(&self) -> bool {
         self.selection_range.is_some() || self.selected_line.is_some()
@@ -842,11 +1740,11 @@ impl MainView {
 
     /// Set theme
     pub fn set_dark_theme(&mut self, dark: bool) {
-        self.highlighter.theme = if dark {
+        self.highlighter.set_theme(if dark {
             crate::highlighter::HighlightTheme::dark()
         } else {
             crate::highlighter::HighlightTheme::light()
-        };
+        });
     }
 
     /// Get the currently visible row range (for on-demand parsing)
@@ -859,9 +1757,9 @@ impl MainView {
         }
         
         let viewport_height = self.virtual_scroll.state.viewport_height;
-        let scroll_offset = self.virtual_scroll.state.scroll_offset;
         
-        let first_visible = (scroll_offset / row_height).floor() as usize;
+        // Already accounts for wrapped rows
+        let first_visible = self.virtual_scroll.state.first_visible_row;
         let visible_count = (viewport_height / row_height).ceil() as usize;
         
         // Add generous overscan for parsing
@@ -878,3 +1776,298 @@ impl Default for MainView {
         Self::new()
     }
 }
+
+/// Replace the timestamp at `span` in a displayed line with its age in
+/// relative mode; lines timed by the fallback get their age prepended, and
+/// lines without any timestamp are shown as they are
+fn apply_timestamp_mode<'a>(
+    text: &'a str,
+    stamp: Option<(DateTime<Local>, TimestampSource)>,
+    span: Option<Range<usize>>,
+    mode: TimestampMode,
+    now: NaiveDateTime,
+) -> Cow<'a, str> {
+    let Some((timestamp, source)) = stamp else {
+        return Cow::Borrowed(text);
+    };
+    if mode == TimestampMode::Absolute {
+        return Cow::Borrowed(text);
+    }
+    // Timestamps keep the line's wall-clock time as their UTC value
+    let age = format_age(now - timestamp.naive_utc());
+    if source != TimestampSource::Line {
+        return Cow::Owned(format!("{}  {}", age, text));
+    }
+    let Some(span) = span else {
+        return Cow::Borrowed(text);
+    };
+    Cow::Owned([&text[..span.start], age.as_str(), &text[span.end..]].concat())
+}
+
+/// Where an entry's parsed timestamp sits in `text`, its raw line with any
+/// prefix stripped
+fn shown_timestamp_span(entry: &LogEntry, text: &str) -> Option<Range<usize>> {
+    let stripped = entry.content.len().checked_sub(text.len())?;
+    let span = entry.timestamp_span.clone()?;
+    (span.start >= stripped).then(|| span.start - stripped..span.end - stripped)
+}
+
+/// Text of the sticky context line: the latest timestamp at the entry and
+/// its grok context fields, or the range of visible line numbers when the
+/// file has no timestamps up to there
+fn context_header_text(
+    top: &LogEntry,
+    timestamp: Option<DateTime<Local>>,
+    last_line: usize,
+    columns: Option<&[String]>,
+) -> String {
+    let Some(timestamp) = timestamp else {
+        let (from, to) = (
+            top.line_number.min(last_line),
+            top.line_number.max(last_line),
+        );
+        return format!("{} {}–{}", t::context_line_range(), from, to);
+    };
+    // Timestamps keep the line's wall-clock time as their UTC value
+    let mut text = timestamp
+        .naive_utc()
+        .format("%Y-%m-%d %H:%M:%S%.3f")
+        .to_string();
+    if let (Some(fields), Some(columns)) = (&top.grok_fields, columns) {
+        for name in columns
+            .iter()
+            .filter(|name| !CONTEXT_SKIPPED_FIELDS.contains(&name.as_str()))
+        {
+            if let Some(value) = fields.get(name).filter(|value| !value.is_empty()) {
+                text.push_str(&format!("  {}={}", name, value));
+            }
+        }
+    }
+    text
+}
+
+/// Short age label such as "2m ago"
+fn format_age(elapsed: TimeDelta) -> String {
+    let secs = elapsed.num_seconds().max(0);
+    let (value, unit) = if secs < 60 {
+        (secs, "s")
+    } else if secs < 3600 {
+        (secs / 60, "m")
+    } else if secs < 86_400 {
+        (secs / 3600, "h")
+    } else {
+        (secs / 86_400, "d")
+    };
+    format!("{}{} {}", value, unit, t::ago())
+}
+
+/// Prefix each line with its line number, right-aligned to the widest number
+pub fn format_numbered_lines(lines: &[(usize, &str)]) -> String {
+    let width = lines
+        .iter()
+        .map(|(n, _)| n.to_string().len())
+        .max()
+        .unwrap_or(0);
+    lines
+        .iter()
+        .map(|(n, content)| format!("{:>width$} | {}", n, content, width = width))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Markdown table with a header row, escaping pipes and line breaks in cells
+pub fn format_markdown_table(columns: &[&str], rows: &[Vec<String>]) -> String {
+    let cell = |text: &str| {
+        text.replace('|', "\\|")
+            .replace("\r\n", "<br>")
+            .replace('\n', "<br>")
+    };
+    let row = |cells: Vec<String>| format!("| {} |", cells.join(" | "));
+
+    let mut lines = vec![
+        row(columns.iter().map(|c| cell(c)).collect()),
+        row(columns.iter().map(|_| "---".to_string()).collect()),
+    ];
+    lines.extend(
+        rows.iter()
+            .map(|cells| row(cells.iter().map(|c| cell(c)).collect())),
+    );
+    lines.join("\n")
+}
+
+/// Lines in a fenced code block, with a fence longer than any backtick run
+/// inside so it can't be closed early
+pub fn format_code_block(lines: &[&str]) -> String {
+    let longest_run = lines
+        .iter()
+        .flat_map(|line| line.split(|c| c != '`'))
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+    let fence = "`".repeat(longest_run.max(2) + 1);
+    format!("{}\n{}\n{}", fence, lines.join("\n"), fence)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grok_parser::BuiltinPattern;
+
+    #[test]
+    fn test_relative_timestamps_replace_only_parsed_timestamps() {
+        let entry = LogEntry::new(1, "2024-01-15 10:30:45 INFO started".to_string(), 0);
+        let now =
+            NaiveDateTime::parse_from_str("2024-01-15 10:32:50", "%Y-%m-%d %H:%M:%S").unwrap();
+
+        let stamp = entry.timestamp.map(|ts| (ts, TimestampSource::Line));
+        let span = shown_timestamp_span(&entry, &entry.content);
+        let relative = TimestampMode::RelativeToNow;
+        let shown = apply_timestamp_mode(&entry.content, stamp, span.clone(), relative, now);
+        assert!(shown.starts_with("2m "), "{}", shown);
+        assert!(shown.ends_with(" INFO started"), "{}", shown);
+        let absolute = TimestampMode::Absolute;
+        let shown = apply_timestamp_mode(&entry.content, stamp, span, absolute, now);
+        assert_eq!(shown, entry.content.as_str());
+
+        // A stripped prefix shifts the timestamp, or hides it when cut into
+        let stripped = &entry.content[11..];
+        let span = shown_timestamp_span(&entry, stripped);
+        assert_eq!(span, None);
+        let prefixed = LogEntry::new(3, "app | 2024-01-15 10:30:45 INFO".to_string(), 0);
+        let stripped = &prefixed.content[6..];
+        let span = shown_timestamp_span(&prefixed, stripped);
+        let shown = apply_timestamp_mode(stripped, stamp, span, relative, now);
+        assert!(shown.starts_with("2m "), "{}", shown);
+        assert!(shown.ends_with(" INFO"), "{}", shown);
+
+        // Lines without a parsed timestamp keep their raw prefix
+        let plain = LogEntry::new(2, "    at com.example.Main".to_string(), 0);
+        let span = shown_timestamp_span(&plain, &plain.content);
+        let shown = apply_timestamp_mode(&plain.content, None, span.clone(), relative, now);
+        assert_eq!(shown, plain.content.as_str());
+
+        // ...and get their fallback time's age in front
+        let shown = apply_timestamp_mode(
+            &plain.content,
+            stamp.map(|(ts, _)| (ts, TimestampSource::Ingestion)),
+            span,
+            relative,
+            now,
+        );
+        assert!(shown.starts_with("2m "), "{}", shown);
+        assert!(shown.ends_with("  at com.example.Main"), "{}", shown);
+
+        assert!(format_age(TimeDelta::seconds(5)).starts_with("5s "));
+        assert!(format_age(TimeDelta::hours(30)).starts_with("1d "));
+    }
+
+    #[test]
+    fn test_numbered_copy_of_filtered_selection() {
+        let mut buffer = LogBuffer::new();
+        for n in 1..=120 {
+            buffer.push(LogEntry::new(n, format!("line {}", n), 0));
+        }
+        // Filtered view showing lines 8, 9, 99 and 100
+        let filtered = [7, 8, 98, 99];
+
+        let mut view = MainView::new();
+        view.selection_range = Some(SelectionRange {
+            start_row: 1,
+            end_row: 3,
+            is_dragging: false,
+        });
+        assert_eq!(
+            view.get_selected_numbered_text(&buffer, Some(&filtered)),
+            Some("  9 | line 9\n 99 | line 99\n100 | line 100".to_string())
+        );
+
+        // Reverse order copies in display order
+        view.set_reverse_order(true);
+        assert_eq!(
+            view.get_selected_numbered_text(&buffer, Some(&filtered)),
+            Some("100 | line 100\n 99 | line 99\n  9 | line 9".to_string())
+        );
+    }
+
+    #[test]
+    fn test_copy_as_markdown_table_or_code_block() {
+        let mut buffer = LogBuffer::new();
+        for (n, line) in ["2024-01-15T10:00:00 ERROR disk | full", "plain ``` line"]
+            .into_iter()
+            .enumerate()
+        {
+            buffer.push(LogEntry::new(n + 1, line.to_string(), 0));
+        }
+
+        let mut view = MainView::new();
+        view.set_selection(0, 1);
+        assert_eq!(
+            view.get_selected_markdown(&buffer, None, None),
+            Some("````\n2024-01-15T10:00:00 ERROR disk | full\nplain ``` line\n````".to_string())
+        );
+
+        // Columns follow the pattern's captures; unmatched lines land in message
+        let mut parser = GrokParser::new();
+        parser
+            .set_builtin_pattern(BuiltinPattern::SimpleLog)
+            .unwrap();
+        assert_eq!(
+            view.get_selected_markdown(&buffer, None, Some(&parser))
+                .unwrap(),
+            [
+                "| timestamp | level | message |",
+                "| --- | --- | --- |",
+                "| 2024-01-15T10:00:00 | ERROR | disk \\| full |",
+                "|  |  | plain ``` line |",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_copy_strips_ansi_sequences() {
+        let mut buffer = LogBuffer::new();
+        buffer.push(LogEntry::new(1, "\x1b[1;31mERROR\x1b[0m disk full".to_string(), 0));
+        buffer.push(LogEntry::new(2, "\x1b[32mok\x1b[m".to_string(), 0));
+
+        let mut view = MainView::new();
+        view.selected_line = Some(0);
+        assert_eq!(view.get_selected_text(&buffer, None), Some("ERROR disk full".to_string()));
+
+        view.set_selection(0, 1);
+        assert_eq!(
+            view.get_selected_text(&buffer, None),
+            Some("ERROR disk full\nok".to_string())
+        );
+        assert_eq!(
+            view.get_selected_numbered_text(&buffer, None),
+            Some("1 | ERROR disk full\n2 | ok".to_string())
+        );
+    }
+
+    #[test]
+    fn test_context_header_shows_timestamp_and_fields_or_line_range() {
+        let mut entry = LogEntry::new(3, "2024-01-15 10:30:45 INFO started".to_string(), 0);
+        entry.set_grok_fields(HashMap::from([
+            ("thread".to_string(), "main".to_string()),
+            ("message".to_string(), "started".to_string()),
+            ("request_id".to_string(), String::new()),
+        ]));
+        let columns = ["thread", "message", "request_id"].map(String::from);
+        assert_eq!(
+            context_header_text(&entry, entry.timestamp, 9, Some(&columns)),
+            "2024-01-15 10:30:45.000  thread=main"
+        );
+
+        // An earlier line's timestamp is shown as given
+        let frame = LogEntry::new(4, "    at com.example.Main".to_string(), 0);
+        assert_eq!(
+            context_header_text(&frame, entry.timestamp, 9, None),
+            "2024-01-15 10:30:45.000"
+        );
+
+        // Without any timestamp, the visible range (in either order) is shown
+        let plain = LogEntry::new(7, "    at com.example.Main".to_string(), 0);
+        assert!(context_header_text(&plain, None, 3, None).ends_with(" 3–7"));
+    }
+}
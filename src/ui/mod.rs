@@ -5,11 +5,17 @@ pub mod advanced_filters_panel;
 pub mod app_titlebar;
 pub mod bookmarks_panel;
 pub mod close_dialog;
+pub mod diff_view;
 pub mod explorer_panel;
 pub mod filter_panel;
+pub mod folder_drop_dialog;
 pub mod global_search_panel;
 pub mod grok_panel;
+pub mod line_detail_panel;
 pub mod main_view;
+pub mod notes_panel;
+pub mod open_recent_dialog;
+pub mod presentation;
 pub mod search_bar;
 pub mod settings_panel;
 pub mod source_picker_dialog;
@@ -17,4 +23,6 @@ pub mod split_view;
 pub mod status_bar;
 pub mod tab_bar;
 pub mod tab_manager;
+pub mod theme;
+pub mod timeline;
 pub mod toolbar;
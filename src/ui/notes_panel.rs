@@ -0,0 +1,81 @@
+//! Notes panel - a per-file scratchpad for investigation notes
+//!
+//! The notes follow the active tab and are stored per file path in the config.
+
+use crate::i18n::Translations as t;
+use egui::{RichText, Ui};
+use std::path::{Path, PathBuf};
+
+/// Actions from the notes panel
+#[derive(Debug, Clone, PartialEq)]
+pub enum NotesAction {
+    /// No action
+    None,
+    /// Notes for a file were edited
+    Changed(PathBuf, String),
+    /// Editing finished, notes can be saved
+    Commit,
+    /// Close the panel
+    Close,
+}
+
+/// Notes panel state
+#[derive(Default)]
+pub struct NotesPanel {
+    /// Whether the panel is visible
+    pub visible: bool,
+    /// File the notes belong to
+    path: Option<PathBuf>,
+    /// Notes being edited
+    text: String,
+}
+
+impl NotesPanel {
+    /// File whose notes are loaded
+    pub fn source(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Load the notes for a file
+    pub fn set_source(&mut self, path: Option<PathBuf>, notes: &str) {
+        self.path = path;
+        self.text = notes.to_string();
+    }
+
+    /// Render the panel
+    pub fn show(&mut self, ui: &mut Ui) -> NotesAction {
+        let mut action = NotesAction::None;
+
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(t::notes()).strong());
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.small_button("✕").on_hover_text(t::close()).clicked() {
+                    action = NotesAction::Close;
+                }
+            });
+        });
+        ui.separator();
+
+        let Some(path) = self.path.clone() else {
+            ui.label(RichText::new(t::notes_no_file()).weak().italics());
+            return action;
+        };
+
+        let response = egui::ScrollArea::vertical()
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                ui.add_sized(
+                    ui.available_size(),
+                    egui::TextEdit::multiline(&mut self.text).hint_text(t::notes_hint()),
+                )
+            })
+            .inner;
+
+        if response.changed() {
+            action = NotesAction::Changed(path, self.text.clone());
+        } else if response.lost_focus() {
+            action = NotesAction::Commit;
+        }
+        action
+    }
+}
@@ -0,0 +1,247 @@
+//! Open Recent dialog
+//!
+//! A keyboard-driven overlay listing recent sources. The first nine entries
+//! are numbered and open with a single key press; typing fuzzy-filters the list.
+//! Once a query is typed, digits are part of it rather than shortcuts.
+
+use crate::i18n::Translations as t;
+use crate::search::fuzzy_score;
+use crate::ui::presentation::display_path;
+use egui::{Key, Modifiers, RichText};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Keys that open the numbered entries 1-9
+const NUMBER_KEYS: [Key; 9] = [
+    Key::Num1,
+    Key::Num2,
+    Key::Num3,
+    Key::Num4,
+    Key::Num5,
+    Key::Num6,
+    Key::Num7,
+    Key::Num8,
+    Key::Num9,
+];
+
+/// A recent source as listed in the dialog
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecentEntry {
+    /// Source path
+    pub path: PathBuf,
+    /// Display label (alias or file name)
+    pub label: String,
+}
+
+/// Filter recent sources by a fuzzy query, best match first.
+/// Without a query the recency order is kept.
+pub fn filter_recents(
+    recents: &[PathBuf],
+    aliases: &HashMap<String, String>,
+    query: &str,
+) -> Vec<RecentEntry> {
+    let mut scored: Vec<(i64, RecentEntry)> = recents
+        .iter()
+        .filter_map(|path| {
+            let path_str = path.to_string_lossy().to_string();
+            let label = aliases.get(&path_str).cloned().unwrap_or_else(|| {
+                path.file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_else(|| path_str.clone())
+            });
+            let score = fuzzy_score(query, &label).max(fuzzy_score(query, &path_str))?;
+            Some((
+                score,
+                RecentEntry {
+                    path: path.clone(),
+                    label,
+                },
+            ))
+        })
+        .collect();
+
+    // Stable sort keeps recency order between equal scores
+    scored.sort_by(|a, b| b.0.cmp(&a.0));
+    scored.into_iter().map(|(_, entry)| entry).collect()
+}
+
+/// Get the entry shown with the given number (1-9)
+pub fn numbered_entry(entries: &[RecentEntry], number: usize) -> Option<&RecentEntry> {
+    if !(1..=NUMBER_KEYS.len()).contains(&number) {
+        return None;
+    }
+    entries.get(number - 1)
+}
+
+/// Open Recent dialog state
+#[derive(Default)]
+pub struct OpenRecentDialog {
+    /// Whether the dialog is open
+    pub open: bool,
+    /// Fuzzy filter query
+    query: String,
+    /// Highlighted entry
+    selected: usize,
+    /// Whether to focus the query input next frame
+    focus_input: bool,
+}
+
+impl OpenRecentDialog {
+    /// Open the dialog with a fresh query
+    pub fn show_dialog(&mut self) {
+        self.open = true;
+        self.query.clear();
+        self.selected = 0;
+        self.focus_input = true;
+    }
+
+    /// Render the dialog. Returns the path to open if one was chosen.
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        recents: &[PathBuf],
+        aliases: &HashMap<String, String>,
+    ) -> Option<PathBuf> {
+        if !self.open {
+            return None;
+        }
+
+        let entries = filter_recents(recents, aliases, &self.query);
+        self.selected = self.selected.min(entries.len().saturating_sub(1));
+
+        // Keyboard handling comes first so number shortcuts never reach the
+        // input; with a query typed they are left to it as literal digits
+        let numbers_open_entries = self.query.is_empty();
+        let (number, up, down, enter, escape) = ctx.input_mut(|i| {
+            let number = NUMBER_KEYS
+                .iter()
+                .position(|key| numbers_open_entries && i.consume_key(Modifiers::NONE, *key))
+                .map(|pos| pos + 1);
+            (
+                number,
+                i.consume_key(Modifiers::NONE, Key::ArrowUp),
+                i.consume_key(Modifiers::NONE, Key::ArrowDown),
+                i.consume_key(Modifiers::NONE, Key::Enter),
+                i.consume_key(Modifiers::NONE, Key::Escape),
+            )
+        });
+
+        if escape {
+            self.open = false;
+            return None;
+        }
+        let chosen = match number {
+            Some(n) => numbered_entry(&entries, n),
+            None if enter => entries.get(self.selected),
+            None => None,
+        };
+        if let Some(entry) = chosen {
+            self.open = false;
+            return Some(entry.path.clone());
+        }
+        if up {
+            self.selected = self.selected.saturating_sub(1);
+        }
+        if down && self.selected + 1 < entries.len() {
+            self.selected += 1;
+        }
+
+        let mut result = None;
+        let mut open = self.open;
+        egui::Window::new(t::open_recent_title())
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_TOP, [0.0, 80.0])
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut self.query)
+                        .hint_text(t::open_recent_hint())
+                        .desired_width(f32::INFINITY),
+                );
+                if self.focus_input {
+                    response.request_focus();
+                    self.focus_input = false;
+                }
+                if response.changed() {
+                    self.selected = 0;
+                }
+
+                ui.add_space(4.0);
+                if entries.is_empty() {
+                    ui.label(RichText::new(t::open_recent_empty()).weak().italics());
+                    return;
+                }
+
+                for (idx, entry) in entries.iter().enumerate() {
+                    let number = if idx < NUMBER_KEYS.len() {
+                        format!("{}", idx + 1)
+                    } else {
+                        " ".to_string()
+                    };
+                    let text = format!("{}  {}", number, entry.label);
+                    let item = ui
+                        .selectable_label(idx == self.selected, RichText::new(text).monospace())
+                        .on_hover_text(display_path(&entry.path));
+                    if item.clicked() {
+                        result = Some(entry.path.clone());
+                    }
+                }
+            });
+
+        self.open = open && result.is_none();
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recents() -> Vec<PathBuf> {
+        vec![
+            PathBuf::from("/var/log/app/error.log"),
+            PathBuf::from("/var/log/nginx/access.log"),
+            PathBuf::from("/home/me/debug.txt"),
+        ]
+    }
+
+    #[test]
+    fn test_number_maps_to_recent_source() {
+        let aliases = HashMap::from([(
+            "/var/log/nginx/access.log".to_string(),
+            "web".to_string(),
+        )]);
+        let entries = filter_recents(&recents(), &aliases, "");
+
+        // Without a query, numbers follow the recency order
+        assert_eq!(numbered_entry(&entries, 1).unwrap().path, recents()[0]);
+        let second = numbered_entry(&entries, 2).unwrap();
+        assert_eq!(second.path, recents()[1]);
+        assert_eq!(second.label, "web");
+        assert_eq!(numbered_entry(&entries, 3).unwrap().label, "debug.txt");
+        assert!(numbered_entry(&entries, 4).is_none());
+        assert!(numbered_entry(&entries, 0).is_none());
+
+        // Filtering renumbers the remaining matches
+        let filtered = filter_recents(&recents(), &aliases, "dbg");
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(
+            numbered_entry(&filtered, 1).unwrap().path,
+            PathBuf::from("/home/me/debug.txt")
+        );
+    }
+
+    #[test]
+    fn test_digits_in_query_filter_literally() {
+        let recents = vec![
+            PathBuf::from("/var/log/app1.log"),
+            PathBuf::from("/var/log/app2.log"),
+            PathBuf::from("/var/log/app12.log"),
+        ];
+        let filtered = filter_recents(&recents, &HashMap::new(), "app2");
+        let labels: Vec<&str> = filtered.iter().map(|e| e.label.as_str()).collect();
+        assert_eq!(labels, ["app2.log", "app12.log"]);
+    }
+}
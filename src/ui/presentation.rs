@@ -0,0 +1,56 @@
+//! Presentation mode - masks file paths while screen sharing
+//!
+//! A global flag consulted by the code that renders paths (tab tooltips,
+//! status messages, explorer and recent file hovers). Only what is shown
+//! changes: opening, copying and the saved config still use the real paths.
+
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Global presentation mode flag
+static PRESENTATION_MODE: AtomicBool = AtomicBool::new(false);
+
+/// Placeholder shown instead of a masked directory
+const MASK: &str = "…";
+
+/// Whether paths are currently masked
+pub fn presentation_mode() -> bool {
+    PRESENTATION_MODE.load(Ordering::Relaxed)
+}
+
+/// Turn presentation mode on or off
+pub fn set_presentation_mode(enabled: bool) {
+    PRESENTATION_MODE.store(enabled, Ordering::Relaxed);
+}
+
+/// Path as it should be shown on screen
+pub fn display_path(path: &Path) -> String {
+    format_path(path, presentation_mode())
+}
+
+/// Format a path, keeping only its file name when `masked`
+pub fn format_path(path: &Path, masked: bool) -> String {
+    if !masked {
+        return path.display().to_string();
+    }
+    match path.file_name() {
+        Some(name) => format!("{}/{}", MASK, name.to_string_lossy()),
+        None => MASK.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path_formatting_honors_mask() {
+        let path = Path::new("/home/alice/projects/secret-client/logs/app.log");
+        assert_eq!(
+            format_path(path, false),
+            "/home/alice/projects/secret-client/logs/app.log"
+        );
+        assert_eq!(format_path(path, true), "…/app.log");
+        assert_eq!(format_path(Path::new("/"), true), "…");
+    }
+}
@@ -1,7 +1,8 @@
 //! Search bar component
 
 use crate::i18n::Translations as t;
-use crate::search::SearchEngine;
+use crate::match_export::MatchExportFormat;
+use crate::search::{AnalysisContext, LogFilter, SearchEngine};
 use egui::{self, Color32, Key, RichText, Ui};
 
 /// Search bar state
@@ -12,6 +13,8 @@ pub struct SearchBar {
     pub input: String,
     /// Whether input should be focused
     pub focus_input: bool,
+    /// Context lines around each match when exporting matches
+    pub export_context: usize,
 }
 
 impl SearchBar {
@@ -21,11 +24,18 @@ impl SearchBar {
             visible: false,
             input: String::new(),
             focus_input: false,
+            export_context: 2,
         }
     }
 
-    /// Show the search bar
-    pub fn show(&mut self, ui: &mut Ui, search: &mut SearchEngine) -> SearchBarAction {
+    /// Show the search bar. `recents` are the recently applied contexts
+    /// offered for quick re-apply.
+    pub fn show(
+        &mut self,
+        ui: &mut Ui,
+        search: &mut SearchEngine,
+        recents: &[AnalysisContext],
+    ) -> SearchBarAction {
         if !self.visible {
             return SearchBarAction::None;
         }
@@ -81,9 +91,14 @@ impl SearchBar {
             }
             case_btn.on_hover_text(t::case_sensitive());
 
+            // Regex and whole word don't apply to fuzzy matching
+            let exact = !search.config.fuzzy;
+
             // Regex toggle
-            let regex_btn =
-                ui.selectable_label(search.config.use_regex, RichText::new(".*").monospace());
+            let regex_btn = ui.add_enabled(
+                exact,
+                egui::Button::selectable(search.config.use_regex, RichText::new(".*").monospace()),
+            );
             if regex_btn.clicked() {
                 search.set_use_regex(!search.config.use_regex);
                 action = SearchBarAction::SearchChanged;
@@ -91,14 +106,39 @@ impl SearchBar {
             regex_btn.on_hover_text(t::use_regex());
 
             // Whole word toggle
-            let word_btn =
-                ui.selectable_label(search.config.whole_word, RichText::new("\\b").monospace());
+            let word_btn = ui.add_enabled(
+                exact,
+                egui::Button::selectable(search.config.whole_word, RichText::new("\\b").monospace()),
+            );
             if word_btn.clicked() {
                 search.set_whole_word(!search.config.whole_word);
                 action = SearchBarAction::SearchChanged;
             }
             word_btn.on_hover_text(t::match_whole_word());
 
+            // Fuzzy toggle
+            let fuzzy_btn =
+                ui.selectable_label(search.config.fuzzy, RichText::new("~").monospace());
+            if fuzzy_btn.clicked() {
+                search.set_fuzzy(!search.config.fuzzy);
+                action = SearchBarAction::SearchChanged;
+            }
+            fuzzy_btn.on_hover_text(t::fuzzy_search());
+
+            // Recently applied search/filter/pattern contexts
+            if !recents.is_empty() {
+                ui.menu_button("🕘", |ui| {
+                    for (i, context) in recents.iter().enumerate() {
+                        if ui.button(context.label()).clicked() {
+                            action = SearchBarAction::ApplyRecentContext(i);
+                            ui.close();
+                        }
+                    }
+                })
+                .response
+                .on_hover_text(t::recent_contexts());
+            }
+
             ui.add_space(16.0);
 
             // Navigation buttons
@@ -119,26 +159,78 @@ impl SearchBar {
             }
 
             // Result count
+            let progress = search.search_progress();
             let result_text = if search.result_count() > 0 {
                 format!(
-                    "{} / {}",
+                    "{} {} {}",
                     search.current_result_number().unwrap_or(0),
+                    t::match_of(),
                     search.result_count()
                 )
-            } else if !self.input.is_empty() {
+            } else if !self.input.is_empty() && progress.is_none() {
                 t::no_results().to_string()
             } else {
                 String::new()
             };
 
-            let text_color = if search.result_count() == 0 && !self.input.is_empty() {
-                Color32::from_rgb(255, 100, 100)
-            } else {
-                Color32::GRAY
-            };
+            let text_color =
+                if search.result_count() == 0 && !self.input.is_empty() && progress.is_none() {
+                    Color32::from_rgb(255, 100, 100)
+                } else {
+                    Color32::GRAY
+                };
 
             ui.label(RichText::new(result_text).color(text_color).size(12.0));
 
+            // Large buffers are still being scanned
+            if let Some(progress) = progress {
+                ui.spinner().on_hover_text(t::search_in_progress());
+                ui.label(
+                    RichText::new(format!("{:.0}%", progress * 100.0))
+                        .color(Color32::GRAY)
+                        .size(11.0),
+                );
+            }
+
+            if search.is_active()
+                && ui
+                    .button("⌫")
+                    .on_hover_text(t::clear_search_highlights())
+                    .clicked()
+            {
+                action = SearchBarAction::ClearHighlights;
+            }
+
+            // Only bookmark once every match was found
+            if search.result_count() > 0
+                && ui
+                    .add_enabled(progress.is_none(), egui::Button::new("🔖"))
+                    .on_hover_text(t::bookmark_all_matches())
+                    .on_disabled_hover_text(t::search_in_progress())
+                    .clicked()
+            {
+                action = SearchBarAction::BookmarkAllMatches;
+            }
+
+            // Export matches with context
+            if search.result_count() > 0 {
+                ui.menu_button("💾", |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(t::export_matches_context());
+                        ui.add(egui::DragValue::new(&mut self.export_context).range(0..=100));
+                    });
+                    ui.separator();
+                    for format in [MatchExportFormat::Text, MatchExportFormat::Json] {
+                        if ui.button(format.display_name()).clicked() {
+                            action = SearchBarAction::ExportMatches(format);
+                            ui.close();
+                        }
+                    }
+                })
+                .response
+                .on_hover_text(t::export_matches());
+            }
+
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                 // Close button
                 if ui.button("✕").on_hover_text(t::close_tooltip()).clicked() {
@@ -161,12 +253,14 @@ impl SearchBar {
         self.visible = false;
     }
 
-    /// Toggle visibility
-    pub fn toggle(&mut self) {
-        if self.visible {
-            self.close();
-        } else {
-            self.open();
+    /// Close the search bar. With `keep_highlights` the matches stay
+    /// highlighted (and find next/previous keep working), otherwise the
+    /// search is cleared.
+    pub fn close_search(&mut self, filter: &mut LogFilter, keep_highlights: bool) {
+        self.close();
+        if !keep_highlights {
+            self.input.clear();
+            filter.clear_search();
         }
     }
 
@@ -196,5 +290,60 @@ pub enum SearchBarAction {
     SearchChanged,
     FindNext,
     FindPrev,
+    /// Save the matches with context to a file
+    ExportMatches(MatchExportFormat),
+    /// Toggle bookmarks on every match in the view
+    BookmarkAllMatches,
+    /// Re-apply a recent context (index into the recents list)
+    ApplyRecentContext(usize),
+    /// Clear the search and its highlights
+    ClearHighlights,
     Close,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log_buffer::LogBuffer;
+    use crate::log_entry::LogEntry;
+
+    fn searched(bar: &mut SearchBar) -> (LogBuffer, LogFilter) {
+        let mut buffer = LogBuffer::new();
+        for (i, line) in ["INFO start", "ERROR failed", "INFO retry", "ERROR failed again"]
+            .iter()
+            .enumerate()
+        {
+            buffer.push(LogEntry::new(i + 1, line.to_string(), 0));
+        }
+        let mut filter = LogFilter::new();
+        bar.open();
+        bar.input = "failed".to_string();
+        filter.search.set_query(bar.input.clone());
+        filter.search.search(&buffer);
+        (buffer, filter)
+    }
+
+    #[test]
+    fn test_closing_keeps_highlights_when_enabled() {
+        let mut bar = SearchBar::new();
+        let (_, mut filter) = searched(&mut bar);
+        filter.search.next();
+
+        bar.close_search(&mut filter, true);
+        assert!(!bar.visible);
+        assert_eq!(bar.input, "failed");
+        assert_eq!(filter.search.result_count(), 2);
+        assert!(filter.search.has_match(1) && filter.search.is_current_match(1));
+        // Find next keeps cycling through the matches
+        assert_eq!(filter.search.next().map(|m| m.buffer_index), Some(3));
+
+        // Without the option closing clears the search
+        let (buffer, mut filter) = searched(&mut bar);
+        bar.close_search(&mut filter, false);
+        filter.apply(&buffer);
+        assert!(bar.input.is_empty());
+        assert!(!filter.search.is_active());
+        assert_eq!(filter.search.result_count(), 0);
+        assert!(!filter.search.has_match(1));
+    }
+}
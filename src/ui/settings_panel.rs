@@ -2,8 +2,15 @@
 //!
 //! Provides UI for configuring server port, theme, language, display settings and more.
 
-use crate::config::{CloseButtonBehavior, DisplayConfig};
+use crate::config::{
+    AccessibilityConfig, AutosaveConfig, ClickAction, CloseButtonBehavior, CorrelationConfig,
+    CorrelationSource, DisplayConfig, FolderDropBehavior, FolderDropConfig, HighlightRule,
+    RedactionConfig, RewriteRule, RowDensity, SpaceKeyAction, StackTraceConfig, TimestampFallback,
+    TimestampMode, TrimStrategy,
+};
 use crate::i18n::{Language, Translations as t};
+use crate::log_entry::LogLevel;
+use crate::ui::presentation;
 use egui::{RichText, Ui};
 
 /// Settings panel state
@@ -14,6 +21,8 @@ pub struct SettingsPanel {
     pub dark_theme: bool,
     /// Enable remote service
     pub enable_remote_service: bool,
+    /// Token agents must send to connect (editable, empty = no check)
+    pub auth_token: String,
     /// Cache directory
     pub cache_dir: String,
     /// Current language
@@ -24,8 +33,46 @@ pub struct SettingsPanel {
     pub mcp_enabled: bool,
     /// MCP server port
     pub mcp_port: String,
+    /// Cache file limit per remote stream in megabytes (0 = unlimited)
+    pub max_cache_mb: u64,
+    /// Returning agents resume their earlier stream
+    pub reuse_agent_streams: bool,
     /// Close button behavior
     pub close_button_behavior: CloseButtonBehavior,
+    /// Space key action
+    pub space_key_action: SpaceKeyAction,
+    /// Folder drop behavior
+    pub folder_drop: FolderDropConfig,
+    /// Periodic autosave
+    pub autosave: AutosaveConfig,
+    /// Where request / correlation ids are read from
+    pub correlation: CorrelationConfig,
+    /// Grouping of multiline stack traces
+    pub stack_traces: StackTraceConfig,
+    /// Why the edited continuation pattern was rejected
+    continuation_error: Option<String>,
+    /// Files up to this many lines get all their grok fields parsed (0 = visible only)
+    pub grok_eager_max_lines: usize,
+    /// Which lines are dropped when a buffer is full
+    pub trim_strategy: TrimStrategy,
+    /// Lines kept at the start of a file when keeping head and tail
+    pub trim_head_lines: usize,
+    /// Lowest level repainted right away while tailing (None = every line)
+    pub repaint_min_level: Option<LogLevel>,
+    /// Repaint interval in milliseconds for batched lines
+    pub reduced_repaint_interval_ms: u64,
+    /// Accessibility options
+    pub accessibility: AccessibilityConfig,
+    /// Redact secrets in copied/exported text
+    pub redaction_enabled: bool,
+    /// Redaction patterns, one per line (editable)
+    pub redaction_patterns: String,
+    /// Redaction replacement text
+    pub redaction_replacement: String,
+    /// Apply rewrite rules to copied/exported text
+    pub rewrite_enabled: bool,
+    /// Rewrite rules (editable)
+    pub rewrite_rules: Vec<RewriteRule>,
 }
 
 impl Default for SettingsPanel {
@@ -47,12 +94,32 @@ impl SettingsPanel {
             server_port: "12500".to_string(),
             dark_theme: true,
             enable_remote_service: false,
+            auth_token: String::new(),
             cache_dir,
             language: Language::default(),
             display_config: DisplayConfig::default(),
             mcp_enabled: false,
             mcp_port: "12600".to_string(),
+            max_cache_mb: 100,
+            reuse_agent_streams: true,
             close_button_behavior: CloseButtonBehavior::Ask,
+            space_key_action: SpaceKeyAction::default(),
+            folder_drop: FolderDropConfig::default(),
+            autosave: AutosaveConfig::default(),
+            correlation: CorrelationConfig::default(),
+            stack_traces: StackTraceConfig::default(),
+            continuation_error: None,
+            grok_eager_max_lines: 20_000,
+            trim_strategy: TrimStrategy::default(),
+            trim_head_lines: 1_000,
+            repaint_min_level: None,
+            reduced_repaint_interval_ms: 1000,
+            accessibility: AccessibilityConfig::default(),
+            redaction_enabled: false,
+            redaction_patterns: String::new(),
+            redaction_replacement: String::new(),
+            rewrite_enabled: false,
+            rewrite_rules: Vec::new(),
         }
     }
 
@@ -98,6 +165,88 @@ impl SettingsPanel {
                 }
             });
 
+            ui.horizontal(|ui| {
+                ui.label(t::row_density());
+                egui::ComboBox::from_id_salt("row_density_selector")
+                    .selected_text(density_label(self.display_config.row_density))
+                    .show_ui(ui, |ui| {
+                        for density in RowDensity::all() {
+                            if ui
+                                .selectable_value(
+                                    &mut self.display_config.row_density,
+                                    *density,
+                                    density_label(*density),
+                                )
+                                .clicked()
+                            {
+                                action = SettingsAction::DisplayConfigChanged;
+                            }
+                        }
+                    });
+            });
+
+            ui.horizontal(|ui| {
+                ui.label(t::click_action());
+                egui::ComboBox::from_id_salt("click_action_selector")
+                    .selected_text(click_action_label(self.display_config.click_action))
+                    .show_ui(ui, |ui| {
+                        for click_action in ClickAction::all() {
+                            if ui
+                                .selectable_value(
+                                    &mut self.display_config.click_action,
+                                    *click_action,
+                                    click_action_label(*click_action),
+                                )
+                                .clicked()
+                            {
+                                action = SettingsAction::DisplayConfigChanged;
+                            }
+                        }
+                    });
+            });
+
+            ui.horizontal(|ui| {
+                ui.label(t::timestamp_fallback());
+                egui::ComboBox::from_id_salt("timestamp_fallback_selector")
+                    .selected_text(timestamp_fallback_label(self.display_config.timestamp_fallback))
+                    .show_ui(ui, |ui| {
+                        for fallback in TimestampFallback::all() {
+                            if ui
+                                .selectable_value(
+                                    &mut self.display_config.timestamp_fallback,
+                                    *fallback,
+                                    timestamp_fallback_label(*fallback),
+                                )
+                                .clicked()
+                            {
+                                action = SettingsAction::DisplayConfigChanged;
+                            }
+                        }
+                    })
+                    .response
+                    .on_hover_text(t::timestamp_fallback_tooltip());
+            });
+
+            ui.horizontal(|ui| {
+                ui.label(t::timestamp_display());
+                egui::ComboBox::from_id_salt("timestamp_mode_selector")
+                    .selected_text(timestamp_mode_label(self.display_config.timestamp_mode))
+                    .show_ui(ui, |ui| {
+                        for mode in TimestampMode::all() {
+                            if ui
+                                .selectable_value(
+                                    &mut self.display_config.timestamp_mode,
+                                    *mode,
+                                    timestamp_mode_label(*mode),
+                                )
+                                .clicked()
+                            {
+                                action = SettingsAction::DisplayConfigChanged;
+                            }
+                        }
+                    });
+            });
+
             ui.horizontal(|ui| {
                 ui.label(t::letter_spacing());
                 if ui
@@ -111,6 +260,107 @@ impl SettingsPanel {
                 }
             });
 
+            ui.horizontal(|ui| {
+                ui.label(t::error_context_lines());
+                if ui
+                    .add(egui::DragValue::new(&mut self.display_config.error_context_lines).range(0..=50))
+                    .changed()
+                {
+                    action = SettingsAction::DisplayConfigChanged;
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.label(t::grok_eager_max_lines());
+                if ui
+                    .add(
+                        egui::DragValue::new(&mut self.grok_eager_max_lines)
+                            .range(0..=1_000_000)
+                            .speed(100),
+                    )
+                    .on_hover_text(t::grok_eager_max_lines_hint())
+                    .changed()
+                {
+                    action = SettingsAction::GrokEagerParseChanged;
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.label(t::trim_strategy());
+                egui::ComboBox::from_id_salt("trim_strategy_selector")
+                    .selected_text(trim_strategy_label(self.trim_strategy))
+                    .show_ui(ui, |ui| {
+                        for strategy in TrimStrategy::all() {
+                            if ui
+                                .selectable_value(
+                                    &mut self.trim_strategy,
+                                    *strategy,
+                                    trim_strategy_label(*strategy),
+                                )
+                                .clicked()
+                            {
+                                action = SettingsAction::TrimStrategyChanged;
+                            }
+                        }
+                    })
+                    .response
+                    .on_hover_text(t::trim_strategy_tooltip());
+            });
+
+            if self.trim_strategy == TrimStrategy::KeepHeadAndTail {
+                ui.horizontal(|ui| {
+                    ui.label(t::trim_head_lines());
+                    if ui
+                        .add(
+                            egui::DragValue::new(&mut self.trim_head_lines)
+                                .range(1..=1_000_000)
+                                .speed(100),
+                        )
+                        .changed()
+                    {
+                        action = SettingsAction::TrimStrategyChanged;
+                    }
+                });
+            }
+
+            ui.horizontal(|ui| {
+                ui.label(t::repaint_min_level());
+                let label = |level: Option<LogLevel>| {
+                    level.map_or(t::repaint_every_line(), |level| level.as_str())
+                };
+                egui::ComboBox::from_id_salt("repaint_min_level_selector")
+                    .selected_text(label(self.repaint_min_level))
+                    .show_ui(ui, |ui| {
+                        let options = std::iter::once(None).chain(LogLevel::all().map(Some));
+                        for level in options {
+                            if ui
+                                .selectable_value(&mut self.repaint_min_level, level, label(level))
+                                .clicked()
+                            {
+                                action = SettingsAction::RepaintLevelChanged;
+                            }
+                        }
+                    })
+                    .response
+                    .on_hover_text(t::repaint_min_level_tooltip());
+            });
+
+            if self.repaint_min_level.is_some() {
+                ui.horizontal(|ui| {
+                    ui.label(t::reduced_repaint_interval());
+                    if ui
+                        .add(
+                            egui::DragValue::new(&mut self.reduced_repaint_interval_ms)
+                                .range(100..=10_000)
+                                .speed(50),
+                        )
+                        .changed()
+                    {
+                        action = SettingsAction::RepaintLevelChanged;
+                    }
+                });
+            }
+
             if ui
                 .checkbox(
                     &mut self.display_config.show_line_numbers,
@@ -121,6 +371,72 @@ impl SettingsPanel {
                 action = SettingsAction::DisplayConfigChanged;
             }
 
+            if ui
+                .checkbox(
+                    &mut self.display_config.copy_with_line_numbers,
+                    t::copy_with_line_numbers_default(),
+                )
+                .changed()
+            {
+                action = SettingsAction::DisplayConfigChanged;
+            }
+
+            if ui
+                .checkbox(
+                    &mut self.display_config.show_source_sizes,
+                    t::show_source_sizes(),
+                )
+                .changed()
+            {
+                action = SettingsAction::DisplayConfigChanged;
+            }
+
+            if ui
+                .checkbox(&mut self.display_config.show_timeline, t::show_timeline())
+                .changed()
+            {
+                action = SettingsAction::DisplayConfigChanged;
+            }
+
+            if ui
+                .checkbox(&mut self.display_config.show_overview, t::show_overview())
+                .changed()
+            {
+                action = SettingsAction::DisplayConfigChanged;
+            }
+
+            if ui
+                .checkbox(
+                    &mut self.display_config.show_context_header,
+                    t::show_context_header(),
+                )
+                .on_hover_text(t::show_context_header_tooltip())
+                .changed()
+            {
+                action = SettingsAction::DisplayConfigChanged;
+            }
+
+            if ui
+                .checkbox(
+                    &mut self.display_config.pause_tail_on_error,
+                    t::pause_tail_on_error(),
+                )
+                .on_hover_text(t::pause_tail_on_error_tooltip())
+                .changed()
+            {
+                action = SettingsAction::DisplayConfigChanged;
+            }
+
+            if ui
+                .checkbox(
+                    &mut self.display_config.keep_search_highlights,
+                    t::keep_search_highlights(),
+                )
+                .changed()
+            {
+                action = SettingsAction::DisplayConfigChanged;
+            }
+
             if ui
                 .checkbox(
                     &mut self.display_config.show_row_separator,
@@ -141,6 +457,105 @@ impl SettingsPanel {
                 action = SettingsAction::DisplayConfigChanged;
             }
 
+            if ui
+                .add_enabled(
+                    self.display_config.show_grok_fields,
+                    egui::Checkbox::new(
+                        &mut self.display_config.grok_columns,
+                        t::grok_columns(),
+                    ),
+                )
+                .changed()
+            {
+                action = SettingsAction::DisplayConfigChanged;
+            }
+
+            if ui
+                .checkbox(
+                    &mut self.display_config.render_ansi_colors,
+                    t::render_ansi_colors(),
+                )
+                .changed()
+            {
+                action = SettingsAction::DisplayConfigChanged;
+            }
+
+            if ui
+                .checkbox(&mut self.display_config.wrap_lines, t::wrap_long_lines())
+                .changed()
+            {
+                action = SettingsAction::DisplayConfigChanged;
+            }
+
+            ui.add_space(4.0);
+            ui.label(t::level_colors());
+            egui::Grid::new("level_colors_grid")
+                .num_columns(3)
+                .spacing([8.0, 4.0])
+                .show(ui, |ui| {
+                    for level in LogLevel::all() {
+                        let color = self.display_config.level_color(level);
+                        let mut rgb = [color.r(), color.g(), color.b()];
+                        ui.label(RichText::new(level.as_str()).monospace().color(color));
+                        if ui.color_edit_button_srgb(&mut rgb).changed() {
+                            self.display_config.level_colors.insert(level, rgb);
+                            action = SettingsAction::DisplayConfigChanged;
+                        }
+                        let custom = self.display_config.level_colors.contains_key(&level);
+                        if ui
+                            .add_enabled(custom, egui::Button::new("↺").small())
+                            .on_hover_text(t::reset_level_color())
+                            .clicked()
+                        {
+                            self.display_config.level_colors.remove(&level);
+                            action = SettingsAction::DisplayConfigChanged;
+                        }
+                        ui.end_row();
+                    }
+                });
+
+            ui.add_space(4.0);
+            ui.label(t::highlight_rules())
+                .on_hover_text(t::highlight_rules_tooltip());
+            let mut remove = None;
+            for (idx, rule) in self.display_config.highlight_rules.iter_mut().enumerate() {
+                ui.horizontal(|ui| {
+                    let mut changed = ui.checkbox(&mut rule.enabled, "").changed();
+                    changed |= ui
+                        .add(
+                            egui::TextEdit::singleline(&mut rule.pattern)
+                                .hint_text(t::highlight_rule_pattern())
+                                .desired_width(120.0)
+                                .code_editor(),
+                        )
+                        .changed();
+                    changed |= ui.color_edit_button_srgb(&mut rule.color).changed();
+                    changed |= ui
+                        .toggle_value(&mut rule.whole_word, "ab")
+                        .on_hover_text(t::match_whole_word())
+                        .changed();
+                    changed |= ui
+                        .toggle_value(&mut rule.regex, ".*")
+                        .on_hover_text(t::use_regex())
+                        .changed();
+                    if changed {
+                        action = SettingsAction::DisplayConfigChanged;
+                    }
+                    if ui.small_button("✕").clicked() {
+                        remove = Some(idx);
+                    }
+                });
+            }
+            if let Some(idx) = remove {
+                self.display_config.highlight_rules.remove(idx);
+                action = SettingsAction::DisplayConfigChanged;
+            }
+            if ui.button(t::add_highlight_rule()).clicked() {
+                self.display_config
+                    .highlight_rules
+                    .push(HighlightRule::default());
+            }
+
             ui.add_space(16.0);
             ui.separator();
             ui.add_space(8.0);
@@ -153,6 +568,31 @@ impl SettingsPanel {
                 action = SettingsAction::ThemeChanged(self.dark_theme);
             }
 
+            if ui
+                .checkbox(&mut self.accessibility.high_contrast, t::high_contrast())
+                .on_hover_text(t::high_contrast_tooltip())
+                .changed()
+            {
+                action = SettingsAction::AccessibilityChanged;
+            }
+
+            if ui
+                .checkbox(&mut self.accessibility.large_targets, t::large_targets())
+                .on_hover_text(t::large_targets_tooltip())
+                .changed()
+            {
+                action = SettingsAction::AccessibilityChanged;
+            }
+
+            let mut presentation_mode = presentation::presentation_mode();
+            if ui
+                .checkbox(&mut presentation_mode, t::presentation_mode())
+                .on_hover_text(t::presentation_mode_tooltip())
+                .changed()
+            {
+                action = SettingsAction::PresentationModeChanged(presentation_mode);
+            }
+
             ui.add_space(8.0);
 
             // Language settings
@@ -226,6 +666,293 @@ impl SettingsPanel {
             ui.separator();
             ui.add_space(8.0);
 
+            // Keyboard settings
+            ui.label(RichText::new(format!("⌨ {}", t::keyboard_settings())).strong());
+            ui.add_space(4.0);
+
+            ui.horizontal(|ui| {
+                ui.label(format!("{}:", t::space_key_action()));
+                egui::ComboBox::from_id_salt("space_key_selector")
+                    .selected_text(space_key_label(self.space_key_action))
+                    .show_ui(ui, |ui| {
+                        for space_action in SpaceKeyAction::all() {
+                            if ui
+                                .selectable_value(
+                                    &mut self.space_key_action,
+                                    *space_action,
+                                    space_key_label(*space_action),
+                                )
+                                .clicked()
+                            {
+                                action = SettingsAction::SpaceKeyActionChanged(self.space_key_action);
+                            }
+                        }
+                    });
+            });
+
+            ui.add_space(16.0);
+            ui.separator();
+            ui.add_space(8.0);
+
+            // Folder drop settings
+            ui.label(RichText::new(format!("📂 {}", t::folder_drop_settings())).strong());
+            ui.add_space(4.0);
+
+            ui.horizontal(|ui| {
+                ui.label(format!("{}:", t::folder_drop_behavior()));
+                egui::ComboBox::from_id_salt("folder_drop_selector")
+                    .selected_text(folder_drop_label(self.folder_drop.behavior))
+                    .show_ui(ui, |ui| {
+                        for behavior in FolderDropBehavior::all() {
+                            if ui
+                                .selectable_value(
+                                    &mut self.folder_drop.behavior,
+                                    *behavior,
+                                    folder_drop_label(*behavior),
+                                )
+                                .clicked()
+                            {
+                                action = SettingsAction::FolderDropChanged;
+                            }
+                        }
+                    });
+            });
+
+            ui.horizontal(|ui| {
+                ui.label(t::folder_drop_max_depth());
+                if ui
+                    .add(egui::DragValue::new(&mut self.folder_drop.max_depth).range(0..=10))
+                    .changed()
+                {
+                    action = SettingsAction::FolderDropChanged;
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.label(t::folder_drop_confirm_above());
+                if ui
+                    .add(egui::DragValue::new(&mut self.folder_drop.confirm_above).range(1..=500))
+                    .changed()
+                {
+                    action = SettingsAction::FolderDropChanged;
+                }
+            });
+
+            ui.add_space(16.0);
+            ui.separator();
+            ui.add_space(8.0);
+
+            // Autosave settings
+            ui.label(RichText::new(format!("💾 {}", t::autosave_settings())).strong());
+            ui.add_space(4.0);
+
+            if ui
+                .checkbox(&mut self.autosave.enabled, t::autosave_enabled())
+                .changed()
+            {
+                action = SettingsAction::AutosaveChanged;
+            }
+
+            ui.add_enabled_ui(self.autosave.enabled, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(t::autosave_interval());
+                    if ui
+                        .add(
+                            egui::DragValue::new(&mut self.autosave.interval_secs)
+                                .range(5..=3600)
+                                .suffix(" s"),
+                        )
+                        .changed()
+                    {
+                        action = SettingsAction::AutosaveChanged;
+                    }
+                });
+            });
+
+            ui.add_space(16.0);
+            ui.separator();
+            ui.add_space(8.0);
+
+            // Correlation id settings
+            ui.label(RichText::new(format!("🔗 {}", t::correlation_settings())).strong());
+            ui.add_space(4.0);
+
+            ui.horizontal(|ui| {
+                ui.label(format!("{}:", t::correlation_source()));
+                egui::ComboBox::from_id_salt("correlation_source_selector")
+                    .selected_text(correlation_source_label(self.correlation.source))
+                    .show_ui(ui, |ui| {
+                        for source in CorrelationSource::all() {
+                            if ui
+                                .selectable_value(
+                                    &mut self.correlation.source,
+                                    *source,
+                                    correlation_source_label(*source),
+                                )
+                                .clicked()
+                            {
+                                action = SettingsAction::CorrelationChanged;
+                            }
+                        }
+                    });
+            });
+
+            ui.horizontal(|ui| {
+                let (label, text) = match self.correlation.source {
+                    CorrelationSource::GrokField => {
+                        (t::correlation_field(), &mut self.correlation.field)
+                    }
+                    CorrelationSource::Regex => {
+                        (t::correlation_pattern(), &mut self.correlation.pattern)
+                    }
+                };
+                ui.label(label);
+                if ui
+                    .add(
+                        egui::TextEdit::singleline(text)
+                            .font(egui::TextStyle::Monospace)
+                            .desired_width(f32::INFINITY),
+                    )
+                    .changed()
+                {
+                    action = SettingsAction::CorrelationChanged;
+                }
+            });
+
+            ui.add_space(16.0);
+            ui.separator();
+            ui.add_space(8.0);
+
+            // Stack trace settings
+            ui.label(RichText::new(format!("🧵 {}", t::stack_traces())).strong());
+            ui.add_space(4.0);
+
+            if ui
+                .checkbox(&mut self.stack_traces.enabled, t::group_stack_traces())
+                .on_hover_text(t::group_stack_traces_tooltip())
+                .changed()
+            {
+                action = SettingsAction::StackTracesChanged;
+            }
+
+            ui.horizontal(|ui| {
+                ui.label(t::continuation_pattern());
+                let response = ui.add_enabled(
+                    self.stack_traces.enabled,
+                    egui::TextEdit::singleline(&mut self.stack_traces.continuation_pattern)
+                        .font(egui::TextStyle::Monospace)
+                        .desired_width(f32::INFINITY),
+                );
+                // Applied once editing ends, and only when the pattern compiles
+                if response.lost_focus() {
+                    match self.stack_traces.continuation_regex() {
+                        Ok(_) => {
+                            self.continuation_error = None;
+                            action = SettingsAction::StackTracesChanged;
+                        }
+                        Err(e) => self.continuation_error = Some(e.to_string()),
+                    }
+                }
+            });
+            if let Some(error) = &self.continuation_error {
+                ui.colored_label(
+                    ui.visuals().error_fg_color,
+                    format!("{}: {}", t::invalid_continuation_pattern(), error),
+                );
+            }
+
+            ui.add_space(16.0);
+            ui.separator();
+            ui.add_space(8.0);
+
+            // Privacy settings
+            ui.label(RichText::new(format!("🔒 {}", t::privacy())).strong());
+            ui.add_space(4.0);
+
+            if ui
+                .checkbox(&mut self.redaction_enabled, t::redact_copied_text())
+                .on_hover_text(t::redact_copied_text_tooltip())
+                .changed()
+            {
+                action = SettingsAction::RedactionConfigChanged;
+            }
+
+            ui.add_enabled_ui(self.redaction_enabled, |ui| {
+                ui.label(t::redaction_patterns());
+                if ui
+                    .add(
+                        egui::TextEdit::multiline(&mut self.redaction_patterns)
+                            .desired_rows(3)
+                            .desired_width(f32::INFINITY)
+                            .code_editor(),
+                    )
+                    .changed()
+                {
+                    action = SettingsAction::RedactionConfigChanged;
+                }
+
+                ui.horizontal(|ui| {
+                    ui.label(t::redaction_replacement());
+                    if ui
+                        .add(
+                            egui::TextEdit::singleline(&mut self.redaction_replacement)
+                                .desired_width(80.0),
+                        )
+                        .changed()
+                    {
+                        action = SettingsAction::RedactionConfigChanged;
+                    }
+                });
+            });
+
+            ui.add_space(8.0);
+
+            if ui
+                .checkbox(&mut self.rewrite_enabled, t::rewrite_copied_text())
+                .on_hover_text(t::rewrite_copied_text_tooltip())
+                .changed()
+            {
+                action = SettingsAction::RedactionConfigChanged;
+            }
+
+            ui.add_enabled_ui(self.rewrite_enabled, |ui| {
+                let mut remove = None;
+                for (idx, rule) in self.rewrite_rules.iter_mut().enumerate() {
+                    ui.horizontal(|ui| {
+                        let pattern = ui.add(
+                            egui::TextEdit::singleline(&mut rule.pattern)
+                                .hint_text(t::rewrite_pattern())
+                                .desired_width(140.0)
+                                .code_editor(),
+                        );
+                        ui.label("→");
+                        let replacement = ui.add(
+                            egui::TextEdit::singleline(&mut rule.replacement)
+                                .hint_text(t::rewrite_replacement())
+                                .desired_width(100.0)
+                                .code_editor(),
+                        );
+                        if pattern.changed() || replacement.changed() {
+                            action = SettingsAction::RedactionConfigChanged;
+                        }
+                        if ui.small_button("✕").clicked() {
+                            remove = Some(idx);
+                        }
+                    });
+                }
+                if let Some(idx) = remove {
+                    self.rewrite_rules.remove(idx);
+                    action = SettingsAction::RedactionConfigChanged;
+                }
+                if ui.button(t::add_rewrite_rule()).clicked() {
+                    self.rewrite_rules.push(RewriteRule::default());
+                }
+            });
+
+            ui.add_space(16.0);
+            ui.separator();
+            ui.add_space(8.0);
+
             // Server settings
             ui.label(RichText::new(format!("🌐 {}", t::remote_service())).strong());
             ui.add_space(4.0);
@@ -240,6 +967,40 @@ impl SettingsPanel {
 
             ui.add_space(4.0);
 
+            ui.horizontal(|ui| {
+                ui.label(t::agent_auth_token());
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut self.auth_token)
+                        .password(true)
+                        .desired_width(160.0)
+                        .hint_text(t::agent_auth_token_hint()),
+                );
+                // Applied once editing ends, not on every keystroke
+                if response.lost_focus() {
+                    action = SettingsAction::AuthTokenChanged;
+                }
+                if ui
+                    .button("🎲")
+                    .on_hover_text(t::generate_auth_token())
+                    .clicked()
+                {
+                    match crate::remote_server::generate_auth_token() {
+                        Ok(token) => {
+                            self.auth_token = token;
+                            action = SettingsAction::AuthTokenChanged;
+                        }
+                        Err(e) => tracing::error!("Failed to generate auth token: {:#}", e),
+                    }
+                }
+                if !self.auth_token.is_empty()
+                    && ui.button("📋").on_hover_text(t::copy_auth_token()).clicked()
+                {
+                    ui.ctx().copy_text(self.auth_token.clone());
+                }
+            });
+
+            ui.add_space(4.0);
+
             if ui
                 .checkbox(&mut self.enable_remote_service, t::enable_remote_service())
                 .changed()
@@ -247,6 +1008,14 @@ impl SettingsPanel {
                 action = SettingsAction::RemoteServiceEnabledChanged;
             }
 
+            if ui
+                .checkbox(&mut self.reuse_agent_streams, t::reuse_agent_streams())
+                .on_hover_text(t::reuse_agent_streams_tooltip())
+                .changed()
+            {
+                action = SettingsAction::ReuseAgentStreamsChanged;
+            }
+
             ui.add_space(4.0);
 
             ui.horizontal(|ui| {
@@ -263,6 +1032,20 @@ impl SettingsPanel {
                 }
             });
 
+            ui.horizontal(|ui| {
+                ui.label(t::max_cache_size());
+                let response = ui
+                    .add(
+                        egui::DragValue::new(&mut self.max_cache_mb)
+                            .range(0..=100_000)
+                            .suffix(" MB"),
+                    )
+                    .on_hover_text(t::max_cache_size_tooltip());
+                if response.changed() {
+                    action = SettingsAction::CacheLimitChanged;
+                }
+            });
+
             ui.add_space(16.0);
             ui.separator();
             ui.add_space(8.0);
@@ -329,10 +1112,109 @@ impl SettingsPanel {
         self.server_port.parse().unwrap_or(12500)
     }
 
+    /// Token agents must send, if one is set
+    pub fn auth_token(&self) -> Option<String> {
+        let token = self.auth_token.trim();
+        (!token.is_empty()).then(|| token.to_string())
+    }
+
     /// Get parsed MCP port number
     pub fn mcp_port_number(&self) -> u16 {
         self.mcp_port.parse().unwrap_or(12600)
     }
+
+    /// Load redaction settings into the editable fields
+    pub fn set_redaction_config(&mut self, config: &RedactionConfig) {
+        self.redaction_enabled = config.enabled;
+        self.redaction_patterns = config.patterns.join("\n");
+        self.redaction_replacement = config.replacement.clone();
+        self.rewrite_enabled = config.rewrite_enabled;
+        self.rewrite_rules = config.rewrite_rules.clone();
+    }
+
+    /// Build redaction config from the editable fields
+    pub fn redaction_config(&self) -> RedactionConfig {
+        RedactionConfig {
+            enabled: self.redaction_enabled,
+            patterns: self
+                .redaction_patterns
+                .lines()
+                .map(|line| line.trim())
+                .filter(|line| !line.is_empty())
+                .map(|line| line.to_string())
+                .collect(),
+            replacement: self.redaction_replacement.clone(),
+            rewrite_enabled: self.rewrite_enabled,
+            rewrite_rules: self.rewrite_rules.clone(),
+        }
+    }
+}
+
+/// Localized label for a row density
+fn density_label(density: RowDensity) -> &'static str {
+    match density {
+        RowDensity::Comfortable => t::density_comfortable(),
+        RowDensity::Compact => t::density_compact(),
+        RowDensity::Dense => t::density_dense(),
+    }
+}
+
+/// Localized label for a click action
+fn click_action_label(click_action: ClickAction) -> &'static str {
+    match click_action {
+        ClickAction::Select => t::click_select(),
+        ClickAction::OpenDetail => t::click_open_detail(),
+    }
+}
+
+/// Localized label for a timestamp fallback
+fn timestamp_fallback_label(fallback: TimestampFallback) -> &'static str {
+    match fallback {
+        TimestampFallback::None => t::timestamp_fallback_none(),
+        TimestampFallback::IngestionTime => t::timestamp_fallback_ingestion(),
+        TimestampFallback::FileMtime => t::timestamp_fallback_mtime(),
+    }
+}
+
+/// Localized label for a buffer trim strategy
+fn trim_strategy_label(strategy: TrimStrategy) -> &'static str {
+    match strategy {
+        TrimStrategy::DropOldest => t::trim_drop_oldest(),
+        TrimStrategy::KeepHeadAndTail => t::trim_keep_head_and_tail(),
+    }
+}
+
+/// Localized label for a timestamp display mode
+fn timestamp_mode_label(mode: TimestampMode) -> &'static str {
+    match mode {
+        TimestampMode::Absolute => t::timestamp_absolute(),
+        TimestampMode::RelativeToNow => t::timestamp_relative(),
+    }
+}
+
+/// Localized label for a Space key action
+fn space_key_label(space_action: SpaceKeyAction) -> &'static str {
+    match space_action {
+        SpaceKeyAction::ToggleMonitoring => t::space_toggle_monitoring(),
+        SpaceKeyAction::PageDown => t::space_page_down(),
+        SpaceKeyAction::Nothing => t::space_nothing(),
+    }
+}
+
+/// Localized label for a folder drop behavior
+fn correlation_source_label(source: CorrelationSource) -> &'static str {
+    match source {
+        CorrelationSource::Regex => t::correlation_source_regex(),
+        CorrelationSource::GrokField => t::correlation_source_field(),
+    }
+}
+
+fn folder_drop_label(behavior: FolderDropBehavior) -> &'static str {
+    match behavior {
+        FolderDropBehavior::OpenAll => t::folder_drop_open_all_files(),
+        FolderDropBehavior::OpenNewest => t::folder_drop_open_newest(),
+        FolderDropBehavior::OpenAsWorkspace => t::folder_drop_open_as_workspace(),
+    }
 }
 
 /// Actions from the settings panel
@@ -340,6 +1222,9 @@ impl SettingsPanel {
 pub enum SettingsAction {
     None,
     PortChanged,
+    AuthTokenChanged,
+    CacheLimitChanged,
+    ReuseAgentStreamsChanged,
     RemoteServiceEnabledChanged,
     ThemeChanged(bool),
     BrowseCacheDir,
@@ -348,4 +1233,15 @@ pub enum SettingsAction {
     McpEnabledChanged(bool),
     McpPortChanged,
     CloseButtonBehaviorChanged(CloseButtonBehavior),
+    SpaceKeyActionChanged(SpaceKeyAction),
+    FolderDropChanged,
+    AutosaveChanged,
+    CorrelationChanged,
+    StackTracesChanged,
+    GrokEagerParseChanged,
+    TrimStrategyChanged,
+    RepaintLevelChanged,
+    AccessibilityChanged,
+    RedactionConfigChanged,
+    PresentationModeChanged(bool),
 }
@@ -5,8 +5,10 @@
 
 use crate::android_logcat::{AndroidDevice, ConnectionType};
 use crate::i18n::Translations as I18n;
+use crate::search::fuzzy_score;
 use eframe::egui::{self, Color32, RichText, Stroke, Vec2};
 use encoding_rs::Encoding;
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -132,12 +134,17 @@ struct FileSuggestion {
     path: PathBuf,
     display_name: String,
     is_dir: bool,
+    /// Fuzzy match score against the typed name
+    score: i64,
 }
 
 // ============================================================================
 // Source Picker Dialog
 // ============================================================================
 
+/// Default cap on listed suggestions
+const DEFAULT_MAX_SUGGESTIONS: usize = 50;
+
 /// Source picker dialog with tabbed interface
 pub struct SourcePickerDialog {
     /// Whether the dialog is open
@@ -168,6 +175,12 @@ pub struct SourcePickerDialog {
     tcp_connect_error: Option<String>,
     /// Whether to show TCP connect input
     show_tcp_connect: bool,
+    /// Whether to clear the device's logcat buffer before viewing it
+    pub clear_logcat: bool,
+    /// Package name or PID to scope each device's logcat to, by serial
+    app_filters: HashMap<String, String>,
+    /// Maximum number of suggestions to list
+    max_suggestions: usize,
 }
 
 impl Default for SourcePickerDialog {
@@ -194,14 +207,24 @@ impl SourcePickerDialog {
             tcp_connect_address: String::new(),
             tcp_connect_error: None,
             show_tcp_connect: false,
+            clear_logcat: true,
+            app_filters: HashMap::new(),
+            max_suggestions: DEFAULT_MAX_SUGGESTIONS,
         }
     }
 
+    /// Set the maximum number of suggestions to list
+    pub fn set_max_suggestions(&mut self, max: usize) {
+        self.max_suggestions = max.max(1);
+    }
+
     /// Get available encodings
     fn get_encodings() -> Vec<(&'static str, Option<&'static Encoding>)> {
         vec![
             ("Auto Detect", None),
             ("UTF-8", Some(encoding_rs::UTF_8)),
+            ("UTF-16LE", Some(encoding_rs::UTF_16LE)),
+            ("UTF-16BE", Some(encoding_rs::UTF_16BE)),
             ("GBK", Some(encoding_rs::GBK)),
             ("GB18030", Some(encoding_rs::GB18030)),
             ("Big5", Some(encoding_rs::BIG5)),
@@ -222,6 +245,11 @@ impl SourcePickerDialog {
         self.android_devices = devices;
     }
 
+    /// Package name or PID entered for a device, empty for all logs
+    pub fn app_filter(&self, serial: &str) -> String {
+        self.app_filters.get(serial).cloned().unwrap_or_default()
+    }
+
     /// Set TCP connection error
     pub fn set_tcp_connect_error(&mut self, error: Option<String>) {
         self.tcp_connect_error = error;
@@ -609,7 +637,7 @@ impl SourcePickerDialog {
 
             if ui.add(browse_btn).clicked() {
                 if let Some(path) = rfd::FileDialog::new()
-                    .add_filter("Log files", &["log", "txt", "json"])
+                    .add_filter("Log files", &["log", "txt", "json", "gz"])
                     .add_filter("All files", &["*"])
                     .pick_file()
                 {
@@ -1109,7 +1137,7 @@ impl SourcePickerDialog {
 
     /// Render a device card
     fn render_device_card(
-        &self,
+        &mut self,
         ui: &mut egui::Ui,
         device: &AndroidDevice,
         colors: &ThemeColors,
@@ -1205,6 +1233,27 @@ impl SourcePickerDialog {
                                 action =
                                     Some(SourcePickerAction::OpenAndroidDevice(device.clone()));
                             }
+
+                            ui.add_space(8.0);
+                            ui.checkbox(&mut self.clear_logcat, I18n::clear_logcat())
+                                .on_hover_text(I18n::clear_logcat_tooltip());
+
+                            ui.add_space(8.0);
+                            let app_filter =
+                                self.app_filters.entry(device.serial.clone()).or_default();
+                            let filter_response = ui
+                                .add(
+                                    egui::TextEdit::singleline(app_filter)
+                                        .hint_text(I18n::logcat_app_filter_hint())
+                                        .desired_width(140.0),
+                                )
+                                .on_hover_text(I18n::logcat_app_filter_tooltip());
+                            if filter_response.lost_focus()
+                                && ui.input(|i| i.key_pressed(egui::Key::Enter))
+                            {
+                                action =
+                                    Some(SourcePickerAction::OpenAndroidDevice(device.clone()));
+                            }
                         }
 
                         if device.connection_type == ConnectionType::Tcp {
@@ -1376,7 +1425,7 @@ impl SourcePickerDialog {
                     let file_name = entry.file_name();
                     let file_name_str = file_name.to_string_lossy().to_lowercase();
 
-                    if pattern.is_empty() || file_name_str.contains(&pattern) {
+                    if let Some(score) = fuzzy_score(&pattern, &file_name_str) {
                         let is_dir = metadata.is_dir();
                         let is_log_file = !is_dir
                             && entry_path
@@ -1396,6 +1445,7 @@ impl SourcePickerDialog {
                                 path: entry_path,
                                 display_name,
                                 is_dir,
+                                score,
                             });
                         }
                     }
@@ -1403,14 +1453,15 @@ impl SourcePickerDialog {
             }
         }
 
-        // Sort: directories first, then by name
-        self.suggestions.sort_by(|a, b| match (a.is_dir, b.is_dir) {
-            (true, false) => std::cmp::Ordering::Less,
-            (false, true) => std::cmp::Ordering::Greater,
-            _ => a.display_name.cmp(&b.display_name),
+        // Sort: best match first, then directories first, then by name
+        self.suggestions.sort_by(|a, b| {
+            b.score
+                .cmp(&a.score)
+                .then_with(|| b.is_dir.cmp(&a.is_dir))
+                .then_with(|| a.display_name.cmp(&b.display_name))
         });
 
         // Limit suggestions
-        self.suggestions.truncate(50);
+        self.suggestions.truncate(self.max_suggestions);
     }
 }
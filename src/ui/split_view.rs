@@ -2,9 +2,30 @@
 //!
 //! Provides a split view container that can show one or two log views side by side.
 //! Supports dragging tabs between panes and adjustable splitter position.
-
+//! In diff mode the two panes are aligned line by line and differences are tinted.
+
+use crate::config::DisplayConfig;
+use crate::i18n::Translations as t;
+use crate::log_buffer::LogBuffer;
+use crate::log_diff::{align_side_by_side, DiffKind, DiffRow};
+use crate::log_entry::LogEntry;
+use crate::ui::diff_view::{added_color, removed_color};
 use crate::ui::tab_bar::TabId;
-use egui::{self, Color32, Rect, Sense, Stroke, Ui, Vec2};
+use egui::{self, Color32, FontId, Rect, Sense, Stroke, Ui, Vec2};
+use std::time::{Duration, Instant};
+
+/// Height of the toolbar above the split panes
+const SPLIT_TOOLBAR_HEIGHT: f32 = 24.0;
+
+/// Lines diffed above and below the visible range in diff mode
+const DIFF_MARGIN: usize = 500;
+
+/// Longest line prefix drawn in diff mode
+const DIFF_MAX_LINE_CHARS: usize = 1000;
+
+/// How often the diff is realigned while lines arrive in or are trimmed
+/// from the diffed range
+const DIFF_REFRESH: Duration = Duration::from_secs(1);
 
 /// Identifies which pane in a split view
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
@@ -16,6 +37,23 @@ pub enum SplitPane {
     Right,
 }
 
+/// How the two panes of a split are rendered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SplitMode {
+    /// Two independent log views
+    #[default]
+    Plain,
+    /// Aligned line diff of the two tabs
+    Diff,
+}
+
+impl SplitMode {
+    /// Get all modes
+    pub fn all() -> &'static [SplitMode] {
+        &[SplitMode::Plain, SplitMode::Diff]
+    }
+}
+
 /// Configuration for the split view
 #[derive(Debug, Clone)]
 pub struct SplitViewConfig {
@@ -31,6 +69,8 @@ pub struct SplitViewConfig {
     pub left_tab: Option<TabId>,
     /// Tab ID for right pane
     pub right_tab: Option<TabId>,
+    /// Plain split or diff
+    pub mode: SplitMode,
 }
 
 impl Default for SplitViewConfig {
@@ -42,6 +82,7 @@ impl Default for SplitViewConfig {
             active_pane: SplitPane::Left,
             left_tab: None,
             right_tab: None,
+            mode: SplitMode::Plain,
         }
     }
 }
@@ -55,6 +96,121 @@ pub enum SplitAction {
     SplitRatioChanged(f32),
 }
 
+/// One pane's lines as seen by the diff
+pub struct DiffSide<'a> {
+    /// Tab shown in the pane
+    pub tab: TabId,
+    /// The tab's buffer
+    pub buffer: &'a LogBuffer,
+    /// Filtered indices if a filter is active
+    pub filtered: Option<&'a [usize]>,
+}
+
+impl DiffSide<'_> {
+    fn len(&self) -> usize {
+        self.filtered.map_or(self.buffer.len(), |f| f.len())
+    }
+
+    /// Line count and first line number, which change as lines are
+    /// appended and trimmed
+    fn shape(&self) -> (usize, usize) {
+        (self.len(), self.entry(0).map_or(0, |e| e.line_number))
+    }
+
+    fn entry(&self, index: usize) -> Option<&LogEntry> {
+        match self.filtered {
+            Some(filtered) => filtered.get(index).and_then(|&i| self.buffer.get(i)),
+            None => self.buffer.get(index),
+        }
+    }
+}
+
+/// Aligned rows for the part of both panes around the visible range
+struct DiffWindow {
+    /// Tabs that were compared
+    tabs: (TabId, TabId),
+    /// Shapes of both sides (see [`DiffSide::shape`]) the window is up to
+    /// date with
+    shapes: ((usize, usize), (usize, usize)),
+    /// First index covered on each side
+    start: (usize, usize),
+    /// End index (exclusive) covered on each side
+    end: (usize, usize),
+    /// Aligned rows, indexing into the sides
+    rows: Vec<DiffRow>,
+    computed_at: Instant,
+}
+
+impl DiffWindow {
+    /// Whether the window no longer covers the rows shown from `top`.
+    /// Lines appended past the window leave it as it is; lines appended
+    /// into it or trimmed from the sides realign it at most every
+    /// [`DIFF_REFRESH`], so tailing doesn't re-diff every frame.
+    fn is_stale(
+        &mut self,
+        tabs: (TabId, TabId),
+        shapes: ((usize, usize), (usize, usize)),
+        top: (usize, usize),
+        visible: usize,
+    ) -> bool {
+        if self.tabs != tabs {
+            return true;
+        }
+        if self.shapes != shapes {
+            let appended_after =
+                |(len, first): (usize, usize), (old_len, old_first): (usize, usize), end: usize| {
+                    first == old_first && len >= old_len && end < old_len
+                };
+            if appended_after(shapes.0, self.shapes.0, self.end.0)
+                && appended_after(shapes.1, self.shapes.1, self.end.1)
+            {
+                self.shapes = shapes;
+            } else if self.computed_at.elapsed() >= DIFF_REFRESH {
+                return true;
+            }
+        }
+        let outside = |start: usize, end: usize, len: usize, top: usize| {
+            (start > 0 && top < start + DIFF_MARGIN / 2)
+                || (end < len && top + visible + DIFF_MARGIN / 2 > end)
+        };
+        let lens = self.lens();
+        outside(self.start.0, self.end.0, lens.0, top.0)
+            || outside(self.start.1, self.end.1, lens.1, top.1)
+    }
+
+    /// Line counts of both sides the window is up to date with
+    fn lens(&self) -> (usize, usize) {
+        (self.shapes.0 .0, self.shapes.1 .0)
+    }
+
+    /// Index of the first row at or after `top` on either side
+    fn row_at(&self, top: (usize, usize)) -> usize {
+        self.rows
+            .iter()
+            .position(|r| r.old.is_some_and(|o| o >= top.0) || r.new.is_some_and(|n| n >= top.1))
+            .unwrap_or(self.rows.len())
+    }
+
+    /// Side positions to anchor on when `row` is the top row
+    fn anchor(&self, row: usize) -> (usize, usize) {
+        let rest = self.rows.get(row..).unwrap_or_default();
+        (
+            rest.iter().find_map(|r| r.old).unwrap_or(self.end.0),
+            rest.iter().find_map(|r| r.new).unwrap_or(self.end.1),
+        )
+    }
+
+    /// Count of (added, removed, changed) rows in the window
+    fn stats(&self) -> (usize, usize, usize) {
+        let count = |kind| self.rows.iter().filter(|r| r.kind == kind).count();
+        (
+            count(DiffKind::Added),
+            count(DiffKind::Removed),
+            count(DiffKind::Changed),
+        )
+    }
+}
+
 /// Split view widget
 pub struct SplitView {
     /// Configuration
@@ -63,6 +219,12 @@ pub struct SplitView {
     dragging_splitter: bool,
     /// Dark theme
     dark_theme: bool,
+    /// Side positions of the top row in diff mode
+    diff_top: (usize, usize),
+    /// Scroll distance not yet turned into whole rows
+    diff_scroll: f32,
+    /// Cached diff around the visible range
+    diff_window: Option<DiffWindow>,
 }
 
 impl Default for SplitView {
@@ -78,6 +240,9 @@ impl SplitView {
             config: SplitViewConfig::default(),
             dragging_splitter: false,
             dark_theme: true,
+            diff_top: (0, 0),
+            diff_scroll: 0.0,
+            diff_window: None,
         }
     }
 
@@ -91,6 +256,11 @@ impl SplitView {
         self.config.is_split
     }
 
+    /// Check if the split is showing a diff
+    pub fn is_diff(&self) -> bool {
+        self.config.is_split && self.config.mode == SplitMode::Diff
+    }
+
     /// Get the active pane
     pub fn active_pane(&self) -> SplitPane {
         self.config.active_pane
@@ -164,6 +334,52 @@ impl SplitView {
             return (available, None, action);
         }
 
+        // Split toolbar
+        let toolbar_rect = Rect::from_min_size(
+            available.min,
+            Vec2::new(available.width(), SPLIT_TOOLBAR_HEIGHT),
+        );
+        ui.scope_builder(egui::UiBuilder::new().max_rect(toolbar_rect), |ui| {
+            ui.horizontal_centered(|ui| {
+                for &mode in SplitMode::all() {
+                    let label = match mode {
+                        SplitMode::Plain => t::split_mode_plain(),
+                        SplitMode::Diff => t::split_mode_diff(),
+                    };
+                    let response = ui.selectable_label(self.config.mode == mode, label);
+                    let response = if mode == SplitMode::Diff {
+                        response.on_hover_text(t::split_mode_diff_tooltip())
+                    } else {
+                        response
+                    };
+                    if response.clicked() {
+                        self.config.mode = mode;
+                    }
+                }
+
+                if let (SplitMode::Diff, Some(window)) = (self.config.mode, &self.diff_window) {
+                    let (added, removed, changed) = window.stats();
+                    ui.separator();
+                    ui.colored_label(
+                        diff_color(DiffKind::Added, self.dark_theme),
+                        format!("+{}", added),
+                    );
+                    ui.colored_label(
+                        diff_color(DiffKind::Removed, self.dark_theme),
+                        format!("-{}", removed),
+                    );
+                    ui.colored_label(
+                        diff_color(DiffKind::Changed, self.dark_theme),
+                        format!("~{}", changed),
+                    );
+                }
+            });
+        });
+        let available = Rect::from_min_max(
+            egui::pos2(available.min.x, toolbar_rect.max.y),
+            available.max,
+        );
+
         // Split mode
         let total_width = available.width();
         let splitter_width = 6.0;
@@ -276,4 +492,155 @@ impl SplitView {
 
         (left_rect, Some(right_rect), action)
     }
+
+    /// Render both panes as an aligned diff
+    ///
+    /// Only the lines around the visible range are diffed, so scrolling
+    /// through huge files stays cheap.
+    pub fn show_diff(
+        &mut self,
+        ui: &mut Ui,
+        left_rect: Rect,
+        right_rect: Rect,
+        left: &DiffSide,
+        right: &DiffSide,
+        display_config: &DisplayConfig,
+    ) {
+        let row_height = display_config.row_height();
+        let visible = (left_rect.height() / row_height).ceil() as usize;
+        let tabs = (left.tab, right.tab);
+        let lens = (left.len(), right.len());
+
+        if self.diff_window.as_ref().is_some_and(|w| w.tabs != tabs) {
+            self.diff_top = (0, 0);
+        }
+        self.diff_top = (self.diff_top.0.min(lens.0), self.diff_top.1.min(lens.1));
+
+        let shapes = (left.shape(), right.shape());
+        let stale = self
+            .diff_window
+            .as_mut()
+            .is_none_or(|w| w.is_stale(tabs, shapes, self.diff_top, visible));
+        if stale {
+            self.diff_window = Some(diff_window(left, right, self.diff_top, visible));
+        }
+        let Some(window) = self.diff_window.as_ref() else {
+            return;
+        };
+        if window.shapes != shapes {
+            // Realigned once the refresh interval is up
+            ui.ctx().request_repaint_after(DIFF_REFRESH);
+        }
+
+        // Scroll by whole rows through the aligned window
+        let mut top_row = window.row_at(self.diff_top);
+        if ui.rect_contains_pointer(left_rect.union(right_rect)) {
+            self.diff_scroll += ui.input(|i| i.smooth_scroll_delta.y);
+            let rows = (-self.diff_scroll / row_height).trunc();
+            self.diff_scroll += rows * row_height;
+            let rows = (rows as isize).clamp(-(DIFF_MARGIN as isize / 2), DIFF_MARGIN as isize / 2);
+            let at_end = window.end == window.lens();
+            let last = if at_end {
+                window.rows.len().saturating_sub(visible)
+            } else {
+                window.rows.len().saturating_sub(1)
+            };
+            top_row = top_row.saturating_add_signed(rows).min(last);
+            self.diff_top = window.anchor(top_row);
+        }
+
+        let font_id = FontId::monospace(display_config.font_size);
+        let rows = window.rows.get(top_row..).unwrap_or_default();
+        for (side, rect, is_left) in [(left, left_rect, true), (right, right_rect, false)] {
+            let painter = ui.painter_at(rect);
+            let gutter = (side.len().max(1).ilog10() as f32 + 2.0) * display_config.font_size * 0.6;
+            for (i, row) in rows.iter().take(visible).enumerate() {
+                let row_rect = Rect::from_min_size(
+                    egui::pos2(rect.min.x, rect.min.y + i as f32 * row_height),
+                    Vec2::new(rect.width(), row_height),
+                );
+                let index = if is_left { row.old } else { row.new };
+                let fill = match (row.kind, index) {
+                    (DiffKind::Unchanged, _) => Color32::TRANSPARENT,
+                    (_, None) => ui.visuals().faint_bg_color,
+                    (kind, Some(_)) => diff_color(kind, self.dark_theme).gamma_multiply(0.2),
+                };
+                painter.rect_filled(row_rect, 0.0, fill);
+
+                let Some(entry) = index.and_then(|i| side.entry(i)) else {
+                    continue;
+                };
+                let content = entry.content.as_str();
+                let content = match content.char_indices().nth(DIFF_MAX_LINE_CHARS) {
+                    Some((end, _)) => &content[..end],
+                    None => content,
+                };
+                let y = row_rect.center().y;
+                painter.text(
+                    egui::pos2(row_rect.min.x + 4.0, y),
+                    egui::Align2::LEFT_CENTER,
+                    entry.line_number.to_string(),
+                    font_id.clone(),
+                    ui.visuals().weak_text_color(),
+                );
+                painter.text(
+                    egui::pos2(row_rect.min.x + 8.0 + gutter, y),
+                    egui::Align2::LEFT_CENTER,
+                    content,
+                    font_id.clone(),
+                    ui.visuals().text_color(),
+                );
+            }
+        }
+    }
+}
+
+/// Diff both sides around `top`, covering the visible rows plus a margin
+fn diff_window(
+    left: &DiffSide,
+    right: &DiffSide,
+    top: (usize, usize),
+    visible: usize,
+) -> DiffWindow {
+    let range = |side: &DiffSide, top: usize| {
+        let start = top.saturating_sub(DIFF_MARGIN);
+        (start, (top + visible + DIFF_MARGIN).min(side.len()))
+    };
+    let (left_start, left_end) = range(left, top.0);
+    let (right_start, right_end) = range(right, top.1);
+    let lines = |side: &DiffSide, start: usize, end: usize| -> Vec<&str> {
+        (start..end)
+            .map(|i| side.entry(i).map_or("", |e| e.content.as_str()))
+            .collect()
+    };
+    let old = lines(left, left_start, left_end);
+    let new = lines(right, right_start, right_end);
+
+    let rows = align_side_by_side(&old, &new)
+        .into_iter()
+        .map(|row| DiffRow {
+            old: row.old.map(|i| i + left_start),
+            new: row.new.map(|i| i + right_start),
+            ..row
+        })
+        .collect();
+    DiffWindow {
+        tabs: (left.tab, right.tab),
+        shapes: (left.shape(), right.shape()),
+        start: (left_start, right_start),
+        end: (left_end, right_end),
+        rows,
+        computed_at: Instant::now(),
+    }
+}
+
+/// Tint for a kind of diff row
+fn diff_color(kind: DiffKind, dark: bool) -> Color32 {
+    match (kind, dark) {
+        (DiffKind::Added, _) => added_color(dark),
+        (DiffKind::Removed, _) => removed_color(dark),
+        (DiffKind::Changed, true) => Color32::from_rgb(230, 180, 80),
+        (DiffKind::Changed, false) => Color32::from_rgb(170, 110, 0),
+        (DiffKind::Unchanged, _) => Color32::TRANSPARENT,
+    }
 }
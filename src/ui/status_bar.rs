@@ -1,11 +1,19 @@
 //! Status bar component
 
+use crate::config::{StripPrefixConfig, TimestampMode};
+use crate::file_stats::FileStats;
 use crate::grok_parser::BuiltinPattern;
 use crate::i18n::Translations as t;
-use crate::log_buffer::LogBuffer;
+use crate::log_buffer::{LevelCounts, LogBuffer};
+use crate::log_entry::LogLevel;
 use crate::log_reader::LogReader;
+use crate::ui::presentation::display_path;
 use egui::{self, Color32, RichText, Ui};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// How long the status bar stays highlighted after a flash
+const FLASH_DURATION: Duration = Duration::from_millis(1500);
 
 /// Grok pattern selection in status bar
 #[derive(Debug, Clone)]
@@ -23,6 +31,10 @@ pub enum StatusBarAction {
     ChangeEncoding(Option<&'static encoding_rs::Encoding>),
     /// Change grok pattern
     ChangeGrokPattern(GrokPatternSelection),
+    /// Change the display prefix strip for the current file (None = disable)
+    ChangeStripPrefix(Option<StripPrefixConfig>),
+    /// Switch between absolute and relative timestamps
+    ToggleTimestampMode,
 }
 
 /// Grok pattern info for display in status bar
@@ -44,6 +56,20 @@ pub struct StatusBar {
     pub message: Option<StatusMessage>,
     /// File read progress (0.0 - 1.0)
     pub progress: Option<f32>,
+    /// Strip prefix input being edited
+    strip_input: String,
+    /// Whether the strip prefix input is a regex
+    strip_is_regex: bool,
+    /// File the strip prefix input was loaded from
+    strip_source: Option<PathBuf>,
+    /// Why the config could not be saved (cleared by the next successful save)
+    config_error: Option<String>,
+    /// Level colors for the histogram, indexed by `level as usize`
+    pub level_colors: [Color32; 6],
+    /// How line timestamps are currently shown
+    pub timestamp_mode: TimestampMode,
+    /// When the last flash started
+    flash_started: Option<Instant>,
 }
 
 impl StatusBar {
@@ -52,6 +78,13 @@ impl StatusBar {
         Self {
             message: None,
             progress: None,
+            strip_input: String::new(),
+            strip_is_regex: false,
+            strip_source: None,
+            config_error: None,
+            level_colors: LogLevel::all().map(|level| level.color()),
+            timestamp_mode: TimestampMode::default(),
+            flash_started: None,
         }
     }
 
@@ -60,12 +93,16 @@ impl StatusBar {
         &mut self,
         ui: &mut Ui,
         file_path: Option<&Path>,
+        source_alias: Option<&str>,
         buffer: &LogBuffer,
         reader: Option<&LogReader>,
         auto_scroll: bool,
         filtered_count: Option<usize>,
         selected_lines: usize,
         grok_info: Option<&GrokPatternInfo>,
+        strip_prefix: Option<&StripPrefixConfig>,
+        file_stats: Option<&FileStats>,
+        decode_progress: Option<f32>,
     ) -> Option<StatusBarAction> {
         let is_dark = ui.ctx().style().visuals.dark_mode;
         let text_color = if is_dark {
@@ -81,13 +118,27 @@ impl StatusBar {
 
         let mut action = None;
 
+        // Fading error highlight after flash()
+        if let Some(started) = self.flash_started {
+            let elapsed = started.elapsed().as_secs_f32() / FLASH_DURATION.as_secs_f32();
+            if elapsed < 1.0 {
+                let color = self.level_colors[LogLevel::Error as usize]
+                    .gamma_multiply(0.35 * (1.0 - elapsed));
+                ui.painter().rect_filled(ui.max_rect(), 0.0, color);
+                ui.ctx().request_repaint();
+            } else {
+                self.flash_started = None;
+            }
+        }
+
         ui.horizontal(|ui| {
             // File info
             if let Some(path) = file_path {
-                let file_name = path
-                    .file_name()
-                    .and_then(|n| n.to_str())
-                    .unwrap_or("Unknown");
+                let file_name = source_alias.unwrap_or_else(|| {
+                    path.file_name()
+                        .and_then(|n| n.to_str())
+                        .unwrap_or("Unknown")
+                });
 
                 ui.label(
                     RichText::new(format!("📄 {}", file_name))
@@ -106,8 +157,21 @@ impl StatusBar {
                 ui.separator();
             }
 
+            // Lazily-loaded files keep only part of the file in memory: the
+            // background scan counts the file up to where it ended and the
+            // buffer the lines tailed since
+            let (total_lines, level_counts, leveled_lines) = match file_stats {
+                Some(stats) => {
+                    let (tailed, tailed_counts) = buffer.level_counts_since(stats.bytes_scanned);
+                    let mut counts = stats.level_counts;
+                    counts.merge(&tailed_counts);
+                    let lines = stats.total_lines + tailed;
+                    (lines.max(buffer.total_lines()), counts, lines)
+                }
+                None => (buffer.total_lines(), *buffer.level_counts(), buffer.len()),
+            };
+
             // Line count
-            let total_lines = buffer.total_lines();
             let displayed_lines = filtered_count.unwrap_or(buffer.len());
 
             if filtered_count.is_some() && displayed_lines != total_lines {
@@ -129,6 +193,35 @@ impl StatusBar {
                 );
             }
 
+            // Level mix of the file
+            level_histogram(ui, &level_counts, leveled_lines, &self.level_colors);
+
+            // Compressed files are decoded whole in the background before they show
+            if let Some(progress) = decode_progress {
+                ui.separator();
+                ui.spinner();
+                ui.label(
+                    RichText::new(format!("{} {:.0}%", t::decompressing(), progress * 100.0))
+                        .color(dim_color)
+                        .small(),
+                );
+            }
+
+            // Counts above are partial until the background scan finishes
+            if let Some(stats) = file_stats.filter(|stats| !stats.complete) {
+                ui.separator();
+                ui.spinner();
+                ui.label(
+                    RichText::new(format!(
+                        "{} {:.0}%",
+                        t::scanning_file(),
+                        stats.progress() * 100.0
+                    ))
+                    .color(dim_color)
+                    .small(),
+                );
+            }
+
             // Selection count
             if selected_lines > 0 {
                 ui.separator();
@@ -149,6 +242,19 @@ impl StatusBar {
 
                 ui.separator();
 
+                if reader.is_compressed() {
+                    ui.label(RichText::new(t::compressed_read_only()).color(dim_color).small());
+                    ui.separator();
+                } else if reader.is_mapped() {
+                    ui.label(
+                        RichText::new(t::mapped_read_only())
+                            .color(dim_color)
+                            .small(),
+                    )
+                    .on_hover_text(t::mapped_read_only_tooltip());
+                    ui.separator();
+                }
+
                 // Encoding selector
                 let current_encoding = reader.encoding_name();
                 egui::ComboBox::from_id_salt("encoding_selector")
@@ -168,6 +274,20 @@ impl StatusBar {
                             action =
                                 Some(StatusBarAction::ChangeEncoding(Some(encoding_rs::UTF_8)));
                         }
+                        if ui
+                            .selectable_label(current_encoding == "UTF-16LE", "UTF-16LE")
+                            .clicked()
+                        {
+                            action =
+                                Some(StatusBarAction::ChangeEncoding(Some(encoding_rs::UTF_16LE)));
+                        }
+                        if ui
+                            .selectable_label(current_encoding == "UTF-16BE", "UTF-16BE")
+                            .clicked()
+                        {
+                            action =
+                                Some(StatusBarAction::ChangeEncoding(Some(encoding_rs::UTF_16BE)));
+                        }
                         if ui
                             .selectable_label(current_encoding == "GBK", "GBK")
                             .clicked()
@@ -268,6 +388,82 @@ impl StatusBar {
                 ui.separator();
             }
 
+            // Display prefix strip (per file)
+            if let Some(path) = file_path {
+                if self.strip_source.as_deref() != Some(path) {
+                    self.strip_source = Some(path.to_path_buf());
+                    self.strip_input = strip_prefix.map(|c| c.pattern.clone()).unwrap_or_default();
+                    self.strip_is_regex = strip_prefix.map(|c| c.is_regex).unwrap_or(false);
+                }
+
+                let strip_color = if strip_prefix.is_some() {
+                    Color32::from_rgb(100, 200, 100)
+                } else {
+                    dim_color
+                };
+
+                ui.menu_button(RichText::new("✂").color(strip_color).small(), |ui| {
+                    ui.set_min_width(240.0);
+                    ui.label(RichText::new(t::strip_prefix()).strong());
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.strip_input)
+                            .hint_text(t::strip_prefix_hint())
+                            .font(egui::TextStyle::Monospace)
+                            .desired_width(f32::INFINITY),
+                    );
+                    ui.checkbox(&mut self.strip_is_regex, t::regex());
+
+                    ui.horizontal(|ui| {
+                        if ui
+                            .add_enabled(!self.strip_input.is_empty(), egui::Button::new(t::apply()))
+                            .clicked()
+                        {
+                            action = Some(StatusBarAction::ChangeStripPrefix(Some(
+                                StripPrefixConfig {
+                                    pattern: self.strip_input.clone(),
+                                    is_regex: self.strip_is_regex,
+                                },
+                            )));
+                            ui.close();
+                        }
+                        if ui
+                            .add_enabled(strip_prefix.is_some(), egui::Button::new(t::clear()))
+                            .clicked()
+                        {
+                            self.strip_input.clear();
+                            action = Some(StatusBarAction::ChangeStripPrefix(None));
+                            ui.close();
+                        }
+                    });
+                })
+                .response
+                .on_hover_text(t::strip_prefix_tooltip());
+
+                ui.separator();
+            }
+
+            // Timestamp display mode
+            let mode_text = match self.timestamp_mode {
+                TimestampMode::Absolute => t::timestamp_absolute(),
+                TimestampMode::RelativeToNow => t::timestamp_relative(),
+            };
+            if ui
+                .add(
+                    egui::Button::new(
+                        RichText::new(format!("🕐 {}", mode_text))
+                            .color(dim_color)
+                            .small(),
+                    )
+                    .frame(false),
+                )
+                .on_hover_text(t::timestamp_mode_tooltip())
+                .clicked()
+            {
+                action = Some(StatusBarAction::ToggleTimestampMode);
+            }
+
+            ui.separator();
+
             // Auto-scroll indicator
             let scroll_text = if auto_scroll {
                 format!("⬇ {}", t::auto())
@@ -292,6 +488,16 @@ impl StatusBar {
 
             // Status message (right aligned)
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                // Stays visible until the config can be saved again
+                if let Some(error) = &self.config_error {
+                    ui.label(
+                        RichText::new(format!("⚠ {}", t::config_save_failed()))
+                            .color(Color32::from_rgb(255, 200, 100))
+                            .small(),
+                    )
+                    .on_hover_text(error);
+                }
+
                 if let Some(msg) = &self.message {
                     let color = match msg.level {
                         StatusLevel::Info => text_color,
@@ -324,6 +530,33 @@ impl StatusBar {
         });
     }
 
+    /// Briefly highlight the status bar to draw attention to it
+    pub fn flash(&mut self) {
+        self.flash_started = Some(Instant::now());
+    }
+
+    /// Track the result of saving the config to `path`.
+    /// The first failure after a successful save is announced with the path;
+    /// the warning indicator stays until a later save succeeds.
+    pub fn report_config_save(&mut self, result: &anyhow::Result<()>, path: Option<&Path>) {
+        match result {
+            Ok(()) => self.config_error = None,
+            Err(e) => {
+                let target = path.map_or_else(String::new, |p| format!(" {}", display_path(p)));
+                let error = format!("{}{}: {:#}", t::config_not_saved(), target, e);
+                if self.config_error.is_none() {
+                    self.set_message(error.clone(), StatusLevel::Warning);
+                }
+                self.config_error = Some(error);
+            }
+        }
+    }
+
+    /// Why the last config save failed, if it did
+    pub fn config_error(&self) -> Option<&str> {
+        self.config_error.as_deref()
+    }
+
     /// Clear the status message
     #[allow(dead_code)]
     pub fn clear_message(&mut self) {
@@ -331,13 +564,11 @@ impl StatusBar {
     }
 
     /// Set progress
-    #[allow(dead_code)]
     pub fn set_progress(&mut self, progress: f32) {
         self.progress = Some(progress.clamp(0.0, 1.0));
     }
 
     /// Clear progress
-    #[allow(dead_code)]
     pub fn clear_progress(&mut self) {
         self.progress = None;
     }
@@ -365,8 +596,55 @@ pub enum StatusLevel {
     Error,
 }
 
+/// Width of the level histogram bar
+const HISTOGRAM_WIDTH: f32 = 72.0;
+
+/// Stacked bar of the share of each log level, with exact counts on hover
+fn level_histogram(ui: &mut Ui, counts: &LevelCounts, lines: usize, colors: &[Color32; 6]) {
+    let total = counts.total();
+    if total == 0 {
+        return;
+    }
+
+    let (rect, response) =
+        ui.allocate_exact_size(egui::vec2(HISTOGRAM_WIDTH, 8.0), egui::Sense::hover());
+    let painter = ui.painter();
+    let mut x = rect.min.x;
+    for level in LogLevel::all() {
+        let count = counts.get(level);
+        if count == 0 {
+            continue;
+        }
+        let width = rect.width() * count as f32 / total as f32;
+        let segment = egui::Rect::from_min_size(
+            egui::pos2(x, rect.min.y),
+            egui::vec2(width, rect.height()),
+        );
+        painter.rect_filled(segment, 0.0, colors[level as usize]);
+        x += width;
+    }
+
+    response.on_hover_ui(|ui| {
+        for level in LogLevel::all().into_iter().rev() {
+            let count = counts.get(level);
+            let share = count as f32 * 100.0 / total as f32;
+            ui.label(
+                RichText::new(format!("{:<5} {:>8}  {:>5.1}%", level.as_str(), count, share))
+                    .color(colors[level as usize])
+                    .monospace(),
+            );
+        }
+        let unleveled = lines.saturating_sub(total);
+        if unleveled > 0 {
+            ui.label(
+                RichText::new(format!("{} {}", unleveled, t::lines_without_level())).weak(),
+            );
+        }
+    });
+}
+
 /// Format file size in human readable form
-fn format_size(bytes: u64) -> String {
+pub fn format_size(bytes: u64) -> String {
     const KB: u64 = 1024;
     const MB: u64 = KB * 1024;
     const GB: u64 = MB * 1024;
@@ -381,3 +659,45 @@ fn format_size(bytes: u64) -> String {
         format!("{} B", bytes)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::autosave::AutosaveWriter;
+    use crate::config::AppConfig;
+
+    #[test]
+    fn test_config_save_failure_warns_once_until_saved() {
+        let dir = tempfile::tempdir().unwrap();
+        // A file where the config directory should be makes the save fail
+        let blocker = dir.path().join("logline");
+        std::fs::write(&blocker, "").unwrap();
+        let bad_path = blocker.join("config.toml");
+
+        let mut status_bar = StatusBar::new();
+        let config = AppConfig::default().to_toml().unwrap();
+        let mut writer = AutosaveWriter::spawn(Duration::from_millis(10));
+        let result = writer.write_now(vec![(bad_path.clone(), config.clone())]);
+        assert!(result.is_err());
+        status_bar.report_config_save(&result, Some(&bad_path));
+
+        let message = status_bar.message.clone().unwrap();
+        assert_eq!(message.level, StatusLevel::Warning);
+        assert!(message.text.contains(&bad_path.display().to_string()));
+        assert!(status_bar.config_error().is_some());
+
+        // Repeated failures keep the indicator without repeating the message
+        status_bar.set_message("other", StatusLevel::Info);
+        let result = writer.write_now(vec![(bad_path.clone(), config.clone())]);
+        status_bar.report_config_save(&result, Some(&bad_path));
+        assert_eq!(status_bar.message.as_ref().unwrap().text, "other");
+        assert!(status_bar.config_error().is_some());
+
+        // A successful retry clears the warning
+        let good_path = dir.path().join("ok").join("config.toml");
+        let result = writer.write_now(vec![(good_path.clone(), config)]);
+        status_bar.report_config_save(&result, Some(&good_path));
+        assert!(status_bar.config_error().is_none());
+        assert!(good_path.exists());
+    }
+}
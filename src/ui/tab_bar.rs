@@ -4,7 +4,9 @@
 //! and visual indicators for active/modified tabs.
 
 use crate::i18n::Translations as I18n;
+use crate::ui::presentation::{display_path, presentation_mode};
 use egui::{self, Color32, Rect, Sense, Stroke, Ui, Vec2};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 /// Truncate text to fit within a given width, adding ellipsis if needed
@@ -82,6 +84,16 @@ pub struct Tab {
     pub is_dirty: bool,
     /// Tooltip text (usually full path)
     pub tooltip: String,
+    /// User-assigned display name (overrides `name`)
+    pub alias: Option<String>,
+    /// Monitoring paused because the tab has been inactive
+    pub idle_paused: bool,
+    /// Initial read still running
+    pub loading: bool,
+    /// Live tab this tab is a frozen snapshot of
+    pub snapshot_of: Option<TabId>,
+    /// Read-only tab merging several sources by timestamp
+    pub merged: bool,
 }
 
 impl Tab {
@@ -100,6 +112,11 @@ impl Tab {
             is_remote: false,
             is_dirty: false,
             tooltip,
+            alias: None,
+            idle_paused: false,
+            loading: false,
+            snapshot_of: None,
+            merged: false,
         }
     }
 
@@ -114,10 +131,74 @@ impl Tab {
             is_remote: true,
             is_dirty: false,
             tooltip,
+            alias: None,
+            idle_paused: false,
+            loading: false,
+            snapshot_of: None,
+            merged: false,
+        }
+    }
+
+    /// Create a snapshot tab of a live tab, labelled with the time it was taken
+    pub fn new_snapshot(id: TabId, source: &Tab, taken_at: &str) -> Self {
+        let path = PathBuf::from(format!("{}@snapshot-{}", source.path.display(), taken_at));
+        let name = format!(
+            "{} ({} {})",
+            source.display_name(),
+            I18n::snapshot(),
+            taken_at
+        );
+        let tooltip = format!("{}\n{} {}", source.tooltip, I18n::snapshot(), taken_at);
+
+        Self {
+            id,
+            name,
+            path,
+            is_remote: source.is_remote,
+            is_dirty: false,
+            tooltip,
+            alias: None,
+            idle_paused: false,
+            loading: false,
+            snapshot_of: Some(source.id),
+            merged: false,
+        }
+    }
+
+    /// Create a tab merging several sources, labelled with the time it was made
+    pub fn new_merged(id: TabId, tags: &[String], created_at: &str) -> Self {
+        let path = PathBuf::from(format!("merged@{}:{}", created_at, tags.join("+")));
+        let name = format!("{} ({})", I18n::merged_sources(), tags.len());
+        let tooltip = format!(
+            "{} {}\n{}",
+            I18n::merged_sources(),
+            created_at,
+            tags.join("\n")
+        );
+
+        Self {
+            id,
+            name,
+            path,
+            is_remote: false,
+            is_dirty: false,
+            tooltip,
+            alias: None,
+            idle_paused: false,
+            loading: false,
+            snapshot_of: None,
+            merged: true,
         }
     }
 }
 
+impl Tab {
+    /// Name shown in the tab (alias if set)
+    pub fn display_name(&self) -> &str {
+        self.alias.as_deref().unwrap_or(&self.name)
+    }
+}
+
 /// Actions from the tab bar
 #[derive(Debug, Clone, PartialEq)]
 pub enum TabBarAction {
@@ -137,6 +218,14 @@ pub enum TabBarAction {
     ReorderTabs(usize, usize),
     /// Open tab in split view (right pane)
     OpenInSplit(TabId),
+    /// Set or clear the tab's alias
+    SetAlias(TabId, Option<String>),
+    /// Freeze the tab's current contents into a snapshot tab
+    TakeSnapshot(TabId),
+    /// Diff a live tab against its snapshot (or a snapshot against its live tab)
+    DiffAgainstSnapshot(TabId),
+    /// Copy the MCP source ID of the tab's source
+    CopySourceId(TabId),
 }
 
 /// Tab bar widget state
@@ -153,6 +242,10 @@ pub struct TabBar {
     hovered_close: Option<TabId>,
     /// Dark theme
     dark_theme: bool,
+    /// Alias being edited in the rename menu
+    rename_input: String,
+    /// Tab the rename input was initialized for
+    rename_target: Option<TabId>,
 }
 
 impl Default for TabBar {
@@ -171,6 +264,8 @@ impl TabBar {
             dragging: None,
             hovered_close: None,
             dark_theme: true,
+            rename_input: String::new(),
+            rename_target: None,
         }
     }
 
@@ -224,6 +319,13 @@ impl TabBar {
         self.tabs.iter().find(|t| &t.path == path).map(|t| t.id)
     }
 
+    /// Apply user-assigned aliases (path -> alias) to open tabs
+    pub fn apply_aliases(&mut self, aliases: &HashMap<String, String>) {
+        for tab in &mut self.tabs {
+            tab.alias = aliases.get(&tab.path.to_string_lossy().to_string()).cloned();
+        }
+    }
+
     /// Get the number of tabs
     pub fn len(&self) -> usize {
         self.tabs.len()
@@ -279,9 +381,9 @@ impl TabBar {
 
         for tab in &self.tabs {
             let display_name = if tab.is_dirty {
-                format!("● {}", tab.name)
+                format!("● {}", tab.display_name())
             } else {
-                tab.name.clone()
+                tab.display_name().to_string()
             };
 
             let galley =
@@ -342,7 +444,19 @@ impl TabBar {
                     }
 
                     // Icon
-                    let icon = if tab.is_remote { "📡" } else { "📄" };
+                    let icon = if tab.loading {
+                        "⏳"
+                    } else if tab.idle_paused {
+                        "⏸"
+                    } else if tab.snapshot_of.is_some() {
+                        "📷"
+                    } else if tab.merged {
+                        "🔀"
+                    } else if tab.is_remote {
+                        "📡"
+                    } else {
+                        "📄"
+                    };
                     let icon_pos = egui::pos2(tab_rect.min.x + 8.0, tab_rect.center().y);
                     ui.painter().text(
                         icon_pos,
@@ -354,9 +468,9 @@ impl TabBar {
 
                     // Tab name with dirty indicator
                     let display_name = if tab.is_dirty {
-                        format!("● {}", tab.name)
+                        format!("● {}", tab.display_name())
                     } else {
-                        tab.name.clone()
+                        tab.display_name().to_string()
                     };
 
                     // Truncate name if too long - force single line
@@ -424,7 +538,19 @@ impl TabBar {
                     );
 
                     // Show tooltip - on_hover_text returns Self so we reassign
-                    let tab_response = tab_response.on_hover_text(&tab.tooltip);
+                    let tooltip = if presentation_mode() {
+                        display_path(&tab.path)
+                    } else {
+                        tab.tooltip.clone()
+                    };
+                    let tab_response = if tab.loading {
+                        tab_response.on_hover_text(format!("{}\n{}", tooltip, I18n::tab_loading()))
+                    } else if tab.idle_paused {
+                        tab_response
+                            .on_hover_text(format!("{}\n{}", tooltip, I18n::tab_idle_paused()))
+                    } else {
+                        tab_response.on_hover_text(tooltip)
+                    };
 
                     if tab_response.clicked() {
                         action = TabBarAction::SelectTab(tab.id);
@@ -451,6 +577,51 @@ impl TabBar {
 
                         ui.separator();
 
+                        // Inline rename (alias)
+                        ui.menu_button(I18n::rename(), |ui| {
+                            let first_frame = self.rename_target != Some(tab.id);
+                            if first_frame {
+                                self.rename_target = Some(tab.id);
+                                self.rename_input = tab.display_name().to_string();
+                            }
+
+                            let response = ui.add(
+                                egui::TextEdit::singleline(&mut self.rename_input)
+                                    .hint_text(tab.name.as_str())
+                                    .desired_width(180.0),
+                            );
+                            if first_frame {
+                                response.request_focus();
+                            }
+
+                            ui.horizontal(|ui| {
+                                if ui.button(I18n::apply()).clicked()
+                                    || (response.lost_focus()
+                                        && ui.input(|i| i.key_pressed(egui::Key::Enter)))
+                                {
+                                    action = TabBarAction::SetAlias(
+                                        tab.id,
+                                        Some(self.rename_input.clone()),
+                                    );
+                                    self.rename_target = None;
+                                    ui.close();
+                                }
+                                if ui
+                                    .add_enabled(
+                                        tab.alias.is_some(),
+                                        egui::Button::new(I18n::reset_name()),
+                                    )
+                                    .clicked()
+                                {
+                                    action = TabBarAction::SetAlias(tab.id, None);
+                                    self.rename_target = None;
+                                    ui.close();
+                                }
+                            });
+                        });
+
+                        ui.separator();
+
                         // Split view options
                         if ui.button(I18n::open_in_split()).clicked() {
                             action = TabBarAction::OpenInSplit(tab.id);
@@ -459,6 +630,33 @@ impl TabBar {
 
                         ui.separator();
 
+                        let live = tab.snapshot_of.is_none() && !tab.merged;
+                        if live && ui.button(I18n::take_snapshot()).clicked() {
+                            action = TabBarAction::TakeSnapshot(tab.id);
+                            ui.close();
+                        }
+                        let has_snapshot = tab.snapshot_of.is_some()
+                            || self.tabs.iter().any(|t| t.snapshot_of == Some(tab.id));
+                        if ui
+                            .add_enabled(
+                                has_snapshot,
+                                egui::Button::new(I18n::diff_against_snapshot()),
+                            )
+                            .clicked()
+                        {
+                            action = TabBarAction::DiffAgainstSnapshot(tab.id);
+                            ui.close();
+                        }
+
+                        ui.separator();
+
+                        if live && ui.button(I18n::copy_source_id()).clicked() {
+                            action = TabBarAction::CopySourceId(tab.id);
+                            ui.close();
+                        }
+
+                        ui.separator();
+
                         if ui.button(I18n::close_all()).clicked() {
                             action = TabBarAction::CloseAllTabs;
                             ui.close();
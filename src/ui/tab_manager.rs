@@ -5,23 +5,33 @@
 //! Also supports split view for viewing two logs side by side.
 
 use crate::bookmarks::BookmarksStore;
-use crate::config::FileGrokConfig;
+use crate::config::{
+    FileGrokConfig, SpaceKeyAction, SplitLayoutConfig, TimestampFallback, TrimStrategy,
+};
+use crate::file_stats::FileStatsScanner;
 use crate::file_watcher::FileWatcher;
 use crate::grok_parser::GrokParser;
 use crate::log_buffer::{LogBuffer, LogBufferConfig};
-use crate::log_entry::LogEntry;
-use crate::log_reader::{LogReader, LogReaderConfig};
-use crate::search::LogFilter;
+use crate::log_diff::LogDiff;
+use crate::log_entry::{LogEntry, LogLevel};
+use crate::log_merge::{read_and_merge, source_tags};
+use crate::log_reader::{FileIdentity, LogReader, LogReaderConfig};
+use crate::search::{FilterConfig, LogFilter};
 use crate::ui::main_view::MainView;
 use crate::ui::split_view::{SplitAction, SplitPane, SplitView};
 use crate::ui::tab_bar::{Tab, TabBar, TabBarAction, TabId};
-
-use anyhow::Result;
-use crossbeam_channel::{bounded, Receiver, Sender};
-use std::collections::HashMap;
-use std::path::PathBuf;
+use crate::ui::timeline::Timeline;
+
+use anyhow::{anyhow, Result};
+use chrono::NaiveDateTime;
+use crossbeam_channel::{bounded, Receiver, Sender, TryRecvError};
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 /// Messages from background reader thread
 #[derive(Debug)]
@@ -45,6 +55,80 @@ pub enum ReaderCommand {
     LoadPreviousChunk(u64, usize), // before_offset, max_lines
 }
 
+/// Wakes the UI from reader threads when a line at or above a minimum level
+/// arrives, so reduced-update mode doesn't wait for its batched repaint
+#[derive(Clone, Default)]
+pub struct RepaintTrigger {
+    /// Context to repaint (None until the app hands it over)
+    ctx: Option<egui::Context>,
+    /// Lowest level that repaints right away (None = reduced-update mode off),
+    /// shared so running readers see settings changes
+    min_level: Arc<RwLock<Option<LogLevel>>>,
+}
+
+impl RepaintTrigger {
+    /// Set the lowest level that repaints right away
+    pub fn set_min_level(&self, level: Option<LogLevel>) {
+        if let Ok(mut min_level) = self.min_level.write() {
+            *min_level = level;
+        }
+    }
+
+    /// Whether lines that just arrived should repaint right away
+    fn should_repaint(&self, entries: &[LogEntry]) -> bool {
+        let Some(min) = self.min_level.read().ok().and_then(|level| *level) else {
+            return false;
+        };
+        entries
+            .iter()
+            .any(|e| e.level.is_some_and(|level| level.is_at_least(min)))
+    }
+
+    /// Repaint now
+    fn repaint(&self) {
+        if let Some(ctx) = &self.ctx {
+            ctx.request_repaint();
+        }
+    }
+}
+
+/// Result of the initial tail read of a file
+struct InitialLoad {
+    /// None for merges, which have no file to follow
+    reader: Option<LogReader>,
+    entries: Vec<LogEntry>,
+    start_offset: u64,
+    total_lines: usize,
+}
+
+/// A file open whose initial read is still running in the background
+pub struct PendingOpen {
+    /// Receives the initial read once it's done
+    rx: Receiver<Result<InitialLoad>>,
+    /// Bookmarked line numbers and their colors to restore once lines are loaded
+    bookmarks: Vec<(usize, u8)>,
+    /// Grok results kept from before a reload
+    grok_results: HashMap<usize, LogEntry>,
+    /// Compressed bytes decoded so far and the file size, for compressed files
+    decode_progress: Option<(Arc<AtomicU64>, u64)>,
+}
+
+/// A bounded live capture: monitoring stops after a number of appended lines
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineCapture {
+    /// Lines to capture before monitoring stops
+    pub limit: usize,
+    /// Lines captured so far
+    pub captured: usize,
+}
+
+impl LineCapture {
+    /// Whether the line budget has been reached
+    pub fn is_complete(&self) -> bool {
+        self.captured >= self.limit
+    }
+}
+
 /// State for a single tab
 pub struct TabState {
     /// File path
@@ -69,14 +153,58 @@ pub struct TabState {
     pub filter_active: bool,
     /// Main log view state
     pub main_view: MainView,
+    /// Timeline sparkline state
+    pub timeline: Timeline,
     /// Pending entries count (for batching)
     pub pending_entries: usize,
     /// Index of the next entry to check for grok parsing
     pub grok_parse_progress: usize,
+    /// Every line gets its grok fields, not only the visible ones: loaded
+    /// lines are parsed in the background and new ones as they arrive
+    pub grok_parse_all: bool,
+    /// Background grok parses, with the results keyed by line number
+    pub grok_parse_jobs: Vec<Receiver<HashMap<usize, LogEntry>>>,
     /// Per-tab grok parser (for file-specific patterns)
     pub grok_parser: Option<GrokParser>,
     /// Per-tab grok config
     pub grok_config: Option<FileGrokConfig>,
+    /// Background full-file statistics (only for lazily-loaded files)
+    pub file_stats: Option<FileStatsScanner>,
+    /// This tab's own filter, set aside while the pinned filter is applied
+    pub own_filter: Option<FilterConfig>,
+    /// When the tab was last shown
+    pub last_active: Instant,
+    /// Monitoring paused because the tab has been inactive
+    pub idle_paused: bool,
+    /// The reader was told to stop and hasn't been restarted
+    pub monitoring_stopped: bool,
+    /// Live tab this tab is a frozen snapshot of (snapshots have no reader)
+    pub snapshot_of: Option<TabId>,
+    /// Read-only merge of several sources (no reader)
+    pub merged: bool,
+    /// Bounded capture in progress (or completed but not yet reported)
+    pub capture: Option<LineCapture>,
+    /// How long the reader holds back a partially written last line
+    pub partial_line_timeout: Duration,
+    /// Files at least this large are memory-mapped as read-only (None = never)
+    pub mmap_threshold: Option<u64>,
+    /// Wakes the UI when important lines arrive in reduced-update mode
+    pub repaint_trigger: RepaintTrigger,
+    /// Initial read in progress; the tab shows as loading until it's done
+    pub loading: Option<PendingOpen>,
+    /// Highest level among lines appended since it was last taken
+    pub arrived_level: Option<LogLevel>,
+    /// The file was rotated or truncated and reloaded (not yet reported)
+    pub reloaded: bool,
+    /// Line number of the first ERROR or FATAL line appended since it was
+    /// last handled
+    pub error_arrived: Option<usize>,
+    /// First and last timestamps, with the buffer revision and shape
+    /// (length, first and last line number) they were found for
+    time_bounds: (
+        (u64, usize, usize, usize),
+        Option<(NaiveDateTime, NaiveDateTime)>,
+    ),
 }
 
 impl TabState {
@@ -97,10 +225,38 @@ impl TabState {
             filtered_indices: Vec::new(),
             filter_active: false,
             main_view: MainView::with_id(view_id),
+            timeline: Timeline::default(),
             pending_entries: 0,
             grok_parse_progress: 0,
+            grok_parse_all: false,
+            grok_parse_jobs: Vec::new(),
             grok_parser: None,
             grok_config: None,
+            file_stats: None,
+            own_filter: None,
+            last_active: Instant::now(),
+            idle_paused: false,
+            monitoring_stopped: false,
+            snapshot_of: None,
+            merged: false,
+            capture: None,
+            partial_line_timeout: LogReaderConfig::default().partial_line_timeout,
+            mmap_threshold: None,
+            repaint_trigger: RepaintTrigger::default(),
+            loading: None,
+            arrived_level: None,
+            reloaded: false,
+            error_arrived: None,
+            time_bounds: Default::default(),
+        }
+    }
+
+    /// Reader configuration for this tab
+    fn reader_config(&self) -> LogReaderConfig {
+        LogReaderConfig {
+            encoding: self.encoding,
+            partial_line_timeout: self.partial_line_timeout,
+            ..Default::default()
         }
     }
 
@@ -109,44 +265,204 @@ impl TabState {
         &mut self,
         encoding: Option<&'static encoding_rs::Encoding>,
         bookmarks_store: &BookmarksStore,
+    ) -> Result<()> {
+        self.start_open(encoding, bookmarks_store, HashMap::new())
+    }
+
+    /// Open the file and start its initial read in the background.
+    /// The tab stays loading until [`Self::poll_loading`] sees the read finish.
+    fn start_open(
+        &mut self,
+        encoding: Option<&'static encoding_rs::Encoding>,
+        bookmarks_store: &BookmarksStore,
+        grok_results: HashMap<usize, LogEntry>,
     ) -> Result<()> {
         // Save encoding
         self.encoding = encoding;
 
-        // Create reader with optional encoding
-        let config = LogReaderConfig {
-            encoding,
-            ..Default::default()
-        };
-        let mut reader = LogReader::with_config(&self.path, config)?;
+        // Create reader with optional encoding; this is cheap, so errors such
+        // as a missing file are still reported right away
+        let mut reader = LogReader::with_config(&self.path, self.reader_config())?;
 
         // Read initial content using tail mode for better performance with large files
         let initial_lines = self.buffer.chunk_size() * 2; // Load ~10k lines initially
-        let (entries, start_offset, total_lines) = reader.read_tail(initial_lines)?;
-        
+        // Compressed files can't be read backwards, so they are decoded whole
+        let max_lines = self.buffer.max_lines();
+        let map = reader.can_map()
+            && self
+                .mmap_threshold
+                .is_some_and(|threshold| reader.file_size() >= threshold);
+        let decode_progress = reader
+            .is_compressed()
+            .then(|| (Arc::new(AtomicU64::new(0)), reader.file_size()));
+        let thread_progress = decode_progress.as_ref().map(|(bytes, _)| bytes.clone());
+        let (tx, rx) = bounded(1);
+        thread::spawn(move || {
+            let load = if let Some(progress) = thread_progress {
+                reader
+                    .read_compressed_with_progress(max_lines, &progress)
+                    .map(|(entries, _)| InitialLoad {
+                        reader: Some(reader),
+                        // Nothing beyond max_lines can be paged in later
+                        total_lines: entries.len(),
+                        entries,
+                        start_offset: 0,
+                    })
+            } else if map {
+                reader
+                    .read_mapped(max_lines)
+                    .map(|(entries, _)| InitialLoad {
+                        reader: Some(reader),
+                        total_lines: entries.len(),
+                        entries,
+                        start_offset: 0,
+                    })
+            } else {
+                reader
+                    .read_tail(initial_lines)
+                    .map(|(entries, start_offset, total_lines)| InitialLoad {
+                        reader: Some(reader),
+                        entries,
+                        start_offset,
+                        total_lines,
+                    })
+            };
+            let _ = tx.send(load);
+        });
+
+        let bookmarks = bookmarks_store
+            .get_bookmarks(&self.path)
+            .map(|b| {
+                b.lines
+                    .iter()
+                    .map(|line| (*line, b.colors.get(line).copied().unwrap_or(0)))
+                    .collect()
+            })
+            .unwrap_or_default();
+        self.loading = Some(PendingOpen {
+            rx,
+            bookmarks,
+            grok_results,
+            decode_progress,
+        });
+        Ok(())
+    }
+
+    /// Read and merge `paths` in the background into this (merged) tab
+    fn start_merge(&mut self, paths: Vec<PathBuf>, tags: Vec<String>) {
+        let max_lines = self.buffer.max_lines();
+        let (tx, rx) = bounded(1);
+        thread::spawn(move || {
+            let load = read_and_merge(&paths, &tags, max_lines).map(|entries| InitialLoad {
+                reader: None,
+                total_lines: entries.len(),
+                entries,
+                start_offset: 0,
+            });
+            let _ = tx.send(load);
+        });
+        self.loading = Some(PendingOpen {
+            rx,
+            bookmarks: Vec::new(),
+            grok_results: HashMap::new(),
+            decode_progress: None,
+        });
+    }
+
+    /// Whether settings changed in the tab are saved for its file; snapshots
+    /// and merges have no file of their own
+    pub fn persists_settings(&self) -> bool {
+        self.snapshot_of.is_none() && !self.merged
+    }
+
+    /// Whether the initial read is still running
+    pub fn is_loading(&self) -> bool {
+        self.loading.is_some()
+    }
+
+    /// Progress (0.0 - 1.0) of a compressed file still being decoded
+    pub fn decode_progress(&self) -> Option<f32> {
+        let (bytes, total) = self.loading.as_ref()?.decode_progress.as_ref()?;
+        if *total == 0 {
+            return Some(1.0);
+        }
+        let decoded = bytes.load(Ordering::Relaxed);
+        Some((decoded as f64 / *total as f64).min(1.0) as f32)
+    }
+
+    /// Finish opening once the initial read is done.
+    /// Returns None while still loading (or if not loading at all).
+    pub fn poll_loading(&mut self) -> Option<Result<()>> {
+        let load = match self.loading.as_ref()?.rx.try_recv() {
+            Ok(load) => load,
+            Err(TryRecvError::Empty) => return None,
+            Err(TryRecvError::Disconnected) => Err(anyhow!("Initial read stopped unexpectedly")),
+        };
+        let pending = self.loading.take()?;
+        Some(load.and_then(|load| self.finish_open(load, pending)))
+    }
+
+    /// Populate the buffer from the initial read and start following the file
+    fn finish_open(&mut self, load: InitialLoad, pending: PendingOpen) -> Result<()> {
+        let InitialLoad {
+            reader,
+            entries,
+            start_offset,
+            total_lines,
+        } = load;
+
         // Initialize buffer with lazy load state
         self.buffer.init_with_tail(entries, start_offset, total_lines);
+        self.refresh_file_mtime();
+
+        // Only part of the file is in memory, so scan the rest in the background
+        // for accurate totals in the status bar
+        self.file_stats = if self.buffer.lazy_load.enabled {
+            let encoding = reader
+                .as_ref()
+                .map(LogReader::encoding)
+                .or(self.encoding)
+                .unwrap_or(encoding_rs::UTF_8);
+            FileStatsScanner::start(&self.path, encoding)
+        } else {
+            None
+        };
 
         // Restore bookmarks for this file
-        if let Some(file_bookmarks) = bookmarks_store.get_bookmarks(&self.path) {
-            let bookmarked_lines: Vec<usize> = file_bookmarks.lines.iter().copied().collect();
-            let indices_to_bookmark: Vec<usize> = bookmarked_lines
-                .into_iter()
-                .filter_map(|line_number| {
-                    self.buffer
-                        .iter()
-                        .position(|e| e.line_number == line_number)
-                })
-                .collect();
+        let indices_to_bookmark: Vec<(usize, u8)> = pending
+            .bookmarks
+            .into_iter()
+            .filter_map(|(line_number, color)| {
+                self.buffer
+                    .iter()
+                    .position(|e| e.line_number == line_number)
+                    .map(|index| (index, color))
+            })
+            .collect();
+
+        for (index, color) in indices_to_bookmark {
+            self.buffer.toggle_bookmark(index);
+            self.buffer.update(index, |e| e.bookmark_color = color);
+        }
 
-            for index in indices_to_bookmark {
-                self.buffer.toggle_bookmark(index);
-            }
+        let restored = restore_grok_results(&mut self.buffer, pending.grok_results);
+        if restored > 0 {
+            tracing::debug!("Kept grok results for {} unchanged lines on reload", restored);
+            self.filter.mark_dirty();
         }
 
         // Update filter
         self.update_filter();
 
+        let Some(reader) = reader else {
+            return Ok(());
+        };
+        // Compressed and mapped files are read-only snapshots: no watcher or tailing
+        if reader.is_compressed() || reader.is_mapped() {
+            self.reader = Some(reader);
+            return Ok(());
+        }
+
         // Create file watcher
         let watcher = FileWatcher::new(&self.path)?;
 
@@ -157,16 +473,20 @@ impl TabState {
         let reader_path = self.path.clone();
         let reader_offset = reader.offset();
         let reader_line_count = reader.line_count();
-        let reader_encoding = encoding;
+        let reader_identity = reader.identity();
+        let reader_config = self.reader_config();
+        let repaint = self.repaint_trigger.clone();
 
         thread::spawn(move || {
             Self::reader_thread(
                 reader_path,
                 reader_offset,
                 reader_line_count,
-                reader_encoding,
+                reader_identity,
+                reader_config,
                 msg_tx,
                 cmd_rx,
+                repaint,
             );
         });
 
@@ -183,14 +503,12 @@ impl TabState {
         path: PathBuf,
         initial_offset: u64,
         initial_line_count: usize,
-        encoding: Option<&'static encoding_rs::Encoding>,
+        initial_identity: Option<FileIdentity>,
+        config: LogReaderConfig,
         msg_tx: Sender<ReaderMessage>,
         cmd_rx: Receiver<ReaderCommand>,
+        repaint: RepaintTrigger,
     ) {
-        let config = LogReaderConfig {
-            encoding,
-            ..Default::default()
-        };
         let mut reader = match LogReader::with_config(&path, config) {
             Ok(r) => r,
             Err(e) => {
@@ -199,12 +517,20 @@ impl TabState {
             }
         };
 
-        reader.seek_with_line_count(initial_offset, initial_line_count);
+        // The file may have been rotated since the initial tail read
+        if reader.identity() == initial_identity {
+            reader.seek_with_line_count(initial_offset, initial_line_count);
+        } else {
+            let _ = msg_tx.send(ReaderMessage::FileReset);
+        }
 
+        // Stopped readers keep serving lazy loads until the tab drops its
+        // command channel
+        let mut tailing = true;
         loop {
             // Check for commands first
             match cmd_rx.try_recv() {
-                Ok(ReaderCommand::Stop) => break,
+                Ok(ReaderCommand::Stop) => tailing = false,
                 Ok(ReaderCommand::LoadPreviousChunk(before_offset, max_lines)) => {
                     // Load previous chunk for lazy loading
                     match reader.read_previous_chunk(before_offset, max_lines) {
@@ -220,27 +546,27 @@ impl TabState {
                         }
                     }
                 }
-                Err(_) => {} // No command
+                Err(TryRecvError::Disconnected) => break,
+                Err(TryRecvError::Empty) => {}
+            }
+            if !tailing {
+                thread::sleep(Duration::from_millis(50));
+                continue;
+            }
+
+            if reader.detect_rotation() || reader.detect_truncation() {
+                let _ = msg_tx.send(ReaderMessage::FileReset);
             }
 
             match reader.has_new_content() {
                 Ok(true) => {
-                    match std::fs::metadata(&path) {
-                        Ok(metadata) => {
-                            let current_size = metadata.len();
-                            if current_size < reader.offset() {
-                                let _ = msg_tx.send(ReaderMessage::FileReset);
-                                reader.seek_with_line_count(0, 0);
-                            }
-                        }
-                        Err(e) => {
-                            let _ = msg_tx.send(ReaderMessage::Error(e.to_string()));
-                        }
-                    }
-
                     match reader.read_new_lines() {
                         Ok(entries) if !entries.is_empty() => {
+                            let important = repaint.should_repaint(&entries);
                             let _ = msg_tx.send(ReaderMessage::NewEntries(entries));
+                            if important {
+                                repaint.repaint();
+                            }
                         }
                         Err(e) => {
                             let _ = msg_tx.send(ReaderMessage::Error(e.to_string()));
@@ -259,6 +585,16 @@ impl TabState {
         }
     }
 
+    /// Re-read the file's modification time for the timestamp fallback.
+    /// Done when lines arrive rather than every frame.
+    fn refresh_file_mtime(&mut self) {
+        let mtime = std::fs::metadata(&self.path)
+            .and_then(|m| m.modified())
+            .ok()
+            .map(chrono::DateTime::<chrono::Local>::from);
+        self.buffer.set_file_mtime(mtime);
+    }
+
     /// Process messages from background reader
     pub fn process_reader_messages(&mut self) -> bool {
         let Some(rx) = &self.reader_rx else {
@@ -273,7 +609,12 @@ impl TabState {
         while let Ok(msg) = rx.try_recv() {
             match msg {
                 ReaderMessage::NewEntries(entries) => {
-                    new_entries.extend(entries);
+                    // Record when tailed lines arrived (timestamp fallback)
+                    let now = chrono::Local::now();
+                    new_entries.extend(entries.into_iter().map(|mut entry| {
+                        entry.ingested_at = Some(now);
+                        entry
+                    }));
                     had_changes = true;
                 }
                 ReaderMessage::PreviousChunk(entries, new_start_offset) => {
@@ -290,6 +631,8 @@ impl TabState {
                 ReaderMessage::FileReset => {
                     had_reset = true;
                     self.buffer.clear();
+                    self.reset_grok_parse();
+                    self.reloaded = true;
                 }
                 ReaderMessage::Error(e) => {
                     tracing::error!("Reader error for {:?}: {}", self.path, e);
@@ -301,10 +644,15 @@ impl TabState {
         // Handle prepended entries first (from lazy loading)
         if let Some((entries, new_start_offset)) = prepend_entries {
             let prepend_count = entries.len();
+            if self.grok_parse_all {
+                self.parse_grok_in_background(entries.clone());
+            }
             self.buffer.prepend(entries);
             self.buffer.lazy_load.loaded_start_offset = new_start_offset;
             self.buffer.lazy_load.first_loaded_line = self.buffer.first_line_number();
             self.buffer.lazy_load.load_more_requested = false;
+            // Row indices shifted; don't count the jump as scrolling
+            self.buffer.lazy_load.last_check_row = None;
             
             // Adjust grok_parse_progress since we prepended items
             // The existing parsed items are now at higher indices
@@ -314,17 +662,73 @@ impl TabState {
             self.pending_entries += 1;
         }
 
+        if let Some(capture) = self.capture.as_mut() {
+            // Keep only the captured window; anything past the budget is dropped
+            new_entries.truncate(capture.limit.saturating_sub(capture.captured));
+            capture.captured += new_entries.len();
+            // Stop tailing but keep the reader channels for lazy loading
+            if capture.is_complete() && !self.monitoring_stopped {
+                self.pause_following();
+            }
+        }
+
         if !new_entries.is_empty() {
+            let arrived = new_entries.iter().filter_map(|e| e.level).max();
+            self.arrived_level = self.arrived_level.max(arrived);
+            if self.error_arrived.is_none() {
+                self.error_arrived = new_entries
+                    .iter()
+                    .find(|e| e.level.is_some_and(|l| l.is_at_least(LogLevel::Error)))
+                    .map(|e| e.line_number);
+            }
+
+            if self.grok_parse_all {
+                if let Some(parser) = self.active_grok_parser() {
+                    for entry in &mut new_entries {
+                        parse_grok_fields(parser, entry);
+                    }
+                }
+                if let Some(parser) = self.grok_parser.as_mut() {
+                    parser.add_json_columns(
+                        new_entries.iter().filter_map(|e| e.grok_fields.as_ref()),
+                    );
+                }
+            }
+
+            self.refresh_file_mtime();
             let old_first_line = self.buffer.first_line_number();
+            let old_omitted = self.buffer.omitted().map_or(0, |gap| gap.count);
             self.buffer.extend(new_entries);
             let new_first_line = self.buffer.first_line_number();
 
             // Adjust grok_parse_progress if items were trimmed from the front
             if new_first_line > old_first_line {
-                let dropped = new_first_line - old_first_line;
+                // A gap that reached the front moved the first line past its lines too
+                let closed = if self.buffer.omitted().is_none() {
+                    old_omitted
+                } else {
+                    0
+                };
+                let dropped = (new_first_line - old_first_line).saturating_sub(closed);
                 self.grok_parse_progress = self.grok_parse_progress.saturating_sub(dropped);
             }
+            // Or from the middle, moving the parsed items after the gap
+            if let Some(gap) = self.buffer.omitted() {
+                let dropped = gap.count.saturating_sub(old_omitted);
+                if self.grok_parse_progress > gap.index {
+                    self.grok_parse_progress = self
+                        .grok_parse_progress
+                        .saturating_sub(dropped)
+                        .max(gap.index);
+                }
+            }
+
+            self.filter.mark_dirty();
+            self.pending_entries += 1;
+        }
 
+        if had_reset {
+            // Drop rows of the old file even if the new one is still empty
             self.filter.mark_dirty();
             self.pending_entries += 1;
         }
@@ -332,6 +736,133 @@ impl TabState {
         had_changes || had_reset
     }
 
+    /// The tab's grok parser, if it has a pattern to parse with
+    fn active_grok_parser(&self) -> Option<&GrokParser> {
+        self.grok_parser.as_ref().filter(|p| p.has_active_pattern())
+    }
+
+    /// Parse one line's grok fields now, unless it already has them
+    pub fn parse_grok_line(&mut self, index: usize) {
+        let Some(parser) = self.grok_parser.as_ref().filter(|p| p.has_active_pattern()) else {
+            return;
+        };
+        self.buffer.update(index, |entry| {
+            if entry.grok_fields.is_none() {
+                parse_grok_fields(parser, entry);
+            }
+        });
+        self.add_json_columns([index]);
+    }
+
+    /// Give the JSON keys of the lines at `indices` that have no column yet
+    /// their own column (see [`GrokParser::add_json_columns`])
+    pub fn add_json_columns(&mut self, indices: impl IntoIterator<Item = usize>) {
+        let Some(parser) = self.grok_parser.as_mut() else {
+            return;
+        };
+        let buffer = &self.buffer;
+        parser.add_json_columns(
+            indices
+                .into_iter()
+                .filter_map(|index| buffer.get(index))
+                .filter_map(|entry| entry.grok_fields.as_ref()),
+        );
+    }
+
+    /// Give every line its grok fields, for features that read the fields
+    /// of lines that were never on screen. Loaded lines are parsed in the
+    /// background (see [`Self::poll_grok_parse_jobs`]); lines loaded later
+    /// are parsed as they arrive.
+    pub fn parse_all_grok_fields(&mut self) {
+        if self.grok_parse_all || self.active_grok_parser().is_none() {
+            return;
+        }
+        self.grok_parse_all = true;
+        let unparsed = self
+            .buffer
+            .iter()
+            .filter(|e| e.grok_fields.is_none())
+            .cloned()
+            .collect();
+        self.parse_grok_in_background(unparsed);
+    }
+
+    /// Parse entries on a background thread with a copy of the tab's parser
+    fn parse_grok_in_background(&mut self, mut entries: Vec<LogEntry>) {
+        let Some(parser) = self.active_grok_parser().cloned() else {
+            return;
+        };
+        if entries.is_empty() {
+            return;
+        }
+        let (tx, rx) = bounded(1);
+        thread::spawn(move || {
+            for entry in &mut entries {
+                parse_grok_fields(&parser, entry);
+            }
+            let parsed = entries
+                .into_iter()
+                .filter(|e| e.grok_fields.is_some())
+                .map(|e| (e.line_number, e))
+                .collect();
+            let _ = tx.send(parsed);
+        });
+        self.grok_parse_jobs.push(rx);
+    }
+
+    /// Store the fields of finished background parses in the buffer and
+    /// refilter. Returns true if any finished.
+    pub fn poll_grok_parse_jobs(&mut self) -> bool {
+        let mut finished = Vec::new();
+        self.grok_parse_jobs.retain(|rx| match rx.try_recv() {
+            Ok(parsed) => {
+                finished.push(parsed);
+                false
+            }
+            Err(TryRecvError::Empty) => true,
+            Err(TryRecvError::Disconnected) => false,
+        });
+        if finished.is_empty() {
+            return false;
+        }
+        for parsed in finished {
+            if let Some(parser) = self.grok_parser.as_mut() {
+                parser.add_json_columns(parsed.values().filter_map(|e| e.grok_fields.as_ref()));
+            }
+            restore_grok_results(&mut self.buffer, parsed);
+        }
+        self.filter.mark_dirty();
+        self.pending_entries += 1;
+        true
+    }
+
+    /// First and last timestamps of the buffer, looked up again only once
+    /// the buffer changed
+    pub fn time_bounds(&mut self) -> Option<(NaiveDateTime, NaiveDateTime)> {
+        let key = (
+            self.buffer.revision(),
+            self.buffer.len(),
+            self.buffer.first_line_number(),
+            self.buffer.last_line_number(),
+        );
+        if self.time_bounds.0 != key {
+            let bounds = self
+                .buffer
+                .first_timestamp()
+                .zip(self.buffer.last_timestamp());
+            self.time_bounds = (key, bounds);
+        }
+        self.time_bounds.1
+    }
+
+    /// Start grok parsing over, e.g. after the pattern changed. Background
+    /// parses with the old pattern are dropped.
+    pub fn reset_grok_parse(&mut self) {
+        self.grok_parse_progress = 0;
+        self.grok_parse_all = false;
+        self.grok_parse_jobs.clear();
+    }
+
     /// Request to load more data (for lazy loading when scrolling up)
     pub fn request_load_more(&mut self) {
         if !self.buffer.lazy_load.enabled 
@@ -352,11 +883,86 @@ impl TabState {
         }
     }
 
+    /// Parse grok fields for the whole buffer of a small file, up to `chunk`
+    /// lines per call, so features reading fields see every line and not only
+    /// the ones scrolled into view. Files with more than `eager_max_lines`
+    /// lines (or 0) are left to visible-only parsing. Returns lines parsed.
+    pub fn parse_grok_chunk(&mut self, eager_max_lines: usize, chunk: usize) -> usize {
+        let Some(parser) = self.grok_parser.as_ref().filter(|p| p.has_active_pattern()) else {
+            return 0;
+        };
+        let file_lines = self.buffer.total_lines().max(self.buffer.len());
+        if eager_max_lines == 0 || file_lines > eager_max_lines {
+            return 0;
+        }
+
+        let start = self.grok_parse_progress;
+        let mut parsed = 0;
+        while parsed < chunk && self.grok_parse_progress < self.buffer.len() {
+            self.buffer.update(self.grok_parse_progress, |entry| {
+                if entry.grok_fields.is_none() {
+                    parse_grok_fields(parser, entry);
+                    parsed += 1;
+                }
+            });
+            self.grok_parse_progress += 1;
+        }
+        if parsed > 0 {
+            self.add_json_columns(start..self.grok_parse_progress);
+        }
+        parsed
+    }
+
     /// Update filtered indices
     pub fn update_filter(&mut self) {
+        if self.filter.filter.needs_grok_fields() {
+            self.parse_all_grok_fields();
+        }
+        // Lines stay in file order until all of them have the field to sort by
+        let sort_pending = !self.grok_parse_jobs.is_empty() && self.filter.filter.sort_by.is_some();
+        let deferred_sort = if sort_pending {
+            self.filter.filter.sort_by.take()
+        } else {
+            None
+        };
         let indices = self.filter.apply(&self.buffer);
         self.filtered_indices = indices.to_vec();
+        if deferred_sort.is_some() {
+            self.filter.filter.sort_by = deferred_sort;
+        }
         self.filter_active = self.filter.is_filtering();
+        self.main_view.correlation_id =
+            self.filter.filter.correlation.as_ref().map(|c| c.id.clone());
+        self.main_view.sort_by = self.filter.filter.sort_by.clone();
+        self.main_view.sort_pending = sort_pending;
+        self.main_view.collapsed_blocks = self.filter.collapsed_blocks().clone();
+    }
+
+    /// Toggle bookmarks on every search match shown in the view, i.e. only
+    /// the matches the filter lets through. Returns how many lines changed
+    /// and whether they were bookmarked rather than unbookmarked.
+    pub fn toggle_match_bookmarks(&mut self) -> (usize, bool) {
+        // Filtered indices may be sorted by a field, so look them up by set
+        let visible: Option<HashSet<usize>> = self
+            .filter_active
+            .then(|| self.filtered_indices.iter().copied().collect());
+        let matches: Vec<usize> = self
+            .filter
+            .search
+            .results()
+            .iter()
+            .map(|m| m.buffer_index)
+            .filter(|index| visible.as_ref().is_none_or(|v| v.contains(index)))
+            .collect();
+        let count = self.buffer.toggle_bookmarks(&matches);
+        let bookmarked = matches
+            .first()
+            .and_then(|&index| self.buffer.get(index))
+            .is_some_and(|entry| entry.bookmarked);
+        if self.filter.filter.bookmarks_only {
+            self.update_filter();
+        }
+        (count, bookmarked)
     }
 
     /// Clear the buffer
@@ -365,7 +971,7 @@ impl TabState {
         self.filtered_indices.clear();
         self.filter.mark_dirty();
         self.main_view.clear_selection();
-        self.grok_parse_progress = 0;
+        self.reset_grok_parse();
     }
 
     /// Close the tab (stop reader, watcher)
@@ -374,23 +980,39 @@ impl TabState {
             let _ = tx.send(ReaderCommand::Stop);
         }
         self.reader_rx = None;
+        self.loading = None;
 
         if let Some(watcher) = self.watcher.take() {
             watcher.stop();
         }
 
         self.reader = None;
+
+        if let Some(stats) = self.file_stats.take() {
+            stats.cancel();
+        }
     }
 
     /// Reload the file
     pub fn reload(&mut self, bookmarks_store: &BookmarksStore) -> Result<()> {
+        if self.snapshot_of.is_some() || self.merged {
+            // Snapshots and merges are frozen and have no file to re-read
+            return Ok(());
+        }
         let encoding = self.encoding;
+        // Keep parsed grok results so an append-only reload only parses new lines
+        let previous = if self.grok_parser.is_some() {
+            grok_snapshot(&self.buffer)
+        } else {
+            HashMap::new()
+        };
+
         self.close();
         self.buffer.clear();
         self.filtered_indices.clear();
         self.main_view.clear_selection();
-        self.grok_parse_progress = 0;
-        self.open_file(encoding, bookmarks_store)
+        self.reset_grok_parse();
+        self.start_open(encoding, bookmarks_store, previous)
     }
 
     /// Check if watcher is active
@@ -403,9 +1025,11 @@ impl TabState {
         if let Some(tx) = &self.reader_tx {
             let _ = tx.send(ReaderCommand::Stop);
         }
+        self.monitoring_stopped = true;
     }
 
-    /// Resume monitoring (restart reader thread)
+    /// Resume monitoring (restart reader thread; replacing the command
+    /// channel ends the stopped one)
     pub fn resume_monitoring(&mut self) {
         if self.watcher.is_none() {
             return;
@@ -418,21 +1042,76 @@ impl TabState {
         let reader_path = self.path.clone();
         let reader_offset = self.reader.as_ref().map(|r| r.offset()).unwrap_or(0);
         let reader_line_count = self.reader.as_ref().map(|r| r.line_count()).unwrap_or(0);
-        let reader_encoding = self.encoding;
+        let reader_identity = self.reader.as_ref().and_then(|r| r.identity());
+        let reader_config = self.reader_config();
+        let repaint = self.repaint_trigger.clone();
 
         thread::spawn(move || {
             Self::reader_thread(
                 reader_path,
                 reader_offset,
                 reader_line_count,
-                reader_encoding,
+                reader_identity,
+                reader_config,
                 msg_tx,
                 cmd_rx,
+                repaint,
             );
         });
 
         self.reader_rx = Some(msg_rx);
         self.reader_tx = Some(cmd_tx);
+        self.monitoring_stopped = false;
+    }
+
+    /// Explicitly stop following: stop monitoring and keep the view where it
+    /// is, even if the user scrolls back to the newest lines
+    pub fn pause_following(&mut self) {
+        self.stop_monitoring();
+        let scroll = &mut self.main_view.virtual_scroll.state;
+        scroll.auto_scroll = false;
+        scroll.follow_paused = true;
+    }
+
+    /// Follow new lines again, restarting monitoring if it was stopped
+    pub fn resume_following(&mut self) {
+        if self.monitoring_stopped {
+            self.resume_monitoring();
+        }
+        let scroll = &mut self.main_view.virtual_scroll.state;
+        scroll.follow_paused = false;
+        scroll.auto_scroll = true;
+        self.main_view.scroll_to_bottom();
+    }
+
+    /// Follow the file until `limit` more lines arrived, then stop monitoring
+    pub fn start_capture(&mut self, limit: usize) {
+        self.capture = Some(LineCapture { limit, captured: 0 });
+    }
+
+    /// Take the capture if its budget has been reached
+    pub fn take_completed_capture(&mut self) -> Option<LineCapture> {
+        self.capture.take_if(|capture| capture.is_complete())
+    }
+
+    /// Perform the configured Space key action
+    /// Returns whether monitoring is active afterwards
+    pub fn handle_space_key(&mut self, action: SpaceKeyAction, monitoring: bool) -> bool {
+        match action {
+            SpaceKeyAction::ToggleMonitoring => {
+                if monitoring {
+                    self.pause_following();
+                } else {
+                    self.resume_following();
+                }
+                !monitoring
+            }
+            SpaceKeyAction::PageDown => {
+                self.main_view.page_down();
+                monitoring
+            }
+            SpaceKeyAction::Nothing => monitoring,
+        }
     }
 
     /// Set theme for main view
@@ -449,8 +1128,22 @@ pub struct TabManager {
     pub states: HashMap<TabId, TabState>,
     /// Split view manager
     pub split_view: SplitView,
+    /// Last split layout, restored when split view is re-enabled
+    last_split_layout: SplitLayoutConfig,
+    /// Whether the last layout should be restored once its files are open
+    pending_split_restore: bool,
+    /// Filter shared by all tabs while the global filter mode is on
+    pinned_filter: Option<FilterConfig>,
+    /// Tab the pinned filter was last applied to
+    pinned_tab: Option<TabId>,
     /// Buffer configuration for new tabs
     buffer_config: LogBufferConfig,
+    /// Partial line hold-back for new tabs
+    partial_line_timeout: Duration,
+    /// Size from which new local tabs memory-map their file
+    mmap_threshold: Option<u64>,
+    /// Repaint trigger shared with every tab's reader
+    repaint_trigger: RepaintTrigger,
     /// Dark theme setting
     dark_theme: bool,
 }
@@ -468,11 +1161,68 @@ impl TabManager {
             tab_bar: TabBar::new(),
             states: HashMap::new(),
             split_view: SplitView::new(),
+            last_split_layout: SplitLayoutConfig::default(),
+            pending_split_restore: false,
+            pinned_filter: None,
+            pinned_tab: None,
             buffer_config,
+            partial_line_timeout: LogReaderConfig::default().partial_line_timeout,
+            mmap_threshold: None,
+            repaint_trigger: RepaintTrigger::default(),
             dark_theme: true,
         }
     }
 
+    /// Set how long tailing holds back a partially written last line
+    pub fn set_partial_line_timeout(&mut self, timeout: Duration) {
+        self.partial_line_timeout = timeout;
+    }
+
+    /// Let readers of new tabs repaint `ctx` when important lines arrive
+    pub fn set_repaint_context(&mut self, ctx: egui::Context) {
+        self.repaint_trigger.ctx = Some(ctx);
+    }
+
+    /// Set the lowest level whose lines repaint right away while tailing
+    /// (None = every line); applies to running readers too
+    pub fn set_repaint_min_level(&mut self, level: Option<LogLevel>) {
+        self.repaint_trigger.set_min_level(level);
+    }
+
+    /// Set the file size from which local files are memory-mapped (None = never)
+    pub fn set_mmap_threshold(&mut self, threshold: Option<u64>) {
+        self.mmap_threshold = threshold;
+    }
+
+    /// Set the stack trace continuation pattern of new and open tabs
+    pub fn set_continuation_pattern(&mut self, pattern: Option<Regex>) {
+        self.buffer_config.continuation = pattern.clone();
+        for state in self.states.values_mut() {
+            state.buffer.set_continuation_pattern(pattern.clone());
+            state.filter.mark_dirty();
+            state.update_filter();
+        }
+    }
+
+    /// Set the timestamp new and open tabs use for lines without one
+    pub fn set_timestamp_fallback(&mut self, fallback: TimestampFallback) {
+        self.buffer_config.timestamp_fallback = fallback;
+        for state in self.states.values_mut() {
+            state.buffer.set_timestamp_fallback(fallback);
+            state.filter.mark_dirty();
+            state.update_filter();
+        }
+    }
+
+    /// Set which lines new and open tabs trim once their buffer is full
+    pub fn set_trim_strategy(&mut self, strategy: TrimStrategy, head_lines: usize) {
+        self.buffer_config.trim_strategy = strategy;
+        self.buffer_config.head_lines = head_lines;
+        for state in self.states.values_mut() {
+            state.buffer.set_trim_strategy(strategy, head_lines);
+        }
+    }
+
     /// Set theme
     pub fn set_dark_theme(&mut self, dark: bool) {
         self.dark_theme = dark;
@@ -503,6 +1253,9 @@ impl TabManager {
         // Create tab state
         let mut state = TabState::new(id, path, self.buffer_config.clone());
         state.set_dark_theme(self.dark_theme);
+        state.partial_line_timeout = self.partial_line_timeout;
+        state.mmap_threshold = self.mmap_threshold;
+        state.repaint_trigger = self.repaint_trigger.clone();
         state.open_file(encoding, bookmarks_store)?;
 
         self.states.insert(id, state);
@@ -530,6 +1283,8 @@ impl TabManager {
         // Create tab state
         let mut state = TabState::new(id, cache_path, self.buffer_config.clone());
         state.set_dark_theme(self.dark_theme);
+        state.partial_line_timeout = self.partial_line_timeout;
+        state.repaint_trigger = self.repaint_trigger.clone();
         state.open_file(None, bookmarks_store)?;
 
         self.states.insert(id, state);
@@ -555,28 +1310,199 @@ impl TabManager {
 
     /// Save bookmarks for a tab
     pub fn save_bookmarks(&self, id: TabId, bookmarks_store: &mut BookmarksStore) {
-        if let Some(state) = self.states.get(&id) {
-            use std::collections::HashSet;
-
-            let bookmarked_lines: HashSet<usize> = state
-                .buffer
-                .iter()
-                .filter(|e| e.bookmarked)
-                .map(|e| e.line_number)
-                .collect();
-
-            bookmarks_store.set_bookmarks(&state.path, bookmarked_lines);
+        if self.sync_bookmarks(id, bookmarks_store) {
             if let Err(e) = bookmarks_store.save() {
                 tracing::error!("Failed to save bookmarks: {}", e);
             }
         }
     }
 
+    /// Copy a tab's bookmarks into the store without writing it to disk.
+    /// Snapshots, merges and tabs still loading are skipped (returns false).
+    fn sync_bookmarks(&self, id: TabId, bookmarks_store: &mut BookmarksStore) -> bool {
+        let Some(state) = self
+            .states
+            .get(&id)
+            .filter(|s| s.snapshot_of.is_none() && !s.merged && !s.is_loading())
+        else {
+            return false;
+        };
+        use std::collections::HashSet;
+
+        let bookmarked_lines: HashSet<usize> = state
+            .buffer
+            .iter()
+            .filter(|e| e.bookmarked)
+            .map(|e| e.line_number)
+            .collect();
+        let colors = state
+            .buffer
+            .iter()
+            .filter(|e| e.bookmarked && e.bookmark_color != 0)
+            .map(|e| (e.line_number, e.bookmark_color))
+            .collect();
+
+        bookmarks_store.set_bookmarks(&state.path, bookmarked_lines);
+        bookmarks_store.set_colors(&state.path, colors);
+        true
+    }
+
+    /// Copy every open tab's bookmarks into the store without writing it
+    pub fn sync_all_bookmarks(&self, bookmarks_store: &mut BookmarksStore) {
+        for &id in self.states.keys() {
+            self.sync_bookmarks(id, bookmarks_store);
+        }
+    }
+
+    /// Freeze a tab's loaded lines into a new snapshot tab.
+    /// Returns the snapshot tab, which becomes active.
+    pub fn take_snapshot(&mut self, id: TabId) -> Option<TabId> {
+        let source_tab = self.tab_bar.tabs.iter().find(|t| t.id == id)?.clone();
+        if source_tab.snapshot_of.is_some() || !self.states.contains_key(&id) {
+            return None;
+        }
+
+        let taken_at = chrono::Local::now().format("%H:%M:%S").to_string();
+        let tab = Tab::new_snapshot(0, &source_tab, &taken_at);
+        let path = tab.path.clone();
+        let snapshot_id = self.tab_bar.add_tab(tab);
+        if self.states.contains_key(&snapshot_id) {
+            // A snapshot was already taken this second
+            return Some(snapshot_id);
+        }
+
+        let source = &self.states[&id];
+        let mut state = TabState::new(snapshot_id, path, self.buffer_config.clone());
+        state.set_dark_theme(self.dark_theme);
+        state.encoding = source.encoding;
+        state.buffer.extend(source.buffer.iter().cloned());
+        state.snapshot_of = Some(id);
+        state.update_filter();
+        self.states.insert(snapshot_id, state);
+
+        Some(snapshot_id)
+    }
+
+    /// Open a read-only tab merging the lines of several files by timestamp.
+    /// The files are read in the background; the tab shows as loading until
+    /// [`Self::poll_loading`] reports it done. Returns the new tab, which
+    /// becomes active.
+    pub fn open_merged(&mut self, paths: &[PathBuf]) -> TabId {
+        let tags = source_tags(paths);
+        let created_at = chrono::Local::now().format("%H:%M:%S").to_string();
+        let tab = Tab::new_merged(0, &tags, &created_at);
+        let path = tab.path.clone();
+        let id = self.tab_bar.add_tab(tab);
+        if self.states.contains_key(&id) {
+            // The same sources were already merged this second
+            return id;
+        }
+
+        let mut state = TabState::new(id, path, self.buffer_config.clone());
+        state.set_dark_theme(self.dark_theme);
+        state.merged = true;
+        state.start_merge(paths.to_vec(), tags);
+        self.states.insert(id, state);
+
+        id
+    }
+
+    /// Find the (snapshot, live) tab pair for a tab: a snapshot pairs with
+    /// its live tab, a live tab with its most recent snapshot
+    pub fn snapshot_pair(&self, id: TabId) -> Option<(TabId, TabId)> {
+        let state = self.states.get(&id)?;
+        if let Some(live) = state.snapshot_of {
+            return self.states.contains_key(&live).then_some((id, live));
+        }
+        self.states
+            .iter()
+            .filter(|(_, s)| s.snapshot_of == Some(id))
+            .map(|(snapshot, _)| *snapshot)
+            .max()
+            .map(|snapshot| (snapshot, id))
+    }
+
+    /// Diff a live tab against its snapshot and show the pair side by side
+    /// (snapshot on the left, live on the right). The diff is computed in
+    /// the background and delivered on the returned receiver.
+    pub fn diff_against_snapshot(&mut self, id: TabId) -> Option<Receiver<LogDiff>> {
+        let (snapshot, live) = self.snapshot_pair(id)?;
+        let diff = LogDiff::between_in_background(
+            &self.states[&snapshot].buffer,
+            &self.states[&live].buffer,
+        );
+
+        self.tab_bar.active_tab = Some(snapshot);
+        self.enable_split(live);
+        Some(diff)
+    }
+
     /// Get the active tab state
     pub fn get_active_state(&self) -> Option<&TabState> {
         self.tab_bar.active_tab.and_then(|id| self.states.get(&id))
     }
 
+    /// Pause monitoring on tabs that have been inactive for `timeout`, and
+    /// resume tabs that became visible again. Returns the number of tabs paused.
+    pub fn update_idle_monitoring(&mut self, timeout: Duration, now: Instant) -> usize {
+        let visible: Vec<TabId> = if self.split_view.is_split() {
+            [SplitPane::Left, SplitPane::Right]
+                .into_iter()
+                .filter_map(|pane| self.split_view.get_pane_tab(pane))
+                .chain(self.tab_bar.active_tab)
+                .collect()
+        } else {
+            self.tab_bar.active_tab.into_iter().collect()
+        };
+
+        let mut paused = 0;
+        for (id, state) in self.states.iter_mut() {
+            if visible.contains(id) {
+                state.last_active = now;
+                if state.idle_paused {
+                    state.idle_paused = false;
+                    state.resume_monitoring();
+                    tracing::debug!("Resumed monitoring of {:?}", state.path);
+                }
+            } else if !state.idle_paused
+                && !state.monitoring_stopped
+                && state.is_watching()
+                && now.saturating_duration_since(state.last_active) >= timeout
+            {
+                state.stop_monitoring();
+                state.idle_paused = true;
+                paused += 1;
+                tracing::debug!("Paused monitoring of idle tab {:?}", state.path);
+            }
+        }
+
+        for tab in self.tab_bar.tabs.iter_mut() {
+            tab.idle_paused = self.states.get(&tab.id).is_some_and(|s| s.idle_paused);
+        }
+        paused
+    }
+
+    /// Whether the active tab shows newest lines first
+    pub fn active_reverse_order(&self) -> bool {
+        self.get_active_state()
+            .is_some_and(|state| state.main_view.is_reverse_order())
+    }
+
+    /// Toggle reverse order for the active tab only.
+    /// Returns the tab's path and its new reverse-order state.
+    pub fn toggle_active_reverse_order(&mut self) -> Option<(PathBuf, bool)> {
+        let state = self.get_active_state_mut()?;
+        state.main_view.toggle_reverse_order();
+        Some((state.path.clone(), state.main_view.is_reverse_order()))
+    }
+
+    /// Restore a tab's saved reverse-order preference
+    pub fn restore_reverse_order(&mut self, tab_id: TabId, reverse: bool) {
+        if let Some(state) = self.states.get_mut(&tab_id) {
+            state.main_view.set_reverse_order(reverse);
+        }
+    }
+
     /// Get the active tab state mutably
     pub fn get_active_state_mut(&mut self) -> Option<&mut TabState> {
         self.tab_bar
@@ -594,13 +1520,96 @@ impl TabManager {
         self.states.values_mut().find(|s| &s.path == path)
     }
 
+    /// Whether settings for a path may be saved: not for the path of a
+    /// snapshot or merged tab, see [`TabState::persists_settings`]
+    pub fn persists_settings(&self, path: &Path) -> bool {
+        self.states
+            .values()
+            .find(|s| s.path == path)
+            .is_none_or(TabState::persists_settings)
+    }
+
+    /// Finish opening tabs whose initial read is done.
+    /// Returns each tab that stopped loading and whether it opened.
+    pub fn poll_loading(&mut self) -> Vec<(TabId, Result<()>)> {
+        let loaded: Vec<(TabId, Result<()>)> = self
+            .states
+            .iter_mut()
+            .filter_map(|(id, state)| Some((*id, state.poll_loading()?)))
+            .collect();
+
+        for tab in self.tab_bar.tabs.iter_mut() {
+            tab.loading = self.states.get(&tab.id).is_some_and(|s| s.is_loading());
+        }
+        loaded
+    }
+
+    /// Highest level among lines appended to any tab since the last call
+    pub fn take_arrived_level(&mut self) -> Option<LogLevel> {
+        self.states
+            .values_mut()
+            .filter_map(|s| s.arrived_level.take())
+            .max()
+    }
+
+    /// Check if any tab is still loading
+    pub fn any_loading(&self) -> bool {
+        self.states.values().any(|s| s.is_loading())
+    }
+
+    /// Check if any tab is grok-parsing lines in the background
+    pub fn any_grok_parsing(&self) -> bool {
+        self.states.values().any(|s| !s.grok_parse_jobs.is_empty())
+    }
+
     /// Process reader messages for all tabs
     pub fn process_all_reader_messages(&mut self) {
         for state in self.states.values_mut() {
             state.process_reader_messages();
+            state.poll_grok_parse_jobs();
         }
     }
 
+    /// Take the captures that reached their line budget, by tab
+    pub fn take_completed_captures(&mut self) -> Vec<(TabId, LineCapture)> {
+        self.states
+            .iter_mut()
+            .filter_map(|(id, state)| Some((*id, state.take_completed_capture()?)))
+            .collect()
+    }
+
+    /// Take the tabs whose file was rotated or truncated and reloaded
+    pub fn take_reloaded_tabs(&mut self) -> Vec<TabId> {
+        self.states
+            .iter_mut()
+            .filter_map(|(id, state)| std::mem::take(&mut state.reloaded).then_some(*id))
+            .collect()
+    }
+
+    /// Stop scrolling tabs that are following the tail when an ERROR or FATAL
+    /// line arrived, if `enabled`, and scroll to the first such line. The
+    /// files keep being read. Returns the tabs that were paused.
+    pub fn pause_on_arrived_errors(&mut self, enabled: bool) -> Vec<TabId> {
+        let mut paused = Vec::new();
+        for (id, state) in self.states.iter_mut() {
+            let Some(line_number) = state.error_arrived.take() else {
+                continue;
+            };
+            if !enabled || !state.main_view.is_auto_scroll() {
+                continue;
+            }
+            let scroll = &mut state.main_view.virtual_scroll.state;
+            scroll.auto_scroll = false;
+            scroll.follow_paused = true;
+            // The line may already be trimmed off by later appends
+            if let Some(index) = state.buffer.index_of_line_number(line_number) {
+                state.main_view.scroll_to_line(index);
+            }
+            paused.push(*id);
+        }
+        paused
+    }
+
     /// Update filters for all tabs with pending entries
     pub fn update_pending_filters(&mut self) {
         for state in self.states.values_mut() {
@@ -673,6 +1682,15 @@ impl TabManager {
                 // Enable split view with this tab in the right pane
                 self.enable_split(id);
             }
+            TabBarAction::SetAlias(_, _) => {
+                // Aliases are persisted in config by the app
+            }
+            TabBarAction::TakeSnapshot(id) => {
+                self.take_snapshot(id);
+            }
+            TabBarAction::DiffAgainstSnapshot(_) | TabBarAction::CopySourceId(_) => {
+                // Handled by the app
+            }
             TabBarAction::None => {}
         }
         action
@@ -715,10 +1733,12 @@ impl TabManager {
             }
         }
         self.split_view.enable_split(right_tab_id);
+        self.remember_split_layout();
     }
 
     /// Disable split view
     pub fn disable_split(&mut self) {
+        self.remember_split_layout();
         // Move the active tab back to the main view
         if let Some(left_tab) = self.split_view.get_pane_tab(SplitPane::Left) {
             self.tab_bar.active_tab = Some(left_tab);
@@ -767,6 +1787,7 @@ impl TabManager {
         match action {
             SplitAction::SplitRatioChanged(_) => {
                 // Ratio is already updated in split_view
+                self.remember_split_layout();
             }
             SplitAction::None => {}
         }
@@ -776,6 +1797,8 @@ impl TabManager {
     pub fn toggle_split(&mut self) {
         if self.split_view.is_split() {
             self.disable_split();
+        } else if self.restore_last_split() {
+            // Restored the previous panes
         } else if self.tab_bar.len() >= 2 {
             // Find a second tab to show in split
             if let Some(active) = self.tab_bar.active_tab {
@@ -785,4 +1808,906 @@ impl TabManager {
             }
         }
     }
+
+    /// Record the current split panes and ratio
+    fn remember_split_layout(&mut self) {
+        self.last_split_layout.split_ratio = self.split_view.config.split_ratio;
+        if !self.split_view.is_split() {
+            return;
+        }
+        let path_of = |id: Option<TabId>| {
+            id.and_then(|id| self.tab_bar.tabs.iter().find(|t| t.id == id))
+                .map(|t| t.path.clone())
+        };
+        let left = path_of(self.split_view.get_pane_tab(SplitPane::Left));
+        let right = path_of(self.split_view.get_pane_tab(SplitPane::Right));
+        if left.is_some() && right.is_some() {
+            self.last_split_layout.left_path = left;
+            self.last_split_layout.right_path = right;
+        }
+    }
+
+    /// Get the split layout for persistence
+    pub fn split_layout(&mut self) -> SplitLayoutConfig {
+        self.remember_split_layout();
+        SplitLayoutConfig {
+            is_split: self.split_view.is_split(),
+            ..self.last_split_layout.clone()
+        }
+    }
+
+    /// Restore a persisted split layout. Panes are re-applied as soon as
+    /// both files are open (see `apply_pending_split`).
+    pub fn restore_split_layout(&mut self, layout: &SplitLayoutConfig) {
+        self.last_split_layout = layout.clone();
+        self.split_view.config.split_ratio = layout.split_ratio.clamp(0.1, 0.9);
+        self.pending_split_restore = layout.is_split;
+        self.apply_pending_split();
+    }
+
+    /// Re-enable a pending split layout if its files are now open
+    pub fn apply_pending_split(&mut self) -> bool {
+        if self.pending_split_restore && !self.split_view.is_split() && self.restore_last_split() {
+            self.pending_split_restore = false;
+            return true;
+        }
+        false
+    }
+
+    /// Re-enable split view with the last remembered panes, if both are still open
+    fn restore_last_split(&mut self) -> bool {
+        let (Some(left_path), Some(right_path)) = (
+            self.last_split_layout.left_path.clone(),
+            self.last_split_layout.right_path.clone(),
+        ) else {
+            return false;
+        };
+        let (Some(left), Some(right)) = (
+            self.tab_bar.find_by_path(&left_path),
+            self.tab_bar.find_by_path(&right_path),
+        ) else {
+            return false;
+        };
+        if left == right {
+            return false;
+        }
+
+        self.split_view.config.split_ratio = self.last_split_layout.split_ratio.clamp(0.1, 0.9);
+        self.tab_bar.active_tab = Some(left);
+        self.split_view.set_pane_tab(SplitPane::Left, Some(left));
+        self.split_view.enable_split(right);
+        self.split_view.set_active_pane(SplitPane::Left);
+        true
+    }
+
+    /// Check if the global filter mode is on
+    pub fn is_filter_pinned(&self) -> bool {
+        self.pinned_filter.is_some()
+    }
+
+    /// Toggle the global filter mode. Pinning shares the active tab's filter
+    /// with every tab; unpinning restores each tab's own filter.
+    /// Returns whether the filter is now pinned
+    pub fn toggle_pinned_filter(&mut self) -> bool {
+        if self.pinned_filter.is_some() {
+            self.pinned_filter = None;
+            self.pinned_tab = None;
+            for state in self.states.values_mut() {
+                if let Some(own) = state.own_filter.take() {
+                    state.filter.filter = own;
+                    state.filter.mark_dirty();
+                    state.update_filter();
+                }
+            }
+            return false;
+        }
+
+        let filter = self
+            .get_active_state()
+            .map(|state| state.filter.filter.clone())
+            .unwrap_or_default();
+        self.pinned_filter = Some(filter);
+        self.pinned_tab = None;
+        self.apply_pinned_filter();
+        true
+    }
+
+    /// Apply the pinned filter to the active tab, setting its own filter aside.
+    /// Called every frame before rendering so tab switches pick it up.
+    pub fn apply_pinned_filter(&mut self) {
+        let Some(active) = self.tab_bar.active_tab else {
+            return;
+        };
+        if self.pinned_filter.is_none() || self.pinned_tab == Some(active) {
+            return;
+        }
+
+        // Carry over edits made to the shared filter on the previous tab
+        if let Some(previous) = self.pinned_tab.and_then(|id| self.states.get(&id)) {
+            self.pinned_filter = Some(previous.filter.filter.clone());
+        }
+
+        let pinned = self.pinned_filter.clone().unwrap_or_default();
+        if let Some(state) = self.states.get_mut(&active) {
+            let own = std::mem::replace(&mut state.filter.filter, pinned);
+            if state.own_filter.is_none() {
+                state.own_filter = Some(own);
+            }
+            state.filter.mark_dirty();
+            state.update_filter();
+        }
+        self.pinned_tab = Some(active);
+    }
+}
+
+/// Parse an entry's grok fields and formatted content
+pub fn parse_grok_fields(parser: &GrokParser, entry: &mut LogEntry) {
+    // Use parser.parse_with_format() to support mixed patterns and formatting
+    let Some((fields, formatted)) = parser.parse_with_format(&entry.content) else {
+        return;
+    };
+    if !fields.is_empty() {
+        entry.set_grok_fields(fields.fields);
+        if let Some((plain_text, segments)) = formatted {
+            entry.formatted_content = Some(plain_text);
+            entry.formatted_segments = Some(segments);
+        }
+    }
+}
+
+/// Collect the grok-parsed entries of a buffer, keyed by line number
+fn grok_snapshot(buffer: &LogBuffer) -> HashMap<usize, LogEntry> {
+    buffer
+        .iter()
+        .filter(|e| e.grok_fields.is_some())
+        .map(|e| (e.line_number, e.clone()))
+        .collect()
+}
+
+/// Restore grok results from a snapshot taken before a reload.
+/// Results are only kept if every snapshotted line that is still loaded has
+/// unchanged content, i.e. the file was only appended to. Returns the number
+/// of entries restored.
+fn restore_grok_results(buffer: &mut LogBuffer, mut previous: HashMap<usize, LogEntry>) -> usize {
+    if previous.is_empty() {
+        return 0;
+    }
+
+    let append_only = buffer.iter().all(|entry| match previous.get(&entry.line_number) {
+        Some(old) => old.content == entry.content,
+        None => true,
+    });
+    if !append_only {
+        return 0;
+    }
+
+    let mut restored = 0;
+    buffer.update_all(|entry| {
+        let Some(old) = previous.remove(&entry.line_number) else {
+            return;
+        };
+        if let Some(fields) = old.grok_fields {
+            entry.set_grok_fields(fields);
+            entry.formatted_content = old.formatted_content;
+            entry.formatted_segments = old.formatted_segments;
+            restored += 1;
+        }
+    });
+    restored
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn add_tab(manager: &mut TabManager, path: &str) -> TabId {
+        manager.tab_bar.add_tab(Tab::new_local(0, PathBuf::from(path)))
+    }
+
+    /// Wait for a tab's initial read to finish
+    fn wait_loaded(state: &mut TabState) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        loop {
+            if let Some(result) = state.poll_loading() {
+                result.unwrap();
+                return;
+            }
+            assert!(Instant::now() < deadline, "initial read timed out");
+            thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn test_split_layout_save_restore() {
+        let mut manager = TabManager::default();
+        let a = add_tab(&mut manager, "/logs/a.log");
+        let b = add_tab(&mut manager, "/logs/b.log");
+        let _c = add_tab(&mut manager, "/logs/c.log");
+        manager.tab_bar.active_tab = Some(a);
+        manager.enable_split(b);
+        manager.split_view.config.split_ratio = 0.3;
+        manager.handle_split_action(SplitAction::SplitRatioChanged(0.3));
+
+        let layout = manager.split_layout();
+        assert!(layout.is_split);
+        assert_eq!(layout.split_ratio, 0.3);
+        assert_eq!(layout.left_path, Some(PathBuf::from("/logs/a.log")));
+        assert_eq!(layout.right_path, Some(PathBuf::from("/logs/b.log")));
+
+        // Restore into a fresh manager where tabs get different IDs
+        let mut restored = TabManager::default();
+        let _c = add_tab(&mut restored, "/logs/c.log");
+        let b = add_tab(&mut restored, "/logs/b.log");
+        restored.restore_split_layout(&layout);
+        // Not all files are open yet
+        assert!(!restored.is_split());
+        assert_eq!(restored.split_view.config.split_ratio, 0.3);
+
+        let a = add_tab(&mut restored, "/logs/a.log");
+        assert!(restored.apply_pending_split());
+        assert_eq!(restored.split_view.get_pane_tab(SplitPane::Left), Some(a));
+        assert_eq!(restored.split_view.get_pane_tab(SplitPane::Right), Some(b));
+    }
+
+    #[test]
+    fn test_toggle_split_restores_last_panes() {
+        let mut manager = TabManager::default();
+        let a = add_tab(&mut manager, "/logs/a.log");
+        let _b = add_tab(&mut manager, "/logs/b.log");
+        let c = add_tab(&mut manager, "/logs/c.log");
+        manager.tab_bar.active_tab = Some(a);
+        manager.enable_split(c);
+        manager.split_view.config.split_ratio = 0.7;
+
+        manager.toggle_split();
+        assert!(!manager.is_split());
+
+        // Re-enabling restores a|c rather than defaulting to the first other tab
+        manager.toggle_split();
+        assert!(manager.is_split());
+        assert_eq!(manager.split_view.get_pane_tab(SplitPane::Left), Some(a));
+        assert_eq!(manager.split_view.get_pane_tab(SplitPane::Right), Some(c));
+        assert_eq!(manager.split_view.config.split_ratio, 0.7);
+    }
+
+    #[test]
+    fn test_pinned_filter_across_tab_switches() {
+        use crate::log_entry::LogLevel;
+
+        let mut manager = TabManager::default();
+        let a = add_tab(&mut manager, "/logs/a.log");
+        let b = add_tab(&mut manager, "/logs/b.log");
+        for (id, path) in [(a, "/logs/a.log"), (b, "/logs/b.log")] {
+            manager
+                .states
+                .insert(id, TabState::new(id, PathBuf::from(path), LogBufferConfig::default()));
+        }
+
+        // Tab b has its own filter: bookmarks only
+        manager.states.get_mut(&b).unwrap().filter.filter.bookmarks_only = true;
+
+        // Pin an errors-only filter from tab a
+        manager.tab_bar.active_tab = Some(a);
+        manager.get_active_state_mut().unwrap().filter.filter.errors_and_warnings_only();
+        assert!(manager.toggle_pinned_filter());
+
+        // Switching to b applies the pinned filter in place of b's own
+        manager.tab_bar.active_tab = Some(b);
+        manager.apply_pinned_filter();
+        let state_b = &manager.states[&b];
+        assert!(!state_b.filter.filter.is_level_enabled(LogLevel::Info));
+        assert!(!state_b.filter.filter.bookmarks_only);
+
+        // Editing the shared filter on b carries over when switching back to a
+        manager
+            .get_active_state_mut()
+            .unwrap()
+            .filter
+            .filter
+            .toggle_level(LogLevel::Warn);
+        manager.tab_bar.active_tab = Some(a);
+        manager.apply_pinned_filter();
+        assert!(!manager.states[&a].filter.filter.is_level_enabled(LogLevel::Warn));
+        assert!(manager.states[&a].filter.filter.is_level_enabled(LogLevel::Error));
+
+        // Unpinning restores each tab's own filter
+        assert!(!manager.toggle_pinned_filter());
+        assert!(manager.states[&b].filter.filter.bookmarks_only);
+        assert!(manager.states[&b].filter.filter.is_level_enabled(LogLevel::Info));
+        assert!(!manager.states[&a].filter.filter.is_level_enabled(LogLevel::Info));
+        assert!(manager.states[&a].own_filter.is_none());
+    }
+
+    #[test]
+    fn test_space_key_action() {
+        let mut state = TabState::new(1, PathBuf::from("/logs/a.log"), LogBufferConfig::default());
+
+        // Default: toggles monitoring
+        assert!(!state.handle_space_key(SpaceKeyAction::default(), true));
+        assert!(state.handle_space_key(SpaceKeyAction::default(), false));
+        assert_eq!(state.main_view.pending_page_scroll(), 0);
+
+        // Page down scrolls without touching monitoring
+        assert!(state.handle_space_key(SpaceKeyAction::PageDown, true));
+        assert!(state.handle_space_key(SpaceKeyAction::PageDown, true));
+        assert_eq!(state.main_view.pending_page_scroll(), 2);
+
+        // Nothing does nothing
+        assert!(!state.handle_space_key(SpaceKeyAction::Nothing, false));
+        assert_eq!(state.main_view.pending_page_scroll(), 2);
+    }
+
+    fn parsed_buffer(lines: &[&str]) -> LogBuffer {
+        let mut buffer = LogBuffer::new();
+        for (i, line) in lines.iter().enumerate() {
+            buffer.push(LogEntry::new(i + 1, line.to_string(), 0));
+        }
+        buffer
+    }
+
+    #[test]
+    fn test_small_files_are_fully_grok_parsed() {
+        use crate::grok_parser::BuiltinPattern;
+
+        let tab = |lines: usize| {
+            let mut state = TabState::new(0, PathBuf::from("app.log"), LogBufferConfig::default());
+            for i in 0..lines {
+                let line = format!("2024-01-15 10:00:{:02} INFO message {}", i % 60, i);
+                state.buffer.push(LogEntry::new(i + 1, line, 0));
+            }
+            let mut parser = GrokParser::new();
+            parser.set_builtin_pattern(BuiltinPattern::SimpleLog).unwrap();
+            state.grok_parser = Some(parser);
+            state
+        };
+
+        // Under the threshold every line gets parsed, a chunk at a time
+        let mut small = tab(50);
+        assert_eq!(small.parse_grok_chunk(100, 20), 20);
+        while small.parse_grok_chunk(100, 20) > 0 {}
+        assert!(small.buffer.iter().all(|e| e.grok_fields.is_some()));
+
+        // Over the threshold (or with eager parsing off) nothing is parsed up front
+        let mut large = tab(150);
+        assert_eq!(large.parse_grok_chunk(100, 20), 0);
+        assert_eq!(large.parse_grok_chunk(0, 20), 0);
+        assert!(large.buffer.iter().all(|e| e.grok_fields.is_none()));
+    }
+
+    #[test]
+    fn test_field_filters_parse_every_line_in_the_background() {
+        use crate::config::{CorrelationConfig, CorrelationSource};
+        use crate::correlation::{CorrelationExtractor, CorrelationFilter};
+        use crate::grok_parser::BuiltinPattern;
+
+        let mut state = TabState::new(0, PathBuf::from("app.log"), LogBufferConfig::default());
+        let (msg_tx, msg_rx) = bounded::<ReaderMessage>(10);
+        state.reader_rx = Some(msg_rx);
+        let line = |n: usize| {
+            let level = if n % 2 == 0 { "WARN" } else { "INFO" };
+            LogEntry::new(n, format!("2024-01-15 10:00:00 {} message {}", level, n), 0)
+        };
+        for n in 1..=6 {
+            state.buffer.push(line(n));
+        }
+        let mut parser = GrokParser::new();
+        parser
+            .set_builtin_pattern(BuiltinPattern::SimpleLog)
+            .unwrap();
+        state.grok_parser = Some(parser);
+
+        let config = CorrelationConfig {
+            source: CorrelationSource::GrokField,
+            field: "level".to_string(),
+            ..Default::default()
+        };
+        state.filter.filter.correlation = Some(CorrelationFilter {
+            extractor: CorrelationExtractor::new(&config).unwrap(),
+            id: "WARN".to_string(),
+        });
+        state.update_filter();
+        // Nothing was parsed on this thread
+        assert!(state.filtered_indices.is_empty());
+
+        while !state.poll_grok_parse_jobs() {
+            thread::sleep(Duration::from_millis(5));
+        }
+        state.update_filter();
+        assert_eq!(state.filtered_indices, vec![1, 3, 5]);
+
+        // Lines arriving later are parsed as they arrive
+        msg_tx
+            .send(ReaderMessage::NewEntries(vec![line(7), line(8)]))
+            .unwrap();
+        state.process_reader_messages();
+        state.update_filter();
+        assert_eq!(state.filtered_indices, vec![1, 3, 5, 7]);
+    }
+
+    #[test]
+    fn test_field_sort_waits_for_every_line_to_be_parsed() {
+        use crate::grok_parser::BuiltinPattern;
+        use crate::search::FieldSort;
+
+        let mut state = TabState::new(0, PathBuf::from("app.log"), LogBufferConfig::default());
+        for (i, level) in ["WARN", "ERROR", "INFO"].iter().enumerate() {
+            let line = format!("2024-01-15 10:00:00 {} message", level);
+            state.buffer.push(LogEntry::new(i + 1, line, 0));
+        }
+        let mut parser = GrokParser::new();
+        parser
+            .set_builtin_pattern(BuiltinPattern::SimpleLog)
+            .unwrap();
+        state.grok_parser = Some(parser);
+
+        state.filter.filter.sort_by = FieldSort::next(None, "level");
+        state.update_filter();
+        // File order until the fields are there
+        assert!(state.main_view.sort_pending);
+        assert_eq!(state.filtered_indices, vec![0, 1, 2]);
+        assert!(state.filter.filter.sort_by.is_some());
+
+        while !state.poll_grok_parse_jobs() {
+            thread::sleep(Duration::from_millis(5));
+        }
+        state.update_filter();
+        assert!(!state.main_view.sort_pending);
+        assert_eq!(state.filtered_indices, vec![1, 2, 0]);
+    }
+
+    #[test]
+    fn test_append_only_reload_keeps_grok_fields() {
+        let mut before = parsed_buffer(&["INFO a", "WARN b"]);
+        for index in 0..before.len() {
+            before.update(index, |entry| {
+                let fields = HashMap::from([("msg".to_string(), entry.content.to_string())]);
+                entry.set_grok_fields(fields);
+                entry.formatted_content = Some(format!("formatted {}", index));
+            });
+        }
+
+        // The file only grew: earlier results are reused, new lines stay unparsed
+        let mut after = parsed_buffer(&["INFO a", "WARN b", "ERROR c"]);
+        assert_eq!(restore_grok_results(&mut after, grok_snapshot(&before)), 2);
+        let first = after.get(0).unwrap();
+        assert_eq!(first.grok_fields.as_ref().unwrap()["msg"], "INFO a");
+        assert_eq!(first.formatted_content.as_deref(), Some("formatted 0"));
+        assert!(after.get(1).unwrap().grok_fields.is_some());
+        assert!(after.get(2).unwrap().grok_fields.is_none());
+
+        // A rewritten line means the file changed: everything is re-parsed
+        let mut rewritten = parsed_buffer(&["INFO a", "WARN changed", "ERROR c"]);
+        assert_eq!(restore_grok_results(&mut rewritten, grok_snapshot(&before)), 0);
+        assert!(rewritten.iter().all(|e| e.grok_fields.is_none()));
+    }
+
+    #[test]
+    fn test_reverse_order_restored_per_file() {
+        use crate::config::AppConfig;
+
+        let mut config = AppConfig::default();
+        let mut manager = TabManager::default();
+        let a = add_tab(&mut manager, "/logs/a.log");
+        let b = add_tab(&mut manager, "/logs/b.log");
+        for (id, path) in [(a, "/logs/a.log"), (b, "/logs/b.log")] {
+            manager
+                .states
+                .insert(id, TabState::new(id, PathBuf::from(path), LogBufferConfig::default()));
+        }
+
+        // Reversing tab a is remembered for its file only
+        manager.tab_bar.active_tab = Some(a);
+        let (path, reverse) = manager.toggle_active_reverse_order().unwrap();
+        config.set_file_reverse_order(&path, reverse);
+        assert!(manager.active_reverse_order());
+
+        // Switching tabs doesn't leak the state
+        manager.tab_bar.active_tab = Some(b);
+        assert!(!manager.active_reverse_order());
+        manager.tab_bar.active_tab = Some(a);
+        assert!(manager.active_reverse_order());
+
+        // Reopening a.log in a new tab restores its preference
+        let reopened = add_tab(&mut manager, "/logs/a.log");
+        let path = PathBuf::from("/logs/a.log");
+        manager
+            .states
+            .insert(reopened, TabState::new(reopened, path.clone(), LogBufferConfig::default()));
+        manager.restore_reverse_order(reopened, config.is_file_reverse_order(&path));
+        manager.tab_bar.active_tab = Some(reopened);
+        assert!(manager.active_reverse_order());
+        assert!(!config.is_file_reverse_order(&PathBuf::from("/logs/b.log")));
+        assert!(!manager.states[&b].main_view.is_reverse_order());
+    }
+
+    #[test]
+    fn test_large_file_opens_in_background() {
+        use std::io::{BufWriter, Write};
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("big.log");
+        let mut file = BufWriter::new(std::fs::File::create(&path).unwrap());
+        for i in 0..200_000 {
+            writeln!(file, "INFO line {}", i).unwrap();
+        }
+        drop(file);
+
+        // The tab exists right away, before any line has been read
+        let mut manager = TabManager::default();
+        let id = manager
+            .open_local_file(path, None, &BookmarksStore::default())
+            .unwrap();
+        assert!(manager.states[&id].is_loading());
+        assert!(manager.states[&id].buffer.is_empty());
+        assert!(manager.any_loading());
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let loaded = loop {
+            let loaded = manager.poll_loading();
+            if !loaded.is_empty() {
+                break loaded;
+            }
+            assert!(Instant::now() < deadline, "initial read timed out");
+            thread::sleep(Duration::from_millis(5));
+        };
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].0, id);
+        assert!(loaded[0].1.is_ok());
+
+        // Ready: the tail is loaded and the file is being followed
+        let state = &manager.states[&id];
+        assert!(!state.is_loading());
+        assert!(state.is_watching());
+        let last = state.buffer.get(state.buffer.len() - 1).unwrap();
+        assert_eq!(last.content, "INFO line 199999");
+        assert!(!manager.tab_bar.tabs[0].loading);
+
+        for state in manager.states.values_mut() {
+            state.close();
+        }
+    }
+
+    #[test]
+    fn test_merge_reads_sources_in_background() {
+        let dir = tempfile::tempdir().unwrap();
+        let api = dir.path().join("api.log");
+        let db = dir.path().join("db.log");
+        std::fs::write(
+            &api,
+            "2024-01-15 10:00:01 INFO request\n2024-01-15 10:00:03 INFO done\n",
+        )
+        .unwrap();
+        std::fs::write(&db, "2024-01-15 10:00:02 INFO query\n").unwrap();
+
+        let mut manager = TabManager::default();
+        let id = manager.open_merged(&[api, db.clone()]);
+        assert!(manager.states[&id].merged);
+        wait_loaded(manager.states.get_mut(&id).unwrap());
+        let lines: Vec<String> = manager.states[&id]
+            .buffer
+            .iter()
+            .map(|e| e.content.to_string())
+            .collect();
+        assert_eq!(
+            lines,
+            [
+                "[api.log] 2024-01-15 10:00:01 INFO request",
+                "[db.log] 2024-01-15 10:00:02 INFO query",
+                "[api.log] 2024-01-15 10:00:03 INFO done",
+            ]
+        );
+
+        // A source that can't be read fails the merge
+        let id = manager.open_merged(&[db, dir.path().join("missing.log")]);
+        let state = manager.states.get_mut(&id).unwrap();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let result = loop {
+            if let Some(result) = state.poll_loading() {
+                break result;
+            }
+            assert!(Instant::now() < deadline, "merge timed out");
+            thread::sleep(Duration::from_millis(5));
+        };
+        assert!(format!("{:#}", result.unwrap_err()).contains("missing.log"));
+    }
+
+    #[test]
+    fn test_only_file_tabs_persist_settings() {
+        let mut manager = TabManager::default();
+        // A real file whose name looks like a merged tab's path
+        for (path, merged) in [("merged@2024.log", false), ("merged@10:42:00:api+db", true)] {
+            let id = add_tab(&mut manager, path);
+            let mut state = TabState::new(id, PathBuf::from(path), LogBufferConfig::default());
+            state.merged = merged;
+            manager.states.insert(id, state);
+        }
+
+        assert!(manager.persists_settings(Path::new("merged@2024.log")));
+        assert!(!manager.persists_settings(Path::new("merged@10:42:00:api+db")));
+        // Paths without a tab, e.g. from the explorer, keep theirs
+        assert!(manager.persists_settings(Path::new("/var/log/app.log")));
+    }
+
+    #[test]
+    fn test_idle_tab_pauses_and_resumes() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut manager = TabManager::default();
+        let mut ids = Vec::new();
+        for name in ["a.log", "b.log"] {
+            let path = dir.path().join(name);
+            std::fs::File::create(&path)
+                .unwrap()
+                .write_all(b"INFO hello\n")
+                .unwrap();
+            let id = add_tab(&mut manager, path.to_str().unwrap());
+            let mut state = TabState::new(id, path, LogBufferConfig::default());
+            state.open_file(None, &BookmarksStore::default()).unwrap();
+            wait_loaded(&mut state);
+            manager.states.insert(id, state);
+            ids.push(id);
+        }
+        let (a, b) = (ids[0], ids[1]);
+        let timeout = Duration::from_secs(60);
+        let start = Instant::now();
+
+        // b is in the background but not idle long enough yet
+        manager.tab_bar.active_tab = Some(a);
+        assert_eq!(manager.update_idle_monitoring(timeout, start), 0);
+
+        // After the timeout b's monitoring is paused and the tab says so
+        let later = start + timeout + Duration::from_secs(1);
+        assert_eq!(manager.update_idle_monitoring(timeout, later), 1);
+        assert!(manager.states[&b].idle_paused);
+        assert!(!manager.states[&a].idle_paused);
+        assert!(manager.tab_bar.tabs.iter().find(|t| t.id == b).unwrap().idle_paused);
+        // Already paused tabs are not paused again
+        assert_eq!(manager.update_idle_monitoring(timeout, later), 0);
+
+        // Activating b resumes it
+        manager.tab_bar.active_tab = Some(b);
+        manager.update_idle_monitoring(timeout, later);
+        assert!(!manager.states[&b].idle_paused);
+        assert!(manager.states[&b].reader_tx.is_some());
+        assert!(!manager.tab_bar.tabs.iter().find(|t| t.id == b).unwrap().idle_paused);
+
+        for state in manager.states.values_mut() {
+            state.close();
+        }
+    }
+
+    #[test]
+    fn test_lines_after_snapshot_are_additions() {
+        use crate::log_diff::DiffKind;
+
+        let mut manager = TabManager::default();
+        let live = add_tab(&mut manager, "/logs/live.log");
+        let mut state = TabState::new(
+            live,
+            PathBuf::from("/logs/live.log"),
+            LogBufferConfig::default(),
+        );
+        state.buffer.extend([
+            LogEntry::new(1, "INFO start".to_string(), 0),
+            LogEntry::new(2, "INFO ready".to_string(), 11),
+        ]);
+        manager.states.insert(live, state);
+
+        let snapshot = manager.take_snapshot(live).unwrap();
+        assert_ne!(snapshot, live);
+        assert_eq!(manager.states[&snapshot].buffer.len(), 2);
+        assert_eq!(manager.snapshot_pair(live), Some((snapshot, live)));
+        assert_eq!(manager.snapshot_pair(snapshot), Some((snapshot, live)));
+
+        // The live tab keeps tailing; the snapshot stays frozen
+        manager.states.get_mut(&live).unwrap().buffer.extend([
+            LogEntry::new(3, "WARN slow".to_string(), 22),
+            LogEntry::new(4, "ERROR failed".to_string(), 32),
+        ]);
+        assert_eq!(manager.states[&snapshot].buffer.len(), 2);
+
+        let diff = manager.diff_against_snapshot(live).unwrap().recv().unwrap();
+        assert_eq!((diff.added, diff.removed), (2, 0));
+        let added: Vec<_> = diff
+            .lines
+            .iter()
+            .filter(|l| l.kind == DiffKind::Added)
+            .map(|l| (l.new_line, l.content.as_str()))
+            .collect();
+        assert_eq!(
+            added,
+            vec![(Some(3), "WARN slow"), (Some(4), "ERROR failed")]
+        );
+
+        // The pair is shown side by side, snapshot on the left
+        assert!(manager.is_split());
+        let panes = [SplitPane::Left, SplitPane::Right].map(|p| manager.split_view.get_pane_tab(p));
+        assert_eq!(panes, [Some(snapshot), Some(live)]);
+    }
+
+    #[test]
+    fn test_repaint_trigger_follows_min_level_changes() {
+        let mut manager = TabManager::default();
+        let trigger = manager.repaint_trigger.clone();
+        let lines = |content: &str| vec![LogEntry::new(1, content.to_string(), 0)];
+
+        // Reduced-update mode off: readers never force a repaint
+        assert!(!trigger.should_repaint(&lines("ERROR failed")));
+
+        // Readers already running see the new level
+        manager.set_repaint_min_level(Some(LogLevel::Warn));
+        assert!(trigger.should_repaint(&lines("ERROR failed")));
+        assert!(trigger.should_repaint(&lines("WARN slow")));
+        assert!(!trigger.should_repaint(&lines("INFO ok")));
+    }
+
+    #[test]
+    fn test_capture_stops_monitoring_after_limit() {
+        let mut state = TabState::new(
+            1,
+            PathBuf::from("/logs/app.log"),
+            LogBufferConfig::default(),
+        );
+        let (msg_tx, msg_rx) = bounded::<ReaderMessage>(10);
+        let (cmd_tx, cmd_rx) = bounded::<ReaderCommand>(10);
+        state.reader_rx = Some(msg_rx);
+        state.reader_tx = Some(cmd_tx);
+        state.start_capture(3);
+
+        let batch = |from: usize, count: usize| {
+            (from..from + count)
+                .map(|n| LogEntry::new(n, format!("INFO line {}", n), 0))
+                .collect::<Vec<_>>()
+        };
+
+        // Below the budget monitoring continues
+        msg_tx.send(ReaderMessage::NewEntries(batch(1, 2))).unwrap();
+        state.process_reader_messages();
+        assert_eq!(state.buffer.len(), 2);
+        assert!(state.take_completed_capture().is_none());
+        assert!(cmd_rx.try_recv().is_err());
+
+        // The batch crossing the budget is cut at the limit and the reader stopped
+        msg_tx.send(ReaderMessage::NewEntries(batch(3, 4))).unwrap();
+        state.process_reader_messages();
+        assert_eq!(state.buffer.len(), 3);
+        assert_eq!(state.buffer.iter().last().unwrap().line_number, 3);
+        assert!(matches!(cmd_rx.try_recv(), Ok(ReaderCommand::Stop)));
+        assert!(state.monitoring_stopped);
+        // The channels stay open so scrolling up can still load older lines
+        assert!(state.reader_tx.is_some() && state.reader_rx.is_some());
+
+        // Lines already in flight past the budget are dropped
+        msg_tx.send(ReaderMessage::NewEntries(batch(7, 2))).unwrap();
+        state.process_reader_messages();
+        assert_eq!(state.buffer.len(), 3);
+        assert_eq!(
+            state.take_completed_capture(),
+            Some(LineCapture { limit: 3, captured: 3 })
+        );
+        assert!(state.capture.is_none());
+    }
+
+    #[test]
+    fn test_file_reset_reloads_and_keeps_auto_scroll() {
+        let mut manager = TabManager::default();
+        let tab = add_tab(&mut manager, "/logs/app.log");
+        let state = TabState::new(
+            tab,
+            PathBuf::from("/logs/app.log"),
+            LogBufferConfig::default(),
+        );
+        manager.states.insert(tab, state);
+        let (msg_tx, msg_rx) = bounded::<ReaderMessage>(10);
+        let state = manager.get_state_mut(tab).unwrap();
+        state.reader_rx = Some(msg_rx);
+        state.main_view.virtual_scroll.state.auto_scroll = true;
+
+        let old = (1..=3).map(|n| LogEntry::new(n, format!("INFO old {}", n), 0));
+        msg_tx
+            .send(ReaderMessage::NewEntries(old.collect()))
+            .unwrap();
+        state.process_reader_messages();
+        state.update_filter();
+        assert_eq!(state.buffer.len(), 3);
+        assert!(manager.take_reloaded_tabs().is_empty());
+
+        let new = LogEntry::new(1, "INFO new 1".to_string(), 0);
+        msg_tx.send(ReaderMessage::FileReset).unwrap();
+        msg_tx.send(ReaderMessage::NewEntries(vec![new])).unwrap();
+        manager.process_all_reader_messages();
+        manager.update_pending_filters();
+
+        let state = &manager.states[&tab];
+        let lines: Vec<_> = state.buffer.iter().map(|e| e.content.as_str()).collect();
+        assert_eq!(lines, ["INFO new 1"]);
+        assert!(state.main_view.is_auto_scroll());
+        assert_eq!(manager.take_reloaded_tabs(), vec![tab]);
+        assert!(manager.take_reloaded_tabs().is_empty());
+    }
+
+    #[test]
+    fn test_match_bookmarks_follow_the_filtered_view() {
+        let mut state = TabState::new(1, PathBuf::from("/logs/app.log"), LogBufferConfig::default());
+        for (i, line) in ["ERROR failed", "INFO failed", "ERROR failed again", "INFO ok"]
+            .iter()
+            .enumerate()
+        {
+            state.buffer.push(LogEntry::new(i + 1, line.to_string(), 0));
+        }
+        state.filter.filter.min_level = Some(LogLevel::Error);
+        state.filter.mark_dirty();
+        state.update_filter();
+        state.filter.search.set_query("failed".to_string());
+        state.filter.search.search(&state.buffer);
+        assert_eq!(state.filter.search.result_count(), 3);
+
+        // The INFO match is filtered out and stays unbookmarked
+        assert_eq!(state.toggle_match_bookmarks(), (2, true));
+        let bookmarked: Vec<_> = state.buffer.iter().map(|e| e.bookmarked).collect();
+        assert_eq!(bookmarked, [true, false, true, false]);
+
+        // Toggling again removes them
+        assert_eq!(state.toggle_match_bookmarks(), (2, false));
+        assert!(state.buffer.iter().all(|e| !e.bookmarked));
+    }
+
+    #[test]
+    fn test_tail_pauses_when_an_error_arrives() {
+        let mut manager = TabManager::default();
+        let tab = add_tab(&mut manager, "/logs/app.log");
+        let state = TabState::new(
+            tab,
+            PathBuf::from("/logs/app.log"),
+            LogBufferConfig::default(),
+        );
+        manager.states.insert(tab, state);
+        let (msg_tx, msg_rx) = bounded::<ReaderMessage>(10);
+        let (cmd_tx, cmd_rx) = bounded::<ReaderCommand>(10);
+        let state = manager.get_state_mut(tab).unwrap();
+        state.reader_rx = Some(msg_rx);
+        state.reader_tx = Some(cmd_tx);
+        state.main_view.virtual_scroll.state.auto_scroll = true;
+
+        let send = |lines: &[(usize, &str)]| {
+            let entries = lines
+                .iter()
+                .map(|(n, line)| LogEntry::new(*n, line.to_string(), 0))
+                .collect();
+            msg_tx.send(ReaderMessage::NewEntries(entries)).unwrap();
+        };
+
+        // Errors arriving while the option is off are not held over
+        send(&[(1, "ERROR first failure")]);
+        manager.process_all_reader_messages();
+        assert!(manager.pause_on_arrived_errors(false).is_empty());
+        assert!(manager.pause_on_arrived_errors(true).is_empty());
+
+        send(&[(2, "INFO all good")]);
+        manager.process_all_reader_messages();
+        assert!(manager.pause_on_arrived_errors(true).is_empty());
+        assert!(manager.states[&tab].main_view.is_auto_scroll());
+
+        send(&[
+            (3, "INFO still fine"),
+            (4, "FATAL out of memory"),
+            (5, "ERROR write failed"),
+        ]);
+        manager.process_all_reader_messages();
+        assert_eq!(manager.pause_on_arrived_errors(true), vec![tab]);
+        let state = &manager.states[&tab];
+        assert!(!state.main_view.is_auto_scroll());
+        assert!(state.main_view.virtual_scroll.state.follow_paused);
+        // The view moves to the first error that arrived
+        assert_eq!(state.main_view.selected_line, Some(3));
+        // Only scrolling stops; the file is still being read
+        assert!(cmd_rx.try_recv().is_err());
+        assert!(state.reader_rx.is_some());
+
+        // Already paused tabs aren't reported again
+        send(&[(6, "ERROR second failure")]);
+        manager.process_all_reader_messages();
+        assert!(manager.pause_on_arrived_errors(true).is_empty());
+    }
 }
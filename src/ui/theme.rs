@@ -0,0 +1,140 @@
+//! Application theme
+//!
+//! Builds the egui style for the configured theme, including the
+//! high-contrast variant and the enlarged hit targets used for accessibility.
+
+use crate::config::{AccessibilityConfig, Theme};
+use egui::{Color32, Stroke, Style, Visuals};
+
+/// UI zoom applied when large hit targets are enabled
+pub const LARGE_TARGET_ZOOM: f32 = 1.25;
+
+/// Build the visuals for a theme
+pub fn visuals(theme: Theme, high_contrast: bool) -> Visuals {
+    let base = match theme {
+        Theme::Dark => Visuals::dark(),
+        Theme::Light => Visuals::light(),
+    };
+    if high_contrast {
+        high_contrast_visuals(base)
+    } else {
+        base
+    }
+}
+
+/// Build the full style for a theme and accessibility settings
+pub fn style(theme: Theme, accessibility: &AccessibilityConfig) -> Style {
+    let mut style = Style {
+        visuals: visuals(theme, accessibility.high_contrast),
+        ..Default::default()
+    };
+    if accessibility.large_targets {
+        enlarge_targets(&mut style);
+    }
+    style
+}
+
+/// Apply the theme to the context
+pub fn apply(ctx: &egui::Context, theme: Theme, accessibility: &AccessibilityConfig) {
+    ctx.set_style(style(theme, accessibility));
+}
+
+/// Apply the zoom for the accessibility settings.
+/// The zoom also scales the custom titlebar, which has no size options.
+pub fn apply_zoom(ctx: &egui::Context, accessibility: &AccessibilityConfig) {
+    ctx.set_zoom_factor(if accessibility.large_targets {
+        LARGE_TARGET_ZOOM
+    } else {
+        1.0
+    });
+}
+
+/// Strong foreground/background separation and thick focus outlines
+fn high_contrast_visuals(mut visuals: Visuals) -> Visuals {
+    let (fg, bg, accent) = if visuals.dark_mode {
+        (Color32::WHITE, Color32::BLACK, Color32::from_rgb(255, 215, 0))
+    } else {
+        (Color32::BLACK, Color32::WHITE, Color32::from_rgb(0, 60, 200))
+    };
+
+    visuals.override_text_color = Some(fg);
+    visuals.panel_fill = bg;
+    visuals.window_fill = bg;
+    visuals.extreme_bg_color = bg;
+    visuals.faint_bg_color = if visuals.dark_mode {
+        Color32::from_gray(30)
+    } else {
+        Color32::from_gray(225)
+    };
+    visuals.window_stroke = Stroke::new(2.0, fg);
+    visuals.hyperlink_color = accent;
+
+    visuals.selection.bg_fill = accent.gamma_multiply(0.5);
+    visuals.selection.stroke = Stroke::new(2.0, fg);
+
+    let widgets = &mut visuals.widgets;
+    widgets.noninteractive.bg_stroke = Stroke::new(1.0, fg);
+    widgets.noninteractive.fg_stroke = Stroke::new(1.0, fg);
+    widgets.inactive.bg_stroke = Stroke::new(1.5, fg);
+    widgets.inactive.fg_stroke = Stroke::new(1.5, fg);
+    widgets.hovered.bg_stroke = Stroke::new(2.5, accent);
+    widgets.hovered.fg_stroke = Stroke::new(2.0, fg);
+    widgets.active.bg_stroke = Stroke::new(3.0, accent);
+    widgets.active.fg_stroke = Stroke::new(2.0, fg);
+    widgets.open.bg_stroke = Stroke::new(2.0, accent);
+
+    visuals
+}
+
+/// Larger buttons, spacing and text
+fn enlarge_targets(style: &mut Style) {
+    let spacing = &mut style.spacing;
+    spacing.interact_size.y = spacing.interact_size.y.max(28.0);
+    spacing.button_padding = egui::vec2(8.0, 5.0);
+    spacing.item_spacing = egui::vec2(10.0, 8.0);
+    spacing.icon_width = 20.0;
+    spacing.icon_width_inner = 12.0;
+
+    for font in style.text_styles.values_mut() {
+        font.size *= 1.15;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_high_contrast_visuals() {
+        let dark = visuals(Theme::Dark, true);
+        assert!(dark.dark_mode);
+        assert_eq!(dark.panel_fill, Color32::BLACK);
+        assert_eq!(dark.override_text_color, Some(Color32::WHITE));
+        assert!(dark.selection.stroke.width >= 2.0);
+        assert!(dark.widgets.hovered.bg_stroke.width > Visuals::dark().widgets.hovered.bg_stroke.width);
+
+        let light = visuals(Theme::Light, true);
+        assert!(!light.dark_mode);
+        assert_eq!(light.panel_fill, Color32::WHITE);
+        assert_eq!(light.override_text_color, Some(Color32::BLACK));
+
+        // Without high contrast the stock egui visuals are used
+        assert_eq!(visuals(Theme::Light, false), Visuals::light());
+    }
+
+    #[test]
+    fn test_large_targets_enlarge_style() {
+        let normal = style(Theme::Dark, &AccessibilityConfig::default());
+        let large = style(
+            Theme::Dark,
+            &AccessibilityConfig {
+                large_targets: true,
+                ..Default::default()
+            },
+        );
+        assert!(large.spacing.interact_size.y > normal.spacing.interact_size.y);
+        assert!(large.spacing.button_padding.y > normal.spacing.button_padding.y);
+        let body = egui::TextStyle::Body;
+        assert!(large.text_styles[&body].size > normal.text_styles[&body].size);
+    }
+}
@@ -0,0 +1,403 @@
+//! Timeline sparkline - line counts over time above the log view
+//!
+//! Dragging across the sparkline selects a time range and filters the view
+//! to it; clicking outside the selected range clears the filter.
+
+use crate::i18n::Translations as t;
+use crate::log_buffer::LogBuffer;
+use crate::search::TimeRange;
+use chrono::{DateTime, Duration, Local};
+use egui::{pos2, vec2, Align2, Color32, FontId, Rect, Sense, Ui};
+use std::collections::VecDeque;
+
+/// Height of the sparkline strip
+const TIMELINE_HEIGHT: f32 = 28.0;
+
+/// Number of time buckets (bars)
+const BUCKET_COUNT: usize = 120;
+
+/// Drags narrower than this (in pixels) count as clicks
+const MIN_DRAG_WIDTH: f32 = 3.0;
+
+/// Bar color
+const BAR_COLOR: Color32 = Color32::from_rgb(0, 122, 204);
+
+/// Fill of the applied time range
+const RANGE_FILL: Color32 = Color32::from_rgba_premultiplied(60, 48, 2, 60);
+
+/// Fill of the range being dragged
+const DRAG_FILL: Color32 = Color32::from_rgba_premultiplied(80, 80, 80, 80);
+
+/// Actions from the timeline
+#[derive(Debug, Clone, PartialEq)]
+pub enum TimelineAction {
+    /// No action
+    None,
+    /// Filter the view to a time range
+    SelectRange(TimeRange),
+    /// Remove the time range filter
+    ClearRange,
+}
+
+/// Line counts per time bucket between the earliest and latest timestamp
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimelineData {
+    pub start: DateTime<Local>,
+    pub end: DateTime<Local>,
+    pub counts: Vec<usize>,
+}
+
+impl TimelineData {
+    /// Bucket sorted timestamps (None without at least two distinct times)
+    fn from_sorted(times: &VecDeque<DateTime<Local>>, buckets: usize) -> Option<Self> {
+        let (&start, &end) = (times.front()?, times.back()?);
+        if start == end || buckets == 0 {
+            return None;
+        }
+
+        let mut data = Self {
+            start,
+            end,
+            counts: vec![0; buckets],
+        };
+        // Bucket indices only grow along the sorted times, so each bucket
+        // is a run found by binary search
+        let bucket_of = |time: DateTime<Local>| {
+            ((data.fraction_of(time) * buckets as f32) as usize).min(buckets - 1)
+        };
+        let bounds: Vec<usize> = (0..=buckets)
+            .map(|b| times.partition_point(|t| bucket_of(*t) < b))
+            .collect();
+        for (count, run) in data.counts.iter_mut().zip(bounds.windows(2)) {
+            *count = run[1] - run[0];
+        }
+        Some(data)
+    }
+
+    /// Time at a horizontal fraction (0..=1) of the timeline
+    pub fn time_at(&self, fraction: f32) -> DateTime<Local> {
+        let span = (self.end - self.start).num_milliseconds() as f64;
+        let offset = span * fraction.clamp(0.0, 1.0) as f64;
+        self.start + Duration::milliseconds(offset.round() as i64)
+    }
+
+    /// Horizontal fraction (0..=1) of a time on the timeline
+    pub fn fraction_of(&self, time: DateTime<Local>) -> f32 {
+        let span = (self.end - self.start).num_milliseconds() as f64;
+        let offset = (time - self.start).num_milliseconds() as f64;
+        (offset / span).clamp(0.0, 1.0) as f32
+    }
+
+    /// Time range covered by a drag between two x positions on a timeline
+    /// drawn from `left` spanning `width` pixels
+    pub fn span_to_range(&self, x0: f32, x1: f32, left: f32, width: f32) -> TimeRange {
+        let (from, to) = if x0 <= x1 { (x0, x1) } else { (x1, x0) };
+        TimeRange {
+            start: self.time_at((from - left) / width),
+            end: self.time_at((to - left) / width),
+        }
+    }
+}
+
+/// Buffer shape an index was synced with (length, first and last line)
+type BufferShape = (usize, usize, usize);
+
+/// Timestamps of a buffer's lines, kept in step as lines are appended and
+/// trimmed so the timeline doesn't rescan the buffer while tailing
+#[derive(Default)]
+struct TimeIndex {
+    /// Buffer revision and shape last synced with
+    synced: Option<(u64, BufferShape)>,
+    /// Line number and timestamp of the timestamped lines, in line order
+    by_line: VecDeque<(usize, DateTime<Local>)>,
+    /// The same timestamps, sorted
+    sorted: VecDeque<DateTime<Local>>,
+}
+
+impl TimeIndex {
+    /// Catch up with the buffer. Returns whether anything changed.
+    fn sync(&mut self, buffer: &LogBuffer) -> bool {
+        let shape = (
+            buffer.len(),
+            buffer.first_line_number(),
+            buffer.last_line_number(),
+        );
+        let revision = buffer.revision();
+        match self.synced {
+            Some(synced) if synced == (revision, shape) => return false,
+            Some((synced_revision, old)) if synced_revision == revision => {
+                if !self.extend(buffer, old, shape) {
+                    self.rebuild(buffer);
+                }
+            }
+            _ => self.rebuild(buffer),
+        }
+        self.synced = Some((revision, shape));
+        true
+    }
+
+    /// Index every line again
+    fn rebuild(&mut self, buffer: &LogBuffer) {
+        self.by_line = buffer
+            .iter()
+            .filter_map(|e| Some((e.line_number, buffer.entry_timestamp(e)?)))
+            .collect();
+        let mut sorted: Vec<_> = self.by_line.iter().map(|(_, t)| *t).collect();
+        sorted.sort_unstable();
+        self.sorted = sorted.into();
+    }
+
+    /// Apply lines appended and trimmed since the buffer had shape `old`.
+    /// Returns false if the buffer changed some other way.
+    fn extend(&mut self, buffer: &LogBuffer, old: BufferShape, new: BufferShape) -> bool {
+        let ((old_len, old_first, old_last), (len, first, last)) = (old, new);
+        if first < old_first || last < old_last {
+            return false;
+        }
+        let appended = (0..len)
+            .rev()
+            .map_while(|i| buffer.get(i))
+            .take_while(|e| e.line_number > old_last)
+            .count();
+        if old_len + appended < len {
+            return false;
+        }
+
+        // Trimmed from the front...
+        while let Some(&(line, time)) = self.by_line.front() {
+            if line >= first {
+                break;
+            }
+            self.by_line.pop_front();
+            self.remove_sorted(time);
+        }
+        // ...or from the gap after the kept head
+        if let Some(gap) = buffer.omitted() {
+            let head_last = gap
+                .index
+                .checked_sub(1)
+                .and_then(|i| buffer.get(i))
+                .map_or(0, |e| e.line_number);
+            let tail_first = buffer.get(gap.index).map_or(usize::MAX, |e| e.line_number);
+            let from = self.by_line.partition_point(|(line, _)| *line <= head_last);
+            let to = self.by_line.partition_point(|(line, _)| *line < tail_first);
+            let trimmed: Vec<_> = self.by_line.drain(from..to.max(from)).collect();
+            for (_, time) in trimmed {
+                self.remove_sorted(time);
+            }
+        }
+
+        for entry in (len - appended..len).filter_map(|i| buffer.get(i)) {
+            if let Some(time) = buffer.entry_timestamp(entry) {
+                self.by_line.push_back((entry.line_number, time));
+                let at = self.sorted.partition_point(|t| *t <= time);
+                self.sorted.insert(at, time);
+            }
+        }
+        true
+    }
+
+    /// Remove one occurrence of a time from the sorted list
+    fn remove_sorted(&mut self, time: DateTime<Local>) {
+        let at = self.sorted.partition_point(|t| *t < time);
+        if self.sorted.get(at) == Some(&time) {
+            self.sorted.remove(at);
+        }
+    }
+
+    /// Number of indexed lines with a timestamp within a range
+    fn count_in_range(&self, range: &TimeRange) -> usize {
+        let end = self.sorted.partition_point(|t| *t <= range.end);
+        let start = self.sorted.partition_point(|t| *t < range.start);
+        end.saturating_sub(start)
+    }
+}
+
+/// Timeline state for one tab
+#[derive(Default)]
+pub struct Timeline {
+    /// Timestamps of the buffer's lines
+    index: TimeIndex,
+    /// Bucketed data, recomputed when the index changes
+    data: Option<TimelineData>,
+    /// Start and current x position of a drag in progress
+    drag: Option<(f32, f32)>,
+}
+
+impl Timeline {
+    /// Bucketed data for the buffer
+    fn data(&mut self, buffer: &LogBuffer) -> Option<TimelineData> {
+        if self.index.sync(buffer) {
+            self.data = TimelineData::from_sorted(&self.index.sorted, BUCKET_COUNT);
+        }
+        self.data.clone()
+    }
+
+    /// Show the sparkline (nothing if the buffer has no timestamps)
+    pub fn show(
+        &mut self,
+        ui: &mut Ui,
+        buffer: &LogBuffer,
+        selected: Option<&TimeRange>,
+    ) -> TimelineAction {
+        let Some(data) = self.data(buffer) else {
+            self.drag = None;
+            return TimelineAction::None;
+        };
+
+        let (rect, response) = ui.allocate_exact_size(
+            vec2(ui.available_width(), TIMELINE_HEIGHT),
+            Sense::click_and_drag(),
+        );
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+
+        let max = data.counts.iter().copied().max().unwrap_or(0).max(1);
+        let bar_width = rect.width() / data.counts.len() as f32;
+        for (i, &count) in data.counts.iter().enumerate().filter(|(_, c)| **c > 0) {
+            let height = (rect.height() - 2.0) * count as f32 / max as f32;
+            let x = rect.left() + i as f32 * bar_width;
+            let bar = Rect::from_min_max(
+                pos2(x, rect.bottom() - height.max(1.0)),
+                pos2(x + bar_width.max(1.0), rect.bottom()),
+            );
+            painter.rect_filled(bar, 0.0, BAR_COLOR);
+        }
+
+        let x_of = |time| rect.left() + data.fraction_of(time) * rect.width();
+        let span_rect =
+            |x0: f32, x1: f32| Rect::from_x_y_ranges(x0.min(x1)..=x0.max(x1), rect.y_range());
+        if let Some(range) = selected {
+            painter.rect_filled(
+                span_rect(x_of(range.start), x_of(range.end)),
+                0.0,
+                RANGE_FILL,
+            );
+        }
+
+        let mut action = TimelineAction::None;
+        let pointer_x = response.interact_pointer_pos().map(|p| p.x);
+        if response.drag_started() {
+            self.drag = pointer_x.map(|x| (x, x));
+        }
+        if let (Some((_, current)), Some(x)) = (self.drag.as_mut(), pointer_x) {
+            *current = x;
+        }
+
+        if let Some((start_x, end_x)) = self.drag {
+            let range = data.span_to_range(start_x, end_x, rect.left(), rect.width());
+            let span = span_rect(start_x, end_x);
+            painter.rect_filled(span, 0.0, DRAG_FILL);
+            painter.text(
+                pos2(span.center().x, rect.top() + 2.0),
+                Align2::CENTER_TOP,
+                format!(
+                    "{} – {} · {} {}",
+                    range.start.format("%H:%M:%S"),
+                    range.end.format("%H:%M:%S"),
+                    self.index.count_in_range(&range),
+                    t::timeline_lines()
+                ),
+                FontId::proportional(11.0),
+                ui.visuals().strong_text_color(),
+            );
+
+            if response.drag_stopped() {
+                self.drag = None;
+                if (end_x - start_x).abs() >= MIN_DRAG_WIDTH {
+                    action = TimelineAction::SelectRange(range);
+                }
+            }
+        } else if response.clicked() {
+            let outside = selected
+                .zip(pointer_x)
+                .is_some_and(|(range, x)| !(x_of(range.start)..=x_of(range.end)).contains(&x));
+            if outside {
+                action = TimelineAction::ClearRange;
+            }
+        }
+
+        response.on_hover_text(t::timeline_hint());
+        action
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TrimStrategy;
+    use crate::log_buffer::LogBufferConfig;
+    use crate::log_entry::LogEntry;
+    use crate::search::LogFilter;
+
+    /// Index synced line by line must match one built from scratch
+    fn assert_index_matches_rebuild(buffer: &LogBuffer, index: &TimeIndex) {
+        let mut fresh = TimeIndex::default();
+        fresh.rebuild(buffer);
+        assert_eq!(index.by_line, fresh.by_line);
+        assert_eq!(index.sorted, fresh.sorted);
+    }
+
+    #[test]
+    fn test_index_follows_appends_and_trims() {
+        for strategy in [TrimStrategy::DropOldest, TrimStrategy::KeepHeadAndTail] {
+            let mut buffer = LogBuffer::with_config(LogBufferConfig {
+                max_lines: 6,
+                trim_strategy: strategy,
+                head_lines: 2,
+                ..Default::default()
+            });
+            let mut index = TimeIndex::default();
+            for n in 1..=20 {
+                // Every third line has no timestamp; times run out of order
+                let line = if n % 3 == 0 {
+                    format!("    at frame {}", n)
+                } else {
+                    format!("2024-01-15 10:{:02}:00 INFO event {}", (n * 7) % 60, n)
+                };
+                buffer.push(LogEntry::new(n, line, 0));
+                assert!(index.sync(&buffer));
+                assert_index_matches_rebuild(&buffer, &index);
+            }
+            assert!(!index.sync(&buffer));
+        }
+    }
+
+    #[test]
+    fn test_drag_span_maps_to_time_range_and_filters() {
+        let mut buffer = LogBuffer::new();
+        for (i, minute) in [0, 10, 20, 30, 40].iter().enumerate() {
+            let line = format!("2024-01-15 10:{:02}:00 INFO event {}", minute, i);
+            buffer.push(LogEntry::new(i + 1, line, 0));
+        }
+        buffer.push(LogEntry::new(
+            6,
+            "    continuation of event 4".to_string(),
+            0,
+        ));
+
+        let mut timeline = Timeline::default();
+        timeline.index.sync(&buffer);
+        let data = TimelineData::from_sorted(&timeline.index.sorted, 4).unwrap();
+        assert_eq!(data.counts, vec![1, 1, 1, 2]);
+
+        // Dragging right to left across the middle of a 400px wide timeline
+        // drawn at x=100 selects 10:10 to 10:30
+        let range = data.span_to_range(400.0, 200.0, 100.0, 400.0);
+        assert_eq!(range.start.format("%H:%M").to_string(), "10:10");
+        assert_eq!(range.end.format("%H:%M").to_string(), "10:30");
+        assert_eq!(timeline.index.count_in_range(&range), 3);
+
+        let mut filter = LogFilter::new();
+        filter.filter.time_range = Some(range);
+        assert_eq!(filter.apply(&buffer), &[1, 2, 3]);
+
+        // The untimestamped line follows the line before it, or is hidden
+        filter.filter.time_range = Some(data.span_to_range(450.0, 500.0, 100.0, 400.0));
+        filter.mark_dirty();
+        assert_eq!(filter.apply(&buffer), &[4, 5]);
+        filter.filter.keep_untimestamped = false;
+        filter.mark_dirty();
+        assert_eq!(filter.apply(&buffer), &[4]);
+    }
+}
@@ -142,6 +142,30 @@ impl Toolbar {
                 action = ToolbarAction::ToggleAutoScroll;
             }
 
+            // Bounded capture: follow N lines, then stop
+            let capture_color = if state.capture_active {
+                Some(Color32::from_rgb(229, 57, 53))
+            } else {
+                None
+            };
+            if styled_button(
+                ui,
+                "⏺",
+                t::capture(),
+                t::capture_tooltip(),
+                state.capture_active,
+                capture_color,
+            ) {
+                action = ToolbarAction::ToggleCapture;
+            }
+            ui.add_enabled(
+                !state.capture_active,
+                egui::DragValue::new(&mut state.capture_lines)
+                    .range(1..=1_000_000)
+                    .suffix(t::capture_lines_suffix()),
+            )
+            .on_hover_text(t::capture_lines_tooltip());
+
             // Clear button
             if styled_button(ui, "🗑", t::clear(), t::clear_display_tooltip(), false, None) {
                 action = ToolbarAction::Clear;
@@ -185,6 +209,23 @@ impl Toolbar {
                 action = ToolbarAction::ToggleReverseOrder;
             }
 
+            // Word wrap toggle
+            let wrap_color = if state.wrap_lines {
+                Some(Color32::from_rgb(38, 198, 218))
+            } else {
+                None
+            };
+            if styled_button(
+                ui,
+                "↩",
+                t::wrap_lines(),
+                t::wrap_lines_tooltip(),
+                state.wrap_lines,
+                wrap_color,
+            ) {
+                action = ToolbarAction::ToggleWrapLines;
+            }
+
             // Separator
             ui.add_space(4.0);
             let sep_rect = ui
@@ -217,6 +258,18 @@ impl Toolbar {
                 action = ToolbarAction::GoToLine;
             }
 
+            // Go to time button
+            if styled_button(
+                ui,
+                "🕐",
+                t::go_to_time(),
+                t::go_to_time_tooltip(),
+                false,
+                None,
+            ) {
+                action = ToolbarAction::GoToTime;
+            }
+
             // Separator
             ui.add_space(4.0);
             let sep_rect = ui
@@ -254,6 +307,23 @@ impl Toolbar {
                 action = ToolbarAction::ToggleSplitView;
             }
 
+            // Notes panel toggle
+            let notes_color = if state.notes_visible {
+                Some(Color32::from_rgb(255, 193, 7))
+            } else {
+                None
+            };
+            if styled_button(
+                ui,
+                "📝",
+                t::notes(),
+                t::toggle_notes_tooltip(),
+                state.notes_visible,
+                notes_color,
+            ) {
+                action = ToolbarAction::ToggleNotes;
+            }
+
             // Separator
             ui.add_space(4.0);
             let sep_rect = ui
@@ -350,6 +420,71 @@ impl Toolbar {
                     filter_changed = true;
                 }
 
+                // Errors with context toggle
+                let context_color = if state.errors_with_context {
+                    LogLevel::Error.color()
+                } else {
+                    inactive_color
+                };
+                let context_btn = ui.add(
+                    egui::Button::new(
+                        RichText::new(format!(
+                            "{} ±{}",
+                            t::errors_with_context(),
+                            state.error_context_lines
+                        ))
+                        .size(11.0)
+                        .color(context_color),
+                    )
+                    .fill(Color32::TRANSPARENT)
+                    .stroke(if state.errors_with_context {
+                        Stroke::new(1.0, context_color)
+                    } else {
+                        Stroke::NONE
+                    })
+                    .corner_radius(CornerRadius::same(10))
+                    .min_size(Vec2::new(0.0, 20.0)),
+                );
+                if context_btn
+                    .on_hover_text(t::errors_with_context_tooltip())
+                    .clicked()
+                {
+                    action = ToolbarAction::ToggleErrorsWithContext;
+                }
+
+                // Pin the current filter across tab switches
+                let pin_color = if state.filter_pinned {
+                    Color32::from_rgb(66, 165, 245)
+                } else {
+                    inactive_color
+                };
+                let pin_btn = ui.add(
+                    egui::Button::new(
+                        RichText::new(format!("📌 {}", t::pin_filter()))
+                            .size(11.0)
+                            .color(pin_color),
+                    )
+                    .fill(Color32::TRANSPARENT)
+                    .stroke(if state.filter_pinned {
+                        Stroke::new(1.0, pin_color)
+                    } else {
+                        Stroke::NONE
+                    })
+                    .corner_radius(CornerRadius::same(10))
+                    .min_size(Vec2::new(0.0, 20.0)),
+                );
+                if pin_btn.on_hover_text(t::pin_filter_tooltip()).clicked() {
+                    action = ToolbarAction::TogglePinnedFilter;
+                }
+
+                if pill_btn(
+                    ui,
+                    &format!("💾 {}", t::export_filtered()),
+                    t::export_filtered_tooltip(),
+                ) {
+                    action = ToolbarAction::ExportFiltered;
+                }
+
                 // Separator
                 ui.add_space(4.0);
                 let sep_rect = ui
@@ -426,7 +561,16 @@ pub struct ToolbarState {
     pub search_visible: bool,
     pub dark_theme: bool,
     pub reverse_order: bool,
+    pub wrap_lines: bool,
     pub split_view_active: bool,
+    pub errors_with_context: bool,
+    pub error_context_lines: usize,
+    pub filter_pinned: bool,
+    pub notes_visible: bool,
+    /// Line budget for a bounded capture
+    pub capture_lines: usize,
+    /// Whether the active tab has a bounded capture running
+    pub capture_active: bool,
 }
 
 impl Default for ToolbarState {
@@ -436,7 +580,14 @@ impl Default for ToolbarState {
             search_visible: false,
             dark_theme: true,
             reverse_order: false,
+            wrap_lines: false,
             split_view_active: false,
+            errors_with_context: false,
+            error_context_lines: 3,
+            filter_pinned: false,
+            notes_visible: false,
+            capture_lines: 1000,
+            capture_active: false,
         }
     }
 }
@@ -451,10 +602,17 @@ pub enum ToolbarAction {
     Clear,
     ToggleSearch,
     GoToLine,
+    GoToTime,
     GoToTop,
     GoToBottom,
     ToggleTheme,
     OpenSettings,
     ToggleReverseOrder,
+    ToggleWrapLines,
     ToggleSplitView,
+    ToggleErrorsWithContext,
+    TogglePinnedFilter,
+    ExportFiltered,
+    ToggleNotes,
+    ToggleCapture,
 }
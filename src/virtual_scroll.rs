@@ -1,7 +1,10 @@
 //! Virtual scrolling implementation for efficient rendering of large log files
 
 use crate::log_buffer::{FilteredLogView, LogBuffer};
-use crate::log_entry::LogEntry;
+use crate::log_entry::{LogEntry, LogLevel};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::VecDeque;
+use std::hash::{Hash, Hasher};
 
 /// Configuration for virtual scrolling
 #[derive(Debug, Clone)]
@@ -40,6 +43,11 @@ pub struct VirtualScrollState {
     pub visible_row_count: usize,
     /// Whether auto-scroll is enabled
     pub auto_scroll: bool,
+    /// Following was turned off explicitly; scrolling back to the edge
+    /// where new lines appear doesn't resume it
+    pub follow_paused: bool,
+    /// Whether the view was at the edge where new lines appear last frame
+    pub at_follow_edge: bool,
     /// Whether user is currently dragging scrollbar
     #[allow(dead_code)]
     pub is_dragging: bool,
@@ -56,6 +64,8 @@ impl Default for VirtualScrollState {
             first_visible_row: 0,
             visible_row_count: 0,
             auto_scroll: true,
+            follow_paused: false,
+            at_follow_edge: true,
             is_dragging: false,
             reverse_order: false,
         }
@@ -160,6 +170,31 @@ impl VirtualScrollState {
         }
     }
 
+    /// Follow new lines based on where the user scrolled to: leaving the edge
+    /// where they appear (bottom, or top in reverse order) stops following and
+    /// coming back to it resumes, unless following was paused explicitly
+    pub fn track_follow(
+        &mut self,
+        offset: f32,
+        max_offset: f32,
+        threshold: f32,
+        user_scrolled: bool,
+    ) {
+        let at_edge = if self.reverse_order {
+            offset <= threshold
+        } else {
+            offset >= max_offset - threshold
+        };
+        if user_scrolled {
+            if !at_edge {
+                self.auto_scroll = false;
+            } else if !self.at_follow_edge && !self.follow_paused {
+                self.auto_scroll = true;
+            }
+        }
+        self.at_follow_edge = at_edge;
+    }
+
     /// Check if scrolled to bottom
     #[allow(dead_code)]
     pub fn is_at_bottom(&self) -> bool {
@@ -303,6 +338,319 @@ impl Default for VirtualScroll {
     }
 }
 
+/// Visual line layout of rows when long lines are wrapped
+///
+/// Kept in logical order so that tailing only appends to it; screen rows
+/// are mapped through `reverse` when reading it back.
+#[derive(Debug, Clone, Default)]
+pub struct WrappedRows {
+    /// Visual lines above each logical row, ending with the total, counted
+    /// from the first entry so trimmed rows can be dropped from the front
+    starts: VecDeque<usize>,
+    /// Source line number of each measured row
+    line_numbers: VecDeque<usize>,
+    /// Hash of the settings the layout was measured with
+    key: u64,
+}
+
+impl WrappedRows {
+    /// Bring the layout up to date with `total_rows` rows
+    ///
+    /// `row_lines` returns the source line number and visual line count of a
+    /// logical row. Measured rows are kept while `key` is unchanged and rows
+    /// were only trimmed from the front or appended; anything else triggers a
+    /// full re-measure.
+    pub fn update<K: Hash>(
+        &mut self,
+        key: K,
+        total_rows: usize,
+        mut row_lines: impl FnMut(usize) -> (usize, usize),
+    ) {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        let key = hasher.finish();
+
+        let trimmed = (key == self.key)
+            .then(|| trimmed_rows(&self.line_numbers, total_rows, |row| row_lines(row).0))
+            .flatten();
+        match trimmed {
+            Some(trimmed) => {
+                self.starts.drain(..trimmed);
+                self.line_numbers.drain(..trimmed);
+            }
+            None => {
+                self.starts = VecDeque::from([0]);
+                self.line_numbers.clear();
+                self.key = key;
+            }
+        }
+        if self.rows() == total_rows {
+            return;
+        }
+
+        let mut total = *self.starts.back().unwrap_or(&0);
+        self.starts.reserve(total_rows - self.rows());
+        self.line_numbers.reserve(total_rows - self.rows());
+        for row in self.rows()..total_rows {
+            let (line_number, lines) = row_lines(row);
+            total += lines.max(1);
+            self.starts.push_back(total);
+            self.line_numbers.push_back(line_number);
+        }
+    }
+
+    /// Re-measure logical rows whose visual line count may have changed,
+    /// such as rows whose grok fields were parsed after they were measured
+    pub fn remeasure(
+        &mut self,
+        rows: std::ops::Range<usize>,
+        mut row_lines: impl FnMut(usize) -> (usize, usize),
+    ) {
+        let rows = rows.start.min(self.rows())..rows.end.min(self.rows());
+        if rows.is_empty() {
+            return;
+        }
+        let old_end = self.starts[rows.end];
+        let mut total = self.starts[rows.start];
+        for row in rows.clone() {
+            total += row_lines(row).1.max(1);
+            self.starts[row + 1] = total;
+        }
+        if total != old_end {
+            for start in self.starts.range_mut(rows.end + 1..) {
+                *start = *start - old_end + total;
+            }
+        }
+    }
+
+    /// Number of measured rows
+    pub fn rows(&self) -> usize {
+        self.starts.len().saturating_sub(1)
+    }
+
+    /// Visual lines taken by all rows
+    pub fn total_lines(&self) -> usize {
+        self.start(self.rows())
+    }
+
+    /// Visual lines above a logical row
+    fn start(&self, logical: usize) -> usize {
+        match (self.starts.get(logical), self.starts.front()) {
+            (Some(start), Some(first)) => start - first,
+            _ => 0,
+        }
+    }
+
+    /// First visual line of a screen row
+    pub fn first_line(&self, row: usize, reverse: bool) -> usize {
+        if row >= self.rows() {
+            return self.total_lines();
+        }
+        if reverse {
+            let logical = self.rows() - 1 - row;
+            self.total_lines() - self.start(logical + 1)
+        } else {
+            self.start(row)
+        }
+    }
+
+    /// Visual lines taken by a screen row
+    pub fn line_count(&self, row: usize, reverse: bool) -> usize {
+        if row >= self.rows() {
+            return 1;
+        }
+        let logical = if reverse { self.rows() - 1 - row } else { row };
+        self.starts[logical + 1] - self.starts[logical]
+    }
+
+    /// Screen row containing a visual line (`rows()` past the end)
+    pub fn row_at_line(&self, line: usize, reverse: bool) -> usize {
+        let total = self.total_lines();
+        if line >= total {
+            return self.rows();
+        }
+        let line = if reverse { total - 1 - line } else { line };
+        let line = line + self.starts[0];
+        let logical = self.starts.partition_point(|&start| start <= line) - 1;
+        if reverse {
+            self.rows() - 1 - logical
+        } else {
+            logical
+        }
+    }
+}
+
+/// Rows dropped from the front of rows last seen with `line_numbers`, or
+/// `None` when the rest of them no longer line up
+fn trimmed_rows(
+    line_numbers: &VecDeque<usize>,
+    total_rows: usize,
+    mut line_of: impl FnMut(usize) -> usize,
+) -> Option<usize> {
+    if total_rows == 0 {
+        return None;
+    }
+    let first = line_of(0);
+    let trimmed = line_numbers.iter().position(|&line| line == first)?;
+    let kept = line_numbers.len() - trimmed;
+    (kept <= total_rows && Some(&line_of(kept - 1)) == line_numbers.back()).then_some(trimmed)
+}
+
+/// What an overview tick marks: the most severe problem and whether a
+/// bookmark shares the slot
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OverviewMark {
+    /// Error or fatal
+    pub level: Option<LogLevel>,
+    pub bookmark: bool,
+}
+
+impl OverviewMark {
+    /// Mark for an entry, if it deserves one
+    pub fn of(entry: &LogEntry) -> Option<Self> {
+        let level = entry.level.filter(|&level| level >= LogLevel::Error);
+        (level.is_some() || entry.bookmarked).then_some(OverviewMark {
+            level,
+            bookmark: entry.bookmarked,
+        })
+    }
+
+    fn merge(&mut self, other: OverviewMark) {
+        self.level = self.level.max(other.level);
+        self.bookmark |= other.bookmark;
+    }
+}
+
+/// Overview marks of a view's rows, extended as lines are appended and
+/// trimmed and rebuilt when the buffer's revision moves
+#[derive(Debug, Clone, Default)]
+pub struct OverviewMarks {
+    /// Source line number of each scanned row
+    line_numbers: VecDeque<usize>,
+    /// Marked rows, counted from the first row scanned since the last rebuild
+    marked: VecDeque<(usize, OverviewMark)>,
+    /// Rows trimmed from the front since the last rebuild
+    trimmed: usize,
+    revision: u64,
+}
+
+impl OverviewMarks {
+    /// Bring the marks up to date with `total_rows` rows; `row_mark` returns
+    /// the source line number and mark of a logical row. Returns whether
+    /// anything changed.
+    pub fn update(
+        &mut self,
+        revision: u64,
+        total_rows: usize,
+        mut row_mark: impl FnMut(usize) -> (usize, Option<OverviewMark>),
+    ) -> bool {
+        let trimmed = (revision == self.revision)
+            .then(|| trimmed_rows(&self.line_numbers, total_rows, |row| row_mark(row).0))
+            .flatten();
+        match trimmed {
+            Some(0) if self.line_numbers.len() == total_rows => return false,
+            Some(trimmed) => {
+                self.line_numbers.drain(..trimmed);
+                self.trimmed += trimmed;
+                let dropped = self.marked.partition_point(|&(row, _)| row < self.trimmed);
+                self.marked.drain(..dropped);
+            }
+            None => {
+                *self = OverviewMarks {
+                    revision,
+                    ..OverviewMarks::default()
+                };
+            }
+        }
+
+        for row in self.line_numbers.len()..total_rows {
+            let (line_number, mark) = row_mark(row);
+            self.line_numbers.push_back(line_number);
+            if let Some(mark) = mark {
+                self.marked.push_back((self.trimmed + row, mark));
+            }
+        }
+        true
+    }
+
+    /// Downsample the rows onto at most `buckets` slots in logical order,
+    /// merging the marks that share a slot
+    pub fn slots(&self, buckets: usize) -> Vec<Option<OverviewMark>> {
+        let total_rows = self.line_numbers.len();
+        let buckets = buckets.min(total_rows);
+        let mut slots = vec![None; buckets];
+        if buckets == 0 {
+            return slots;
+        }
+        for &(row, mark) in &self.marked {
+            let slot = (row - self.trimmed) * buckets / total_rows;
+            slots[slot].get_or_insert(mark).merge(mark);
+        }
+        slots
+    }
+}
+
+/// Vertical placement of screen rows, uniform or wrapped
+#[derive(Debug, Clone, Copy)]
+pub struct RowMetrics<'a> {
+    /// Height of one visual line
+    pub row_height: f32,
+    /// Wrapped layout, `None` when every row is a single line
+    pub wrapped: Option<&'a WrappedRows>,
+    /// Whether rows are shown newest first
+    pub reverse: bool,
+    /// Screen row with a line of its own above it, marking lines omitted
+    /// between head and tail
+    pub gap_row: Option<usize>,
+}
+
+impl RowMetrics<'_> {
+    /// First visual line of a row, not counting the gap line
+    fn first_line(&self, row: usize) -> usize {
+        self.wrapped
+            .map_or(row, |wrapped| wrapped.first_line(row, self.reverse))
+    }
+
+    /// Offset of a row's top edge from the top of the rows
+    pub fn top(&self, row: usize) -> f32 {
+        let gap = usize::from(self.gap_row.is_some_and(|gap| row >= gap));
+        (self.first_line(row) + gap) as f32 * self.row_height
+    }
+
+    /// Offset of the gap line's top edge from the top of the rows
+    pub fn gap_top(&self) -> Option<f32> {
+        self.gap_row
+            .map(|gap| self.first_line(gap) as f32 * self.row_height)
+    }
+
+    /// Height of a row
+    pub fn height(&self, row: usize) -> f32 {
+        let lines = self
+            .wrapped
+            .map_or(1, |wrapped| wrapped.line_count(row, self.reverse));
+        lines as f32 * self.row_height
+    }
+
+    /// Row at an offset from the top of the rows
+    pub fn row_at(&self, y: f32) -> usize {
+        let mut line = (y / self.row_height).floor().max(0.0) as usize;
+        // The gap line belongs to the row below it
+        if self.gap_row.is_some_and(|gap| line > self.first_line(gap)) {
+            line -= 1;
+        }
+        self.wrapped
+            .map_or(line, |wrapped| wrapped.row_at_line(line, self.reverse))
+    }
+
+    /// Height of all rows
+    pub fn content_height(&self, total_rows: usize) -> f32 {
+        let lines = self
+            .wrapped
+            .map_or(total_rows, |wrapped| wrapped.total_lines());
+        (lines + usize::from(self.gap_row.is_some())) as f32 * self.row_height
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -317,6 +665,187 @@ mod tests {
         assert!(range.end <= 1000);
     }
 
+    #[test]
+    fn test_visible_range_at_densities() {
+        use crate::config::{DisplayConfig, RowDensity};
+
+        let mut last_visible = 0;
+        for density in RowDensity::all() {
+            let display = DisplayConfig {
+                row_density: *density,
+                ..Default::default()
+            };
+            let row_height = display.row_height();
+            let mut scroll = VirtualScroll::with_config(VirtualScrollConfig {
+                row_height,
+                overscan: 0,
+                ..Default::default()
+            });
+            scroll.state.auto_scroll = false;
+            scroll.update(10_000, 600.0);
+            scroll.scroll_to_row(1000);
+
+            let range = scroll.state.visible_range(10_000);
+            let expected = (600.0 / row_height).ceil() as usize;
+            assert_eq!(range.start, 1000);
+            assert_eq!(range.len(), expected);
+            // Denser rows show more lines in the same viewport
+            assert!(range.len() > last_visible);
+            last_visible = range.len();
+
+            // Click-to-line maps back to the same row
+            assert_eq!(scroll.row_at_y(row_height * 0.5), 1000);
+            assert_eq!(scroll.row_at_y(row_height * 1.5), 1001);
+        }
+    }
+
+    #[test]
+    fn test_wrapped_rows_map_positions_both_ways() {
+        // (line number, visual lines) per logical row
+        let mut rows = vec![(1, 1), (2, 3), (3, 1)];
+        let mut wrapped = WrappedRows::default();
+        wrapped.update(80, rows.len(), |row| rows[row]);
+        assert_eq!(wrapped.total_lines(), 5);
+
+        let metrics = RowMetrics {
+            row_height: 10.0,
+            wrapped: Some(&wrapped),
+            reverse: false,
+            gap_row: None,
+        };
+        assert_eq!(metrics.top(2), 40.0);
+        assert_eq!(metrics.height(1), 30.0);
+        assert_eq!(metrics.row_at(25.0), 1);
+        assert_eq!(metrics.row_at(45.0), 2);
+        assert_eq!(metrics.row_at(55.0), 3);
+
+        // Newest first: the last logical row is on top
+        let reversed = RowMetrics {
+            reverse: true,
+            ..metrics
+        };
+        assert_eq!(reversed.top(0), 0.0);
+        assert_eq!(reversed.top(1), 10.0);
+        assert_eq!(reversed.top(2), 40.0);
+        assert_eq!(reversed.row_at(35.0), 1);
+
+        // A gap line above the last row pushes it down
+        let gapped = RowMetrics {
+            gap_row: Some(2),
+            ..metrics
+        };
+        assert_eq!(gapped.gap_top(), Some(40.0));
+        assert_eq!(gapped.top(1), 10.0);
+        assert_eq!(gapped.top(2), 50.0);
+        assert_eq!(gapped.row_at(45.0), 2);
+        assert_eq!(gapped.row_at(35.0), 1);
+        assert_eq!(gapped.content_height(3), 60.0);
+
+        // Appended rows extend the layout, a new width re-measures it
+        rows.push((4, 2));
+        wrapped.update(80, rows.len(), |row| rows[row]);
+        assert_eq!(wrapped.total_lines(), 7);
+        wrapped.update(40, rows.len(), |row| (rows[row].0, 1));
+        assert_eq!(wrapped.total_lines(), 4);
+    }
+
+    #[test]
+    fn test_wrapped_rows_follow_trims_and_remeasures() {
+        let mut rows = vec![(1, 1), (2, 3), (3, 1), (4, 2)];
+        let mut wrapped = WrappedRows::default();
+        wrapped.update(80, rows.len(), |row| rows[row]);
+        assert_eq!(wrapped.total_lines(), 7);
+
+        // Trimmed rows drop out without measuring the kept ones again
+        rows.drain(..2);
+        rows.push((5, 1));
+        rows[0].1 = 9;
+        wrapped.update(80, rows.len(), |row| rows[row]);
+        assert_eq!(wrapped.total_lines(), 4);
+        assert_eq!(wrapped.first_line(2, false), 3);
+        assert_eq!(wrapped.row_at_line(1, false), 1);
+
+        // A row that grew is picked up and shifts the rows after it
+        wrapped.remeasure(0..1, |row| rows[row]);
+        assert_eq!(wrapped.total_lines(), 12);
+        assert_eq!(wrapped.first_line(1, false), 9);
+        assert_eq!(wrapped.first_line(0, true), 0);
+        assert_eq!(wrapped.line_count(2, true), 9);
+    }
+
+    #[test]
+    fn test_overview_marks_merge_per_bucket() {
+        let bookmark = OverviewMark {
+            level: None,
+            bookmark: true,
+        };
+        let level = |level| OverviewMark {
+            level: Some(level),
+            bookmark: false,
+        };
+        let mut rows = vec![None; 1000];
+        rows[10] = Some(bookmark);
+        rows[11] = Some(level(LogLevel::Error));
+        rows[505] = Some(bookmark);
+        rows[999] = Some(level(LogLevel::Fatal));
+        rows[998] = Some(level(LogLevel::Error));
+
+        let mut marks = OverviewMarks::default();
+        assert!(marks.update(0, rows.len(), |row| (row + 1, rows[row])));
+        let slots = marks.slots(100);
+        assert_eq!(slots.len(), 100);
+        assert_eq!(
+            slots[1],
+            Some(OverviewMark {
+                level: Some(LogLevel::Error),
+                bookmark: true,
+            })
+        );
+        assert_eq!(slots[50], Some(bookmark));
+        assert_eq!(slots[99], Some(level(LogLevel::Fatal)));
+        assert_eq!(slots.iter().flatten().count(), 3);
+
+        // Unchanged rows are not scanned again
+        assert!(!marks.update(0, rows.len(), |row| (row + 1, None)));
+
+        // Fewer rows than pixels: one slot per row
+        let mut marks = OverviewMarks::default();
+        marks.update(0, 3, |row| (row + 1, rows[row + 997]));
+        assert_eq!(
+            marks.slots(100),
+            vec![
+                None,
+                Some(level(LogLevel::Error)),
+                Some(level(LogLevel::Fatal))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_overview_marks_follow_appends_and_trims() {
+        let error = OverviewMark {
+            level: Some(LogLevel::Error),
+            bookmark: false,
+        };
+        // (line number, mark) per logical row
+        let mut rows: Vec<_> = (1..=4).map(|line| (line, None)).collect();
+        rows[1].1 = Some(error);
+        let mut marks = OverviewMarks::default();
+        marks.update(0, rows.len(), |row| rows[row]);
+        assert_eq!(marks.slots(4), vec![None, Some(error), None, None]);
+
+        // Tailing at capacity: only the new rows are scanned
+        rows.drain(..2);
+        rows.extend([(5, Some(error)), (6, None)]);
+        rows[0].1 = Some(error);
+        marks.update(0, rows.len(), |row| rows[row]);
+        assert_eq!(marks.slots(4), vec![None, None, Some(error), None]);
+
+        // A new revision rescans everything
+        marks.update(1, rows.len(), |row| rows[row]);
+        assert_eq!(marks.slots(4), vec![Some(error), None, Some(error), None]);
+    }
+
     #[test]
     fn test_scroll_to_row() {
         let mut scroll = VirtualScroll::new();
@@ -326,4 +855,37 @@ mod tests {
         let range = scroll.state.visible_range(1000);
         assert!(range.contains(&500) || range.start <= 500);
     }
+
+    #[test]
+    fn test_follow_disengages_and_reengages_with_scrolling() {
+        let mut state = VirtualScrollState::new();
+        assert!(state.auto_scroll);
+
+        // Scrolling up away from the bottom stops following
+        state.track_follow(500.0, 1000.0, 10.0, true);
+        assert!(!state.auto_scroll);
+        // New lines growing the content don't change that
+        state.track_follow(500.0, 1200.0, 10.0, false);
+        assert!(!state.auto_scroll);
+        // Scrolling back to the bottom resumes it
+        state.track_follow(1195.0, 1200.0, 10.0, true);
+        assert!(state.auto_scroll);
+
+        // An explicit pause isn't overridden by returning to the bottom
+        state.auto_scroll = false;
+        state.follow_paused = true;
+        state.track_follow(800.0, 1200.0, 10.0, true);
+        state.track_follow(1200.0, 1200.0, 10.0, true);
+        assert!(!state.auto_scroll);
+
+        // In reverse order new lines appear at the top
+        let mut state = VirtualScrollState {
+            reverse_order: true,
+            ..Default::default()
+        };
+        state.track_follow(300.0, 1000.0, 10.0, true);
+        assert!(!state.auto_scroll);
+        state.track_follow(0.0, 1000.0, 10.0, true);
+        assert!(state.auto_scroll);
+    }
 }